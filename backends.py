@@ -618,6 +618,34 @@ class ZhipuBackend(OpenAIBackend):
         super().__init__(system_prompt, api_key, base_url, model, max_history)
 
 
+class AnthropicBackend(OpenAIBackend):
+    """Anthropic Claude API backend (via its OpenAI-compatible endpoint)"""
+
+    def __init__(
+        self,
+        system_prompt: str,
+        api_key: str,
+        model: str = "claude-3-5-haiku-latest",
+        base_url: str = "https://api.anthropic.com/v1",
+        max_history: int = 10,
+    ):
+        super().__init__(system_prompt, api_key, base_url, model, max_history)
+
+
+class GeminiBackend(OpenAIBackend):
+    """Google Gemini API backend (via its OpenAI-compatible endpoint)"""
+
+    def __init__(
+        self,
+        system_prompt: str,
+        api_key: str,
+        model: str = "gemini-2.0-flash",
+        base_url: str = "https://generativelanguage.googleapis.com/v1beta/openai",
+        max_history: int = 10,
+    ):
+        super().__init__(system_prompt, api_key, base_url, model, max_history)
+
+
 def create_backend(backend_type: str, system_prompt: str, **kwargs) -> LLMBackend:
     """Factory function to create LLM backend"""
     backends = {
@@ -627,6 +655,8 @@ def create_backend(backend_type: str, system_prompt: str, **kwargs) -> LLMBacken
         "openrouter": OpenRouterBackend,
         "custom": CustomBackend,
         "zhipu": ZhipuBackend,
+        "anthropic": AnthropicBackend,
+        "gemini": GeminiBackend,
     }
 
     if backend_type not in backends:
@@ -15,6 +15,8 @@ logger = get_logger(__name__)
 # Default constants (copied from speekium to avoid circular import)
 DEFAULT_SAMPLE_RATE = 16000
 DEFAULT_ASR_MODEL = "iic/SenseVoiceSmall"
+# Languages SenseVoice detects and can be told to expect ("auto" = detect)
+ASR_LANGUAGES = ("zh", "en", "ja", "ko", "yue")
 DEFAULT_VAD_THRESHOLD = 0.5
 DEFAULT_VAD_CONSECUTIVE_THRESHOLD = 3
 DEFAULT_VAD_SILENCE_DURATION = 0.8
@@ -22,6 +24,29 @@ DEFAULT_VAD_PRE_BUFFER = 0.3
 DEFAULT_MIN_SPEECH_DURATION = 0.4
 DEFAULT_MAX_RECORDING_DURATION = 30
 DEFAULT_TTS_RATE = "+0%"
+DEFAULT_TTS_PITCH = "+0Hz"
+DEFAULT_TTS_VOLUME = "+0%"
+
+
+def _is_local_url(url: str) -> bool:
+    """Whether a provider base URL points at this machine or the local network"""
+    import ipaddress
+    from urllib.parse import urlparse
+
+    host = (urlparse(url.strip()).hostname or "").lower()
+    if host == "localhost" or host.endswith((".localhost", ".local")):
+        return True
+    try:
+        ip = ipaddress.ip_address(host)
+    except ValueError:
+        return False
+    return ip.is_loopback or (ip.version == 4 and (ip.is_private or ip.is_link_local))
+
+
+def _is_offline() -> bool:
+    from config_manager import ConfigManager
+
+    return ConfigManager.is_offline()
 
 
 class ConfigLoader:
@@ -47,14 +72,32 @@ class ConfigLoader:
             ),
         }
 
+    def _load_asr_config(self, config: dict) -> dict:
+        """Load ASR language settings from config dict (unknown languages are ignored)."""
+        asr_language = config.get("asr_language", "auto")
+        if asr_language not in ASR_LANGUAGES:
+            asr_language = "auto"
+        return {
+            "asr_language": asr_language,
+            "asr_languages": [
+                lang for lang in config.get("asr_languages", []) if lang in ASR_LANGUAGES
+            ],
+        }
+
     def _load_tts_config(self, config: dict) -> dict:
-        """Load TTS backend and rate from config dict."""
+        """Load TTS backend, voice and speech parameters from config dict."""
         tts_backend = config.get("tts_backend", "edge")
         tts_rate = config.get("tts_rate", DEFAULT_TTS_RATE)
+        # Edge TTS is an online service
+        if tts_backend == "edge" and _is_offline():
+            tts_backend = "piper"
 
         return {
             "tts_backend": tts_backend,
+            "tts_voice": config.get("tts_voice", "auto"),
             "tts_rate": tts_rate,
+            "tts_pitch": config.get("tts_pitch", DEFAULT_TTS_PITCH),
+            "tts_volume": config.get("tts_volume", DEFAULT_TTS_VOLUME),
         }
 
     def _load_llm_config(self, config: dict) -> dict:
@@ -69,6 +112,17 @@ class ConfigLoader:
                 current_provider_config = provider
                 break
 
+        # While offline, fall back to a local provider (Ollama) if the selected one is remote
+        if _is_offline() and not _is_local_url(current_provider_config.get("base_url", "")):
+            for provider in llm_providers:
+                if _is_local_url(provider.get("base_url", "")):
+                    logger.info(
+                        "offline_llm_fallback", from_provider=llm_provider, to_provider=provider.get("name")
+                    )
+                    llm_provider = provider.get("name")
+                    current_provider_config = provider
+                    break
+
         return {
             "llm_provider": llm_provider,
             "llm_providers": llm_providers,
@@ -84,6 +138,7 @@ class ConfigLoader:
             config = ConfigManager.load()
 
             vad_config = self._load_vad_config(config)
+            asr_config = self._load_asr_config(config)
             tts_config = self._load_tts_config(config)
             llm_config = self._load_llm_config(config)
 
@@ -98,6 +153,7 @@ class ConfigLoader:
 
             return {
                 "vad": vad_config,
+                "asr": asr_config,
                 "tts": tts_config,
                 "llm": llm_config,
             }
@@ -117,9 +173,16 @@ class ConfigLoader:
                 "vad_min_speech_duration": DEFAULT_MIN_SPEECH_DURATION,
                 "vad_max_recording_duration": DEFAULT_MAX_RECORDING_DURATION,
             },
+            "asr": {
+                "asr_language": "auto",
+                "asr_languages": [],
+            },
             "tts": {
                 "tts_backend": "edge",
+                "tts_voice": "auto",
                 "tts_rate": DEFAULT_TTS_RATE,
+                "tts_pitch": DEFAULT_TTS_PITCH,
+                "tts_volume": DEFAULT_TTS_VOLUME,
             },
             "llm": {
                 "llm_provider": "ollama",
@@ -156,7 +219,7 @@ class ConfigLoader:
             from config_manager import ConfigManager
 
             config = ConfigManager.load()
-            self._tts_backend = config.get("tts_backend", "edge")
+            self._tts_backend = self._load_tts_config(config)["tts_backend"]
         except Exception as e:
             logger.warning("tts_config_refresh_failed", error=str(e))
         return self._tts_backend
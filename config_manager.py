@@ -1,8 +1,18 @@
 import json
 import os
 import sys
+from contextlib import contextmanager
 from typing import Any
 
+try:
+    import fcntl
+except ImportError:  # Windows
+    fcntl = None
+try:
+    import msvcrt
+except ImportError:  # macOS / Linux
+    msvcrt = None
+
 APP_NAME = "speekium"
 
 
@@ -46,11 +56,17 @@ def get_config_dir() -> str:
 
 
 CONFIG_PATH = os.path.join(get_config_dir(), "config.json")
+CONFIG_LOCK_PATH = CONFIG_PATH + ".lock"
 print(f"📄 配置文件路径: {CONFIG_PATH}", file=sys.stderr)
 
+# Config schema version (keep in sync with CONFIG_VERSION in src-tauri/src/config.rs)
+CONFIG_VERSION = 3
+
 # NEW: Unified LLM provider configuration
 DEFAULT_CONFIG: dict[str, Any] = {
+    "config_version": CONFIG_VERSION,
     # LLM Provider Configuration
+    "llm_backend": "daemon",  # daemon | native (text chats call the provider directly from Rust)
     "llm_provider": "ollama",  # Currently selected provider
     "llm_providers": [
         {
@@ -83,10 +99,26 @@ DEFAULT_CONFIG: dict[str, Any] = {
             "api_key": "",
             "model": "glm-4-flash",
         },
+        {
+            "name": "anthropic",
+            "base_url": "https://api.anthropic.com/v1",  # OpenAI-compatible endpoint
+            "api_key": "",
+            "model": "claude-3-5-haiku-latest",
+        },
+        {
+            "name": "gemini",
+            "base_url": "https://generativelanguage.googleapis.com/v1beta/openai",  # OpenAI-compatible endpoint
+            "api_key": "",
+            "model": "gemini-2.0-flash",
+        },
     ],
     # TTS Configuration
     "tts_backend": "edge",
+    "tts_voice": "auto",  # Edge TTS voice id (e.g. "en-US-JennyNeural"); auto = by the reply's language
     "tts_rate": "+0%",
+    "tts_pitch": "+0Hz",
+    "tts_volume": "+0%",
+    "tts_cache_max_mb": 100,  # Size limit of the TTS audio cache (0 = disabled)
     # VAD Configuration
     "vad_threshold": 0.5,  # Voice detection threshold (0.0-1.0, lower = more sensitive)
     "vad_consecutive_threshold": 3,  # Consecutive detections to confirm speech start
@@ -94,22 +126,268 @@ DEFAULT_CONFIG: dict[str, Any] = {
     "vad_pre_buffer": 0.3,  # Pre-buffer duration to capture speech start (seconds)
     "vad_min_speech_duration": 0.4,  # Minimum speech duration (seconds)
     "vad_max_recording_duration": 30,  # Maximum recording duration (seconds)
+    # Audio Input Configuration
+    "audio_input_device": None,  # Input device id for PTT recording (None = system default)
+    # PTT Recording Limits (0 = disabled)
+    "silence_timeout_ms": 10000,  # Stop PTT recording after this much silence (milliseconds)
+    "max_recording_secs": 120,  # Maximum PTT recording length (seconds)
+    # Meeting Mode (system audio transcribed into a dedicated session)
+    "meeting_chunk_secs": 30,  # Length of each transcribed chunk (seconds)
+    "meeting_capture_mic": True,  # Mix the microphone into the system audio
+    # Long-form Dictation
+    "dictation_pause_ms": 800,  # Pause that ends a dictation chunk (milliseconds)
+    "dictation_stop_phrases": ["stop dictation", "停止听写", "ディクテーション終了"],  # Said at the end of a chunk, ends dictation
+    # File Transcription
+    "transcription_concurrency": 1,  # Files transcribed at the same time (1-4)
+    "noise_suppression": False,  # Filter background noise (fans, keyboards) from PTT recordings before ASR
+    "asr_partial_results": True,  # Show live partial transcription while recording
+    "asr_streaming": True,  # Stream PTT audio to ASR in ~1s chunks while recording (lower latency)
+    "asr_language": "auto",  # auto | zh | en | ja | ko | yue (forced ASR language; auto = detect)
+    "asr_languages": [],  # Languages you speak; an auto-detected language outside the list is decoded again as the first one
+    "ptt_double_tap_latch": False,  # Double-tap PTT to record hands-free until the next press
+    "overlay_display": None,  # Display name to pin the PTT overlay to (None = display under the cursor)
+    "overlay_positions": {},  # Dragged PTT overlay positions, keyed by display layout hash
+    # Utterance Audio Archive (0 = no limit)
+    "audio_archive_enabled": False,  # Keep each PTT recording next to its message for replay
+    "audio_archive_max_mb": 500,  # Delete the oldest recordings above this total size
+    "audio_archive_max_days": 30,  # Delete recordings older than this
+    # Database Encryption
+    "database_encryption": False,  # Encrypt the history database with SQLCipher (key in the OS keychain)
+    # Storage Cleanup
+    "storage_auto_clean": True,  # Delete leftover temporary audio at startup
+    "storage_auto_clean_days": 7,  # Age (days) of temporary audio removed by the startup cleanup
+    # Transcription History (text-input dictations; 0 = no limit)
+    "transcription_history_max_days": 30,  # Delete dictations older than this
+    "transcription_history_max_items": 500,  # Keep at most this many dictations
+    # Conversation History Retention (0 / False = keep; favorite sessions are always kept)
+    "session_history_max_days": 0,  # Delete sessions not updated for this many days
+    "session_history_max_messages": 0,  # Keep at most this many messages, deleting the oldest
+    "session_history_favorites_only": False,  # Delete sessions that are not favorites once idle for a day
+    # Local API Server (localhost only; Bearer token required, generated when the server first starts)
+    "api_server": {"enabled": False, "port": 8787, "token": ""},
+    # Outbound HTTP proxy: http://, https:// or socks5:// url (empty = direct); no_proxy hosts bypass it
+    "proxy": {"url": "", "no_proxy": ["localhost", "127.0.0.1"]},
+    "offline_mode": False,  # Never call remote LLM/TTS services; use the local ones instead
+    "daemon_memory_limit_mb": 0,  # Restart this daemon (once idle) when it uses more memory (MB, 0 = no limit)
+    # Focus Pause: pause continuous listening for Do Not Disturb and meeting apps
+    "focus_pause": {
+        "enabled": True,
+        "system_dnd": True,  # Pause while the system Do Not Disturb / Focus mode is on
+        "mute_earcons": True,  # Mute sound cues while paused
+        # Bundle ids / app names / executables that pause listening while frontmost or using the mic
+        "apps": ["us.zoom.xos", "zoom.us", "Zoom", "Microsoft Teams", "Teams", "Webex", "FaceTime"],
+    },
+    # Shortcut Apps: bundle ids / app names where the global shortcuts and dictation are off
+    # (blocked), or, if allowed is not empty, the only apps where they are on
+    "shortcut_apps": {"blocked": [], "allowed": []},
+    # Updates: "stable" or "beta" channel; endpoint is the manifest URL ({channel} is replaced)
+    "updater": {
+        "channel": "stable",
+        "endpoint": "https://github.com/kanweiwei/speekium/releases/download/updater/{channel}.json",
+    },
+    # Voice Pipeline: "app" runs ASR, LLM and TTS as separate steps from the app,
+    # "daemon" runs LLM + TTS here in one go (ptt_chat)
+    "pipeline": {
+        "orchestrator": "app",
+        "retries": {"asr": 1, "llm": 1, "tts": 1},  # Extra attempts per stage after a failure
+        "skip": [],  # Stages left out: "llm" (transcript only) and/or "tts" (text reply only)
+    },
+    # Spoken replies (app pipeline): the full reply is shown, only the spoken text is cleaned up
+    "tts_postprocess": {
+        "enabled": True,
+        "strip_code": True,  # Leave out fenced code blocks
+        "strip_markdown": True,  # Remove headings, emphasis, inline code and link markup
+        "lists_to_prose": True,  # Speak list items and table rows as sentences
+        "max_sentences": 0,  # Sentences spoken per reply (0 = no limit)
+    },
+    # Numbers and dates in transcripts written with digits ("twenty third of May" -> "May 23")
+    "itn": {
+        "conversation": False,  # Transcripts sent to the LLM
+        "text_input": True,  # Text typed in text-input mode
+        "locale": "en-US",  # en-US | en-GB | en-IN: date order and digit grouping
+    },
+    # Sound Cues: short sounds on state changes (per cue: enabled, volume 0.0 - 1.0)
+    "earcons": {
+        "enabled": True,
+        "cues": {
+            "record_start": {"enabled": True, "volume": 0.5},
+            "record_stop": {"enabled": True, "volume": 0.5},
+            "error": {"enabled": True, "volume": 0.5},
+            "complete": {"enabled": True, "volume": 0.5},
+        },
+    },
+    # Ask Selection: run a prompt template on the selected text (shortcut ask_selection)
+    "ask_selection": {
+        "template": "summarize",  # summarize | translate | fix_grammar | a name from templates
+        "output": "popover",  # popover (show the answer) | replace (type it over the selection)
+        "templates": {},  # Extra/overridden templates: name -> prompt, {text} = the selection
+    },
     # Conversation Configuration
     "max_history": 10,
     "work_mode": "conversation",  # conversation | text-input
-    "recording_mode": "push-to-talk",  # push-to-talk | continuous
+    "recording_mode": "push-to-talk",  # push-to-talk | continuous | meeting
+    "text_input_strategy": "clipboard",  # clipboard | accessibility (accessibility: macOS only, falls back to clipboard; some apps such as Chromium/Electron report success without inserting)
+    "text_input_profiles": {},  # Per-app text input behavior keyed by bundle id / app name ("*" = all apps)
+    "text_input_ime_safe": True,  # macOS: while an input method (e.g. Pinyin) is active, inject Unicode key events instead of pasting
+    "text_commands_enabled": True,  # Turn spoken commands ("new line", "press enter", ...) into edits in text-input mode
+    "text_commands": {},  # Extra/overridden command phrases: phrase -> {"insert": text} | {"key": name} | "delete_last" | "all_caps"
     "system_prompt": "你是一个有帮助的语音助手。",
+    "active_persona": None,  # Persona id (personas table) whose system prompt and voice are used; None = defaults
     # Hotkey Configuration
     "push_to_talk_hotkey": {
         "modifiers": ["Alt"],
         "key": "Digit3",
         "displayName": "⌥3",
     },
+    "ptt_trigger": "shortcut",  # shortcut | mouse_button | fn_key (mouse/Fn: macOS only)
+    "ptt_mouse_button": 3,  # Mouse button number for the mouse_button trigger (2 = middle, 3/4 = side)
+    # Global action shortcuts (same format as push_to_talk_hotkey)
+    "shortcuts": {
+        "toggle_window": {
+            "modifiers": ["CmdOrCtrl", "Shift"],
+            "key": "Space",
+            "displayName": "⌘⇧Space",
+        },
+        "toggle_work_mode": {
+            "modifiers": ["Alt"],
+            "key": "Digit1",
+            "displayName": "⌥1",
+        },
+        "toggle_recording_mode": {
+            "modifiers": ["Alt"],
+            "key": "Digit2",
+            "displayName": "⌥2",
+        },
+        "paste_transcript": {
+            "modifiers": ["Alt"],
+            "key": "Digit4",
+            "displayName": "⌥4",
+        },
+        "transcription_picker": {
+            "modifiers": ["Alt"],
+            "key": "Digit5",
+            "displayName": "⌥5",
+        },
+        "toggle_history": {
+            "modifiers": ["Alt"],
+            "key": "Digit6",
+            "displayName": "⌥6",
+        },
+        "ask_selection": {
+            "modifiers": ["Alt"],
+            "key": "Digit7",
+            "displayName": "⌥7",
+        },
+        "toggle_dictation": {
+            "modifiers": ["Alt"],
+            "key": "Digit8",
+            "displayName": "⌥8",
+        },
+        "undo_last_typing": {
+            "modifiers": ["Alt"],
+            "key": "Digit9",
+            "displayName": "⌥9",
+        },
+    },
     # Onboarding Configuration
     "onboarding_completed": False,
+    # Startup (start at login itself is an OS setting: set_autostart)
+    "start_minimized": False,  # Start in the tray without showing the main window
 }
 
 
+@contextmanager
+def _config_lock():
+    """Exclusive lock on config.json.lock (same lock file as the Rust app)"""
+    with open(CONFIG_LOCK_PATH, "a+b") as lock_file:
+        if fcntl is not None:
+            fcntl.flock(lock_file.fileno(), fcntl.LOCK_EX)
+        elif msvcrt is not None:
+            lock_file.seek(0)
+            msvcrt.locking(lock_file.fileno(), msvcrt.LK_LOCK, 1)
+        try:
+            yield
+        finally:
+            if fcntl is not None:
+                fcntl.flock(lock_file.fileno(), fcntl.LOCK_UN)
+            elif msvcrt is not None:
+                lock_file.seek(0)
+                msvcrt.locking(lock_file.fileno(), msvcrt.LK_UNLCK, 1)
+
+
+def _migrate_v1_to_v2(config: dict[str, Any]) -> None:
+    """v1 -> v2: normalize mode spellings and drop malformed provider entries"""
+    if config.get("recording_mode") == "push_to_talk":
+        config["recording_mode"] = "push-to-talk"
+    if config.get("work_mode") == "text_input":
+        config["work_mode"] = "text-input"
+
+    providers = config.get("llm_providers")
+    if isinstance(providers, list):
+        config["llm_providers"] = [
+            p for p in providers if isinstance(p, dict) and isinstance(p.get("name"), str)
+        ]
+
+
+def _migrate_v2_to_v3(config: dict[str, Any]) -> None:
+    """v2 -> v3: paste by default, the Accessibility strategy misses text in some apps"""
+    if config.get("text_input_strategy") == "accessibility":
+        config["text_input_strategy"] = "clipboard"
+
+
+# Migration steps in version order: MIGRATIONS[n] upgrades version n + 1 to n + 2
+MIGRATIONS = [_migrate_v1_to_v2, _migrate_v2_to_v3]
+
+
+def migrate_config(config: dict[str, Any]) -> bool:
+    """Apply every migration between the config's version and CONFIG_VERSION
+
+    Files written before versioning are v1. Returns True if the config changed.
+    """
+    version = config.get("config_version", 1)
+    if not isinstance(version, int) or version < 1:
+        version = 1
+    if version >= CONFIG_VERSION:
+        return False
+
+    for migration in MIGRATIONS[version - 1 :]:
+        migration(config)
+    config["config_version"] = CONFIG_VERSION
+    return True
+
+
+# API keys from the OS keychain, by provider name. The Rust app sends them with
+# reload_config; config.json itself only holds empty api_key fields.
+_api_keys: dict[str, str] = {}
+
+# Set by the Rust app with reload_config while it is offline (offline_mode or no
+# connection); remote LLM/TTS services are then swapped for local ones.
+_offline = False
+
+
+def _with_api_keys(config: dict[str, Any]) -> dict[str, Any]:
+    """Config with the keychain API keys filled into llm_providers"""
+    providers = config.get("llm_providers", [])
+    return {
+        **config,
+        "llm_providers": [
+            {**p, "api_key": _api_keys[p.get("name")]} if p.get("name") in _api_keys else p
+            for p in providers
+        ],
+    }
+
+
+def _without_api_keys(config: dict[str, Any]) -> dict[str, Any]:
+    """Config with keychain API keys blanked, so they never go back to the file"""
+    providers = config.get("llm_providers", [])
+    return {
+        **config,
+        "llm_providers": [
+            {**p, "api_key": ""} if p.get("name") in _api_keys else p for p in providers
+        ],
+    }
+
+
 class ConfigManager:
     @staticmethod
     def load(silent: bool = False) -> dict[str, Any]:
@@ -126,24 +404,28 @@ class ConfigManager:
             if not silent:
                 print(f"📝 配置文件不存在，创建默认配置文件", file=sys.stderr)
             ConfigManager.save(DEFAULT_CONFIG)
-            return DEFAULT_CONFIG.copy()
+            return _with_api_keys(DEFAULT_CONFIG)
 
         try:
             with open(CONFIG_PATH, encoding="utf-8") as f:
                 config = json.load(f)
-                # Merge with defaults to ensure all fields exist
-                merged = {**DEFAULT_CONFIG, **config}
+            if migrate_config(config):
                 if not silent:
-                    print(f"✅ 配置文件加载成功", file=sys.stderr)
-                    print(f"📊 当前 LLM 服务商: {merged.get('llm_provider')}", file=sys.stderr)
-                    providers = merged.get("llm_providers", [])
-                    for p in providers:
-                        if p.get("name") == "zhipu":
-                            print(
-                                f"📊 智谱 API Key 长度: {len(p.get('api_key', ''))}",
-                                file=sys.stderr,
-                            )
-                return merged
+                    print(f"🔄 配置已迁移到版本 {CONFIG_VERSION}", file=sys.stderr)
+                ConfigManager.save(config)
+            # Merge with defaults to ensure all fields exist
+            merged = _with_api_keys({**DEFAULT_CONFIG, **config})
+            if not silent:
+                print(f"✅ 配置文件加载成功", file=sys.stderr)
+                print(f"📊 当前 LLM 服务商: {merged.get('llm_provider')}", file=sys.stderr)
+                providers = merged.get("llm_providers", [])
+                for p in providers:
+                    if p.get("name") == "zhipu":
+                        print(
+                            f"📊 智谱 API Key 长度: {len(p.get('api_key', ''))}",
+                            file=sys.stderr,
+                        )
+            return merged
         except Exception as e:
             if not silent:
                 print(f"❌ 配置文件加载失败: {e}", file=sys.stderr)
@@ -151,11 +433,18 @@ class ConfigManager:
 
     @staticmethod
     def save(config: dict[str, Any]) -> None:
-        """Save configuration file"""
+        """Save configuration file
+
+        Holds the config lock shared with the Rust app and replaces the file
+        atomically, so a concurrent write from either side is never lost halfway.
+        """
         print(f"💾 正在保存配置文件: {CONFIG_PATH}", file=sys.stderr)
         try:
-            with open(CONFIG_PATH, "w", encoding="utf-8") as f:
-                json.dump(config, f, ensure_ascii=False, indent=2)
+            tmp_path = CONFIG_PATH + ".tmp"
+            with _config_lock():
+                with open(tmp_path, "w", encoding="utf-8") as f:
+                    json.dump(_without_api_keys(config), f, ensure_ascii=False, indent=2)
+                os.replace(tmp_path, CONFIG_PATH)
             print(f"✅ 配置文件保存成功", file=sys.stderr)
             # Verify file was actually written
             if os.path.exists(CONFIG_PATH):
@@ -165,6 +454,31 @@ class ConfigManager:
             print(f"❌ 配置文件保存失败: {e}", file=sys.stderr)
             raise
 
+    @staticmethod
+    def set_api_keys(keys: dict[str, str]) -> None:
+        """Replace the keychain API keys (provider name -> key) sent by the app"""
+        _api_keys.clear()
+        _api_keys.update({name: key for name, key in keys.items() if key})
+
+    @staticmethod
+    def set_config_dir(config_dir: str) -> None:
+        """Use config.json in another directory (the app switched profile)"""
+        global CONFIG_PATH, CONFIG_LOCK_PATH
+        os.makedirs(config_dir, exist_ok=True)
+        CONFIG_PATH = os.path.join(config_dir, "config.json")
+        CONFIG_LOCK_PATH = CONFIG_PATH + ".lock"
+        print(f"📄 配置文件路径: {CONFIG_PATH}", file=sys.stderr)
+
+    @staticmethod
+    def set_offline(offline: bool) -> None:
+        """Remember whether the app is offline (prefer local models while it is)"""
+        global _offline
+        _offline = bool(offline)
+
+    @staticmethod
+    def is_offline() -> bool:
+        return _offline
+
     @staticmethod
     def get_path() -> str:
         """Get configuration file path"""
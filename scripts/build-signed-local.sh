@@ -135,8 +135,16 @@ find "$SIDECAR_DIR" -name ".gitignore" -delete 2>/dev/null || true
 find "$SIDECAR_DIR" -name "*.pyc" -delete 2>/dev/null || true
 find "$SIDECAR_DIR" -name "__pycache__" -type d -exec rm -rf {} + 2>/dev/null || true
 
+# Checksum manifest the app verifies before launching the sidecar
+# (written after the sidecar is signed, since signing changes the executable)
+write_sidecar_manifest() {
+  (cd "$SIDECAR_DIR" && shasum -a 256 worker_daemon > worker_daemon.sha256)
+  echo "✅ Sidecar manifest written"
+}
+
 if [ "$SKIP_SIGN" = true ]; then
   echo "⏭️  Skipping signing (--no-sign)"
+  write_sidecar_manifest
 else
   # Step 6: Sign from inside out with entitlements
   echo "🔏 Signing (inside-out with entitlements)..."
@@ -175,6 +183,7 @@ else
   echo "  Signing sidecar with microphone entitlements..."
   codesign --force --options runtime --timestamp --sign "$APPLE_SIGNING_IDENTITY" \
     --entitlements "$SIDECAR_ENTITLEMENTS" "$SIDECAR_DIR/worker_daemon"
+  write_sidecar_manifest
 
   # Sign main app executable with microphone entitlements
   echo "  Signing main app with microphone entitlements..."
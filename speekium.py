@@ -21,7 +21,7 @@ import tempfile
 import time
 from collections import deque
 from concurrent.futures import ThreadPoolExecutor
-from typing import TYPE_CHECKING
+from typing import TYPE_CHECKING, Optional
 
 # Lazy-loaded modules (imported on-demand for cold start optimization)
 # - torch: loaded when needed for VAD/ASR
@@ -29,7 +29,7 @@ from typing import TYPE_CHECKING
 # - sounddevice: loaded when needed for recording
 
 from backends import create_backend
-from config_loader import ConfigLoader, get_config_loader
+from config_loader import ASR_LANGUAGES, ConfigLoader, get_config_loader
 from error_tracker import get_error_tracker
 from logger import get_logger, set_component
 from mode_manager import ModeManager, RecordingMode
@@ -45,18 +45,19 @@ logger = get_logger(__name__)
 _temp_files: list[str] = []
 
 
-def create_secure_temp_file(suffix: str = ".tmp") -> str:
+def create_secure_temp_file(suffix: str = ".tmp", prefix: str = "speekium_") -> str:
     """
     Create a temporary file with secure permissions (0600 - owner read/write only)
 
     Args:
         suffix: File extension
+        prefix: File name prefix (lets the app's storage cleanup find leftovers)
 
     Returns:
         Path to the temporary file
     """
     # Create temp file with delete=False (we'll manage cleanup manually)
-    fd, path = tempfile.mkstemp(suffix=suffix)
+    fd, path = tempfile.mkstemp(suffix=suffix, prefix=prefix)
 
     # Set secure permissions: 0600 (owner read/write only)
     os.chmod(path, stat.S_IRUSR | stat.S_IWUSR)
@@ -118,6 +119,11 @@ USE_STREAMING = True  # Stream output (speak while generating)
 # Default fallback if config loading fails
 TTS_BACKEND = "edge"
 TTS_RATE = "+0%"  # Speed for Edge TTS: negative=slower, positive=faster, 0%=normal
+TTS_PITCH = "+0Hz"  # Pitch offset for Edge TTS
+TTS_VOLUME = "+0%"  # Volume offset for Edge TTS
+
+# ===== ASR =====
+MAX_ASR_ALTERNATIVES = 5  # Other hypotheses kept per utterance
 
 # ===== Edge TTS Voices (online, auto-selected based on detected language) =====
 DEFAULT_LANGUAGE = "zh"
@@ -283,6 +289,23 @@ SYSTEM_PROMPT = """You are Speekium, an intelligent voice assistant. Follow thes
 8. Be friendly, like chatting with a friend"""
 
 
+# ===== Init Progress =====
+def emit_init_progress(
+    stage: str, pct: float, downloaded: Optional[int] = None, total: Optional[int] = None
+):
+    """Report how far one startup stage is (0-100); Rust weighs the stages into daemon-progress
+
+    Stages are announced up front by the daemon's init_plan event:
+    vad_load, asr_download, asr_load, llm.
+    """
+    fields = {"stage": stage, "pct": max(0, min(100, int(pct)))}
+    if downloaded is not None:
+        fields["bytes"] = downloaded
+    if total is not None:
+        fields["total_bytes"] = total
+    logger.info("init_progress", **fields)
+
+
 # ===== Download Progress Tracker =====
 class DownloadProgressTracker:
     """Track and emit HuggingFace model download progress"""
@@ -368,10 +391,19 @@ class DownloadProgressTracker:
 class ModelScopeProgressCallback:
     """Custom ModelScope progress callback that emits JSON logs for frontend"""
 
-    def __init__(self, filename: str, file_size: int, model_name: str = "SenseVoice ASR"):
+    def __init__(
+        self,
+        filename: str,
+        file_size: int,
+        model_name: str = "SenseVoice ASR",
+        totals: Optional[dict] = None,
+    ):
         self.filename = filename
         self.file_size = file_size
         self.model_name = model_name
+        # Bytes over all files of the model (shared by the per-file callbacks)
+        self.totals = totals if totals is not None else {"downloaded": 0, "total": 0}
+        self.totals["total"] += file_size
         self.downloaded = 0
         self.start_time = 0
         self.last_emit_time = 0
@@ -384,6 +416,7 @@ class ModelScopeProgressCallback:
         import time
 
         self.downloaded += size
+        self.totals["downloaded"] += size
         current_time = time.time()
 
         # Emit update every 0.5 seconds
@@ -423,6 +456,12 @@ class ModelScopeProgressCallback:
                 speed=speed_str,
                 total_size=total_str,
             )
+            emit_init_progress(
+                "asr_download",
+                self.totals["downloaded"] / max(self.totals["total"], 1) * 100,
+                self.totals["downloaded"],
+                self.totals["total"],
+            )
 
     def end(self):
         """Called when download completes"""
@@ -459,6 +498,7 @@ class VoiceAssistant:
         # Load TTS config
         tts_config = config.get("tts", {})
         self._tts_backend = tts_config.get("tts_backend", "edge")
+        self._apply_tts_config(tts_config)
 
         # TTS generation/playback state - to pause VAD during TTS
         self.is_generating_tts = False
@@ -483,6 +523,31 @@ class VoiceAssistant:
             "vad_max_recording_duration", MAX_RECORDING_DURATION
         )
 
+        # ASR language: "auto" or one of ASR_LANGUAGES; expected languages narrow auto-detection
+        asr_config = config.get("asr", {})
+        self.asr_language = asr_config.get("asr_language", "auto")
+        self.asr_languages = asr_config.get("asr_languages", [])
+
+    def _load_asr_config(self):
+        """Reload the ASR language settings from config."""
+        asr_config = self.config_loader.load_all_config().get("asr", {})
+        self.asr_language = asr_config.get("asr_language", "auto")
+        self.asr_languages = asr_config.get("asr_languages", [])
+
+    def _apply_tts_config(self, tts_config: dict):
+        """Use the configured voice and speech parameters ("auto" voice = by language)."""
+        voice = tts_config.get("tts_voice", "auto")
+        self.tts_voice = voice if voice and voice != "auto" else None
+        self.tts_rate = tts_config.get("tts_rate", TTS_RATE)
+        self.tts_pitch = tts_config.get("tts_pitch", TTS_PITCH)
+        self.tts_volume = tts_config.get("tts_volume", TTS_VOLUME)
+
+    def _load_tts_config(self):
+        """Reload the TTS voice and speech parameters from config."""
+        tts_config = self.config_loader.load_all_config().get("tts", {})
+        self._tts_backend = tts_config.get("tts_backend", "edge")
+        self._apply_tts_config(tts_config)
+
     def get_tts_backend(self):
         """Get current TTS backend from config (refreshes on each call)."""
         self._tts_backend = self.config_loader.get_tts_backend()
@@ -594,8 +659,12 @@ class VoiceAssistant:
                     from modelscope.utils.constant import Invoke, ThirdParty
 
                     # Create a factory for progress callbacks
+                    download_totals = {"downloaded": 0, "total": 0}
+
                     def create_progress_callback(filename: str, file_size: int):
-                        return ModelScopeProgressCallback(filename, file_size, "SenseVoice ASR")
+                        return ModelScopeProgressCallback(
+                            filename, file_size, "SenseVoice ASR", download_totals
+                        )
 
                     # Download with progress tracking
                     logger.info("model_loading", model="SenseVoice")
@@ -606,6 +675,7 @@ class VoiceAssistant:
                         progress_callbacks=[create_progress_callback],
                     )
                     logger.info("asr_model_download_completed", path=model_cache_dir)
+                    emit_init_progress("asr_download", 100)
                 except Exception as e:
                     logger.warning("asr_preload_failed", error=str(e))
                     # Fallback: let FunASR handle download (without progress tracking)
@@ -615,6 +685,7 @@ class VoiceAssistant:
                 logger.info("asr_model_found_in_cache", path=model_path)
 
             logger.info("model_loading", model="SenseVoice")
+            emit_init_progress("asr_load", 0)
             load_start = time.time()
             try:
                 # Use GPU acceleration on Apple Silicon (MPS) or CUDA
@@ -640,6 +711,7 @@ class VoiceAssistant:
                 self.asr_model = AutoModel(model=ASR_MODEL, device=device, quantize=quantize)
                 load_time = time.time() - load_start
                 logger.info("model_loaded", model="SenseVoice", load_time_ms=int(load_time * 1000))
+                emit_init_progress("asr_load", 80)
 
                 # Warmup model with dummy inference for faster first response
                 logger.info("asr_warmup_start")
@@ -654,6 +726,7 @@ class VoiceAssistant:
                 except Exception as e:
                     # Warmup failure is not critical
                     logger.warning("asr_warmup_failed", error=str(e)[:100])
+                emit_init_progress("asr_load", 100)
 
             except Exception as e:
                 # 记录 ASR 加载错误
@@ -702,6 +775,7 @@ class VoiceAssistant:
 
             try:
                 logger.info("model_loading", model="VAD")
+                emit_init_progress("vad_load", 0)
 
                 # Lazy import for cold start optimization
                 import torch
@@ -743,6 +817,7 @@ class VoiceAssistant:
                         raise Exception(f"VAD model loading failed: {error_str}") from e
 
                 logger.info("model_loaded", model="VAD")
+                emit_init_progress("vad_load", 100)
             except Exception as e:
                 # 记录 VAD 加载错误
                 error_tracker = get_error_tracker()
@@ -795,6 +870,8 @@ class VoiceAssistant:
                 "OpenRouterBackend": "openrouter",
                 "CustomBackend": "custom",
                 "ZhipuBackend": "zhipu",
+                "AnthropicBackend": "anthropic",
+                "GeminiBackend": "gemini",
             }
             current_backend = backend_type_map.get(current_backend_type, "")
 
@@ -1108,8 +1185,83 @@ class VoiceAssistant:
         logger.info("ptt_recording_complete", duration=len(audio) / SAMPLE_RATE)
         return audio
 
-    def transcribe(self, audio):
-        """Transcribe audio and detect language. Returns (text, language)."""
+    def transcribe(self, audio, language=None, hotwords=None):
+        """Transcribe audio and detect language. Returns (text, language).
+
+        language: "auto" or one of ASR_LANGUAGES; defaults to the configured asr_language
+        hotwords: terms the model should prefer (the user's custom vocabulary)
+        """
+        result = self.transcribe_detailed(audio, language, hotwords)
+        return result["text"], result["language"]
+
+    @staticmethod
+    def _asr_hypotheses(entry: dict) -> list:
+        """Raw texts of the n-best list of a model result, if the model reports one"""
+        hypotheses = []
+        for item in entry.get("nbest") or []:
+            text = item.get("text") if isinstance(item, dict) else item
+            if isinstance(text, str):
+                hypotheses.append(text)
+        return hypotheses
+
+    @staticmethod
+    def _asr_word_timings(entry: dict) -> list:
+        """Word timings of a model result: [{"word", "start_ms", "end_ms"}]
+
+        Models that support output_timestamp return parallel "words" and
+        "timestamp" ([start_ms, end_ms]) lists; others return nothing.
+        """
+        words = entry.get("words") or []
+        timestamps = entry.get("timestamp") or []
+        timings = []
+        for word, span in zip(words, timestamps):
+            word = re.sub(r"<\|[^|]+\|>", "", str(word)).strip()
+            if not word or not isinstance(span, (list, tuple)) or len(span) != 2:
+                continue
+            timings.append({"word": word, "start_ms": int(span[0]), "end_ms": int(span[1])})
+        return timings
+
+    @staticmethod
+    def _asr_speaker_segments(entry: dict) -> list:
+        """Speaker segments of a model result: [{"speaker", "start_ms", "end_ms", "text"}]
+
+        Models with a speaker model (spk_model) return "sentence_info" with a
+        0-based "spk" per sentence; speakers are numbered from 1 here. Results
+        with a single speaker return nothing.
+        """
+        segments = []
+        for sentence in entry.get("sentence_info") or []:
+            if not isinstance(sentence, dict) or not isinstance(sentence.get("spk"), int):
+                continue
+            text = re.sub(r"<\|[^|]+\|>", "", str(sentence.get("text", ""))).strip()
+            if not text:
+                continue
+            segments.append(
+                {
+                    "speaker": sentence["spk"] + 1,
+                    "start_ms": int(sentence.get("start", 0)),
+                    "end_ms": int(sentence.get("end", 0)),
+                    "text": text,
+                }
+            )
+        if len({segment["speaker"] for segment in segments}) < 2:
+            return []
+        return segments
+
+    def transcribe_detailed(self, audio, language=None, hotwords=None) -> dict:
+        """Transcribe audio; returns {"text", "language", "confidence", "alternatives", "words", "segments"}
+
+        confidence (0-1) is None when the model does not report one. alternatives
+        are other hypotheses, best first: the model's n-best list if it has one,
+        and the first decode when auto-detection was retried in an expected language.
+        words are the word timings of the transcript (empty if the model has none).
+        segments split the transcript by speaker when the model diarizes it.
+        hotwords are passed to models that support hotword biasing; others ignore them.
+        """
+        hint = language if language in ASR_LANGUAGES else (self.asr_language or "auto")
+        options = {"output_timestamp": True}
+        if hotwords:
+            options["hotword"] = " ".join(hotwords)
         t0 = time.time()
         set_component("ASR")
         logger.info("asr_processing", audio_duration=len(audio) / SAMPLE_RATE)
@@ -1125,7 +1277,7 @@ class VoiceAssistant:
 
         # Security: Use secure temp file
         t3 = time.time()
-        tmp_file = create_secure_temp_file(suffix=".wav")
+        tmp_file = create_secure_temp_file(suffix=".wav", prefix="speekium_asr_")
         t4 = time.time()
         logger.debug("asr_timing", step="create_temp_file", ms=int((t4 - t3) * 1000))
 
@@ -1141,11 +1293,32 @@ class VoiceAssistant:
             logger.debug("asr_timing", step="write_wav_file", ms=int((t8 - t7) * 1000))
 
             t9 = time.time()
-            result = model.generate(input=tmp_file)
+            result = model.generate(input=tmp_file, language=hint, **options)
             t10 = time.time()
             logger.debug("asr_timing", step="model_generate", ms=int((t10 - t9) * 1000))
 
             raw_text = result[0]["text"] if result else ""
+            entry = result[0] if result else {}
+            other_raw_texts = self._asr_hypotheses(entry)
+
+            # Auto-detection picked a language the user does not expect: decode as the first expected one
+            detected = re.search(r"<\|(zh|en|ja|ko|yue)\|>", raw_text)
+            if (
+                hint == "auto"
+                and self.asr_languages
+                and detected
+                and detected.group(1) not in self.asr_languages
+            ):
+                logger.info(
+                    "asr_language_retry", detected=detected.group(1), language=self.asr_languages[0]
+                )
+                result = model.generate(
+                    input=tmp_file, language=self.asr_languages[0], **options
+                )
+                other_raw_texts = [raw_text] + other_raw_texts
+                raw_text = result[0]["text"] if result else ""
+                entry = result[0] if result else {}
+                other_raw_texts = self._asr_hypotheses(entry) + other_raw_texts
         finally:
             if tmp_file and os.path.exists(tmp_file):
                 os.remove(tmp_file)
@@ -1157,13 +1330,29 @@ class VoiceAssistant:
         # Clean all tags from text
         text = re.sub(r"<\|[^|]+\|>", "", raw_text).strip()
 
+        alternatives = []
+        for other in other_raw_texts:
+            other = re.sub(r"<\|[^|]+\|>", "", other).strip()
+            if other and other != text and other not in alternatives:
+                alternatives.append(other)
+        confidence = entry.get("confidence")
+        if not isinstance(confidence, (int, float)):
+            confidence = None
+
         total_ms = int((time.time() - t0) * 1000)
         audio_duration_ms = int(len(audio) / SAMPLE_RATE * 1000)
         rtf = total_ms / audio_duration_ms if audio_duration_ms > 0 else 0
         logger.info(
             "asr_result", language=language, text=text, total_ms=total_ms, rtf=f"{rtf:.2f}x"
         )
-        return text, language
+        return {
+            "text": text,
+            "language": language,
+            "confidence": confidence,
+            "alternatives": alternatives[:MAX_ASR_ALTERNATIVES],
+            "words": self._asr_word_timings(entry),
+            "segments": self._asr_speaker_segments(entry),
+        }
 
     def detect_speech_start(self, timeout=1.5):
         """Check if speech starts within timeout. Returns True if speech detected."""
@@ -1393,24 +1582,36 @@ class VoiceAssistant:
         # Default to English
         return "en"
 
-    async def generate_audio(self, text, language=None):
-        """Generate TTS audio file, returns file path."""
+    async def generate_audio(self, text, language=None, speech=None):
+        """Generate TTS audio file, returns file path.
+
+        `speech` holds the request's voice, rate, pitch and volume (missing =
+        configured).
+        """
         # Auto-detect language from text content for better TTS matching
         detected_lang = self.detect_text_language(text)
 
         # Always use Edge TTS
-        return await self._generate_audio_edge(text, detected_lang)
+        return await self._generate_audio_edge(text, detected_lang, speech or {})
 
-    async def _generate_audio_edge(self, text, language):
+    async def _generate_audio_edge(self, text, language, speech):
         """Generate audio using Edge TTS (online)."""
         # Lazy import for cold start optimization
         import edge_tts
 
         try:
-            voice = EDGE_TTS_VOICES.get(language, EDGE_TTS_VOICES[DEFAULT_LANGUAGE])
+            # Request (or persona) > configured voice > voice for the text's language
+            voice = (
+                speech.get("voice")
+                or self.tts_voice
+                or EDGE_TTS_VOICES.get(language, EDGE_TTS_VOICES[DEFAULT_LANGUAGE])
+            )
+            rate = speech.get("rate") or self.tts_rate
+            pitch = speech.get("pitch") or self.tts_pitch
+            volume = speech.get("volume") or self.tts_volume
             # Security: Use secure temp file
-            tmp_file = create_secure_temp_file(suffix=".mp3")
-            communicate = edge_tts.Communicate(text, voice, rate=TTS_RATE)
+            tmp_file = create_secure_temp_file(suffix=".mp3", prefix="speekium_tts_")
+            communicate = edge_tts.Communicate(text, voice, rate=rate, pitch=pitch, volume=volume)
             await communicate.save(tmp_file)
             return tmp_file
         except Exception as e:
@@ -0,0 +1,346 @@
+// ============================================================================
+// API Server Module - Local HTTP/WebSocket API
+// ============================================================================
+//
+// With `api_server.enabled`, a server on 127.0.0.1:<port> exposes the voice
+// pipeline to other apps (shell scripts, editor plugins):
+//
+//   GET  /health              daemon readiness
+//   POST /transcribe          WAV body -> {"success", "text", "language"}
+//   POST /chat                {"text"} -> {"success", "content"}
+//   POST /tts                 {"text"} -> audio file (path in X-Audio-Path)
+//   GET  /events?topics=a,b   WebSocket stream of PTT events (all if no topics)
+//
+// Every request must carry `api_server.token` as a bearer token (or
+// `?token=` for clients that can't set WebSocket headers). An empty token is
+// replaced by a random one when the server starts, and saved to config.json.
+// Requests with an Origin header come from a web page and are refused:
+// browsers don't apply CORS to WebSocket upgrades or simple POSTs, so any
+// site could otherwise reach the server.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+use axum::body::Bytes;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{DefaultBodyLimit, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+use crate::config::{self, ApiServerConfig};
+use crate::daemon::{call_daemon_async, DAEMON_READY};
+use crate::types::{ChatResult, RecordResult};
+
+const API_SERVER_KEY: &str = "api_server";
+
+/// Largest /transcribe upload: about 25 min of 44.1 kHz stereo, over 2 h of 16 kHz mono
+const MAX_UPLOAD_BYTES: usize = 256 * 1024 * 1024;
+
+/// PTT events buffered per WebSocket client before it starts missing some
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// PTT events forwarded to WebSocket clients (set once the server runs)
+static PTT_EVENTS: OnceLock<broadcast::Sender<serde_json::Value>> = OnceLock::new();
+
+type ApiResult<T> = Result<T, (StatusCode, Json<serde_json::Value>)>;
+
+fn api_error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<serde_json::Value>) {
+    (status, Json(serde_json::json!({ "success": false, "error": message.into() })))
+}
+
+#[derive(Clone)]
+struct ServerState {
+    app: tauri::AppHandle,
+    token: String,
+}
+
+impl ServerState {
+    /// Refuse browser requests, then check the bearer token (header or `token` query parameter)
+    fn authorize(&self, headers: &HeaderMap, query: &HashMap<String, String>) -> ApiResult<()> {
+        if headers.contains_key(header::ORIGIN) {
+            return Err(api_error(StatusCode::FORBIDDEN, "Requests from web pages are not allowed"));
+        }
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        let provided = bearer.or(query.get("token").map(String::as_str));
+        match provided {
+            Some(provided) if tokens_match(provided, &self.token) => Ok(()),
+            _ => Err(api_error(StatusCode::UNAUTHORIZED, "Invalid or missing token")),
+        }
+    }
+}
+
+/// Compare tokens in constant time (for tokens of equal length)
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    let (provided, expected) = (provided.as_bytes(), expected.as_bytes());
+    !expected.is_empty()
+        && provided.len() == expected.len()
+        && provided.iter().zip(expected).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Random token for a server that has none yet
+fn generate_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// Fail fast instead of waiting for a daemon that is still loading models
+fn require_daemon() -> ApiResult<()> {
+    if DAEMON_READY.load(Ordering::SeqCst) {
+        Ok(())
+    } else {
+        Err(api_error(StatusCode::SERVICE_UNAVAILABLE, "Daemon is not ready"))
+    }
+}
+
+// ============================================================================
+// Startup
+// ============================================================================
+
+/// Start the API server in the background if `api_server.enabled` is set
+///
+/// Changes to `api_server` take effect after a restart.
+pub fn start_if_enabled(app: &tauri::AppHandle) {
+    let mut server: ApiServerConfig = config::read_config_value(API_SERVER_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    if !server.enabled {
+        return;
+    }
+
+    // The server never runs without a token
+    if server.token.trim().is_empty() {
+        server.token = generate_token();
+        let saved = serde_json::to_value(&server)
+            .map_err(|e| e.to_string())
+            .and_then(|value| config::write_config_value(API_SERVER_KEY, value));
+        if let Err(e) = saved {
+            eprintln!("[API SERVER] Failed to save the generated token, not starting: {}", e);
+            return;
+        }
+        println!("[API SERVER] Generated a token (api_server.token in config.json)");
+    }
+
+    let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+    let _ = PTT_EVENTS.set(events);
+
+    let state = ServerState {
+        app: app.clone(),
+        token: server.token,
+    };
+    let router = Router::new()
+        .route("/health", get(health))
+        .route("/transcribe", post(transcribe).layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES)))
+        .route("/chat", post(chat))
+        .route("/tts", post(tts))
+        .route("/events", get(event_stream))
+        .with_state(state);
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, server.port));
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("[API SERVER] Failed to listen on {}: {}", addr, e);
+                return;
+            }
+        };
+        println!("[API SERVER] Listening on http://{}", addr);
+        if let Err(e) = axum::serve(listener, router).await {
+            eprintln!("[API SERVER] Server stopped: {}", e);
+        }
+    });
+}
+
+/// Forward a PTT event to WebSocket clients (no-op when the server is off)
+pub fn publish_ptt_event(event: &serde_json::Value) {
+    if let Some(events) = PTT_EVENTS.get() {
+        // Err only means no client is connected
+        let _ = events.send(event.clone());
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+async fn health(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> ApiResult<Json<serde_json::Value>> {
+    state.authorize(&headers, &query)?;
+    Ok(Json(serde_json::json!({
+        "status": "ok",
+        "daemon_ready": DAEMON_READY.load(Ordering::SeqCst),
+    })))
+}
+
+/// Transcribe a WAV upload (any sample rate, mono or stereo)
+async fn transcribe(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+    body: Bytes,
+) -> ApiResult<Json<RecordResult>> {
+    state.authorize(&headers, &query)?;
+    require_daemon()?;
+    if body.is_empty() {
+        return Err(api_error(StatusCode::BAD_REQUEST, "Request body must be a WAV file"));
+    }
+
+    // The daemon deletes the file after loading it; it is only removed here if the call fails
+    let path = std::env::temp_dir().join(format!("speekium_asr_{}.wav", uuid::Uuid::new_v4().simple()));
+    std::fs::write(&path, &body)
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to save upload: {}", e)))?;
+
+    let result = call_daemon_async("transcribe", serde_json::json!({
+        "audio_path": path.to_string_lossy(),
+        "hotwords": crate::vocabulary::request_hotwords(),
+    }))
+    .await
+    .map_err(|e| {
+        let _ = std::fs::remove_file(&path);
+        api_error(StatusCode::BAD_GATEWAY, e)
+    })?;
+
+    let mut result: RecordResult = serde_json::from_value(result)
+        .map_err(|e| api_error(StatusCode::BAD_GATEWAY, format!("Failed to parse result: {}", e)))?;
+    result.text = result.text.map(|text| crate::textproc::process_transcript(&state.app, &text));
+    if let Some(text) = &result.text {
+        result.alternatives = crate::asr_alternatives::process_alternatives(&state.app, text, &result.alternatives);
+    }
+    Ok(Json(result))
+}
+
+#[derive(Deserialize)]
+struct TextRequest {
+    text: String,
+}
+
+async fn chat(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+    Json(request): Json<TextRequest>,
+) -> ApiResult<Json<ChatResult>> {
+    state.authorize(&headers, &query)?;
+    if crate::llm::native_enabled() {
+        return Ok(Json(crate::llm::chat(&state.app, &request.text).await));
+    }
+    require_daemon()?;
+
+    let args = crate::personas::with_persona(&state.app, serde_json::json!({ "text": request.text }));
+    let result = call_daemon_async("chat", args)
+        .await
+        .map_err(|e| api_error(StatusCode::BAD_GATEWAY, e))?;
+    let result = serde_json::from_value(result)
+        .map_err(|e| api_error(StatusCode::BAD_GATEWAY, format!("Failed to parse result: {}", e)))?;
+    Ok(Json(result))
+}
+
+/// Synthesize speech and return the audio file
+async fn tts(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+    Json(request): Json<TextRequest>,
+) -> ApiResult<Response> {
+    state.authorize(&headers, &query)?;
+    require_daemon()?;
+
+    let result = crate::commands::generate_tts(state.app.clone(), request.text, None)
+        .await
+        .map_err(|e| api_error(StatusCode::BAD_GATEWAY, e))?;
+    let audio_path = match (result.success, result.audio_path) {
+        (true, Some(path)) => path,
+        _ => {
+            let error = result.error.unwrap_or_else(|| "TTS failed".to_string());
+            return Err(api_error(StatusCode::BAD_GATEWAY, error));
+        }
+    };
+
+    let audio = tokio::fs::read(&audio_path)
+        .await
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read audio: {}", e)))?;
+    let content_type = if audio_path.ends_with(".wav") { "audio/wav" } else { "audio/mpeg" };
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::HeaderName::from_static("x-audio-path"), audio_path),
+        ],
+        audio,
+    )
+        .into_response())
+}
+
+/// Stream PTT events, optionally filtered by `topics` (comma-separated event names)
+async fn event_stream(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+    ws: WebSocketUpgrade,
+) -> ApiResult<Response> {
+    state.authorize(&headers, &query)?;
+    let Some(events) = PTT_EVENTS.get() else {
+        return Err(api_error(StatusCode::SERVICE_UNAVAILABLE, "Event stream is not available"));
+    };
+
+    let topics: Vec<String> = query
+        .get("topics")
+        .map(|t| t.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let receiver = events.subscribe();
+    Ok(ws.on_upgrade(move |socket| forward_events(socket, receiver, topics)))
+}
+
+async fn forward_events(mut socket: WebSocket, mut receiver: broadcast::Receiver<serde_json::Value>, topics: Vec<String>) {
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    // A slow client just misses some events
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let name = event.get("ptt_event").and_then(|v| v.as_str()).unwrap_or("");
+                if !topics.is_empty() && !topics.iter().any(|t| t == name) {
+                    continue;
+                }
+                if socket.send(Message::Text(event.to_string().into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => {
+                // Incoming messages are ignored; stop when the client goes away
+                match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        let token = generate_token();
+        assert_eq!(token.len(), 64);
+        assert!(tokens_match(&token, &token));
+        assert!(!tokens_match(&token[..63], &token));
+        assert!(!tokens_match(&token.to_uppercase(), &token));
+        assert!(!tokens_match("", ""));
+    }
+}
@@ -3,11 +3,28 @@ use tauri::Manager;
 use crate::audio::AudioRecorder;
 use crate::daemon::AUDIO_RECORDER;
 use crate::database;
-use crate::database::Database;
+use crate::db_encryption;
 use crate::state::AppState;
 use crate::ui;
 use crate::shortcuts;
+use crate::shortcut_apps;
 use crate::daemon;
+use crate::diagnostics;
+use crate::api_server;
+use crate::audio_archive;
+use crate::deep_link;
+use crate::storage;
+use crate::secrets;
+use crate::network;
+use crate::focus_pause;
+use crate::prompt_templates;
+use crate::reminders;
+use crate::retention;
+use crate::file_transcription;
+use crate::autostart;
+use crate::instance;
+use crate::vocabulary;
+use crate::chat_draft;
 
 use crate::daemon::{
     APP_HANDLE,
@@ -27,6 +44,14 @@ fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     // Tray icon remains active for showing the window again
     #[cfg(target_os = "macos")]
     app.set_activation_policy(ActivationPolicy::Regular);
+
+    diagnostics::record_app_start();
+
+    // Write panics to a crash report in the app data directory
+    if let Ok(app_data_dir) = app.path().app_data_dir() {
+        diagnostics::install_panic_hook(app_data_dir);
+    }
+
     // Initialize AudioRecorder singleton (only once at startup)
     // This triggers microphone permission request on first access
     // cpal 0.17 fixes the repeated permission popup issue
@@ -47,22 +72,75 @@ fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let db_path = database::get_database_path(app.handle())
         .map_err(|e| tauri::Error::Anyhow(anyhow::anyhow!("Failed to get database path: {}", e)))?;
 
-    let db = Database::new(db_path)
+    let db = db_encryption::open(db_path)
         .map_err(|e| tauri::Error::Anyhow(anyhow::anyhow!("Failed to initialize database: {}", e)))?;
 
+    // Hotwords sent with every transcription request
+    vocabulary::reload(&db);
+    crate::textproc::reload_symbols(&db);
+
     app.manage(AppState { db });
 
+    // Replies that were streaming when the app last quit
+    chat_draft::interrupt_stale_drafts(app.handle());
+
+    // Delete conversations that fall outside the history retention policy
+    retention::start_scheduler(app.handle());
+
+    // Remove archived recordings that fall outside the retention policy
+    audio_archive::spawn_retention_cleanup(app.handle());
+
+    // Remove temporary audio left behind by earlier runs
+    storage::spawn_startup_cleanup(app.handle());
+
+    // Local HTTP/WebSocket API for other apps
+    api_server::start_if_enabled(app.handle());
+
+    // speekium:// URLs from other apps
+    deep_link::setup(app.handle());
+
     // Create tray icon (reads language from config file)
     ui::create_tray(app.handle(), cleanup_daemon)?;
 
     // Store app handle globally BEFORE starting dispatcher
     let _ = APP_HANDLE.set(app.handle().clone());
 
+    // Start in the tray only (e.g. when started at login)
+    if autostart::start_minimized() {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+        #[cfg(target_os = "macos")]
+        set_activation_policy_accessory();
+    }
+
+    // Move API keys saved by older versions out of config.json
+    secrets::migrate_plaintext_keys();
+
+    // Headless CLI commands run in this instance instead of starting their own daemon
+    instance::start_listener();
+
+    // Offline detection (remote providers are refused while offline)
+    network::start_connectivity_monitor();
+
+    // Pause continuous listening for Do Not Disturb and meeting apps
+    focus_pause::start_focus_monitor();
+
     // Start recording mode event dispatcher
     shortcuts::start_recording_mode_dispatcher(app.handle());
 
     // Register shortcuts
     shortcuts::register_shortcuts(app.handle())?;
+    prompt_templates::register_hotkeys(app.handle());
+
+    // Turn the shortcuts off while a blocked app is frontmost
+    shortcut_apps::start_monitor(app.handle());
+
+    // Spoken reminders (fires any that came due while the app was closed)
+    reminders::start_scheduler(app.handle());
+
+    // File transcriptions interrupted by the last quit
+    file_transcription::resume_jobs(app.handle());
 
     // Start daemon asynchronously (non-blocking)
     // This allows the UI to show immediately while daemon loads in background
@@ -88,9 +166,21 @@ fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 // ============================================================================
 
 fn handle_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    // Recordings dropped on the main window are transcribed into new sessions
+    if let tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) = event {
+        if window.label() == "main" {
+            crate::file_transcription::handle_drop(window.app_handle(), paths);
+        }
+        return;
+    }
     if let tauri::WindowEvent::CloseRequested { api, .. } = event {
         // Prevent window close, hide window and app instead
         api.prevent_close();
+        // The history and pinned windows are hidden on their own; the main window may still be in use
+        if window.label() == ui::HISTORY_WINDOW_LABEL || window.label() == ui::PINNED_WINDOW_LABEL {
+            let _ = window.hide();
+            return;
+        }
         // Ignore hide error - nothing we can do if it fails
         let _ = window.hide();
         // macOS: Hide app and change to Accessory policy (removes from Dock)
@@ -145,9 +235,19 @@ fn handle_run_event(app_handle: &tauri::AppHandle, event: tauri::RunEvent) {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let mut builder = tauri::Builder::default()
+        // Must come first: a second launch (e.g. from a speekium:// link) hands its URL to this instance
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            // speekium:// links are handled by the deep-link plugin without raising the window
+            if !argv.iter().any(|arg| arg.starts_with("speekium://")) {
+                ui::show_main_window(app);
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
-        .plugin(tauri_plugin_notification::init());
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(crate::updater::plugin());
 
     #[cfg(target_os = "macos")]
     {
@@ -165,16 +265,36 @@ pub fn run() {
             crate::commands::get_work_mode,
             crate::commands::set_work_mode,
             crate::commands::get_app_status,
+            crate::commands::get_last_transcription,
+            crate::status::get_status_history,
             crate::commands::interrupt_operation,
+            crate::commands::overlay_interrupt,
+            crate::commands::cancel_daemon_request,
             crate::commands::chat_llm,
+            crate::commands::chat_llm_with_history,
             crate::commands::chat_llm_stream,
             crate::commands::chat_tts_stream,
             crate::commands::generate_tts,
             crate::commands::load_config,
             crate::commands::save_config,
             crate::commands::update_hotkey,
+            crate::commands::update_shortcut,
+            crate::commands::get_shortcuts,
+            crate::commands::validate_shortcut,
+            crate::commands::set_ptt_trigger,
+            crate::commands::list_audio_input_devices,
+            crate::commands::set_audio_input_device,
+            crate::commands::list_displays,
+            crate::commands::set_overlay_display,
+            crate::commands::reset_overlay_position,
+            crate::commands::resize_overlay,
+            crate::commands::open_history_window,
+            crate::commands::close_history_window,
+            crate::commands::open_pinned_window,
+            crate::commands::close_pinned_window,
             crate::commands::get_daemon_state,
             crate::commands::daemon_health,
+            crate::commands::get_daemon_resources,
             crate::commands::get_app_language,
             crate::commands::set_app_language,
             crate::commands::get_model_status,
@@ -183,15 +303,103 @@ pub fn run() {
             crate::commands::cloud_sync_download,
             crate::commands::get_dropbox_auth_url,
             // API commands
-            crate::api::test_ollama_connection,
-            crate::api::list_ollama_models,
-            crate::api::test_openai_connection,
-            crate::api::test_openrouter_connection,
-            crate::api::test_custom_connection,
-            crate::api::test_zhipu_connection,
+            crate::providers::test_llm_connection,
+            crate::providers::list_models,
+            crate::secrets::set_api_key,
+            crate::secrets::get_api_key,
+            crate::secrets::delete_api_key,
+            crate::permissions::check_permissions,
+            crate::permissions::request_permission,
+            crate::permissions::open_permission_settings,
+            crate::network::get_connectivity,
+            crate::focus_pause::get_listening_paused,
+            crate::focus_pause::set_dnd_override,
+            crate::asr_language::get_asr_language,
+            crate::asr_language::set_asr_language,
+            crate::asr_language::set_asr_session,
+            crate::tts_voices::list_tts_voices,
+            crate::tts_voices::set_tts_voice,
+            crate::tts_voices::preview_voice,
+            crate::transcriptions::list_transcriptions,
+            crate::transcriptions::delete_transcription,
+            crate::transcriptions::paste_transcription,
+            crate::transcriptions::close_transcription_picker,
+            crate::selection::ask_selection,
+            crate::selection::list_selection_templates,
+            crate::selection::get_selection_answer,
+            crate::selection::copy_selection_answer,
+            crate::selection::replace_selection_with_answer,
+            crate::selection::close_selection_answer,
+            crate::earcons::play_cue,
             crate::api::get_error_stats,
             crate::api::upload_errors_to_github,
+            // Diagnostics commands
+            crate::diagnostics::get_last_crash_report,
+            crate::diagnostics::clear_crash_report,
+            // Metrics commands
+            crate::metrics::get_latency_stats,
+            crate::usage_stats::get_usage_stats,
+            crate::profiles::list_profiles,
+            crate::profiles::create_profile,
+            crate::profiles::switch_profile,
+            crate::vocabulary::list_vocabulary,
+            crate::vocabulary::add_vocabulary_term,
+            crate::vocabulary::update_vocabulary_term,
+            crate::vocabulary::delete_vocabulary_term,
+            crate::vocabulary::import_vocabulary,
+            crate::events::resync_events,
+            crate::updater::check_for_updates,
+            crate::updater::download_update,
+            crate::updater::install_and_restart,
+            crate::textproc::add_replacement,
+            crate::textproc::list_replacements,
+            crate::textproc::delete_replacement,
+            crate::textproc::list_default_symbols,
+            crate::textproc::list_symbol_shortcuts,
+            crate::textproc::set_symbol_shortcut,
+            crate::textproc::delete_symbol_shortcut,
+            crate::personas::create_persona,
+            crate::personas::list_personas,
+            crate::personas::update_persona,
+            crate::personas::delete_persona,
+            crate::personas::get_active_persona,
+            crate::personas::set_active_persona,
+            crate::prompt_templates::create_prompt_template,
+            crate::prompt_templates::list_prompt_templates,
+            crate::prompt_templates::update_prompt_template,
+            crate::prompt_templates::delete_prompt_template,
+            crate::prompt_templates::run_prompt_template,
+            crate::reminders::create_reminder,
+            crate::reminders::list_reminders,
+            crate::reminders::delete_reminder,
+            crate::autostart::get_autostart,
+            crate::autostart::set_autostart,
+            crate::self_test::run_self_test,
+            crate::recording_journal::list_pending_recordings,
+            crate::recording_journal::recover_pending_recordings,
+            crate::recording_journal::discard_pending_recordings,
+            crate::asr_alternatives::get_message_alternatives,
+            crate::word_timings::get_message_word_timings,
+            crate::meeting::get_meeting_status,
+            crate::dictation::start_dictation,
+            crate::dictation::stop_dictation,
+            crate::dictation::get_dictation_document,
+            crate::file_transcription::transcribe_file,
+            crate::file_transcription::transcribe_files,
+            crate::file_transcription::list_jobs,
+            crate::file_transcription::cancel_job,
+            crate::file_transcription::retry_job,
+            crate::tts_export::synthesize_to_file,
+            crate::focus_pause::pause_listening,
+            crate::focus_pause::resume_listening,
+            crate::db_encryption::set_database_encryption,
+            crate::retention::run_retention_now,
+            crate::audio_archive::get_message_audio,
+            crate::tts_cache::clear_tts_cache,
+            crate::storage::get_storage_usage,
+            crate::storage::clean_storage,
             crate::platform::type_text_command,
+            crate::platform::undo_last_typing,
             // Database commands
             crate::db_commands::db_create_session,
             crate::db_commands::db_list_sessions,
@@ -199,9 +407,19 @@ pub fn run() {
             crate::db_commands::db_toggle_favorite,
             crate::db_commands::db_update_session,
             crate::db_commands::db_delete_session,
+            crate::db_commands::db_trash_session,
+            crate::db_commands::db_restore_session,
+            crate::db_commands::db_empty_trash,
             crate::db_commands::db_add_message,
             crate::db_commands::db_get_messages,
-            crate::db_commands::db_delete_message
+            crate::db_commands::db_delete_message,
+            crate::db_commands::db_pin_message,
+            crate::db_commands::db_list_pinned,
+            crate::db_commands::db_get_draft,
+            crate::db_commands::db_auto_title_session,
+            crate::db_commands::db_export_session,
+            crate::db_commands::db_export_all,
+            crate::db_commands::db_import_sessions
         ])
         .setup(setup_app)
         .on_window_event(handle_window_event)
@@ -0,0 +1,93 @@
+// ============================================================================
+// ASR Alternatives Module - Confidence and N-Best Transcripts
+// ============================================================================
+//
+// Along with the transcript the daemon returns the model's confidence (when it
+// reports one) and alternative hypotheses, best first. Like the detected
+// language (see asr_language.rs) they are kept until the frontend saves the
+// user message (db_add_message), then stored on the message so the UI can
+// offer them when a word was misrecognized (`get_message_alternatives`).
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::State;
+
+use crate::database::{Database, Message};
+use crate::state::AppState;
+use crate::types::MessageAlternatives;
+
+/// Alternatives are dropped if no message is saved within this time
+const PENDING_ALTERNATIVES_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Confidence and alternatives of the last transcript, waiting for its message to be saved
+struct PendingAlternatives {
+    transcript: String,
+    confidence: Option<f64>,
+    alternatives: Vec<String>,
+    recognized_at: Instant,
+}
+
+static PENDING_ALTERNATIVES: Mutex<Option<PendingAlternatives>> = Mutex::new(None);
+
+/// Post-process the alternatives like the transcript, dropping empty ones and duplicates of it
+pub fn process_alternatives<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    transcript: &str,
+    alternatives: &[String],
+) -> Vec<String> {
+    let mut processed: Vec<String> = Vec::new();
+    for alternative in alternatives {
+        let text = crate::textproc::process_transcript(app, alternative);
+        if !text.trim().is_empty() && text != transcript && !processed.contains(&text) {
+            processed.push(text);
+        }
+    }
+    processed
+}
+
+/// Remember the confidence and alternatives of a transcript until its message is saved
+pub fn set_pending_alternatives(transcript: &str, confidence: Option<f64>, alternatives: Vec<String>) {
+    if let Ok(mut pending) = PENDING_ALTERNATIVES.lock() {
+        *pending = (confidence.is_some() || !alternatives.is_empty()).then(|| PendingAlternatives {
+            transcript: transcript.to_string(),
+            confidence,
+            alternatives,
+            recognized_at: Instant::now(),
+        });
+    }
+}
+
+/// Store the pending alternatives on a newly saved user message with the same transcript
+pub fn attach_pending_alternatives(db: &Database, message: &Message) {
+    if message.role != "user" {
+        return;
+    }
+    let pending = match PENDING_ALTERNATIVES.lock() {
+        Ok(mut pending) => pending.take_if(|p| {
+            p.transcript == message.content && p.recognized_at.elapsed() < PENDING_ALTERNATIVES_TIMEOUT
+        }),
+        Err(_) => None,
+    };
+    let Some(pending) = pending else {
+        return;
+    };
+
+    if let Err(e) = db.set_message_alternatives(&message.id, pending.confidence, &pending.alternatives) {
+        eprintln!("[ASR ALTERNATIVES] {}", e);
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// ASR confidence and alternative transcripts of a message (none for typed messages)
+#[tauri::command]
+pub fn get_message_alternatives(
+    state: State<'_, AppState>,
+    message_id: String,
+) -> Result<MessageAlternatives, String> {
+    let (confidence, alternatives) = state.db.get_message_alternatives(&message_id)?;
+    Ok(MessageAlternatives { message_id, confidence, alternatives })
+}
@@ -0,0 +1,173 @@
+// ============================================================================
+// ASR Language Module - Language Hints and Per-Session Stickiness
+// ============================================================================
+//
+// SenseVoice detects the spoken language by itself, which goes wrong on short
+// utterances and for accents. Three settings steer it:
+//
+//   asr_language    "auto" or a language the model is forced to
+//                   (set_asr_language, also forwarded to the daemon)
+//   asr_languages   languages the user speaks; an auto-detected language
+//                   outside the list is decoded again as the first one
+//   session         with "auto", the language of the latest spoken message
+//                   of the active session is sent with each recording
+//
+// The detected language of a transcript is kept until the frontend saves the
+// user message (db_add_message), then stored on the message and used for the
+// following recordings of that session.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::config;
+use crate::database::{Database, Message};
+use crate::daemon::{call_daemon_async, is_daemon_ready};
+use crate::state::AppState;
+
+/// Languages SenseVoice can be told to expect (keep in sync with config_loader.ASR_LANGUAGES)
+pub const ASR_LANGUAGES: [&str; 5] = ["zh", "en", "ja", "ko", "yue"];
+
+const AUTO: &str = "auto";
+
+/// A transcript language is dropped if no message is saved within this time
+const PENDING_LANGUAGE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Detected language of the last transcript, waiting for its message to be saved
+struct PendingLanguage {
+    transcript: String,
+    language: String,
+    detected_at: Instant,
+}
+
+static PENDING_LANGUAGE: Mutex<Option<PendingLanguage>> = Mutex::new(None);
+
+/// Language of the active session (None = no spoken message yet)
+static SESSION_LANGUAGE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Whether `language` is "auto" or a supported language code
+pub fn is_valid_language(language: &str) -> bool {
+    language == AUTO || ASR_LANGUAGES.contains(&language)
+}
+
+fn configured_language() -> String {
+    config::read_config_value("asr_language")
+        .and_then(|v| v.as_str().map(str::to_string))
+        .filter(|language| is_valid_language(language))
+        .unwrap_or_else(|| AUTO.to_string())
+}
+
+/// Language hint sent with a recording: the configured language, else the session's
+pub fn request_language() -> String {
+    let configured = configured_language();
+    if configured != AUTO {
+        return configured;
+    }
+    SESSION_LANGUAGE
+        .lock()
+        .ok()
+        .and_then(|language| language.clone())
+        .unwrap_or(configured)
+}
+
+/// Remember the detected language of a transcript until its message is saved
+pub fn set_pending_language(transcript: &str, language: &str) {
+    if !ASR_LANGUAGES.contains(&language) {
+        return;
+    }
+    if let Ok(mut pending) = PENDING_LANGUAGE.lock() {
+        *pending = Some(PendingLanguage {
+            transcript: transcript.to_string(),
+            language: language.to_string(),
+            detected_at: Instant::now(),
+        });
+    }
+}
+
+/// Store the pending language on a newly saved user message with the same transcript
+///
+/// The language also becomes the session's language for the next recordings.
+pub fn attach_pending_language(db: &Database, message: &mut Message) {
+    if message.role != "user" {
+        return;
+    }
+    let pending = match PENDING_LANGUAGE.lock() {
+        Ok(mut pending) => pending.take_if(|p| {
+            p.transcript == message.content && p.detected_at.elapsed() < PENDING_LANGUAGE_TIMEOUT
+        }),
+        Err(_) => None,
+    };
+    let Some(pending) = pending else {
+        return;
+    };
+
+    match db.set_message_language(&message.id, &pending.language) {
+        Ok(()) => {
+            if let Ok(mut session_language) = SESSION_LANGUAGE.lock() {
+                *session_language = Some(pending.language.clone());
+            }
+            message.language = Some(pending.language);
+        }
+        Err(e) => eprintln!("[ASR LANGUAGE] {}", e),
+    }
+}
+
+/// ASR language settings as shown in the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct AsrLanguageInfo {
+    /// "auto" or a forced language
+    pub language: String,
+    /// Expected languages (`asr_languages`)
+    pub languages: Vec<String>,
+    /// Language of the active session, used while `language` is "auto"
+    pub session_language: Option<String>,
+    pub supported: Vec<&'static str>,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Current ASR language settings
+#[tauri::command]
+pub fn get_asr_language() -> AsrLanguageInfo {
+    let languages = config::read_config_value("asr_languages")
+        .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+        .unwrap_or_default();
+    AsrLanguageInfo {
+        language: configured_language(),
+        languages,
+        session_language: SESSION_LANGUAGE.lock().ok().and_then(|language| language.clone()),
+        supported: ASR_LANGUAGES.to_vec(),
+    }
+}
+
+/// Force the ASR language, or "auto" to detect it (saved to config)
+#[tauri::command]
+pub async fn set_asr_language(language: String) -> Result<AsrLanguageInfo, String> {
+    if !is_valid_language(&language) {
+        return Err(format!("Unsupported ASR language: {}", language));
+    }
+    config::write_config_value("asr_language", serde_json::json!(language))?;
+
+    // A daemon that is still starting reads the saved value
+    if is_daemon_ready() {
+        call_daemon_async("set_asr_language", serde_json::json!({ "language": language })).await?;
+    }
+    Ok(get_asr_language())
+}
+
+/// Switch the session whose language later recordings prefer (None = new / no session)
+///
+/// Returns the session's language.
+#[tauri::command]
+pub fn set_asr_session(state: State<'_, AppState>, session_id: Option<String>) -> Result<Option<String>, String> {
+    let language = match session_id {
+        Some(session_id) => state.db.get_session_language(&session_id)?,
+        None => None,
+    };
+    *SESSION_LANGUAGE.lock().unwrap() = language.clone();
+    Ok(language)
+}
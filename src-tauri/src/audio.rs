@@ -5,21 +5,188 @@
 // ============================================================================
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use serde::Serialize;
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU64, Ordering}};
 use std::sync::mpsc::{channel, Sender, Receiver};
 use std::thread::{self, JoinHandle};
 use std::fs::File;
 use std::io::Write;
+use std::time::{Duration, Instant};
 
 // Audio recording constants
 const SAMPLE_RATE: u32 = 16000;  // 16kHz for ASR
 const CHANNELS: u16 = 1;  // Mono
 
+/// Config key for the selected input device id
+pub const AUDIO_INPUT_DEVICE_KEY: &str = "audio_input_device";
+
+// Recording limit defaults (overridable via config.json)
+const DEFAULT_SILENCE_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_MAX_RECORDING_SECS: u64 = 120;
+// RMS level above which a chunk counts as voice for the silence timeout
+const SILENCE_RMS_THRESHOLD: f32 = 0.01;
+
+/// Time to wait for the recording thread to open the input stream
+const STREAM_START_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Backend error texts that mean another app holds the device exclusively
+/// (CoreAudio hog mode, WASAPI exclusive mode, ALSA / PulseAudio busy)
+const BUSY_PATTERNS: [&str; 7] = [
+    "busy",
+    "in use",
+    "exclusive",
+    "hog",
+    "AUDCLNT_E_DEVICE_IN_USE",
+    "0x8889000A",
+    "EBUSY",
+];
+
+/// Backend error texts that mean microphone access was denied
+const PERMISSION_PATTERNS: [&str; 3] = ["permission", "access denied", "E_ACCESSDENIED"];
+
 /// Commands sent to the recording thread
 enum RecordingCommand {
     Stop,
 }
 
+/// Why the microphone could not be opened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MicError {
+    /// A recording is already running in this app
+    AlreadyRecording,
+    /// No input device is connected
+    NoDevice,
+    /// Another app holds the device exclusively
+    DeviceBusy { device: String },
+    /// The device was unplugged or disabled
+    DeviceUnavailable { device: String },
+    /// The OS denied microphone access
+    PermissionDenied { device: String },
+    Other { device: String, message: String },
+}
+
+impl MicError {
+    /// Classify a cpal backend error message
+    fn from_backend(device: &str, message: &str) -> Self {
+        let lower = message.to_lowercase();
+        let matches = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(&p.to_lowercase()));
+        let device = device.to_string();
+        if matches(&BUSY_PATTERNS) {
+            MicError::DeviceBusy { device }
+        } else if lower.contains("no longer available") {
+            MicError::DeviceUnavailable { device }
+        } else if matches(&PERMISSION_PATTERNS) {
+            MicError::PermissionDenied { device }
+        } else {
+            MicError::Other { device, message: message.to_string() }
+        }
+    }
+
+    fn from_build(device: &str, error: cpal::BuildStreamError) -> Self {
+        match error {
+            cpal::BuildStreamError::DeviceNotAvailable => MicError::DeviceUnavailable { device: device.to_string() },
+            error => MicError::from_backend(device, &error.to_string()),
+        }
+    }
+
+    fn from_play(device: &str, error: cpal::PlayStreamError) -> Self {
+        match error {
+            cpal::PlayStreamError::DeviceNotAvailable => MicError::DeviceUnavailable { device: device.to_string() },
+            error => MicError::from_backend(device, &error.to_string()),
+        }
+    }
+
+    /// Name of the device that failed
+    pub fn device(&self) -> Option<&str> {
+        match self {
+            MicError::DeviceBusy { device }
+            | MicError::DeviceUnavailable { device }
+            | MicError::PermissionDenied { device }
+            | MicError::Other { device, .. } => Some(device),
+            MicError::AlreadyRecording | MicError::NoDevice => None,
+        }
+    }
+
+    /// Whether the device may become usable without user action
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, MicError::DeviceBusy { .. } | MicError::DeviceUnavailable { .. })
+    }
+}
+
+impl std::fmt::Display for MicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MicError::AlreadyRecording => write!(f, "Already recording"),
+            MicError::NoDevice => write!(f, "No input device available"),
+            MicError::DeviceBusy { device } => write!(f, "Microphone \"{}\" is in use by another app", device),
+            MicError::DeviceUnavailable { device } => write!(f, "Microphone \"{}\" is not available", device),
+            MicError::PermissionDenied { device } => write!(f, "Access to microphone \"{}\" was denied", device),
+            MicError::Other { device, message } => write!(f, "Failed to open microphone \"{}\": {}", device, message),
+        }
+    }
+}
+
+impl From<MicError> for String {
+    fn from(error: MicError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Reason a recording was stopped automatically
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingTimeout {
+    /// No voice detected for `silence_timeout_ms`
+    Silence,
+    /// Recording reached `max_recording_secs`
+    MaxDuration,
+}
+
+impl RecordingTimeout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordingTimeout::Silence => "silence",
+            RecordingTimeout::MaxDuration => "max_duration",
+        }
+    }
+}
+
+/// Limits that stop a recording automatically (a value of 0 disables the limit)
+#[derive(Debug, Clone, Copy)]
+struct RecordingLimits {
+    silence_timeout: Option<Duration>,
+    max_duration: Option<Duration>,
+}
+
+impl RecordingLimits {
+    /// Read limits from config.json, so changes apply to the next recording
+    fn from_config() -> Self {
+        let config = crate::config::read_config();
+        let silence_ms = config.get("silence_timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_SILENCE_TIMEOUT_MS);
+        let max_secs = config.get("max_recording_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_MAX_RECORDING_SECS);
+
+        RecordingLimits {
+            silence_timeout: (silence_ms > 0).then(|| Duration::from_millis(silence_ms)),
+            max_duration: (max_secs > 0).then(|| Duration::from_secs(max_secs)),
+        }
+    }
+
+    /// Check whether a limit has been hit
+    fn check(&self, elapsed: Duration, since_voice: Duration) -> Option<RecordingTimeout> {
+        if self.max_duration.is_some_and(|max| elapsed >= max) {
+            return Some(RecordingTimeout::MaxDuration);
+        }
+        if self.silence_timeout.is_some_and(|timeout| since_voice >= timeout) {
+            return Some(RecordingTimeout::Silence);
+        }
+        None
+    }
+}
+
 /// Thread-safe audio recorder
 /// Uses a background thread for recording to avoid Send/Sync issues with cpal::Stream
 pub struct AudioRecorder {
@@ -31,6 +198,8 @@ pub struct AudioRecorder {
     command_tx: Option<Sender<RecordingCommand>>,
     /// Recording thread handle
     thread_handle: Option<JoinHandle<()>>,
+    /// Preferred input device id (None = system default)
+    device_id: Option<String>,
 }
 
 // Mark as Send + Sync since we only store thread-safe types
@@ -45,13 +214,37 @@ impl AudioRecorder {
             buffer: Arc::new(Mutex::new(Vec::new())),
             command_tx: None,
             thread_handle: None,
+            device_id: crate::config::read_config_value(AUDIO_INPUT_DEVICE_KEY)
+                .and_then(|v| v.as_str().map(String::from)),
         })
     }
 
+    /// Preferred input device id (None = system default)
+    pub fn device_id(&self) -> Option<&str> {
+        self.device_id.as_deref()
+    }
+
+    /// Set the preferred input device (None = system default)
+    ///
+    /// Takes effect on the next recording.
+    pub fn set_device(&mut self, device_id: Option<String>) {
+        self.device_id = device_id;
+    }
+
     /// Start recording audio in a background thread
-    pub fn start_recording(&mut self) -> Result<(), String> {
+    ///
+    /// `on_timeout` is called from the recording thread if the recording is
+    /// stopped automatically by the silence timeout or maximum length. The
+    /// recorded audio is kept until `stop_recording` is called.
+    ///
+    /// Waits until the input stream is running, so a device that cannot be
+    /// opened (e.g. held exclusively by another app) is reported here.
+    pub fn start_recording<F>(&mut self, on_timeout: F) -> Result<(), MicError>
+    where
+        F: FnOnce(RecordingTimeout) + Send + 'static,
+    {
         if self.is_recording.load(Ordering::SeqCst) {
-            return Err("Already recording".to_string());
+            return Err(MicError::AlreadyRecording);
         }
 
         // Clear previous buffer
@@ -62,22 +255,39 @@ impl AudioRecorder {
 
         // Create command channel
         let (tx, rx) = channel();
-        self.command_tx = Some(tx);
+        let (started_tx, started_rx) = channel();
 
         // Clone shared state for the thread
         let is_recording = self.is_recording.clone();
         let buffer = self.buffer.clone();
+        let device_id = self.device_id.clone();
+        let limits = RecordingLimits::from_config();
 
         // Set recording flag before spawning thread
         is_recording.store(true, Ordering::SeqCst);
 
         // Spawn recording thread
+        let thread_recording = is_recording.clone();
         let handle = thread::spawn(move || {
-            if let Err(_e) = run_recording_thread(is_recording.clone(), buffer, rx) {
+            let result = run_recording_thread(thread_recording.clone(), buffer, rx, device_id.as_deref(), limits, &started_tx);
+            thread_recording.store(false, Ordering::SeqCst);
+            match result {
+                Ok(Some(reason)) => on_timeout(reason),
+                Ok(None) => {}
+                Err(e) => {
+                    let _ = started_tx.send(Err(e));
+                }
             }
-            is_recording.store(false, Ordering::SeqCst);
         });
 
+        // A slow device is treated as started; stop_recording cleans up either way
+        if let Ok(Err(e)) = started_rx.recv_timeout(STREAM_START_TIMEOUT) {
+            let _ = handle.join();
+            is_recording.store(false, Ordering::SeqCst);
+            return Err(e);
+        }
+
+        self.command_tx = Some(tx);
         self.thread_handle = Some(handle);
 
         Ok(())
@@ -85,7 +295,8 @@ impl AudioRecorder {
 
     /// Stop recording and save audio to a temporary WAV file
     pub fn stop_recording(&mut self) -> Result<AudioData, String> {
-        if !self.is_recording.load(Ordering::SeqCst) {
+        // A recording that timed out has already stopped but its thread has not been joined
+        if !self.is_recording.load(Ordering::SeqCst) && self.thread_handle.is_none() {
             return Err("Not recording".to_string());
         }
 
@@ -100,7 +311,7 @@ impl AudioRecorder {
         }
 
         // Get recorded samples
-        let samples = {
+        let mut samples = {
             let buffer = self.buffer.lock().unwrap();
             buffer.clone()
         };
@@ -111,6 +322,10 @@ impl AudioRecorder {
             return Err("No audio data recorded".to_string());
         }
 
+        if crate::dsp::is_enabled() {
+            crate::dsp::suppress_noise(&mut samples, SAMPLE_RATE);
+        }
+
         // Convert to WAV format
         let wav_data = samples_to_wav(&samples)?;
 
@@ -135,6 +350,11 @@ impl AudioRecorder {
     pub fn is_recording(&self) -> bool {
         self.is_recording.load(Ordering::SeqCst)
     }
+
+    /// Samples of the current recording (16kHz mono), kept until the next one starts
+    pub fn buffer(&self) -> Arc<Mutex<Vec<f32>>> {
+        self.buffer.clone()
+    }
 }
 
 /// Audio data result
@@ -149,6 +369,102 @@ pub struct AudioData {
     pub sample_count: usize,
 }
 
+/// Audio input device info
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioInputDevice {
+    /// Stable device id (persisted in config)
+    pub id: String,
+    /// Human-readable device name
+    pub name: String,
+    /// Whether this is the system default input device
+    pub is_default: bool,
+}
+
+/// Get a device's stable id string
+fn device_id_string(device: &cpal::Device) -> Option<String> {
+    device.id().ok().map(|id| id.to_string())
+}
+
+/// List available audio input devices
+pub fn list_input_devices() -> Result<Vec<AudioInputDevice>, String> {
+    let host = cpal::default_host();
+    let default_id = host.default_input_device().as_ref().and_then(device_id_string);
+
+    let devices = host.input_devices()
+        .map_err(|e| format!("Failed to enumerate input devices: {}", e))?;
+
+    Ok(devices
+        .filter_map(|device| {
+            let id = device_id_string(&device)?;
+            let name = device.description()
+                .map(|d| d.name().to_string())
+                .unwrap_or_else(|_| id.clone());
+            Some(AudioInputDevice {
+                is_default: default_id.as_deref() == Some(id.as_str()),
+                id,
+                name,
+            })
+        })
+        .collect())
+}
+
+/// Open the preferred input device, falling back to the system default
+/// if it is not set or no longer available
+pub fn resolve_input_device(host: &cpal::Host, device_id: Option<&str>) -> Result<cpal::Device, MicError> {
+    if let Some(wanted) = device_id {
+        let found = host.input_devices()
+            .ok()
+            .and_then(|mut devices| {
+                devices.find(|d| device_id_string(d).as_deref() == Some(wanted))
+            });
+
+        if let Some(device) = found {
+            return Ok(device);
+        }
+        eprintln!("⚠️ Input device '{}' not found, falling back to default", wanted);
+    }
+
+    host.default_input_device().ok_or(MicError::NoDevice)
+}
+
+/// Display name of a device for error messages
+pub fn device_name(device: &cpal::Device) -> String {
+    device.description()
+        .map(|d| d.name().to_string())
+        .ok()
+        .or_else(|| device_id_string(device))
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// Open the default input device for a moment and discard the audio
+///
+/// Fails if the OS denies microphone access. On macOS the first call shows
+/// the microphone permission prompt.
+pub fn probe_microphone() -> Result<(), String> {
+    let host = cpal::default_host();
+    let device = host.default_input_device()
+        .ok_or_else(|| "No input device available".to_string())?;
+    let config = device.default_input_config()
+        .map_err(|e| format!("Failed to get input config: {}", e))?;
+    let stream = device
+        .build_input_stream_raw(&config.config(), config.sample_format(), |_: &cpal::Data, _: &cpal::InputCallbackInfo| {}, |_| {}, None)
+        .map_err(|e| format!("Failed to open input device: {}", e))?;
+    stream.play().map_err(|e| format!("Failed to start input stream: {}", e))?;
+    thread::sleep(Duration::from_millis(100));
+    Ok(())
+}
+
+/// Write samples to a temporary WAV file named `speekium_<label>_<uuid>.wav`
+pub fn write_temp_wav(samples: &[f32], label: &str) -> Result<String, String> {
+    let wav_data = samples_to_wav(samples)?;
+    let path = std::env::temp_dir()
+        .join(format!("speekium_{}_{}.wav", label, uuid::Uuid::new_v4()))
+        .to_string_lossy()
+        .to_string();
+    std::fs::write(&path, wav_data).map_err(|e| format!("Failed to write WAV file: {}", e))?;
+    Ok(path)
+}
+
 /// Create a unique temporary file path for WAV audio
 fn create_temp_wav_path() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -163,20 +479,38 @@ fn create_temp_wav_path() -> String {
     temp_dir.join(filename).to_string_lossy().to_string()
 }
 
+/// Update the last-voice timestamp if the chunk is louder than the silence threshold
+fn track_voice(samples: &[f32], started: Instant, last_voice_ms: &AtomicU64) {
+    if samples.is_empty() {
+        return;
+    }
+    let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+    if rms > SILENCE_RMS_THRESHOLD {
+        last_voice_ms.store(started.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+}
+
 /// Run the recording in a dedicated thread
+///
+/// Returns the timeout reason if the recording was stopped by a limit.
+/// `stream_started` gets Ok once the input stream is running.
 fn run_recording_thread(
     is_recording: Arc<AtomicBool>,
     buffer: Arc<Mutex<Vec<f32>>>,
     rx: Receiver<RecordingCommand>,
-) -> Result<(), String> {
-    // Get default host and input device
+    device_id: Option<&str>,
+    limits: RecordingLimits,
+    stream_started: &Sender<Result<(), MicError>>,
+) -> Result<Option<RecordingTimeout>, MicError> {
+    // Get selected (or default) input device
     let host = cpal::default_host();
-    let device = host.default_input_device()
-        .ok_or_else(|| "No input device available".to_string())?;
+    let device = resolve_input_device(&host, device_id)?;
+    let name = device_name(&device);
 
 
     // Configure stream
-    let config = find_suitable_config(&device)?;
+    let config = find_suitable_config(&device)
+        .map_err(|e| MicError::from_backend(&name, &e))?;
     let actual_sample_rate = config.sample_rate();
     let actual_channels = config.channels();
 
@@ -189,6 +523,11 @@ fn run_recording_thread(
     let buffer_clone = buffer.clone();
     let is_recording_clone = is_recording.clone();
 
+    // Silence tracking (milliseconds since start of the last voiced chunk)
+    let started = Instant::now();
+    let last_voice_ms = Arc::new(AtomicU64::new(0));
+    let last_voice_clone = last_voice_ms.clone();
+
     // Create input stream based on sample format
     let stream = match config.sample_format() {
         cpal::SampleFormat::F32 => {
@@ -197,6 +536,7 @@ fn run_recording_thread(
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
                     if is_recording_clone.load(Ordering::SeqCst) {
                         let processed = process_audio_data(data, actual_sample_rate, actual_channels);
+                        track_voice(&processed, started, &last_voice_clone);
                         if let Ok(mut buf) = buffer_clone.lock() {
                             buf.extend_from_slice(&processed);
                         }
@@ -204,7 +544,7 @@ fn run_recording_thread(
                 },
                 err_fn,
                 None,
-            ).map_err(|e| format!("Failed to build input stream: {}", e))?
+            ).map_err(|e| MicError::from_build(&name, e))?
         }
         cpal::SampleFormat::I16 => {
             device.build_input_stream(
@@ -216,6 +556,7 @@ fn run_recording_thread(
                             .map(|&s| s as f32 / i16::MAX as f32)
                             .collect();
                         let processed = process_audio_data(&float_data, actual_sample_rate, actual_channels);
+                        track_voice(&processed, started, &last_voice_clone);
                         if let Ok(mut buf) = buffer_clone.lock() {
                             buf.extend_from_slice(&processed);
                         }
@@ -223,7 +564,7 @@ fn run_recording_thread(
                 },
                 err_fn,
                 None,
-            ).map_err(|e| format!("Failed to build input stream: {}", e))?
+            ).map_err(|e| MicError::from_build(&name, e))?
         }
         cpal::SampleFormat::U16 => {
             device.build_input_stream(
@@ -235,6 +576,7 @@ fn run_recording_thread(
                             .map(|&s| (s as f32 / u16::MAX as f32) * 2.0 - 1.0)
                             .collect();
                         let processed = process_audio_data(&float_data, actual_sample_rate, actual_channels);
+                        track_voice(&processed, started, &last_voice_clone);
                         if let Ok(mut buf) = buffer_clone.lock() {
                             buf.extend_from_slice(&processed);
                         }
@@ -242,18 +584,23 @@ fn run_recording_thread(
                 },
                 err_fn,
                 None,
-            ).map_err(|e| format!("Failed to build input stream: {}", e))?
+            ).map_err(|e| MicError::from_build(&name, e))?
         }
         format => {
-            return Err(format!("Unsupported sample format: {:?}", format));
+            return Err(MicError::Other {
+                device: name,
+                message: format!("Unsupported sample format: {:?}", format),
+            });
         }
     };
 
     // Start the stream
-    stream.play().map_err(|e| format!("Failed to start stream: {}", e))?;
+    stream.play().map_err(|e| MicError::from_play(&name, e))?;
+    let _ = stream_started.send(Ok(()));
 
 
     // Wait for stop command (with timeout check)
+    let mut timeout = None;
     loop {
         // Check for stop command (non-blocking with timeout)
         match rx.recv_timeout(std::time::Duration::from_millis(100)) {
@@ -272,16 +619,24 @@ fn run_recording_thread(
         if !is_recording.load(Ordering::SeqCst) {
             break;
         }
+
+        // Stop automatically if a recording limit is hit
+        let elapsed = started.elapsed();
+        let since_voice = elapsed.saturating_sub(Duration::from_millis(last_voice_ms.load(Ordering::Relaxed)));
+        if let Some(reason) = limits.check(elapsed, since_voice) {
+            timeout = Some(reason);
+            break;
+        }
     }
 
     // Stream will be dropped here, releasing the audio device
     drop(stream);
 
-    Ok(())
+    Ok(timeout)
 }
 
 /// Find a suitable audio config, preferring 16kHz mono
-fn find_suitable_config(device: &cpal::Device) -> Result<cpal::SupportedStreamConfig, String> {
+pub fn find_suitable_config(device: &cpal::Device) -> Result<cpal::SupportedStreamConfig, String> {
     let supported_configs = device.supported_input_configs()
         .map_err(|e| format!("Failed to get supported configs: {}", e))?;
 
@@ -312,7 +667,7 @@ fn find_suitable_config(device: &cpal::Device) -> Result<cpal::SupportedStreamCo
 }
 
 /// Process audio data: resample to 16kHz and convert to mono if needed
-fn process_audio_data(data: &[f32], src_rate: u32, channels: u16) -> Vec<f32> {
+pub fn process_audio_data(data: &[f32], src_rate: u32, channels: u16) -> Vec<f32> {
     // Convert to mono if stereo
     let mono_data: Vec<f32> = if channels > 1 {
         data.chunks(channels as usize)
@@ -0,0 +1,217 @@
+// ============================================================================
+// Audio Archive Module - Recorded Utterances Linked to Messages
+// ============================================================================
+//
+// With `audio_archive_enabled`, every PTT recording is copied into
+// <app data>/audio before the daemon deletes the temporary WAV. The copy is
+// linked to the user message that is saved with the same transcript, so it
+// can be replayed from history. A retention policy (`audio_archive_max_mb`,
+// `audio_archive_max_days`) removes old recordings at startup and after each
+// new one.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use tauri::{Manager, State};
+
+use crate::config;
+use crate::database::{Database, Message};
+use crate::state::AppState;
+
+const AUDIO_ARCHIVE_ENABLED_KEY: &str = "audio_archive_enabled";
+const AUDIO_ARCHIVE_MAX_MB_KEY: &str = "audio_archive_max_mb";
+const AUDIO_ARCHIVE_MAX_DAYS_KEY: &str = "audio_archive_max_days";
+
+/// Retention defaults (keep in sync with default_config)
+const DEFAULT_MAX_MB: u64 = 500;
+const DEFAULT_MAX_DAYS: u64 = 30;
+
+/// Subdirectory of the app data directory holding archived recordings
+const AUDIO_ARCHIVE_DIR_NAME: &str = "audio";
+
+/// How long an archived recording waits for its message to be saved
+const PENDING_AUDIO_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Unlinked recordings younger than this are kept (their message may not be saved yet)
+const ORPHAN_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// Archived recording waiting for its user message
+struct PendingAudio {
+    transcript: String,
+    path: PathBuf,
+    archived_at: Instant,
+}
+
+static PENDING_AUDIO: Mutex<Option<PendingAudio>> = Mutex::new(None);
+
+pub(crate) fn archive_dir<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(AUDIO_ARCHIVE_DIR_NAME))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+fn is_enabled() -> bool {
+    config::read_config_value(AUDIO_ARCHIVE_ENABLED_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Archiving
+// ============================================================================
+
+/// Copy a finished recording into the archive
+///
+/// Returns None when archiving is disabled or the copy failed.
+pub fn archive_recording<R: tauri::Runtime>(app: &tauri::AppHandle<R>, wav_path: &str) -> Option<PathBuf> {
+    if !is_enabled() {
+        return None;
+    }
+
+    let result = archive_dir(app).and_then(|dir| {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create audio archive directory: {}", e))?;
+        let file_name = format!(
+            "{}-{}.wav",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        let dest = dir.join(file_name);
+        std::fs::copy(wav_path, &dest)
+            .map_err(|e| format!("Failed to archive recording: {}", e))?;
+        Ok(dest)
+    });
+
+    result.map_err(|e| eprintln!("[AUDIO ARCHIVE] {}", e)).ok()
+}
+
+/// Remember an archived recording until the user message with `transcript` is saved
+pub fn set_pending_audio(transcript: &str, path: PathBuf) {
+    if let Ok(mut pending) = PENDING_AUDIO.lock() {
+        // A previous recording that never got its message is left to the cleanup
+        *pending = Some(PendingAudio {
+            transcript: transcript.to_string(),
+            path,
+            archived_at: Instant::now(),
+        });
+    }
+}
+
+/// Delete an archived recording that no message will use (e.g. no speech)
+pub fn discard_recording(path: &Path) {
+    let _ = std::fs::remove_file(path);
+}
+
+/// Link the pending recording to a newly saved user message with the same transcript
+pub fn attach_pending_audio(db: &Database, message: &mut Message) {
+    if message.role != "user" {
+        return;
+    }
+    let pending = match PENDING_AUDIO.lock() {
+        Ok(mut pending) => pending.take_if(|p| {
+            p.transcript == message.content && p.archived_at.elapsed() < PENDING_AUDIO_TIMEOUT
+        }),
+        Err(_) => None,
+    };
+    let Some(pending) = pending else {
+        return;
+    };
+
+    let path = pending.path.to_string_lossy().into_owned();
+    match db.set_message_audio(&message.id, &path) {
+        Ok(()) => message.audio_path = Some(path),
+        Err(e) => eprintln!("[AUDIO ARCHIVE] {}", e),
+    }
+}
+
+// ============================================================================
+// Retention
+// ============================================================================
+
+/// Remove expired, orphaned and over-quota recordings (oldest first)
+///
+/// Returns the number of removed files.
+pub fn enforce_retention<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<usize, String> {
+    let dir = archive_dir(app)?;
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let Some(state) = app.try_state::<AppState>() else {
+        return Ok(0);
+    };
+
+    // 0 = no limit
+    let max_bytes = config::read_config_value(AUDIO_ARCHIVE_MAX_MB_KEY)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_MAX_MB)
+        .saturating_mul(1024 * 1024);
+    let max_age = config::read_config_value(AUDIO_ARCHIVE_MAX_DAYS_KEY)
+        .and_then(|v| v.as_u64())
+        .or(Some(DEFAULT_MAX_DAYS))
+        .filter(|days| *days > 0)
+        .map(|days| Duration::from_secs(days * 24 * 60 * 60));
+    let linked: HashSet<String> = state.db.list_message_audio_paths()?.into_iter().collect();
+
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read audio archive: {}", e))?;
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wav"))
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(&path).ok()?;
+            let modified = metadata.modified().ok()?;
+            Some((path, metadata.len(), modified))
+        })
+        .collect();
+    files.sort_by_key(|(_, _, modified)| *modified);
+
+    let now = SystemTime::now();
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    let mut removed = 0;
+    for (path, size, modified) in &files {
+        let age = now.duration_since(*modified).unwrap_or_default();
+        let path_str = path.to_string_lossy();
+
+        let orphaned = !linked.contains(path_str.as_ref()) && age > ORPHAN_GRACE_PERIOD;
+        let expired = max_age.is_some_and(|max| age > max);
+        let over_quota = max_bytes > 0 && total > max_bytes;
+        if !(orphaned || expired || over_quota) {
+            continue;
+        }
+
+        if std::fs::remove_file(path).is_ok() {
+            total = total.saturating_sub(*size);
+            removed += 1;
+            state.db.clear_message_audio(&path_str)?;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Apply the retention policy on a background thread
+pub fn spawn_retention_cleanup<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = enforce_retention(&app) {
+            eprintln!("[AUDIO ARCHIVE] Cleanup failed: {}", e);
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// File path of a message's archived recording (None if it has none or the file is gone)
+///
+/// The frontend turns it into an asset URL with `convertFileSrc` for replay.
+#[tauri::command]
+pub fn get_message_audio(state: State<'_, AppState>, message_id: String) -> Result<Option<String>, String> {
+    let path = state.db.get_message_audio(&message_id)?;
+    Ok(path.filter(|p| Path::new(p).exists()))
+}
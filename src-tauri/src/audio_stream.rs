@@ -0,0 +1,148 @@
+// ============================================================================
+// Audio Stream Module - Streaming PTT Audio to the Daemon
+// ============================================================================
+//
+// Sending the whole WAV on key release makes long utterances wait for ASR of
+// everything at the end. With `asr_streaming` on, the audio recorded so far is
+// sent to the daemon about once a second (`ptt_audio_chunk`, one WAV per
+// chunk) while the key is held. The daemon transcribes it in segments cut at
+// pauses, so on release (`ptt_audio_end` with the remaining audio) only the
+// last segment is left.
+//
+// Streaming is skipped while `noise_suppression` is on: the filter needs the
+// whole recording to estimate the noise. Recordings shorter than one chunk
+// are sent with `ptt_audio` as before.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Config key enabling streamed PTT audio
+pub const ASR_STREAMING_KEY: &str = "asr_streaming";
+
+/// Interval between chunks
+const CHUNK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Stream of the PTT recording in progress
+struct ActiveStream {
+    id: String,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    stop_tx: Sender<()>,
+    /// Returns (samples sent, chunks sent)
+    handle: JoinHandle<(usize, u64)>,
+}
+
+static ACTIVE: Mutex<Option<ActiveStream>> = Mutex::new(None);
+
+/// Rest of a streamed recording, sent with `ptt_audio_end`
+pub struct StreamTail {
+    pub stream_id: String,
+    /// Audio recorded after the last chunk (None if there is none)
+    pub audio_path: Option<String>,
+}
+
+/// Whether PTT recordings are streamed
+pub fn is_enabled() -> bool {
+    let streaming = crate::config::read_config_value(ASR_STREAMING_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    streaming && !crate::dsp::is_enabled()
+}
+
+fn send_chunk(stream_id: &str, seq: u64, samples: &[f32], language: &str, hotwords: &[String]) -> Result<(), String> {
+    let path = crate::audio::write_temp_wav(samples, "chunk")?;
+    let args = serde_json::json!({
+        "stream_id": stream_id,
+        "seq": seq,
+        "audio_path": path,
+        "language": language,
+        "hotwords": hotwords,
+    });
+    let result = crate::daemon::daemon_client().and_then(|client| client.send_no_wait("ptt_audio_chunk", args));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&path);
+    }
+    result
+}
+
+/// Start sending chunks of the recording that fills `buffer`
+///
+/// Replaces a stream that was not finished.
+pub fn start(buffer: Arc<Mutex<Vec<f32>>>) {
+    cancel();
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let language = crate::asr_language::request_language();
+    let hotwords = crate::vocabulary::request_hotwords();
+    let (stop_tx, stop_rx) = channel();
+    let thread_id = id.clone();
+    let thread_buffer = buffer.clone();
+    let handle = std::thread::spawn(move || {
+        let (mut sent, mut seq) = (0, 0);
+        while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(CHUNK_INTERVAL) {
+            let chunk = {
+                let buffer = thread_buffer.lock().unwrap();
+                buffer[sent.min(buffer.len())..].to_vec()
+            };
+            if chunk.is_empty() {
+                continue;
+            }
+            match send_chunk(&thread_id, seq, &chunk, &language, &hotwords) {
+                Ok(()) => {
+                    sent += chunk.len();
+                    seq += 1;
+                }
+                Err(e) => eprintln!("[AUDIO STREAM] Failed to send chunk {}: {}", seq, e),
+            }
+        }
+        (sent, seq)
+    });
+
+    *ACTIVE.lock().unwrap() = Some(ActiveStream {
+        id,
+        buffer,
+        stop_tx,
+        handle,
+    });
+}
+
+/// Stop streaming after the recording stopped and write the remaining audio
+///
+/// Returns None if no chunk was sent; the recording is then submitted whole.
+pub fn finish() -> Option<StreamTail> {
+    let stream = ACTIVE.lock().unwrap().take()?;
+    let _ = stream.stop_tx.send(());
+    let (sent, seq) = stream.handle.join().ok()?;
+    if seq == 0 {
+        return None;
+    }
+
+    let rest = {
+        let buffer = stream.buffer.lock().unwrap();
+        buffer[sent.min(buffer.len())..].to_vec()
+    };
+    let audio_path = if rest.is_empty() {
+        None
+    } else {
+        match crate::audio::write_temp_wav(&rest, "chunk") {
+            Ok(path) => Some(path),
+            Err(e) => {
+                eprintln!("[AUDIO STREAM] Failed to write the last chunk: {}", e);
+                None
+            }
+        }
+    };
+    Some(StreamTail {
+        stream_id: stream.id,
+        audio_path,
+    })
+}
+
+/// Stop streaming a recording that is discarded
+pub fn cancel() {
+    if let Some(stream) = ACTIVE.lock().unwrap().take() {
+        let _ = stream.stop_tx.send(());
+        let _ = stream.handle.join();
+    }
+}
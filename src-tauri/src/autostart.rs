@@ -0,0 +1,51 @@
+// ============================================================================
+// Autostart Module - Start at Login
+// ============================================================================
+//
+// Starting with the OS is registered natively instead of through config.json,
+// so it also reflects changes made in the system settings:
+//
+//   macOS     LaunchAgent in ~/Library/LaunchAgents (RunAtLoad)
+//   Windows   value in HKCU\...\CurrentVersion\Run
+//   Linux     XDG autostart entry in ~/.config/autostart
+//
+// Entries are named after the app identifier and start the current
+// executable. `start_minimized` is separate: it keeps the main window hidden
+// at startup (only the tray icon appears), whether the app was started at
+// login or by hand.
+
+use crate::config;
+
+const START_MINIMIZED_KEY: &str = "start_minimized";
+
+/// Whether the main window stays hidden at startup
+///
+/// Ignored until onboarding is completed, so a fresh install always shows it.
+pub fn start_minimized() -> bool {
+    let enabled = config::read_config_value(START_MINIMIZED_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let onboarded = config::read_config_value("onboarding_completed")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    enabled && onboarded
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Whether the app starts at login
+#[tauri::command]
+pub fn get_autostart(app: tauri::AppHandle) -> bool {
+    crate::platform::autostart_enabled(&app.config().identifier)
+}
+
+/// Register or remove the start-at-login entry
+#[tauri::command]
+pub fn set_autostart(app: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate the executable: {}", e))?;
+    crate::platform::set_autostart(&app.config().identifier, &exe, enabled)?;
+    println!("[AUTOSTART] Start at login {}", if enabled { "enabled" } else { "disabled" });
+    Ok(crate::platform::autostart_enabled(&app.config().identifier))
+}
@@ -0,0 +1,104 @@
+// ============================================================================
+// Chat Draft Module - Persisting Streamed Replies While They Arrive
+// ============================================================================
+//
+// A streamed reply used to be saved by the frontend once it was complete, so
+// a crash mid-stream lost it. When chat_llm_stream is given a session, the
+// reply is written to a draft message instead: the row is created with the
+// first chunk, its text is saved at most every DRAFT_SAVE_INTERVAL, and it is
+// completed on "done" or marked interrupted on an error or cancel. Drafts
+// still open at startup (the app died mid-stream) are marked interrupted.
+//
+// `db_get_draft(session_id)` returns the latest draft or interrupted reply of
+// a session, so the UI can restore a partial answer.
+
+use std::time::{Duration, Instant};
+
+use tauri::Manager;
+
+use crate::database::{MESSAGE_STATUS_DRAFT, MESSAGE_STATUS_INTERRUPTED};
+use crate::state::AppState;
+
+/// Minimum time between two saves of a draft's text
+const DRAFT_SAVE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Draft message of one streamed reply (does nothing without a session)
+pub struct DraftWriter {
+    app: tauri::AppHandle,
+    session_id: Option<String>,
+    message_id: Option<String>,
+    content: String,
+    saved_at: Instant,
+}
+
+impl DraftWriter {
+    pub fn new(app: &tauri::AppHandle, session_id: Option<String>) -> Self {
+        Self {
+            app: app.clone(),
+            session_id,
+            message_id: None,
+            content: String::new(),
+            saved_at: Instant::now(),
+        }
+    }
+
+    /// Append a chunk, saving the text if the last save is old enough
+    pub fn push(&mut self, chunk: &str) {
+        if self.session_id.is_none() {
+            return;
+        }
+        self.content.push_str(chunk);
+        if self.message_id.is_none() || self.saved_at.elapsed() >= DRAFT_SAVE_INTERVAL {
+            self.save(Some(MESSAGE_STATUS_DRAFT));
+        }
+    }
+
+    /// Save the complete reply; returns its message id
+    pub fn finish(mut self) -> Option<String> {
+        self.save(None);
+        self.message_id
+    }
+
+    /// Keep the text received so far as an interrupted reply
+    pub fn interrupt(mut self) {
+        self.save(Some(MESSAGE_STATUS_INTERRUPTED));
+    }
+
+    fn save(&mut self, status: Option<&str>) {
+        let Some(session_id) = &self.session_id else {
+            return;
+        };
+        let Some(state) = self.app.try_state::<AppState>() else {
+            return;
+        };
+        // Nothing arrived: no message
+        if self.message_id.is_none() && self.content.is_empty() {
+            return;
+        }
+
+        let result = match &self.message_id {
+            Some(id) => state.db.update_draft_message(id, &self.content, status).map(|_| ()),
+            None => state.db.add_draft_message(session_id).and_then(|message| {
+                state.db.update_draft_message(&message.id, &self.content, status)?;
+                self.message_id = Some(message.id);
+                Ok(())
+            }),
+        };
+        if let Err(e) = result {
+            eprintln!("[CHAT DRAFT] {}", e);
+        }
+        self.saved_at = Instant::now();
+    }
+}
+
+/// Mark the drafts of an earlier run as interrupted (call once the database is open)
+pub fn interrupt_stale_drafts(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    match state.db.interrupt_stale_drafts() {
+        Ok(0) => {}
+        Ok(count) => println!("[CHAT DRAFT] Marked {} unfinished reply(s) as interrupted", count),
+        Err(e) => eprintln!("[CHAT DRAFT] {}", e),
+    }
+}
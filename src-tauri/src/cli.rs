@@ -0,0 +1,182 @@
+// ============================================================================
+// CLI Module - Headless Companion Commands
+// ============================================================================
+//
+//   speekium transcribe <file.wav>
+//   speekium tts "<text>" [-o <output file>]
+//   speekium chat "<prompt>"
+//
+// These start the daemon without any window or tray, run one operation,
+// print the JSON result to stdout and exit (0 on success, 1 on failure,
+// 2 on usage errors). Daemon logs are only shown with `--verbose`.
+
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use tokio::io::AsyncBufReadExt;
+
+use crate::daemon::{call_daemon, cleanup_daemon, PythonDaemon, DAEMON, DAEMON_READY, PTT_STDERR};
+
+const USAGE: &str = "Usage:
+  speekium transcribe <file.wav> [--verbose]
+  speekium tts <text> [-o <output file>] [--verbose]
+  speekium chat <prompt> [--verbose]";
+
+/// A parsed CLI invocation
+#[derive(Debug, PartialEq, Eq)]
+enum CliCommand {
+    Transcribe { path: String },
+    Tts { text: String, output: Option<String> },
+    Chat { prompt: String },
+}
+
+/// Parse the arguments after the program name
+///
+/// Returns Ok(None) when the arguments are not a CLI command (start the app).
+fn parse_args(args: &[String]) -> Result<Option<(CliCommand, bool)>, String> {
+    let Some(name) = args.first() else {
+        return Ok(None);
+    };
+    if !matches!(name.as_str(), "transcribe" | "tts" | "chat") {
+        return Ok(None);
+    }
+
+    let mut verbose = false;
+    let mut output = None;
+    let mut positional = Vec::new();
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--verbose" | "-v" => verbose = true,
+            "-o" | "--output" if name == "tts" => {
+                output = Some(rest.next().ok_or("-o needs a file name")?.clone());
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let [argument] = positional.as_slice() else {
+        return Err(format!("{} takes exactly one argument", name));
+    };
+    let argument = argument.clone();
+    let command = match name.as_str() {
+        "transcribe" => CliCommand::Transcribe { path: argument },
+        "tts" => CliCommand::Tts { text: argument, output },
+        _ => CliCommand::Chat { prompt: argument },
+    };
+    Ok(Some((command, verbose)))
+}
+
+/// Run a CLI command if the process arguments contain one
+///
+/// Returns the exit code, or None to start the app normally.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (command, verbose) = match parse_args(&args) {
+        Ok(Some(parsed)) => parsed,
+        Ok(None) => return None,
+        Err(e) => {
+            attach_console();
+            eprintln!("{}\n\n{}", e, USAGE);
+            return Some(2);
+        }
+    };
+    attach_console();
+
+    // Use the running app's daemon instead of starting a second one
+    if let Some(result) = crate::instance::forward(&args) {
+        return Some(finish(&result));
+    }
+
+    if let Err(e) = start_daemon(verbose) {
+        print_result(&serde_json::json!({ "success": false, "error": e }));
+        return Some(1);
+    }
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let result = execute(&command, &cwd).unwrap_or_else(|e| serde_json::json!({ "success": false, "error": e }));
+    cleanup_daemon();
+
+    Some(finish(&result))
+}
+
+/// Run CLI arguments forwarded from another process with this app's daemon (see instance)
+pub fn run_forwarded(args: &[String], cwd: &Path) -> serde_json::Value {
+    let result = match parse_args(args) {
+        Ok(Some((command, _))) => execute(&command, cwd),
+        Ok(None) => Err("Not a CLI command".to_string()),
+        Err(e) => Err(e),
+    };
+    result.unwrap_or_else(|e| serde_json::json!({ "success": false, "error": e }))
+}
+
+/// Print the result and return the exit code
+fn finish(result: &serde_json::Value) -> i32 {
+    print_result(result);
+    let success = result.get("success").and_then(|v| v.as_bool()).unwrap_or(false);
+    if success { 0 } else { 1 }
+}
+
+fn print_result(result: &serde_json::Value) {
+    println!("{}", serde_json::to_string_pretty(result).unwrap_or_else(|_| result.to_string()));
+}
+
+/// Spawn the daemon and wait until its models are loaded
+fn start_daemon(verbose: bool) -> Result<(), String> {
+    if !verbose {
+        eprintln!("Starting voice service...");
+    }
+    let daemon = PythonDaemon::new()?;
+    *DAEMON.lock().map_err(|e| format!("DAEMON lock poisoned: {}", e))? = Some(daemon);
+    DAEMON_READY.store(true, Ordering::SeqCst);
+
+    // Nobody reads PTT events here; drain stderr so the daemon never blocks on a full pipe
+    if let Some(mut stderr) = PTT_STDERR.lock().ok().and_then(|mut s| s.take()) {
+        tauri::async_runtime::spawn(async move {
+            let mut line = String::new();
+            while matches!(stderr.read_line(&mut line).await, Ok(n) if n > 0) {
+                if verbose {
+                    eprint!("{}", line);
+                }
+                line.clear();
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Run a command; relative file names are resolved against `cwd`
+fn execute(command: &CliCommand, cwd: &Path) -> Result<serde_json::Value, String> {
+    match command {
+        CliCommand::Transcribe { path } => {
+            let path = cwd.join(path);
+            let path = path.to_string_lossy();
+            // The daemon deletes the file it transcribes, so hand it a copy
+            let copy = std::env::temp_dir().join(format!("speekium_asr_{}.wav", uuid::Uuid::new_v4().simple()));
+            std::fs::copy(path.as_ref(), &copy).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            call_daemon("transcribe", serde_json::json!({ "audio_path": copy.to_string_lossy() }))
+        }
+        CliCommand::Tts { text, output } => {
+            let mut result = call_daemon("tts", serde_json::json!({ "text": text }))?;
+            let audio_path = result.get("audio_path").and_then(|v| v.as_str()).map(str::to_string);
+            if let (Some(output), Some(audio_path)) = (output, audio_path) {
+                let output = cwd.join(output);
+                let output = output.to_string_lossy();
+                // The file keeps the backend's format (e.g. MP3 for Edge TTS)
+                std::fs::copy(&audio_path, output.as_ref()).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+                let _ = std::fs::remove_file(&audio_path);
+                result["audio_path"] = serde_json::json!(output);
+            }
+            Ok(result)
+        }
+        CliCommand::Chat { prompt } => call_daemon("chat", serde_json::json!({ "text": prompt })),
+    }
+}
+
+/// Release builds on Windows have no console; reuse the terminal we were started from
+fn attach_console() {
+    #[cfg(target_os = "windows")]
+    unsafe {
+        use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
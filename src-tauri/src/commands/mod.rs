@@ -7,32 +7,51 @@
 // even with pub use re-exports.
 //
 // Commands are organized into logical sections below for maintainability:
-// - Recording Commands (9 commands)
-// - Chat Commands (4 commands)
-// - Config Commands (3 commands)
-// - Daemon Commands (2 commands)
+// - Recording Commands (12 commands)
+// - Chat Commands (5 commands)
+// - Config Commands (9 commands)
+// - Cloud Sync Commands (3 commands)
+// - Audio Device Commands (2 commands)
+// - Display Commands (4 commands)
+// - Window Commands (4 commands)
+// - Daemon Commands (5 commands)
 // ============================================================================
 
 use std::sync::MutexGuard;
-use tauri::Emitter;
+use tauri::Manager;
 use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
-use crate::types::{RecordingMode, WorkMode, AppStatus, RecordResult, ChatResult, TTSResult, ConfigResult, HealthResult, ModelStatusResult, DaemonStatusPayload};
+use crate::types::{AudioChunkPayload, RecordingMode, WorkMode, AppStatus, RecordResult, ChatResult, TTSResult, ConfigResult, HealthResult, ModelStatusResult, DaemonStatusPayload, DaemonResources, ShortcutValidationResult};
 use crate::daemon::{
     STREAMING_IN_PROGRESS, RECORDING_ABORTED, RECORDING_MODE, WORK_MODE,
-    APP_STATUS, DAEMON, CURRENT_PTT_SHORTCUT, APP_HANDLE, call_daemon,
+    APP_STATUS, LAST_TRANSCRIPT, DAEMON, ACTION_SHORTCUTS, CURRENT_PTT_SHORTCUT, APP_HANDLE, AUDIO_RECORDER, call_daemon, call_daemon_async, call_daemon_async_with, daemon_client,
 };
+use crate::audio;
+use crate::config;
 use crate::ui;
 use crate::shortcuts;
+use crate::daemon::client::PendingRequest;
+use crate::daemon::ipc::{Frame, FrameKind};
+use crate::state::AppState;
+use crate::chat_draft::DraftWriter;
+use crate::events::{emit_event, AppEvent};
+use crate::tts_voices::SpeechParams;
+use crate::daemon::request::{
+    cancel_active_requests, cancel_active_streams, default_timeout, register_stream, unregister_request, CancelToken,
+    RequestOptions, STREAM_IDLE_TIMEOUT,
+};
 use std::sync::atomic::Ordering;
-use std::io::{BufRead, Write};
 use std::path::Path;
 use std::sync::Mutex;
+use std::time::Duration;
 
 // ============================================================================
 // Error Handling Helpers
 // ============================================================================
 
+/// Conversation turns sent as context when `max_history` is not configured
+const DEFAULT_MAX_HISTORY_TURNS: i64 = 10;
+
 /// Helper function to acquire a mutex lock with proper error handling
 fn acquire_lock<'a, T>(lock: &'a Mutex<T>, context: &str) -> Result<MutexGuard<'a, T>, String> {
     lock.lock()
@@ -40,7 +59,7 @@ fn acquire_lock<'a, T>(lock: &'a Mutex<T>, context: &str) -> Result<MutexGuard<'
 }
 
 // ============================================================================
-// Recording Commands (9 commands)
+// Recording Commands (12 commands)
 // ============================================================================
 
 #[tauri::command]
@@ -56,6 +75,10 @@ pub async fn record_audio(app_handle: tauri::AppHandle, mode: String, duration:
             success: false,
             text: None,
             language: None,
+            confidence: None,
+            alternatives: Vec::new(),
+            words: Vec::new(),
+            segments: Vec::new(),
             error: Some("Recording blocked: streaming in progress".to_string()),
         });
     }
@@ -68,6 +91,10 @@ pub async fn record_audio(app_handle: tauri::AppHandle, mode: String, duration:
             success: false,
             text: None,
             language: None,
+            confidence: None,
+            alternatives: Vec::new(),
+            words: Vec::new(),
+            segments: Vec::new(),
             error: Some("Recording cancelled".to_string()),
         });
     }
@@ -82,6 +109,10 @@ pub async fn record_audio(app_handle: tauri::AppHandle, mode: String, duration:
             success: false,
             text: None,
             language: None,
+            confidence: None,
+            alternatives: Vec::new(),
+            words: Vec::new(),
+            segments: Vec::new(),
             error: Some("Recording mode changed".to_string()),
         });
     }
@@ -94,11 +125,29 @@ pub async fn record_audio(app_handle: tauri::AppHandle, mode: String, duration:
                 success: false,
                 text: None,
                 language: None,
+                confidence: None,
+                alternatives: Vec::new(),
+                words: Vec::new(),
+                segments: Vec::new(),
                 error: Some("Recording cancelled".to_string()),
             });
         }
     }
 
+    // Paused for Do Not Disturb or a meeting app; the frontend retries later
+    if is_continuous_mode && crate::focus_pause::is_paused() {
+        return Ok(RecordResult {
+            success: false,
+            text: None,
+            language: None,
+            confidence: None,
+            alternatives: Vec::new(),
+            words: Vec::new(),
+            segments: Vec::new(),
+            error: Some("Listening paused".to_string()),
+        });
+    }
+
     // Handle duration parameter: support numeric string, "auto", or empty
     let duration_val = match duration {
         Some(d) => {
@@ -113,24 +162,48 @@ pub async fn record_audio(app_handle: tauri::AppHandle, mode: String, duration:
 
     let args = serde_json::json!({
         "mode": mode,
-        "duration": duration_val
+        "duration": duration_val,
+        "hotwords": crate::vocabulary::request_hotwords(),
     });
 
     // Send recording start state to all windows (unified state sync)
+    let recording_status = if is_continuous_mode { AppStatus::Listening } else { AppStatus::Recording };
+    APP_STATUS.transition(recording_status, "record_audio");
     ui::emit_ptt_state(&app_handle, "recording");
 
-    let result = call_daemon("record", args);
+    // Continuous mode waits for speech for as long as the user is silent,
+    // fixed-duration recording only needs time for the recording plus ASR
+    let options = if is_continuous_mode {
+        RequestOptions::for_command("record").with_timeout(None)
+    } else {
+        let limit = Duration::from_secs_f32(duration_val) + default_timeout("ptt_audio");
+        RequestOptions::for_command("record").with_timeout(Some(limit))
+    };
+    let result = call_daemon_async_with("record", args, options).await;
 
     // Send processing state
+    APP_STATUS.transition(AppStatus::AsrProcessing, "record_audio");
     ui::emit_ptt_state(&app_handle, "processing");
 
     // Handle result
     let parsed_result = result.and_then(|r| {
-        serde_json::from_value(r)
+        serde_json::from_value::<RecordResult>(r)
             .map_err(|e| format!("Failed to parse result: {}", e))
+    }).map(|mut record| {
+        // Apply the user's replacements before the transcript is typed or chatted
+        record.text = record.text.map(|text| crate::textproc::process_transcript(&app_handle, &text));
+        if let Some(text) = &record.text {
+            record.alternatives = crate::asr_alternatives::process_alternatives(&app_handle, text, &record.alternatives);
+            crate::asr_alternatives::set_pending_alternatives(text, record.confidence, record.alternatives.clone());
+            crate::word_timings::set_pending_timings(text, record.words.clone());
+            record.segments = crate::diarization::process_segments(&app_handle, std::mem::take(&mut record.segments));
+            crate::diarization::set_pending_segments(text, record.segments.clone());
+        }
+        record
     });
 
     // Send idle state
+    APP_STATUS.transition(AppStatus::Idle, "record_audio");
     ui::emit_ptt_state(&app_handle, "idle");
 
     parsed_result
@@ -141,6 +214,8 @@ pub fn set_recording_mode(mode: String) -> Result<(), String> {
     let new_mode = RecordingMode::from_str(mode.as_str())
         .ok_or_else(|| format!("Invalid recording mode: {}", mode))?;
 
+    // Fails if the system audio cannot be captured; the mode stays unchanged then
+    crate::meeting::apply_recording_mode(new_mode)?;
     *acquire_lock(&RECORDING_MODE, "update_recording_mode")? = new_mode;
 
     if new_mode == RecordingMode::Continuous {
@@ -155,6 +230,10 @@ pub fn set_recording_mode(mode: String) -> Result<(), String> {
         }
     }
 
+    if let Some(handle) = APP_HANDLE.get() {
+        let _ = crate::ui::update_tray_menu(handle);
+    }
+
     Ok(())
 }
 
@@ -172,27 +251,34 @@ pub fn set_work_mode(mode: String) -> Result<(), String> {
     let _old_mode = *acquire_lock(&WORK_MODE, "set_work_mode")?;
     *acquire_lock(&WORK_MODE, "update_work_mode")? = new_mode;
 
+    if let Some(handle) = APP_HANDLE.get() {
+        let _ = crate::ui::update_tray_menu(handle);
+    }
+
     Ok(())
 }
 
 #[tauri::command]
 pub fn get_recording_mode() -> Result<String, String> {
     let mode = *acquire_lock(&RECORDING_MODE, "get_recording_mode")?;
-    Ok(match mode {
-        RecordingMode::PushToTalk => "push-to-talk".to_string(),
-        RecordingMode::Continuous => "continuous".to_string(),
-    })
+    Ok(mode.as_str().to_string())
 }
 
 #[tauri::command]
 pub fn get_app_status() -> Result<String, String> {
-    let status = *acquire_lock(&APP_STATUS, "get_app_status")?;
-    Ok(status.as_str().to_string())
+    Ok(APP_STATUS.current().as_str().to_string())
+}
+
+/// Most recent ASR result (None before the first transcription)
+#[tauri::command]
+pub fn get_last_transcription() -> Result<Option<String>, String> {
+    let transcript = acquire_lock(&LAST_TRANSCRIPT, "get_last_transcription")?;
+    Ok(transcript.clone())
 }
 
 #[tauri::command]
 pub fn interrupt_operation(priority: u8) -> Result<String, String> {
-    let current_status = *acquire_lock(&APP_STATUS, "interrupt_recording")?;
+    let current_status = APP_STATUS.current();
 
     if current_status.can_be_interrupted(priority) {
         match current_status {
@@ -201,16 +287,19 @@ pub fn interrupt_operation(priority: u8) -> Result<String, String> {
             }
             AppStatus::Listening => {}
             AppStatus::LlmProcessing | AppStatus::TtsProcessing | AppStatus::Playing => {
+                // Stops generation and the daemon's audio playback
                 match call_daemon("interrupt", serde_json::json!({"priority": priority})) {
                     Ok(_) => {}
                     Err(_e) => {}
                 }
+                // Stop the stream readers from emitting the chunks still in flight
+                cancel_active_streams();
             }
             _ => {}
         }
 
         if priority <= 2 {
-            *acquire_lock(&APP_STATUS, "interrupt_recording")? = AppStatus::Idle;
+            APP_STATUS.transition(AppStatus::Idle, "interrupt");
         }
 
         Ok(format!("Interrupted: {}", current_status.as_str()))
@@ -223,13 +312,30 @@ pub fn interrupt_operation(priority: u8) -> Result<String, String> {
     }
 }
 
+/// Cancel the current pipeline from the PTT overlay's interrupt button
+#[tauri::command]
+pub fn overlay_interrupt(app: tauri::AppHandle) -> Result<String, String> {
+    // PTT recordings run in Rust: discard the audio instead of submitting it
+    if shortcuts::cancel_ptt_recording(&app) {
+        APP_STATUS.transition(AppStatus::Idle, "overlay_interrupt");
+        return Ok("Interrupted: recording".to_string());
+    }
+
+    interrupt_operation(2)
+}
+
+/// Abandon all daemon requests currently waiting for a response
+///
+/// Returns false if no request was in flight.
+#[tauri::command]
+pub fn cancel_daemon_request() -> bool {
+    cancel_active_requests() > 0
+}
+
 #[tauri::command]
 pub fn update_recording_mode(mode: String) -> Result<(), String> {
-    let current_mode = match mode.as_str() {
-        "push-to-talk" => Ok(RecordingMode::PushToTalk),
-        "continuous" => Ok(RecordingMode::Continuous),
-        _ => Err(format!("Invalid recording mode: {}", mode)),
-    }?;
+    let current_mode = RecordingMode::from_str(mode.as_str())
+        .ok_or_else(|| format!("Invalid recording mode: {}", mode))?;
 
     if let Err(_e) = shortcuts::write_recording_mode_to_config(&mode) {}
 
@@ -241,10 +347,7 @@ pub fn update_recording_mode(mode: String) -> Result<(), String> {
         }
     }
 
-    let is_recording = {
-        let status = acquire_lock(&APP_STATUS, "update_recording_mode")?;
-        matches!(*status, AppStatus::Recording | AppStatus::Listening)
-    };
+    let is_recording = matches!(APP_STATUS.current(), AppStatus::Recording | AppStatus::Listening);
 
     if !is_recording {
         if let Some(handle) = APP_HANDLE.get() {
@@ -255,7 +358,7 @@ pub fn update_recording_mode(mode: String) -> Result<(), String> {
                         shortcuts::register_ptt_from_config(&handle_clone);
                     });
                 }
-                RecordingMode::Continuous => {
+                RecordingMode::Continuous | RecordingMode::Meeting => {
                     let mut current = acquire_lock(&CURRENT_PTT_SHORTCUT, "update_recording_mode")?;
                     if let Some(ref shortcut_str) = *current {
                         if let Ok(shortcut) = shortcut_str.parse::<tauri_plugin_global_shortcut::Shortcut>() {
@@ -272,94 +375,217 @@ pub fn update_recording_mode(mode: String) -> Result<(), String> {
 }
 
 // ============================================================================
-// Chat Commands (4 commands)
+// Chat Commands (5 commands)
 // ============================================================================
 
 #[tauri::command]
-pub async fn chat_llm(text: String) -> Result<ChatResult, String> {
-    let args = serde_json::json!({ "text": text });
+pub async fn chat_llm(app: tauri::AppHandle, text: String) -> Result<ChatResult, String> {
+    if crate::llm::native_enabled() {
+        return Ok(crate::llm::chat(&app, &text).await);
+    }
 
-    let result = call_daemon("chat", args)?;
+    let args = crate::personas::with_persona(&app, serde_json::json!({ "text": text }));
+
+    let result = call_daemon_async("chat", args).await?;
 
     serde_json::from_value(result)
         .map_err(|e| format!("Failed to parse result: {}", e))
 }
 
+/// Chat within a session: prior messages are sent as context, and both the
+/// user message and the reply are saved to the session
+///
+/// After the first exchange the session is retitled automatically.
+#[tauri::command]
+pub async fn chat_llm_with_history(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    session_id: String,
+    text: String,
+) -> Result<ChatResult, String> {
+    let max_turns = config::read_config_value("max_history")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(DEFAULT_MAX_HISTORY_TURNS);
+    // Each turn is a user message plus an assistant reply
+    let limit = (max_turns.max(0) * 2) as i32;
+
+    let history: Vec<serde_json::Value> = state.db.get_recent_messages(&session_id, limit)?
+        .into_iter()
+        .map(|m| serde_json::json!({ "role": m.role, "content": m.content }))
+        .collect();
+
+    let chat_result = if crate::llm::native_enabled() {
+        crate::llm::chat_with_history(&app, &text, &history).await
+    } else {
+        let args = crate::personas::with_persona(&app, serde_json::json!({
+            "text": text,
+            "history": history,
+        }));
+        let result = call_daemon_async("chat", args).await?;
+
+        serde_json::from_value(result)
+            .map_err(|e| format!("Failed to parse result: {}", e))?
+    };
+
+    // Only a completed exchange is saved, so a failed request leaves no orphan message
+    if let (true, Some(content)) = (chat_result.success, chat_result.content.as_deref()) {
+        state.db.add_message(&session_id, "user", &text)?;
+        state.db.add_message(&session_id, "assistant", content)?;
+
+        // First exchange of the session: replace the placeholder title
+        if history.is_empty() {
+            crate::db_commands::spawn_auto_title(app, session_id);
+        }
+    }
+
+    Ok(chat_result)
+}
+
+/// Mark a streaming chat as started
+fn start_stream(source: &str) {
+    STREAMING_IN_PROGRESS.store(true, Ordering::SeqCst);
+    APP_STATUS.transition(AppStatus::LlmProcessing, source);
+}
+
+/// Mark a streaming chat as finished (done, failed or abandoned)
+fn end_stream(source: &str) {
+    STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+    APP_STATUS.transition(AppStatus::Idle, source);
+}
+
+/// How long a cancelled stream's leftover daemon output is drained
+const STREAM_CANCEL_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Next frame of a streaming request, or None once `cancel` fires
+async fn next_stream_frame(request: &mut PendingRequest, cancel: &CancelToken) -> Option<Result<Option<Frame>, String>> {
+    tokio::select! {
+        received = request.recv_timeout(STREAM_IDLE_TIMEOUT) => Some(received),
+        _ = cancel.cancelled() => None,
+    }
+}
+
+/// Abandon an interrupted stream: drain its leftover output and tell the UI to stop rendering it
+fn cancel_stream(window: &tauri::Window, request: PendingRequest, source: &str) {
+    request.drain(STREAM_CANCEL_DRAIN_TIMEOUT);
+    emit_event(window.app_handle(), AppEvent::StreamCancelled(source.to_string()));
+    end_stream(source);
+}
+
+/// chat_llm_stream without the daemon (`llm_backend: "native"`), with the same events
+async fn native_chat_stream(window: &tauri::Window, text: &str, mut draft: DraftWriter) {
+    let cancel = CancelToken::new();
+    let key = register_stream(cancel.clone());
+
+    let result = crate::llm::chat_stream(window.app_handle(), text, &cancel, |chunk| {
+        draft.push(chunk);
+        emit_event(window.app_handle(), AppEvent::ChatChunk(chunk.to_string()));
+    })
+    .await;
+    unregister_request(key);
+
+    match result {
+        Ok(crate::llm::StreamOutcome::Done(_)) => {
+            emit_event(window.app_handle(), AppEvent::ChatDone(draft.finish()));
+        }
+        Ok(crate::llm::StreamOutcome::Cancelled(_)) => {
+            draft.interrupt();
+            emit_event(window.app_handle(), AppEvent::StreamCancelled("chat_stream".to_string()));
+        }
+        Err(e) => {
+            draft.interrupt();
+            emit_event(window.app_handle(), AppEvent::ChatError(e));
+        }
+    }
+    end_stream("chat_stream");
+}
+
+/// Stream a reply as `chat-chunk` events, ending with `chat-done`, `chat-error` or `stream-cancelled`
+///
+/// With a session, the reply is saved to it as it arrives (see chat_draft) and
+/// `chat-done` carries the id of the saved message.
 #[tauri::command]
 pub async fn chat_llm_stream(
     window: tauri::Window,
-    text: String
+    text: String,
+    session_id: Option<String>,
 ) -> Result<(), String> {
-    STREAMING_IN_PROGRESS.store(true, Ordering::SeqCst);
+    start_stream("chat_stream");
 
-    std::thread::spawn(move || {
-        let daemon_lock = DAEMON.lock();
-        let mut daemon = match daemon_lock {
-            Ok(d) => d,
+    tauri::async_runtime::spawn(async move {
+        let mut draft = DraftWriter::new(window.app_handle(), session_id);
+        if crate::llm::native_enabled() {
+            native_chat_stream(&window, &text, draft).await;
+            return;
+        }
+
+        let client = match daemon_client() {
+            Ok(c) => c,
             Err(e) => {
-                let _ = window.emit("chat-error", format!("DAEMON lock poisoned: {}", e));
-                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                emit_event(window.app_handle(), AppEvent::ChatError(e));
+                end_stream("chat_stream");
                 return;
             }
         };
-        let daemon = match daemon.as_mut() {
-            Some(d) => d,
-            None => {
-                let _ = window.emit("chat-error", "Daemon not available");
-                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+
+        let args = crate::personas::with_persona(window.app_handle(), serde_json::json!({"text": text}));
+        let mut request = match client.send_request("chat_stream", args) {
+            Ok(r) => r,
+            Err(e) => {
+                emit_event(window.app_handle(), AppEvent::ChatError(format!("Write error: {}", e)));
+                end_stream("chat_stream");
                 return;
             }
         };
 
-        let request = serde_json::json!({
-            "command": "chat_stream",
-            "args": {"text": text}
-        });
-
-        if let Err(e) = writeln!(daemon.stdin, "{}", request.to_string()) {
-            let _ = window.emit("chat-error", format!("Write error: {}", e));
-            STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
-            return;
-        }
-
-        if let Err(e) = daemon.stdin.flush() {
-            let _ = window.emit("chat-error", format!("Flush error: {}", e));
-            STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
-            return;
-        }
+        // Cancelled by interrupt_operation (or shutdown)
+        let cancel = CancelToken::new();
+        let key = register_stream(cancel.clone());
 
         loop {
-            let mut line = String::new();
-            match daemon.stdout.read_line(&mut line) {
-                Ok(0) => {
-                    let _ = window.emit("chat-error", "Daemon connection lost");
-                    STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+            let Some(received) = next_stream_frame(&mut request, &cancel).await else {
+                draft.interrupt();
+                cancel_stream(&window, request, "chat_stream");
+                break;
+            };
+            match received {
+                Ok(None) => {
+                    draft.interrupt();
+                    emit_event(window.app_handle(), AppEvent::ChatError("Daemon stopped responding".to_string()));
+                    end_stream("chat_stream");
                     break;
                 }
-                Ok(_) => {
-                    if let Ok(chunk) = serde_json::from_str::<serde_json::Value>(&line) {
-                        if chunk.get("event").is_some() {
-                            continue;
-                        }
-
+                // The final response only arrives on its own if the stream ended abnormally
+                Ok(Some(frame)) if frame.kind == FrameKind::Response => {
+                    draft.interrupt();
+                    let error = frame.payload.get("error").and_then(|v| v.as_str())
+                        .unwrap_or("Stream ended unexpectedly");
+                    emit_event(window.app_handle(), AppEvent::ChatError(error.to_string()));
+                    end_stream("chat_stream");
+                    break;
+                }
+                Ok(Some(frame)) => {
+                    if frame.kind == FrameKind::Stream {
+                        let chunk = frame.payload;
                         let chunk_type = chunk.get("type").and_then(|v| v.as_str()).unwrap_or("");
 
                         match chunk_type {
                             "chunk" => {
                                 if let Some(content) = chunk.get("content").and_then(|v| v.as_str()) {
-                                    let _ = window.emit("chat-chunk", content);
+                                    draft.push(content);
+                                    emit_event(window.app_handle(), AppEvent::ChatChunk(content.to_string()));
                                 }
                             }
                             "done" => {
-                                let _ = window.emit("chat-done", ());
-                                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                                emit_event(window.app_handle(), AppEvent::ChatDone(draft.finish()));
+                                end_stream("chat_stream");
                                 break;
                             }
                             "error" => {
+                                draft.interrupt();
                                 if let Some(error) = chunk.get("error").and_then(|v| v.as_str()) {
-                                    let _ = window.emit("chat-error", error);
+                                    emit_event(window.app_handle(), AppEvent::ChatError(error.to_string()));
                                 }
-                                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                                end_stream("chat_stream");
                                 break;
                             }
                             _ => {}
@@ -367,12 +593,14 @@ pub async fn chat_llm_stream(
                     }
                 }
                 Err(e) => {
-                    let _ = window.emit("chat-error", format!("Read error: {}", e));
-                    STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                    draft.interrupt();
+                    emit_event(window.app_handle(), AppEvent::ChatError(format!("Read error: {}", e)));
+                    end_stream("chat_stream");
                     break;
                 }
             }
         }
+        unregister_request(key);
     });
 
     Ok(())
@@ -382,90 +610,92 @@ pub async fn chat_llm_stream(
 pub async fn chat_tts_stream(
     window: tauri::Window,
     text: String,
-    auto_play: Option<bool>
+    auto_play: Option<bool>,
+    params: Option<SpeechParams>,
 ) -> Result<(), String> {
-    STREAMING_IN_PROGRESS.store(true, Ordering::SeqCst);
+    let speech = crate::tts_voices::speech_value(params.as_ref())?;
 
-    std::thread::spawn(move || {
-        let daemon_lock = DAEMON.lock();
-        let mut daemon = match daemon_lock {
-            Ok(d) => d,
+    start_stream("chat_tts_stream");
+
+    tauri::async_runtime::spawn(async move {
+        let client = match daemon_client() {
+            Ok(c) => c,
             Err(e) => {
-                let _ = window.emit("chat-error", format!("DAEMON lock poisoned: {}", e));
-                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                emit_event(window.app_handle(), AppEvent::TtsError(e));
+                end_stream("chat_tts_stream");
                 return;
             }
         };
-        let daemon = match daemon.as_mut() {
-            Some(d) => d,
-            None => {
-                let _ = window.emit("tts-error", "Daemon not available");
-                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+
+        let mut args = crate::personas::with_persona(window.app_handle(), serde_json::json!({
+            "text": text,
+            "auto_play": auto_play.unwrap_or(true)
+        }));
+        crate::tts_voices::insert_speech(&mut args, speech);
+
+        let mut request = match client.send_request("chat_tts_stream", args) {
+            Ok(r) => r,
+            Err(e) => {
+                emit_event(window.app_handle(), AppEvent::TtsError(format!("Write error: {}", e)));
+                end_stream("chat_tts_stream");
                 return;
             }
         };
 
-        let request = serde_json::json!({
-            "command": "chat_tts_stream",
-            "args": {
-                "text": text.clone(),
-                "auto_play": auto_play.unwrap_or(true)
-            }
-        });
-
-        if let Err(e) = writeln!(daemon.stdin, "{}", request.to_string()) {
-            let _ = window.emit("tts-error", format!("Write error: {}", e));
-            STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
-            return;
-        }
-
-        if let Err(e) = daemon.stdin.flush() {
-            let _ = window.emit("tts-error", format!("Flush error: {}", e));
-            STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
-            return;
-        }
+        // Cancelled by interrupt_operation (or shutdown)
+        let cancel = CancelToken::new();
+        let key = register_stream(cancel.clone());
 
         loop {
-            let mut line = String::new();
-            match daemon.stdout.read_line(&mut line) {
-                Ok(0) => {
-                    let _ = window.emit("tts-error", "Daemon connection lost");
-                    STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+            let Some(received) = next_stream_frame(&mut request, &cancel).await else {
+                cancel_stream(&window, request, "chat_tts_stream");
+                break;
+            };
+            match received {
+                Ok(None) => {
+                    emit_event(window.app_handle(), AppEvent::TtsError("Daemon stopped responding".to_string()));
+                    end_stream("chat_tts_stream");
                     break;
                 }
-                Ok(_n) => {
-                    if let Ok(chunk) = serde_json::from_str::<serde_json::Value>(&line) {
-                        if chunk.get("event").is_some() {
-                            continue;
-                        }
-
+                // The final response only arrives on its own if the stream ended abnormally
+                Ok(Some(frame)) if frame.kind == FrameKind::Response => {
+                    let error = frame.payload.get("error").and_then(|v| v.as_str())
+                        .unwrap_or("Stream ended unexpectedly");
+                    emit_event(window.app_handle(), AppEvent::TtsError(error.to_string()));
+                    end_stream("chat_tts_stream");
+                    break;
+                }
+                Ok(Some(frame)) => {
+                    if frame.kind == FrameKind::Stream {
+                        let chunk = frame.payload;
                         let chunk_type = chunk.get("type").and_then(|v| v.as_str()).unwrap_or("");
 
                         match chunk_type {
                             "text_chunk" => {
                                 if let Some(content) = chunk.get("content").and_then(|v| v.as_str()) {
-                                    let _ = window.emit("tts-text-chunk", content);
+                                    emit_event(window.app_handle(), AppEvent::TtsTextChunk(content.to_string()));
                                 }
                             }
                             "audio_chunk" => {
+                                APP_STATUS.transition(AppStatus::TtsProcessing, "chat_tts_stream");
                                 if let Some(audio_path) = chunk.get("audio_path").and_then(|v| v.as_str()) {
                                     let text = chunk.get("text").and_then(|v| v.as_str()).unwrap_or("");
-                                    let _ = window.emit("tts-audio-chunk", serde_json::json!({
-                                        "audio_path": audio_path,
-                                        "text": text
+                                    emit_event(window.app_handle(), AppEvent::TtsAudioChunk(AudioChunkPayload {
+                                        audio_path: audio_path.to_string(),
+                                        text: text.to_string(),
                                     }));
                                 }
                             }
                             "done" => {
-                                let _ = window.emit("tts-done", ());
-                                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                                emit_event(window.app_handle(), AppEvent::TtsDone);
+                                end_stream("chat_tts_stream");
                                 break;
                             }
                             "error" => {
                                 if let Some(error) = chunk.get("error").and_then(|v| v.as_str()) {
-                                    let _ = window.emit("tts-error", error);
+                                    emit_event(window.app_handle(), AppEvent::TtsError(error.to_string()));
                                 }
-                                STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                                end_stream("chat_tts_stream");
                                 break;
                             }
                             _ => {}
@@ -473,58 +703,193 @@ pub async fn chat_tts_stream(
                     }
                 }
                 Err(e) => {
-                    let _ = window.emit("tts-error", format!("Read error: {}", e));
-                    STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+                    emit_event(window.app_handle(), AppEvent::TtsError(format!("Read error: {}", e)));
+                    end_stream("chat_tts_stream");
                     break;
                 }
             }
         }
+        unregister_request(key);
     });
 
     Ok(())
 }
 
 #[tauri::command]
-pub async fn generate_tts(text: String) -> Result<TTSResult, String> {
-    let args = serde_json::json!({ "text": text });
+pub async fn generate_tts(
+    app: tauri::AppHandle,
+    text: String,
+    params: Option<SpeechParams>,
+) -> Result<TTSResult, String> {
+    // Audio with per-request overrides is neither looked up nor stored in the cache
+    let speech = crate::tts_voices::speech_value(params.as_ref())?;
+    let overridden = speech.is_some();
+
+    // Repeated phrases are served from the cache without calling the daemon
+    if !overridden {
+        if let Some(audio_path) = crate::tts_cache::lookup(&app, &text) {
+            return Ok(TTSResult {
+                success: true,
+                audio_path: Some(audio_path),
+                error: None,
+            });
+        }
+    }
 
-    let result = call_daemon("tts", args)?;
+    let mut args = crate::personas::with_persona(&app, serde_json::json!({ "text": text }));
+    crate::tts_voices::insert_speech(&mut args, speech);
+    let result = call_daemon_async("tts", args).await?;
 
-    serde_json::from_value(result)
-        .map_err(|e| format!("Failed to parse result: {}", e))
+    let mut tts: TTSResult = serde_json::from_value(result)
+        .map_err(|e| format!("Failed to parse result: {}", e))?;
+    if tts.success && !overridden {
+        tts.audio_path = tts.audio_path.map(|path| crate::tts_cache::store(&app, &text, &path));
+    }
+    Ok(tts)
 }
 
 // ============================================================================
-// Config Commands (3 commands)
+// Config Commands (9 commands)
 // ============================================================================
 
 #[tauri::command]
-pub async fn load_config() -> Result<ConfigResult, String> {
-    let result = call_daemon("config", serde_json::json!({}))?;
+pub fn load_config() -> Result<ConfigResult, String> {
+    // Read straight from config.json so settings work while the daemon is loading
+    let mut config = config::load_config_with_defaults();
+
+    // Report a corrupt config instead of silently using it
+    let invalid_fields = config::Config::from_value(&config).err();
+    if let Some(ref errors) = invalid_fields {
+        eprintln!("⚠️ {}", config::describe_errors(errors));
+    }
 
-    serde_json::from_value(result)
-        .map_err(|e| format!("Failed to parse result: {}", e))
+    // The settings UI edits API keys alongside the rest of the provider
+    crate::secrets::fill_api_keys(&mut config);
+
+    Ok(ConfigResult {
+        success: true,
+        config: Some(config),
+        error: None,
+        invalid_fields,
+    })
 }
 
 #[tauri::command]
-pub async fn save_config(config: serde_json::Value) -> Result<serde_json::Value, String> {
-    call_daemon("save_config", config)
+pub fn save_config(mut config: serde_json::Value) -> Result<serde_json::Value, String> {
+    if let Err(errors) = config::Config::from_value(&config) {
+        return Ok(serde_json::json!({
+            "success": false,
+            "error": config::describe_errors(&errors),
+            "invalid_fields": errors,
+        }));
+    }
+
+    // API keys go to the keychain, not config.json
+    crate::secrets::take_api_keys(&mut config);
+
+    let updates = config.as_object()
+        .ok_or_else(|| "Config must be a JSON object".to_string())?;
+    if let Err(e) = config::update_config(updates) {
+        return Ok(serde_json::json!({"success": false, "error": e}));
+    }
+
+    // offline_mode may have changed
+    crate::network::refresh();
+
+    // Apply the change in the daemon (deferred until it is ready)
+    config::notify_daemon_config_changed();
+    Ok(serde_json::json!({"success": true}))
 }
 
 #[tauri::command]
 pub async fn update_hotkey(hotkey_config: serde_json::Value) -> Result<serde_json::Value, String> {
     let _display_name = hotkey_config.get("displayName").and_then(|v| v.as_str()).unwrap_or("unknown");
 
-    let result = call_daemon("update_hotkey", hotkey_config.clone())?;
+    // Refuse a PTT hotkey that is already bound to another action
+    if let Some(shortcut_str) = shortcuts::hotkey_config_to_shortcut_string(&hotkey_config) {
+        if let Some(owner) = shortcuts::find_shortcut_conflict(&shortcut_str, Some(shortcuts::PTT_SHORTCUT_OWNER))? {
+            return Err(format!("Shortcut {} is already used by {}", shortcut_str, owner));
+        }
+    }
 
+    // The hotkey itself is saved with save_config; only registration happens here,
+    // so this works while the daemon is still loading
     if let Some(shortcut_str) = shortcuts::hotkey_config_to_shortcut_string(&hotkey_config) {
         if let Some(app_handle) = APP_HANDLE.get() {
             if let Err(_e) = shortcuts::register_ptt_shortcut(app_handle, &shortcut_str) {}
         }
     }
 
-    serde_json::from_value(result)
-        .map_err(|e| format!("Failed to parse result: {}", e))
+    Ok(serde_json::json!({"success": true}))
+}
+
+/// Remap a global action shortcut (toggle_window, toggle_work_mode, toggle_recording_mode, paste_transcript)
+///
+/// Returns the registered shortcut string.
+#[tauri::command]
+pub fn update_shortcut(action: String, hotkey_config: serde_json::Value) -> Result<String, String> {
+    let action = shortcuts::ShortcutAction::from_str(&action)
+        .ok_or_else(|| format!("Unknown shortcut action: {}", action))?;
+    let app_handle = APP_HANDLE.get()
+        .ok_or_else(|| "App not initialized".to_string())?;
+
+    shortcuts::update_action_shortcut(app_handle, action, &hotkey_config)
+}
+
+/// Select the PTT trigger: "shortcut" | "mouse_button" | "fn_key"
+///
+/// The mouse button and Fn key are only available on macOS. Falls back to
+/// the keyboard shortcut if the input listener cannot start (missing
+/// permission).
+#[tauri::command]
+pub async fn set_ptt_trigger(trigger: String, mouse_button: Option<i64>) -> Result<(), String> {
+    let parsed = crate::types::PttTrigger::from_str(&trigger, mouse_button.unwrap_or_default())
+        .ok_or_else(|| format!("Invalid PTT trigger: {}", trigger))?;
+    if parsed != crate::types::PttTrigger::Shortcut && !crate::platform::INPUT_LISTENER_SUPPORTED {
+        return Err("Mouse button / Fn key PTT is not supported on this platform".to_string());
+    }
+
+    config::write_config_value(shortcuts::PTT_TRIGGER_KEY, serde_json::json!(trigger))?;
+    if let Some(button) = mouse_button {
+        config::write_config_value(shortcuts::PTT_MOUSE_BUTTON_KEY, serde_json::json!(button))?;
+    }
+
+    let app_handle = APP_HANDLE.get()
+        .ok_or_else(|| "App not initialized".to_string())?;
+    shortcuts::register_ptt_from_config(app_handle);
+
+    Ok(())
+}
+
+/// Dry-run a shortcut before saving it (parse, in-app conflicts, OS registration)
+///
+/// `owner` is the action name or "push_to_talk" being edited.
+#[tauri::command]
+pub fn validate_shortcut(shortcut_str: String, owner: Option<String>) -> Result<ShortcutValidationResult, String> {
+    let app_handle = APP_HANDLE.get()
+        .ok_or_else(|| "App not initialized".to_string())?;
+
+    Ok(shortcuts::validate_shortcut(app_handle, &shortcut_str, owner.as_deref()))
+}
+
+/// Currently registered action shortcuts (action name -> shortcut string)
+#[tauri::command]
+pub fn get_shortcuts() -> Result<std::collections::BTreeMap<String, String>, String> {
+    Ok(acquire_lock(&ACTION_SHORTCUTS, "get_shortcuts")?.clone())
+}
+
+#[tauri::command]
+pub fn get_app_language() -> String {
+    crate::ui::get_language_from_config()
+}
+
+#[tauri::command]
+pub fn set_app_language(language: String, app: tauri::AppHandle) -> Result<(), String> {
+    crate::ui::write_language_to_config(&language)
+        .map_err(|e| format!("Failed to write language to config: {}", e))?;
+    // Update tray menu with new language
+    crate::ui::update_tray_menu(&app)
+        .map_err(|e| format!("Failed to update tray menu: {}", e))
 }
 
 // ============================================================================
@@ -614,47 +979,165 @@ print(get_dropbox_auth_url('speekium://oauth/callback'))
 }
 
 // ============================================================================
-// Daemon Commands (2 commands)
+// Audio Device Commands (2 commands)
 // ============================================================================
 
 #[tauri::command]
-pub async fn get_daemon_state() -> Result<serde_json::Value, String> {
-    let result = call_daemon("get_daemon_state", serde_json::json!({}))?;
+pub fn list_audio_input_devices() -> Result<Vec<audio::AudioInputDevice>, String> {
+    audio::list_input_devices()
+}
 
-    serde_json::from_value(result)
-        .map_err(|e| format!("Failed to parse result: {}", e))
+/// Select the input device used for PTT recording (None = system default)
+#[tauri::command]
+pub fn set_audio_input_device(device_id: Option<String>) -> Result<(), String> {
+    config::write_config_value(
+        audio::AUDIO_INPUT_DEVICE_KEY,
+        serde_json::json!(device_id),
+    )?;
+
+    let mut recorder_guard = acquire_lock(&AUDIO_RECORDER, "set_audio_input_device")?;
+    if let Some(ref mut recorder) = *recorder_guard {
+        recorder.set_device(device_id);
+    }
+
+    Ok(())
 }
 
+// ============================================================================
+// Display Commands (4 commands)
+// ============================================================================
+
 #[tauri::command]
-pub async fn daemon_health(app: tauri::AppHandle) -> Result<HealthResult, String> {
-    if STREAMING_IN_PROGRESS.load(Ordering::SeqCst) {
-        return Ok(HealthResult {
-            success: true,
-            status: Some("streaming".to_string()),
-            command_count: None,
-            models_loaded: None,
-            error: None,
-        });
+pub fn list_displays(app: tauri::AppHandle) -> Result<Vec<ui::DisplayInfo>, String> {
+    ui::list_displays(&app)
+}
+
+/// Pin the PTT overlay to a display by name (None = display under the cursor)
+#[tauri::command]
+pub fn set_overlay_display(display_name: Option<String>) -> Result<(), String> {
+    config::write_config_value(ui::OVERLAY_DISPLAY_KEY, serde_json::json!(display_name))
+}
+
+/// Resize the PTT overlay to fit its content (logical pixels)
+#[tauri::command]
+pub fn resize_overlay(app: tauri::AppHandle, width: f64, height: f64) -> Result<(), String> {
+    ui::resize_overlay(&app, width, height)
+}
+
+/// Move the PTT overlay back to its default place (forgets the dragged position)
+#[tauri::command]
+pub fn reset_overlay_position(app: tauri::AppHandle) -> Result<(), String> {
+    ui::reset_overlay_position(&app)
+}
+
+// ============================================================================
+// Window Commands (4 commands)
+// ============================================================================
+
+/// Show the standalone history window (created on first use)
+#[tauri::command]
+pub fn open_history_window(app: tauri::AppHandle) -> Result<(), String> {
+    ui::show_history_window(&app)
+}
+
+/// Hide the history window (it keeps its state for the next open)
+#[tauri::command]
+pub fn close_history_window(app: tauri::AppHandle) {
+    ui::hide_history_window(&app);
+}
+
+/// Show the always-on-top pinned answers window (created on first use)
+#[tauri::command]
+pub fn open_pinned_window(app: tauri::AppHandle) -> Result<(), String> {
+    ui::show_pinned_window(&app)
+}
+
+/// Hide the pinned answers window
+#[tauri::command]
+pub fn close_pinned_window(app: tauri::AppHandle) {
+    ui::hide_pinned_window(&app);
+}
+
+// ============================================================================
+// Daemon Commands (5 commands)
+// ============================================================================
+
+/// Number of error log entries included in get_daemon_state
+const DIAGNOSTICS_RECENT_ERRORS: usize = 5;
+
+/// Daemon state plus the app's own state under `app`, for the diagnostics panel
+///
+/// Works while the daemon is not ready (`ready: false`, app state only).
+#[tauri::command]
+pub async fn get_daemon_state(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
+    let ready = crate::daemon::is_daemon_ready();
+    let mut result = if ready {
+        call_daemon_async("get_daemon_state", serde_json::json!({})).await?
+    } else {
+        serde_json::json!({ "success": true })
+    };
+
+    let client = daemon_client().ok();
+    let audio_device = AUDIO_RECORDER
+        .lock()
+        .ok()
+        .and_then(|recorder| recorder.as_ref().and_then(|r| r.device_id().map(str::to_string)));
+    let app_state = serde_json::json!({
+        "status": APP_STATUS.current().as_str(),
+        "recording_mode": RECORDING_MODE.lock().map(|m| m.as_str()).unwrap_or("unknown"),
+        "work_mode": WORK_MODE.lock().map(|m| m.as_str()).unwrap_or("unknown"),
+        "shortcuts": {
+            "push_to_talk": CURRENT_PTT_SHORTCUT.lock().ok().and_then(|s| s.clone()),
+            "actions": ACTION_SHORTCUTS.lock().map(|s| s.clone()).unwrap_or_default(),
+        },
+        // null = system default
+        "audio_device": audio_device,
+        "overlay_visible": app
+            .get_webview_window("ptt-overlay")
+            .and_then(|overlay| overlay.is_visible().ok())
+            .unwrap_or(false),
+        "command_queue": {
+            "length": client.as_ref().map_or(0, |c| c.queue_len()),
+            "in_flight": client.as_ref().map_or(0, |c| c.in_flight()),
+        },
+        "uptime_secs": crate::diagnostics::app_uptime().as_secs(),
+        "daemon_uptime_secs": crate::diagnostics::daemon_uptime().map(|d| d.as_secs()),
+        "recent_errors": crate::diagnostics::recent_errors(DIAGNOSTICS_RECENT_ERRORS),
+    });
+
+    if let Some(map) = result.as_object_mut() {
+        map.insert("ready".to_string(), serde_json::json!(ready));
+        map.insert("app".to_string(), app_state);
     }
+    Ok(result)
+}
 
-    let result = call_daemon("health", serde_json::json!({}))?;
+#[tauri::command]
+pub async fn daemon_health(app: tauri::AppHandle) -> Result<HealthResult, String> {
+    let result = call_daemon_async("health", serde_json::json!({})).await?;
 
     let health_result: HealthResult = serde_json::from_value(result)
         .map_err(|e| format!("Failed to parse result: {}", e))?;
 
     if health_result.success {
-        let _ = app.emit("daemon-status", DaemonStatusPayload {
+        emit_event(&app, AppEvent::DaemonStatus(DaemonStatusPayload {
             status: "ready".to_string(),
             message: crate::ui::get_daemon_message("ready"),
-        });
+        }));
     }
 
     Ok(health_result)
 }
 
+/// Latest daemon memory / CPU sample (None until the daemon has been sampled)
+#[tauri::command]
+pub fn get_daemon_resources() -> Option<DaemonResources> {
+    crate::daemon::latest_resources()
+}
+
 #[tauri::command]
 pub async fn get_model_status() -> Result<ModelStatusResult, String> {
-    let result = call_daemon("model_status", serde_json::json!({}))?;
+    let result = call_daemon_async("model_status", serde_json::json!({})).await?;
 
     // Debug: log the raw JSON result
     eprintln!("Raw model_status result from daemon: {}", result);
@@ -704,23 +1187,3 @@ pub async fn open_folder(path: String) -> Result<(), String> {
     }
     Ok(())
 }
-
-// ============================================================================
-// Config Commands (3 commands)
-// ============================================================================
-
-#[tauri::command]
-pub fn get_app_language() -> String {
-    crate::ui::get_language_from_config()
-}
-
-#[tauri::command]
-pub fn set_app_language(language: String, app: tauri::AppHandle) -> Result<(), String> {
-    crate::ui::write_language_to_config(&language)
-        .map_err(|e| format!("Failed to write language to config: {}", e))?;
-    // Update tray menu with new language
-    crate::ui::update_tray_menu(&app)
-        .map_err(|e| format!("Failed to update tray menu: {}", e))
-}
-
-// ============================================================================
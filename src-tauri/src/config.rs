@@ -0,0 +1,1215 @@
+// ============================================================================
+// Config Module - Config Store for config.json
+// ============================================================================
+//
+// Rust owns config.json: settings are read and written here directly, so
+// they work instantly even while the Python daemon is still loading models.
+// After a change the daemon is told to reload the file (right away if it is
+// ready, otherwise as soon as it becomes ready). Writes take an exclusive
+// lock on a sidecar lock file that the daemon's ConfigManager honors too,
+// and replace config.json atomically.
+//
+// The file carries a `config_version`; older files are migrated step by step
+// when read (the daemon's ConfigManager applies the same migrations). Values
+// sent to save_config are checked against the typed [`Config`] first.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::daemon::{daemon_client, is_daemon_ready, APP_HANDLE};
+use crate::earcons::Cue;
+use crate::pipeline::Stage;
+use crate::platform::Key;
+use crate::selection::AskSelectionOutput;
+use crate::text_commands::TextCommandAction;
+use crate::types::{ConfigFieldError, PttTrigger, RecordingMode, TextInputStrategy, WorkMode};
+
+/// Current config schema version (keep in sync with config_manager.py)
+pub const CONFIG_VERSION: u64 = 3;
+
+/// Config key holding the schema version
+pub const CONFIG_VERSION_KEY: &str = "config_version";
+
+/// Lock file guarding config.json writes (shared with the daemon)
+const CONFIG_LOCK_FILE_NAME: &str = "config.json.lock";
+
+/// Config changed while the daemon was not ready; it is told to reload once it is
+static DAEMON_RELOAD_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Get the config directory of the active profile (same directory the Python daemon uses)
+pub fn get_config_dir() -> Result<PathBuf, String> {
+    Ok(crate::profiles::active_dir(&get_app_data_dir()?))
+}
+
+/// Get the app data directory, shared by all profiles
+pub fn get_app_data_dir() -> Result<PathBuf, String> {
+    if let Some(app_handle) = APP_HANDLE.get() {
+        return app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e));
+    }
+
+    // Fallback if APP_HANDLE not set (shouldn't happen in normal operation)
+    #[cfg(target_os = "macos")]
+    {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        Ok(PathBuf::from(home).join("Library/Application Support/com.speekium.app"))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
+        Ok(PathBuf::from(appdata).join("com.speekium.app"))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let xdg = std::env::var("XDG_CONFIG_HOME")
+            .unwrap_or_else(|_| format!("{}/.config", std::env::var("HOME").unwrap_or_else(|_| ".".to_string())));
+        Ok(PathBuf::from(xdg).join("com.speekium.app"))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Err("Unsupported platform".to_string())
+    }
+}
+
+/// Get the config.json path
+pub fn get_config_path() -> Result<PathBuf, String> {
+    Ok(get_config_dir()?.join("config.json"))
+}
+
+// ============================================================================
+// Reading and Writing
+// ============================================================================
+
+/// Read the whole config file (migrated to the current version), empty object if missing or invalid
+pub fn read_config() -> serde_json::Value {
+    let mut config = get_config_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .filter(serde_json::Value::is_object)
+        .unwrap_or_else(|| serde_json::json!({}));
+    migrate_config(&mut config);
+    config
+}
+
+/// Full config: defaults overlaid with config.json (what the settings UI shows)
+pub fn load_config_with_defaults() -> serde_json::Value {
+    let mut config = default_config();
+    if let (Some(merged), serde_json::Value::Object(stored)) = (config.as_object_mut(), read_config()) {
+        merged.extend(stored);
+    }
+    config
+}
+
+/// Read a single top-level config value
+pub fn read_config_value(key: &str) -> Option<serde_json::Value> {
+    read_config().get(key).cloned().filter(|v| !v.is_null())
+}
+
+/// Take the exclusive config write lock (released when the file is dropped)
+fn lock_config() -> Result<File, String> {
+    let config_dir = get_config_dir()?;
+    std::fs::create_dir_all(&config_dir)
+        .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(config_dir.join(CONFIG_LOCK_FILE_NAME))
+        .map_err(|e| format!("Failed to open config lock: {}", e))?;
+    lock.lock()
+        .map_err(|e| format!("Failed to lock config: {}", e))?;
+    Ok(lock)
+}
+
+/// Replace config.json atomically (write a temp file, then rename)
+fn write_config_file(config: &serde_json::Value) -> Result<(), String> {
+    let config_path = get_config_path()?;
+    let tmp_path = config_path.with_extension("json.tmp");
+
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(&tmp_path, json)
+        .map_err(|e| format!("Failed to write config: {}", e))?;
+    std::fs::rename(&tmp_path, &config_path)
+        .map_err(|e| format!("Failed to replace config: {}", e))
+}
+
+/// Merge top-level values into config.json, preserving all other fields
+///
+/// Returns the updated config.
+pub fn update_config(updates: &serde_json::Map<String, serde_json::Value>) -> Result<serde_json::Value, String> {
+    let _lock = lock_config()?;
+
+    let mut config = read_config();
+    if let Some(map) = config.as_object_mut() {
+        map.extend(updates.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    write_config_file(&config)?;
+    if updates.contains_key("shortcut_apps") {
+        crate::shortcut_apps::reload_config(&config);
+    }
+    Ok(config)
+}
+
+/// Update a single top-level config value, preserving all other fields
+pub fn write_config_value(key: &str, value: serde_json::Value) -> Result<(), String> {
+    let mut updates = serde_json::Map::new();
+    updates.insert(key.to_string(), value);
+    update_config(&updates).map(|_| ())
+}
+
+/// Migrate the config file on disk to the current version
+///
+/// Returns true if the file was rewritten.
+pub fn migrate_config_file() -> Result<bool, String> {
+    let config_path = get_config_path()?;
+    if !config_path.exists() {
+        return Ok(false);
+    }
+
+    let _lock = lock_config()?;
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config: {}", e))?;
+    let mut config: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    if !migrate_config(&mut config) {
+        return Ok(false);
+    }
+    write_config_file(&config)?;
+    Ok(true)
+}
+
+// ============================================================================
+// Daemon Sync
+// ============================================================================
+
+/// Tell the daemon to reload config.json after a change
+///
+/// If the daemon is still starting, the reload is sent once it is ready
+/// (see [`flush_pending_daemon_reload`]).
+pub fn notify_daemon_config_changed() {
+    DAEMON_RELOAD_PENDING.store(true, Ordering::SeqCst);
+    if is_daemon_ready() {
+        flush_pending_daemon_reload();
+    }
+}
+
+/// Send a pending reload to the daemon (called when the daemon becomes ready)
+pub fn flush_pending_daemon_reload() {
+    if !DAEMON_RELOAD_PENDING.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    // API keys are not in config.json; the daemon gets them from the keychain through us
+    // The config directory changes when the profile is switched
+    let args = serde_json::json!({
+        "api_keys": crate::secrets::api_keys(),
+        "offline": crate::network::is_offline(),
+        "config_dir": get_config_dir().ok(),
+    });
+    let sent = daemon_client().and_then(|client| client.send_no_wait("reload_config", args));
+    if let Err(e) = sent {
+        eprintln!("⚠️ Failed to notify daemon of config change: {}", e);
+        DAEMON_RELOAD_PENDING.store(true, Ordering::SeqCst);
+    }
+}
+
+// ============================================================================
+// Text Input Profiles
+// ============================================================================
+
+/// Profile key that applies to every application
+const TEXT_INPUT_PROFILE_DEFAULT_KEY: &str = "*";
+
+/// Delay between typed characters when a profile doesn't set key_delay_ms
+const DEFAULT_KEY_DELAY_MS: u64 = 10;
+
+/// Longest accepted key_delay_ms, so a typo can't stall typing for minutes
+const MAX_KEY_DELAY_MS: u64 = 1000;
+
+/// Text-input profile for an application
+///
+/// Profiles are keyed by bundle id or app name (case-insensitive); the first
+/// identifier with a profile wins, layered over the "*" profile.
+pub fn text_input_profile(app_ids: &[&str]) -> TextInputProfile {
+    let profiles: BTreeMap<String, TextInputProfile> = read_config_value("text_input_profiles")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    let find = |id: &str| {
+        profiles
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(id))
+            .map(|(_, profile)| profile.clone())
+    };
+    let base = find(TEXT_INPUT_PROFILE_DEFAULT_KEY).unwrap_or_default();
+
+    app_ids
+        .iter()
+        .find_map(|id| find(id))
+        .map(|profile| profile.or(&base))
+        .unwrap_or(base)
+}
+
+// ============================================================================
+// Defaults
+// ============================================================================
+
+/// Default configuration (keep in sync with DEFAULT_CONFIG in config_manager.py)
+pub fn default_config() -> serde_json::Value {
+    serde_json::json!({
+        "config_version": CONFIG_VERSION,
+        // LLM Provider Configuration
+        "llm_backend": "daemon",
+        "llm_provider": "ollama",
+        "llm_providers": [
+            { "name": "ollama", "base_url": "http://localhost:11434", "api_key": "", "model": "qwen2.5:1.5b" },
+            { "name": "openai", "base_url": "https://api.openai.com/v1", "api_key": "", "model": "gpt-4o-mini" },
+            { "name": "openrouter", "base_url": "https://openrouter.ai/api/v1", "api_key": "", "model": "anthropic/claude-3.5-sonnet" },
+            { "name": "custom", "base_url": "", "api_key": "", "model": "" },
+            { "name": "zhipu", "base_url": "https://open.bigmodel.cn/api/paas/v4", "api_key": "", "model": "glm-4-flash" },
+            { "name": "anthropic", "base_url": "https://api.anthropic.com/v1", "api_key": "", "model": "claude-3-5-haiku-latest" },
+            { "name": "gemini", "base_url": "https://generativelanguage.googleapis.com/v1beta/openai", "api_key": "", "model": "gemini-2.0-flash" },
+        ],
+        // TTS Configuration
+        "tts_backend": "edge",
+        "tts_voice": "auto",
+        "tts_rate": "+0%",
+        "tts_pitch": "+0Hz",
+        "tts_volume": "+0%",
+        "tts_cache_max_mb": 100,
+        // VAD Configuration
+        "vad_threshold": 0.5,
+        "vad_consecutive_threshold": 3,
+        "vad_silence_duration": 0.8,
+        "vad_pre_buffer": 0.3,
+        "vad_min_speech_duration": 0.4,
+        "vad_max_recording_duration": 30,
+        // Audio Input Configuration
+        "audio_input_device": null,
+        // PTT Recording Limits (0 = disabled)
+        "silence_timeout_ms": 10000,
+        "max_recording_secs": 120,
+        // Meeting mode: system audio (plus the microphone) transcribed in chunks
+        "meeting_chunk_secs": 30,
+        "meeting_capture_mic": true,
+        // Long-form dictation: chunks end at pauses; saying a stop phrase ends dictation
+        "dictation_pause_ms": 800,
+        "dictation_stop_phrases": ["stop dictation", "停止听写", "ディクテーション終了"],
+        // File transcription jobs run at the same time (1-4)
+        "transcription_concurrency": 1,
+        // High-pass filter + spectral gating on PTT recordings
+        "noise_suppression": false,
+        "asr_partial_results": true,
+        // Send PTT audio to the daemon in ~1s chunks while recording (off while noise_suppression is on)
+        "asr_streaming": true,
+        // ASR Language ("auto" = detect; asr_languages = languages the user speaks)
+        "asr_language": "auto",
+        "asr_languages": [],
+        "ptt_double_tap_latch": false,
+        "overlay_display": null,
+        "overlay_positions": {},
+        // Utterance Audio Archive (0 = no limit)
+        "audio_archive_enabled": false,
+        "audio_archive_max_mb": 500,
+        "audio_archive_max_days": 30,
+        // SQLCipher encryption of the history database (key in the OS keychain)
+        "database_encryption": false,
+        // Storage Cleanup
+        "storage_auto_clean": true,
+        "storage_auto_clean_days": 7,
+        // Transcription History (0 = no limit)
+        "transcription_history_max_days": 30,
+        "transcription_history_max_items": 500,
+        // Conversation History Retention (0 / false = keep; favorites are always kept)
+        "session_history_max_days": 0,
+        "session_history_max_messages": 0,
+        "session_history_favorites_only": false,
+        // Local API Server (token: required on every request, generated when the server first starts)
+        "api_server": { "enabled": false, "port": 8787, "token": "" },
+        // Outbound HTTP Proxy (empty url = direct connections)
+        "proxy": { "url": "", "no_proxy": ["localhost", "127.0.0.1"] },
+        "offline_mode": false,
+        // Restart the daemon once idle when its memory exceeds this (MB, 0 = no limit)
+        "daemon_memory_limit_mb": 0,
+        // Focus Pause (apps: bundle ids / names / executables that pause continuous listening)
+        "focus_pause": {
+            "enabled": true,
+            "system_dnd": true,
+            "mute_earcons": true,
+            "apps": ["us.zoom.xos", "zoom.us", "Zoom", "Microsoft Teams", "Teams", "Webex", "FaceTime"]
+        },
+        // Shortcut Apps (bundle ids / names; blocked: shortcuts and dictation off there,
+        // allowed: if not empty, the only apps where they are on)
+        "shortcut_apps": { "blocked": [], "allowed": [] },
+        // Updates (channel: "stable" or "beta"; endpoint: manifest URL, {channel} is replaced)
+        "updater": {
+            "channel": "stable",
+            "endpoint": "https://github.com/kanweiwei/speekium/releases/download/updater/{channel}.json"
+        },
+        // Sound Cues (per cue: enabled, volume 0.0 - 1.0)
+        "earcons": {
+            "enabled": true,
+            "cues": {
+                "record_start": { "enabled": true, "volume": 0.5 },
+                "record_stop": { "enabled": true, "volume": 0.5 },
+                "error": { "enabled": true, "volume": 0.5 },
+                "complete": { "enabled": true, "volume": 0.5 }
+            }
+        },
+        // Voice Pipeline (orchestrator: "app" = stage by stage from the app, "daemon" = LLM + TTS in the daemon;
+        // retries: extra attempts per stage; skip: stages left out, "llm" and/or "tts")
+        "pipeline": {
+            "orchestrator": "app",
+            "retries": { "asr": 1, "llm": 1, "tts": 1 },
+            "skip": []
+        },
+        // Spoken replies: drop code blocks and markdown, speak lists as sentences (max_sentences 0 = no limit)
+        "tts_postprocess": {
+            "enabled": true,
+            "strip_code": true,
+            "strip_markdown": true,
+            "lists_to_prose": true,
+            "max_sentences": 0
+        },
+        // Numbers and dates in transcripts written with digits, per work mode (locale: en-US, en-GB, en-IN)
+        "itn": { "conversation": false, "text_input": true, "locale": "en-US" },
+        // Ask Selection (templates: extra/overridden prompts, {text} = the selection)
+        "ask_selection": { "template": "summarize", "output": "popover", "templates": {} },
+        // Conversation Configuration
+        "max_history": 10,
+        "work_mode": "conversation",
+        "recording_mode": "push-to-talk",
+        "text_input_strategy": "clipboard",
+        "text_input_profiles": {},
+        "text_input_ime_safe": true,
+        "text_commands_enabled": true,
+        "text_commands": {},
+        "system_prompt": "你是一个有帮助的语音助手。",
+        "active_persona": null,
+        // Hotkey Configuration
+        "push_to_talk_hotkey": { "modifiers": ["Alt"], "key": "Digit3", "displayName": "⌥3" },
+        "ptt_trigger": "shortcut",
+        "ptt_mouse_button": 3,
+        "shortcuts": {
+            "toggle_window": { "modifiers": ["CmdOrCtrl", "Shift"], "key": "Space", "displayName": "⌘⇧Space" },
+            "toggle_work_mode": { "modifiers": ["Alt"], "key": "Digit1", "displayName": "⌥1" },
+            "toggle_recording_mode": { "modifiers": ["Alt"], "key": "Digit2", "displayName": "⌥2" },
+            "paste_transcript": { "modifiers": ["Alt"], "key": "Digit4", "displayName": "⌥4" },
+            "transcription_picker": { "modifiers": ["Alt"], "key": "Digit5", "displayName": "⌥5" },
+            "toggle_history": { "modifiers": ["Alt"], "key": "Digit6", "displayName": "⌥6" },
+            "ask_selection": { "modifiers": ["Alt"], "key": "Digit7", "displayName": "⌥7" },
+            "toggle_dictation": { "modifiers": ["Alt"], "key": "Digit8", "displayName": "⌥8" },
+            "undo_last_typing": { "modifiers": ["Alt"], "key": "Digit9", "displayName": "⌥9" },
+        },
+        // Onboarding Configuration
+        "onboarding_completed": false,
+        // Startup (start at login is an OS setting, see set_autostart)
+        "start_minimized": false,
+    })
+}
+
+// ============================================================================
+// Migrations
+// ============================================================================
+
+/// Migration steps, in version order (v1 -> v2, v2 -> v3, ...)
+const MIGRATIONS: [fn(&mut serde_json::Map<String, serde_json::Value>); (CONFIG_VERSION - 1) as usize] = [
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+];
+
+/// Schema version of a config (files written before versioning are v1)
+pub fn config_version(config: &serde_json::Value) -> u64 {
+    config.get(CONFIG_VERSION_KEY).and_then(|v| v.as_u64()).unwrap_or(1)
+}
+
+/// Apply every migration between the config's version and [`CONFIG_VERSION`]
+///
+/// Returns true if the config was changed. Configs from a newer app version
+/// are left untouched.
+pub fn migrate_config(config: &mut serde_json::Value) -> bool {
+    let from = config_version(config);
+    if from >= CONFIG_VERSION {
+        return false;
+    }
+    let Some(map) = config.as_object_mut() else {
+        return false;
+    };
+
+    // MIGRATIONS[n] upgrades version n + 1 to n + 2
+    for migration in &MIGRATIONS[(from.max(1) - 1) as usize..] {
+        migration(map);
+    }
+    map.insert(CONFIG_VERSION_KEY.to_string(), serde_json::json!(CONFIG_VERSION));
+    true
+}
+
+/// v1 -> v2: normalize mode spellings and drop malformed provider entries
+fn migrate_v1_to_v2(config: &mut serde_json::Map<String, serde_json::Value>) {
+    for (key, from, to) in [
+        ("recording_mode", "push_to_talk", "push-to-talk"),
+        ("work_mode", "text_input", "text-input"),
+    ] {
+        if config.get(key).and_then(|v| v.as_str()) == Some(from) {
+            config.insert(key.to_string(), serde_json::json!(to));
+        }
+    }
+
+    if let Some(providers) = config.get_mut("llm_providers").and_then(|v| v.as_array_mut()) {
+        providers.retain(|p| p.get("name").and_then(|n| n.as_str()).is_some());
+    }
+}
+
+/// v2 -> v3: paste by default, the Accessibility strategy misses text in some apps
+fn migrate_v2_to_v3(config: &mut serde_json::Map<String, serde_json::Value>) {
+    if config.get("text_input_strategy").and_then(|v| v.as_str()) == Some("accessibility") {
+        config.insert("text_input_strategy".to_string(), serde_json::json!("clipboard"));
+    }
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Hotkey as stored in config (push_to_talk_hotkey / shortcuts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyConfig {
+    pub modifiers: Vec<String>,
+    pub key: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+/// Dragged PTT overlay position (physical pixels)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlayPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Per-application text-input behavior (unset fields inherit from the "*" profile)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TextInputProfile {
+    /// Insert text at all (false for e.g. password managers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Add a newline after the text (e.g. to send a chat message)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub append_newline: Option<bool>,
+    /// Add a space after the text so consecutive dictations don't run together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trailing_space: Option<bool>,
+    /// "paste" (default) or "type": simulate a key press per character, for
+    /// apps that limit or block pasting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_method: Option<String>,
+    /// Delay between typed characters in "type" mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_delay_ms: Option<u64>,
+    /// Insert long texts in parts of this many characters (0 = all at once)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_chars: Option<usize>,
+    /// Undo the last insertion with Cmd/Ctrl+Z instead of backspaces (for
+    /// apps where one undo step takes back exactly one paste)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undo_shortcut: Option<bool>,
+}
+
+impl TextInputProfile {
+    /// Fill unset fields from `base`
+    fn or(self, base: &TextInputProfile) -> TextInputProfile {
+        TextInputProfile {
+            enabled: self.enabled.or(base.enabled),
+            append_newline: self.append_newline.or(base.append_newline),
+            trailing_space: self.trailing_space.or(base.trailing_space),
+            input_method: self.input_method.or_else(|| base.input_method.clone()),
+            key_delay_ms: self.key_delay_ms.or(base.key_delay_ms),
+            chunk_chars: self.chunk_chars.or(base.chunk_chars),
+            undo_shortcut: self.undo_shortcut.or(base.undo_shortcut),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    /// Type character by character instead of pasting
+    pub fn types_keys(&self) -> bool {
+        self.input_method.as_deref() == Some("type")
+    }
+
+    pub fn key_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.key_delay_ms.unwrap_or(DEFAULT_KEY_DELAY_MS).min(MAX_KEY_DELAY_MS))
+    }
+
+    pub fn chunk_chars(&self) -> usize {
+        self.chunk_chars.unwrap_or(0)
+    }
+
+    pub fn undoes_with_shortcut(&self) -> bool {
+        self.undo_shortcut.unwrap_or(false)
+    }
+
+    /// Text to insert after applying the profile
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        if self.trailing_space.unwrap_or(false) && !result.ends_with(char::is_whitespace) {
+            result.push(' ');
+        }
+        if self.append_newline.unwrap_or(false) {
+            result.push('\n');
+        }
+        result
+    }
+}
+
+/// Local HTTP/WebSocket API server (`api_server`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiServerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_api_server_port")]
+    pub port: u16,
+    /// Required as `Authorization: Bearer <token>` (or `?token=`); generated when empty
+    #[serde(default)]
+    pub token: String,
+}
+
+fn default_api_server_port() -> u16 {
+    8787
+}
+
+impl Default for ApiServerConfig {
+    fn default() -> Self {
+        ApiServerConfig {
+            enabled: false,
+            port: default_api_server_port(),
+            token: String::new(),
+        }
+    }
+}
+
+/// Outbound HTTP proxy (`proxy`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    /// http://, https:// or socks5:// URL (empty = direct connections)
+    #[serde(default)]
+    pub url: String,
+    /// Hosts and domains reached directly (e.g. "localhost", ".corp.example.com")
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+}
+
+/// Prompt templates run on selected text (`ask_selection`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AskSelectionConfig {
+    /// Template used by the shortcut
+    #[serde(default = "default_ask_selection_template")]
+    pub template: String,
+    /// "popover" (show the answer) or "replace" (type it over the selection)
+    #[serde(default = "default_ask_selection_output")]
+    pub output: String,
+    /// Extra or overridden templates: name -> prompt, `{text}` is replaced by the selection
+    #[serde(default)]
+    pub templates: BTreeMap<String, String>,
+}
+
+fn default_ask_selection_template() -> String {
+    "summarize".to_string()
+}
+
+fn default_ask_selection_output() -> String {
+    "popover".to_string()
+}
+
+impl Default for AskSelectionConfig {
+    fn default() -> Self {
+        AskSelectionConfig {
+            template: default_ask_selection_template(),
+            output: default_ask_selection_output(),
+            templates: BTreeMap::new(),
+        }
+    }
+}
+
+/// Auto-pause of continuous listening (`focus_pause`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusPauseConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Pause while the system Do Not Disturb / Focus mode is on
+    #[serde(default = "default_true")]
+    pub system_dnd: bool,
+    /// Mute sound cues while paused
+    #[serde(default = "default_true")]
+    pub mute_earcons: bool,
+    /// Apps that pause listening while frontmost or using the microphone
+    #[serde(default)]
+    pub apps: Vec<String>,
+}
+
+impl Default for FocusPauseConfig {
+    fn default() -> Self {
+        FocusPauseConfig {
+            enabled: true,
+            system_dnd: true,
+            mute_earcons: true,
+            apps: Vec::new(),
+        }
+    }
+}
+
+/// Apps where the global shortcuts are off (`shortcut_apps`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShortcutAppsConfig {
+    /// Apps where the shortcuts are off while frontmost
+    #[serde(default)]
+    pub blocked: Vec<String>,
+    /// If not empty, the only apps where the shortcuts are on
+    #[serde(default)]
+    pub allowed: Vec<String>,
+}
+
+/// Update channel and manifest location (`updater`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdaterConfig {
+    /// "stable" or "beta"
+    #[serde(default = "default_update_channel")]
+    pub channel: String,
+    /// Manifest URL, `{channel}` is replaced by the channel
+    #[serde(default = "default_update_endpoint")]
+    pub endpoint: String,
+}
+
+pub const UPDATE_CHANNELS: &[&str] = &["stable", "beta"];
+
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
+fn default_update_endpoint() -> String {
+    "https://github.com/kanweiwei/speekium/releases/download/updater/{channel}.json".to_string()
+}
+
+impl Default for UpdaterConfig {
+    fn default() -> Self {
+        UpdaterConfig {
+            channel: default_update_channel(),
+            endpoint: default_update_endpoint(),
+        }
+    }
+}
+
+/// Stage control of the PTT voice pipeline (`pipeline`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    /// "app" or "daemon"
+    #[serde(default = "default_orchestrator")]
+    pub orchestrator: String,
+    /// Stage name ("asr", "llm", "tts") -> extra attempts after a failure
+    #[serde(default)]
+    pub retries: BTreeMap<String, u32>,
+    /// Stages left out ("llm", "tts")
+    #[serde(default)]
+    pub skip: Vec<String>,
+}
+
+fn default_orchestrator() -> String {
+    crate::pipeline::APP_ORCHESTRATOR.to_string()
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        PipelineConfig {
+            orchestrator: default_orchestrator(),
+            retries: BTreeMap::new(),
+            skip: Vec::new(),
+        }
+    }
+}
+
+/// Clean-up of reply text before it is spoken (`tts_postprocess`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsPostprocessConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Leave out fenced code blocks
+    #[serde(default = "default_true")]
+    pub strip_code: bool,
+    /// Remove headings, emphasis, inline code and link markup
+    #[serde(default = "default_true")]
+    pub strip_markdown: bool,
+    /// Speak list items and table rows as sentences
+    #[serde(default = "default_true")]
+    pub lists_to_prose: bool,
+    /// Sentences spoken per reply (0 = no limit)
+    #[serde(default)]
+    pub max_sentences: u32,
+}
+
+impl Default for TtsPostprocessConfig {
+    fn default() -> Self {
+        TtsPostprocessConfig {
+            enabled: true,
+            strip_code: true,
+            strip_markdown: true,
+            lists_to_prose: true,
+            max_sentences: 0,
+        }
+    }
+}
+
+/// Spoken numbers and dates written with digits (`itn`, see itn)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItnConfig {
+    /// Normalize transcripts sent to the LLM
+    #[serde(default)]
+    pub conversation: bool,
+    /// Normalize text typed in text-input mode
+    #[serde(default = "default_true")]
+    pub text_input: bool,
+    /// "en-US", "en-GB" or "en-IN": date order and digit grouping
+    #[serde(default = "default_itn_locale")]
+    pub locale: String,
+}
+
+fn default_itn_locale() -> String {
+    "en-US".to_string()
+}
+
+impl Default for ItnConfig {
+    fn default() -> Self {
+        ItnConfig {
+            conversation: false,
+            text_input: true,
+            locale: default_itn_locale(),
+        }
+    }
+}
+
+/// Sound cues played on state changes (`earcons`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarconsConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Cue name -> settings (missing cues use the defaults)
+    #[serde(default)]
+    pub cues: BTreeMap<String, CueConfig>,
+}
+
+impl Default for EarconsConfig {
+    fn default() -> Self {
+        EarconsConfig {
+            enabled: true,
+            cues: BTreeMap::new(),
+        }
+    }
+}
+
+/// Settings of one sound cue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CueConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 0.0 - 1.0
+    #[serde(default = "default_cue_volume")]
+    pub volume: f32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_cue_volume() -> f32 {
+    0.5
+}
+
+impl Default for CueConfig {
+    fn default() -> Self {
+        CueConfig {
+            enabled: true,
+            volume: default_cue_volume(),
+        }
+    }
+}
+
+/// LLM provider entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmProviderConfig {
+    pub name: String,
+    #[serde(default)]
+    pub base_url: String,
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default)]
+    pub model: String,
+}
+
+/// Typed view of config.json
+///
+/// Every field is optional because save_config receives partial updates;
+/// keys not listed here are kept as-is in `extra`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    pub config_version: Option<u64>,
+    pub language: Option<String>,
+    pub llm_backend: Option<String>,
+    pub llm_provider: Option<String>,
+    pub llm_providers: Option<Vec<LlmProviderConfig>>,
+    pub tts_backend: Option<String>,
+    pub tts_voice: Option<String>,
+    pub tts_rate: Option<String>,
+    pub tts_pitch: Option<String>,
+    pub tts_volume: Option<String>,
+    pub tts_cache_max_mb: Option<u64>,
+    pub vad_threshold: Option<f64>,
+    pub vad_consecutive_threshold: Option<u32>,
+    pub vad_silence_duration: Option<f64>,
+    pub vad_pre_buffer: Option<f64>,
+    pub vad_min_speech_duration: Option<f64>,
+    pub vad_max_recording_duration: Option<f64>,
+    pub audio_input_device: Option<String>,
+    pub silence_timeout_ms: Option<u64>,
+    pub max_recording_secs: Option<u64>,
+    pub meeting_chunk_secs: Option<u64>,
+    pub meeting_capture_mic: Option<bool>,
+    pub dictation_pause_ms: Option<u64>,
+    pub dictation_stop_phrases: Option<Vec<String>>,
+    pub transcription_concurrency: Option<u64>,
+    pub noise_suppression: Option<bool>,
+    pub asr_partial_results: Option<bool>,
+    pub asr_streaming: Option<bool>,
+    pub asr_language: Option<String>,
+    pub asr_languages: Option<Vec<String>>,
+    pub ptt_double_tap_latch: Option<bool>,
+    pub overlay_display: Option<String>,
+    pub overlay_positions: Option<BTreeMap<String, OverlayPosition>>,
+    pub audio_archive_enabled: Option<bool>,
+    pub audio_archive_max_mb: Option<u64>,
+    pub audio_archive_max_days: Option<u64>,
+    pub database_encryption: Option<bool>,
+    pub storage_auto_clean: Option<bool>,
+    pub storage_auto_clean_days: Option<u64>,
+    pub transcription_history_max_days: Option<u64>,
+    pub transcription_history_max_items: Option<u64>,
+    pub session_history_max_days: Option<u64>,
+    pub session_history_max_messages: Option<u64>,
+    pub session_history_favorites_only: Option<bool>,
+    pub api_server: Option<ApiServerConfig>,
+    pub proxy: Option<ProxyConfig>,
+    pub offline_mode: Option<bool>,
+    pub daemon_memory_limit_mb: Option<u64>,
+    pub focus_pause: Option<FocusPauseConfig>,
+    pub shortcut_apps: Option<ShortcutAppsConfig>,
+    pub updater: Option<UpdaterConfig>,
+    pub earcons: Option<EarconsConfig>,
+    pub pipeline: Option<PipelineConfig>,
+    pub tts_postprocess: Option<TtsPostprocessConfig>,
+    pub itn: Option<ItnConfig>,
+    pub ask_selection: Option<AskSelectionConfig>,
+    pub max_history: Option<u32>,
+    pub work_mode: Option<String>,
+    pub recording_mode: Option<String>,
+    pub text_input_strategy: Option<String>,
+    pub text_input_profiles: Option<BTreeMap<String, TextInputProfile>>,
+    pub text_input_ime_safe: Option<bool>,
+    pub text_commands_enabled: Option<bool>,
+    pub text_commands: Option<BTreeMap<String, TextCommandAction>>,
+    pub system_prompt: Option<String>,
+    pub active_persona: Option<i64>,
+    pub push_to_talk_hotkey: Option<HotkeyConfig>,
+    pub ptt_trigger: Option<String>,
+    pub ptt_mouse_button: Option<i64>,
+    pub shortcuts: Option<BTreeMap<String, HotkeyConfig>>,
+    pub onboarding_completed: Option<bool>,
+    pub start_minimized: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Config {
+    /// Parse and validate a (possibly partial) config object
+    ///
+    /// Collects every invalid field instead of stopping at the first one.
+    pub fn from_value(value: &serde_json::Value) -> Result<Config, Vec<ConfigFieldError>> {
+        let Some(map) = value.as_object() else {
+            return Err(vec![field_error("config", "must be a JSON object")]);
+        };
+
+        // Type errors: check each field on its own so all of them are reported
+        let mut errors: Vec<ConfigFieldError> = map
+            .iter()
+            .filter_map(|(key, value)| {
+                let single = serde_json::json!({ key: value });
+                serde_json::from_value::<Config>(single)
+                    .err()
+                    .map(|e| field_error(key, &e.to_string()))
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let config: Config = serde_json::from_value(value.clone())
+            .map_err(|e| vec![field_error("config", &e.to_string())])?;
+
+        // Value errors
+        config.check_values(&mut errors);
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn check_values(&self, errors: &mut Vec<ConfigFieldError>) {
+        let mut check = |ok: bool, field: &str, message: &str| {
+            if !ok {
+                errors.push(field_error(field, message));
+            }
+        };
+
+        if let Some(version) = self.config_version {
+            check(version <= CONFIG_VERSION, CONFIG_VERSION_KEY, "is newer than this app supports");
+        }
+        if let Some(threshold) = self.vad_threshold {
+            check((0.0..=1.0).contains(&threshold), "vad_threshold", "must be between 0.0 and 1.0");
+        }
+        if let Some(count) = self.vad_consecutive_threshold {
+            check(count >= 1, "vad_consecutive_threshold", "must be at least 1");
+        }
+        for (field, value) in [
+            ("vad_silence_duration", self.vad_silence_duration),
+            ("vad_pre_buffer", self.vad_pre_buffer),
+            ("vad_min_speech_duration", self.vad_min_speech_duration),
+        ] {
+            if let Some(value) = value {
+                check(value >= 0.0, field, "must not be negative");
+            }
+        }
+        if let Some(duration) = self.vad_max_recording_duration {
+            check(duration > 0.0, "vad_max_recording_duration", "must be greater than 0");
+        }
+        if let Some(mode) = &self.work_mode {
+            check(WorkMode::from_str(mode).is_some(), "work_mode", "must be \"conversation\" or \"text-input\"");
+        }
+        if let Some(mode) = &self.recording_mode {
+            check(
+                RecordingMode::from_str(mode).is_some(),
+                "recording_mode",
+                "must be \"push-to-talk\" or \"continuous\"",
+            );
+        }
+        if let Some(strategy) = &self.text_input_strategy {
+            check(
+                TextInputStrategy::from_str(strategy).is_some(),
+                "text_input_strategy",
+                "must be \"accessibility\" or \"clipboard\"",
+            );
+        }
+        if let Some(commands) = &self.text_commands {
+            check(
+                commands.keys().all(|phrase| !phrase.trim().is_empty()),
+                "text_commands",
+                "phrases must not be empty",
+            );
+            check(
+                commands.values().all(|action| match action {
+                    TextCommandAction::Key(name) => Key::from_str(name).is_some(),
+                    _ => true,
+                }),
+                "text_commands",
+                "key must be \"enter\", \"tab\", \"escape\" or \"backspace\"",
+            );
+        }
+        for (field, value, unit) in [
+            ("tts_rate", &self.tts_rate, "%"),
+            ("tts_pitch", &self.tts_pitch, "Hz"),
+            ("tts_volume", &self.tts_volume, "%"),
+        ] {
+            if let Some(value) = value {
+                check(
+                    crate::tts_voices::is_edge_offset(value, unit),
+                    field,
+                    &format!("must be a signed offset like \"+0{}\"", unit),
+                );
+            }
+        }
+        if let Some(voice) = &self.tts_voice {
+            check(!voice.trim().is_empty(), "tts_voice", "must not be empty (use \"auto\")");
+        }
+        if let Some(language) = &self.asr_language {
+            check(
+                crate::asr_language::is_valid_language(language),
+                "asr_language",
+                "must be \"auto\" or one of zh, en, ja, ko, yue",
+            );
+        }
+        if let Some(languages) = &self.asr_languages {
+            check(
+                languages.iter().all(|l| crate::asr_language::ASR_LANGUAGES.contains(&l.as_str())),
+                "asr_languages",
+                "languages must be zh, en, ja, ko or yue",
+            );
+        }
+        if let Some(server) = &self.api_server {
+            check(server.port != 0, "api_server", "port must not be 0");
+        }
+        if let Some(proxy) = &self.proxy {
+            check(
+                proxy.url.trim().is_empty() || crate::http::parse_proxy_url(&proxy.url).is_some(),
+                "proxy",
+                "url must be an http://, https:// or socks5:// URL",
+            );
+        }
+        if let Some(focus_pause) = &self.focus_pause {
+            check(
+                focus_pause.apps.iter().all(|app| !app.trim().is_empty()),
+                "focus_pause",
+                "app names must not be empty",
+            );
+        }
+        if let Some(shortcut_apps) = &self.shortcut_apps {
+            check(
+                shortcut_apps.blocked.iter().chain(&shortcut_apps.allowed).all(|app| !app.trim().is_empty()),
+                "shortcut_apps",
+                "app names must not be empty",
+            );
+        }
+        if let Some(updater) = &self.updater {
+            check(
+                UPDATE_CHANNELS.contains(&updater.channel.as_str()),
+                "updater",
+                "channel must be \"stable\" or \"beta\"",
+            );
+            check(
+                updater.endpoint.trim().starts_with("https://"),
+                "updater",
+                "endpoint must be an https:// URL",
+            );
+        }
+        if let Some(earcons) = &self.earcons {
+            check(
+                earcons.cues.keys().all(|name| Cue::from_str(name).is_some()),
+                "earcons",
+                "cues must be \"record_start\", \"record_stop\", \"error\" or \"complete\"",
+            );
+            check(
+                earcons.cues.values().all(|cue| (0.0..=1.0).contains(&cue.volume)),
+                "earcons",
+                "volume must be between 0.0 and 1.0",
+            );
+        }
+        if let Some(pipeline) = &self.pipeline {
+            check(
+                [crate::pipeline::APP_ORCHESTRATOR, crate::pipeline::DAEMON_ORCHESTRATOR]
+                    .contains(&pipeline.orchestrator.as_str()),
+                "pipeline",
+                "orchestrator must be \"app\" or \"daemon\"",
+            );
+            check(
+                pipeline.retries.keys().all(|name| Stage::from_str(name).is_some()),
+                "pipeline",
+                "retries must be given for \"asr\", \"llm\" or \"tts\"",
+            );
+            check(
+                pipeline.retries.values().all(|&retries| retries <= crate::pipeline::MAX_RETRIES),
+                "pipeline",
+                "retries must be at most 5",
+            );
+            check(
+                pipeline.skip.iter().all(|name| matches!(Stage::from_str(name), Some(Stage::Llm | Stage::Tts))),
+                "pipeline",
+                "only \"llm\" and \"tts\" can be skipped",
+            );
+        }
+        if let Some(itn) = &self.itn {
+            check(
+                crate::itn::Locale::from_str(&itn.locale).is_some(),
+                "itn",
+                "locale must be \"en-US\", \"en-GB\" or \"en-IN\"",
+            );
+        }
+        if let Some(ask) = &self.ask_selection {
+            check(
+                AskSelectionOutput::from_str(&ask.output).is_some(),
+                "ask_selection",
+                "output must be \"popover\" or \"replace\"",
+            );
+            check(
+                ask.templates.iter().all(|(name, prompt)| !name.trim().is_empty() && !prompt.trim().is_empty()),
+                "ask_selection",
+                "template names and prompts must not be empty",
+            );
+            check(
+                crate::selection::template_prompt(ask, &ask.template).is_some(),
+                "ask_selection",
+                "template is not a built-in or configured template",
+            );
+        }
+        if let Some(trigger) = &self.ptt_trigger {
+            check(
+                PttTrigger::from_str(trigger, 0).is_some(),
+                "ptt_trigger",
+                "must be \"shortcut\", \"mouse_button\" or \"fn_key\"",
+            );
+        }
+        if let Some(button) = self.ptt_mouse_button {
+            check(button >= 2, "ptt_mouse_button", "must be 2 (middle) or a side button");
+        }
+        if let Some(hotkey) = &self.push_to_talk_hotkey {
+            check(!hotkey.key.is_empty(), "push_to_talk_hotkey", "key must not be empty");
+        }
+        if let Some(shortcuts) = &self.shortcuts {
+            check(shortcuts.values().all(|h| !h.key.is_empty()), "shortcuts", "key must not be empty");
+        }
+        if let Some(backend) = &self.llm_backend {
+            check(
+                matches!(backend.as_str(), "daemon" | "native"),
+                "llm_backend",
+                "must be \"daemon\" or \"native\"",
+            );
+        }
+        if let Some(providers) = &self.llm_providers {
+            check(
+                providers.iter().all(|p| !p.name.is_empty()),
+                "llm_providers",
+                "every provider needs a name",
+            );
+            if let Some(current) = &self.llm_provider {
+                check(
+                    providers.iter().any(|p| &p.name == current),
+                    "llm_provider",
+                    "is not in llm_providers",
+                );
+            }
+        }
+    }
+}
+
+fn field_error(field: &str, message: &str) -> ConfigFieldError {
+    ConfigFieldError {
+        field: field.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// One-line summary of validation errors
+pub fn describe_errors(errors: &[ConfigFieldError]) -> String {
+    let fields: Vec<String> = errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect();
+    format!("Invalid config: {}", fields.join("; "))
+}
@@ -0,0 +1,349 @@
+//! Daemon Client
+//!
+//! Multiplexes concurrent requests over the framed daemon connection.
+//!
+//! Daemon I/O runs on the async runtime: a writer task owns the daemon's stdin
+//! and a single driver task owns its stdout. Every request registers a channel
+//! under its id, and the driver routes the matching response and stream frames
+//! to it. Callers therefore never hold the global DAEMON lock while waiting, and
+//! several requests (e.g. a health check during a chat stream) can be in flight
+//! at once without tying up a thread each.
+//!
+//! Reading and writing are separate tasks because `read_frame` is not
+//! cancel-safe, so it cannot be raced against outgoing frames in one loop.
+//!
+//! Outgoing frames wait in a priority queue (see [`CommandPriority`]): when
+//! several are queued, the writer sends PTT audio before interrupts, chats and
+//! finally housekeeping such as health checks.
+
+use std::cmp::{Ordering as CmpOrdering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::time::Instant;
+
+use super::ipc::{next_request_id, read_frame, write_frame, Frame, FrameKind};
+use super::request::{stops_on_recording_abort, CommandPriority, RequestOptions};
+use super::state::{DAEMON_KILL_REQUEST, RECORDING_ABORTED};
+
+/// How often a waiting request checks for cancellation
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Channels of in-flight requests, keyed by request id
+type PendingMap = Arc<Mutex<HashMap<u64, UnboundedSender<Frame>>>>;
+
+/// Frame waiting for the writer task
+struct QueuedFrame {
+    priority: CommandPriority,
+    /// Arrival order, so equal priorities stay first-in first-out
+    seq: u64,
+    frame: Frame,
+}
+
+impl QueuedFrame {
+    fn key(&self) -> (CommandPriority, Reverse<u64>) {
+        (self.priority, Reverse(self.seq))
+    }
+}
+
+impl PartialEq for QueuedFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for QueuedFrame {}
+
+impl PartialOrd for QueuedFrame {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedFrame {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Run a future to completion from synchronous code
+///
+/// Works both on plain threads and on async runtime workers (the worker is
+/// marked as blocking so other tasks keep running).
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => tauri::async_runtime::block_on(future),
+    }
+}
+
+// ============================================================================
+// DaemonClient
+// ============================================================================
+
+/// Cloneable handle for sending requests to one daemon process
+#[derive(Clone)]
+pub struct DaemonClient {
+    pid: u32,
+    writer: UnboundedSender<(CommandPriority, Frame)>,
+    /// Frames sent to the writer task but not yet written
+    queued: Arc<AtomicUsize>,
+    pending: PendingMap,
+}
+
+impl DaemonClient {
+    /// Start the writer and driver tasks for a daemon connection
+    pub fn spawn<W, R>(pid: u32, mut stdin: W, mut stdout: R) -> Self
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let (writer, mut frames_to_write) = unbounded_channel::<(CommandPriority, Frame)>();
+        let queued = Arc::new(AtomicUsize::new(0));
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+
+        // Writer task: exits when every client handle is dropped or the pipe breaks
+        let writer_queued = queued.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut queue = BinaryHeap::new();
+            let mut seq = 0u64;
+            loop {
+                if queue.is_empty() {
+                    let Some((priority, frame)) = frames_to_write.recv().await else {
+                        break;
+                    };
+                    seq += 1;
+                    queue.push(QueuedFrame { priority, seq, frame });
+                }
+                // Everything that arrived meanwhile competes for the next write
+                while let Ok((priority, frame)) = frames_to_write.try_recv() {
+                    seq += 1;
+                    queue.push(QueuedFrame { priority, seq, frame });
+                }
+
+                let Some(next) = queue.pop() else {
+                    continue;
+                };
+                let written = write_frame(&mut stdin, &next.frame).await;
+                writer_queued.fetch_sub(1, Ordering::SeqCst);
+                if let Err(e) = written {
+                    eprintln!("[DAEMON DEBUG] Writer stopped: {}", e);
+                    break;
+                }
+            }
+        });
+
+        // Driver task: routes frames to the request that is waiting for them
+        let driver_pending = pending.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let frame = match read_frame(&mut stdout).await {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => break,
+                    Err(e) => {
+                        eprintln!("[DAEMON DEBUG] Driver stopped: {}", e);
+                        break;
+                    }
+                };
+
+                let Some(id) = frame.id else {
+                    continue;
+                };
+
+                let Ok(mut pending) = driver_pending.lock() else {
+                    break;
+                };
+                match frame.kind {
+                    // Final frame of a request
+                    FrameKind::Response => {
+                        if let Some(tx) = pending.remove(&id) {
+                            let _ = tx.send(frame);
+                        }
+                    }
+                    FrameKind::Stream => {
+                        if let Some(tx) = pending.get(&id) {
+                            let _ = tx.send(frame);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            // Connection lost: dropping the senders wakes up every waiting request
+            if let Ok(mut pending) = driver_pending.lock() {
+                pending.clear();
+            }
+        });
+
+        DaemonClient { pid, writer, queued, pending }
+    }
+
+    /// Process id of the daemon this client talks to
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Commands waiting to be written to the daemon
+    pub fn queue_len(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// Requests sent and still waiting for their response
+    pub fn in_flight(&self) -> usize {
+        self.pending.lock().map(|p| p.len()).unwrap_or(0)
+    }
+
+    /// Queue a frame for the writer task
+    fn enqueue(&self, command: &str, frame: Frame) -> Result<(), String> {
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        self.writer.send((CommandPriority::for_command(command), frame)).map_err(|_| {
+            self.queued.fetch_sub(1, Ordering::SeqCst);
+            "Daemon connection lost".to_string()
+        })
+    }
+
+    /// Send a request and return a handle for reading its response/stream frames
+    pub fn send_request(&self, command: &str, args: serde_json::Value) -> Result<PendingRequest, String> {
+        let id = next_request_id();
+        let (tx, rx) = unbounded_channel();
+
+        self.pending.lock()
+            .map_err(|e| format!("Pending requests lock poisoned: {}", e))?
+            .insert(id, tx);
+
+        let request = PendingRequest {
+            id,
+            frames: rx,
+            pending: self.pending.clone(),
+        };
+
+        self.enqueue(command, Frame::request(id, command, args))?;
+
+        Ok(request)
+    }
+
+    /// Send a request without waiting for its response (fire-and-forget)
+    pub fn send_no_wait(&self, command: &str, args: serde_json::Value) -> Result<(), String> {
+        self.enqueue(command, Frame::request(next_request_id(), command, args))
+    }
+
+    /// Send command and wait for its response with a timeout / cancel token
+    ///
+    /// On timeout the supervisor is asked to kill the daemon if
+    /// `restart_on_timeout` is set; it is then restarted automatically.
+    pub async fn request(
+        &self,
+        command: &str,
+        args: serde_json::Value,
+        options: &RequestOptions,
+    ) -> Result<serde_json::Value, String> {
+        let mut request = self.send_request(command, args)?;
+        let deadline = options.timeout.map(|t| Instant::now() + t);
+        let abortable = stops_on_recording_abort(command);
+
+        loop {
+            // Check if recording should be aborted (for continuous mode)
+            if abortable && RECORDING_ABORTED.swap(false, Ordering::SeqCst) {
+                return Ok(serde_json::json!({
+                    "success": false,
+                    "error": "Recording cancelled"
+                }));
+            }
+
+            if options.cancel.as_ref().is_some_and(|c| c.is_cancelled()) {
+                return Err(format!("Command '{}' cancelled", command));
+            }
+
+            if let Some(deadline) = deadline {
+                if Instant::now() >= deadline {
+                    if options.restart_on_timeout {
+                        eprintln!("[DAEMON DEBUG] Command '{}' timed out, requesting daemon restart", command);
+                        DAEMON_KILL_REQUEST.store(self.pid, Ordering::SeqCst);
+                    }
+                    return Err(format!(
+                        "Command '{}' timed out after {}s",
+                        command,
+                        options.timeout.unwrap_or_default().as_secs()
+                    ));
+                }
+            }
+
+            let frame = match request.recv_timeout(CANCEL_POLL_INTERVAL).await
+                .map_err(|e| format!("Failed to read response: {}", e))?
+            {
+                Some(frame) => frame,
+                None => continue,
+            };
+
+            if frame.kind == FrameKind::Response {
+                return Ok(frame.payload);
+            }
+        }
+    }
+
+    /// Blocking version of [`DaemonClient::request`] for synchronous callers
+    pub fn send_command_with(
+        &self,
+        command: &str,
+        args: serde_json::Value,
+        options: &RequestOptions,
+    ) -> Result<serde_json::Value, String> {
+        block_on(self.request(command, args, options))
+    }
+}
+
+// ============================================================================
+// PendingRequest
+// ============================================================================
+
+/// Handle for the frames of one in-flight request
+///
+/// Dropping it unregisters the request; later frames for it are discarded.
+pub struct PendingRequest {
+    id: u64,
+    frames: UnboundedReceiver<Frame>,
+    pending: PendingMap,
+}
+
+impl PendingRequest {
+    /// Wait up to `timeout` for the next frame of this request
+    ///
+    /// Returns `Ok(None)` if nothing arrived in time.
+    pub async fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<Frame>, String> {
+        match tokio::time::timeout(timeout, self.frames.recv()).await {
+            Ok(Some(frame)) => Ok(Some(frame)),
+            Ok(None) => Err("Daemon connection lost".to_string()),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Consume the remaining frames of an abandoned request in the background
+    ///
+    /// The request stays registered until its final response (or `timeout`),
+    /// so the daemon's leftover output for it is read and dropped here.
+    pub fn drain(mut self, timeout: Duration) {
+        tauri::async_runtime::spawn(async move {
+            let _ = tokio::time::timeout(timeout, async {
+                while let Some(frame) = self.frames.recv().await {
+                    if frame.kind == FrameKind::Response {
+                        break;
+                    }
+                }
+            })
+            .await;
+        });
+    }
+}
+
+impl Drop for PendingRequest {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&self.id);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! This module handles detecting the daemon execution mode based on environment:
 //! - Production (app bundle): look for sidecar executable
 //! - Development: use Python script
+//! - Mock (`SPEEKIUM_MOCK_DAEMON=1`): the app binary itself, see [`super::mock`]
 
 use crate::types::DaemonMode;
 
@@ -15,6 +16,7 @@ use crate::types::DaemonMode;
 /// # Returns
 /// - `DaemonMode::Production` with executable path if sidecar is found
 /// - `DaemonMode::Development` with script path if Python script is found
+/// - `DaemonMode::Mock` with the current executable if `SPEEKIUM_MOCK_DAEMON=1`
 ///
 /// # Search Paths
 /// **Development mode** (when executable is in target/):
@@ -31,6 +33,10 @@ pub fn detect_daemon_mode() -> Result<DaemonMode, String> {
     let current_exe = std::env::current_exe()
         .map_err(|e| format!("Failed to get current executable path: {}", e))?;
 
+    if super::mock::enabled() {
+        return Ok(DaemonMode::Mock { executable_path: current_exe });
+    }
+
     let exe_dir = current_exe.parent()
         .ok_or_else(|| "Failed to get executable directory".to_string())?;
 
@@ -0,0 +1,161 @@
+//! Daemon Health Monitor
+//!
+//! Background task that pings the ready daemon with a quick `health` command
+//! and reports the result to the frontend as `daemon-health` events.
+//!
+//! The interval adapts to what the app is doing: frequent while the user is
+//! active, longer once the pipeline has been idle for a while, and short after
+//! a failed ping to confirm the problem quickly. Checks are skipped while a
+//! stream or any other pipeline stage is running, while any daemon request is
+//! in flight (chats, titles and API calls leave the status idle) and while
+//! files are transcribed, so they never compete with real work or fail behind
+//! a slow request. A little jitter keeps the pings from lining up with other
+//! periodic work. After [`MAX_CONSECUTIVE_FAILURES`] failed pings the daemon is
+//! killed and the supervisor restarts it.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+
+use crate::types::DaemonHealthPayload;
+
+use super::request::RequestOptions;
+use super::startup::{daemon_client, is_daemon_ready};
+use super::state::{APP_STATUS, DAEMON_KILL_REQUEST, DAEMON_SHUTTING_DOWN, STREAMING_IN_PROGRESS};
+use crate::events::{emit_event, AppEvent};
+
+/// Interval while the user has been active recently
+const ACTIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Interval once the pipeline has been idle for [`IDLE_AFTER`]
+const IDLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// No status change for this long counts as idle
+const IDLE_AFTER: Duration = Duration::from_secs(120);
+
+/// Interval after a failed ping
+const FAILURE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often to look again while checks are paused (stream or pipeline busy)
+const PAUSED_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A ping that takes longer than this counts as failed
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Failed pings in a row before the daemon is restarted
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Maximum jitter added to or removed from an interval, in percent
+const JITTER_PERCENT: u64 = 20;
+
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Start the health monitor (once per app run)
+pub fn start_health_monitor(app_handle: tauri::AppHandle) {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let mut consecutive_failures = 0u32;
+        let mut delay = ACTIVE_INTERVAL;
+
+        loop {
+            tokio::time::sleep(with_jitter(delay)).await;
+            if DAEMON_SHUTTING_DOWN.load(Ordering::SeqCst) {
+                return;
+            }
+
+            // Startup and restarts are handled by the supervisor
+            if !is_daemon_ready() {
+                consecutive_failures = 0;
+                delay = ACTIVE_INTERVAL;
+                continue;
+            }
+            if STREAMING_IN_PROGRESS.load(Ordering::SeqCst)
+                || !APP_STATUS.current().is_idle()
+                || daemon_busy()
+            {
+                delay = PAUSED_INTERVAL;
+                continue;
+            }
+
+            let (pid, result, latency) = ping().await;
+            match result {
+                Ok(()) => consecutive_failures = 0,
+                Err(_) => consecutive_failures += 1,
+            }
+            let restarting = consecutive_failures >= MAX_CONSECUTIVE_FAILURES;
+            delay = if consecutive_failures > 0 && !restarting {
+                FAILURE_INTERVAL
+            } else {
+                next_interval()
+            };
+
+            emit_event(&app_handle, AppEvent::DaemonHealth(DaemonHealthPayload {
+                healthy: result.is_ok(),
+                latency_ms: latency.as_millis() as u64,
+                consecutive_failures,
+                error: result.err(),
+                restarting,
+                next_check_secs: delay.as_secs(),
+            }));
+
+            if restarting {
+                eprintln!("[DAEMON DEBUG] {} health checks failed, requesting daemon restart", consecutive_failures);
+                if let Some(pid) = pid {
+                    DAEMON_KILL_REQUEST.store(pid, Ordering::SeqCst);
+                }
+                consecutive_failures = 0;
+            }
+        }
+    });
+}
+
+/// Whether a daemon request or a file transcription is running
+fn daemon_busy() -> bool {
+    daemon_client().is_ok_and(|client| client.in_flight() > 0) || crate::file_transcription::is_running()
+}
+
+/// Send a quick health command; returns the daemon pid, the outcome and the latency
+async fn ping() -> (Option<u32>, Result<(), String>, Duration) {
+    let started = Instant::now();
+    let client = match daemon_client() {
+        Ok(client) => client,
+        Err(e) => return (None, Err(e), started.elapsed()),
+    };
+    let options = RequestOptions::for_command("health")
+        .with_timeout(Some(PING_TIMEOUT))
+        // Failures are counted here; one slow ping must not kill the daemon
+        .with_restart_on_timeout(false);
+    let result = client
+        .request("health", serde_json::json!({ "quick": true }), &options)
+        .await
+        .and_then(|response| {
+            if response.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+                Ok(())
+            } else {
+                Err(response.get("error").and_then(|v| v.as_str()).unwrap_or("Unhealthy").to_string())
+            }
+        });
+    (Some(client.pid()), result, started.elapsed())
+}
+
+/// Regular interval: longer once the pipeline has been idle for a while
+fn next_interval() -> Duration {
+    let idle_for = APP_STATUS
+        .last_change_at()
+        .map(|at| Duration::from_millis((chrono::Utc::now().timestamp_millis() - at).max(0) as u64));
+    match idle_for {
+        Some(idle_for) if idle_for < IDLE_AFTER => ACTIVE_INTERVAL,
+        _ => IDLE_INTERVAL,
+    }
+}
+
+/// Randomly lengthen or shorten an interval by up to [`JITTER_PERCENT`]
+fn with_jitter(interval: Duration) -> Duration {
+    let random = RandomState::new().hash_one(Instant::now());
+    let percent = 100 - JITTER_PERCENT + random % (2 * JITTER_PERCENT + 1);
+    interval * percent as u32 / 100
+}
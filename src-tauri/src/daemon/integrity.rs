@@ -0,0 +1,96 @@
+//! Sidecar Integrity and Version Handshake
+//!
+//! A partial update can leave an app next to a daemon built from a different
+//! release, whose commands and events no longer match. Two checks catch this
+//! before the daemon is used:
+//!
+//! - The daemon's first frame is a `daemon_hello` log event carrying its
+//!   `protocol_version`, which must equal [`PROTOCOL_VERSION`]. A daemon that
+//!   reports "ready" without a hello predates the handshake and is rejected too.
+//! - A packaged sidecar may ship with a manifest, `worker_daemon.sha256` next
+//!   to the executable (`sha256sum` format). When present, the executable's
+//!   SHA-256 must match it before the daemon is launched. Without a manifest
+//!   (development builds) the check is skipped.
+//!
+//! Both failures are permanent for this install, so the supervisor does not
+//! retry them (see [`DAEMON_INCOMPATIBLE`](super::state::DAEMON_INCOMPATIBLE)).
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// Protocol version spoken by this app (PROTOCOL_VERSION in worker_daemon.py)
+pub const PROTOCOL_VERSION: u64 = 1;
+
+/// Extension of the checksum manifest next to the sidecar
+const MANIFEST_EXTENSION: &str = "sha256";
+
+/// Check the protocol version of a `daemon_hello` event
+pub fn check_hello(event: &serde_json::Value) -> Result<(), String> {
+    match event.get("protocol_version").and_then(|v| v.as_u64()) {
+        Some(PROTOCOL_VERSION) => Ok(()),
+        Some(version) => Err(format!(
+            "voice service speaks protocol {}, this app expects {}",
+            version, PROTOCOL_VERSION
+        )),
+        None => Err("voice service did not report a protocol version".to_string()),
+    }
+}
+
+/// Manifest path for a sidecar executable (`worker_daemon` -> `worker_daemon.sha256`)
+fn manifest_path(executable: &Path) -> PathBuf {
+    let mut name = executable.file_stem().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(MANIFEST_EXTENSION);
+    executable.with_file_name(name)
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Verify a sidecar executable against its manifest, if it has one
+pub fn verify_sidecar(executable: &Path) -> Result<(), String> {
+    let manifest = manifest_path(executable);
+    let content = match std::fs::read_to_string(&manifest) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("[DAEMON] No sidecar manifest, skipping checksum verification");
+            return Ok(());
+        }
+        Err(e) => return Err(format!("Failed to read {}: {}", manifest.display(), e)),
+    };
+
+    // "<hex digest>  <file name>" (the name is optional)
+    let expected = content
+        .split_whitespace()
+        .next()
+        .filter(|digest| digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("{} is not a SHA-256 manifest", manifest.display()))?
+        .to_ascii_lowercase();
+
+    let actual = sha256_file(executable)?;
+    if actual != expected {
+        return Err(format!(
+            "{} does not match its manifest (expected {}, found {})",
+            executable.display(),
+            expected,
+            actual
+        ));
+    }
+    println!("[DAEMON] Sidecar checksum verified");
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Framed IPC Protocol
+//!
+//! This module implements the wire protocol between Rust and the Python daemon.
+//!
+//! # Frame Format
+//!
+//! Every message in both directions is a frame:
+//! - 4-byte big-endian payload length
+//! - UTF-8 JSON envelope of exactly that length
+//!
+//! The envelope carries a `kind` so log events can never be mistaken for
+//! command responses, and an optional request `id` so responses and stream
+//! chunks can be correlated with the request that produced them:
+//!
+//! ```json
+//! {"id": 7, "kind": "request", "payload": {"command": "chat", "args": {"text": "hi"}}}
+//! {"id": 7, "kind": "stream", "payload": {"type": "chunk", "content": "Hel"}}
+//! {"id": 7, "kind": "response", "payload": {"success": true}}
+//! {"kind": "log", "payload": {"event": "model_loaded", "model": "VAD"}}
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Maximum accepted payload size (protects against reading garbage as a length)
+pub const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Request id counter (0 is never used, so it can't collide with a missing id)
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Allocate a new unique request id
+pub fn next_request_id() -> u64 {
+    NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+// ============================================================================
+// Frame Types
+// ============================================================================
+
+/// Frame kind
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameKind {
+    /// Command sent from Rust to the daemon
+    Request,
+    /// Final result of a request
+    Response,
+    /// Intermediate chunk of a streaming request
+    Stream,
+    /// Log / progress event, not tied to any request
+    Log,
+}
+
+/// IPC frame envelope
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Frame {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    pub kind: FrameKind,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+impl Frame {
+    /// Build a request frame for a daemon command
+    pub fn request(id: u64, command: &str, args: serde_json::Value) -> Self {
+        Frame {
+            id: Some(id),
+            kind: FrameKind::Request,
+            payload: serde_json::json!({
+                "command": command,
+                "args": args
+            }),
+        }
+    }
+
+    /// Check whether this frame belongs to the given request
+    pub fn is_for(&self, request_id: u64) -> bool {
+        self.id == Some(request_id)
+    }
+}
+
+// ============================================================================
+// Encoding / Decoding
+// ============================================================================
+
+/// Write a single frame and flush the writer
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, frame: &Frame) -> Result<(), String> {
+    let body = serde_json::to_vec(frame)
+        .map_err(|e| format!("Failed to serialize frame: {}", e))?;
+
+    if body.len() > MAX_FRAME_SIZE {
+        return Err(format!("Frame too large: {} bytes", body.len()));
+    }
+
+    writer.write_all(&(body.len() as u32).to_be_bytes()).await
+        .map_err(|e| format!("Failed to write frame header: {}", e))?;
+    writer.write_all(&body).await
+        .map_err(|e| format!("Failed to write frame body: {}", e))?;
+    writer.flush().await
+        .map_err(|e| format!("Failed to flush frame: {}", e))?;
+
+    Ok(())
+}
+
+/// Read a single frame
+///
+/// Returns `Ok(None)` on a clean EOF (daemon closed its output).
+/// Not cancel-safe: a partially read frame is lost if the future is dropped.
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<Frame>, String> {
+    let mut header = [0u8; 4];
+    match reader.read_exact(&mut header).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(format!("Failed to read frame header: {}", e)),
+    }
+
+    let len = u32::from_be_bytes(header) as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(format!("Frame too large: {} bytes", len));
+    }
+
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await
+        .map_err(|e| format!("Failed to read frame body: {}", e))?;
+
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse frame: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let mut buf = Vec::new();
+        let frame = Frame::request(42, "chat", serde_json::json!({"text": "多行\n文本"}));
+        block_on(write_frame(&mut buf, &frame)).unwrap();
+
+        let mut reader = std::io::Cursor::new(buf);
+        let decoded = block_on(read_frame(&mut reader)).unwrap().unwrap();
+        assert!(decoded.is_for(42));
+        assert_eq!(decoded.kind, FrameKind::Request);
+        assert_eq!(decoded.payload["args"]["text"], "多行\n文本");
+
+        // Clean EOF after the last frame
+        assert!(block_on(read_frame(&mut reader)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rejects_oversized_frame() {
+        let header = ((MAX_FRAME_SIZE + 1) as u32).to_be_bytes();
+        let mut reader = std::io::Cursor::new(header.to_vec());
+        assert!(block_on(read_frame(&mut reader)).is_err());
+    }
+}
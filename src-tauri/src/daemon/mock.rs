@@ -0,0 +1,301 @@
+//! Mock Daemon
+//!
+//! With `SPEEKIUM_MOCK_DAEMON=1` the app launches itself with
+//! [`MOCK_DAEMON_ARG`] instead of the Python worker daemon. The mock speaks
+//! the same framed IPC protocol (see [`super::ipc`]) with canned results:
+//!
+//! - ASR (`transcribe`, `ptt_audio`, ...) returns [`MOCK_TRANSCRIPT`]
+//! - chat replies are [`LOREM`], streamed sentence by sentence
+//! - TTS writes a short silent WAV; playback just waits
+//! - PTT events go to stderr as JSON lines, like the real daemon's
+//!
+//! This lets the frontend and Rust side run without a Python environment or
+//! models, and gives CI a daemon to run integration tests against.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+use super::integrity::PROTOCOL_VERSION;
+use super::ipc::{read_frame, write_frame, Frame, FrameKind};
+
+/// Argument that makes the app binary run as the mock daemon
+pub const MOCK_DAEMON_ARG: &str = "--mock-daemon";
+
+/// Environment variable that selects the mock daemon
+const MOCK_DAEMON_ENV: &str = "SPEEKIUM_MOCK_DAEMON";
+
+/// Result of every transcription
+pub const MOCK_TRANSCRIPT: &str = "This is a mock transcript.";
+
+/// Reply to every chat
+pub const LOREM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. \
+    Sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. \
+    Ut enim ad minim veniam, quis nostrud exercitation.";
+
+/// Delay between streamed sentences (and per played clip)
+const STREAM_DELAY: Duration = Duration::from_millis(150);
+
+/// Length of generated TTS clips in samples (0.5 s at 16 kHz)
+const TTS_SAMPLES: usize = 8000;
+
+/// Whether the mock daemon replaces the real one
+pub fn enabled() -> bool {
+    std::env::var(MOCK_DAEMON_ENV).is_ok_and(|v| matches!(v.trim(), "1" | "true"))
+}
+
+/// Run as the mock daemon if the process was started with [`MOCK_DAEMON_ARG`]
+pub fn run_from_args() -> Option<i32> {
+    if std::env::args().nth(1).as_deref() != Some(MOCK_DAEMON_ARG) {
+        return None;
+    }
+    let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start mock daemon: {}", e);
+            return Some(1);
+        }
+    };
+    runtime.block_on(serve(tokio::io::stdin(), tokio::io::stdout()));
+    Some(0)
+}
+
+type Output<W> = Arc<Mutex<W>>;
+
+async fn send<W: AsyncWrite + Unpin>(out: &Output<W>, kind: FrameKind, id: Option<u64>, payload: serde_json::Value) {
+    let frame = Frame { id, kind, payload };
+    let _ = write_frame(&mut *out.lock().await, &frame).await;
+}
+
+fn log(event: &str) -> serde_json::Value {
+    serde_json::json!({ "event": event })
+}
+
+/// PTT events go to stderr, like the real daemon's
+fn ptt_event(event: &str, data: serde_json::Value) {
+    let mut payload = serde_json::json!({ "ptt_event": event });
+    if let (Some(payload), Some(data)) = (payload.as_object_mut(), data.as_object()) {
+        payload.extend(data.clone());
+    }
+    eprintln!("{}", payload);
+}
+
+fn sentences() -> impl Iterator<Item = String> {
+    LOREM.split_inclusive(". ").map(|s| s.trim().to_string())
+}
+
+fn silent_clip() -> Result<String, String> {
+    crate::audio::write_temp_wav(&[0.0; TTS_SAMPLES], "mock_tts")
+}
+
+/// Serve requests until the input closes or `exit` is received; returns the writer
+pub async fn serve<R, W>(mut reader: R, writer: W) -> W
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let out: Output<W> = Arc::new(Mutex::new(writer));
+    let interrupted = Arc::new(AtomicBool::new(false));
+
+    send(&out, FrameKind::Log, None, serde_json::json!({ "event": "daemon_hello", "protocol_version": PROTOCOL_VERSION })).await;
+    send(&out, FrameKind::Log, None, log("daemon_initializing")).await;
+    send(&out, FrameKind::Log, None, serde_json::json!({ "event": "daemon_success", "message": "Mock daemon ready" })).await;
+
+    let mut tasks = JoinSet::new();
+    while let Ok(Some(frame)) = read_frame(&mut reader).await {
+        if frame.kind != FrameKind::Request {
+            continue;
+        }
+        let command = frame.payload.get("command").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let args = frame.payload.get("args").cloned().unwrap_or_default();
+
+        if command == "exit" {
+            send(&out, FrameKind::Response, frame.id, serde_json::json!({ "success": true, "message": "Daemon shutting down" })).await;
+            break;
+        }
+        if command == "interrupt" {
+            interrupted.store(true, Ordering::SeqCst);
+        }
+
+        // Requests run concurrently, like in the real daemon
+        let out = out.clone();
+        let interrupted = interrupted.clone();
+        tasks.spawn(async move {
+            let result = handle(&command, &args, frame.id, &out, &interrupted).await;
+            send(&out, FrameKind::Response, frame.id, result).await;
+        });
+    }
+
+    while tasks.join_next().await.is_some() {}
+    drop(tasks);
+    match Arc::try_unwrap(out) {
+        Ok(writer) => writer.into_inner(),
+        Err(_) => unreachable!("all request tasks have finished"),
+    }
+}
+
+/// Canned result of one command (streams and PTT events are sent along the way)
+async fn handle<W: AsyncWrite + Unpin>(
+    command: &str,
+    args: &serde_json::Value,
+    id: Option<u64>,
+    out: &Output<W>,
+    interrupted: &AtomicBool,
+) -> serde_json::Value {
+    let audio_path = args.get("audio_path").and_then(|v| v.as_str()).unwrap_or_default();
+    match command {
+        "health" => serde_json::json!({
+            "success": true,
+            "status": "healthy",
+            "models_loaded": { "vad": true, "asr": true, "llm": true },
+            "mock": true,
+        }),
+        "transcribe" | "ptt_audio" | "ptt_audio_end" => {
+            // The real daemon deletes the recordings it was sent
+            let _ = std::fs::remove_file(audio_path);
+            let emit_result = command != "transcribe" && args.get("emit_result").and_then(|v| v.as_bool()).unwrap_or(true);
+            if emit_result {
+                interrupted.store(false, Ordering::SeqCst);
+                ptt_event("user_message", serde_json::json!({ "text": MOCK_TRANSCRIPT }));
+                for sentence in sentences() {
+                    if interrupted.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    tokio::time::sleep(STREAM_DELAY).await;
+                    ptt_event("assistant_chunk", serde_json::json!({ "content": sentence }));
+                }
+                ptt_event("assistant_done", serde_json::json!({ "content": LOREM }));
+                ptt_event("idle", serde_json::json!({}));
+            }
+            serde_json::json!({ "success": true, "text": MOCK_TRANSCRIPT, "language": "en" })
+        }
+        "ptt_audio_chunk" => {
+            let _ = std::fs::remove_file(audio_path);
+            serde_json::json!({ "success": true })
+        }
+        "ptt_press" => {
+            ptt_event("recording", serde_json::json!({}));
+            serde_json::json!({ "success": true })
+        }
+        "chat" => serde_json::json!({ "success": true, "content": LOREM }),
+        "ptt_chat" => serde_json::json!({ "success": true, "text": args.get("text") }),
+        "generate_title" => serde_json::json!({ "success": true, "title": "Mock conversation" }),
+        "chat_stream" | "chat_tts_stream" => {
+            interrupted.store(false, Ordering::SeqCst);
+            let speak = command == "chat_tts_stream";
+            for sentence in sentences() {
+                if interrupted.load(Ordering::SeqCst) {
+                    send(out, FrameKind::Stream, id, serde_json::json!({ "type": "interrupted", "reason": "Interrupted" })).await;
+                    break;
+                }
+                tokio::time::sleep(STREAM_DELAY).await;
+                let kind = if speak { "text_chunk" } else { "chunk" };
+                send(out, FrameKind::Stream, id, serde_json::json!({ "type": kind, "content": sentence })).await;
+                if speak {
+                    if let Ok(path) = silent_clip() {
+                        send(out, FrameKind::Stream, id, serde_json::json!({ "type": "audio_chunk", "audio_path": path, "text": sentence })).await;
+                    }
+                }
+            }
+            send(out, FrameKind::Stream, id, serde_json::json!({ "type": "done" })).await;
+            serde_json::json!({ "success": true })
+        }
+        "tts" => match silent_clip() {
+            Ok(path) => serde_json::json!({ "success": true, "audio_path": path }),
+            Err(e) => serde_json::json!({ "success": false, "error": e }),
+        },
+        "play_audio" => {
+            if args.get("clear_interrupt").and_then(|v| v.as_bool()).unwrap_or(false) {
+                interrupted.store(false, Ordering::SeqCst);
+            }
+            if !interrupted.load(Ordering::SeqCst) {
+                tokio::time::sleep(STREAM_DELAY).await;
+            }
+            serde_json::json!({ "success": true, "interrupted": interrupted.load(Ordering::SeqCst) })
+        }
+        "list_tts_voices" => serde_json::json!({
+            "success": true,
+            "voices": [{ "id": "mock-voice", "name": "Mock Voice", "locale": "en-US", "gender": "" }],
+        }),
+        "config" => serde_json::json!({ "success": true, "config": crate::config::read_config() }),
+        "model_status" => serde_json::json!({
+            "success": true,
+            "models": {
+                "asr": { "loaded": true, "exists": true, "name": "mock", "path": "", "size": "0 B" },
+                "vad": { "loaded": true, "exists": true, "name": "mock", "path": "", "size": "0 B" },
+            },
+        }),
+        "get_daemon_state" => serde_json::json!({
+            "success": true,
+            "running": true,
+            "ptt_recording": false,
+            "interrupt_flag_set": interrupted.load(Ordering::SeqCst),
+            "models_loaded": { "vad": true, "asr": true, "llm": true, "tts": true },
+        }),
+        "interrupt" => serde_json::json!({ "success": true, "message": "Interrupt signal sent" }),
+        "save_config" | "reload_config" | "update_hotkey" | "set_recording_mode" | "set_asr_language"
+        | "ptt_release" | "record_start" => serde_json::json!({ "success": true }),
+        "record" | "record_stop" => serde_json::json!({ "success": true, "text": MOCK_TRANSCRIPT, "language": "en" }),
+        _ => serde_json::json!({ "success": false, "error": format!("Unknown command: {}", command) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn frames(bytes: Vec<u8>) -> Vec<Frame> {
+        let mut reader = std::io::Cursor::new(bytes);
+        let mut frames = Vec::new();
+        while let Some(frame) = block_on(read_frame(&mut reader)).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn test_handshake_and_chat_stream() {
+        let mut input = Vec::new();
+        block_on(write_frame(&mut input, &Frame::request(1, "health", serde_json::json!({ "quick": true })))).unwrap();
+        block_on(write_frame(&mut input, &Frame::request(2, "chat_stream", serde_json::json!({ "text": "hi" })))).unwrap();
+
+        let output = frames(block_on(serve(std::io::Cursor::new(input), Vec::new())));
+
+        assert_eq!(output[0].payload["event"], "daemon_hello");
+        assert_eq!(output[0].payload["protocol_version"], PROTOCOL_VERSION);
+        let health = output.iter().find(|f| f.is_for(1) && f.kind == FrameKind::Response).unwrap();
+        assert_eq!(health.payload["success"], true);
+
+        let streamed: String = output
+            .iter()
+            .filter(|f| f.is_for(2) && f.kind == FrameKind::Stream && f.payload["type"] == "chunk")
+            .map(|f| f.payload["content"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(streamed, LOREM);
+        assert!(output.iter().any(|f| f.is_for(2) && f.payload["type"] == "done"));
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let mut input = Vec::new();
+        block_on(write_frame(&mut input, &Frame::request(1, "no_such_command", serde_json::json!({})))).unwrap();
+
+        let output = frames(block_on(serve(std::io::Cursor::new(input), Vec::new())));
+
+        let response = output.iter().find(|f| f.is_for(1)).unwrap();
+        assert_eq!(response.payload["success"], false);
+    }
+}
@@ -7,8 +7,17 @@
 //!
 //! - [`state`] - Global state variables
 //! - [`detector`] - Daemon execution mode detection
+//! - [`integrity`] - Sidecar checksum manifest and protocol version handshake
+//! - [`mock`] - In-app mock daemon with canned results (`SPEEKIUM_MOCK_DAEMON=1`)
 //! - [`process`] - PythonDaemon struct and communication methods
+//! - [`client`] - Request multiplexing over async writer/driver tasks
+//! - [`ipc`] - Length-prefixed framed IPC protocol with request-id correlation
+//! - [`request`] - Per-request timeout, cancellation and priority options
+//! - [`health`] - Background health monitor with adaptive interval
+//! - [`resources`] - Memory / CPU sampling and memory ceiling restarts
 //! - [`startup`] - Async daemon startup and management functions
+//! - [`progress`] - Weighted startup progress from the daemon's init events
+//! - [`shutdown`] - Graceful shutdown that drains in-flight operations
 //!
 //! # Public API
 //!
@@ -17,14 +26,26 @@
 //! - `ensure_daemon_running()` - Ensure daemon is running
 //! - `is_daemon_ready()` - Check if daemon is ready
 //! - `call_daemon()` - Send command to daemon
-//! - `cleanup_daemon()` - Cleanup daemon resources
+//! - `call_daemon_async()` / `call_daemon_async_with()` - Send command from async code
+//!   without blocking a thread (optionally with custom timeout / cancellation)
+//! - `daemon_client()` - Client handle for streaming requests
+//! - `cleanup_daemon()` - Drain in-flight operations and stop the daemon
 //! - `start_daemon_async()` - Start daemon asynchronously
 //! - `start_ptt_reader()` - Start PTT event reader (re-exported from ptt module)
 
 mod state;
 mod detector;
+mod integrity;
+mod mock;
 mod process;
+pub mod client;
 mod startup;
+mod progress;
+mod shutdown;
+mod health;
+mod resources;
+pub mod ipc;
+pub mod request;
 
 // Re-export ptt module for PTT functionality
 pub use crate::ptt::start_ptt_reader;
@@ -66,9 +87,24 @@ pub use state::APP_STATUS;
 /// Current PTT shortcut string
 pub use state::CURRENT_PTT_SHORTCUT;
 
+/// Registered action shortcuts
+pub use state::ACTION_SHORTCUTS;
+
 /// PTT key state
 pub use state::PTT_KEY_PRESSED;
 
+/// PTT recording stopped by timeout flag
+pub use state::PTT_TIMED_OUT;
+
+/// PTT trigger backend state
+pub use state::{PTT_TRIGGER, INPUT_LISTENER_STARTED};
+
+/// PTT double-tap latch state
+pub use state::{PTT_LATCHED, PTT_TAP_PENDING, PTT_IGNORE_RELEASE, PTT_PRESSED_AT};
+
+/// Most recent transcript
+pub use state::LAST_TRANSCRIPT;
+
 /// Global audio recorder
 pub use state::AUDIO_RECORDER;
 
@@ -79,6 +115,8 @@ pub use state::RECORDING_MODE_CHANNEL;
 // Public API - Types
 // ============================================================================
 
+/// Daemon process handle (used directly by the headless CLI)
+pub use process::PythonDaemon;
 
 // ============================================================================
 // Public API - Functions
@@ -93,9 +131,23 @@ pub use startup::is_daemon_ready;
 /// Call daemon command and wait for response
 pub use startup::call_daemon;
 
-/// Cleanup daemon and release resources
-pub use startup::cleanup_daemon;
+/// Call daemon command from async code (optionally with custom timeout / cancellation)
+pub use startup::{call_daemon_async, call_daemon_async_with};
+
+/// Get a client handle for the running daemon
+pub use startup::daemon_client;
+
+/// Drain in-flight operations, then stop the daemon
+pub use shutdown::cleanup_daemon;
 
 /// Start daemon asynchronously
 pub use startup::start_daemon_async;
 
+/// Sync work / recording mode globals from config.json
+pub use startup::load_modes_from_config;
+
+/// Latest daemon memory / CPU sample
+pub use resources::latest_resources;
+
+/// Run as the mock daemon when started with `--mock-daemon`
+pub use mock::run_from_args as run_mock_daemon;
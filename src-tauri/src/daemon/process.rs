@@ -3,22 +3,40 @@
 //! This module contains the PythonDaemon struct which wraps the Python
 //! worker daemon process and provides methods for communication.
 
-use std::process::{Command, Stdio, Child, ChildStdin, ChildStdout};
-use std::io::{BufReader, BufWriter, Write, BufRead, Read};
-use std::sync::atomic::Ordering;
+use std::process::Stdio;
 
-use super::state::{PTT_STDERR, RECORDING_ABORTED};
+use tokio::io::{BufReader, BufWriter};
+use tokio::process::{Command, Child, ChildStdin, ChildStdout};
+
+use super::state::PTT_STDERR;
 use super::detector::detect_daemon_mode;
+use super::client::{block_on, DaemonClient};
+use super::ipc::{read_frame, FrameKind};
+use super::request::RequestOptions;
+
+/// Spawn a daemon process on the async runtime
+///
+/// Tokio child pipes are registered with the runtime's reactor when the process
+/// is spawned, so the runtime is entered even when called from a plain thread.
+/// The configured outbound proxy is passed on in the environment.
+pub fn spawn_process(command: &mut Command) -> std::io::Result<Child> {
+    let runtime = tauri::async_runtime::handle();
+    let _guard = runtime.inner().enter();
+    crate::http::apply_proxy_env(command);
+    command.spawn()
+}
 
 // ============================================================================
 // PythonDaemon Struct
 // ============================================================================
 
-/// Python daemon process wrapper with stdin/stdout communication
+/// Python daemon process wrapper with framed stdin/stdout communication (see [`super::ipc`])
+///
+/// All I/O goes through a [`DaemonClient`], which can be cloned out of the
+/// global DAEMON lock so requests never block each other.
 pub struct PythonDaemon {
     pub process: Child,
-    pub stdin: BufWriter<ChildStdin>,
-    pub stdout: BufReader<ChildStdout>,
+    client: DaemonClient,
 }
 
 // ============================================================================
@@ -43,6 +61,7 @@ impl PythonDaemon {
         let current_path = std::env::var("PATH").unwrap_or_default();
         let extra_paths = "/opt/homebrew/bin:/usr/local/bin:/usr/bin";
         let enhanced_path = format!("{}:{}", extra_paths, current_path);
+        let config_dir = crate::config::get_config_dir()?;
 
         // Build command based on mode
         let mut child = match daemon_mode {
@@ -57,15 +76,24 @@ impl PythonDaemon {
                     current_path
                 );
 
-                Command::new(&executable_path)
+                spawn_process(Command::new(&executable_path)
                     .arg("daemon")
                     .env("PATH", production_path)
+                    .env("SPEEKIUM_CONFIG_DIR", &config_dir)
                     .stdin(Stdio::piped())
                     .stdout(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .spawn()
+                    .stderr(Stdio::piped()))
                     .map_err(|e| format!("Failed to start sidecar daemon: {} (path: {:?})", e, executable_path))?
             }
+            crate::types::DaemonMode::Mock { ref executable_path } => {
+                spawn_process(Command::new(executable_path)
+                    .arg(super::mock::MOCK_DAEMON_ARG)
+                    .env("SPEEKIUM_CONFIG_DIR", &config_dir)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped()))
+                    .map_err(|e| format!("Failed to start mock daemon: {} (path: {:?})", e, executable_path))?
+            }
             crate::types::DaemonMode::Development { script_path } => {
                 // Try to use venv Python if available (in project root)
                 let project_root = script_path.parent().unwrap_or(std::path::Path::new("."));
@@ -77,14 +105,14 @@ impl PythonDaemon {
                     std::path::PathBuf::from("python3")
                 };
 
-                Command::new(&python_cmd)
+                spawn_process(Command::new(&python_cmd)
                     .arg(&script_path)
                     .arg("daemon")
                     .env("PATH", enhanced_path)
+                    .env("SPEEKIUM_CONFIG_DIR", &config_dir)
                     .stdin(Stdio::piped())
                     .stdout(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .spawn()
+                    .stderr(Stdio::piped()))
                     .map_err(|e| format!("Failed to start Python daemon: {} (python: {:?}, script: {:?})", e, python_cmd, script_path))?
             }
         };
@@ -105,136 +133,59 @@ impl PythonDaemon {
             *ptt_stderr = Some(stderr);
         }
 
-        // Wait for daemon initialization - read stdout until "ready" event
+        // Wait for daemon initialization - read frames until "ready" event
         // No timeout - let it load as long as needed (user can see download progress)
-        let mut initialized = false;
-
         loop {
-            let mut line = String::new();
-            match stdout.read_line(&mut line) {
-                Ok(0) => {
+            let frame = match block_on(read_frame(&mut stdout)) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => {
                     // EOF - daemon exited unexpectedly
                     return Err("Daemon exited during initialization".to_string());
                 }
-                Ok(_) => {
-                    eprintln!("[DAEMON DEBUG] stdout line: {}", line.trim());
-                    // Parse JSON log events
-                    if let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) {
-                        if let Some(event_type) = event.get("event").and_then(|v| v.as_str()) {
-                            // Check if this is the "ready" daemon_success event (last init event)
-                            if event_type == "daemon_success" {
-                                if let Some(message) = event.get("message").and_then(|v| v.as_str()) {
-                                    eprintln!("[DAEMON DEBUG] Got daemon_success message: {}", message);
-                                    if message.contains("就绪") || message.contains("ready") {
-                                        initialized = true;
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
                 Err(e) => {
                     return Err(format!("Failed to read daemon output: {}", e));
                 }
-            }
-        }
-
-        Ok(PythonDaemon {
-            process: child,
-            stdin,
-            stdout,
-        })
-    }
-
-    /// Send command to daemon and wait for response
-    pub fn send_command(&mut self, command: &str, args: serde_json::Value) -> Result<serde_json::Value, String> {
-        // Build request
-        let request = serde_json::json!({
-            "command": command,
-            "args": args
-        });
+            };
 
-        // Send to stdin
-        writeln!(self.stdin, "{}", request.to_string())
-            .map_err(|e| format!("Failed to write command: {}", e))?;
-
-        self.stdin.flush()
-            .map_err(|e| format!("Failed to flush stdin: {}", e))?;
-
-        // Read response from stdout, skip log events
-        // Daemon log events have "event" field, command responses have "success" field
-        loop {
-            // Check if recording should be aborted (for continuous mode)
-            if RECORDING_ABORTED.load(Ordering::SeqCst) {
-                RECORDING_ABORTED.store(false, Ordering::SeqCst);
-                return Ok(serde_json::json!({
-                    "success": false,
-                    "error": "Recording cancelled"
-                }));
+            if frame.kind != FrameKind::Log {
+                continue;
             }
 
-            let mut line = String::new();
-            self.stdout.read_line(&mut line)
-                .map_err(|e| {
-                    format!("Failed to read response: {}", e)
-                })?;
-
-            // Parse JSON
-            let result: serde_json::Value = serde_json::from_str(&line)
-                .map_err(|e| {
-                    format!("Failed to parse JSON: {}", e)
-                })?;
-
-            // Check if this is a log event (has "event" field)
-            if result.get("event").is_some() {
-                continue;  // Skip log, continue reading next line
-            }
-
-            // Skip health/status responses - wait for our actual command response
-            // Health responses have "status" field, model_status has "models" field
-            match command {
-                "model_status" => {
-                    // model_status should have "models" field
-                    if result.get("models").is_some() {
-                        return Ok(result);
-                    }
-                }
-                "health" => {
-                    // health should have "status" field
-                    if result.get("status").is_some() {
-                        return Ok(result);
-                    }
-                }
-                _ => {
-                    // For other commands, just return the first valid response
-                    if result.get("success").is_some() {
-                        return Ok(result);
+            let event = &frame.payload;
+            eprintln!("[DAEMON DEBUG] log frame: {}", event);
+            // Check if this is the "ready" daemon_success event (last init event)
+            if event.get("event").and_then(|v| v.as_str()) == Some("daemon_success") {
+                if let Some(message) = event.get("message").and_then(|v| v.as_str()) {
+                    eprintln!("[DAEMON DEBUG] Got daemon_success message: {}", message);
+                    if message.contains("就绪") || message.contains("ready") {
+                        break;
                     }
                 }
             }
-
-            // Not our expected response, keep reading
-            continue;
         }
+
+        Ok(PythonDaemon::from_parts(child, stdin, stdout))
     }
 
-    /// Send command without waiting for response (fire-and-forget)
-    pub fn send_command_no_wait(&mut self, command: &str, args: serde_json::Value) -> Result<(), String> {
-        // Build request
-        let request = serde_json::json!({
-            "command": command,
-            "args": args
-        });
+    /// Wrap an initialized daemon process and start its I/O tasks
+    pub fn from_parts(process: Child, stdin: BufWriter<ChildStdin>, stdout: BufReader<ChildStdout>) -> Self {
+        let client = DaemonClient::spawn(process.id().unwrap_or_default(), stdin, stdout);
+        PythonDaemon { process, client }
+    }
 
-        // Send to stdin
-        writeln!(self.stdin, "{}", request.to_string())
-            .map_err(|e| format!("Failed to write command: {}", e))?;
+    /// Get a client handle for this daemon (usable without holding the DAEMON lock)
+    pub fn client(&self) -> DaemonClient {
+        self.client.clone()
+    }
 
-        self.stdin.flush()
-            .map_err(|e| format!("Failed to flush stdin: {}", e))?;
+    /// Send command to daemon and wait for response using the command's default options
+    pub fn send_command(&mut self, command: &str, args: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.client.send_command_with(command, args, &RequestOptions::for_command(command))
+    }
 
-        Ok(())
+    /// Send command without waiting for response (fire-and-forget)
+    pub fn send_command_no_wait(&mut self, command: &str, args: serde_json::Value) -> Result<(), String> {
+        self.client.send_no_wait(command, args)
     }
 
     /// Check if daemon is healthy
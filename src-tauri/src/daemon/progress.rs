@@ -0,0 +1,103 @@
+//! Daemon Startup Progress
+//!
+//! During startup the daemon reports a plan and per-stage progress as log
+//! events:
+//!
+//! - `init_plan` `{stages: [{stage, weight}]}` - the stages that will run
+//!   (downloads only when the model is missing) and their share of the time
+//! - `init_progress` `{stage, pct, bytes?, total_bytes?}` - 0-100 within a stage
+//!
+//! [`InitProgress`] weighs these into one overall percentage, re-emitted as
+//! `daemon-progress` events for the splash screen. The overall value never
+//! goes backwards, even when a download discovers more files.
+
+use crate::types::DaemonProgressPayload;
+
+/// Plan used if the daemon does not send one (cached models)
+const DEFAULT_PLAN: [(&str, f64); 3] = [("vad_load", 5.0), ("asr_load", 30.0), ("llm", 5.0)];
+
+struct StageProgress {
+    stage: String,
+    weight: f64,
+    pct: f64,
+}
+
+/// Weighted startup progress over the planned stages
+pub struct InitProgress {
+    stages: Vec<StageProgress>,
+    percent: u32,
+}
+
+impl InitProgress {
+    pub fn new() -> Self {
+        InitProgress {
+            stages: DEFAULT_PLAN
+                .iter()
+                .map(|(stage, weight)| StageProgress { stage: stage.to_string(), weight: *weight, pct: 0.0 })
+                .collect(),
+            percent: 0,
+        }
+    }
+
+    /// Replace the plan with the stages of an `init_plan` event
+    pub fn set_plan(&mut self, event: &serde_json::Value) {
+        let stages: Vec<StageProgress> = event
+            .get("stages")
+            .and_then(|v| v.as_array())
+            .map(|stages| {
+                stages
+                    .iter()
+                    .filter_map(|s| {
+                        Some(StageProgress {
+                            stage: s.get("stage")?.as_str()?.to_string(),
+                            weight: s.get("weight")?.as_f64()?.max(0.0),
+                            pct: 0.0,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !stages.is_empty() {
+            self.stages = stages;
+        }
+    }
+
+    /// Apply an `init_progress` event; returns the payload to emit
+    pub fn update(&mut self, event: &serde_json::Value) -> Option<DaemonProgressPayload> {
+        let stage = event.get("stage")?.as_str()?.to_string();
+        let pct = event.get("pct")?.as_f64()?.clamp(0.0, 100.0);
+
+        match self.stages.iter_mut().find(|s| s.stage == stage) {
+            Some(entry) => entry.pct = entry.pct.max(pct),
+            // A stage outside the plan still shows, with no weight
+            None => self.stages.push(StageProgress { stage: stage.clone(), weight: 0.0, pct }),
+        }
+
+        let total: f64 = self.stages.iter().map(|s| s.weight).sum();
+        if total > 0.0 {
+            let done: f64 = self.stages.iter().map(|s| s.weight * s.pct / 100.0).sum();
+            // 100 is reserved for "ready"
+            self.percent = self.percent.max(((done / total * 100.0) as u32).min(99));
+        }
+
+        Some(DaemonProgressPayload {
+            stage,
+            stage_percent: pct as u32,
+            percent: self.percent,
+            bytes: event.get("bytes").and_then(|v| v.as_u64()),
+            total_bytes: event.get("total_bytes").and_then(|v| v.as_u64()),
+        })
+    }
+
+    /// Payload for a fully started daemon
+    pub fn complete(&mut self) -> DaemonProgressPayload {
+        self.percent = 100;
+        DaemonProgressPayload {
+            stage: "complete".to_string(),
+            stage_percent: 100,
+            percent: 100,
+            bytes: None,
+            total_bytes: None,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Daemon Request Options
+//!
+//! Per-request timeout, cancellation and priority for commands sent to the daemon.
+//!
+//! A timed-out request usually means the daemon is wedged (e.g. a blocking
+//! ASR call), so by default it also asks the supervisor to kill the daemon,
+//! which is then restarted.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Maximum gap between two frames of a streaming response
+pub const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Cancellation tokens of requests currently waiting in `call_daemon`
+static ACTIVE_REQUESTS: Mutex<Option<HashMap<u64, CancelToken>>> = Mutex::new(None);
+
+/// Keys of ACTIVE_REQUESTS entries that are streaming chats
+static ACTIVE_STREAMS: Mutex<Vec<u64>> = Mutex::new(Vec::new());
+
+/// Key counter for ACTIVE_REQUESTS
+static NEXT_ACTIVE_KEY: AtomicU64 = AtomicU64::new(1);
+
+/// How often [`CancelToken::cancelled`] checks the flag
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+// ============================================================================
+// Cancel Token
+// ============================================================================
+
+/// Shared flag used to abandon a pending request
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the token is cancelled (for use in `select!`)
+    pub async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(CANCEL_CHECK_INTERVAL).await;
+        }
+    }
+}
+
+// ============================================================================
+// Command Priority
+// ============================================================================
+
+/// Order in which queued commands are written to the daemon (highest first)
+///
+/// Interactive work jumps ahead of housekeeping; commands of the same
+/// priority keep their order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommandPriority {
+    /// Housekeeping: health checks, state and model queries, config reloads
+    Housekeeping,
+    /// Chats, TTS and other requests the user is waiting on
+    Chat,
+    /// Interrupts and shutdown
+    Interrupt,
+    /// PTT audio and recording control
+    Audio,
+}
+
+impl CommandPriority {
+    pub fn for_command(command: &str) -> Self {
+        match command {
+            "ptt_press" | "ptt_release" | "ptt_audio" | "ptt_audio_chunk" | "ptt_audio_end" | "record"
+            | "record_stop" | "transcribe" => CommandPriority::Audio,
+            "interrupt" | "exit" => CommandPriority::Interrupt,
+            "health" | "model_status" | "get_daemon_state" | "config" | "reload_config" | "update_hotkey"
+            | "generate_title" => CommandPriority::Housekeeping,
+            _ => CommandPriority::Chat,
+        }
+    }
+}
+
+/// Whether RECORDING_ABORTED cancels the command (a recording in progress)
+///
+/// Other requests run concurrently and must not take an abort meant for it.
+pub fn stops_on_recording_abort(command: &str) -> bool {
+    command == "record"
+}
+
+// ============================================================================
+// Request Options
+// ============================================================================
+
+/// Options controlling how long to wait for a daemon response
+#[derive(Debug, Clone)]
+pub struct RequestOptions {
+    /// Maximum time to wait for the response (None = wait forever)
+    pub timeout: Option<Duration>,
+    /// Token to abandon the request early
+    pub cancel: Option<CancelToken>,
+    /// Kill the daemon on timeout so the supervisor restarts it
+    pub restart_on_timeout: bool,
+}
+
+impl RequestOptions {
+    /// Default options for a command
+    pub fn for_command(command: &str) -> Self {
+        RequestOptions {
+            timeout: Some(default_timeout(command)),
+            cancel: None,
+            restart_on_timeout: true,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn with_restart_on_timeout(mut self, restart: bool) -> Self {
+        self.restart_on_timeout = restart;
+        self
+    }
+}
+
+/// Default response timeout per command type
+pub fn default_timeout(command: &str) -> Duration {
+    match command {
+        // Quick state queries
+        "health" | "config" | "save_config" | "reload_config" | "update_hotkey" | "model_status"
+        | "get_daemon_state" | "set_recording_mode" | "interrupt" | "exit" => Duration::from_secs(15),
+        // Continuous recording waits for speech before it starts
+        "record" => Duration::from_secs(300),
+        // ASR on recorded audio
+        "ptt_audio" | "ptt_audio_end" | "record_stop" => Duration::from_secs(120),
+        // LLM / TTS generation
+        "chat" | "tts" | "generate_title" => Duration::from_secs(180),
+        // Playback of one reply sentence
+        "play_audio" => Duration::from_secs(180),
+        _ => Duration::from_secs(60),
+    }
+}
+
+/// Track a waiting request so it can be cancelled; returns a key for [`unregister_request`]
+pub fn register_request(token: CancelToken) -> u64 {
+    let key = NEXT_ACTIVE_KEY.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut active) = ACTIVE_REQUESTS.lock() {
+        active.get_or_insert_with(HashMap::new).insert(key, token);
+    }
+    key
+}
+
+/// Track a streaming chat; it is cancelled by [`cancel_active_streams`] as well
+pub fn register_stream(token: CancelToken) -> u64 {
+    let key = register_request(token);
+    if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+        streams.push(key);
+    }
+    key
+}
+
+/// Stop tracking a request (or stream) once it has finished
+pub fn unregister_request(key: u64) {
+    if let Ok(mut active) = ACTIVE_REQUESTS.lock() {
+        if let Some(map) = active.as_mut() {
+            map.remove(&key);
+        }
+    }
+    if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+        streams.retain(|k| *k != key);
+    }
+}
+
+/// Number of requests currently waiting for a daemon response
+pub fn active_request_count() -> usize {
+    ACTIVE_REQUESTS
+        .lock()
+        .map(|active| active.as_ref().map_or(0, HashMap::len))
+        .unwrap_or(0)
+}
+
+/// Cancel the streaming chats in progress (other requests keep waiting)
+///
+/// Returns the number of cancelled streams.
+pub fn cancel_active_streams() -> usize {
+    let streams = match ACTIVE_STREAMS.lock() {
+        Ok(streams) => streams.clone(),
+        Err(_) => return 0,
+    };
+    match ACTIVE_REQUESTS.lock() {
+        Ok(active) => active.as_ref().map_or(0, |map| {
+            streams
+                .iter()
+                .filter_map(|key| map.get(key))
+                .inspect(|token| token.cancel())
+                .count()
+        }),
+        Err(_) => 0,
+    }
+}
+
+/// Cancel every request currently waiting for a daemon response
+///
+/// Returns the number of cancelled requests.
+pub fn cancel_active_requests() -> usize {
+    match ACTIVE_REQUESTS.lock() {
+        Ok(active) => active.as_ref().map_or(0, |map| {
+            map.values().for_each(CancelToken::cancel);
+            map.len()
+        }),
+        Err(_) => 0,
+    }
+}
@@ -0,0 +1,135 @@
+//! Daemon Resource Monitor
+//!
+//! Samples the daemon's resident memory and CPU usage every
+//! [`SAMPLE_INTERVAL`], keeps the latest sample for `get_daemon_resources`
+//! and stores each one in the `daemon_resource_metrics` table (the last
+//! [`KEEP_SAMPLES`] of history).
+//!
+//! With `daemon_memory_limit_mb` set, a daemon whose memory grows above the
+//! limit (e.g. leaking model caches) is restarted. The restart waits until no
+//! request is in flight and the pipeline is idle, so a reply is never cut off;
+//! the supervisor then starts a fresh daemon.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::Manager;
+
+use crate::database::ResourceSample;
+use crate::state::AppState;
+use crate::types::DaemonResources;
+
+use super::request::active_request_count;
+use super::startup::{daemon_client, is_daemon_ready};
+use super::state::{APP_STATUS, DAEMON_KILL_REQUEST, DAEMON_SHUTTING_DOWN, STREAMING_IN_PROGRESS};
+
+/// Interval between samples
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How far back stored samples are kept
+const KEEP_SAMPLES: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Config key of the memory ceiling in MB (0 = no limit)
+pub const MEMORY_LIMIT_KEY: &str = "daemon_memory_limit_mb";
+
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Most recent sample
+static LATEST: Mutex<Option<DaemonResources>> = Mutex::new(None);
+
+/// Previous CPU reading of the sampled daemon
+struct CpuReading {
+    pid: u32,
+    cpu_secs: f64,
+    at: Instant,
+}
+
+fn memory_limit_mb() -> u64 {
+    crate::config::read_config_value(MEMORY_LIMIT_KEY)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
+
+/// Latest daemon resource sample (None before the first one)
+pub fn latest_resources() -> Option<DaemonResources> {
+    LATEST.lock().ok().and_then(|latest| latest.clone())
+}
+
+/// Start the resource monitor (once per app run)
+pub fn start_resource_monitor(app_handle: tauri::AppHandle) {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let mut previous: Option<CpuReading> = None;
+        let mut restart_pending = false;
+
+        loop {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+            if DAEMON_SHUTTING_DOWN.load(Ordering::SeqCst) {
+                return;
+            }
+            if !is_daemon_ready() {
+                continue;
+            }
+            let Ok(client) = daemon_client() else {
+                continue;
+            };
+            let pid = client.pid();
+            let Some(usage) = crate::platform::process_usage(pid) else {
+                continue;
+            };
+
+            // A restarted daemon starts over
+            if previous.as_ref().is_some_and(|p| p.pid != pid) {
+                previous = None;
+                restart_pending = false;
+            }
+            let now = Instant::now();
+            let cpu_percent = previous.as_ref().map(|p| {
+                let wall = now.duration_since(p.at).as_secs_f64();
+                ((usage.cpu_secs - p.cpu_secs).max(0.0) / wall * 100.0 * 10.0).round() / 10.0
+            });
+            previous = Some(CpuReading { pid, cpu_secs: usage.cpu_secs, at: now });
+
+            let limit_mb = memory_limit_mb();
+            if limit_mb > 0 && usage.rss_bytes > limit_mb * 1024 * 1024 && !restart_pending {
+                println!(
+                    "[DAEMON RESOURCES] Daemon uses {} MB (limit {} MB), restarting once idle",
+                    usage.rss_bytes / (1024 * 1024),
+                    limit_mb
+                );
+                restart_pending = true;
+            }
+
+            let sampled_at = chrono::Utc::now().timestamp_millis();
+            if let Ok(mut latest) = LATEST.lock() {
+                *latest = Some(DaemonResources {
+                    pid,
+                    rss_bytes: usage.rss_bytes,
+                    cpu_percent,
+                    sampled_at,
+                    memory_limit_mb: limit_mb,
+                    restart_pending,
+                });
+            }
+
+            if let Some(state) = app_handle.try_state::<AppState>() {
+                let sample = ResourceSample { created_at: sampled_at, pid, rss_bytes: usage.rss_bytes, cpu_percent };
+                let keep_since = sampled_at - KEEP_SAMPLES.as_millis() as i64;
+                if let Err(e) = state.db.add_resource_sample(&sample, keep_since) {
+                    eprintln!("[DAEMON RESOURCES] Failed to store sample: {}", e);
+                }
+            }
+
+            let idle = active_request_count() == 0
+                && !STREAMING_IN_PROGRESS.load(Ordering::SeqCst)
+                && APP_STATUS.current().is_idle();
+            if restart_pending && idle {
+                eprintln!("[DAEMON RESOURCES] Restarting daemon {} over its memory limit", pid);
+                DAEMON_KILL_REQUEST.store(pid, Ordering::SeqCst);
+            }
+        }
+    });
+}
@@ -0,0 +1,130 @@
+//! Graceful Shutdown
+//!
+//! Drains in-flight work before the daemon is stopped on app exit, in order:
+//! 1. Wait for an active recording to finish (app exit is priority 3, which
+//!    never interrupts a recording)
+//! 2. Interrupt generation and playback, cancel waiting requests and let
+//!    streams wind down
+//! 3. Flush pending database writes
+//! 4. Send `exit` and wait for the process, killing it after a bounded wait
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use tauri::Manager;
+
+use crate::state::AppState;
+use crate::types::AppStatus;
+
+use super::state::{
+    DAEMON, DAEMON_SHUTTING_DOWN, APP_HANDLE, APP_STATUS, STREAMING_IN_PROGRESS, PTT_PROCESSING,
+};
+#[cfg(target_os = "macos")]
+use super::state::AUDIO_RECORDER;
+use super::client::block_on;
+use super::request::cancel_active_requests;
+
+/// Interrupt priority used for app exit (see [`AppStatus::can_be_interrupted`])
+const EXIT_PRIORITY: u8 = 3;
+
+/// Maximum time to wait for an active recording to finish
+const RECORDING_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum time to wait for streams to stop after the interrupt
+const STREAM_DRAIN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Maximum time to wait for the daemon to exit before killing it
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often drain conditions are re-checked
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Set once shutdown has started (tray quit and ExitRequested both trigger it)
+static SHUTDOWN_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Current app status
+fn current_status() -> AppStatus {
+    APP_STATUS.current()
+}
+
+/// Poll `done` until it returns true or `timeout` elapses; returns whether it finished
+fn wait_until(timeout: Duration, done: impl Fn() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while !done() {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(DRAIN_POLL_INTERVAL);
+    }
+    true
+}
+
+/// Shut down the daemon after draining in-flight operations
+///
+/// Safe to call more than once; only the first call does the work.
+pub fn cleanup_daemon() {
+    if SHUTDOWN_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    // Tell the supervisor this exit is intentional
+    DAEMON_SHUTTING_DOWN.store(true, Ordering::SeqCst);
+
+    // 1. Let an active recording finish so the user's speech isn't lost
+    if !current_status().can_be_interrupted(EXIT_PRIORITY)
+        && !wait_until(RECORDING_DRAIN_TIMEOUT, || current_status().can_be_interrupted(EXIT_PRIORITY))
+    {
+        eprintln!("[SHUTDOWN] Recording still active after {}s, stopping anyway", RECORDING_DRAIN_TIMEOUT.as_secs());
+    }
+
+    // Release the microphone
+    #[cfg(target_os = "macos")]
+    {
+        let mut recorder = AUDIO_RECORDER.lock().unwrap();
+        if let Some(mut audio_rec) = recorder.take() {
+            if audio_rec.is_recording() {
+                let _ = audio_rec.stop_recording();
+            }
+        }
+    }
+
+    // 2. Stop generation and playback, then let streams end on their own
+    let daemon_busy = !current_status().is_idle()
+        || STREAMING_IN_PROGRESS.load(Ordering::SeqCst)
+        || PTT_PROCESSING.load(Ordering::SeqCst);
+    if daemon_busy {
+        if let Ok(client) = super::startup::daemon_client() {
+            let _ = client.send_no_wait("interrupt", serde_json::json!({"priority": EXIT_PRIORITY}));
+        }
+    }
+    let cancelled = cancel_active_requests();
+    if cancelled > 0 {
+        eprintln!("[SHUTDOWN] Cancelled {} pending request(s)", cancelled);
+    }
+    if !wait_until(STREAM_DRAIN_TIMEOUT, || !STREAMING_IN_PROGRESS.load(Ordering::SeqCst)) {
+        eprintln!("[SHUTDOWN] Stream still active after interrupt");
+    }
+
+    // 3. Store the interaction in progress and wait for pending DB writes
+    if let Some(app) = APP_HANDLE.get() {
+        crate::metrics::finish_interaction(app);
+        if let Some(state) = app.try_state::<AppState>() {
+            if let Err(e) = state.db.flush() {
+                eprintln!("[SHUTDOWN] {}", e);
+            }
+        }
+    }
+
+    // 4. Ask the daemon to exit, kill it if it doesn't within the bound
+    let daemon = DAEMON.lock().ok().and_then(|mut d| d.take());
+    if let Some(mut d) = daemon {
+        let _ = d.send_command_no_wait("exit", serde_json::json!({}));
+
+        let exited = block_on(tokio::time::timeout(EXIT_TIMEOUT, d.process.wait()));
+        if exited.is_err() {
+            eprintln!("[SHUTDOWN] Daemon did not exit within {}s, killing it", EXIT_TIMEOUT.as_secs());
+            let _ = d.process.start_kill();
+            let _ = block_on(d.process.wait());
+        }
+    }
+}
@@ -3,23 +3,36 @@
 //! This module contains functions for daemon lifecycle management:
 //! - Async daemon startup with progress reporting
 //! - Daemon health checks
-//! - Daemon cleanup
+//! - Crash supervision with automatic restart (exponential backoff)
+//! - Sidecar verification and protocol handshake (see [`super::integrity`])
 
-use std::process::{Command, Stdio};
-use std::io::{BufReader, BufWriter, BufRead};
+use std::process::Stdio;
+use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 
-use tauri::{Emitter, Manager};
-use crate::types::{DaemonMode, DaemonStatusPayload, DownloadProgressPayload, ModelLoadingPayload};
+use tauri::{Manager};
+use tokio::io::{BufReader, BufWriter};
+use tokio::process::Command;
+use crate::types::{
+    AppStatus, DaemonMode, DaemonStatusPayload, DownloadProgressPayload, ModelLoadingPayload,
+    RecordingMode, WorkMode,
+};
 use crate::ui;
+use crate::diagnostics;
 
 use super::state::{
-    DAEMON, DAEMON_READY, PTT_STDERR, STREAMING_IN_PROGRESS,
-    APP_HANDLE, WORK_MODE, RECORDING_MODE, AUDIO_RECORDER,
+    DAEMON, DAEMON_READY, DAEMON_SHUTTING_DOWN, DAEMON_KILL_REQUEST, DAEMON_INCOMPATIBLE, PTT_STDERR, STREAMING_IN_PROGRESS,
+    APP_HANDLE, WORK_MODE, RECORDING_MODE, APP_STATUS, PTT_PROCESSING,
 };
-use super::process::PythonDaemon;
+use super::process::{spawn_process, PythonDaemon};
+use super::client::{block_on, DaemonClient};
+use super::request::{register_request, unregister_request, RequestOptions};
 use super::detector::detect_daemon_mode;
+use super::integrity;
+use super::ipc::{read_frame, FrameKind};
+use super::progress::InitProgress;
+use crate::events::{emit_event, AppEvent};
 
 // ============================================================================
 // Daemon Management Functions
@@ -31,25 +44,20 @@ pub fn ensure_daemon_running() -> Result<(), String> {
 
     // If daemon exists, check health first
     if let Some(ref mut d) = *daemon {
-        // Skip health check during streaming
-        if STREAMING_IN_PROGRESS.load(Ordering::SeqCst) {
-            return Ok(());
-        }
-
         if d.health_check() {
             // Daemon is already running and healthy
             // Send ready event to frontend in case it's waiting
             if let Some(handle) = APP_HANDLE.get() {
-                let _ = handle.emit("daemon-status", DaemonStatusPayload {
+                emit_event(handle, AppEvent::DaemonStatus(DaemonStatusPayload {
                     status: "ready".to_string(),
                     message: ui::get_daemon_message("ready"),
-                });
+                }));
             }
             return Ok(());  // Healthy, return directly
         }
 
         // Unhealthy, terminate and restart
-        let _ = d.process.kill();
+        let _ = d.process.start_kill();
     }
 
     // Start new daemon
@@ -64,43 +72,53 @@ pub fn is_daemon_ready() -> bool {
     ready
 }
 
-/// Call daemon command and wait for response
+/// Call daemon command and wait for response (default timeout for the command)
+///
+/// Blocks the calling thread; async commands should use [`call_daemon_async`].
 pub fn call_daemon(command: &str, args: serde_json::Value) -> Result<serde_json::Value, String> {
+    block_on(call_daemon_async(command, args))
+}
+
+/// Call daemon command from async code (default timeout for the command)
+pub async fn call_daemon_async(command: &str, args: serde_json::Value) -> Result<serde_json::Value, String> {
+    call_daemon_async_with(command, args, RequestOptions::for_command(command)).await
+}
+
+/// Call daemon command from async code with explicit timeout / cancellation options
+///
+/// The request can also be cancelled via [`super::request::cancel_active_requests`].
+pub async fn call_daemon_async_with(
+    command: &str,
+    args: serde_json::Value,
+    options: RequestOptions,
+) -> Result<serde_json::Value, String> {
     // Wait for daemon to be ready (no timeout - user can see download progress)
     while !is_daemon_ready() {
-        std::thread::sleep(Duration::from_millis(100));
+        tokio::time::sleep(Duration::from_millis(100)).await;
     }
 
-    let mut daemon = DAEMON.lock().unwrap();
-    let daemon = daemon.as_mut().ok_or("Daemon not available")?;
+    let client = daemon_client()?;
 
-    daemon.send_command(command, args)
-}
+    // Register a cancel token so the request can be abandoned from another thread
+    let cancel = options.cancel.clone().unwrap_or_default();
+    let options = options.with_cancel(cancel.clone());
+    let key = register_request(cancel);
 
-/// Cleanup daemon and release resources
-pub fn cleanup_daemon() {
-    // First, clean up AUDIO_RECORDER to release the microphone
-    {
-        #[cfg(target_os = "macos")]
-        {
-            let mut recorder = AUDIO_RECORDER.lock().unwrap();
-            if let Some(mut audio_rec) = recorder.take() {
-                if audio_rec.is_recording() {
-                    let _ = audio_rec.stop_recording();
-                }
-            }
-        }
-    }
+    let result = client.request(command, args, &options).await;
 
-    // Then clean up the daemon
-    let mut daemon = DAEMON.lock().unwrap();
-    if let Some(mut d) = daemon.take() {
-        // Send exit command
-        let _ = d.send_command("exit", serde_json::json!({}));
+    unregister_request(key);
+    result
+}
 
-        // Wait for process to exit
-        let _ = d.process.wait();
-    }
+/// Get a client handle for the running daemon
+///
+/// The DAEMON lock is only held while cloning the handle.
+pub fn daemon_client() -> Result<DaemonClient, String> {
+    let daemon = DAEMON.lock()
+        .map_err(|e| format!("DAEMON lock poisoned: {}", e))?;
+    daemon.as_ref()
+        .map(PythonDaemon::client)
+        .ok_or_else(|| "Daemon not available".to_string())
 }
 
 // ============================================================================
@@ -110,385 +128,629 @@ pub fn cleanup_daemon() {
 /// Start daemon asynchronously with status events to frontend
 ///
 /// This allows the UI to show immediately while daemon loads in background.
-/// `on_ready` is an optional callback called after daemon is fully initialized.
+/// `on_ready` is an optional callback called after daemon is fully initialized,
+/// and again after every automatic restart (see [`supervise_daemon`]).
 ///
 /// # Progress Events
 /// - `loading` with "正在启动语音服务..." message
 /// - `loading` with various progress messages during model loading
 /// - `ready` with "就绪" message when daemon is ready
 /// - `error` if startup fails
+/// - `restarting` when the daemon crashed and is being restarted
 pub fn start_daemon_async(app_handle: tauri::AppHandle, on_ready: Option<impl Fn() + Send + Sync + 'static>) {
+    let on_ready: Option<Arc<dyn Fn() + Send + Sync>> = on_ready.map(|f| Arc::new(f) as Arc<dyn Fn() + Send + Sync>);
+
+    // Pings the daemon whenever it is ready, for the rest of the app lifetime
+    super::health::start_health_monitor(app_handle.clone());
+    // Samples memory / CPU and enforces daemon_memory_limit_mb
+    super::resources::start_resource_monitor(app_handle.clone());
+
     std::thread::spawn(move || {
-        // Send initial loading status
-        let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-            status: "loading".to_string(),
-            message: ui::get_daemon_message("starting"),
-        });
-
-        // Detect execution mode
-        let daemon_mode = match detect_daemon_mode() {
-            Ok(mode) => mode,
-            Err(e) => {
-                eprintln!("[DAEMON DEBUG] detect_daemon_mode failed: {}", e);
-                let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                    status: "error".to_string(),
-                    message: format!("{}: {}", ui::get_daemon_message("startup_failed"), e),
-                });
-                return;
+        if launch_daemon(&app_handle) {
+            // Call on_ready callback if provided (e.g., to register PTT shortcuts)
+            if let Some(ref callback) = on_ready {
+                callback();
             }
-        };
+        }
+
+        // Watch the daemon for unexpected exits for the rest of the app lifetime
+        supervise_daemon(&app_handle, on_ready);
+    });
+}
+
+/// Spawn the daemon process and wait until it is fully initialized
+///
+/// Emits `daemon-status`, `download-progress` and `model-loading` events along the way.
+/// Returns `true` once the daemon is stored in [`DAEMON`] and marked ready.
+fn launch_daemon(app_handle: &tauri::AppHandle) -> bool {
+    diagnostics::start_init_timeline();
+
+    // Send initial loading status
+    emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+        status: "loading".to_string(),
+        message: ui::get_daemon_message("starting"),
+    }));
+
+    // Detect execution mode
+    let daemon_mode = match detect_daemon_mode() {
+        Ok(mode) => mode,
+        Err(e) => {
+            eprintln!("[DAEMON DEBUG] detect_daemon_mode failed: {}", e);
+            emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                status: "error".to_string(),
+                message: format!("{}: {}", ui::get_daemon_message("startup_failed"), e),
+            }));
+            return false;
+        }
+    };
+
+    eprintln!("[DAEMON DEBUG] Detected mode: {:?}", daemon_mode);
+
+    // Get config directory for daemon
+    let config_dir = match app_handle.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                status: "error".to_string(),
+                message: format!("{}: {}", ui::get_daemon_message("config_dir_error"), e),
+            }));
+            return false;
+        }
+    };
 
-        eprintln!("[DAEMON DEBUG] Detected mode: {:?}", daemon_mode);
+    // Build PATH environment variable
+    let current_path = std::env::var("PATH").unwrap_or_default();
+    let extra_paths = "/opt/homebrew/bin:/usr/local/bin:/usr/bin";
+    let enhanced_path = format!("{}:{}", extra_paths, current_path);
 
-        // Get config directory for daemon
-        let config_dir = match app_handle.path().app_data_dir() {
-            Ok(dir) => dir,
-            Err(e) => {
-                let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
+    // Convert config_dir to string for environment variable
+    let config_dir_str = config_dir.to_string_lossy().to_string();
+
+    eprintln!("[DAEMON DEBUG] Config dir: {}", config_dir_str);
+
+    // Build command based on mode
+    let mut child = match daemon_mode {
+        DaemonMode::Production { ref executable_path } => {
+            if let Err(e) = integrity::verify_sidecar(executable_path) {
+                eprintln!("[DAEMON] Sidecar verification failed: {}", e);
+                DAEMON_INCOMPATIBLE.store(true, Ordering::SeqCst);
+                emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
                     status: "error".to_string(),
-                    message: format!("{}: {}", ui::get_daemon_message("config_dir_error"), e),
-                });
-                return;
+                    message: ui::get_daemon_message("sidecar_integrity_failed"),
+                }));
+                return false;
             }
-        };
 
-        // Build PATH environment variable
-        let current_path = std::env::var("PATH").unwrap_or_default();
-        let extra_paths = "/opt/homebrew/bin:/usr/local/bin:/usr/bin";
-        let enhanced_path = format!("{}:{}", extra_paths, current_path);
-
-        // Convert config_dir to string for environment variable
-        let config_dir_str = config_dir.to_string_lossy().to_string();
-
-        eprintln!("[DAEMON DEBUG] Config dir: {}", config_dir_str);
-
-        // Build command based on mode
-        let mut child = match daemon_mode {
-            DaemonMode::Production { ref executable_path } => {
-                let internal_dir = executable_path.parent()
-                    .map(|p| p.join("_internal"))
-                    .unwrap_or_default();
-                let production_path = format!("{}:{}:{}",
-                    internal_dir.display(),
-                    extra_paths,
-                    current_path
-                );
-
-                match Command::new(&executable_path)
-                    .arg("daemon")
-                    .env("PATH", production_path)
-                    .env("SPEEKIUM_CONFIG_DIR", &config_dir_str)
-                    .stdin(Stdio::piped())
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .spawn()
-                {
-                    Ok(child) => child,
-                    Err(e) => {
-                        let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                            status: "error".to_string(),
-                            message: format!("{}: {}", ui::get_daemon_message("startup_failed"), e),
-                        });
-                        return;
-                    }
+            let internal_dir = executable_path.parent()
+                .map(|p| p.join("_internal"))
+                .unwrap_or_default();
+            let production_path = format!("{}:{}:{}",
+                internal_dir.display(),
+                extra_paths,
+                current_path
+            );
+
+            match spawn_process(Command::new(&executable_path)
+                .arg("daemon")
+                .env("PATH", production_path)
+                .env("SPEEKIUM_CONFIG_DIR", &config_dir_str)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()))
+            {
+                Ok(child) => child,
+                Err(e) => {
+                    emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                        status: "error".to_string(),
+                        message: format!("{}: {}", ui::get_daemon_message("startup_failed"), e),
+                    }));
+                    return false;
                 }
             }
-            DaemonMode::Development { script_path } => {
-                let project_root = script_path.parent().unwrap_or(std::path::Path::new("."));
-                let venv_python = project_root.join(".venv/bin/python3");
-                // Use venv Python if available, fallback to system Python
-                let python_cmd = if venv_python.exists() {
-                    venv_python
-                } else {
-                    std::path::PathBuf::from("python3")
-                };
-
-                eprintln!("[DAEMON DEBUG] Spawning: {:?} {:?} daemon", python_cmd, script_path);
-
-                match Command::new(&python_cmd)
-                    .arg(&script_path)
-                    .arg("daemon")
-                    .env("PATH", enhanced_path)
-                    .env("SPEEKIUM_CONFIG_DIR", &config_dir_str)
-                    .stdin(Stdio::piped())
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .spawn()
-                {
-                    Ok(child) => {
-                        eprintln!("[DAEMON DEBUG] Spawn succeeded, pid: {:?}", child.id());
-                        child
-                    },
-                    Err(e) => {
-                        eprintln!("[DAEMON DEBUG] Spawn failed: {}", e);
-                        let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                            status: "error".to_string(),
-                            message: format!("{}: {}", ui::get_daemon_message("startup_failed"), e),
-                        });
-                        return;
-                    }
+        }
+        DaemonMode::Mock { ref executable_path } => {
+            match spawn_process(Command::new(executable_path)
+                .arg(super::mock::MOCK_DAEMON_ARG)
+                .env("SPEEKIUM_CONFIG_DIR", &config_dir_str)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()))
+            {
+                Ok(child) => child,
+                Err(e) => {
+                    emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                        status: "error".to_string(),
+                        message: format!("{}: {}", ui::get_daemon_message("startup_failed"), e),
+                    }));
+                    return false;
                 }
             }
-        };
-
-        // Get stdin/stdout/stderr
-        let stdin = match child.stdin.take() {
-            Some(s) => BufWriter::new(s),
-            None => {
-                let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                    status: "error".to_string(),
-                    message: ui::get_daemon_message("stdin_error"),
-                });
-                return;
-            }
-        };
-        let mut stdout = match child.stdout.take() {
-            Some(s) => BufReader::new(s),
-            None => {
-                let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                    status: "error".to_string(),
-                    message: ui::get_daemon_message("stdout_error"),
-                });
-                return;
-            }
-        };
-        let stderr = match child.stderr.take() {
-            Some(s) => BufReader::new(s),
-            None => {
-                let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                    status: "error".to_string(),
-                    message: ui::get_daemon_message("stderr_error"),
-                });
-                return;
+        }
+        DaemonMode::Development { script_path } => {
+            let project_root = script_path.parent().unwrap_or(std::path::Path::new("."));
+            let venv_python = project_root.join(".venv/bin/python3");
+            // Use venv Python if available, fallback to system Python
+            let python_cmd = if venv_python.exists() {
+                venv_python
+            } else {
+                std::path::PathBuf::from("python3")
+            };
+
+            eprintln!("[DAEMON DEBUG] Spawning: {:?} {:?} daemon", python_cmd, script_path);
+
+            match spawn_process(Command::new(&python_cmd)
+                .arg(&script_path)
+                .arg("daemon")
+                .env("PATH", enhanced_path)
+                .env("SPEEKIUM_CONFIG_DIR", &config_dir_str)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()))
+            {
+                Ok(child) => {
+                    eprintln!("[DAEMON DEBUG] Spawn succeeded, pid: {:?}", child.id());
+                    child
+                },
+                Err(e) => {
+                    eprintln!("[DAEMON DEBUG] Spawn failed: {}", e);
+                    emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                        status: "error".to_string(),
+                        message: format!("{}: {}", ui::get_daemon_message("startup_failed"), e),
+                    }));
+                    return false;
+                }
             }
-        };
-
-        // Store stderr for PTT event reader
-        {
-            let mut ptt_stderr = PTT_STDERR.lock().unwrap();
-            *ptt_stderr = Some(stderr);
         }
+    };
+
+    // Get stdin/stdout/stderr
+    let stdin = match child.stdin.take() {
+        Some(s) => BufWriter::new(s),
+        None => {
+            emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                status: "error".to_string(),
+                message: ui::get_daemon_message("stdin_error"),
+            }));
+            return false;
+        }
+    };
+    let mut stdout = match child.stdout.take() {
+        Some(s) => BufReader::new(s),
+        None => {
+            emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                status: "error".to_string(),
+                message: ui::get_daemon_message("stdout_error"),
+            }));
+            return false;
+        }
+    };
+    let stderr = match child.stderr.take() {
+        Some(s) => BufReader::new(s),
+        None => {
+            emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                status: "error".to_string(),
+                message: ui::get_daemon_message("stderr_error"),
+            }));
+            return false;
+        }
+    };
 
-        // Wait for daemon initialization with progress updates
-        // No timeout - let it load as long as needed
-        let mut initialized = false;
+    // Store stderr for PTT event reader
+    {
+        let mut ptt_stderr = PTT_STDERR.lock().unwrap();
+        *ptt_stderr = Some(stderr);
+    }
 
-        loop {
-            let mut line = String::new();
-            match stdout.read_line(&mut line) {
-                Ok(0) => {
-                    // EOF - daemon exited
-                    eprintln!("[DAEMON DEBUG] EOF received, daemon exited");
-                    let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                        status: "error".to_string(),
-                        message: ui::get_daemon_message("daemon_exited"),
-                    });
-                    return;
-                }
-                Ok(_) => {
-                    eprintln!("[DAEMON DEBUG] stdout: {}", line.trim());
-                    // Parse JSON log events and forward status to frontend
-                    if let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) {
-                        if let Some(event_type) = event.get("event").and_then(|v| v.as_str()) {
-                            // Handle download progress events
-                            if event_type == "download_started" {
-                                let model = event.get("model")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("Unknown")
-                                    .to_string();
-                                let _ = app_handle.emit("download-progress", DownloadProgressPayload {
-                                    event_type: "started".to_string(),
-                                    model,
-                                    percent: None,
-                                    speed: None,
-                                    total_size: event.get("size").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                                    downloaded: None,
-                                    total: None,
-                                });
+    // Wait for daemon initialization with progress updates
+    // No timeout - let it load as long as needed
+    let mut initialized = false;
+    let mut handshake_done = false;
+    let mut progress = InitProgress::new();
+
+    loop {
+        match block_on(read_frame(&mut stdout)) {
+            Ok(None) => {
+                // EOF - daemon exited
+                eprintln!("[DAEMON DEBUG] EOF received, daemon exited");
+                emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                    status: "error".to_string(),
+                    message: ui::get_daemon_message("daemon_exited"),
+                }));
+                return false;
+            }
+            Ok(Some(frame)) => {
+                eprintln!("[DAEMON DEBUG] frame: {:?} {}", frame.kind, frame.payload);
+                // Only log frames carry init progress; forward status to frontend
+                if frame.kind == FrameKind::Log {
+                    let event = frame.payload;
+                    if let Some(event_type) = event.get("event").and_then(|v| v.as_str()) {
+                        if !matches!(event_type, "download_progress" | "init_progress") {
+                            diagnostics::record_init_event(event_type);
+                        }
+
+                        // Protocol handshake (first frame of a current daemon)
+                        let handshake = match event_type {
+                            "daemon_hello" => Some(integrity::check_hello(&event)),
+                            // Ready without a hello: built before the handshake existed
+                            "daemon_success" if !handshake_done => Some(integrity::check_hello(&serde_json::Value::Null)),
+                            _ => None,
+                        };
+                        if let Some(result) = handshake {
+                            if let Err(e) = result {
+                                eprintln!("[DAEMON] Protocol handshake failed: {}", e);
+                                DAEMON_INCOMPATIBLE.store(true, Ordering::SeqCst);
+                                let _ = child.start_kill();
+                                emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                                    status: "error".to_string(),
+                                    message: format!("{} ({})", ui::get_daemon_message("protocol_mismatch"), e),
+                                }));
+                                return false;
+                            }
+                            handshake_done = true;
+                            if event_type == "daemon_hello" {
                                 continue;
                             }
+                        }
+
+                        // Weighted progress for the splash screen
+                        if event_type == "init_plan" {
+                            progress.set_plan(&event);
+                            continue;
+                        }
+                        if event_type == "init_progress" {
+                            if let Some(payload) = progress.update(&event) {
+                                emit_event(app_handle, AppEvent::DaemonProgress(payload));
+                            }
+                            continue;
+                        }
+
+                        // Handle download progress events
+                        if event_type == "download_started" {
+                            let model = event.get("model")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("Unknown")
+                                .to_string();
+                            emit_event(app_handle, AppEvent::DownloadProgress(DownloadProgressPayload {
+                                event_type: "started".to_string(),
+                                model,
+                                percent: None,
+                                speed: None,
+                                total_size: event.get("size").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                                downloaded: None,
+                                total: None,
+                            }));
+                            continue;
+                        }
+
+                        if event_type == "download_progress" {
+                            let model = event.get("model")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("Unknown")
+                                .to_string();
+                            let percent = event.get("percent").and_then(|v| v.as_u64()).map(|p| p as u32);
+                            let speed = event.get("speed").and_then(|v| v.as_str()).map(|s| s.to_string());
+                            let total_size = event.get("total_size").and_then(|v| v.as_str()).map(|s| s.to_string());
+                            let downloaded = event.get("downloaded").and_then(|v| v.as_u64());
+                            let total = event.get("total").and_then(|v| v.as_u64());
+                            emit_event(app_handle, AppEvent::DownloadProgress(DownloadProgressPayload {
+                                event_type: "progress".to_string(),
+                                model,
+                                percent,
+                                speed,
+                                total_size,
+                                downloaded,
+                                total,
+                            }));
+                            continue;
+                        }
+
+                        if event_type == "download_completed" {
+                            let model = event.get("model")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("Unknown")
+                                .to_string();
+                            emit_event(app_handle, AppEvent::DownloadProgress(DownloadProgressPayload {
+                                event_type: "completed".to_string(),
+                                model,
+                                percent: Some(100),
+                                speed: None,
+                                total_size: None,
+                                downloaded: None,
+                                total: None,
+                            }));
+                            continue;
+                        }
 
-                            if event_type == "download_progress" {
+                        // Map daemon events to user-friendly messages and model loading stages
+                        // Determine if this is a "loaded" event (status should be "loaded" instead of "loading")
+                        let is_loaded_event = matches!(event_type,
+                            "model_loaded" | "vad_loaded" | "asr_loaded" | "llm_loaded" | "tts_loaded"
+                        );
+
+                        let (status_message, model_stage) = match event_type {
+                            "daemon_initializing" => (ui::get_daemon_message("initializing"), None),
+                            "loading_voice_assistant" => (ui::get_daemon_message("loading_assistant"), None),
+                            "model_loading" => {
+                                // Extract model name from event
                                 let model = event.get("model")
                                     .and_then(|v| v.as_str())
-                                    .unwrap_or("Unknown")
-                                    .to_string();
-                                let percent = event.get("percent").and_then(|v| v.as_u64()).map(|p| p as u32);
-                                let speed = event.get("speed").and_then(|v| v.as_str()).map(|s| s.to_string());
-                                let total_size = event.get("total_size").and_then(|v| v.as_str()).map(|s| s.to_string());
-                                let downloaded = event.get("downloaded").and_then(|v| v.as_u64());
-                                let total = event.get("total").and_then(|v| v.as_u64());
-                                let _ = app_handle.emit("download-progress", DownloadProgressPayload {
-                                    event_type: "progress".to_string(),
-                                    model,
-                                    percent,
-                                    speed,
-                                    total_size,
-                                    downloaded,
-                                    total,
-                                });
-                                continue;
+                                    .unwrap_or("Unknown");
+                                let stage = match model {
+                                    "VAD" => "vad",
+                                    "SenseVoice" => "asr",
+                                    _ => "unknown"
+                                };
+                                (format!("Loading {} model...", model), Some(stage.to_string()))
                             }
-
-                            if event_type == "download_completed" {
+                            "model_loaded" => {
                                 let model = event.get("model")
                                     .and_then(|v| v.as_str())
-                                    .unwrap_or("Unknown")
-                                    .to_string();
-                                let _ = app_handle.emit("download-progress", DownloadProgressPayload {
-                                    event_type: "completed".to_string(),
-                                    model,
-                                    percent: Some(100),
-                                    speed: None,
-                                    total_size: None,
-                                    downloaded: None,
-                                    total: None,
-                                });
-                                continue;
+                                    .unwrap_or("Unknown");
+                                let stage = match model {
+                                    "VAD" => "vad",
+                                    "SenseVoice" => "asr",
+                                    _ => "unknown"
+                                };
+                                (format!("{} loaded", model), Some(stage.to_string()))
                             }
-
-                            // Map daemon events to user-friendly messages and model loading stages
-                            // Determine if this is a "loaded" event (status should be "loaded" instead of "loading")
-                            let is_loaded_event = matches!(event_type,
-                                "model_loaded" | "vad_loaded" | "asr_loaded" | "llm_loaded" | "tts_loaded"
-                            );
-
-                            let (status_message, model_stage) = match event_type {
-                                "daemon_initializing" => (ui::get_daemon_message("initializing"), None),
-                                "loading_voice_assistant" => (ui::get_daemon_message("loading_assistant"), None),
-                                "model_loading" => {
-                                    // Extract model name from event
-                                    let model = event.get("model")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("Unknown");
-                                    let stage = match model {
-                                        "VAD" => "vad",
-                                        "SenseVoice" => "asr",
-                                        _ => "unknown"
-                                    };
-                                    (format!("Loading {} model...", model), Some(stage.to_string()))
-                                }
-                                "model_loaded" => {
-                                    let model = event.get("model")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or("Unknown");
-                                    let stage = match model {
-                                        "VAD" => "vad",
-                                        "SenseVoice" => "asr",
-                                        _ => "unknown"
-                                    };
-                                    (format!("{} loaded", model), Some(stage.to_string()))
-                                }
-                                "loading_asr" | "asr_loaded" => (ui::get_daemon_message("loading_asr"), Some("asr".to_string())),
-                                "loading_vad" | "vad_loaded" => ("Loading VAD model...".to_string(), Some("vad".to_string())),
-                                // Note: LLM and TTS don't download model files, only VAD and ASR do
-                                "loading_llm" | "llm_loaded" => (ui::get_daemon_message("loading_llm"), None),
-                                "loading_tts" | "tts_loaded" => (ui::get_daemon_message("loading_tts"), None),
-                                "resource_limits_failed" => (ui::get_daemon_message("resource_limits_failed"), None),
-                                "daemon_success" => {
-                                    if let Some(message) = event.get("message").and_then(|v| v.as_str()) {
-                                        if message.contains("就绪") || message.contains("ready") {
-                                            initialized = true;
-                                            (ui::get_daemon_message("service_ready"), Some("complete".to_string()))
-                                        } else {
-                                            (message.to_string(), None)
-                                        }
+                            "loading_asr" | "asr_loaded" => (ui::get_daemon_message("loading_asr"), Some("asr".to_string())),
+                            "loading_vad" | "vad_loaded" => ("Loading VAD model...".to_string(), Some("vad".to_string())),
+                            // Note: LLM and TTS don't download model files, only VAD and ASR do
+                            "loading_llm" | "llm_loaded" => (ui::get_daemon_message("loading_llm"), None),
+                            "loading_tts" | "tts_loaded" => (ui::get_daemon_message("loading_tts"), None),
+                            "resource_limits_failed" => (ui::get_daemon_message("resource_limits_failed"), None),
+                            "daemon_success" => {
+                                if let Some(message) = event.get("message").and_then(|v| v.as_str()) {
+                                    if message.contains("就绪") || message.contains("ready") {
+                                        initialized = true;
+                                        (ui::get_daemon_message("service_ready"), Some("complete".to_string()))
                                     } else {
-                                        (ui::get_daemon_message("init_success"), None)
+                                        (message.to_string(), None)
                                     }
+                                } else {
+                                    (ui::get_daemon_message("init_success"), None)
                                 }
-                                _ => {
-                                    // For other events, use message if available
-                                    let msg = event.get("message")
-                                        .and_then(|v| v.as_str())
-                                        .unwrap_or(&ui::get_daemon_message("loading"))
-                                        .to_string();
-                                    (msg, None)
-                                }
-                            };
-
-                            // Send progress update to frontend
-                            let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                                status: "loading".to_string(),
-                                message: status_message.clone(),
-                            });
-
-                            // Send model loading stage event
-                            if let Some(stage) = model_stage {
-                                let _ = app_handle.emit("model-loading", ModelLoadingPayload {
-                                    stage: stage.clone(),
-                                    status: if stage == "complete" || is_loaded_event { "loaded".to_string() } else { "loading".to_string() },
-                                    message: status_message,
-                                });
                             }
-
-                            if initialized {
-                                break;
+                            _ => {
+                                // For other events, use message if available
+                                let msg = event.get("message")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or(&ui::get_daemon_message("loading"))
+                                    .to_string();
+                                (msg, None)
                             }
+                        };
+
+                        // Send progress update to frontend
+                        emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                            status: "loading".to_string(),
+                            message: status_message.clone(),
+                        }));
+
+                        // Send model loading stage event
+                        if let Some(stage) = model_stage {
+                            emit_event(app_handle, AppEvent::ModelLoading(ModelLoadingPayload {
+                                stage: stage.clone(),
+                                status: if stage == "complete" || is_loaded_event { "loaded".to_string() } else { "loading".to_string() },
+                                message: status_message,
+                            }));
+                        }
+
+                        if initialized {
+                            emit_event(app_handle, AppEvent::DaemonProgress(progress.complete()));
+                            break;
                         }
                     }
                 }
-                Err(e) => {
-                    let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-                        status: "error".to_string(),
-                        message: format!("{}: {}", ui::get_daemon_message("read_error"), e),
-                    });
-                    return;
-                }
+            }
+            Err(e) => {
+                emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                    status: "error".to_string(),
+                    message: format!("{}: {}", ui::get_daemon_message("read_error"), e),
+                }));
+                return false;
             }
         }
+    }
 
-        // Store daemon instance
-        {
-            let mut daemon = DAEMON.lock().unwrap();
-            *daemon = Some(PythonDaemon {
-                process: child,
-                stdin,
-                stdout,
-            });
-        }
+    // Store daemon instance
+    {
+        let mut daemon = DAEMON.lock().unwrap();
+        *daemon = Some(PythonDaemon::from_parts(child, stdin, stdout));
+    }
 
-        // CRITICAL: Load config and sync work_mode/recording_mode to Rust globals
-        // This ensures backend state matches config file on startup
-        {
-            let mut daemon_guard = DAEMON.lock().unwrap();
-            if let Some(ref mut daemon) = *daemon_guard {
-                match daemon.send_command("config", serde_json::json!({})) {
-                    Ok(config_response) => {
-                        if let Some(config) = config_response.get("config") {
-                            // Sync work_mode from config to Rust WORK_MODE global
-                            if let Some(work_mode_str) = config.get("work_mode").and_then(|v| v.as_str()) {
-                                if let Some(work_mode) = crate::types::WorkMode::from_str(work_mode_str) {
-                                    *WORK_MODE.lock().unwrap() = work_mode;
-                                }
-                            }
+    // CRITICAL: Load config and sync work_mode/recording_mode to Rust globals
+    // This ensures backend state matches config file on startup
+    load_modes_from_config();
 
-                            // Sync recording_mode from config to Rust RECORDING_MODE global
-                            if let Some(recording_mode_str) = config.get("recording_mode").and_then(|v| v.as_str()) {
-                                if let Some(recording_mode) = crate::types::RecordingMode::from_str(recording_mode_str) {
-                                    *RECORDING_MODE.lock().unwrap() = recording_mode;
-                                }
-                            }
-                        }
+    // Mark daemon as ready - this allows commands to be executed
+    DAEMON_READY.store(true, Ordering::Release);
+    diagnostics::record_init_event("ready");
+
+    // Settings saved while the daemon was loading
+    crate::config::flush_pending_daemon_reload();
+
+    // Send ready status to frontend
+    emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+        status: "ready".to_string(),
+        message: ui::get_daemon_message("ready"),
+    }));
+
+    true
+}
+
+// ============================================================================
+// Daemon Supervisor
+// ============================================================================
+
+/// Interval between daemon liveness checks
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Maximum consecutive restart attempts before giving up
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// Initial restart delay, doubled after each failed attempt
+const RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the restart delay
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// A daemon that stayed up this long is considered stable (resets the backoff)
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+
+/// Backoff delay before the given restart attempt (0-based)
+fn restart_delay(attempt: u32) -> Duration {
+    RESTART_BASE_DELAY
+        .saturating_mul(1u32 << attempt.min(16))
+        .min(RESTART_MAX_DELAY)
+}
+
+/// Block until the current daemon process exits
+///
+/// Returns `None` if the app is shutting down (exit is intentional).
+fn wait_for_daemon_exit() -> Option<String> {
+    loop {
+        if DAEMON_SHUTTING_DOWN.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        // The DAEMON lock is only held briefly (requests run on the client threads)
+        if let Ok(mut daemon_guard) = DAEMON.try_lock() {
+            match daemon_guard.as_mut() {
+                Some(d) => {
+                    // A request timed out on this daemon: kill it so it gets restarted
+                    let kill_pid = DAEMON_KILL_REQUEST.swap(0, Ordering::SeqCst);
+                    if kill_pid != 0 && d.process.id() == Some(kill_pid) {
+                        let _ = d.process.start_kill();
                     }
-                    Err(_e) => {
+                    match d.process.try_wait() {
+                        Ok(Some(status)) => return Some(status.to_string()),
+                        Ok(None) => {}
+                        Err(e) => return Some(format!("wait failed: {}", e)),
                     }
                 }
+                None => return Some("daemon not running".to_string()),
             }
         }
 
-        // Mark daemon as ready - this allows commands to be executed
-        DAEMON_READY.store(true, Ordering::Release);
+        std::thread::sleep(SUPERVISOR_POLL_INTERVAL);
+    }
+}
+
+/// Supervise the daemon: detect unexpected exits and restart with exponential backoff
+///
+/// Runs on the startup thread after the initial launch and only returns when the
+/// app shuts down or restarts are exhausted.
+fn supervise_daemon(app_handle: &tauri::AppHandle, on_ready: Option<Arc<dyn Fn() + Send + Sync>>) {
+    let mut attempt: u32 = 0;
+    let mut launched_at = Instant::now();
+
+    loop {
+        // A mismatched sidecar would fail the same way on every restart
+        if DAEMON_INCOMPATIBLE.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let exit_reason = match wait_for_daemon_exit() {
+            Some(reason) => reason,
+            None => return,
+        };
 
-        // Send ready status to frontend
-        let _ = app_handle.emit("daemon-status", DaemonStatusPayload {
-            status: "ready".to_string(),
-            message: ui::get_daemon_message("ready"),
-        });
+        eprintln!("[DAEMON DEBUG] Daemon exited unexpectedly: {}", exit_reason);
+        diagnostics::report_daemon_exit(&exit_reason);
 
-        // Call on_ready callback if provided (e.g., to register PTT shortcuts)
-        if let Some(callback) = on_ready {
-            callback();
+        if launched_at.elapsed() >= STABLE_UPTIME {
+            attempt = 0;
         }
-    });
+
+        // Remember in-memory session state before the relaunch reloads it from config
+        let work_mode = *WORK_MODE.lock().unwrap();
+        let recording_mode = *RECORDING_MODE.lock().unwrap();
+
+        DAEMON_READY.store(false, Ordering::Release);
+        if let Ok(mut daemon_guard) = DAEMON.lock() {
+            *daemon_guard = None;
+        }
+        reset_pipeline_state(app_handle);
+
+        loop {
+            if attempt >= MAX_RESTART_ATTEMPTS {
+                emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                    status: "error".to_string(),
+                    message: ui::get_daemon_message("restart_failed"),
+                }));
+                return;
+            }
+
+            let delay = restart_delay(attempt);
+            attempt += 1;
+
+            emit_event(app_handle, AppEvent::DaemonStatus(DaemonStatusPayload {
+                status: "restarting".to_string(),
+                message: format!("{} ({}/{})", ui::get_daemon_message("restarting"), attempt, MAX_RESTART_ATTEMPTS),
+            }));
+
+            std::thread::sleep(delay);
+            if DAEMON_SHUTTING_DOWN.load(Ordering::SeqCst) {
+                return;
+            }
+
+            if launch_daemon(app_handle) {
+                restore_session_state(work_mode, recording_mode);
+                if let Some(ref callback) = on_ready {
+                    callback();
+                }
+                launched_at = Instant::now();
+                break;
+            }
+            if DAEMON_INCOMPATIBLE.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+    }
+}
+
+/// Sync the WORK_MODE / RECORDING_MODE globals from config.json (startup, profile switch)
+pub fn load_modes_from_config() {
+    let config = crate::config::read_config();
+
+    // Sync work_mode from config to Rust WORK_MODE global
+    if let Some(work_mode_str) = config.get("work_mode").and_then(|v| v.as_str()) {
+        if let Some(work_mode) = crate::types::WorkMode::from_str(work_mode_str) {
+            *WORK_MODE.lock().unwrap() = work_mode;
+        }
+    }
+
+    // Sync recording_mode from config to Rust RECORDING_MODE global
+    if let Some(recording_mode_str) = config.get("recording_mode").and_then(|v| v.as_str()) {
+        if let Some(recording_mode) = crate::types::RecordingMode::from_str(recording_mode_str) {
+            // A meeting is never resumed on launch: capturing system audio needs a fresh start
+            *RECORDING_MODE.lock().unwrap() = match recording_mode {
+                crate::types::RecordingMode::Meeting => crate::types::RecordingMode::PushToTalk,
+                mode => mode,
+            };
+        }
+    }
+}
+
+/// Clear transient pipeline flags left behind by a crashed daemon
+fn reset_pipeline_state(app_handle: &tauri::AppHandle) {
+    STREAMING_IN_PROGRESS.store(false, Ordering::SeqCst);
+    PTT_PROCESSING.store(false, Ordering::SeqCst);
+    APP_STATUS.transition(AppStatus::Idle, "daemon_restart");
+    ui::emit_ptt_state(app_handle, "idle");
+}
+
+/// Re-apply modes that were active before the crash to Rust globals and the new daemon
+fn restore_session_state(work_mode: WorkMode, recording_mode: RecordingMode) {
+    *WORK_MODE.lock().unwrap() = work_mode;
+    *RECORDING_MODE.lock().unwrap() = recording_mode;
+
+    if let Ok(mut daemon_guard) = DAEMON.lock() {
+        if let Some(ref mut daemon) = *daemon_guard {
+            let _ = daemon.send_command_no_wait("set_recording_mode", serde_json::json!({
+                "mode": recording_mode.as_str()
+            }));
+        }
+    }
 }
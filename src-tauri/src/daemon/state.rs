@@ -3,10 +3,11 @@
 //! This module contains all global state variables used for daemon management
 //! and application coordination.
 
-use std::sync::{Mutex, atomic::AtomicBool};
-use std::io::BufReader;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, atomic::{AtomicBool, AtomicU32}};
 
-use std::process::ChildStderr;
+use tokio::io::BufReader;
+use tokio::process::ChildStderr;
 
 // ============================================================================
 // Global State
@@ -18,10 +19,19 @@ pub static DAEMON: Mutex<Option<super::process::PythonDaemon>> = Mutex::new(None
 /// Daemon ready flag - set to true only after daemon is fully initialized
 pub static DAEMON_READY: AtomicBool = AtomicBool::new(false);
 
+/// Shutdown flag - set when the app exits so the supervisor doesn't restart the daemon
+pub static DAEMON_SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Pid of a hung daemon the supervisor should kill and restart (0 = none)
+pub static DAEMON_KILL_REQUEST: AtomicU32 = AtomicU32::new(0);
+
+/// Set when the sidecar failed verification or the protocol handshake (restarting can't help)
+pub static DAEMON_INCOMPATIBLE: AtomicBool = AtomicBool::new(false);
+
 /// PTT stderr reader handle
 pub static PTT_STDERR: Mutex<Option<BufReader<ChildStderr>>> = Mutex::new(None);
 
-/// Streaming operation flag - prevent health checks from interfering
+/// Streaming operation flag - a chat/TTS stream is producing output (blocks new recordings)
 pub static STREAMING_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 
 /// Global app handle for daemon operations
@@ -39,15 +49,42 @@ pub static RECORDING_MODE: Mutex<crate::types::RecordingMode> = Mutex::new(crate
 /// Current work mode
 pub static WORK_MODE: Mutex<crate::types::WorkMode> = Mutex::new(crate::types::WorkMode::TextInput);
 
-/// Application status
-pub static APP_STATUS: Mutex<crate::types::AppStatus> = Mutex::new(crate::types::AppStatus::Idle);
+/// Application status (change it only through `transition`)
+pub static APP_STATUS: crate::status::StateMachine = crate::status::StateMachine::new();
 
 /// Current PTT shortcut string (for dynamic update)
 pub static CURRENT_PTT_SHORTCUT: Mutex<Option<String>> = Mutex::new(None);
 
+/// Registered action shortcuts (action name -> shortcut string)
+pub static ACTION_SHORTCUTS: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
 /// PTT key state - prevent key repeat from triggering multiple presses
 pub static PTT_KEY_PRESSED: AtomicBool = AtomicBool::new(false);
 
+/// Set when a PTT recording was stopped by a timeout, so the later key release is ignored
+pub static PTT_TIMED_OUT: AtomicBool = AtomicBool::new(false);
+
+/// Active PTT trigger backend
+pub static PTT_TRIGGER: Mutex<crate::types::PttTrigger> = Mutex::new(crate::types::PttTrigger::Shortcut);
+
+/// Low-level input listener (mouse button / Fn key PTT) is running
+pub static INPUT_LISTENER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// PTT recording latched on by a double-tap (hands-free until the next press)
+pub static PTT_LATCHED: AtomicBool = AtomicBool::new(false);
+
+/// A short PTT tap is waiting to see whether a second tap follows
+pub static PTT_TAP_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Ignore the next PTT key release (its press latched or unlatched recording)
+pub static PTT_IGNORE_RELEASE: AtomicBool = AtomicBool::new(false);
+
+/// When the PTT key was last pressed (to tell taps from holds)
+pub static PTT_PRESSED_AT: Mutex<Option<std::time::Instant>> = Mutex::new(None);
+
+/// Most recent speech recognition result (for the tray "Copy last transcript" item)
+pub static LAST_TRANSCRIPT: Mutex<Option<String>> = Mutex::new(None);
+
 /// Global audio recorder (Rust-side recording)
 pub static AUDIO_RECORDER: Mutex<Option<crate::audio::AudioRecorder>> = Mutex::new(None);
 
@@ -2,12 +2,13 @@
 // Database Module - SQLite History Storage
 // ============================================================================
 
-use rusqlite::{params, Connection, Result as SqliteResult};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::sync::MutexGuard;
 use tauri::Manager;
+use crate::types::{SpeakerSegment, WordTiming};
 
 /// Helper function to acquire a mutex lock with proper error handling
 fn acquire_lock<'a, T>(lock: &'a Mutex<T>, context: &str) -> Result<MutexGuard<'a, T>, String> {
@@ -15,6 +16,46 @@ fn acquire_lock<'a, T>(lock: &'a Mutex<T>, context: &str) -> Result<MutexGuard<'
         .map_err(|e| format!("{}: lock poisoned: {}", context, e))
 }
 
+/// Speaker segments of a message, in order
+fn load_segments(conn: &Connection, message_id: &str) -> Result<Vec<SpeakerSegment>, String> {
+    let mut stmt = conn
+        .prepare_cached(
+            "SELECT speaker, start_ms, end_ms, text FROM message_segments
+             WHERE message_id = ?1
+             ORDER BY start_ms ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let segments = stmt
+        .query_map(params![message_id], |row| {
+            Ok(SpeakerSegment {
+                speaker: row.get(0)?,
+                start_ms: row.get::<_, i64>(1)? as u64,
+                end_ms: row.get::<_, i64>(2)? as u64,
+                text: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Failed to query message segments: {}", e))?
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Failed to collect message segments: {}", e))?;
+
+    Ok(segments)
+}
+
+fn transcription_job_from_row(row: &rusqlite::Row) -> SqliteResult<TranscriptionJob> {
+    Ok(TranscriptionJob {
+        id: row.get(0)?,
+        path: row.get(1)?,
+        file_name: row.get(2)?,
+        status: row.get(3)?,
+        percent: row.get(4)?,
+        session_id: row.get(5)?,
+        error: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
 // ============================================================================
 // Data Structures
 // ============================================================================
@@ -27,6 +68,9 @@ pub struct Session {
     pub is_favorite: bool,
     pub created_at: i64,
     pub updated_at: i64,
+    /// When the session was moved to the trash (None = not in the trash)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +80,189 @@ pub struct Message {
     pub role: String,
     pub content: String,
     pub timestamp: i64,
+    /// Archived recording of the utterance (user messages, see audio_archive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_path: Option<String>,
+    /// Language detected by ASR (user messages from speech)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Per-speaker parts of a diarized recording (only loaded by get_messages)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<SpeakerSegment>,
+    /// Pinned to the pinned answers window
+    #[serde(default)]
+    pub pinned: bool,
+    /// Reply still being streamed ("draft") or cut off ("interrupted"); None = complete
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+/// Message status of a reply that is still being streamed
+pub const MESSAGE_STATUS_DRAFT: &str = "draft";
+
+/// Message status of a streamed reply that ended early (error, cancel or crash)
+pub const MESSAGE_STATUS_INTERRUPTED: &str = "interrupted";
+
+/// A pinned message with its session title, for the pinned answers window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedMessage {
+    pub id: String,
+    pub session_id: String,
+    pub session_title: String,
+    pub role: String,
+    pub content: String,
+    pub timestamp: i64,
+    pub pinned_at: i64,
+}
+
+/// Pipeline stage offsets of one voice interaction, in ms after recording stopped
+///
+/// A stage is `None` when the interaction did not reach it (e.g. no TTS in text input mode).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyRecord {
+    pub created_at: i64,
+    pub asr_ms: Option<i64>,
+    pub first_token_ms: Option<i64>,
+    pub first_audio_ms: Option<i64>,
+    pub playback_ms: Option<i64>,
+}
+
+/// Daemon memory / CPU sample, taken every few seconds (see daemon resources)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceSample {
+    pub created_at: i64,
+    pub pid: u32,
+    pub rss_bytes: u64,
+    /// CPU usage since the previous sample (100 = one full core)
+    pub cpu_percent: Option<f64>,
+}
+
+/// User-defined transcript replacement (see textproc)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replacement {
+    pub id: i64,
+    pub pattern: String,
+    pub replacement: String,
+    /// `pattern` is a regex instead of a whole-word phrase
+    pub is_regex: bool,
+    pub created_at: i64,
+}
+
+/// Assistant persona: system prompt and voice used for chat and TTS (see personas)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Persona {
+    pub id: i64,
+    pub name: String,
+    pub system_prompt: String,
+    /// TTS voice (None = the configured voice)
+    pub voice: Option<String>,
+    /// Speech rate multiplier (1.0 = normal)
+    pub speed: f64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Prompt filled with a spoken utterance and run by its hotkey (see prompt_templates)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub id: i64,
+    pub name: String,
+    /// Prompt; `{text}` is replaced by the utterance
+    pub template: String,
+    /// Global shortcut (None = only run from the UI)
+    pub hotkey: Option<String>,
+    /// "speak", "type" or "window"
+    pub output: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Recording kept until its ASR finishes (see recording_journal)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingRecording {
+    pub id: i64,
+    /// Journal copy of the WAV
+    pub path: String,
+    pub duration_secs: f64,
+    /// "pending" (not transcribed yet) or "failed" (ASR returned an error)
+    pub status: String,
+    pub created_at: i64,
+}
+
+/// Spoken reminder, once or on a cron schedule (see reminders)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: i64,
+    pub text: String,
+    /// Cron expression or date/time as entered
+    pub schedule: String,
+    pub recurring: bool,
+    /// Next trigger time (Unix milliseconds); None once a one-time reminder has fired
+    pub next_run: Option<i64>,
+    pub created_at: i64,
+}
+
+/// Text-input dictation kept for re-pasting (see transcriptions)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcription {
+    pub id: i64,
+    pub text: String,
+    pub created_at: i64,
+}
+
+/// Custom vocabulary term sent to the ASR as a hotword (see vocabulary)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyTerm {
+    pub id: i64,
+    pub term: String,
+    pub created_at: i64,
+}
+
+/// Spoken phrase typed as a symbol or emoji in text-input mode (see textproc)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolShortcut {
+    pub id: i64,
+    pub phrase: String,
+    pub symbol: String,
+    pub created_at: i64,
+}
+
+/// Queued file transcription (see file_transcription)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionJob {
+    pub id: String,
+    pub path: String,
+    /// File name without extension, also the title of the new session
+    pub file_name: String,
+    /// "queued", "decoding", "transcribing", "done", "failed" or "cancelled"
+    pub status: String,
+    pub percent: u32,
+    /// Session holding the transcript (set when done)
+    pub session_id: Option<String>,
+    /// Set when failed
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Session removed by the history retention policy (see retention)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionSession {
+    pub id: String,
+    pub title: String,
+    pub updated_at: i64,
+    /// "age" (older than the limit), "not_favorite" (favorites only) or
+    /// "message_cap" (all its messages were over the cap)
+    pub reason: String,
+}
+
+/// What a retention run deleted, or would delete in a dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub sessions: Vec<RetentionSession>,
+    /// Messages removed, including those of the removed sessions
+    pub messages: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -53,27 +280,53 @@ pub struct PaginatedResult<T> {
 
 pub struct Database {
     conn: Mutex<Connection>,
+    path: Mutex<PathBuf>,
+}
+
+/// Open a connection, unlocking it with the SQLCipher `key` if given
+fn open_connection(db_path: &Path, key: Option<&str>) -> Result<Connection, String> {
+    let conn = Connection::open(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+
+    if let Some(key) = key {
+        conn.pragma_update(None, "key", key)
+            .map_err(|e| format!("Failed to set database key: {}", e))?;
+    }
+    // The key is only checked on first access
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to read database (wrong key?): {}", e))?;
+
+    // Enable foreign keys
+    conn.execute("PRAGMA foreign_keys = ON", [])
+        .map_err(|e| format!("Failed to enable foreign keys: {}", e))?;
+
+    Ok(conn)
+}
+
+/// Stand-in connection while the database file is closed; it has no tables and refuses writes
+fn closed_placeholder() -> Result<Connection, String> {
+    let conn = Connection::open_in_memory().map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.execute_batch("PRAGMA query_only = ON;")
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    Ok(conn)
 }
 
 impl Database {
     /// Create a new database instance at the specified path
-    pub fn new(db_path: PathBuf) -> Result<Self, String> {
+    ///
+    /// `key` unlocks a database encrypted with SQLCipher (see db_encryption).
+    pub fn new(db_path: PathBuf, key: Option<&str>) -> Result<Self, String> {
         // Ensure parent directory exists
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent)
                 .map_err(|e| format!("Failed to create database directory: {}", e))?;
         }
 
-        // Open database connection
-        let conn = Connection::open(&db_path)
-            .map_err(|e| format!("Failed to open database: {}", e))?;
-
-        // Enable foreign keys
-        conn.execute("PRAGMA foreign_keys = ON", [])
-            .map_err(|e| format!("Failed to enable foreign keys: {}", e))?;
+        let conn = open_connection(&db_path, key)?;
 
         let db = Database {
             conn: Mutex::new(conn),
+            path: Mutex::new(db_path.clone()),
         };
 
         // Run migrations
@@ -84,6 +337,104 @@ impl Database {
         Ok(db)
     }
 
+    /// Location of the database file
+    pub fn path(&self) -> PathBuf {
+        self.path.lock().map(|path| path.clone()).unwrap_or_default()
+    }
+
+    /// Close the database and open the one at `db_path` instead (profile switch)
+    ///
+    /// On failure the current database stays open.
+    pub fn reopen(&self, db_path: PathBuf, key: Option<&str>) -> Result<(), String> {
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create database directory: {}", e))?;
+        }
+        let new_conn = open_connection(&db_path, key)?;
+
+        {
+            let mut conn = acquire_lock(&self.conn, "reopen")?;
+            let old = std::mem::replace(&mut *conn, new_conn);
+            if let Err((_, e)) = old.close() {
+                eprintln!("⚠️ Failed to close database: {}", e);
+            }
+            *acquire_lock(&self.path, "reopen")? = db_path.clone();
+        }
+
+        self.run_migrations()?;
+        println!("✅ Database reopened at: {:?}", db_path);
+        Ok(())
+    }
+
+    /// Rewrite the database file with another SQLCipher key (None = unencrypted)
+    ///
+    /// The data is copied with `sqlcipher_export` into a new file, which then
+    /// replaces the old one. The old file is kept until the new one opens with
+    /// `to_key`; on failure it is put back and reopened with `from_key`.
+    pub fn convert_encryption(&self, from_key: Option<&str>, to_key: Option<&str>) -> Result<(), String> {
+        let mut conn = acquire_lock(&self.conn, "convert_encryption")?;
+        let path = self.path();
+        let converted = path.with_extension("db.converting");
+        let _ = std::fs::remove_file(&converted);
+
+        // sqlcipher_export copies everything but the schema version
+        let version: i32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to get schema version: {}", e))?;
+        conn.execute(
+            "ATTACH DATABASE ?1 AS converted KEY ?2",
+            params![converted.to_string_lossy(), to_key.unwrap_or("")],
+        )
+        .map_err(|e| format!("Failed to create converted database: {}", e))?;
+        let exported = conn
+            .query_row("SELECT sqlcipher_export('converted')", [], |_| Ok(()))
+            .and_then(|_| conn.execute_batch(&format!("PRAGMA converted.user_version = {};", version)));
+        let _ = conn.execute("DETACH DATABASE converted", []);
+        if let Err(e) = exported {
+            let _ = std::fs::remove_file(&converted);
+            return Err(format!("Failed to convert database: {}", e));
+        }
+
+        // The file can only be replaced once the old connection is closed
+        let placeholder = closed_placeholder()?;
+        if let Err((old, e)) = std::mem::replace(&mut *conn, placeholder).close() {
+            *conn = old;
+            let _ = std::fs::remove_file(&converted);
+            return Err(format!("Failed to close database: {}", e));
+        }
+
+        let previous = path.with_extension("db.previous");
+        let _ = std::fs::remove_file(&previous);
+        let installed = std::fs::rename(&path, &previous)
+            .map_err(|e| format!("Failed to replace database: {}", e))
+            .and_then(|_| {
+                std::fs::rename(&converted, &path).map_err(|e| {
+                    let _ = std::fs::rename(&previous, &path);
+                    format!("Failed to replace database: {}", e)
+                })
+            })
+            .and_then(|_| {
+                open_connection(&path, to_key).map_err(|e| {
+                    let _ = std::fs::rename(&previous, &path);
+                    format!("Failed to open converted database: {}", e)
+                })
+            });
+        match installed {
+            Ok(converted_conn) => {
+                *conn = converted_conn;
+                let _ = std::fs::remove_file(&previous);
+                Ok(())
+            }
+            Err(e) => {
+                let _ = std::fs::remove_file(&converted);
+                // Without this the placeholder stays, and every call fails
+                *conn = open_connection(&path, from_key)
+                    .map_err(|reopen| format!("{}; reopening the database failed too: {}", e, reopen))?;
+                Err(e)
+            }
+        }
+    }
+
     /// Run database migrations
     fn run_migrations(&self) -> Result<(), String> {
         let conn = acquire_lock(&self.conn, "run_migrations")?;
@@ -151,276 +502,2147 @@ impl Database {
             println!("✅ Migration v2 completed");
         }
 
-        Ok(())
-    }
+        // Migration v2 -> v3: Add latency metrics table
+        if version < 3 {
+            println!("🔄 Running migration v2 -> v3: Add latency metrics table");
 
-    // ========================================================================
-    // Session CRUD Operations
-    // ========================================================================
+            conn.execute_batch(
+                "
+                -- Per-interaction pipeline latency (ms after recording stop)
+                CREATE TABLE IF NOT EXISTS latency_metrics (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    created_at INTEGER NOT NULL,
+                    asr_ms INTEGER,
+                    first_token_ms INTEGER,
+                    first_audio_ms INTEGER,
+                    playback_ms INTEGER
+                );
+                CREATE INDEX IF NOT EXISTS idx_latency_created ON latency_metrics(created_at DESC);
 
-    /// Create a new session
-    pub fn create_session(&self, title: String) -> Result<Session, String> {
-        let conn = acquire_lock(&self.conn, "create_session")?;
+                -- Update schema version
+                PRAGMA user_version = 3;
+                ",
+            )
+            .map_err(|e| format!("Migration v3 failed: {}", e))?;
 
-        let id = uuid::Uuid::new_v4().to_string();
-        let now = chrono::Utc::now().timestamp_millis();
+            println!("✅ Migration v3 completed");
+        }
 
-        conn.execute(
-            "INSERT INTO sessions (id, title, is_favorite, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![id, title, 0, now, now],
-        )
-        .map_err(|e| format!("Failed to create session: {}", e))?;
+        // Migration v3 -> v4: Add transcript replacements table
+        if version < 4 {
+            println!("🔄 Running migration v3 -> v4: Add transcript replacements table");
 
-        Ok(Session {
-            id,
-            title,
-            is_favorite: false,
-            created_at: now,
-            updated_at: now,
-        })
-    }
+            conn.execute_batch(
+                "
+                -- User-defined replacements applied to ASR output
+                CREATE TABLE IF NOT EXISTS text_replacements (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    pattern TEXT NOT NULL,
+                    replacement TEXT NOT NULL,
+                    is_regex INTEGER NOT NULL DEFAULT 0,
+                    created_at INTEGER NOT NULL
+                );
 
-    /// List sessions with pagination
-    #[allow(dead_code)]
-    pub fn list_sessions(&self, page: i32, page_size: i32) -> Result<PaginatedResult<Session>, String> {
-        self.list_sessions_filtered(page, page_size, None)
-    }
+                -- Update schema version
+                PRAGMA user_version = 4;
+                ",
+            )
+            .map_err(|e| format!("Migration v4 failed: {}", e))?;
 
-    /// List sessions with pagination and optional favorite filter
-    pub fn list_sessions_filtered(
-        &self,
-        page: i32,
-        page_size: i32,
-        filter_favorite: Option<bool>,
-    ) -> Result<PaginatedResult<Session>, String> {
-        let conn = acquire_lock(&self.conn, "list_sessions_filtered")?;
+            println!("✅ Migration v4 completed");
+        }
 
-        // Build WHERE clause for filtering
-        let where_clause = match filter_favorite {
-            Some(true) => " WHERE is_favorite = 1",
-            Some(false) => " WHERE is_favorite = 0",
-            None => "",
-        };
+        // Migration v4 -> v5: Add audio_path column to messages
+        if version < 5 {
+            println!("🔄 Running migration v4 -> v5: Add audio_path column to messages");
 
-        // Get total count
-        let total: i64 = conn
-            .query_row(
-                &format!("SELECT COUNT(*) FROM sessions{}", where_clause),
-                [],
-                |row| row.get(0),
-            )
-            .map_err(|e| format!("Failed to count sessions: {}", e))?;
+            conn.execute_batch(
+                "
+                -- Archived utterance recording (NULL = not archived)
+                ALTER TABLE messages ADD COLUMN audio_path TEXT;
 
-        // Calculate offset
-        let offset = (page - 1) * page_size;
+                -- Update schema version
+                PRAGMA user_version = 5;
+                ",
+            )
+            .map_err(|e| format!("Migration v5 failed: {}", e))?;
 
-        // Query sessions
-        let query = format!(
-            "SELECT id, title, is_favorite, created_at, updated_at FROM sessions{} ORDER BY updated_at DESC LIMIT ?1 OFFSET ?2",
-            where_clause
-        );
+            println!("✅ Migration v5 completed");
+        }
 
-        let mut stmt = conn
-            .prepare(&query)
-            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+        // Migration v5 -> v6: Add personas table
+        if version < 6 {
+            println!("🔄 Running migration v5 -> v6: Add personas table");
 
-        let sessions = stmt
-            .query_map(params![page_size, offset], |row| {
-                Ok(Session {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    is_favorite: row.get::<_, i32>(2)? == 1,
-                    created_at: row.get(3)?,
-                    updated_at: row.get(4)?,
-                })
-            })
-            .map_err(|e| format!("Failed to query sessions: {}", e))?
-            .collect::<SqliteResult<Vec<_>>>()
-            .map_err(|e| format!("Failed to collect sessions: {}", e))?;
+            conn.execute_batch(
+                "
+                -- Assistant personas (system prompt + TTS voice)
+                CREATE TABLE IF NOT EXISTS personas (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    system_prompt TEXT NOT NULL,
+                    voice TEXT,
+                    speed REAL NOT NULL DEFAULT 1.0,
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );
 
-        let has_more = (offset + page_size) < total as i32;
+                -- Update schema version
+                PRAGMA user_version = 6;
+                ",
+            )
+            .map_err(|e| format!("Migration v6 failed: {}", e))?;
 
-        Ok(PaginatedResult {
-            items: sessions,
-            total,
-            page,
-            page_size,
-            has_more,
-        })
-    }
+            println!("✅ Migration v6 completed");
+        }
 
-    /// Get a single session by ID
-    pub fn get_session(&self, session_id: &str) -> Result<Session, String> {
-        let conn = acquire_lock(&self.conn, "get_session")?;
+        // Migration v6 -> v7: Add transcriptions table
+        if version < 7 {
+            println!("🔄 Running migration v6 -> v7: Add transcriptions table");
 
-        conn.query_row(
-            "SELECT id, title, is_favorite, created_at, updated_at FROM sessions WHERE id = ?1",
-            params![session_id],
-            |row| {
-                Ok(Session {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    is_favorite: row.get::<_, i32>(2)? == 1,
-                    created_at: row.get(3)?,
-                    updated_at: row.get(4)?,
-                })
-            },
-        )
-        .map_err(|e| format!("Session not found: {}", e))
-    }
+            conn.execute_batch(
+                "
+                -- Text-input dictations (transcription history)
+                CREATE TABLE IF NOT EXISTS transcriptions (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    text TEXT NOT NULL,
+                    created_at INTEGER NOT NULL
+                );
 
-    /// Toggle favorite status of a session
-    pub fn toggle_favorite(&self, session_id: &str) -> Result<bool, String> {
-        let conn = acquire_lock(&self.conn, "toggle_favorite")?;
+                CREATE INDEX IF NOT EXISTS idx_transcriptions_created ON transcriptions(created_at DESC);
 
-        // Get current state directly without calling get_session (avoids deadlock)
-        let current_state: i32 = conn
-            .query_row(
-                "SELECT is_favorite FROM sessions WHERE id = ?1",
-                params![session_id],
-                |row| row.get(0),
+                -- Update schema version
+                PRAGMA user_version = 7;
+                ",
             )
-            .map_err(|e| format!("Failed to query session: {}", e))?;
+            .map_err(|e| format!("Migration v7 failed: {}", e))?;
 
-        let new_state = current_state == 0;
+            println!("✅ Migration v7 completed");
+        }
 
-        // Update state
-        let now = chrono::Utc::now().timestamp_millis();
-        let rows_affected = conn
-            .execute(
-                "UPDATE sessions SET is_favorite = ?1, updated_at = ?2 WHERE id = ?3",
-                params![if new_state { 1 } else { 0 }, now, session_id],
+        // Migration v7 -> v8: Add language column to messages
+        if version < 8 {
+            println!("🔄 Running migration v7 -> v8: Add language column to messages");
+
+            conn.execute_batch(
+                "
+                -- ASR language of spoken messages (NULL = typed or unknown)
+                ALTER TABLE messages ADD COLUMN language TEXT;
+
+                -- Update schema version
+                PRAGMA user_version = 8;
+                ",
+            )
+            .map_err(|e| format!("Migration v8 failed: {}", e))?;
+
+            println!("✅ Migration v8 completed");
+        }
+
+        // Migration v8 -> v9: Add prompt_templates table
+        if version < 9 {
+            println!("🔄 Running migration v8 -> v9: Add prompt_templates table");
+
+            conn.execute_batch(
+                "
+                -- Prompt templates run by hotkey on a spoken utterance
+                CREATE TABLE IF NOT EXISTS prompt_templates (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    template TEXT NOT NULL,
+                    hotkey TEXT,
+                    output TEXT NOT NULL DEFAULT 'window',
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );
+
+                -- Update schema version
+                PRAGMA user_version = 9;
+                ",
+            )
+            .map_err(|e| format!("Migration v9 failed: {}", e))?;
+
+            println!("✅ Migration v9 completed");
+        }
+
+        // Migration v9 -> v10: Add reminders table
+        if version < 10 {
+            println!("🔄 Running migration v9 -> v10: Add reminders table");
+
+            conn.execute_batch(
+                "
+                -- Spoken reminders (one-time or cron)
+                CREATE TABLE IF NOT EXISTS reminders (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    text TEXT NOT NULL,
+                    schedule TEXT NOT NULL,
+                    recurring INTEGER NOT NULL DEFAULT 0,
+                    next_run INTEGER,
+                    created_at INTEGER NOT NULL
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_reminders_next_run ON reminders(next_run);
+
+                -- Update schema version
+                PRAGMA user_version = 10;
+                ",
+            )
+            .map_err(|e| format!("Migration v10 failed: {}", e))?;
+
+            println!("✅ Migration v10 completed");
+        }
+
+        // Migration v10 -> v11: Add daemon resource metrics table
+        if version < 11 {
+            println!("🔄 Running migration v10 -> v11: Add daemon resource metrics table");
+
+            conn.execute_batch(
+                "
+                -- Daemon memory / CPU samples (pruned to recent days)
+                CREATE TABLE IF NOT EXISTS daemon_resource_metrics (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    created_at INTEGER NOT NULL,
+                    pid INTEGER NOT NULL,
+                    rss_bytes INTEGER NOT NULL,
+                    cpu_percent REAL
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_daemon_resource_created ON daemon_resource_metrics(created_at DESC);
+
+                -- Update schema version
+                PRAGMA user_version = 11;
+                ",
+            )
+            .map_err(|e| format!("Migration v11 failed: {}", e))?;
+
+            println!("✅ Migration v11 completed");
+        }
+
+        // Migration v11 -> v12: Add recording journal table
+        if version < 12 {
+            println!("🔄 Running migration v11 -> v12: Add recording journal table");
+
+            conn.execute_batch(
+                "
+                -- Recordings waiting for ASR (left behind by a crash or failed ASR)
+                CREATE TABLE IF NOT EXISTS pending_recordings (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    path TEXT NOT NULL,
+                    duration_secs REAL NOT NULL DEFAULT 0,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    created_at INTEGER NOT NULL
+                );
+
+                -- Update schema version
+                PRAGMA user_version = 12;
+                ",
+            )
+            .map_err(|e| format!("Migration v12 failed: {}", e))?;
+
+            println!("✅ Migration v12 completed");
+        }
+
+        // Migration v12 -> v13: Add ASR confidence and alternatives to messages
+        if version < 13 {
+            println!("🔄 Running migration v12 -> v13: Add ASR confidence and alternatives to messages");
+
+            conn.execute_batch(
+                "
+                -- ASR confidence (0-1) and other hypotheses as a JSON array (NULL = typed or unknown)
+                ALTER TABLE messages ADD COLUMN asr_confidence REAL;
+                ALTER TABLE messages ADD COLUMN asr_alternatives TEXT;
+
+                -- Update schema version
+                PRAGMA user_version = 13;
+                ",
+            )
+            .map_err(|e| format!("Migration v13 failed: {}", e))?;
+
+            println!("✅ Migration v13 completed");
+        }
+
+        // Migration v13 -> v14: Add word timings to messages
+        if version < 14 {
+            println!("🔄 Running migration v13 -> v14: Add word timings to messages");
+
+            conn.execute_batch(
+                "
+                -- ASR word timings as a JSON array (NULL = typed or unknown)
+                ALTER TABLE messages ADD COLUMN word_timings TEXT;
+
+                -- Update schema version
+                PRAGMA user_version = 14;
+                ",
+            )
+            .map_err(|e| format!("Migration v14 failed: {}", e))?;
+
+            println!("✅ Migration v14 completed");
+        }
+
+        // Migration v14 -> v15: Add speaker segments table
+        if version < 15 {
+            println!("🔄 Running migration v14 -> v15: Add speaker segments table");
+
+            conn.execute_batch(
+                "
+                -- Per-speaker parts of diarized messages
+                CREATE TABLE IF NOT EXISTS message_segments (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    message_id TEXT NOT NULL,
+                    speaker INTEGER NOT NULL,
+                    start_ms INTEGER NOT NULL,
+                    end_ms INTEGER NOT NULL,
+                    text TEXT NOT NULL,
+                    FOREIGN KEY(message_id) REFERENCES messages(id) ON DELETE CASCADE
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_message_segments_message ON message_segments(message_id, start_ms);
+
+                -- Update schema version
+                PRAGMA user_version = 15;
+                ",
+            )
+            .map_err(|e| format!("Migration v15 failed: {}", e))?;
+
+            println!("✅ Migration v15 completed");
+        }
+
+        // Migration v15 -> v16: Add transcription jobs table
+        if version < 16 {
+            println!("🔄 Running migration v15 -> v16: Add transcription jobs table");
+
+            conn.execute_batch(
+                "
+                -- File transcription queue
+                CREATE TABLE IF NOT EXISTS transcription_jobs (
+                    id TEXT PRIMARY KEY,
+                    path TEXT NOT NULL,
+                    file_name TEXT NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'queued',
+                    percent INTEGER NOT NULL DEFAULT 0,
+                    session_id TEXT,
+                    error TEXT,
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_transcription_jobs_status ON transcription_jobs(status, created_at);
+
+                -- Update schema version
+                PRAGMA user_version = 16;
+                ",
+            )
+            .map_err(|e| format!("Migration v16 failed: {}", e))?;
+
+            println!("✅ Migration v16 completed");
+        }
+
+        // Migration v16 -> v17: Add session trash
+        if version < 17 {
+            println!("🔄 Running migration v16 -> v17: Add session trash");
+
+            conn.execute_batch(
+                "
+                -- Sessions in the trash (NULL = not deleted)
+                ALTER TABLE sessions ADD COLUMN deleted_at INTEGER;
+
+                CREATE INDEX IF NOT EXISTS idx_sessions_deleted ON sessions(deleted_at);
+
+                -- Update schema version
+                PRAGMA user_version = 17;
+                ",
+            )
+            .map_err(|e| format!("Migration v17 failed: {}", e))?;
+
+            println!("✅ Migration v17 completed");
+        }
+
+        // Migration v17 -> v18: Add message pinning
+        if version < 18 {
+            println!("🔄 Running migration v17 -> v18: Add message pinning");
+
+            conn.execute_batch(
+                "
+                -- When the message was pinned (NULL = not pinned)
+                ALTER TABLE messages ADD COLUMN pinned_at INTEGER;
+
+                CREATE INDEX IF NOT EXISTS idx_messages_pinned ON messages(pinned_at);
+
+                -- Update schema version
+                PRAGMA user_version = 18;
+                ",
+            )
+            .map_err(|e| format!("Migration v18 failed: {}", e))?;
+
+            println!("✅ Migration v18 completed");
+        }
+
+        // Migration v18 -> v19: Add custom vocabulary
+        if version < 19 {
+            println!("🔄 Running migration v18 -> v19: Add custom vocabulary");
+
+            conn.execute_batch(
+                "
+                -- Names and terms the ASR should prefer (hotwords)
+                CREATE TABLE IF NOT EXISTS vocabulary_terms (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    term TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                    created_at INTEGER NOT NULL
+                );
+
+                -- Update schema version
+                PRAGMA user_version = 19;
+                ",
+            )
+            .map_err(|e| format!("Migration v19 failed: {}", e))?;
+
+            println!("✅ Migration v19 completed");
+        }
+
+        // Migration v19 -> v20: Add streamed reply drafts
+        if version < 20 {
+            println!("🔄 Running migration v19 -> v20: Add streamed reply drafts");
+
+            conn.execute_batch(
+                "
+                -- 'draft' while a reply is streamed, 'interrupted' if it ended early
+                ALTER TABLE messages ADD COLUMN status TEXT;
+
+                -- Update schema version
+                PRAGMA user_version = 20;
+                ",
+            )
+            .map_err(|e| format!("Migration v20 failed: {}", e))?;
+
+            println!("✅ Migration v20 completed");
+        }
+
+        // Migration v20 -> v21: Add symbol shortcuts
+        if version < 21 {
+            println!("🔄 Running migration v20 -> v21: Add symbol shortcuts");
+
+            conn.execute_batch(
+                "
+                -- Spoken phrases typed as symbols or emoji, on top of the built-in ones
+                CREATE TABLE IF NOT EXISTS symbol_shortcuts (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    phrase TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                    symbol TEXT NOT NULL,
+                    created_at INTEGER NOT NULL
+                );
+
+                -- Update schema version
+                PRAGMA user_version = 21;
+                ",
+            )
+            .map_err(|e| format!("Migration v21 failed: {}", e))?;
+
+            println!("✅ Migration v21 completed");
+        }
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Session CRUD Operations
+    // ========================================================================
+
+    /// Create a new session
+    pub fn create_session(&self, title: String) -> Result<Session, String> {
+        let conn = acquire_lock(&self.conn, "create_session")?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT INTO sessions (id, title, is_favorite, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, title, 0, now, now],
+        )
+        .map_err(|e| format!("Failed to create session: {}", e))?;
+
+        Ok(Session {
+            id,
+            title,
+            is_favorite: false,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        })
+    }
+
+    /// List sessions with pagination (trashed sessions excluded)
+    #[allow(dead_code)]
+    pub fn list_sessions(&self, page: i32, page_size: i32) -> Result<PaginatedResult<Session>, String> {
+        self.list_sessions_filtered(page, page_size, None, false)
+    }
+
+    /// List sessions with pagination and optional favorite filter
+    ///
+    /// `trashed` lists the sessions in the trash instead of the others.
+    pub fn list_sessions_filtered(
+        &self,
+        page: i32,
+        page_size: i32,
+        filter_favorite: Option<bool>,
+        trashed: bool,
+    ) -> Result<PaginatedResult<Session>, String> {
+        let conn = acquire_lock(&self.conn, "list_sessions_filtered")?;
+
+        // Build WHERE clause for filtering
+        let trash_condition = if trashed { "deleted_at IS NOT NULL" } else { "deleted_at IS NULL" };
+        let where_clause = match filter_favorite {
+            Some(true) => format!(" WHERE {} AND is_favorite = 1", trash_condition),
+            Some(false) => format!(" WHERE {} AND is_favorite = 0", trash_condition),
+            None => format!(" WHERE {}", trash_condition),
+        };
+
+        // Get total count
+        let total: i64 = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM sessions{}", where_clause),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to count sessions: {}", e))?;
+
+        // Calculate offset
+        let offset = (page - 1) * page_size;
+
+        // Query sessions
+        let query = format!(
+            "SELECT id, title, is_favorite, created_at, updated_at, deleted_at FROM sessions{} ORDER BY updated_at DESC LIMIT ?1 OFFSET ?2",
+            where_clause
+        );
+
+        let mut stmt = conn
+            .prepare(&query)
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let sessions = stmt
+            .query_map(params![page_size, offset], |row| {
+                Ok(Session {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    is_favorite: row.get::<_, i32>(2)? == 1,
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                    deleted_at: row.get(5)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query sessions: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect sessions: {}", e))?;
+
+        let has_more = (offset + page_size) < total as i32;
+
+        Ok(PaginatedResult {
+            items: sessions,
+            total,
+            page,
+            page_size,
+            has_more,
+        })
+    }
+
+    /// List every session not in the trash (newest first), used for export
+    pub fn list_all_sessions(&self) -> Result<Vec<Session>, String> {
+        let conn = acquire_lock(&self.conn, "list_all_sessions")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, title, is_favorite, created_at, updated_at, deleted_at FROM sessions
+                 WHERE deleted_at IS NULL
+                 ORDER BY updated_at DESC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let sessions = stmt
+            .query_map([], |row| {
+                Ok(Session {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    is_favorite: row.get::<_, i32>(2)? == 1,
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                    deleted_at: row.get(5)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query sessions: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect sessions: {}", e))?;
+
+        Ok(sessions)
+    }
+
+    /// Get a single session by ID
+    pub fn get_session(&self, session_id: &str) -> Result<Session, String> {
+        let conn = acquire_lock(&self.conn, "get_session")?;
+
+        conn.query_row(
+            "SELECT id, title, is_favorite, created_at, updated_at, deleted_at FROM sessions WHERE id = ?1",
+            params![session_id],
+            |row| {
+                Ok(Session {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    is_favorite: row.get::<_, i32>(2)? == 1,
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                    deleted_at: row.get(5)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Session not found: {}", e))
+    }
+
+    /// Toggle favorite status of a session
+    pub fn toggle_favorite(&self, session_id: &str) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "toggle_favorite")?;
+
+        // Get current state directly without calling get_session (avoids deadlock)
+        let current_state: i32 = conn
+            .query_row(
+                "SELECT is_favorite FROM sessions WHERE id = ?1",
+                params![session_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to query session: {}", e))?;
+
+        let new_state = current_state == 0;
+
+        // Update state
+        let now = chrono::Utc::now().timestamp_millis();
+        let rows_affected = conn
+            .execute(
+                "UPDATE sessions SET is_favorite = ?1, updated_at = ?2 WHERE id = ?3",
+                params![if new_state { 1 } else { 0 }, now, session_id],
+            )
+            .map_err(|e| format!("Failed to toggle favorite: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err("Session not found".to_string());
+        }
+
+        Ok(new_state)
+    }
+
+    /// Update a session's title
+    pub fn update_session(&self, session_id: &str, title: String) -> Result<Session, String> {
+        let conn = acquire_lock(&self.conn, "update_session")?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE sessions SET title = ?1, updated_at = ?2 WHERE id = ?3",
+                params![title, now, session_id],
+            )
+            .map_err(|e| format!("Failed to update session: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err("Session not found".to_string());
+        }
+
+        // Return updated session
+        drop(conn);
+        self.get_session(session_id)
+    }
+
+    /// Delete a session and all its messages
+    pub fn delete_session(&self, session_id: &str) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_session")?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM sessions WHERE id = ?1", params![session_id])
+            .map_err(|e| format!("Failed to delete session: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Move a session to the trash; returns false if it was not found or already there
+    pub fn trash_session(&self, session_id: &str) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "trash_session")?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE sessions SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+                params![now, session_id],
+            )
+            .map_err(|e| format!("Failed to trash session: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Take a session out of the trash; returns false if it was not in the trash
+    pub fn restore_session(&self, session_id: &str) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "restore_session")?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE sessions SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+                params![session_id],
+            )
+            .map_err(|e| format!("Failed to restore session: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Delete the sessions in the trash (only those trashed before `cutoff`, if given)
+    pub fn empty_trash(&self, cutoff: Option<i64>) -> Result<usize, String> {
+        let conn = acquire_lock(&self.conn, "empty_trash")?;
+
+        conn.execute(
+            "DELETE FROM sessions WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)",
+            params![cutoff],
+        )
+        .map_err(|e| format!("Failed to empty trash: {}", e))
+    }
+
+    // ========================================================================
+    // Message CRUD Operations
+    // ========================================================================
+
+    /// Add a message to a session
+    pub fn add_message(
+        &self,
+        session_id: &str,
+        role: &str,
+        content: &str,
+    ) -> Result<Message, String> {
+        let conn = acquire_lock(&self.conn, "add_message")?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        // Insert message
+        conn.execute(
+            "INSERT INTO messages (id, session_id, role, content, timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, session_id, role, content, now],
+        )
+        .map_err(|e| format!("Failed to add message: {}", e))?;
+
+        // Update session's updated_at
+        conn.execute(
+            "UPDATE sessions SET updated_at = ?1 WHERE id = ?2",
+            params![now, session_id],
+        )
+        .map_err(|e| format!("Failed to update session timestamp: {}", e))?;
+
+        Ok(Message {
+            id,
+            session_id: session_id.to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            timestamp: now,
+            audio_path: None,
+            language: None,
+            segments: Vec::new(),
+            pinned: false,
+            status: None,
+        })
+    }
+
+    /// Get messages for a session with pagination
+    pub fn get_messages(
+        &self,
+        session_id: &str,
+        page: i32,
+        page_size: i32,
+    ) -> Result<PaginatedResult<Message>, String> {
+        let conn = acquire_lock(&self.conn, "get_messages")?;
+
+        // Get total count for this session
+        let total: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM messages WHERE session_id = ?1",
+                params![session_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to count messages: {}", e))?;
+
+        // Calculate offset
+        let offset = (page - 1) * page_size;
+
+        // Query messages (ordered by timestamp ascending for chat display)
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, session_id, role, content, timestamp, audio_path, language, pinned_at IS NOT NULL, status FROM messages
+                 WHERE session_id = ?1
+                 ORDER BY timestamp ASC
+                 LIMIT ?2 OFFSET ?3",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let mut messages = stmt
+            .query_map(params![session_id, page_size, offset], |row| {
+                Ok(Message {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    role: row.get(2)?,
+                    content: row.get(3)?,
+                    timestamp: row.get(4)?,
+                    audio_path: row.get(5)?,
+                    language: row.get(6)?,
+                    segments: Vec::new(),
+                    pinned: row.get(7)?,
+                    status: row.get(8)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query messages: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect messages: {}", e))?;
+
+        for message in &mut messages {
+            message.segments = load_segments(&conn, &message.id)?;
+        }
+
+        let has_more = (offset + page_size) < total as i32;
+
+        Ok(PaginatedResult {
+            items: messages,
+            total,
+            page,
+            page_size,
+            has_more,
+        })
+    }
+
+    /// Get every message of a session without pagination, used for export
+    pub fn get_all_messages(&self, session_id: &str) -> Result<Vec<Message>, String> {
+        let conn = acquire_lock(&self.conn, "get_all_messages")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, session_id, role, content, timestamp, language, pinned_at IS NOT NULL, status FROM messages
+                 WHERE session_id = ?1
+                 ORDER BY timestamp ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let messages = stmt
+            .query_map(params![session_id], |row| {
+                Ok(Message {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    role: row.get(2)?,
+                    content: row.get(3)?,
+                    timestamp: row.get(4)?,
+                    // Local recordings are not part of an export
+                    audio_path: None,
+                    language: row.get(5)?,
+                    segments: Vec::new(),
+                    pinned: row.get(6)?,
+                    status: row.get(7)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query messages: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect messages: {}", e))?;
+
+        Ok(messages)
+    }
+
+    /// Get the latest `limit` messages of a session, oldest first
+    pub fn get_recent_messages(&self, session_id: &str, limit: i32) -> Result<Vec<Message>, String> {
+        let conn = acquire_lock(&self.conn, "get_recent_messages")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, session_id, role, content, timestamp, audio_path, language, pinned_at IS NOT NULL, status FROM messages
+                 WHERE session_id = ?1
+                 ORDER BY timestamp DESC
+                 LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let mut messages = stmt
+            .query_map(params![session_id, limit], |row| {
+                Ok(Message {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    role: row.get(2)?,
+                    content: row.get(3)?,
+                    timestamp: row.get(4)?,
+                    audio_path: row.get(5)?,
+                    language: row.get(6)?,
+                    segments: Vec::new(),
+                    pinned: row.get(7)?,
+                    status: row.get(8)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query messages: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect messages: {}", e))?;
+
+        messages.reverse();
+        Ok(messages)
+    }
+
+    /// Import a session and its messages, keeping existing records
+    ///
+    /// Sessions and messages are deduplicated by id. Returns whether the session
+    /// was newly created and how many messages were inserted.
+    pub fn import_session(&self, session: &Session, messages: &[Message]) -> Result<(bool, usize), String> {
+        let mut conn = acquire_lock(&self.conn, "import_session")?;
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let session_created = tx
+            .execute(
+                "INSERT OR IGNORE INTO sessions (id, title, is_favorite, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    session.id,
+                    session.title,
+                    if session.is_favorite { 1 } else { 0 },
+                    session.created_at,
+                    session.updated_at
+                ],
+            )
+            .map_err(|e| format!("Failed to import session: {}", e))?
+            > 0;
+
+        let mut messages_inserted = 0;
+        for msg in messages {
+            // Messages always belong to the session they were exported with
+            messages_inserted += tx
+                .execute(
+                    "INSERT OR IGNORE INTO messages (id, session_id, role, content, timestamp, language) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![msg.id, session.id, msg.role, msg.content, msg.timestamp, msg.language],
+                )
+                .map_err(|e| format!("Failed to import message: {}", e))?;
+        }
+
+        // Merged messages may be newer than the local session
+        if !session_created && messages_inserted > 0 {
+            tx.execute(
+                "UPDATE sessions SET updated_at = MAX(updated_at, ?1) WHERE id = ?2",
+                params![session.updated_at, session.id],
+            )
+            .map_err(|e| format!("Failed to update session: {}", e))?;
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit import: {}", e))?;
+
+        Ok((session_created, messages_inserted))
+    }
+
+    /// Delete a single message
+    pub fn delete_message(&self, message_id: &str) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_message")?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM messages WHERE id = ?1", params![message_id])
+            .map_err(|e| format!("Failed to delete message: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    // ========================================================================
+    // Streamed Reply Drafts
+    // ========================================================================
+
+    /// Add an empty assistant message for a reply that is about to be streamed
+    pub fn add_draft_message(&self, session_id: &str) -> Result<Message, String> {
+        let conn = acquire_lock(&self.conn, "add_draft_message")?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT INTO messages (id, session_id, role, content, timestamp, status)
+             VALUES (?1, ?2, 'assistant', '', ?3, ?4)",
+            params![id, session_id, now, MESSAGE_STATUS_DRAFT],
+        )
+        .map_err(|e| format!("Failed to add draft message: {}", e))?;
+
+        conn.execute(
+            "UPDATE sessions SET updated_at = ?1 WHERE id = ?2",
+            params![now, session_id],
+        )
+        .map_err(|e| format!("Failed to update session timestamp: {}", e))?;
+
+        Ok(Message {
+            id,
+            session_id: session_id.to_string(),
+            role: "assistant".to_string(),
+            content: String::new(),
+            timestamp: now,
+            audio_path: None,
+            language: None,
+            segments: Vec::new(),
+            pinned: false,
+            status: Some(MESSAGE_STATUS_DRAFT.to_string()),
+        })
+    }
+
+    /// Save the text of a draft so far; `status` None marks it complete
+    pub fn update_draft_message(&self, message_id: &str, content: &str, status: Option<&str>) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "update_draft_message")?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE messages SET content = ?1, status = ?2 WHERE id = ?3",
+                params![content, status, message_id],
+            )
+            .map_err(|e| format!("Failed to update draft message: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Latest draft or interrupted reply of a session
+    pub fn get_draft_message(&self, session_id: &str) -> Result<Option<Message>, String> {
+        let conn = acquire_lock(&self.conn, "get_draft_message")?;
+
+        conn.query_row(
+            "SELECT id, session_id, role, content, timestamp, status FROM messages
+             WHERE session_id = ?1 AND status IS NOT NULL
+             ORDER BY timestamp DESC
+             LIMIT 1",
+            params![session_id],
+            |row| {
+                Ok(Message {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    role: row.get(2)?,
+                    content: row.get(3)?,
+                    timestamp: row.get(4)?,
+                    audio_path: None,
+                    language: None,
+                    segments: Vec::new(),
+                    pinned: false,
+                    status: row.get(5)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to query draft message: {}", e))
+    }
+
+    /// Mark drafts left by a run that ended mid-stream as interrupted
+    pub fn interrupt_stale_drafts(&self) -> Result<usize, String> {
+        let conn = acquire_lock(&self.conn, "interrupt_stale_drafts")?;
+
+        conn.execute(
+            "UPDATE messages SET status = ?1 WHERE status = ?2",
+            params![MESSAGE_STATUS_INTERRUPTED, MESSAGE_STATUS_DRAFT],
+        )
+        .map_err(|e| format!("Failed to interrupt drafts: {}", e))
+    }
+
+    // ========================================================================
+    // Pinned Messages
+    // ========================================================================
+
+    /// Pin or unpin a message; returns false if the message was not found
+    pub fn set_message_pinned(&self, message_id: &str, pinned: bool) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "set_message_pinned")?;
+        let pinned_at = pinned.then(|| chrono::Utc::now().timestamp_millis());
+
+        // Pinning again keeps the original pin time (and so the order)
+        let rows_affected = conn
+            .execute(
+                "UPDATE messages SET pinned_at = CASE WHEN ?1 IS NULL THEN NULL ELSE COALESCE(pinned_at, ?1) END
+                 WHERE id = ?2",
+                params![pinned_at, message_id],
+            )
+            .map_err(|e| format!("Failed to pin message: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// List pinned messages (most recently pinned first), skipping trashed sessions
+    pub fn list_pinned_messages(&self) -> Result<Vec<PinnedMessage>, String> {
+        let conn = acquire_lock(&self.conn, "list_pinned_messages")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT m.id, m.session_id, s.title, m.role, m.content, m.timestamp, m.pinned_at
+                 FROM messages m
+                 JOIN sessions s ON s.id = m.session_id
+                 WHERE m.pinned_at IS NOT NULL AND s.deleted_at IS NULL
+                 ORDER BY m.pinned_at DESC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let messages = stmt
+            .query_map([], |row| {
+                Ok(PinnedMessage {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    session_title: row.get(2)?,
+                    role: row.get(3)?,
+                    content: row.get(4)?,
+                    timestamp: row.get(5)?,
+                    pinned_at: row.get(6)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query pinned messages: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect pinned messages: {}", e))?;
+
+        Ok(messages)
+    }
+
+    // ========================================================================
+    // History Retention
+    // ========================================================================
+
+    /// Delete old sessions and messages; favorite sessions are never touched
+    ///
+    /// - sessions last updated before `age_cutoff` or `inactive_cutoff` are deleted
+    /// - then the oldest messages are deleted while there are more than
+    ///   `max_messages`, along with the sessions this leaves empty
+    ///
+    /// With `dry_run` the deletions are rolled back and only reported.
+    pub fn apply_retention(
+        &self,
+        age_cutoff: Option<i64>,
+        inactive_cutoff: Option<i64>,
+        max_messages: Option<i64>,
+        dry_run: bool,
+    ) -> Result<RetentionReport, String> {
+        let mut conn = acquire_lock(&self.conn, "apply_retention")?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let mut report = RetentionReport { dry_run, sessions: Vec::new(), messages: 0 };
+
+        if let Some(cutoff) = age_cutoff.max(inactive_cutoff) {
+            let expired = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT s.id, s.title, s.updated_at,
+                                (SELECT COUNT(*) FROM messages m WHERE m.session_id = s.id)
+                         FROM sessions s
+                         WHERE s.is_favorite = 0 AND s.updated_at < ?1
+                         ORDER BY s.updated_at ASC",
+                    )
+                    .map_err(|e| format!("Failed to prepare query: {}", e))?;
+                let rows = stmt
+                    .query_map(params![cutoff], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?))
+                    })
+                    .map_err(|e| format!("Failed to query expired sessions: {}", e))?
+                    .collect::<SqliteResult<Vec<_>>>()
+                    .map_err(|e| format!("Failed to collect expired sessions: {}", e))?;
+                rows
+            };
+
+            for (id, title, updated_at, message_count) in expired {
+                tx.execute("DELETE FROM sessions WHERE id = ?1", params![id])
+                    .map_err(|e| format!("Failed to delete session: {}", e))?;
+                let reason = if age_cutoff.is_some_and(|cutoff| updated_at < cutoff) { "age" } else { "not_favorite" };
+                report.messages += message_count as usize;
+                report.sessions.push(RetentionSession { id, title, updated_at, reason: reason.to_string() });
+            }
+        }
+
+        if let Some(max_messages) = max_messages {
+            let total: i64 = tx
+                .query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))
+                .map_err(|e| format!("Failed to count messages: {}", e))?;
+            let excess = total - max_messages;
+            if excess > 0 {
+                let oldest = "SELECT m.id, m.session_id FROM messages m
+                              JOIN sessions s ON s.id = m.session_id
+                              WHERE s.is_favorite = 0
+                              ORDER BY m.timestamp ASC
+                              LIMIT ?1";
+                let touched = {
+                    let mut stmt = tx
+                        .prepare(&format!("SELECT DISTINCT session_id FROM ({})", oldest))
+                        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+                    let rows = stmt
+                        .query_map(params![excess], |row| row.get::<_, String>(0))
+                        .map_err(|e| format!("Failed to query oldest messages: {}", e))?
+                        .collect::<SqliteResult<Vec<_>>>()
+                        .map_err(|e| format!("Failed to collect oldest messages: {}", e))?;
+                    rows
+                };
+                report.messages += tx
+                    .execute(&format!("DELETE FROM messages WHERE id IN (SELECT id FROM ({}))", oldest), params![excess])
+                    .map_err(|e| format!("Failed to delete messages: {}", e))?;
+
+                // Sessions left without messages
+                for session_id in touched {
+                    let emptied = tx
+                        .query_row(
+                            "SELECT title, updated_at FROM sessions
+                             WHERE id = ?1 AND NOT EXISTS (SELECT 1 FROM messages WHERE session_id = ?1)",
+                            params![session_id],
+                            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+                        )
+                        .optional()
+                        .map_err(|e| format!("Failed to check session: {}", e))?;
+                    if let Some((title, updated_at)) = emptied {
+                        tx.execute("DELETE FROM sessions WHERE id = ?1", params![session_id])
+                            .map_err(|e| format!("Failed to delete session: {}", e))?;
+                        report.sessions.push(RetentionSession {
+                            id: session_id,
+                            title,
+                            updated_at,
+                            reason: "message_cap".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if dry_run {
+            tx.rollback().map_err(|e| format!("Failed to roll back retention: {}", e))?;
+        } else {
+            tx.commit().map_err(|e| format!("Failed to commit retention: {}", e))?;
+        }
+        Ok(report)
+    }
+
+    // ========================================================================
+    // Message Language
+    // ========================================================================
+
+    /// Record the ASR language of a message
+    pub fn set_message_language(&self, message_id: &str, language: &str) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "set_message_language")?;
+
+        conn.execute(
+            "UPDATE messages SET language = ?1 WHERE id = ?2",
+            params![language, message_id],
+        )
+        .map_err(|e| format!("Failed to set message language: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Store the ASR confidence and alternative transcripts of a spoken message
+    pub fn set_message_alternatives(
+        &self,
+        message_id: &str,
+        confidence: Option<f64>,
+        alternatives: &[String],
+    ) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "set_message_alternatives")?;
+        let alternatives = serde_json::to_string(alternatives)
+            .map_err(|e| format!("Failed to serialize alternatives: {}", e))?;
+
+        conn.execute(
+            "UPDATE messages SET asr_confidence = ?1, asr_alternatives = ?2 WHERE id = ?3",
+            params![confidence, alternatives, message_id],
+        )
+        .map_err(|e| format!("Failed to set message alternatives: {}", e))?;
+
+        Ok(())
+    }
+
+    /// ASR confidence and alternative transcripts of a message (empty for typed messages)
+    pub fn get_message_alternatives(&self, message_id: &str) -> Result<(Option<f64>, Vec<String>), String> {
+        let conn = acquire_lock(&self.conn, "get_message_alternatives")?;
+
+        let (confidence, alternatives): (Option<f64>, Option<String>) = conn
+            .query_row(
+                "SELECT asr_confidence, asr_alternatives FROM messages WHERE id = ?1",
+                params![message_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| format!("Failed to get message alternatives: {}", e))?;
+
+        let alternatives = alternatives
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Ok((confidence, alternatives))
+    }
+
+    /// Store the speaker segments of a diarized message, replacing any previous ones
+    pub fn set_message_segments(&self, message_id: &str, segments: &[SpeakerSegment]) -> Result<(), String> {
+        let mut conn = acquire_lock(&self.conn, "set_message_segments")?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        tx.execute("DELETE FROM message_segments WHERE message_id = ?1", params![message_id])
+            .map_err(|e| format!("Failed to clear message segments: {}", e))?;
+        for segment in segments {
+            tx.execute(
+                "INSERT INTO message_segments (message_id, speaker, start_ms, end_ms, text)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![message_id, segment.speaker, segment.start_ms as i64, segment.end_ms as i64, segment.text],
             )
-            .map_err(|e| format!("Failed to toggle favorite: {}", e))?;
+            .map_err(|e| format!("Failed to add message segment: {}", e))?;
+        }
+
+        tx.commit().map_err(|e| format!("Failed to commit message segments: {}", e))?;
+        Ok(())
+    }
+
+    /// Store the ASR word timings of a spoken message
+    pub fn set_message_word_timings(&self, message_id: &str, words: &[WordTiming]) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "set_message_word_timings")?;
+        let words = serde_json::to_string(words)
+            .map_err(|e| format!("Failed to serialize word timings: {}", e))?;
+
+        conn.execute(
+            "UPDATE messages SET word_timings = ?1 WHERE id = ?2",
+            params![words, message_id],
+        )
+        .map_err(|e| format!("Failed to set message word timings: {}", e))?;
+
+        Ok(())
+    }
+
+    /// ASR word timings of a message (empty for typed messages)
+    pub fn get_message_word_timings(&self, message_id: &str) -> Result<Vec<WordTiming>, String> {
+        let conn = acquire_lock(&self.conn, "get_message_word_timings")?;
+
+        let words: Option<String> = conn
+            .query_row(
+                "SELECT word_timings FROM messages WHERE id = ?1",
+                params![message_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to get message word timings: {}", e))?;
+
+        Ok(words.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default())
+    }
+
+    /// Language of the latest spoken message in a session (None if there is none)
+    pub fn get_session_language(&self, session_id: &str) -> Result<Option<String>, String> {
+        let conn = acquire_lock(&self.conn, "get_session_language")?;
+
+        conn.query_row(
+            "SELECT language FROM messages
+             WHERE session_id = ?1 AND language IS NOT NULL
+             ORDER BY timestamp DESC
+             LIMIT 1",
+            params![session_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to get session language: {}", e))
+    }
+
+    // ========================================================================
+    // Message Audio
+    // ========================================================================
+
+    /// Link an archived recording to a message
+    pub fn set_message_audio(&self, message_id: &str, audio_path: &str) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "set_message_audio")?;
+
+        conn.execute(
+            "UPDATE messages SET audio_path = ?1 WHERE id = ?2",
+            params![audio_path, message_id],
+        )
+        .map_err(|e| format!("Failed to set message audio: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the archived recording of a message
+    pub fn get_message_audio(&self, message_id: &str) -> Result<Option<String>, String> {
+        let conn = acquire_lock(&self.conn, "get_message_audio")?;
+
+        conn.query_row(
+            "SELECT audio_path FROM messages WHERE id = ?1",
+            params![message_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to get message audio: {}", e))
+    }
+
+    /// Unlink a recording that was removed from the archive
+    pub fn clear_message_audio(&self, audio_path: &str) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "clear_message_audio")?;
+
+        conn.execute(
+            "UPDATE messages SET audio_path = NULL WHERE audio_path = ?1",
+            params![audio_path],
+        )
+        .map_err(|e| format!("Failed to clear message audio: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Every recording path still linked to a message
+    pub fn list_message_audio_paths(&self) -> Result<Vec<String>, String> {
+        let conn = acquire_lock(&self.conn, "list_message_audio_paths")?;
+
+        let mut stmt = conn
+            .prepare("SELECT audio_path FROM messages WHERE audio_path IS NOT NULL")
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let paths = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| format!("Failed to query message audio: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect message audio: {}", e))?;
+
+        Ok(paths)
+    }
+
+    // ========================================================================
+    // Latency Metrics
+    // ========================================================================
+
+    /// Store the stage timings of one voice interaction
+    pub fn add_latency_record(&self, record: &LatencyRecord) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "add_latency_record")?;
+
+        conn.execute(
+            "INSERT INTO latency_metrics (created_at, asr_ms, first_token_ms, first_audio_ms, playback_ms) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                record.created_at,
+                record.asr_ms,
+                record.first_token_ms,
+                record.first_audio_ms,
+                record.playback_ms
+            ],
+        )
+        .map_err(|e| format!("Failed to add latency record: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the most recent latency records (newest first)
+    pub fn get_latency_records(&self, limit: i32) -> Result<Vec<LatencyRecord>, String> {
+        let conn = acquire_lock(&self.conn, "get_latency_records")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT created_at, asr_ms, first_token_ms, first_audio_ms, playback_ms
+                 FROM latency_metrics
+                 ORDER BY created_at DESC
+                 LIMIT ?1",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let records = stmt
+            .query_map(params![limit], |row| {
+                Ok(LatencyRecord {
+                    created_at: row.get(0)?,
+                    asr_ms: row.get(1)?,
+                    first_token_ms: row.get(2)?,
+                    first_audio_ms: row.get(3)?,
+                    playback_ms: row.get(4)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query latency records: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect latency records: {}", e))?;
+
+        Ok(records)
+    }
+
+    /// Get the latency records created at or after `since` (oldest first)
+    pub fn get_latency_records_since(&self, since: i64) -> Result<Vec<LatencyRecord>, String> {
+        let conn = acquire_lock(&self.conn, "get_latency_records_since")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT created_at, asr_ms, first_token_ms, first_audio_ms, playback_ms
+                 FROM latency_metrics
+                 WHERE created_at >= ?1
+                 ORDER BY created_at ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let records = stmt
+            .query_map(params![since], |row| {
+                Ok(LatencyRecord {
+                    created_at: row.get(0)?,
+                    asr_ms: row.get(1)?,
+                    first_token_ms: row.get(2)?,
+                    first_audio_ms: row.get(3)?,
+                    playback_ms: row.get(4)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query latency records: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect latency records: {}", e))?;
+
+        Ok(records)
+    }
+
+    /// Timestamp and text of the user messages since `since`, trashed sessions included
+    pub fn get_user_message_texts_since(&self, since: i64) -> Result<Vec<(i64, String)>, String> {
+        let conn = acquire_lock(&self.conn, "get_user_message_texts_since")?;
+
+        let mut stmt = conn
+            .prepare("SELECT timestamp, content FROM messages WHERE role = 'user' AND timestamp >= ?1")
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let texts = stmt
+            .query_map(params![since], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to query messages: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect messages: {}", e))?;
+
+        Ok(texts)
+    }
+
+    /// Store a daemon resource sample, dropping samples older than `keep_since` (Unix milliseconds)
+    pub fn add_resource_sample(&self, sample: &ResourceSample, keep_since: i64) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "add_resource_sample")?;
+
+        conn.execute(
+            "INSERT INTO daemon_resource_metrics (created_at, pid, rss_bytes, cpu_percent) VALUES (?1, ?2, ?3, ?4)",
+            params![sample.created_at, sample.pid, sample.rss_bytes as i64, sample.cpu_percent],
+        )
+        .map_err(|e| format!("Failed to add resource sample: {}", e))?;
+
+        conn.execute("DELETE FROM daemon_resource_metrics WHERE created_at < ?1", params![keep_since])
+            .map_err(|e| format!("Failed to prune resource samples: {}", e))?;
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Recording Journal
+    // ========================================================================
+
+    /// Register a recording that is about to be transcribed
+    pub fn add_pending_recording(&self, path: &str, duration_secs: f64) -> Result<PendingRecording, String> {
+        let conn = acquire_lock(&self.conn, "add_pending_recording")?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT INTO pending_recordings (path, duration_secs, status, created_at) VALUES (?1, ?2, 'pending', ?3)",
+            params![path, duration_secs, now],
+        )
+        .map_err(|e| format!("Failed to add pending recording: {}", e))?;
+
+        Ok(PendingRecording {
+            id: conn.last_insert_rowid(),
+            path: path.to_string(),
+            duration_secs,
+            status: "pending".to_string(),
+            created_at: now,
+        })
+    }
+
+    /// Get all journaled recordings, oldest first
+    pub fn list_pending_recordings(&self) -> Result<Vec<PendingRecording>, String> {
+        let conn = acquire_lock(&self.conn, "list_pending_recordings")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, path, duration_secs, status, created_at
+                 FROM pending_recordings
+                 ORDER BY created_at ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let recordings = stmt
+            .query_map([], |row| {
+                Ok(PendingRecording {
+                    id: row.get(0)?,
+                    path: row.get(1)?,
+                    duration_secs: row.get(2)?,
+                    status: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query pending recordings: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect pending recordings: {}", e))?;
+
+        Ok(recordings)
+    }
+
+    /// Update the status of a journaled recording
+    pub fn set_pending_recording_status(&self, id: i64, status: &str) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "set_pending_recording_status")?;
+
+        conn.execute("UPDATE pending_recordings SET status = ?1 WHERE id = ?2", params![status, id])
+            .map_err(|e| format!("Failed to update pending recording: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Remove a recording from the journal
+    pub fn delete_pending_recording(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_pending_recording")?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM pending_recordings WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete pending recording: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    // ========================================================================
+    // Transcription Jobs
+    // ========================================================================
+
+    /// Queue a file for transcription
+    pub fn add_transcription_job(&self, path: &str, file_name: &str) -> Result<TranscriptionJob, String> {
+        let conn = acquire_lock(&self.conn, "add_transcription_job")?;
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT INTO transcription_jobs (id, path, file_name, status, percent, created_at, updated_at)
+             VALUES (?1, ?2, ?3, 'queued', 0, ?4, ?4)",
+            params![id, path, file_name, now],
+        )
+        .map_err(|e| format!("Failed to add transcription job: {}", e))?;
+
+        Ok(TranscriptionJob {
+            id,
+            path: path.to_string(),
+            file_name: file_name.to_string(),
+            status: "queued".to_string(),
+            percent: 0,
+            session_id: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Get a transcription job by id
+    pub fn get_transcription_job(&self, id: &str) -> Result<TranscriptionJob, String> {
+        let conn = acquire_lock(&self.conn, "get_transcription_job")?;
+
+        conn.query_row(
+            "SELECT id, path, file_name, status, percent, session_id, error, created_at, updated_at
+             FROM transcription_jobs WHERE id = ?1",
+            params![id],
+            transcription_job_from_row,
+        )
+        .optional()
+        .map_err(|e| format!("Failed to get transcription job: {}", e))?
+        .ok_or_else(|| format!("Transcription job {} not found", id))
+    }
+
+    /// Get all transcription jobs, newest first
+    pub fn list_transcription_jobs(&self) -> Result<Vec<TranscriptionJob>, String> {
+        let conn = acquire_lock(&self.conn, "list_transcription_jobs")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, path, file_name, status, percent, session_id, error, created_at, updated_at
+                 FROM transcription_jobs
+                 ORDER BY created_at DESC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let jobs = stmt
+            .query_map([], transcription_job_from_row)
+            .map_err(|e| format!("Failed to query transcription jobs: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect transcription jobs: {}", e))?;
+
+        Ok(jobs)
+    }
+
+    /// Get the oldest queued job, if any
+    pub fn next_queued_transcription_job(&self) -> Result<Option<TranscriptionJob>, String> {
+        let conn = acquire_lock(&self.conn, "next_queued_transcription_job")?;
+
+        conn.query_row(
+            "SELECT id, path, file_name, status, percent, session_id, error, created_at, updated_at
+             FROM transcription_jobs
+             WHERE status = 'queued'
+             ORDER BY created_at ASC
+             LIMIT 1",
+            [],
+            transcription_job_from_row,
+        )
+        .optional()
+        .map_err(|e| format!("Failed to get next transcription job: {}", e))
+    }
+
+    /// Save the status, progress and result of a job; sets `updated_at`
+    pub fn update_transcription_job(&self, job: &mut TranscriptionJob) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "update_transcription_job")?;
+        job.updated_at = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "UPDATE transcription_jobs
+             SET status = ?1, percent = ?2, session_id = ?3, error = ?4, updated_at = ?5
+             WHERE id = ?6",
+            params![job.status, job.percent, job.session_id, job.error, job.updated_at, job.id],
+        )
+        .map_err(|e| format!("Failed to update transcription job: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Queue again the jobs that were running when the app quit
+    pub fn requeue_interrupted_transcription_jobs(&self) -> Result<usize, String> {
+        let conn = acquire_lock(&self.conn, "requeue_interrupted_transcription_jobs")?;
+
+        conn.execute(
+            "UPDATE transcription_jobs SET status = 'queued', percent = 0
+             WHERE status IN ('decoding', 'transcribing')",
+            [],
+        )
+        .map_err(|e| format!("Failed to requeue transcription jobs: {}", e))
+    }
+
+    // ========================================================================
+    // Transcript Replacements
+    // ========================================================================
+
+    /// Add a transcript replacement
+    pub fn add_replacement(&self, pattern: &str, replacement: &str, is_regex: bool) -> Result<Replacement, String> {
+        let conn = acquire_lock(&self.conn, "add_replacement")?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT INTO text_replacements (pattern, replacement, is_regex, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![pattern, replacement, is_regex, now],
+        )
+        .map_err(|e| format!("Failed to add replacement: {}", e))?;
+
+        Ok(Replacement {
+            id: conn.last_insert_rowid(),
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            is_regex,
+            created_at: now,
+        })
+    }
+
+    /// Get all transcript replacements in the order they are applied
+    pub fn list_replacements(&self) -> Result<Vec<Replacement>, String> {
+        let conn = acquire_lock(&self.conn, "list_replacements")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, pattern, replacement, is_regex, created_at
+                 FROM text_replacements
+                 ORDER BY id ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let replacements = stmt
+            .query_map([], |row| {
+                Ok(Replacement {
+                    id: row.get(0)?,
+                    pattern: row.get(1)?,
+                    replacement: row.get(2)?,
+                    is_regex: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query replacements: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect replacements: {}", e))?;
+
+        Ok(replacements)
+    }
+
+    /// Delete a transcript replacement
+    pub fn delete_replacement(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_replacement")?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM text_replacements WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete replacement: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    // ========================================================================
+    // Personas
+    // ========================================================================
+
+    /// Create a persona
+    pub fn add_persona(&self, name: &str, system_prompt: &str, voice: Option<&str>, speed: f64) -> Result<Persona, String> {
+        let conn = acquire_lock(&self.conn, "add_persona")?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT INTO personas (name, system_prompt, voice, speed, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![name, system_prompt, voice, speed, now],
+        )
+        .map_err(|e| format!("Failed to add persona: {}", e))?;
+
+        Ok(Persona {
+            id: conn.last_insert_rowid(),
+            name: name.to_string(),
+            system_prompt: system_prompt.to_string(),
+            voice: voice.map(str::to_string),
+            speed,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Get all personas by name
+    pub fn list_personas(&self) -> Result<Vec<Persona>, String> {
+        let conn = acquire_lock(&self.conn, "list_personas")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, system_prompt, voice, speed, created_at, updated_at
+                 FROM personas
+                 ORDER BY name COLLATE NOCASE ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let personas = stmt
+            .query_map([], Self::persona_from_row)
+            .map_err(|e| format!("Failed to query personas: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect personas: {}", e))?;
+
+        Ok(personas)
+    }
+
+    /// Get a persona by ID
+    pub fn get_persona(&self, id: i64) -> Result<Persona, String> {
+        let conn = acquire_lock(&self.conn, "get_persona")?;
+
+        conn.query_row(
+            "SELECT id, name, system_prompt, voice, speed, created_at, updated_at
+             FROM personas WHERE id = ?1",
+            params![id],
+            Self::persona_from_row,
+        )
+        .map_err(|e| format!("Persona not found: {}", e))
+    }
+
+    /// Update a persona
+    pub fn update_persona(
+        &self,
+        id: i64,
+        name: &str,
+        system_prompt: &str,
+        voice: Option<&str>,
+        speed: f64,
+    ) -> Result<Persona, String> {
+        {
+            let conn = acquire_lock(&self.conn, "update_persona")?;
+            let now = chrono::Utc::now().timestamp_millis();
+
+            let rows_affected = conn
+                .execute(
+                    "UPDATE personas SET name = ?1, system_prompt = ?2, voice = ?3, speed = ?4, updated_at = ?5
+                     WHERE id = ?6",
+                    params![name, system_prompt, voice, speed, now, id],
+                )
+                .map_err(|e| format!("Failed to update persona: {}", e))?;
+            if rows_affected == 0 {
+                return Err(format!("Persona not found: {}", id));
+            }
+        }
+
+        self.get_persona(id)
+    }
+
+    /// Delete a persona
+    pub fn delete_persona(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_persona")?;
 
-        if rows_affected == 0 {
-            return Err("Session not found".to_string());
-        }
+        let rows_affected = conn
+            .execute("DELETE FROM personas WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete persona: {}", e))?;
 
-        Ok(new_state)
+        Ok(rows_affected > 0)
     }
 
-    /// Update a session's title
-    pub fn update_session(&self, session_id: &str, title: String) -> Result<Session, String> {
-        let conn = acquire_lock(&self.conn, "update_session")?;
+    fn persona_from_row(row: &rusqlite::Row) -> SqliteResult<Persona> {
+        Ok(Persona {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            system_prompt: row.get(2)?,
+            voice: row.get(3)?,
+            speed: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+
+    // ========================================================================
+    // Prompt Templates
+    // ========================================================================
 
+    /// Create a prompt template
+    pub fn add_prompt_template(
+        &self,
+        name: &str,
+        template: &str,
+        hotkey: Option<&str>,
+        output: &str,
+    ) -> Result<PromptTemplate, String> {
+        let conn = acquire_lock(&self.conn, "add_prompt_template")?;
         let now = chrono::Utc::now().timestamp_millis();
 
-        let rows_affected = conn
-            .execute(
-                "UPDATE sessions SET title = ?1, updated_at = ?2 WHERE id = ?3",
-                params![title, now, session_id],
+        conn.execute(
+            "INSERT INTO prompt_templates (name, template, hotkey, output, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![name, template, hotkey, output, now],
+        )
+        .map_err(|e| format!("Failed to add prompt template: {}", e))?;
+
+        Ok(PromptTemplate {
+            id: conn.last_insert_rowid(),
+            name: name.to_string(),
+            template: template.to_string(),
+            hotkey: hotkey.map(str::to_string),
+            output: output.to_string(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Get all prompt templates by name
+    pub fn list_prompt_templates(&self) -> Result<Vec<PromptTemplate>, String> {
+        let conn = acquire_lock(&self.conn, "list_prompt_templates")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, template, hotkey, output, created_at, updated_at
+                 FROM prompt_templates
+                 ORDER BY name COLLATE NOCASE ASC",
             )
-            .map_err(|e| format!("Failed to update session: {}", e))?;
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
-        if rows_affected == 0 {
-            return Err("Session not found".to_string());
+        let templates = stmt
+            .query_map([], Self::prompt_template_from_row)
+            .map_err(|e| format!("Failed to query prompt templates: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect prompt templates: {}", e))?;
+
+        Ok(templates)
+    }
+
+    /// Get a prompt template by ID
+    pub fn get_prompt_template(&self, id: i64) -> Result<PromptTemplate, String> {
+        let conn = acquire_lock(&self.conn, "get_prompt_template")?;
+
+        conn.query_row(
+            "SELECT id, name, template, hotkey, output, created_at, updated_at
+             FROM prompt_templates WHERE id = ?1",
+            params![id],
+            Self::prompt_template_from_row,
+        )
+        .map_err(|e| format!("Prompt template not found: {}", e))
+    }
+
+    /// Update a prompt template
+    pub fn update_prompt_template(
+        &self,
+        id: i64,
+        name: &str,
+        template: &str,
+        hotkey: Option<&str>,
+        output: &str,
+    ) -> Result<PromptTemplate, String> {
+        {
+            let conn = acquire_lock(&self.conn, "update_prompt_template")?;
+            let now = chrono::Utc::now().timestamp_millis();
+
+            let rows_affected = conn
+                .execute(
+                    "UPDATE prompt_templates SET name = ?1, template = ?2, hotkey = ?3, output = ?4, updated_at = ?5
+                     WHERE id = ?6",
+                    params![name, template, hotkey, output, now, id],
+                )
+                .map_err(|e| format!("Failed to update prompt template: {}", e))?;
+            if rows_affected == 0 {
+                return Err(format!("Prompt template not found: {}", id));
+            }
         }
 
-        // Return updated session
-        drop(conn);
-        self.get_session(session_id)
+        self.get_prompt_template(id)
     }
 
-    /// Delete a session and all its messages
-    pub fn delete_session(&self, session_id: &str) -> Result<bool, String> {
-        let conn = acquire_lock(&self.conn, "delete_session")?;
+    /// Delete a prompt template
+    pub fn delete_prompt_template(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_prompt_template")?;
 
         let rows_affected = conn
-            .execute("DELETE FROM sessions WHERE id = ?1", params![session_id])
-            .map_err(|e| format!("Failed to delete session: {}", e))?;
+            .execute("DELETE FROM prompt_templates WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete prompt template: {}", e))?;
 
         Ok(rows_affected > 0)
     }
 
+    fn prompt_template_from_row(row: &rusqlite::Row) -> SqliteResult<PromptTemplate> {
+        Ok(PromptTemplate {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            template: row.get(2)?,
+            hotkey: row.get(3)?,
+            output: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+
     // ========================================================================
-    // Message CRUD Operations
+    // Reminders
     // ========================================================================
 
-    /// Add a message to a session
-    pub fn add_message(
-        &self,
-        session_id: &str,
-        role: &str,
-        content: &str,
-    ) -> Result<Message, String> {
-        let conn = acquire_lock(&self.conn, "add_message")?;
-
-        let id = uuid::Uuid::new_v4().to_string();
+    /// Create a reminder
+    pub fn add_reminder(&self, text: &str, schedule: &str, recurring: bool, next_run: i64) -> Result<Reminder, String> {
+        let conn = acquire_lock(&self.conn, "add_reminder")?;
         let now = chrono::Utc::now().timestamp_millis();
 
-        // Insert message
         conn.execute(
-            "INSERT INTO messages (id, session_id, role, content, timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![id, session_id, role, content, now],
+            "INSERT INTO reminders (text, schedule, recurring, next_run, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![text, schedule, recurring, next_run, now],
         )
-        .map_err(|e| format!("Failed to add message: {}", e))?;
+        .map_err(|e| format!("Failed to add reminder: {}", e))?;
+
+        Ok(Reminder {
+            id: conn.last_insert_rowid(),
+            text: text.to_string(),
+            schedule: schedule.to_string(),
+            recurring,
+            next_run: Some(next_run),
+            created_at: now,
+        })
+    }
+
+    /// Get all reminders, upcoming first (fired one-time reminders last)
+    pub fn list_reminders(&self) -> Result<Vec<Reminder>, String> {
+        let conn = acquire_lock(&self.conn, "list_reminders")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, text, schedule, recurring, next_run, created_at
+                 FROM reminders
+                 ORDER BY next_run IS NULL, next_run ASC, created_at DESC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let reminders = stmt
+            .query_map([], Self::reminder_from_row)
+            .map_err(|e| format!("Failed to query reminders: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect reminders: {}", e))?;
+
+        Ok(reminders)
+    }
+
+    /// Reminders whose trigger time is at or before `now` (Unix milliseconds)
+    pub fn due_reminders(&self, now: i64) -> Result<Vec<Reminder>, String> {
+        let conn = acquire_lock(&self.conn, "due_reminders")?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, text, schedule, recurring, next_run, created_at
+                 FROM reminders
+                 WHERE next_run IS NOT NULL AND next_run <= ?1
+                 ORDER BY next_run ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let reminders = stmt
+            .query_map(params![now], Self::reminder_from_row)
+            .map_err(|e| format!("Failed to query reminders: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect reminders: {}", e))?;
+
+        Ok(reminders)
+    }
+
+    /// Set the next trigger time (None = done)
+    pub fn set_reminder_next_run(&self, id: i64, next_run: Option<i64>) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "set_reminder_next_run")?;
+
+        conn.execute("UPDATE reminders SET next_run = ?1 WHERE id = ?2", params![next_run, id])
+            .map_err(|e| format!("Failed to update reminder: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Delete a reminder
+    pub fn delete_reminder(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_reminder")?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM reminders WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete reminder: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    fn reminder_from_row(row: &rusqlite::Row) -> SqliteResult<Reminder> {
+        Ok(Reminder {
+            id: row.get(0)?,
+            text: row.get(1)?,
+            schedule: row.get(2)?,
+            recurring: row.get(3)?,
+            next_run: row.get(4)?,
+            created_at: row.get(5)?,
+        })
+    }
+
+    // ========================================================================
+    // Transcription History
+    // ========================================================================
+
+    /// Store a dictation
+    pub fn add_transcription(&self, text: &str) -> Result<Transcription, String> {
+        let conn = acquire_lock(&self.conn, "add_transcription")?;
+        let now = chrono::Utc::now().timestamp_millis();
 
-        // Update session's updated_at
         conn.execute(
-            "UPDATE sessions SET updated_at = ?1 WHERE id = ?2",
-            params![now, session_id],
+            "INSERT INTO transcriptions (text, created_at) VALUES (?1, ?2)",
+            params![text, now],
         )
-        .map_err(|e| format!("Failed to update session timestamp: {}", e))?;
+        .map_err(|e| format!("Failed to add transcription: {}", e))?;
 
-        Ok(Message {
-            id,
-            session_id: session_id.to_string(),
-            role: role.to_string(),
-            content: content.to_string(),
-            timestamp: now,
+        Ok(Transcription {
+            id: conn.last_insert_rowid(),
+            text: text.to_string(),
+            created_at: now,
         })
     }
 
-    /// Get messages for a session with pagination
-    pub fn get_messages(
-        &self,
-        session_id: &str,
-        page: i32,
-        page_size: i32,
-    ) -> Result<PaginatedResult<Message>, String> {
-        let conn = acquire_lock(&self.conn, "get_messages")?;
+    /// Timestamp and text of the dictations since `since`
+    pub fn get_transcription_texts_since(&self, since: i64) -> Result<Vec<(i64, String)>, String> {
+        let conn = acquire_lock(&self.conn, "get_transcription_texts_since")?;
+
+        let mut stmt = conn
+            .prepare("SELECT created_at, text FROM transcriptions WHERE created_at >= ?1")
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let texts = stmt
+            .query_map(params![since], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to query transcriptions: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect transcriptions: {}", e))?;
+
+        Ok(texts)
+    }
+
+    /// List dictations with pagination (newest first)
+    pub fn list_transcriptions(&self, page: i32, page_size: i32) -> Result<PaginatedResult<Transcription>, String> {
+        let conn = acquire_lock(&self.conn, "list_transcriptions")?;
 
-        // Get total count for this session
         let total: i64 = conn
-            .query_row(
-                "SELECT COUNT(*) FROM messages WHERE session_id = ?1",
-                params![session_id],
-                |row| row.get(0),
-            )
-            .map_err(|e| format!("Failed to count messages: {}", e))?;
+            .query_row("SELECT COUNT(*) FROM transcriptions", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to count transcriptions: {}", e))?;
 
-        // Calculate offset
         let offset = (page - 1) * page_size;
 
-        // Query messages (ordered by timestamp ascending for chat display)
         let mut stmt = conn
             .prepare(
-                "SELECT id, session_id, role, content, timestamp FROM messages
-                 WHERE session_id = ?1
-                 ORDER BY timestamp ASC
-                 LIMIT ?2 OFFSET ?3",
+                "SELECT id, text, created_at FROM transcriptions
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT ?1 OFFSET ?2",
             )
             .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
-        let messages = stmt
-            .query_map(params![session_id, page_size, offset], |row| {
-                Ok(Message {
-                    id: row.get(0)?,
-                    session_id: row.get(1)?,
-                    role: row.get(2)?,
-                    content: row.get(3)?,
-                    timestamp: row.get(4)?,
-                })
-            })
-            .map_err(|e| format!("Failed to query messages: {}", e))?
+        let transcriptions = stmt
+            .query_map(params![page_size, offset], Self::transcription_from_row)
+            .map_err(|e| format!("Failed to query transcriptions: {}", e))?
             .collect::<SqliteResult<Vec<_>>>()
-            .map_err(|e| format!("Failed to collect messages: {}", e))?;
+            .map_err(|e| format!("Failed to collect transcriptions: {}", e))?;
 
         let has_more = (offset + page_size) < total as i32;
 
         Ok(PaginatedResult {
-            items: messages,
+            items: transcriptions,
             total,
             page,
             page_size,
@@ -428,16 +2650,222 @@ impl Database {
         })
     }
 
-    /// Delete a single message
-    pub fn delete_message(&self, message_id: &str) -> Result<bool, String> {
-        let conn = acquire_lock(&self.conn, "delete_message")?;
+    /// Get a dictation by ID
+    pub fn get_transcription(&self, id: i64) -> Result<Transcription, String> {
+        let conn = acquire_lock(&self.conn, "get_transcription")?;
+
+        conn.query_row(
+            "SELECT id, text, created_at FROM transcriptions WHERE id = ?1",
+            params![id],
+            Self::transcription_from_row,
+        )
+        .map_err(|e| format!("Transcription not found: {}", e))
+    }
+
+    /// Delete a dictation
+    pub fn delete_transcription(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_transcription")?;
 
         let rows_affected = conn
-            .execute("DELETE FROM messages WHERE id = ?1", params![message_id])
-            .map_err(|e| format!("Failed to delete message: {}", e))?;
+            .execute("DELETE FROM transcriptions WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete transcription: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Delete dictations older than `cutoff` (ms timestamp) and all but the newest `max_items`
+    ///
+    /// `None` disables a limit. Returns the number of deleted dictations.
+    pub fn prune_transcriptions(&self, cutoff: Option<i64>, max_items: Option<i64>) -> Result<usize, String> {
+        let conn = acquire_lock(&self.conn, "prune_transcriptions")?;
+
+        let mut deleted = 0;
+        if let Some(cutoff) = cutoff {
+            deleted += conn
+                .execute("DELETE FROM transcriptions WHERE created_at < ?1", params![cutoff])
+                .map_err(|e| format!("Failed to prune transcriptions: {}", e))?;
+        }
+        if let Some(max_items) = max_items {
+            deleted += conn
+                .execute(
+                    "DELETE FROM transcriptions WHERE id NOT IN
+                     (SELECT id FROM transcriptions ORDER BY created_at DESC, id DESC LIMIT ?1)",
+                    params![max_items],
+                )
+                .map_err(|e| format!("Failed to prune transcriptions: {}", e))?;
+        }
+
+        Ok(deleted)
+    }
+
+    fn transcription_from_row(row: &rusqlite::Row) -> SqliteResult<Transcription> {
+        Ok(Transcription {
+            id: row.get(0)?,
+            text: row.get(1)?,
+            created_at: row.get(2)?,
+        })
+    }
+
+    // ========================================================================
+    // Custom Vocabulary
+    // ========================================================================
+
+    /// List vocabulary terms (alphabetical)
+    pub fn list_vocabulary(&self) -> Result<Vec<VocabularyTerm>, String> {
+        let conn = acquire_lock(&self.conn, "list_vocabulary")?;
+
+        let mut stmt = conn
+            .prepare("SELECT id, term, created_at FROM vocabulary_terms ORDER BY term COLLATE NOCASE")
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let terms = stmt
+            .query_map([], Self::vocabulary_term_from_row)
+            .map_err(|e| format!("Failed to query vocabulary: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect vocabulary: {}", e))?;
+
+        Ok(terms)
+    }
+
+    /// Add a vocabulary term; fails if it exists (case-insensitive)
+    pub fn add_vocabulary_term(&self, term: &str) -> Result<VocabularyTerm, String> {
+        let conn = acquire_lock(&self.conn, "add_vocabulary_term")?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.execute(
+            "INSERT INTO vocabulary_terms (term, created_at) VALUES (?1, ?2)",
+            params![term, now],
+        )
+        .map_err(|e| format!("Failed to add vocabulary term: {}", e))?;
+
+        Ok(VocabularyTerm {
+            id: conn.last_insert_rowid(),
+            term: term.to_string(),
+            created_at: now,
+        })
+    }
+
+    /// Add the terms not in the vocabulary yet; returns how many were added
+    pub fn import_vocabulary_terms(&self, terms: &[String]) -> Result<usize, String> {
+        let mut conn = acquire_lock(&self.conn, "import_vocabulary_terms")?;
+        let now = chrono::Utc::now().timestamp_millis();
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let mut added = 0;
+        for term in terms {
+            added += tx
+                .execute(
+                    "INSERT OR IGNORE INTO vocabulary_terms (term, created_at) VALUES (?1, ?2)",
+                    params![term, now],
+                )
+                .map_err(|e| format!("Failed to import vocabulary term: {}", e))?;
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+        Ok(added)
+    }
+
+    /// Change a vocabulary term; returns false if it was not found
+    pub fn update_vocabulary_term(&self, id: i64, term: &str) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "update_vocabulary_term")?;
+
+        let rows_affected = conn
+            .execute("UPDATE vocabulary_terms SET term = ?1 WHERE id = ?2", params![term, id])
+            .map_err(|e| format!("Failed to update vocabulary term: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Delete a vocabulary term
+    pub fn delete_vocabulary_term(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_vocabulary_term")?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM vocabulary_terms WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete vocabulary term: {}", e))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    fn vocabulary_term_from_row(row: &rusqlite::Row) -> SqliteResult<VocabularyTerm> {
+        Ok(VocabularyTerm {
+            id: row.get(0)?,
+            term: row.get(1)?,
+            created_at: row.get(2)?,
+        })
+    }
+
+    // ========================================================================
+    // Symbol Shortcuts
+    // ========================================================================
+
+    /// List symbol shortcuts (alphabetical by phrase)
+    pub fn list_symbol_shortcuts(&self) -> Result<Vec<SymbolShortcut>, String> {
+        let conn = acquire_lock(&self.conn, "list_symbol_shortcuts")?;
+
+        let mut stmt = conn
+            .prepare("SELECT id, phrase, symbol, created_at FROM symbol_shortcuts ORDER BY phrase COLLATE NOCASE")
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let shortcuts = stmt
+            .query_map([], |row| {
+                Ok(SymbolShortcut {
+                    id: row.get(0)?,
+                    phrase: row.get(1)?,
+                    symbol: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query symbol shortcuts: {}", e))?
+            .collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Failed to collect symbol shortcuts: {}", e))?;
+
+        Ok(shortcuts)
+    }
+
+    /// Add a symbol shortcut, replacing the symbol of an existing phrase (case-insensitive)
+    pub fn set_symbol_shortcut(&self, phrase: &str, symbol: &str) -> Result<SymbolShortcut, String> {
+        let conn = acquire_lock(&self.conn, "set_symbol_shortcut")?;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        conn.query_row(
+            "INSERT INTO symbol_shortcuts (phrase, symbol, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(phrase) DO UPDATE SET symbol = excluded.symbol
+             RETURNING id, phrase, symbol, created_at",
+            params![phrase, symbol, now],
+            |row| {
+                Ok(SymbolShortcut {
+                    id: row.get(0)?,
+                    phrase: row.get(1)?,
+                    symbol: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            },
+        )
+        .map_err(|e| format!("Failed to save symbol shortcut: {}", e))
+    }
+
+    /// Delete a symbol shortcut
+    pub fn delete_symbol_shortcut(&self, id: i64) -> Result<bool, String> {
+        let conn = acquire_lock(&self.conn, "delete_symbol_shortcut")?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM symbol_shortcuts WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete symbol shortcut: {}", e))?;
 
         Ok(rows_affected > 0)
     }
+
+    /// Wait for in-flight writes and flush cached pages to disk (used on shutdown)
+    pub fn flush(&self) -> Result<(), String> {
+        let conn = acquire_lock(&self.conn, "flush")?;
+
+        conn.cache_flush()
+            .map_err(|e| format!("Failed to flush database: {}", e))
+    }
 }
 
 // ============================================================================
@@ -451,5 +2879,6 @@ pub fn get_database_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, Strin
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))?;
 
-    Ok(app_data_dir.join("speekium.db"))
+    // Each profile has its own database
+    Ok(crate::profiles::active_dir(&app_data_dir).join("speekium.db"))
 }
@@ -1,7 +1,15 @@
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_dialog::DialogExt;
 
-use crate::database::{Session, Message, PaginatedResult};
+use crate::daemon::call_daemon;
+use crate::database::{Session, Message, PaginatedResult, PinnedMessage};
+use crate::export::{self, ExportFormat, ExportedSession, ImportSummary};
 use crate::state::AppState;
+use crate::types::SessionTitleUpdatedPayload;
+use crate::events::{emit_event, AppEvent};
+
+/// Messages summarized into an automatic session title (the first exchange)
+const AUTO_TITLE_MESSAGE_COUNT: i32 = 2;
 
 // ============================================================================
 // Database Commands
@@ -9,10 +17,13 @@ use crate::state::AppState;
 
 #[tauri::command]
 pub async fn db_create_session(
+    app: AppHandle,
     state: State<'_, AppState>,
     title: String,
 ) -> Result<Session, String> {
-    state.db.create_session(title)
+    let session = state.db.create_session(title)?;
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(session)
 }
 
 #[tauri::command]
@@ -21,8 +32,9 @@ pub async fn db_list_sessions(
     page: i32,
     page_size: i32,
     filter_favorite: Option<bool>,
+    trashed: Option<bool>,
 ) -> Result<PaginatedResult<Session>, String> {
-    state.db.list_sessions_filtered(page, page_size, filter_favorite)
+    state.db.list_sessions_filtered(page, page_size, filter_favorite, trashed.unwrap_or(false))
 }
 
 #[tauri::command]
@@ -52,10 +64,46 @@ pub async fn db_update_session(
 
 #[tauri::command]
 pub async fn db_delete_session(
+    app: AppHandle,
     state: State<'_, AppState>,
     session_id: String,
 ) -> Result<bool, String> {
-    state.db.delete_session(&session_id)
+    let deleted = state.db.delete_session(&session_id)?;
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn db_trash_session(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<bool, String> {
+    let trashed = state.db.trash_session(&session_id)?;
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(trashed)
+}
+
+#[tauri::command]
+pub async fn db_restore_session(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<bool, String> {
+    let restored = state.db.restore_session(&session_id)?;
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(restored)
+}
+
+/// Permanently delete every session in the trash; returns how many were deleted
+#[tauri::command]
+pub async fn db_empty_trash(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let deleted = state.db.empty_trash(None)?;
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(deleted)
 }
 
 #[tauri::command]
@@ -65,7 +113,13 @@ pub async fn db_add_message(
     role: String,
     content: String,
 ) -> Result<Message, String> {
-    state.db.add_message(&session_id, &role, &content)
+    let mut message = state.db.add_message(&session_id, &role, &content)?;
+    crate::audio_archive::attach_pending_audio(&state.db, &mut message);
+    crate::asr_language::attach_pending_language(&state.db, &mut message);
+    crate::asr_alternatives::attach_pending_alternatives(&state.db, &message);
+    crate::word_timings::attach_pending_timings(&state.db, &message);
+    crate::diarization::attach_pending_segments(&state.db, &mut message);
+    Ok(message)
 }
 
 #[tauri::command]
@@ -86,38 +140,236 @@ pub async fn db_delete_message(
     state.db.delete_message(&message_id)
 }
 
+/// Pin or unpin a message; the pinned answers window refreshes on `pinned-messages-changed`
+#[tauri::command]
+pub async fn db_pin_message(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    message_id: String,
+    pinned: bool,
+) -> Result<bool, String> {
+    let found = state.db.set_message_pinned(&message_id, pinned)?;
+    emit_event(&app, AppEvent::PinnedMessagesChanged);
+    Ok(found)
+}
+
+#[tauri::command]
+pub async fn db_list_pinned(state: State<'_, AppState>) -> Result<Vec<PinnedMessage>, String> {
+    state.db.list_pinned_messages()
+}
+
+/// Latest streamed reply of a session that is unfinished or was cut off (see chat_draft)
+#[tauri::command]
+pub async fn db_get_draft(state: State<'_, AppState>, session_id: String) -> Result<Option<Message>, String> {
+    state.db.get_draft_message(&session_id)
+}
+
+// ============================================================================
+// Auto Title Commands
+// ============================================================================
+
+/// Generate a title for a session from its first exchange in the background
+///
+/// Emits `session-title-updated` once the title has been saved.
+pub fn spawn_auto_title(app: AppHandle, session_id: String) {
+    std::thread::spawn(move || {
+        if let Err(e) = auto_title_session(&app, &session_id) {
+            eprintln!("⚠️ Auto title failed for session {}: {}", session_id, e);
+        }
+    });
+}
+
+fn auto_title_session(app: &AppHandle, session_id: &str) -> Result<(), String> {
+    let state = app.state::<AppState>();
+
+    let messages = state.db.get_messages(session_id, 1, AUTO_TITLE_MESSAGE_COUNT)?.items;
+    if !messages.iter().any(|m| m.role == "assistant") {
+        return Err("No assistant reply yet".to_string());
+    }
+
+    let messages: Vec<serde_json::Value> = messages
+        .into_iter()
+        .map(|m| serde_json::json!({ "role": m.role, "content": m.content }))
+        .collect();
+
+    let result = call_daemon("generate_title", serde_json::json!({ "messages": messages }))?;
+    if !result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        let error = result.get("error").and_then(|v| v.as_str()).unwrap_or("Unknown error");
+        return Err(error.to_string());
+    }
+    let title = result.get("title")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| "Daemon returned no title".to_string())?;
+
+    let session = state.db.update_session(session_id, title.to_string())?;
+
+    emit_event(app, AppEvent::SessionTitleUpdated(SessionTitleUpdatedPayload {
+        session_id: session.id,
+        title: session.title,
+    }));
+    let _ = crate::ui::update_tray_menu(app);
+
+    Ok(())
+}
+
+/// Retitle a session from its first exchange (runs in the background)
 #[tauri::command]
-pub async fn export_conversation(
+pub async fn db_auto_title_session(
+    app: AppHandle,
+    session_id: String,
+) -> Result<(), String> {
+    spawn_auto_title(app, session_id);
+    Ok(())
+}
+
+// ============================================================================
+// Export Commands
+// ============================================================================
+
+/// Ask the user for a destination file and write the rendered export there
+///
+/// Returns the saved path, or None if the dialog was cancelled.
+fn save_export(
+    app: &AppHandle,
+    sessions: Vec<ExportedSession>,
+    format: ExportFormat,
+    default_name: &str,
+) -> Result<Option<String>, String> {
+    let file_path = app
+        .dialog()
+        .file()
+        .add_filter(format.filter_name(), &[format.extension()])
+        .set_file_name(format!("{}.{}", default_name, format.extension()))
+        .blocking_save_file();
+
+    let Some(file_path) = file_path else {
+        return Ok(None);
+    };
+    let path = file_path
+        .into_path()
+        .map_err(|e| format!("Invalid export path: {}", e))?;
+
+    let content = export::render(sessions, format)?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+fn parse_export_format(format: &str) -> Result<ExportFormat, String> {
+    ExportFormat::from_str(format)
+        .ok_or_else(|| format!("Unsupported export format: {}", format))
+}
+
+/// Replace characters that are not allowed in file names
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect();
+    let trimmed = cleaned.trim();
+    if trimmed.is_empty() {
+        "session".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[tauri::command]
+pub async fn db_export_session(
+    app: AppHandle,
     state: State<'_, AppState>,
     session_id: String,
-) -> Result<String, String> {
-    use chrono::{DateTime, Local};
-    
-    // Get session info
-    let session = state.db.get_session(&session_id)
-        .map_err(|e| format!("Failed to get session: {}", e))?;
-    
-    // Get all messages for the session
-    let messages = state.db.get_messages(&session_id, 0, 1000)
-        .map_err(|e| format!("Failed to get messages: {}", e))?;
-    
-    // Build Markdown content
-    let mut markdown = format!("# {}\n\n", session.title);
-    markdown.push_str(&format!("导出时间: {}\n\n", Local::now().format("%Y-%m-%d %H:%M:%S")));
-    markdown.push_str("---\n\n");
-    
-    for msg in messages.items {
-        let role_emoji = match msg.role.as_str() {
-            "user" => "👤 用户",
-            "assistant" => "🤖 助手",
-            _ => "📝 系统",
-        };
-        let timestamp = DateTime::from_timestamp_millis(msg.timestamp)
-            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
-            .unwrap_or_else(|| "Unknown".to_string());
-        
-        markdown.push_str(&format!("### {} - {}\n\n{}\n\n", role_emoji, timestamp, msg.content));
+    format: String,
+) -> Result<Option<String>, String> {
+    let format = parse_export_format(&format)?;
+
+    let session = state.db.get_session(&session_id)?;
+    let messages = state.db.get_all_messages(&session_id)?;
+    let default_name = sanitize_file_name(&session.title);
+
+    save_export(&app, vec![ExportedSession { session, messages }], format, &default_name)
+}
+
+#[tauri::command]
+pub async fn db_export_all(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    format: String,
+) -> Result<Option<String>, String> {
+    let format = parse_export_format(&format)?;
+
+    let mut sessions = Vec::new();
+    for session in state.db.list_all_sessions()? {
+        let messages = state.db.get_all_messages(&session.id)?;
+        sessions.push(ExportedSession { session, messages });
+    }
+
+    let default_name = format!("speekium-export-{}", chrono::Local::now().format("%Y%m%d"));
+    save_export(&app, sessions, format, &default_name)
+}
+
+// ============================================================================
+// Import Commands
+// ============================================================================
+
+/// Import sessions from JSON export bundles
+///
+/// When `paths` is not given the user picks files in an open dialog.
+/// Returns None if the dialog was cancelled.
+#[tauri::command]
+pub async fn db_import_sessions(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    paths: Option<Vec<String>>,
+) -> Result<Option<ImportSummary>, String> {
+    let paths: Vec<std::path::PathBuf> = match paths {
+        Some(paths) => paths.into_iter().map(std::path::PathBuf::from).collect(),
+        None => {
+            let picked = app
+                .dialog()
+                .file()
+                .add_filter(ExportFormat::Json.filter_name(), &[ExportFormat::Json.extension()])
+                .blocking_pick_files();
+
+            let Some(picked) = picked else {
+                return Ok(None);
+            };
+            picked
+                .into_iter()
+                .map(|p| p.into_path().map_err(|e| format!("Invalid import path: {}", e)))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let mut summary = ImportSummary::default();
+    for path in paths {
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let bundle = export::parse_bundle(&content)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        for exported in bundle.sessions {
+            let (created, inserted) = state.db.import_session(&exported.session, &exported.messages)?;
+            if created {
+                summary.sessions_imported += 1;
+            } else {
+                summary.sessions_skipped += 1;
+            }
+            summary.messages_imported += inserted;
+            summary.messages_skipped += exported.messages.len() - inserted;
+        }
     }
-    
-    Ok(markdown)
+
+    println!(
+        "📥 Imported {} sessions ({} skipped), {} messages ({} skipped)",
+        summary.sessions_imported,
+        summary.sessions_skipped,
+        summary.messages_imported,
+        summary.messages_skipped
+    );
+
+    Ok(Some(summary))
 }
@@ -0,0 +1,140 @@
+// ============================================================================
+// Database Encryption Module - SQLCipher Encryption at Rest
+// ============================================================================
+//
+// The history database is built with SQLCipher. With `database_encryption`
+// on, the file is encrypted with a random key kept in the OS keychain (the
+// "database-key" entry next to the API keys, see secrets.rs); nothing in the
+// file itself reveals the key.
+//
+// Whether the file on disk is encrypted is read from its header, so it is
+// opened correctly whatever the setting says. At startup the file is then
+// converted to match the setting, which also encrypts databases created
+// before encryption was turned on. `set_database_encryption` converts it
+// while the app runs.
+//
+// Each profile has its own database and setting, and so its own key entry.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use tauri::Manager;
+
+use crate::database::Database;
+use crate::state::AppState;
+
+const CONFIG_KEY: &str = "database_encryption";
+
+/// Keychain entry holding the database key (of the default profile)
+const KEY_ENTRY: &str = "database-key";
+
+/// First bytes of every unencrypted SQLite file
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Whether the file at `path` is an encrypted database (a missing or empty file is not)
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    match std::fs::File::open(path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(()) => &header != SQLITE_HEADER,
+        Err(_) => false,
+    }
+}
+
+fn enabled_in_config() -> bool {
+    crate::config::read_config_value(CONFIG_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Random 256-bit raw key (`x'<hex>'`, used by SQLCipher as is, without key derivation)
+///
+/// Keys made before were 64-character passphrases; SQLCipher still derives
+/// a key from those, so they keep working.
+fn new_key() -> Result<String, String> {
+    let mut key = [0u8; 32];
+    openssl::rand::rand_bytes(&mut key).map_err(|e| format!("Failed to generate a database key: {}", e))?;
+    Ok(format!("x'{}'", hex::encode(key)))
+}
+
+/// Keychain entry of the active profile's database key
+fn key_entry() -> String {
+    crate::profiles::scoped_name(KEY_ENTRY)
+}
+
+fn stored_key() -> Result<String, String> {
+    crate::secrets::get_key(&key_entry())?
+        .ok_or_else(|| "The database is encrypted but its key is missing from the keychain".to_string())
+}
+
+fn key_for(db_path: &Path) -> Result<Option<String>, String> {
+    if is_encrypted(db_path) {
+        stored_key().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Open the database, unlocking it if encrypted, and apply the encryption setting
+pub fn open(db_path: PathBuf) -> Result<Database, String> {
+    let key = key_for(&db_path)?;
+    let db = Database::new(db_path, key.as_deref())?;
+
+    // A failed conversion leaves the database as it was
+    if let Err(e) = apply(&db, enabled_in_config()) {
+        eprintln!("[DB ENCRYPTION] {}", e);
+    }
+    Ok(db)
+}
+
+/// Switch `db` to the database at `db_path` (call with the new profile active)
+pub fn reopen(db: &Database, db_path: PathBuf) -> Result<(), String> {
+    let key = key_for(&db_path)?;
+    db.reopen(db_path, key.as_deref())?;
+
+    if let Err(e) = apply(db, enabled_in_config()) {
+        eprintln!("[DB ENCRYPTION] {}", e);
+    }
+    Ok(())
+}
+
+/// Encrypt or decrypt the database file to match `enabled`
+fn apply(db: &Database, enabled: bool) -> Result<(), String> {
+    if is_encrypted(&db.path()) == enabled {
+        return Ok(());
+    }
+
+    if enabled {
+        // Stored first, so an encrypted file never exists without its key
+        let key = new_key()?;
+        crate::secrets::store_key(&key_entry(), &key)?;
+        db.convert_encryption(None, Some(&key))?;
+        println!("🔒 Database encrypted");
+    } else {
+        let key = stored_key()?;
+        db.convert_encryption(Some(&key), None)?;
+        if let Err(e) = crate::secrets::delete_key(&key_entry()) {
+            eprintln!("[DB ENCRYPTION] {}", e);
+        }
+        println!("🔓 Database decrypted");
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Encrypt or decrypt the history database; returns whether it is now encrypted
+#[tauri::command]
+pub async fn set_database_encryption(app: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
+    let encrypted = tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        apply(&state.db, enabled)?;
+        Ok::<_, String>(is_encrypted(&state.db.path()))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    crate::config::write_config_value(CONFIG_KEY, serde_json::json!(encrypted))?;
+    Ok(encrypted)
+}
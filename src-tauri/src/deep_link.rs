@@ -0,0 +1,97 @@
+// ============================================================================
+// Deep Link Module - speekium:// URL Handling
+// ============================================================================
+//
+// Other apps can trigger actions through the `speekium://` URL scheme:
+//
+//   speekium://record               start / stop a hands-free recording
+//   speekium://chat?text=...        send a message (shown and spoken like PTT)
+//   speekium://open-session/<id>    show a history session in the main window
+//
+// URLs arrive through the deep-link plugin, both when they launch the app and
+// (forwarded by the single-instance plugin) while it is already running.
+
+use std::sync::atomic::Ordering;
+
+use tauri::Url;
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::daemon::{daemon_client, DAEMON_READY};
+use crate::shortcuts;
+use crate::ui;
+use crate::events::{emit_event, AppEvent};
+
+const SCHEME: &str = "speekium";
+
+/// Register the URL handler and process the URL the app was launched with
+pub fn setup(app: &tauri::AppHandle) {
+    // Installed bundles register the scheme themselves; dev builds and AppImages need this
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    if let Err(e) = app.deep_link().register_all() {
+        eprintln!("[DEEP LINK] Failed to register URL scheme: {}", e);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_url(&handle, &url);
+        }
+    });
+
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        for url in urls {
+            handle_url(app, &url);
+        }
+    }
+}
+
+/// Map a speekium:// URL to the matching action
+pub fn handle_url(app: &tauri::AppHandle, url: &Url) {
+    if url.scheme() != SCHEME {
+        return;
+    }
+
+    match url.host_str().unwrap_or("") {
+        "record" => shortcuts::toggle_ptt_recording(app),
+        "chat" => {
+            let text = url
+                .query_pairs()
+                .find(|(key, _)| key == "text")
+                .map(|(_, value)| value.trim().to_string())
+                .unwrap_or_default();
+            if text.is_empty() {
+                return;
+            }
+            ui::show_main_window(app);
+            send_chat(app, &text);
+        }
+        "open-session" => {
+            let session_id = url.path().trim_matches('/');
+            if session_id.is_empty() {
+                return;
+            }
+            ui::show_main_window(app);
+            emit_event(app, AppEvent::OpenSession(session_id.to_string()));
+        }
+        action => eprintln!("[DEEP LINK] Unknown action: {}", action),
+    }
+}
+
+/// Submit text like a finished PTT transcript (the reply streams to the UI)
+fn send_chat(app: &tauri::AppHandle, text: &str) {
+    if !DAEMON_READY.load(Ordering::SeqCst) {
+        eprintln!("[DEEP LINK] Daemon is not ready, ignoring chat");
+        return;
+    }
+    let result = daemon_client().and_then(|client| {
+        let args = crate::personas::with_persona(app, serde_json::json!({
+            "text": text,
+            "auto_chat": true,
+            "use_tts": true,
+        }));
+        client.send_no_wait("ptt_chat", args)
+    });
+    if let Err(e) = result {
+        eprintln!("[DEEP LINK] Failed to send chat: {}", e);
+    }
+}
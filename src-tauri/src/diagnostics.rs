@@ -0,0 +1,254 @@
+// ============================================================================
+// Diagnostics Module - Crash Reports and Daemon Init Timeline
+// ============================================================================
+//
+// Panics and unexpected daemon exits are written to `last_crash.json` in the
+// app data directory together with the tail of the daemon's stderr and the
+// timeline of the last daemon initialization. The frontend reads the report
+// on the next start and offers to send it.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::types::{CrashReport, InitTimelineEntry};
+
+/// Crash report file name (in the app data directory)
+const CRASH_FILE_NAME: &str = "last_crash.json";
+
+/// Error log shared with the Python error tracker (picked up by error upload)
+const ERRORS_FILE_NAME: &str = "errors.jsonl";
+
+/// Number of daemon stderr lines kept for crash reports
+const STDERR_TAIL_LINES: usize = 200;
+
+/// Directory crash reports are written to (set when the panic hook is installed)
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Most recent daemon stderr lines (PTT events excluded)
+static STDERR_TAIL: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Daemon launch time and events of the current initialization
+static INIT_TIMELINE: Mutex<Option<(Instant, Vec<InitTimelineEntry>)>> = Mutex::new(None);
+
+/// When the app was started (set during setup)
+static APP_STARTED_AT: OnceLock<Instant> = OnceLock::new();
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// Remember when the app was started (for [`app_uptime`])
+pub fn record_app_start() {
+    let _ = APP_STARTED_AT.set(Instant::now());
+}
+
+/// Remember a daemon stderr line for the next crash report
+pub fn record_stderr_line(line: &str) {
+    if let Ok(mut tail) = STDERR_TAIL.lock() {
+        if tail.len() >= STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line.to_string());
+    }
+}
+
+/// Start a new init timeline (called when the daemon is launched)
+pub fn start_init_timeline() {
+    if let Ok(mut timeline) = INIT_TIMELINE.lock() {
+        *timeline = Some((Instant::now(), Vec::new()));
+    }
+}
+
+/// Append a daemon init event to the current timeline
+pub fn record_init_event(event: &str) {
+    if let Ok(mut timeline) = INIT_TIMELINE.lock() {
+        if let Some((started_at, entries)) = timeline.as_mut() {
+            let elapsed_ms = started_at.elapsed().as_millis() as u64;
+            let previous_ms = entries.last().map_or(0, |e| e.elapsed_ms);
+            entries.push(InitTimelineEntry {
+                event: event.to_string(),
+                elapsed_ms,
+                duration_ms: elapsed_ms.saturating_sub(previous_ms),
+            });
+        }
+    }
+}
+
+/// Time since the app was started
+pub fn app_uptime() -> Duration {
+    APP_STARTED_AT.get().map_or(Duration::ZERO, Instant::elapsed)
+}
+
+/// Time since the current daemon was launched (None before the first launch)
+pub fn daemon_uptime() -> Option<Duration> {
+    let timeline = INIT_TIMELINE.lock().ok()?;
+    timeline.as_ref().map(|(started_at, _)| started_at.elapsed())
+}
+
+/// Latest entries of the shared error log, oldest first (without stack traces)
+pub fn recent_errors(count: usize) -> Vec<serde_json::Value> {
+    let Some(content) = CRASH_DIR
+        .get()
+        .and_then(|dir| std::fs::read_to_string(dir.join(ERRORS_FILE_NAME)).ok())
+    else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .map(|record| {
+            serde_json::json!({
+                "timestamp": record.get("timestamp"),
+                "level": record.get("level"),
+                "error_type": record.get("error_type"),
+                "message": record.get("message"),
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Crash Reports
+// ============================================================================
+
+fn build_report(
+    kind: &str,
+    message: String,
+    location: Option<String>,
+    backtrace: Option<String>,
+) -> CrashReport {
+    CrashReport {
+        kind: kind.to_string(),
+        timestamp: chrono::Local::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        message,
+        location,
+        thread: std::thread::current().name().map(str::to_string),
+        backtrace,
+        // try_lock: a panic may happen while one of these locks is held
+        daemon_stderr_tail: STDERR_TAIL
+            .try_lock()
+            .map(|tail| tail.iter().cloned().collect())
+            .unwrap_or_default(),
+        init_timeline: INIT_TIMELINE
+            .try_lock()
+            .ok()
+            .and_then(|timeline| timeline.as_ref().map(|(_, entries)| entries.clone()))
+            .unwrap_or_default(),
+    }
+}
+
+fn write_report(report: &CrashReport) {
+    let Some(dir) = CRASH_DIR.get() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(dir);
+    if let Ok(json) = serde_json::to_string_pretty(report) {
+        if let Err(e) = std::fs::write(dir.join(CRASH_FILE_NAME), json) {
+            eprintln!("Failed to write crash report: {}", e);
+        }
+    }
+}
+
+/// Install a panic hook that writes a crash report before the default hook runs
+pub fn install_panic_hook(crash_dir: PathBuf) {
+    let _ = CRASH_DIR.set(crash_dir);
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Unknown panic".to_string()
+        };
+        let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+
+        write_report(&build_report("panic", message, location, Some(backtrace)));
+        default_hook(info);
+    }));
+}
+
+/// Write a crash report for a daemon that exited unexpectedly
+pub fn report_daemon_exit(exit_reason: &str) {
+    write_report(&build_report(
+        "daemon_exit",
+        format!("Daemon exited unexpectedly: {}", exit_reason),
+        None,
+        None,
+    ));
+}
+
+fn crash_file() -> Result<PathBuf, String> {
+    CRASH_DIR
+        .get()
+        .map(|dir| dir.join(CRASH_FILE_NAME))
+        .ok_or_else(|| "Crash reporting is not initialized".to_string())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Crash report left by the previous run, if any
+#[tauri::command]
+pub fn get_last_crash_report() -> Result<Option<CrashReport>, String> {
+    let path = crash_file()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read crash report: {}", e))?;
+    let report = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse crash report: {}", e))?;
+    Ok(Some(report))
+}
+
+/// Remove the last crash report, queueing it in the error log first when `send` is true
+#[tauri::command]
+pub fn clear_crash_report(send: bool) -> Result<(), String> {
+    let path = crash_file()?;
+    if !path.exists() {
+        return Ok(());
+    }
+
+    if send {
+        let report = get_last_crash_report()?
+            .ok_or_else(|| "Crash report disappeared".to_string())?;
+        let record = serde_json::json!({
+            "timestamp": report.timestamp,
+            "level": "error",
+            "message": report.message,
+            "error_type": if report.kind == "panic" { "RustPanic" } else { "DaemonExit" },
+            "context": {
+                "app_version": report.app_version,
+                "os": report.os,
+                "location": report.location,
+                "thread": report.thread,
+                "daemon_stderr_tail": report.daemon_stderr_tail,
+                "init_timeline": report.init_timeline,
+            },
+            "stack_trace": report.backtrace,
+        });
+
+        use std::io::Write;
+        let errors_path = path.with_file_name(ERRORS_FILE_NAME);
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&errors_path)
+            .map_err(|e| format!("Failed to open error log: {}", e))?;
+        writeln!(file, "{}", record)
+            .map_err(|e| format!("Failed to write error log: {}", e))?;
+    }
+
+    std::fs::remove_file(&path)
+        .map_err(|e| format!("Failed to remove crash report: {}", e))
+}
@@ -0,0 +1,76 @@
+// ============================================================================
+// Diarization Module - Speaker Segments of Spoken Messages
+// ============================================================================
+//
+// A recording with several speakers (a meeting) can come back from ASR split
+// into segments, each labelled with a 1-based speaker number. The daemon only
+// sends segments when the model diarized the recording and found more than
+// one speaker. Like the detected language (see asr_language.rs), they are
+// kept until the frontend saves the user message (db_add_message), then
+// stored in `message_segments` and returned with the message by
+// `db_get_messages`, so the UI can render "Speaker 1 / Speaker 2" transcripts.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::database::{Database, Message};
+use crate::types::SpeakerSegment;
+
+/// Segments are dropped if no message is saved within this time
+const PENDING_SEGMENTS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Speaker segments of the last transcript, waiting for its message to be saved
+struct PendingSegments {
+    transcript: String,
+    segments: Vec<SpeakerSegment>,
+    recognized_at: Instant,
+}
+
+static PENDING_SEGMENTS: Mutex<Option<PendingSegments>> = Mutex::new(None);
+
+/// Post-process the text of each segment like the transcript, dropping empty ones
+pub fn process_segments<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    segments: Vec<SpeakerSegment>,
+) -> Vec<SpeakerSegment> {
+    segments
+        .into_iter()
+        .map(|segment| SpeakerSegment {
+            text: crate::textproc::process_transcript(app, &segment.text),
+            ..segment
+        })
+        .filter(|segment| !segment.text.trim().is_empty())
+        .collect()
+}
+
+/// Remember the speaker segments of a transcript until its message is saved
+pub fn set_pending_segments(transcript: &str, segments: Vec<SpeakerSegment>) {
+    if let Ok(mut pending) = PENDING_SEGMENTS.lock() {
+        *pending = (!segments.is_empty()).then(|| PendingSegments {
+            transcript: transcript.to_string(),
+            segments,
+            recognized_at: Instant::now(),
+        });
+    }
+}
+
+/// Store the pending segments on a newly saved user message with the same transcript
+pub fn attach_pending_segments(db: &Database, message: &mut Message) {
+    if message.role != "user" {
+        return;
+    }
+    let pending = match PENDING_SEGMENTS.lock() {
+        Ok(mut pending) => pending.take_if(|p| {
+            p.transcript == message.content && p.recognized_at.elapsed() < PENDING_SEGMENTS_TIMEOUT
+        }),
+        Err(_) => None,
+    };
+    let Some(pending) = pending else {
+        return;
+    };
+
+    match db.set_message_segments(&message.id, &pending.segments) {
+        Ok(()) => message.segments = pending.segments,
+        Err(e) => eprintln!("[DIARIZATION] {}", e),
+    }
+}
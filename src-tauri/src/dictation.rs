@@ -0,0 +1,327 @@
+// ============================================================================
+// Dictation Module - Long-Form Dictation with Automatic Chunking
+// ============================================================================
+//
+// Continuous mode is tuned for short commands: one utterance, one reply.
+// Dictation records until it is stopped and builds a document instead:
+//
+//   - the microphone audio is sliced at pauses (`dictation_pause_ms` without
+//     voice), or every MAX_CHUNK_SECS during long monologues
+//   - each chunk is transcribed in order while the next one is recorded
+//   - transcripts are appended to the document buffer and emitted as
+//     `dictation-append` {text, document}
+//
+// Dictation stops with the toggle_dictation shortcut, `stop_dictation`, or by
+// saying one of `dictation_stop_phrases` at the end of a chunk. The document
+// stays available (`get_dictation_document`) until the next dictation starts.
+
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+
+use crate::daemon::call_daemon_async;
+use crate::loopback::{self, CaptureBuffer};
+use crate::types::{DictationAppendPayload, DictationState};
+use crate::events::{emit_event, AppEvent};
+
+const DEFAULT_PAUSE_MS: u64 = 800;
+/// Longest chunk, sliced even without a pause
+const MAX_CHUNK_SECS: usize = 30;
+const SAMPLE_RATE: usize = 16000;
+
+/// RMS level above which audio counts as voice
+const VOICE_RMS_THRESHOLD: f32 = 0.01;
+/// Audio kept before the first voice of a chunk, so its first word is not cut
+const PRE_ROLL_SAMPLES: usize = SAMPLE_RATE * 3 / 10;
+/// How often the capture thread checks for pauses
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time to wait for the microphone to open
+const START_TIMEOUT: Duration = Duration::from_secs(3);
+
+const DEFAULT_STOP_PHRASES: [&str; 3] = ["stop dictation", "停止听写", "ディクテーション終了"];
+
+struct ActiveDictation {
+    stop_tx: Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+static DICTATION: Mutex<Option<ActiveDictation>> = Mutex::new(None);
+
+/// Transcripts of the current (or last) dictation
+static DOCUMENT: Mutex<String> = Mutex::new(String::new());
+
+fn pause_duration() -> Duration {
+    Duration::from_millis(
+        crate::config::read_config_value("dictation_pause_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_PAUSE_MS),
+    )
+}
+
+fn stop_phrases() -> Vec<String> {
+    crate::config::read_config_value("dictation_stop_phrases")
+        .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+        .unwrap_or_else(|| DEFAULT_STOP_PHRASES.iter().map(|p| p.to_string()).collect())
+}
+
+/// Lowercase letters and digits only, for comparing spoken phrases
+fn normalize(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Remove a trailing stop phrase; returns the remaining text if one was found
+fn strip_stop_phrase(text: &str, phrases: &[String]) -> Option<String> {
+    let normalized = normalize(text);
+    let phrase = phrases
+        .iter()
+        .map(|p| normalize(p))
+        .find(|p| !p.is_empty() && normalized.ends_with(p.as_str()))?;
+
+    // Walk back over the phrase's characters (ignoring punctuation) to find where it starts
+    let mut remaining = phrase.chars().count();
+    let mut cut = text.len();
+    for (index, c) in text.char_indices().rev() {
+        if remaining == 0 {
+            break;
+        }
+        if c.is_alphanumeric() {
+            remaining -= c.to_lowercase().count();
+        }
+        cut = index;
+    }
+    Some(text[..cut].trim_end().to_string())
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xFF00..=0xFFEF)
+}
+
+/// Append a transcript to the document; CJK text is joined without a space
+fn append(document: &mut String, text: &str) {
+    let joins_cjk = document.chars().last().is_some_and(is_cjk) || text.chars().next().is_some_and(is_cjk);
+    if !document.is_empty() && !joins_cjk {
+        document.push(' ');
+    }
+    document.push_str(text);
+}
+
+fn is_voiced(samples: &[f32]) -> bool {
+    !samples.is_empty()
+        && (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt() > VOICE_RMS_THRESHOLD
+}
+
+pub fn is_active() -> bool {
+    DICTATION.lock().map(|d| d.is_some()).unwrap_or(false)
+}
+
+/// Open the microphone and start a new document
+pub fn start<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
+    let mut dictation = DICTATION.lock().map_err(|e| e.to_string())?;
+    if dictation.is_some() {
+        return Ok(());
+    }
+
+    let (stop_tx, stop_rx) = channel();
+    let (started_tx, started_rx) = channel();
+    let (chunk_tx, chunk_rx) = tokio::sync::mpsc::unbounded_channel();
+    let pause = pause_duration();
+    let device_id = crate::config::read_config_value(crate::audio::AUDIO_INPUT_DEVICE_KEY)
+        .and_then(|v| v.as_str().map(String::from));
+
+    let thread = thread::spawn(move || {
+        run_capture(stop_rx, chunk_tx, pause, device_id.as_deref(), &started_tx);
+    });
+    match started_rx.recv_timeout(START_TIMEOUT) {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            let _ = thread.join();
+            return Err(e);
+        }
+        Err(_) => {
+            let _ = stop_tx.send(());
+            return Err("Timed out opening the microphone".to_string());
+        }
+    }
+
+    if let Ok(mut document) = DOCUMENT.lock() {
+        document.clear();
+    }
+    *dictation = Some(ActiveDictation { stop_tx, thread });
+    drop(dictation);
+    println!("[DICTATION] Started");
+
+    tauri::async_runtime::spawn(transcribe_chunks(app.clone(), chunk_rx));
+    emit_event(app, AppEvent::DictationState(DictationState { active: true }));
+    Ok(())
+}
+
+/// Stop recording; the last chunk is still transcribed and appended
+pub fn stop() {
+    let active = DICTATION.lock().ok().and_then(|mut dictation| dictation.take());
+    if let Some(active) = active {
+        let _ = active.stop_tx.send(());
+        let _ = active.thread.join();
+        println!("[DICTATION] Recording stopped");
+    }
+}
+
+/// Capture thread: slice the microphone audio at pauses until stopped
+fn run_capture(
+    stop_rx: Receiver<()>,
+    chunk_tx: tokio::sync::mpsc::UnboundedSender<Vec<f32>>,
+    pause: Duration,
+    device_id: Option<&str>,
+    started: &Sender<Result<(), String>>,
+) {
+    let buffer: CaptureBuffer = Arc::new(Mutex::new(Vec::new()));
+    let source = match loopback::open_microphone(device_id, buffer.clone()) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("[DICTATION] {}", e);
+            let _ = started.send(Err(e));
+            return;
+        }
+    };
+    let _ = started.send(Ok(()));
+
+    let mut chunk: Vec<f32> = Vec::new();
+    let mut last_voice: Option<Instant> = None;
+    loop {
+        let stopped = match stop_rx.recv_timeout(POLL_INTERVAL) {
+            Err(RecvTimeoutError::Timeout) => false,
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => true,
+        };
+
+        let samples = buffer.lock().map(|mut buf| std::mem::take(&mut *buf)).unwrap_or_default();
+        if is_voiced(&samples) {
+            last_voice = Some(Instant::now());
+        }
+        chunk.extend_from_slice(&samples);
+
+        match last_voice {
+            // Nothing said yet: only keep the pre-roll
+            None => {
+                let excess = chunk.len().saturating_sub(PRE_ROLL_SAMPLES);
+                chunk.drain(..excess);
+            }
+            Some(voiced_at) => {
+                let paused = voiced_at.elapsed() >= pause;
+                if stopped || paused || chunk.len() >= MAX_CHUNK_SECS * SAMPLE_RATE {
+                    let _ = chunk_tx.send(std::mem::take(&mut chunk));
+                    last_voice = None;
+                }
+            }
+        }
+        if stopped {
+            break;
+        }
+    }
+    drop(source);
+}
+
+/// Worker: transcribe chunks in order and append them to the document
+async fn transcribe_chunks<R: tauri::Runtime>(app: tauri::AppHandle<R>, mut chunks: tokio::sync::mpsc::UnboundedReceiver<Vec<f32>>) {
+    let phrases = stop_phrases();
+    while let Some(samples) = chunks.recv().await {
+        let text = match transcribe(&app, &samples).await {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("[DICTATION] Chunk failed: {}", e);
+                continue;
+            }
+        };
+        let (text, stop_requested) = match strip_stop_phrase(&text, &phrases) {
+            Some(rest) => (rest, true),
+            None => (text, false),
+        };
+
+        if !text.trim().is_empty() {
+            let document = match DOCUMENT.lock() {
+                Ok(mut document) => {
+                    append(&mut document, text.trim());
+                    document.clone()
+                }
+                Err(_) => continue,
+            };
+            emit_event(&app, AppEvent::DictationAppend(DictationAppendPayload { text, document }));
+        }
+        if stop_requested {
+            println!("[DICTATION] Stop phrase heard");
+            let _ = tauri::async_runtime::spawn_blocking(stop).await;
+        }
+    }
+    println!("[DICTATION] Finished");
+    emit_event(&app, AppEvent::DictationState(DictationState { active: false }));
+}
+
+async fn transcribe<R: tauri::Runtime>(app: &tauri::AppHandle<R>, samples: &[f32]) -> Result<String, String> {
+    // The daemon deletes the file after reading it
+    let audio_path = crate::audio::write_temp_wav(samples, "dictation")?;
+    let result = call_daemon_async(
+        "transcribe",
+        serde_json::json!({
+            "audio_path": audio_path,
+            "language": crate::asr_language::request_language(),
+            "hotwords": crate::vocabulary::request_hotwords(),
+        }),
+    )
+    .await?;
+    if !result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return Err(result.get("error").and_then(|v| v.as_str()).unwrap_or("ASR failed").to_string());
+    }
+    let text = result.get("text").and_then(|v| v.as_str()).unwrap_or_default();
+    Ok(crate::textproc::process_transcript(app, text))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn start_dictation(app: tauri::AppHandle) -> Result<(), String> {
+    start(&app)
+}
+
+/// Stop dictating; the rest arrives as `dictation-append` events
+#[tauri::command]
+pub async fn stop_dictation() -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(stop).await.map_err(|e| e.to_string())
+}
+
+/// Text dictated so far (kept until the next dictation starts)
+#[tauri::command]
+pub fn get_dictation_document() -> Result<String, String> {
+    DOCUMENT.lock().map(|document| document.clone()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phrases() -> Vec<String> {
+        DEFAULT_STOP_PHRASES.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_strip_stop_phrase() {
+        assert_eq!(strip_stop_phrase("Thanks everyone. Stop dictation.", &phrases()), Some("Thanks everyone.".to_string()));
+        assert_eq!(strip_stop_phrase("stop dictation", &phrases()), Some(String::new()));
+        assert_eq!(strip_stop_phrase("今天就到这里，停止听写。", &phrases()), Some("今天就到这里，".to_string()));
+        assert_eq!(strip_stop_phrase("Don't stop dictation yet please", &phrases()), None);
+    }
+
+    #[test]
+    fn test_append_joins_cjk_without_space() {
+        let mut document = String::new();
+        append(&mut document, "Hello there.");
+        append(&mut document, "Next sentence.");
+        assert_eq!(document, "Hello there. Next sentence.");
+
+        let mut document = "你好。".to_string();
+        append(&mut document, "再见");
+        assert_eq!(document, "你好。再见");
+    }
+}
@@ -0,0 +1,252 @@
+// ============================================================================
+// DSP Module - Noise Suppression for Recorded Audio
+// ============================================================================
+//
+// Optional clean-up of PTT recordings before they are written to WAV
+// (`noise_suppression`), for users with fans, air conditioning or keyboards
+// in the background:
+//
+//   1. High-pass filter (2nd-order Butterworth) removes rumble and hum
+//      below the speech range.
+//   2. Spectral gating: the noise spectrum is estimated from the quietest
+//      frames of the recording, and frequency bins that do not rise clearly
+//      above it are attenuated.
+//
+// Everything works on the whole buffer after recording, so there is no added
+// latency while speaking.
+
+use std::f32::consts::PI;
+
+/// Config key enabling the DSP stage
+pub const NOISE_SUPPRESSION_KEY: &str = "noise_suppression";
+
+/// High-pass cutoff; speech fundamentals start around 85 Hz
+const HIGH_PASS_HZ: f32 = 80.0;
+
+/// Quality factor of a Butterworth filter
+const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// STFT frame length (32 ms at 16 kHz) and hop (50% overlap)
+const FRAME_LEN: usize = 512;
+const HOP: usize = FRAME_LEN / 2;
+
+/// Share of the quietest frames used to estimate the noise spectrum
+const NOISE_FRAME_RATIO: f32 = 0.1;
+
+/// A bin is kept if its magnitude exceeds the noise estimate by this factor
+const GATE_THRESHOLD: f32 = 2.0;
+
+/// Gain applied to gated bins (about -20 dB; 0 would sound "underwater")
+const GATE_ATTENUATION: f32 = 0.1;
+
+/// Weight of the previous frame's gain, smooths gating over time
+const GAIN_SMOOTHING: f32 = 0.5;
+
+/// Whether the `noise_suppression` setting is on
+pub fn is_enabled() -> bool {
+    crate::config::read_config_value(NOISE_SUPPRESSION_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Apply high-pass filtering and spectral gating to mono samples
+pub fn suppress_noise(samples: &mut [f32], sample_rate: u32) {
+    high_pass(samples, sample_rate, HIGH_PASS_HZ);
+    spectral_gate(samples);
+}
+
+// ============================================================================
+// High-Pass Filter
+// ============================================================================
+
+/// 2nd-order Butterworth high-pass (RBJ audio EQ cookbook biquad)
+fn high_pass(samples: &mut [f32], sample_rate: u32, cutoff_hz: f32) {
+    let w0 = 2.0 * PI * cutoff_hz / sample_rate as f32;
+    let alpha = w0.sin() / (2.0 * BUTTERWORTH_Q);
+    let cos = w0.cos();
+    let a0 = 1.0 + alpha;
+    let b0 = (1.0 + cos) / 2.0 / a0;
+    let b1 = -(1.0 + cos) / a0;
+    let b2 = b0;
+    let a1 = -2.0 * cos / a0;
+    let a2 = (1.0 - alpha) / a0;
+
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+    for sample in samples.iter_mut() {
+        let x0 = *sample;
+        let y0 = b0 * x0 + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+        x2 = x1;
+        x1 = x0;
+        y2 = y1;
+        y1 = y0;
+        *sample = y0;
+    }
+}
+
+// ============================================================================
+// Spectral Gating
+// ============================================================================
+
+/// Square root of a periodic Hann window: used for analysis and synthesis, so
+/// the overlap-added windows sum to 1 at 50% overlap
+fn sqrt_hann() -> Vec<f32> {
+    (0..FRAME_LEN)
+        .map(|i| (0.5 - 0.5 * (2.0 * PI * i as f32 / FRAME_LEN as f32).cos()).sqrt())
+        .collect()
+}
+
+fn spectral_gate(samples: &mut [f32]) {
+    if samples.len() < FRAME_LEN * 2 {
+        return;
+    }
+    let window = sqrt_hann();
+    let bins = FRAME_LEN / 2 + 1;
+
+    // Spectra of all frames
+    let frame_count = (samples.len() - FRAME_LEN) / HOP + 1;
+    let spectra: Vec<Vec<Complex>> = (0..frame_count)
+        .map(|frame| {
+            let start = frame * HOP;
+            let mut buffer: Vec<Complex> = samples[start..start + FRAME_LEN]
+                .iter()
+                .zip(&window)
+                .map(|(s, w)| Complex::new(s * w, 0.0))
+                .collect();
+            fft(&mut buffer, false);
+            buffer
+        })
+        .collect();
+
+    // Noise profile: mean magnitude per bin over the quietest frames
+    let mut energies: Vec<(usize, f32)> = spectra
+        .iter()
+        .enumerate()
+        .map(|(i, spectrum)| (i, spectrum[..bins].iter().map(|c| c.norm_sqr()).sum()))
+        .collect();
+    energies.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let noise_frames = ((frame_count as f32 * NOISE_FRAME_RATIO) as usize).max(1);
+    let mut noise = vec![0.0f32; bins];
+    for &(frame, _) in &energies[..noise_frames] {
+        for (bin, value) in noise.iter_mut().enumerate() {
+            *value += spectra[frame][bin].norm() / noise_frames as f32;
+        }
+    }
+
+    // Gate each frame and overlap-add it back
+    let mut output = vec![0.0f32; samples.len()];
+    let mut gains = vec![1.0f32; bins];
+    for (frame, mut spectrum) in spectra.into_iter().enumerate() {
+        for bin in 0..bins {
+            let target = if spectrum[bin].norm() > noise[bin] * GATE_THRESHOLD {
+                1.0
+            } else {
+                GATE_ATTENUATION
+            };
+            gains[bin] = GAIN_SMOOTHING * gains[bin] + (1.0 - GAIN_SMOOTHING) * target;
+            spectrum[bin] = spectrum[bin].scale(gains[bin]);
+            // Keep the spectrum conjugate-symmetric so the result stays real
+            if bin > 0 && bin < FRAME_LEN / 2 {
+                spectrum[FRAME_LEN - bin] = spectrum[bin].conj();
+            }
+        }
+        fft(&mut spectrum, true);
+        let start = frame * HOP;
+        for (i, value) in spectrum.iter().enumerate() {
+            output[start + i] += value.re * window[i];
+        }
+    }
+
+    // The first and last half frame are only covered by one window: keep them as they were
+    let covered = (frame_count - 1) * HOP + FRAME_LEN;
+    samples[HOP..covered - HOP].copy_from_slice(&output[HOP..covered - HOP]);
+}
+
+// ============================================================================
+// FFT
+// ============================================================================
+
+#[derive(Debug, Clone, Copy)]
+struct Complex {
+    re: f32,
+    im: f32,
+}
+
+impl Complex {
+    fn new(re: f32, im: f32) -> Self {
+        Complex { re, im }
+    }
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+
+    fn scale(self, factor: f32) -> Complex {
+        Complex::new(self.re * factor, self.im * factor)
+    }
+
+    fn conj(self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+
+    fn norm_sqr(self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    fn norm(self) -> f32 {
+        self.norm_sqr().sqrt()
+    }
+}
+
+/// In-place radix-2 FFT (length must be a power of two); `inverse` includes the 1/N scaling
+fn fft(buffer: &mut [Complex], inverse: bool) {
+    let n = buffer.len();
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            buffer.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f32;
+        let step = Complex::new(angle.cos(), angle.sin());
+        for start in (0..n).step_by(len) {
+            let mut twiddle = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let even = buffer[start + k];
+                let odd = buffer[start + k + len / 2].mul(twiddle);
+                buffer[start + k] = even.add(odd);
+                buffer[start + k + len / 2] = even.sub(odd);
+                twiddle = twiddle.mul(step);
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        for value in buffer.iter_mut() {
+            *value = value.scale(1.0 / n as f32);
+        }
+    }
+}
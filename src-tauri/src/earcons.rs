@@ -0,0 +1,181 @@
+// ============================================================================
+// Earcons Module - Sound Cues for State Changes
+// ============================================================================
+//
+// Short built-in sounds for record-start, record-stop, error and completion.
+// They are synthesized (a few sine notes each) and played natively through a
+// cpal output stream, the same library used for recording, so they also work
+// while every window is hidden.
+//
+// Config (`earcons`): a master `enabled` switch and per-cue `enabled` /
+// `volume` (0.0 - 1.0) under `cues`; cues missing there use the defaults.
+// The frontend plays cues with the `play_cue` command. Cues are muted while
+// continuous listening is paused by focus_pause.
+
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+
+use crate::config::{self, EarconsConfig};
+
+const CONFIG_KEY: &str = "earcons";
+
+/// Fade in/out of each note, avoids clicks
+const FADE_SECS: f32 = 0.005;
+
+/// Extra time the stream is kept open after the last note
+const TAIL: Duration = Duration::from_millis(50);
+
+/// A built-in sound cue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    RecordStart,
+    RecordStop,
+    Error,
+    Complete,
+}
+
+impl Cue {
+    pub const ALL: [Cue; 4] = [Cue::RecordStart, Cue::RecordStop, Cue::Error, Cue::Complete];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Cue::RecordStart => "record_start",
+            Cue::RecordStop => "record_stop",
+            Cue::Error => "error",
+            Cue::Complete => "complete",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Cue::ALL.into_iter().find(|cue| cue.as_str() == s)
+    }
+
+    /// Notes as (frequency in Hz, length in seconds); a frequency of 0 is a rest
+    fn notes(&self) -> &'static [(f32, f32)] {
+        match self {
+            Cue::RecordStart => &[(660.0, 0.06), (880.0, 0.08)],
+            Cue::RecordStop => &[(880.0, 0.06), (660.0, 0.08)],
+            Cue::Error => &[(220.0, 0.12), (0.0, 0.05), (220.0, 0.12)],
+            Cue::Complete => &[(523.25, 0.07), (659.25, 0.07), (783.99, 0.12)],
+        }
+    }
+}
+
+fn load_config() -> EarconsConfig {
+    config::read_config_value(CONFIG_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Volume of a cue if it is enabled
+fn cue_volume(config: &EarconsConfig, cue: Cue) -> Option<f32> {
+    if !config.enabled {
+        return None;
+    }
+    let cue_config = config.cues.get(cue.as_str()).cloned().unwrap_or_default();
+    (cue_config.enabled && cue_config.volume > 0.0).then_some(cue_config.volume.min(1.0))
+}
+
+/// Mono samples of a cue at the given sample rate
+fn render(cue: Cue, sample_rate: u32, volume: f32) -> Vec<f32> {
+    let rate = sample_rate as f32;
+    let fade = (FADE_SECS * rate) as usize;
+    let mut samples = Vec::new();
+    for &(frequency, length) in cue.notes() {
+        let count = (length * rate) as usize;
+        for i in 0..count {
+            if frequency == 0.0 {
+                samples.push(0.0);
+                continue;
+            }
+            let envelope = (i.min(count - i) as f32 / fade.max(1) as f32).min(1.0);
+            let phase = 2.0 * std::f32::consts::PI * frequency * i as f32 / rate;
+            samples.push(phase.sin() * envelope * volume);
+        }
+    }
+    samples
+}
+
+/// Play a cue if it is enabled in config (does not block)
+pub fn play(cue: Cue) {
+    if crate::focus_pause::earcons_muted() {
+        return;
+    }
+    let Some(volume) = cue_volume(&load_config(), cue) else {
+        return;
+    };
+    // cpal streams are not Send: open, play and drop the stream on one thread
+    std::thread::spawn(move || {
+        if let Err(e) = play_blocking(cue, volume) {
+            eprintln!("[EARCONS] Failed to play {}: {}", cue.as_str(), e);
+        }
+    });
+}
+
+fn play_blocking(cue: Cue, volume: f32) -> Result<(), String> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "No output device".to_string())?;
+    let supported = device
+        .default_output_config()
+        .map_err(|e| format!("Failed to get output config: {}", e))?;
+    let samples = render(cue, supported.sample_rate(), volume);
+    let length = Duration::from_secs_f32(samples.len() as f32 / supported.sample_rate() as f32);
+
+    let format = supported.sample_format();
+    let stream_config: cpal::StreamConfig = supported.into();
+    let stream = match format {
+        cpal::SampleFormat::F32 => build_stream::<f32>(&device, &stream_config, samples),
+        cpal::SampleFormat::I16 => build_stream::<i16>(&device, &stream_config, samples),
+        cpal::SampleFormat::U16 => build_stream::<u16>(&device, &stream_config, samples),
+        format => return Err(format!("Unsupported sample format: {:?}", format)),
+    }?;
+    stream.play().map_err(|e| format!("Failed to start stream: {}", e))?;
+
+    std::thread::sleep(length + TAIL);
+    Ok(())
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: Vec<f32>,
+) -> Result<cpal::Stream, String>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let channels = config.channels as usize;
+    let mut position = 0;
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(channels) {
+                    let sample = samples.get(position).copied().unwrap_or(0.0);
+                    position += 1;
+                    for out in frame.iter_mut() {
+                        *out = T::from_sample(sample);
+                    }
+                }
+            },
+            |e| eprintln!("[EARCONS] Stream error: {}", e),
+            None,
+        )
+        .map_err(|e| format!("Failed to build output stream: {}", e))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Play a sound cue ("record_start", "record_stop", "error", "complete")
+///
+/// Respects the `earcons` settings; disabled cues are silently skipped.
+#[tauri::command]
+pub fn play_cue(name: String) -> Result<(), String> {
+    let cue = Cue::from_str(&name).ok_or_else(|| format!("Unknown cue: {}", name))?;
+    play(cue);
+    Ok(())
+}
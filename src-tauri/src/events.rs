@@ -0,0 +1,236 @@
+// ============================================================================
+// Events Module - Typed Events Sent to the Frontend
+// ============================================================================
+//
+// Every event the backend sends is a variant of AppEvent, so its name and
+// payload type are fixed in one place instead of a string at each call site.
+// The variant name in kebab-case is the event name ("ptt-state" for
+// PttState), and emit_event sends it twice:
+//
+//   <name>      the payload alone, what the existing listeners expect
+//   app-event   {"type": <name>, "payload": <payload>, "seq": <n>}, one
+//               channel a window can listen to for everything (see
+//               useTauriAPI AppEvent)
+//
+// Events are broadcast to every window (as Emitter::emit on a window does).
+//
+// A webview that reloads (hot reload, crash) misses what was sent meanwhile,
+// e.g. the tail of a streamed answer. Every event gets an increasing `seq`,
+// and the last REPLAY_CAPACITY events of each name are kept: the frontend
+// passes the last seq it saw to `resync_events` and gets the newer ones back
+// in order. Sequence numbers restart with the app.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::database::{Message, Reminder, RetentionReport, Transcription, TranscriptionJob};
+use crate::profiles::ProfileList;
+use crate::selection::SelectionAnswer;
+use crate::types::{
+    AudioChunkPayload, ConnectivityPayload, DaemonHealthPayload, DaemonProgressPayload, DaemonStatusPayload,
+    DictationAppendPayload, DictationState, DownloadProgressPayload, ListeningPausedPayload, MeetingStatus,
+    MicConflictPayload, ModelLoadingPayload, ProviderErrorPayload, RecordingTimeoutPayload,
+    SessionTitleUpdatedPayload, StatusTransition, TtsExportProgressPayload, TypingBlockedPayload,
+    UpdateProgressPayload,
+};
+
+/// Channel carrying every event with its name
+pub const APP_EVENT_CHANNEL: &str = "app-event";
+
+/// Events kept for resync_events, per event name
+const REPLAY_CAPACITY: usize = 256;
+
+/// Recent app-event values by event name, with the last seq handed out
+struct ReplayBuffer {
+    seq: u64,
+    channels: BTreeMap<String, VecDeque<serde_json::Value>>,
+}
+
+static REPLAY: Mutex<ReplayBuffer> = Mutex::new(ReplayBuffer::new());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "payload", rename_all = "kebab-case")]
+pub enum AppEvent {
+    // Status
+    AppStatusChanged(StatusTransition),
+    ConnectivityChanged(ConnectivityPayload),
+    ListeningPaused(ListeningPausedPayload),
+    ProviderError(ProviderErrorPayload),
+
+    // Daemon
+    DaemonHealth(DaemonHealthPayload),
+    DaemonProgress(DaemonProgressPayload),
+    DaemonStatus(DaemonStatusPayload),
+    DownloadProgress(DownloadProgressPayload),
+    ModelLoading(ModelLoadingPayload),
+    UpdateDownloadProgress(UpdateProgressPayload),
+
+    // Push-to-talk and continuous recording
+    /// "listening", "detected", "recording", "latched", "processing", "idle" or "error"
+    PttState(String),
+    PttAsrPartial(String),
+    PttUserMessage(String),
+    PttAssistantChunk(String),
+    PttAssistantDone(String),
+    PttAudioChunk(AudioChunkPayload),
+    PttError(String),
+    MicConflict(MicConflictPayload),
+    RecordingModeChanged(String),
+    RecordingTimeout(RecordingTimeoutPayload),
+
+    // Streaming chat and TTS
+    ChatChunk(String),
+    /// Id of the reply saved to the session (see chat_draft)
+    ChatDone(Option<String>),
+    ChatError(String),
+    TtsTextChunk(String),
+    TtsAudioChunk(AudioChunkPayload),
+    TtsDone,
+    TtsError(String),
+    /// Command whose stream was interrupted ("chat_stream", "chat_tts_stream")
+    StreamCancelled(String),
+    TtsExportProgress(TtsExportProgressPayload),
+
+    // History
+    SessionTitleUpdated(SessionTitleUpdatedPayload),
+    /// Session to show in the main window
+    OpenSession(String),
+    HistoryRetentionApplied(RetentionReport),
+    HistoryWindowShown,
+    PinnedMessagesChanged,
+    PinnedWindowShown,
+    ProfileSwitched(ProfileList),
+
+    // Dictation, meetings and file transcription
+    TranscriptionAdded(Transcription),
+    TranscriptionPickerShown,
+    DictationState(DictationState),
+    DictationAppend(DictationAppendPayload),
+    MeetingState(MeetingStatus),
+    MeetingTranscript(Box<Message>),
+    FileTranscriptionProgress(Box<TranscriptionJob>),
+    /// Text-input mode refused to type (e.g. into a password field)
+    TypingBlocked(TypingBlockedPayload),
+
+    // Other features
+    ReminderFired(Reminder),
+    SelectionAnswer(SelectionAnswer),
+}
+
+/// Event name and payload of `event`
+fn split(event: &AppEvent) -> Result<(String, serde_json::Value, serde_json::Value), String> {
+    let value = serde_json::to_value(event).map_err(|e| format!("Failed to serialize event: {}", e))?;
+    let name = value
+        .get("type")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Event has no name".to_string())?
+        .to_string();
+    let payload = value.get("payload").cloned().unwrap_or(serde_json::Value::Null);
+    Ok((name, payload, value))
+}
+
+impl ReplayBuffer {
+    const fn new() -> Self {
+        Self { seq: 0, channels: BTreeMap::new() }
+    }
+
+    /// Number `value` (an app-event value) and keep it; returns it with its seq
+    fn record(&mut self, name: &str, mut value: serde_json::Value) -> serde_json::Value {
+        self.seq += 1;
+        value["seq"] = self.seq.into();
+
+        let channel = self.channels.entry(name.to_string()).or_default();
+        if channel.len() == REPLAY_CAPACITY {
+            channel.pop_front();
+        }
+        channel.push_back(value.clone());
+        value
+    }
+
+    /// Kept events newer than `since_seq`, oldest first
+    fn since(&self, since_seq: u64) -> Vec<serde_json::Value> {
+        let seq_of = |value: &serde_json::Value| value["seq"].as_u64().unwrap_or_default();
+        let mut events: Vec<serde_json::Value> = self
+            .channels
+            .values()
+            .flat_map(|channel| channel.iter().filter(|value| seq_of(value) > since_seq).cloned())
+            .collect();
+        events.sort_by_key(seq_of);
+        events
+    }
+}
+
+/// Send `event` to every window, under its own name and on APP_EVENT_CHANNEL
+pub fn emit_event<R: tauri::Runtime>(app: &tauri::AppHandle<R>, event: AppEvent) {
+    let (name, payload, value) = match split(&event) {
+        Ok(parts) => parts,
+        Err(e) => {
+            eprintln!("[EVENTS] {}", e);
+            return;
+        }
+    };
+    let value = REPLAY.lock().unwrap().record(&name, value);
+    let _ = app.emit(&name, payload);
+    let _ = app.emit(APP_EVENT_CHANNEL, value);
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// app-event values sent after `since_seq` (0 = all kept), oldest first
+#[tauri::command]
+pub fn resync_events(since_seq: u64) -> Vec<serde_json::Value> {
+    REPLAY.lock().unwrap().since(since_seq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_names_and_payloads() {
+        let (name, payload, value) = split(&AppEvent::PttState("recording".into())).unwrap();
+        assert_eq!(name, "ptt-state");
+        assert_eq!(payload, serde_json::json!("recording"));
+        assert_eq!(value, serde_json::json!({ "type": "ptt-state", "payload": "recording" }));
+
+        let (name, payload, _) = split(&AppEvent::TtsDone).unwrap();
+        assert_eq!(name, "tts-done");
+        assert_eq!(payload, serde_json::Value::Null);
+
+        let chunk = AudioChunkPayload { audio_path: "/tmp/a.wav".into(), text: "Hi".into() };
+        let (name, payload, _) = split(&AppEvent::TtsAudioChunk(chunk)).unwrap();
+        assert_eq!(name, "tts-audio-chunk");
+        assert_eq!(payload, serde_json::json!({ "audio_path": "/tmp/a.wav", "text": "Hi" }));
+
+        let (name, _, _) = split(&AppEvent::ChatDone(None)).unwrap();
+        assert_eq!(name, "chat-done");
+    }
+
+    #[test]
+    fn test_replay_since_seq() {
+        let mut replay = ReplayBuffer::new();
+        for i in 0..REPLAY_CAPACITY + 2 {
+            let (name, _, value) = split(&AppEvent::ChatChunk(i.to_string())).unwrap();
+            replay.record(&name, value);
+        }
+        let (name, _, value) = split(&AppEvent::ChatDone(None)).unwrap();
+        let done = replay.record(&name, value);
+        assert_eq!(done["seq"], (REPLAY_CAPACITY as u64 + 3));
+
+        // The two oldest chunks were dropped
+        let all = replay.since(0);
+        assert_eq!(all.len(), REPLAY_CAPACITY + 1);
+        assert_eq!(all[0]["seq"], 3);
+        assert_eq!(all[0]["payload"], "2");
+
+        let tail = replay.since(REPLAY_CAPACITY as u64 + 1);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[0]["type"], "chat-chunk");
+        assert_eq!(tail[1], done);
+    }
+}
@@ -0,0 +1,230 @@
+// ============================================================================
+// Export Module - Conversation Serialization (Markdown / JSON / HTML)
+// ============================================================================
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::database::{Message, Session};
+
+/// Identifier written into JSON bundles (checked again on import)
+pub const EXPORT_FORMAT_ID: &str = "speekium-export";
+
+/// Current JSON bundle version
+pub const EXPORT_VERSION: u32 = 1;
+
+// ============================================================================
+// Data Structures
+// ============================================================================
+
+/// Supported export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "markdown" | "md" => Some(ExportFormat::Markdown),
+            "json" => Some(ExportFormat::Json),
+            "html" => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    /// Display name for the save dialog filter
+    pub fn filter_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Html => "HTML",
+        }
+    }
+}
+
+/// A session together with all of its messages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedSession {
+    #[serde(flatten)]
+    pub session: Session,
+    pub messages: Vec<Message>,
+}
+
+/// Top-level JSON export bundle
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportBundle {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: i64,
+    pub sessions: Vec<ExportedSession>,
+}
+
+impl ExportBundle {
+    pub fn new(sessions: Vec<ExportedSession>) -> Self {
+        ExportBundle {
+            format: EXPORT_FORMAT_ID.to_string(),
+            version: EXPORT_VERSION,
+            exported_at: Utc::now().timestamp_millis(),
+            sessions,
+        }
+    }
+}
+
+/// Summary of an import operation
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub sessions_imported: usize,
+    pub sessions_skipped: usize,
+    pub messages_imported: usize,
+    pub messages_skipped: usize,
+}
+
+/// Parse and validate a JSON export bundle
+pub fn parse_bundle(content: &str) -> Result<ExportBundle, String> {
+    let bundle: ExportBundle = serde_json::from_str(content)
+        .map_err(|e| format!("Invalid export file: {}", e))?;
+
+    if bundle.format != EXPORT_FORMAT_ID {
+        return Err(format!("Unrecognized export format: {}", bundle.format));
+    }
+    if bundle.version > EXPORT_VERSION {
+        return Err(format!(
+            "Export version {} is newer than supported version {}",
+            bundle.version, EXPORT_VERSION
+        ));
+    }
+
+    Ok(bundle)
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Render sessions in the requested format
+pub fn render(sessions: Vec<ExportedSession>, format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Markdown => Ok(render_markdown(&sessions)),
+        ExportFormat::Json => serde_json::to_string_pretty(&ExportBundle::new(sessions))
+            .map_err(|e| format!("Failed to serialize export: {}", e)),
+        ExportFormat::Html => Ok(render_html(&sessions)),
+    }
+}
+
+fn format_timestamp(timestamp_ms: i64) -> String {
+    DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+fn role_label(role: &str) -> &'static str {
+    match role {
+        "user" => "👤 用户",
+        "assistant" => "🤖 助手",
+        _ => "📝 系统",
+    }
+}
+
+fn render_markdown(sessions: &[ExportedSession]) -> String {
+    let mut markdown = String::new();
+    markdown.push_str(&format!("导出时间: {}\n\n", Local::now().format("%Y-%m-%d %H:%M:%S")));
+
+    for exported in sessions {
+        let session = &exported.session;
+        markdown.push_str("---\n\n");
+        let star = if session.is_favorite { "⭐ " } else { "" };
+        markdown.push_str(&format!("# {}{}\n\n", star, session.title));
+        markdown.push_str(&format!(
+            "- 创建时间: {}\n- 更新时间: {}\n- 消息数: {}\n\n",
+            format_timestamp(session.created_at),
+            format_timestamp(session.updated_at),
+            exported.messages.len()
+        ));
+
+        for msg in &exported.messages {
+            markdown.push_str(&format!(
+                "### {} - {}\n\n{}\n\n",
+                role_label(&msg.role),
+                format_timestamp(msg.timestamp),
+                msg.content
+            ));
+        }
+    }
+
+    markdown
+}
+
+/// Escape text for safe inclusion in HTML
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:820px;margin:2rem auto;padding:0 1rem;color:#1f2937}\
+h1{font-size:1.4rem;margin-top:2.5rem}\
+.meta{color:#6b7280;font-size:.85rem}\
+.msg{border-radius:10px;padding:.75rem 1rem;margin:.75rem 0;white-space:pre-wrap}\
+.user{background:#eef2ff}.assistant{background:#f3f4f6}.system{background:#fef3c7}\
+.role{font-weight:600;font-size:.85rem;margin-bottom:.25rem}";
+
+fn render_html(sessions: &[ExportedSession]) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Speekium Export</title>\n");
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", HTML_STYLE));
+    html.push_str(&format!(
+        "<p class=\"meta\">导出时间: {}</p>\n",
+        Local::now().format("%Y-%m-%d %H:%M:%S")
+    ));
+
+    for exported in sessions {
+        let session = &exported.session;
+        html.push_str(&format!(
+            "<section>\n<h1>{}{}</h1>\n<p class=\"meta\">创建时间: {} · 更新时间: {} · 消息数: {}</p>\n",
+            if session.is_favorite { "⭐ " } else { "" },
+            escape_html(&session.title),
+            format_timestamp(session.created_at),
+            format_timestamp(session.updated_at),
+            exported.messages.len()
+        ));
+
+        for msg in &exported.messages {
+            let class = match msg.role.as_str() {
+                "user" | "assistant" => msg.role.as_str(),
+                _ => "system",
+            };
+            html.push_str(&format!(
+                "<div class=\"msg {}\"><div class=\"role\">{} · {}</div>{}</div>\n",
+                class,
+                role_label(&msg.role),
+                format_timestamp(msg.timestamp),
+                escape_html(&msg.content)
+            ));
+        }
+
+        html.push_str("</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
@@ -0,0 +1,562 @@
+// ============================================================================
+// File Transcription Module - Transcribing Existing Recordings
+// ============================================================================
+//
+// Voice memos and other recordings (wav, mp3, m4a) can be transcribed with
+// `transcribe_file(s)` or by dropping them on the main window. Each file is
+// a background job:
+//
+//   decoding      WAV is parsed here and mp3/m4a (AAC) are decoded with
+//                 symphonia; other codecs in an m4a (e.g. ALAC) are converted
+//                 to WAV with the system's tools if present (afconvert on
+//                 macOS, ffmpeg elsewhere)
+//   transcribing  the 16 kHz mono audio (audio.rs resampler) is sent to ASR in
+//                 chunks of about CHUNK_SECS, cut at the quietest moment near
+//                 the end of each chunk so words are not split
+//   done          the transcript is saved as a new session named after the file
+//
+// Jobs are queued in the `transcription_jobs` table and run oldest first,
+// `transcription_concurrency` at a time (1 by default). They can be listed,
+// cancelled and retried; jobs interrupted by quitting start again on launch.
+// Every change of a job is emitted as a `file-transcription-progress` event.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{Manager};
+
+use crate::daemon::call_daemon_async_with;
+use crate::daemon::request::{CancelToken, RequestOptions};
+use crate::database::{Database, TranscriptionJob};
+use crate::state::AppState;
+use crate::events::{emit_event, AppEvent};
+
+pub const SUPPORTED_EXTENSIONS: [&str; 3] = ["wav", "mp3", "m4a"];
+
+const SAMPLE_RATE: usize = 16000;
+/// Target chunk length sent to ASR
+const CHUNK_SECS: usize = 30;
+/// The cut is placed at the quietest window within this much of the chunk end
+const CUT_SEARCH_SECS: usize = 5;
+const CUT_WINDOW: usize = SAMPLE_RATE / 10;
+
+const DEFAULT_CONCURRENCY: u64 = 1;
+const MAX_CONCURRENCY: u64 = 4;
+
+/// Cancel tokens of the jobs being transcribed, by job id
+static RUNNING: Mutex<Vec<(String, CancelToken)>> = Mutex::new(Vec::new());
+
+fn is_supported(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SUPPORTED_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Parse a WAV file (PCM 8/16/24/32-bit or 32-bit float) into 16kHz mono
+fn parse_wav(bytes: &[u8]) -> Result<Vec<f32>, String> {
+    if bytes.get(0..4) != Some(b"RIFF") || bytes.get(8..12) != Some(b"WAVE") {
+        return Err("Not a WAV file".to_string());
+    }
+
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = read_u32(bytes, pos + 4).unwrap_or(0) as usize;
+        let body = pos + 8;
+        match id {
+            b"fmt " => {
+                let mut tag = read_u16(bytes, body).ok_or("Truncated fmt chunk")?;
+                // WAVE_FORMAT_EXTENSIBLE: the real format is in the sub-format GUID
+                if tag == 0xFFFE {
+                    tag = read_u16(bytes, body + 24).ok_or("Truncated fmt chunk")?;
+                }
+                let channels = read_u16(bytes, body + 2).ok_or("Truncated fmt chunk")?;
+                let rate = read_u32(bytes, body + 4).ok_or("Truncated fmt chunk")?;
+                let bits = read_u16(bytes, body + 14).ok_or("Truncated fmt chunk")?;
+                format = Some((tag, channels.max(1), rate, bits));
+            }
+            b"data" => {
+                let (tag, channels, rate, bits) = format.ok_or("WAV data before fmt chunk")?;
+                let data = &bytes[body..(body + size).min(bytes.len())];
+                let samples: Vec<f32> = match (tag, bits) {
+                    (1, 8) => data.iter().map(|&b| (b as f32 - 128.0) / 128.0).collect(),
+                    (1, 16) => data
+                        .chunks_exact(2)
+                        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+                        .collect(),
+                    (1, 24) => data
+                        .chunks_exact(3)
+                        .map(|b| i32::from_le_bytes([0, b[0], b[1], b[2]]) as f32 / 2147483648.0)
+                        .collect(),
+                    (1, 32) => data
+                        .chunks_exact(4)
+                        .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2147483648.0)
+                        .collect(),
+                    (3, 32) => data
+                        .chunks_exact(4)
+                        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                        .collect(),
+                    _ => return Err(format!("Unsupported WAV encoding (format {}, {} bit)", tag, bits)),
+                };
+                return Ok(crate::audio::process_audio_data(&samples, rate, channels));
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size
+        pos = body + size + (size & 1);
+    }
+    Err("WAV file has no audio data".to_string())
+}
+
+/// Decode an mp3/m4a file with symphonia into 16kHz mono
+fn decode_compressed(path: &Path) -> Result<Vec<f32>, String> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::errors::Error;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let mut format = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Unsupported audio file: {}", e))?
+        .format;
+    let track = format.default_track().ok_or("Audio file has no track")?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Unsupported audio codec: {}", e))?;
+
+    let mut samples = Vec::new();
+    let mut format_of_samples = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(format!("Failed to decode the file: {}", e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt frame is skipped
+            Err(Error::DecodeError(_)) => continue,
+            Err(e) => return Err(format!("Failed to decode the file: {}", e)),
+        };
+        let spec = *decoded.spec();
+        format_of_samples = Some((spec.rate, spec.channels.count() as u16));
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend_from_slice(buffer.samples());
+    }
+    let (rate, channels) = format_of_samples.ok_or("File has no audio data")?;
+    Ok(crate::audio::process_audio_data(&samples, rate, channels))
+}
+
+/// Convert a compressed file to WAV with the system's tools
+fn convert_to_wav(path: &Path) -> Result<PathBuf, String> {
+    let output = std::env::temp_dir().join(format!("speekium_file_{}.wav", uuid::Uuid::new_v4().simple()));
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("afconvert");
+        command.args(["-f", "WAVE", "-d", "LEI16"]).arg(path).arg(&output);
+        command
+    };
+    #[cfg(not(target_os = "macos"))]
+    let mut command = {
+        let mut command = std::process::Command::new("ffmpeg");
+        command
+            .args(["-y", "-loglevel", "error", "-i"])
+            .arg(path)
+            .args(["-ac", "1", "-ar", "16000"])
+            .arg(&output);
+        command
+    };
+
+    let result = command.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            "Converting this file needs ffmpeg, which was not found".to_string()
+        } else {
+            format!("Failed to convert the file: {}", e)
+        }
+    })?;
+    if !result.status.success() {
+        let _ = std::fs::remove_file(&output);
+        return Err(format!(
+            "Failed to convert the file: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    Ok(output)
+}
+
+/// Decode a supported file into 16kHz mono samples
+fn decode(path: &Path) -> Result<Vec<f32>, String> {
+    let is_wav = path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    if is_wav {
+        let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        return parse_wav(&bytes);
+    }
+
+    let decode_error = match decode_compressed(path) {
+        Ok(samples) => return Ok(samples),
+        Err(e) => e,
+    };
+    // Codecs symphonia lacks may still convert with the system's tools
+    let wav = convert_to_wav(path).map_err(|e| {
+        eprintln!("[FILE ASR] Fallback conversion failed: {}", e);
+        decode_error
+    })?;
+    let result = std::fs::read(&wav)
+        .map_err(|e| format!("Failed to read converted audio: {}", e))
+        .and_then(|bytes| parse_wav(&bytes));
+    let _ = std::fs::remove_file(&wav);
+    result
+}
+
+/// Split audio into chunks of about CHUNK_SECS, cutting at quiet moments
+fn split_chunks(samples: &[f32]) -> Vec<&[f32]> {
+    let target = CHUNK_SECS * SAMPLE_RATE;
+    let search = CUT_SEARCH_SECS * SAMPLE_RATE;
+    let mut chunks = Vec::new();
+    let mut rest = samples;
+    while rest.len() > target {
+        let energy = |start: usize| rest[start..start + CUT_WINDOW].iter().map(|s| s * s).sum::<f32>();
+        let cut = (target - search..target)
+            .step_by(CUT_WINDOW)
+            .min_by(|&a, &b| energy(a).total_cmp(&energy(b)))
+            .map(|start| start + CUT_WINDOW / 2)
+            .unwrap_or(target);
+        let (chunk, tail) = rest.split_at(cut);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+// ============================================================================
+// Jobs
+// ============================================================================
+
+fn concurrency() -> usize {
+    crate::config::read_config_value("transcription_concurrency")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY) as usize
+}
+
+/// Save a job's state and report it to the frontend
+fn save_job<R: tauri::Runtime>(app: &tauri::AppHandle<R>, db: &Database, job: &mut TranscriptionJob) {
+    if let Err(e) = db.update_transcription_job(job) {
+        eprintln!("[FILE ASR] {}", e);
+    }
+    emit_event(app, AppEvent::FileTranscriptionProgress(Box::new(job.clone())));
+}
+
+async fn transcribe_chunk(samples: &[f32], cancel: &CancelToken) -> Result<String, String> {
+    // The daemon deletes the file after reading it
+    let audio_path = crate::audio::write_temp_wav(samples, "file")?;
+    let result = call_daemon_async_with(
+        "transcribe",
+        serde_json::json!({
+            "audio_path": audio_path,
+            "language": crate::asr_language::request_language(),
+            "hotwords": crate::vocabulary::request_hotwords(),
+        }),
+        RequestOptions::for_command("transcribe").with_cancel(cancel.clone()),
+    )
+    .await?;
+    if !result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return Err(result.get("error").and_then(|v| v.as_str()).unwrap_or("ASR failed").to_string());
+    }
+    Ok(result.get("text").and_then(|v| v.as_str()).unwrap_or_default().to_string())
+}
+
+/// Decode, transcribe and save one file; returns the new session id
+///
+/// Cancellation is checked between chunks and abandons the chunk in flight.
+async fn run_job<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    db: &Database,
+    job: &mut TranscriptionJob,
+    cancel: &CancelToken,
+) -> Result<String, String> {
+    let file = PathBuf::from(&job.path);
+    let samples = tauri::async_runtime::spawn_blocking(move || decode(&file))
+        .await
+        .map_err(|e| e.to_string())??;
+    if samples.is_empty() {
+        return Err("The file contains no audio".to_string());
+    }
+
+    let chunks = split_chunks(&samples);
+    let mut texts = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        if cancel.is_cancelled() {
+            return Err("Cancelled".to_string());
+        }
+        job.status = "transcribing".to_string();
+        job.percent = (index * 100 / chunks.len()) as u32;
+        save_job(app, db, job);
+
+        let text = crate::textproc::process_transcript(app, &transcribe_chunk(chunk, cancel).await?);
+        if !text.trim().is_empty() {
+            texts.push(text);
+        }
+    }
+    if cancel.is_cancelled() {
+        return Err("Cancelled".to_string());
+    }
+    if texts.is_empty() {
+        return Err("No speech was recognized".to_string());
+    }
+
+    let session = db.create_session(job.file_name.clone())?;
+    for text in &texts {
+        db.add_message(&session.id, "user", text)?;
+    }
+    let _ = crate::ui::update_tray_menu(app);
+    Ok(session.id)
+}
+
+/// Run a job started by `pump`, then start the next queued one
+async fn process_job<R: tauri::Runtime>(app: tauri::AppHandle<R>, mut job: TranscriptionJob, cancel: CancelToken) {
+    if let Some(state) = app.try_state::<AppState>() {
+        println!("[FILE ASR] Job {}: {}", job.id, job.path);
+        match run_job(&app, &state.db, &mut job, &cancel).await {
+            _ if cancel.is_cancelled() => {
+                println!("[FILE ASR] Job {} cancelled", job.id);
+                job.status = "cancelled".to_string();
+            }
+            Ok(session_id) => {
+                println!("[FILE ASR] Job {} saved to session {}", job.id, session_id);
+                job.status = "done".to_string();
+                job.percent = 100;
+                job.session_id = Some(session_id);
+            }
+            Err(e) => {
+                eprintln!("[FILE ASR] Job {} failed: {}", job.id, e);
+                job.status = "failed".to_string();
+                job.error = Some(e);
+            }
+        }
+        save_job(&app, &state.db, &mut job);
+    }
+
+    if let Ok(mut running) = RUNNING.lock() {
+        running.retain(|(id, _)| *id != job.id);
+    }
+    pump(&app);
+}
+
+/// Start queued jobs, oldest first, while fewer than `transcription_concurrency` run
+fn pump<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    // Held while picking jobs, so a job is never started twice
+    let Ok(mut running) = RUNNING.lock() else {
+        return;
+    };
+    while running.len() < concurrency() {
+        let mut job = match state.db.next_queued_transcription_job() {
+            Ok(Some(job)) => job,
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("[FILE ASR] {}", e);
+                break;
+            }
+        };
+        job.status = "decoding".to_string();
+        save_job(app, &state.db, &mut job);
+
+        let cancel = CancelToken::new();
+        running.push((job.id.clone(), cancel.clone()));
+        tauri::async_runtime::spawn(process_job(app.clone(), job, cancel));
+    }
+}
+
+/// Queue files for transcription; nothing is queued if one of them is not supported
+pub fn enqueue<R: tauri::Runtime>(app: &tauri::AppHandle<R>, paths: &[PathBuf]) -> Result<Vec<TranscriptionJob>, String> {
+    for path in paths {
+        if !path.is_file() {
+            return Err(format!("{} is not a file", path.display()));
+        }
+        if !is_supported(path) {
+            return Err(format!("Unsupported file type (supported: {})", SUPPORTED_EXTENSIONS.join(", ")));
+        }
+    }
+    let state = app.try_state::<AppState>().ok_or_else(|| "Database is not ready".to_string())?;
+
+    let mut jobs = Vec::new();
+    for path in paths {
+        let file_name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Recording".to_string());
+        let job = state.db.add_transcription_job(&path.to_string_lossy(), &file_name)?;
+        emit_event(app, AppEvent::FileTranscriptionProgress(Box::new(job.clone())));
+        jobs.push(job);
+    }
+    pump(app);
+    Ok(jobs)
+}
+
+/// Whether a job is being transcribed right now
+pub fn is_running() -> bool {
+    RUNNING.lock().is_ok_and(|running| !running.is_empty())
+}
+
+/// Transcribe the supported files dropped on the main window
+pub fn handle_drop<R: tauri::Runtime>(app: &tauri::AppHandle<R>, paths: &[PathBuf]) {
+    let paths: Vec<PathBuf> = paths.iter().filter(|p| is_supported(p)).cloned().collect();
+    if let Err(e) = enqueue(app, &paths) {
+        eprintln!("[FILE ASR] {}", e);
+    }
+}
+
+/// Queue again the jobs interrupted by the last quit (call once the database is managed)
+pub fn resume_jobs<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    match state.db.requeue_interrupted_transcription_jobs() {
+        Ok(0) => {}
+        Ok(count) => println!("[FILE ASR] Requeued {} interrupted job(s)", count),
+        Err(e) => eprintln!("[FILE ASR] {}", e),
+    }
+    pump(app);
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Transcribe a wav/mp3/m4a file into a new session; returns the job id
+#[tauri::command]
+pub fn transcribe_file(app: tauri::AppHandle, path: String) -> Result<String, String> {
+    let jobs = enqueue(&app, &[PathBuf::from(path)])?;
+    Ok(jobs.into_iter().next().map(|job| job.id).unwrap_or_default())
+}
+
+/// Queue several files; each becomes a session of its own
+#[tauri::command]
+pub fn transcribe_files(app: tauri::AppHandle, paths: Vec<String>) -> Result<Vec<TranscriptionJob>, String> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    enqueue(&app, &paths)
+}
+
+/// All transcription jobs, newest first
+#[tauri::command]
+pub fn list_jobs(state: tauri::State<'_, AppState>) -> Result<Vec<TranscriptionJob>, String> {
+    state.db.list_transcription_jobs()
+}
+
+/// Cancel a queued or running job; a running job stops after its current chunk
+#[tauri::command]
+pub fn cancel_job(app: tauri::AppHandle, job_id: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    // Held so `pump` cannot start the job meanwhile
+    let running = RUNNING.lock().map_err(|e| e.to_string())?;
+    let mut job = state.db.get_transcription_job(&job_id)?;
+    match job.status.as_str() {
+        "queued" => {
+            job.status = "cancelled".to_string();
+            save_job(&app, &state.db, &mut job);
+        }
+        "decoding" | "transcribing" => {
+            if let Some((_, cancel)) = running.iter().find(|(id, _)| *id == job_id) {
+                cancel.cancel();
+            }
+        }
+        status => return Err(format!("Job is already {}", status)),
+    }
+    Ok(())
+}
+
+/// Queue a failed or cancelled job again
+#[tauri::command]
+pub fn retry_job(app: tauri::AppHandle, job_id: String) -> Result<TranscriptionJob, String> {
+    let state = app.state::<AppState>();
+    let mut job = state.db.get_transcription_job(&job_id)?;
+    if job.status != "failed" && job.status != "cancelled" {
+        return Err(format!("Only failed or cancelled jobs can be retried (job is {})", job.status));
+    }
+    job.status = "queued".to_string();
+    job.percent = 0;
+    job.session_id = None;
+    job.error = None;
+    save_job(&app, &state.db, &mut job);
+    pump(&app);
+    Ok(job)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_wav_resamples_to_mono_16k() {
+        // 0.5 s of 32 kHz stereo 16-bit PCM
+        let frames: u32 = 16000;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + frames * 4).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&32000u32.to_le_bytes());
+        wav.extend_from_slice(&128000u32.to_le_bytes());
+        wav.extend_from_slice(&4u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(frames * 4).to_le_bytes());
+        for _ in 0..frames {
+            wav.extend_from_slice(&16384i16.to_le_bytes());
+            wav.extend_from_slice(&0i16.to_le_bytes());
+        }
+
+        let samples = parse_wav(&wav).unwrap();
+        assert_eq!(samples.len(), 8000);
+        assert!((samples[100] - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_split_chunks_cuts_at_silence() {
+        let mut samples = vec![0.5f32; 40 * SAMPLE_RATE];
+        // A pause 2 s before the target length
+        samples[28 * SAMPLE_RATE..28 * SAMPLE_RATE + SAMPLE_RATE / 5].fill(0.0);
+
+        let chunks = split_chunks(&samples);
+        assert_eq!(chunks.len(), 2);
+        let cut = chunks[0].len();
+        assert!((28 * SAMPLE_RATE..28 * SAMPLE_RATE + SAMPLE_RATE / 5).contains(&cut));
+        assert_eq!(chunks[0].len() + chunks[1].len(), samples.len());
+    }
+}
@@ -0,0 +1,267 @@
+// ============================================================================
+// Focus Pause Module - Auto-Pause for Do Not Disturb and Meetings
+// ============================================================================
+//
+// Continuous listening is paused (and sound cues muted) while:
+//
+//   manual       the user paused it (`pause_listening`, tray "Pause for 30
+//                min"), until `resume_listening` or the given time runs out
+//   dnd          the system Do Not Disturb / Focus mode is on
+//                (macOS Focus, GNOME; `focus_pause.system_dnd`)
+//   app          an app from `focus_pause.apps` is frontmost (macOS)
+//   microphone   an app from `focus_pause.apps` is using the microphone
+//                (Windows, Linux)
+//
+// Entries of `focus_pause.apps` match a bundle id, app name or executable
+// name, case-insensitively. `set_dnd_override` forces the pause on ("pause")
+// or off ("resume") until it is set back to "auto".
+//
+// A monitor checks the state every few seconds and emits `listening-paused`
+// on every change; while paused, the app status reads "paused" instead of
+// "idle". Push-to-talk keeps working while paused.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+
+use crate::config::{self, FocusPauseConfig};
+use crate::daemon::{APP_HANDLE, APP_STATUS, DAEMON};
+use crate::types::{AppStatus, ListeningPausedPayload};
+use crate::events::{emit_event, AppEvent};
+
+const CONFIG_KEY: &str = "focus_pause";
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Manual override of the automatic detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DndOverride {
+    Auto,
+    Pause,
+    Resume,
+}
+
+impl DndOverride {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "auto" => Some(DndOverride::Auto),
+            "pause" => Some(DndOverride::Pause),
+            "resume" => Some(DndOverride::Resume),
+            _ => None,
+        }
+    }
+}
+
+static OVERRIDE: Mutex<DndOverride> = Mutex::new(DndOverride::Auto);
+
+/// Pause requested with `pause_listening`
+#[derive(Debug, Clone, Copy)]
+struct ManualPause {
+    /// End of the pause (Unix ms); None = until resumed
+    until: Option<i64>,
+}
+
+static MANUAL_PAUSE: Mutex<Option<ManualPause>> = Mutex::new(None);
+
+/// State last reported with `listening-paused`
+static STATE: Mutex<Option<ListeningPausedPayload>> = Mutex::new(None);
+
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn load_config() -> FocusPauseConfig {
+    config::read_config_value(CONFIG_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Whether continuous listening is paused
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::SeqCst)
+}
+
+/// Whether sound cues are muted by the pause
+pub fn earcons_muted() -> bool {
+    is_paused() && load_config().mute_earcons
+}
+
+fn matches_app(config: &FocusPauseConfig, id: &str) -> Option<String> {
+    config
+        .apps
+        .iter()
+        .find(|app| app.eq_ignore_ascii_case(id))
+        .map(|_| id.to_string())
+}
+
+/// Current pause state from the override, the system DND and the watched apps
+fn detect() -> ListeningPausedPayload {
+    let paused = |reason: &str, app: Option<String>| ListeningPausedPayload {
+        paused: true,
+        reason: Some(reason.to_string()),
+        app,
+        until: None,
+    };
+    {
+        let mut manual = MANUAL_PAUSE.lock().unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        match *manual {
+            Some(ManualPause { until: Some(until) }) if until <= now => *manual = None,
+            Some(ManualPause { until }) => {
+                return ListeningPausedPayload { until, ..paused("manual", None) };
+            }
+            None => {}
+        }
+    }
+    match *OVERRIDE.lock().unwrap() {
+        DndOverride::Pause => return paused("override", None),
+        DndOverride::Resume => return ListeningPausedPayload::default(),
+        DndOverride::Auto => {}
+    }
+
+    let config = load_config();
+    if !config.enabled {
+        return ListeningPausedPayload::default();
+    }
+    if config.system_dnd && crate::platform::system_dnd_active() == Some(true) {
+        return paused("dnd", None);
+    }
+    let frontmost = crate::platform::frontmost_app()
+        .and_then(|front| front.ids().into_iter().find_map(|id| matches_app(&config, id)));
+    if let Some(app) = frontmost {
+        return paused("app", Some(app));
+    }
+    let recording = crate::platform::microphone_apps()
+        .into_iter()
+        .find_map(|id| matches_app(&config, &id));
+    if let Some(app) = recording {
+        return paused("microphone", Some(app));
+    }
+    ListeningPausedPayload::default()
+}
+
+/// Re-evaluate the pause; on a change, stop the listening in progress and emit `listening-paused`
+fn refresh(state: ListeningPausedPayload) {
+    {
+        let mut last = STATE.lock().unwrap();
+        if last.as_ref() == Some(&state) {
+            return;
+        }
+        *last = Some(state.clone());
+    }
+    PAUSED.store(state.paused, Ordering::SeqCst);
+    println!(
+        "🔕 Listening {} ({})",
+        if state.paused { "paused" } else { "resumed" },
+        state.reason.as_deref().unwrap_or("-")
+    );
+    match APP_STATUS.current() {
+        AppStatus::Idle if state.paused => {
+            APP_STATUS.transition(AppStatus::Paused, "listening_paused");
+        }
+        AppStatus::Paused if !state.paused => {
+            APP_STATUS.transition(AppStatus::Idle, "listening_resumed");
+        }
+        _ => {}
+    }
+
+    // Stop waiting for speech; the next continuous recording sees the pause
+    if state.paused && APP_STATUS.current() == AppStatus::Listening {
+        if let Ok(mut daemon_guard) = DAEMON.lock() {
+            if let Some(ref mut daemon) = *daemon_guard {
+                let _ = daemon.send_command_no_wait("interrupt", serde_json::json!({"priority": 1}));
+            }
+        }
+    }
+    if let Some(app) = APP_HANDLE.get() {
+        // The tray offers "Pause" or "Resume"
+        let _ = crate::ui::update_tray_menu(app);
+        emit_event(app, AppEvent::ListeningPaused(state));
+    }
+}
+
+async fn check() {
+    match tauri::async_runtime::spawn_blocking(detect).await {
+        Ok(state) => refresh(state),
+        Err(e) => eprintln!("[FOCUS PAUSE] Check failed: {}", e),
+    }
+}
+
+/// Start the periodic pause check (once per app run)
+pub fn start_focus_monitor() {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check().await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Pause listening until `resume` or for `duration`
+pub async fn pause_for(duration: Option<Duration>) -> ListeningPausedPayload {
+    let until = duration.map(|d| chrono::Utc::now().timestamp_millis() + d.as_millis() as i64);
+    *MANUAL_PAUSE.lock().unwrap() = Some(ManualPause { until });
+    if let Some(duration) = duration {
+        // Resume on time instead of at the next periodic check
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(duration).await;
+            check().await;
+        });
+    }
+    check().await;
+    get_listening_paused()
+}
+
+/// End a manual or forced pause; automatic detection applies again
+pub async fn resume() -> ListeningPausedPayload {
+    *MANUAL_PAUSE.lock().unwrap() = None;
+    {
+        let mut mode = OVERRIDE.lock().unwrap();
+        if *mode == DndOverride::Pause {
+            *mode = DndOverride::Auto;
+        }
+    }
+    check().await;
+    get_listening_paused()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Current pause state
+#[tauri::command]
+pub fn get_listening_paused() -> ListeningPausedPayload {
+    STATE.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Force the pause on ("pause") or off ("resume"), or follow detection again ("auto")
+///
+/// Not saved: every app start begins with "auto".
+#[tauri::command]
+pub async fn set_dnd_override(mode: String) -> Result<ListeningPausedPayload, String> {
+    let mode = DndOverride::from_str(&mode)
+        .ok_or_else(|| format!("Invalid override: {} (expected auto, pause or resume)", mode))?;
+    *OVERRIDE.lock().unwrap() = mode;
+    check().await;
+    Ok(get_listening_paused())
+}
+
+/// Pause continuous listening, for `duration_secs` or until `resume_listening`
+#[tauri::command]
+pub async fn pause_listening(duration_secs: Option<u64>) -> Result<ListeningPausedPayload, String> {
+    if duration_secs == Some(0) {
+        return Err("Duration must be at least 1 second".to_string());
+    }
+    Ok(pause_for(duration_secs.map(Duration::from_secs)).await)
+}
+
+/// End a pause started with `pause_listening` (or a forced "pause" override)
+#[tauri::command]
+pub async fn resume_listening() -> Result<ListeningPausedPayload, String> {
+    Ok(resume().await)
+}
@@ -0,0 +1,196 @@
+// ============================================================================
+// HTTP Module - Shared Outbound HTTP Client
+// ============================================================================
+//
+// Every outbound request (provider connection tests, model lists, the native
+// LLM client) gets its reqwest client from here, so the `proxy` config is
+// honored everywhere: an http://, https:// or socks5:// URL, plus a
+// `no_proxy` list of hosts and domains reached directly (local Ollama by
+// default). The daemon receives http(s) proxies as the standard
+// HTTP(S)_PROXY / NO_PROXY variables when it is started (its httpx client has
+// no SOCKS support, so a socks5 proxy only covers requests made from Rust).
+//
+// Provider calls go through `send`, which allows a few concurrent requests
+// per provider and retries 429 / 5xx responses with exponential backoff (or
+// the server's Retry-After). Each failed attempt is reported to the frontend
+// as `provider-error`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::{self, ProxyConfig};
+use crate::daemon::APP_HANDLE;
+use crate::types::ProviderErrorPayload;
+use crate::events::{emit_event, AppEvent};
+
+/// Requests in flight per provider; more wait for a free slot
+const MAX_CONCURRENT_PER_PROVIDER: usize = 4;
+
+/// Retries after the first attempt (429 and 5xx only)
+const MAX_RETRIES: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Longest wait between attempts, also for a larger Retry-After
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Concurrency slots per provider name
+static PROVIDER_SLOTS: Mutex<Option<HashMap<String, Arc<Semaphore>>>> = Mutex::new(None);
+
+/// Proxy URL schemes reqwest can connect through
+const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+/// Parse a proxy URL, None if it is not a supported proxy
+pub fn parse_proxy_url(url: &str) -> Option<reqwest::Url> {
+    reqwest::Url::parse(url.trim())
+        .ok()
+        .filter(|url| PROXY_SCHEMES.contains(&url.scheme()) && url.host_str().is_some())
+}
+
+/// URL of the configured proxy, for clients not built here (the updater)
+pub fn proxy_url() -> Option<reqwest::Url> {
+    proxy_config().and_then(|proxy| parse_proxy_url(&proxy.url))
+}
+
+/// Configured proxy (None = direct connections)
+fn proxy_config() -> Option<ProxyConfig> {
+    config::read_config_value("proxy")
+        .and_then(|v| serde_json::from_value::<ProxyConfig>(v).ok())
+        .filter(|proxy| !proxy.url.trim().is_empty())
+}
+
+/// Client builder with the configured proxy applied (add timeouts, then build)
+pub fn client_builder() -> Result<reqwest::ClientBuilder, String> {
+    let builder = reqwest::Client::builder();
+    let Some(proxy) = proxy_config() else {
+        return Ok(builder);
+    };
+
+    let url = parse_proxy_url(&proxy.url).ok_or_else(|| format!("Invalid proxy URL: {}", proxy.url))?;
+    let no_proxy = reqwest::NoProxy::from_string(&proxy.no_proxy.join(","));
+    let proxy = reqwest::Proxy::all(url)
+        .map_err(|e| format!("Invalid proxy: {}", e))?
+        .no_proxy(no_proxy);
+    Ok(builder.proxy(proxy))
+}
+
+/// Client with the configured proxy and default settings
+pub fn client() -> Result<reqwest::Client, String> {
+    client_builder()?
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Pass an http(s) proxy to a child process through the standard environment variables
+pub fn apply_proxy_env(command: &mut tokio::process::Command) {
+    let Some(proxy) = proxy_config() else {
+        return;
+    };
+    let Some(url) = parse_proxy_url(&proxy.url).filter(|url| url.scheme().starts_with("http")) else {
+        return;
+    };
+    for name in ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"] {
+        command.env(name, url.as_str());
+    }
+    let no_proxy = proxy.no_proxy.join(",");
+    command.env("NO_PROXY", &no_proxy).env("no_proxy", &no_proxy);
+}
+
+// ============================================================================
+// Provider Requests
+// ============================================================================
+
+/// Successful provider response; keeps the provider's concurrency slot until dropped
+pub struct ProviderResponse {
+    pub response: reqwest::Response,
+    _slot: OwnedSemaphorePermit,
+}
+
+fn provider_slots(provider: &str) -> Arc<Semaphore> {
+    let mut slots = PROVIDER_SLOTS.lock().unwrap_or_else(|e| e.into_inner());
+    slots
+        .get_or_insert_with(HashMap::new)
+        .entry(provider.to_string())
+        .or_insert_with(|| Arc::new(Semaphore::new(MAX_CONCURRENT_PER_PROVIDER)))
+        .clone()
+}
+
+/// Delay requested by a Retry-After header (seconds or an HTTP date)
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let millis = (date.timestamp_millis() - chrono::Utc::now().timestamp_millis()).max(0);
+    Some(Duration::from_millis(millis as u64))
+}
+
+fn report_failure(payload: ProviderErrorPayload) {
+    eprintln!(
+        "[HTTP] {} request failed (retries: {}, retrying: {}): {}",
+        payload.provider, payload.retries, payload.retrying, payload.error
+    );
+    if let Some(app) = APP_HANDLE.get() {
+        emit_event(app, AppEvent::ProviderError(payload));
+    }
+}
+
+/// Send a request to an LLM/API provider, retrying rate limits and server errors
+///
+/// Non-success responses become `Err("API error: <status> - <body>")`. The
+/// request body must be cloneable (JSON bodies are).
+pub async fn send(provider: &str, request: reqwest::RequestBuilder) -> Result<ProviderResponse, String> {
+    let slot = provider_slots(provider)
+        .acquire_owned()
+        .await
+        .map_err(|_| format!("{} requests are shut down", provider))?;
+
+    let mut retries = 0;
+    loop {
+        let attempt = request
+            .try_clone()
+            .ok_or_else(|| "Request body cannot be retried".to_string())?;
+        let response = match attempt.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let error = format!("Failed to connect to {}: {}", provider, e);
+                report_failure(ProviderErrorPayload {
+                    provider: provider.to_string(),
+                    error: error.clone(),
+                    status: None,
+                    retries,
+                    retrying: false,
+                });
+                return Err(error);
+            }
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(ProviderResponse { response, _slot: slot });
+        }
+
+        let retryable = status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+        let retrying = retryable && retries < MAX_RETRIES;
+        let delay = retry_after(&response)
+            .unwrap_or(INITIAL_BACKOFF * 2u32.pow(retries))
+            .min(MAX_BACKOFF);
+        let body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+        let error = format!("API error: {} - {}", status, body.trim());
+        report_failure(ProviderErrorPayload {
+            provider: provider.to_string(),
+            error: error.clone(),
+            status: Some(status.as_u16()),
+            retries,
+            retrying,
+        });
+        if !retrying {
+            return Err(error);
+        }
+
+        tokio::time::sleep(delay).await;
+        retries += 1;
+    }
+}
@@ -0,0 +1,204 @@
+// ============================================================================
+// Instance Module - Running-App Lock and CLI Forwarding
+// ============================================================================
+//
+// The single-instance plugin stops a second app launch: it hands speekium://
+// links to the running app and shows its window. Headless CLI commands
+// (`speekium chat ...`, see cli) start before Tauri and used to spawn a
+// second daemon next to the app's, loading every model twice.
+//
+// The running app therefore holds an exclusive lock on `instance.lock` and
+// listens on a loopback port, written with a random token to `instance.json`.
+// A CLI invocation that finds the lock taken sends its arguments (one JSON
+// line) to that port, and the app runs the command with its own daemon and
+// answers with the result. If the app cannot be reached the CLI falls back
+// to its own daemon.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+
+use crate::config;
+
+const LOCK_FILE_NAME: &str = "instance.lock";
+const INFO_FILE_NAME: &str = "instance.json";
+
+/// How long a CLI invocation tries to reach the running app
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Lock held for the lifetime of the app
+static INSTANCE_LOCK: OnceLock<File> = OnceLock::new();
+
+/// Contents of instance.json
+#[derive(Debug, Serialize, Deserialize)]
+struct InstanceInfo {
+    pid: u32,
+    port: u16,
+    token: String,
+}
+
+/// A forwarded CLI invocation
+#[derive(Debug, Serialize, Deserialize)]
+struct ForwardRequest {
+    token: String,
+    args: Vec<String>,
+    /// Working directory of the CLI (relative file names are resolved against it)
+    cwd: PathBuf,
+}
+
+fn open_lock_file(dir: &Path) -> Result<File, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(LOCK_FILE_NAME))
+        .map_err(|e| format!("Failed to open instance lock: {}", e))
+}
+
+/// Write instance.json readable by the current user only
+fn write_info(path: &Path, info: &InstanceInfo) -> Result<(), String> {
+    let mut options = OpenOptions::new();
+    options.create(true).truncate(true).write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let content = serde_json::to_string(info).map_err(|e| e.to_string())?;
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// ============================================================================
+// App Side
+// ============================================================================
+
+/// Take the instance lock and accept forwarded CLI commands (call once at startup)
+pub fn start_listener() {
+    if let Err(e) = try_start_listener() {
+        eprintln!("[INSTANCE] CLI forwarding unavailable: {}", e);
+    }
+}
+
+fn try_start_listener() -> Result<(), String> {
+    let dir = config::get_app_data_dir()?;
+    let lock = open_lock_file(&dir)?;
+    lock.try_lock()
+        .map_err(|_| "another instance holds the instance lock".to_string())?;
+    let _ = INSTANCE_LOCK.set(lock);
+
+    let listener = std::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+        .map_err(|e| format!("Failed to bind control port: {}", e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to configure control port: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    write_info(
+        &dir.join(INFO_FILE_NAME),
+        &InstanceInfo { pid: std::process::id(), port, token: token.clone() },
+    )?;
+    println!("[INSTANCE] Accepting CLI commands on 127.0.0.1:{}", port);
+
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::from_std(listener) {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("[INSTANCE] Control port failed: {}", e);
+                return;
+            }
+        };
+        while let Ok((stream, _)) = listener.accept().await {
+            let token = token.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = handle_connection(stream, &token).await {
+                    eprintln!("[INSTANCE] Forwarded command failed: {}", e);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+async fn handle_connection(stream: tokio::net::TcpStream, token: &str) -> Result<(), String> {
+    let (reader, mut writer) = stream.into_split();
+    let mut line = String::new();
+    tokio::io::BufReader::new(reader)
+        .read_line(&mut line)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let request: ForwardRequest = serde_json::from_str(&line).map_err(|e| format!("Invalid request: {}", e))?;
+    let result = if request.token != token {
+        serde_json::json!({ "success": false, "error": "Invalid instance token" })
+    } else {
+        println!("[INSTANCE] Running forwarded command: {:?}", request.args.first());
+        tauri::async_runtime::spawn_blocking(move || crate::cli::run_forwarded(&request.args, &request.cwd))
+            .await
+            .unwrap_or_else(|e| serde_json::json!({ "success": false, "error": e.to_string() }))
+    };
+
+    let mut response = result.to_string();
+    response.push('\n');
+    writer.write_all(response.as_bytes()).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// CLI Side
+// ============================================================================
+
+/// Run CLI arguments in the running app, if there is one
+///
+/// Returns None when no app instance holds the lock or it cannot be reached.
+pub fn forward(args: &[String]) -> Option<serde_json::Value> {
+    let dir = config::get_app_data_dir().ok()?;
+    let lock = open_lock_file(&dir).ok()?;
+    if lock.try_lock().is_ok() {
+        // Nobody else holds it: no app running
+        return None;
+    }
+
+    let content = std::fs::read_to_string(dir.join(INFO_FILE_NAME)).ok()?;
+    let info: InstanceInfo = serde_json::from_str(&content).ok()?;
+    let request = ForwardRequest {
+        token: info.token,
+        args: args.to_vec(),
+        cwd: std::env::current_dir().unwrap_or_default(),
+    };
+
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, info.port));
+    let mut stream = match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("Running app (pid {}) not reachable, starting own voice service: {}", info.pid, e);
+            return None;
+        }
+    };
+    // Once connected the command may already be running: report failures instead of running it again
+    Some(exchange(&mut stream, &request).unwrap_or_else(|e| serde_json::json!({ "success": false, "error": e })))
+}
+
+fn exchange(stream: &mut TcpStream, request: &ForwardRequest) -> Result<serde_json::Value, String> {
+    let mut line = serde_json::to_string(request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    stream
+        .write_all(line.as_bytes())
+        .map_err(|e| format!("Failed to reach the running app: {}", e))?;
+
+    // No read timeout: the app may still be loading models
+    let mut response = String::new();
+    BufReader::new(stream)
+        .read_line(&mut response)
+        .map_err(|e| format!("No response from the running app: {}", e))?;
+    serde_json::from_str(&response).map_err(|e| format!("Invalid response from the running app: {}", e))
+}
@@ -0,0 +1,532 @@
+// ============================================================================
+// ITN Module - Inverse Text Normalization of Dictated Numbers and Dates
+// ============================================================================
+//
+// ASR output spells numbers out. This stage of the textproc pipeline writes
+// them the way they are usually typed:
+//
+//   twenty third of May           -> May 23 (en-US), 23 May (en-GB, en-IN)
+//   two hundred dollars           -> $200
+//   three point five percent      -> 3.5%
+//   twelve thousand five hundred  -> 12,500
+//   the twenty first century      -> the 21st century
+//
+// Only English number words are recognized. Numbers below ten stay words
+// ("one of them") unless a unit or a decimal follows. The `itn` config turns
+// the stage on per work mode (text-input only by default: the LLM reads
+// words just as well) and sets the locale of dates and digit grouping.
+
+use crate::config::ItnConfig;
+use crate::types::WorkMode;
+
+/// How dates and large numbers are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    /// May 23, 2024 - 1,250,000
+    UnitedStates,
+    /// 23 May 2024 - 1,250,000
+    UnitedKingdom,
+    /// 23 May 2024 - 12,50,000
+    India,
+}
+
+impl Locale {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "en-US" => Some(Locale::UnitedStates),
+            "en-GB" => Some(Locale::UnitedKingdom),
+            "en-IN" => Some(Locale::India),
+            _ => None,
+        }
+    }
+}
+
+/// Numbers from this value on get digit grouping (years stay 2024)
+const GROUPING_MIN: u64 = 10_000;
+
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+fn unit(word: &str) -> Option<u64> {
+    let units = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    units.iter().position(|u| *u == word).map(|n| n as u64)
+}
+
+fn teen(word: &str) -> Option<u64> {
+    let teens = [
+        "ten", "eleven", "twelve", "thirteen", "fourteen",
+        "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    ];
+    teens.iter().position(|t| *t == word).map(|n| n as u64 + 10)
+}
+
+fn tens(word: &str) -> Option<u64> {
+    let tens = ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+    tens.iter().position(|t| *t == word).map(|n| (n as u64 + 2) * 10)
+}
+
+fn scale(word: &str) -> Option<u64> {
+    match word {
+        "thousand" => Some(1_000),
+        "million" => Some(1_000_000),
+        "billion" => Some(1_000_000_000),
+        _ => None,
+    }
+}
+
+/// "first" .. "nineteenth", "twentieth" .. "ninetieth"
+fn ordinal_word(word: &str) -> Option<u64> {
+    let ordinals = [
+        "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+        "tenth", "eleventh", "twelfth", "thirteenth", "fourteenth", "fifteenth",
+        "sixteenth", "seventeenth", "eighteenth", "nineteenth",
+    ];
+    if let Some(n) = ordinals.iter().position(|o| *o == word) {
+        return Some(n as u64 + 1);
+    }
+    let tens = ["twentieth", "thirtieth", "fortieth", "fiftieth", "sixtieth", "seventieth", "eightieth", "ninetieth"];
+    tens.iter().position(|t| *t == word).map(|n| (n as u64 + 2) * 10)
+}
+
+fn month(word: &str) -> Option<usize> {
+    MONTHS.iter().position(|m| m.eq_ignore_ascii_case(word))
+}
+
+/// Symbol written before an amount of this currency
+fn currency(word: &str) -> Option<&'static str> {
+    match word {
+        "dollar" | "dollars" | "bucks" => Some("$"),
+        "euro" | "euros" => Some("€"),
+        "yen" | "yuan" => Some("¥"),
+        "rupee" | "rupees" => Some("₹"),
+        _ => None,
+    }
+}
+
+fn ordinal_suffix(n: u64) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// Digits of `n`, grouped by the locale from GROUPING_MIN on
+fn format_integer(n: u64, locale: Locale) -> String {
+    let digits = n.to_string();
+    if n < GROUPING_MIN {
+        return digits;
+    }
+
+    // Group sizes from the right: 3,3,3... or 3,2,2... (lakh / crore)
+    let mut groups = Vec::new();
+    let mut rest = digits.as_str();
+    let mut size = 3;
+    while rest.len() > size {
+        let (head, tail) = rest.split_at(rest.len() - size);
+        groups.push(tail);
+        rest = head;
+        if locale == Locale::India {
+            size = 2;
+        }
+    }
+    groups.push(rest);
+    groups.reverse();
+    groups.join(",")
+}
+
+/// A word of the transcript (ASCII letters) and where it is
+struct Word<'a> {
+    text: &'a str,
+    lower: String,
+    start: usize,
+    end: usize,
+}
+
+/// What the last word of a cardinal number was
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Last {
+    Start,
+    Zero,
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+/// A spoken number: integer part, optional decimal digits
+struct Number {
+    value: u64,
+    decimals: Option<String>,
+    /// Read as a year ("nineteen ninety nine")
+    year: bool,
+    next: usize,
+}
+
+struct Normalizer<'a> {
+    text: &'a str,
+    words: Vec<Word<'a>>,
+    locale: Locale,
+}
+
+impl<'a> Normalizer<'a> {
+    fn new(text: &'a str, locale: Locale) -> Self {
+        let mut words = Vec::new();
+        let mut start = None;
+        for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+            match (start, c.is_ascii_alphabetic()) {
+                (None, true) => start = Some(i),
+                (Some(s), false) => {
+                    words.push(Word { text: &text[s..i], lower: text[s..i].to_ascii_lowercase(), start: s, end: i });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        Normalizer { text, words, locale }
+    }
+
+    fn word(&self, i: usize) -> Option<&str> {
+        self.words.get(i).map(|w| w.lower.as_str())
+    }
+
+    /// Words `i` and `i + 1` belong to one phrase: only spaces or hyphens between them
+    fn connected(&self, i: usize) -> bool {
+        let (Some(a), Some(b)) = (self.words.get(i), self.words.get(i + 1)) else {
+            return false;
+        };
+        let gap = &self.text[a.end..b.start];
+        !gap.is_empty() && gap.chars().all(|c| c == ' ' || c == '-')
+    }
+
+    /// Next word is `word`, in the same phrase
+    fn followed_by(&self, i: usize, word: &str) -> bool {
+        self.connected(i) && self.word(i + 1) == Some(word)
+    }
+
+    /// Cardinal number starting at word `i`; returns it, its last word kind and the next index
+    fn cardinal(&self, i: usize) -> Option<(u64, Last, usize)> {
+        let mut total = 0;
+        let mut chunk = 0;
+        let mut last = Last::Start;
+        let mut last_scale = u64::MAX;
+        let mut end = i;
+        let mut j = i;
+
+        while let Some(word) = self.word(j) {
+            if j > i && !self.connected(j - 1) {
+                break;
+            }
+            let starts_number = |k: usize| {
+                self.word(k)
+                    .is_some_and(|w| unit(w).is_some_and(|n| n > 0) || teen(w).is_some() || tens(w).is_some())
+            };
+
+            if let Some(n) = unit(word) {
+                if n == 0 {
+                    // "zero" is a number on its own only
+                    if last == Last::Start {
+                        last = Last::Zero;
+                        end = j + 1;
+                    }
+                    break;
+                }
+                if !matches!(last, Last::Start | Last::Tens | Last::Hundred | Last::Scale) {
+                    break;
+                }
+                chunk += n;
+                last = Last::Unit;
+            } else if let Some(n) = teen(word).or_else(|| tens(word)) {
+                if !matches!(last, Last::Start | Last::Hundred | Last::Scale) {
+                    break;
+                }
+                chunk += n;
+                last = if n < 20 { Last::Teen } else { Last::Tens };
+            } else if word == "hundred" {
+                if !matches!(last, Last::Unit | Last::Teen | Last::Tens) || chunk >= 100 {
+                    break;
+                }
+                chunk *= 100;
+                last = Last::Hundred;
+            } else if let Some(s) = scale(word) {
+                if !matches!(last, Last::Unit | Last::Teen | Last::Tens | Last::Hundred) || s >= last_scale {
+                    break;
+                }
+                total += chunk * s;
+                chunk = 0;
+                last_scale = s;
+                last = Last::Scale;
+            } else if word == "a" && last == Last::Start && self.connected(j)
+                && self.word(j + 1).is_some_and(|w| w == "hundred" || scale(w).is_some())
+            {
+                // "a hundred", "a thousand"
+                chunk = 1;
+                last = Last::Unit;
+            } else if word == "and" && matches!(last, Last::Hundred | Last::Scale) && self.connected(j) && starts_number(j + 1) {
+                // "two hundred and five": not the end of the number
+                j += 1;
+                continue;
+            } else {
+                break;
+            }
+            j += 1;
+            end = j;
+        }
+
+        (end > i).then_some((total + chunk, last, end))
+    }
+
+    /// Number starting at word `i`: a cardinal, a year said in pairs or a decimal
+    fn number(&self, i: usize) -> Option<Number> {
+        let (value, last, next) = self.cardinal(i)?;
+
+        // "nineteen ninety nine", "twenty twenty four"
+        let two_digits = |value: u64, last: Last| (10..100).contains(&value) && matches!(last, Last::Teen | Last::Tens | Last::Unit);
+        if two_digits(value, last) && self.connected(next - 1) {
+            if let Some((low, low_last, low_next)) = self.cardinal(next) {
+                if two_digits(low, low_last) {
+                    return Some(Number { value: value * 100 + low, decimals: None, year: true, next: low_next });
+                }
+            }
+        }
+
+        // "three point one four"
+        if self.followed_by(next - 1, "point") && self.connected(next) {
+            let mut digits = String::new();
+            let mut j = next + 1;
+            while let Some(d) = self.word(j).and_then(unit) {
+                if j > next + 1 && !self.connected(j - 1) {
+                    break;
+                }
+                digits.push_str(&d.to_string());
+                j += 1;
+            }
+            if !digits.is_empty() {
+                return Some(Number { value, decimals: Some(digits), year: false, next: j });
+            }
+        }
+
+        Some(Number { value, decimals: None, year: false, next })
+    }
+
+    /// Ordinal up to 99 starting at word `i` ("third", "twenty third"), and the next index
+    fn ordinal(&self, i: usize) -> Option<(u64, usize)> {
+        let word = self.word(i)?;
+        if let Some(n) = ordinal_word(word) {
+            return Some((n, i + 1));
+        }
+        let tens = tens(word)?;
+        let n = self.word(i + 1).and_then(ordinal_word).filter(|n| *n < 10 && self.connected(i))?;
+        Some((tens + n, i + 2))
+    }
+
+    fn format_number(&self, number: &Number) -> String {
+        let integer = if number.year { number.value.to_string() } else { format_integer(number.value, self.locale) };
+        match &number.decimals {
+            Some(decimals) => format!("{}.{}", integer, decimals),
+            None => integer,
+        }
+    }
+
+    /// Year after a date at word `i`, e.g. ", twenty twenty four"
+    fn year(&self, i: usize) -> Option<(u64, usize)> {
+        let prev = self.words.get(i.checked_sub(1)?)?;
+        let word = self.words.get(i)?;
+        let gap = &self.text[prev.end..word.start];
+        if !matches!(gap, " " | ", ") {
+            return None;
+        }
+        let number = self.number(i).filter(|n| n.decimals.is_none() && (1000..3000).contains(&n.value))?;
+        Some((number.value, number.next))
+    }
+
+    fn format_date(&self, month: usize, day: u64, year: Option<u64>) -> String {
+        let month = MONTHS[month];
+        match (self.locale, year) {
+            (Locale::UnitedStates, Some(year)) => format!("{} {}, {}", month, day, year),
+            (Locale::UnitedStates, None) => format!("{} {}", month, day),
+            (_, Some(year)) => format!("{} {} {}", day, month, year),
+            (_, None) => format!("{} {}", day, month),
+        }
+    }
+
+    /// "[the] twenty third [of] May", "May [the] twenty third", "May twenty three"
+    fn date(&self, i: usize) -> Option<(String, usize)> {
+        let (month, day, next) = self.day_month(i).or_else(|| self.month_day(i))?;
+        if !(1..=31).contains(&day) {
+            return None;
+        }
+        let (year, next) = match self.year(next) {
+            Some((year, after)) => (Some(year), after),
+            None => (None, next),
+        };
+        Some((self.format_date(month, day, year), next))
+    }
+
+    fn day_month(&self, i: usize) -> Option<(usize, u64, usize)> {
+        let start = if self.word(i) == Some("the") && self.connected(i) { i + 1 } else { i };
+        let (day, mut j) = self.ordinal(start)?;
+        let after_of = self.followed_by(j - 1, "of");
+        if after_of {
+            j += 1;
+        }
+        if !self.connected(j - 1) {
+            return None;
+        }
+        let month = self.month_at(j, after_of)?;
+        Some((month, day, j + 1))
+    }
+
+    fn month_day(&self, i: usize) -> Option<(usize, u64, usize)> {
+        let month = self.month_at(i, false)?;
+        let mut j = i + 1;
+        if self.followed_by(i, "the") {
+            j += 1;
+        }
+        if !self.connected(j - 1) {
+            return None;
+        }
+        if let Some((day, next)) = self.ordinal(j) {
+            return Some((month, day, next));
+        }
+        let (day, last, next) = self.cardinal(j)?;
+        (last != Last::Zero).then_some((month, day, next))
+    }
+
+    /// Month name at word `i`; "may" and "march" must be capitalized unless they follow "of"
+    fn month_at(&self, i: usize, after_of: bool) -> Option<usize> {
+        let word = self.words.get(i)?;
+        let month = month(&word.lower)?;
+        let ambiguous = matches!(word.lower.as_str(), "may" | "march");
+        let capitalized = word.text.starts_with(|c: char| c.is_ascii_uppercase());
+        (!ambiguous || after_of || capitalized).then_some(month)
+    }
+
+    /// A number with its unit, or a number of ten or more
+    fn quantity(&self, i: usize) -> Option<(String, usize)> {
+        let number = self.number(i)?;
+        let next = number.next;
+
+        if self.followed_by(next - 1, "percent") {
+            return Some((format!("{}%", self.format_number(&number)), next + 1));
+        }
+        if self.followed_by(next - 1, "per") && self.followed_by(next, "cent") {
+            return Some((format!("{}%", self.format_number(&number)), next + 2));
+        }
+        if let Some(symbol) = self.connected(next - 1).then(|| self.word(next).and_then(currency)).flatten() {
+            let mut amount = self.format_number(&number);
+            let mut end = next + 1;
+            // "two dollars and fifty cents"
+            if number.decimals.is_none() && self.followed_by(next, "and") && self.connected(next + 1) {
+                if let Some((cents, _, after)) = self.cardinal(next + 2) {
+                    if (1..100).contains(&cents) && self.followed_by(after - 1, "cents") {
+                        amount = format!("{}.{:02}", amount, cents);
+                        end = after + 1;
+                    }
+                }
+            }
+            return Some((format!("{}{}", symbol, amount), end));
+        }
+
+        (number.year || number.decimals.is_some() || number.value >= 10).then(|| (self.format_number(&number), next))
+    }
+
+    /// "twenty first" -> "21st" (ordinals below ten stay words)
+    fn ordinal_text(&self, i: usize) -> Option<(String, usize)> {
+        let (n, next) = self.ordinal(i).filter(|(n, _)| *n >= 10)?;
+        Some((format!("{}{}", n, ordinal_suffix(n)), next))
+    }
+
+    fn normalize(&self) -> String {
+        let mut result = String::with_capacity(self.text.len());
+        let mut copied = 0;
+        let mut i = 0;
+        while i < self.words.len() {
+            let found = self.date(i)
+                .or_else(|| self.ordinal_text(i))
+                .or_else(|| self.quantity(i));
+            match found {
+                Some((replacement, next)) => {
+                    result.push_str(&self.text[copied..self.words[i].start]);
+                    result.push_str(&replacement);
+                    copied = self.words[next - 1].end;
+                    i = next;
+                }
+                None => i += 1,
+            }
+        }
+        result.push_str(&self.text[copied..]);
+        result
+    }
+}
+
+/// Write spoken numbers, amounts and dates in `text` with digits
+pub fn normalize(text: &str, locale: Locale) -> String {
+    Normalizer::new(text, locale).normalize()
+}
+
+/// The textproc stage: normalize if the `itn` config enables it for the current work mode
+pub fn process(text: &str) -> String {
+    let config: ItnConfig = crate::config::read_config_value("itn")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let enabled = match *crate::daemon::WORK_MODE.lock().unwrap() {
+        WorkMode::Conversation => config.conversation,
+        WorkMode::TextInput => config.text_input,
+    };
+    if !enabled {
+        return text.to_string();
+    }
+    normalize(text, Locale::from_str(&config.locale).unwrap_or(Locale::UnitedStates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn us(text: &str) -> String {
+        normalize(text, Locale::UnitedStates)
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(us("I have one question"), "I have one question");
+        assert_eq!(us("Twenty three people came."), "23 people came.");
+        assert_eq!(us("twenty-three"), "23");
+        assert_eq!(us("two hundred and five"), "205");
+        assert_eq!(us("a hundred"), "100");
+        assert_eq!(us("twelve thousand five hundred"), "12,500");
+        assert_eq!(us("one million two hundred thousand"), "1,200,000");
+        assert_eq!(us("twelve hundred"), "1200");
+        assert_eq!(us("three point one four"), "3.14");
+        assert_eq!(us("nineteen ninety nine"), "1999");
+        assert_eq!(us("five, six"), "five, six");
+        assert_eq!(normalize("twelve hundred thousand", Locale::India), "12,00,000");
+    }
+
+    #[test]
+    fn test_units() {
+        assert_eq!(us("It costs two hundred dollars."), "It costs $200.");
+        assert_eq!(us("five dollars and fifty cents"), "$5.50");
+        assert_eq!(us("three euros"), "€3");
+        assert_eq!(us("fifty percent"), "50%");
+        assert_eq!(us("two point five per cent"), "2.5%");
+    }
+
+    #[test]
+    fn test_ordinals_and_dates() {
+        assert_eq!(us("the twenty first century"), "the 21st century");
+        assert_eq!(us("the first time"), "the first time");
+        assert_eq!(us("on the twenty third of May"), "on May 23");
+        assert_eq!(us("May twenty third"), "May 23");
+        assert_eq!(us("June the first, twenty twenty four"), "June 1, 2024");
+        assert_eq!(us("you may first check"), "you may first check");
+        assert_eq!(normalize("the twenty third of may", Locale::UnitedKingdom), "23 May");
+        assert_eq!(normalize("March third two thousand and one", Locale::UnitedKingdom), "3 March 2001");
+    }
+}
@@ -1,20 +1,78 @@
+#![recursion_limit = "256"]
+
 // Module declarations
 mod database;
 mod audio;
+mod audio_archive;
+mod audio_stream;
+mod autostart;
+mod config;
 mod types;
+mod updater;
 mod state;
+mod status;
 mod platform;
 mod ui;
 mod ptt;
 mod daemon;
 mod api;
+mod api_server;
+mod asr_alternatives;
+mod asr_language;
 mod shortcuts;
+mod shortcut_apps;
+mod chat_draft;
 mod commands;
 mod db_commands;
+mod db_encryption;
+mod deep_link;
+mod export;
+mod file_transcription;
+mod focus_pause;
+mod http;
+mod instance;
+mod itn;
+mod diagnostics;
+mod diarization;
+mod dictation;
+mod dsp;
+mod earcons;
+mod events;
+mod llm;
+mod loopback;
+mod meeting;
+mod metrics;
+mod network;
+mod permissions;
+mod pipeline;
+mod profiles;
+mod prompt_templates;
+mod personas;
+mod providers;
+mod recording_journal;
+mod reminders;
+mod retention;
+mod secrets;
+mod selection;
+mod self_test;
+mod speech_text;
+mod storage;
+mod text_commands;
+mod textproc;
+mod transcriptions;
+mod tts_cache;
+mod tts_export;
+mod tts_voices;
+mod usage_stats;
+mod vocabulary;
+mod word_timings;
 mod app;
+mod cli;
 
 // Public API
 pub use app::run;
+pub use cli::run_from_args as run_cli;
+pub use daemon::run_mock_daemon;
 
 // Re-export daemon globals for use in other modules
 pub use daemon::{
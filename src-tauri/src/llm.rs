@@ -0,0 +1,253 @@
+// ============================================================================
+// LLM Module - Native Streaming Chat Client
+// ============================================================================
+//
+// With `llm_backend: "native"`, text chats go straight from Rust to the
+// configured provider instead of through the Python daemon. Ollama is read
+// from its NDJSON `/api/chat` stream; every other provider is treated as
+// OpenAI-compatible (`/chat/completions` with server-sent events). Like the
+// daemon's backends, the client keeps the recent conversation in memory;
+// session chats pass the session's messages instead and leave it untouched.
+//
+// Voice replies (chat_tts_stream, PTT) still go through the daemon, which
+// synthesizes speech sentence by sentence as the reply streams in.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::config::{self, LlmProviderConfig};
+use crate::daemon::request::{CancelToken, STREAM_IDLE_TIMEOUT};
+use crate::types::ChatResult;
+
+const LLM_BACKEND_KEY: &str = "llm_backend";
+const NATIVE_BACKEND: &str = "native";
+
+/// Used when neither a persona nor `system_prompt` is set (keep in sync with default_config)
+const DEFAULT_SYSTEM_PROMPT: &str = "你是一个有帮助的语音助手。";
+const DEFAULT_MAX_HISTORY_TURNS: u64 = 10;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Previous user/assistant messages, oldest first
+static HISTORY: Mutex<Vec<serde_json::Value>> = Mutex::new(Vec::new());
+
+/// How a provider streams its reply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamFormat {
+    /// Ollama `/api/chat`: one JSON object per line
+    Ollama,
+    /// OpenAI-compatible `/chat/completions`: `data: {...}` lines, ended by `data: [DONE]`
+    OpenAi,
+}
+
+/// How a streamed reply ended
+pub enum StreamOutcome {
+    /// Complete reply
+    Done(String),
+    /// Cancelled by an interrupt; holds the text received until then
+    Cancelled(String),
+}
+
+/// Whether chats bypass the daemon (`llm_backend: "native"`)
+pub fn native_enabled() -> bool {
+    config::read_config_value(LLM_BACKEND_KEY).and_then(|v| v.as_str().map(str::to_string)).as_deref()
+        == Some(NATIVE_BACKEND)
+}
+
+/// The `llm_providers` entry selected by `llm_provider`
+fn current_provider() -> Result<LlmProviderConfig, String> {
+    let config = config::load_config_with_defaults();
+    let name = config.get("llm_provider").and_then(|v| v.as_str()).unwrap_or("ollama");
+    let providers: Vec<LlmProviderConfig> = config
+        .get("llm_providers")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    let provider = providers
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("LLM provider '{}' is not configured", name))?;
+    let provider = LlmProviderConfig {
+        api_key: crate::secrets::api_key_for(&provider),
+        ..provider
+    };
+    if provider.base_url.trim().is_empty() || provider.model.trim().is_empty() {
+        return Err(format!("LLM provider '{}' needs a base URL and a model", name));
+    }
+    Ok(provider)
+}
+
+/// System prompt, prior turns and the new message
+fn build_messages<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    history: &[serde_json::Value],
+    text: &str,
+) -> Vec<serde_json::Value> {
+    let system_prompt = crate::personas::active_persona(app)
+        .map(|persona| persona.system_prompt)
+        .or_else(|| config::read_config_value("system_prompt").and_then(|v| v.as_str().map(str::to_string)))
+        .filter(|prompt| !prompt.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_SYSTEM_PROMPT.to_string());
+
+    let mut messages = vec![serde_json::json!({ "role": "system", "content": system_prompt })];
+    messages.extend(history.iter().cloned());
+    messages.push(serde_json::json!({ "role": "user", "content": text }));
+    messages
+}
+
+/// Remember a finished exchange, keeping the last `max_history` turns
+fn remember_exchange(text: &str, reply: &str) {
+    let max_turns = config::read_config_value("max_history")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_MAX_HISTORY_TURNS) as usize;
+    if let Ok(mut history) = HISTORY.lock() {
+        history.push(serde_json::json!({ "role": "user", "content": text }));
+        history.push(serde_json::json!({ "role": "assistant", "content": reply }));
+        // Each turn is a user message plus an assistant reply
+        let excess = history.len().saturating_sub(max_turns * 2);
+        history.drain(..excess);
+    }
+}
+
+/// Text of one line of the stream (None for keep-alives, metadata and the end marker)
+fn parse_line(format: StreamFormat, line: &str) -> Result<Option<String>, String> {
+    let json = match format {
+        StreamFormat::Ollama => line,
+        StreamFormat::OpenAi => match line.strip_prefix("data:").map(str::trim) {
+            Some("[DONE]") | None => return Ok(None),
+            Some(data) => data,
+        },
+    };
+    if json.is_empty() {
+        return Ok(None);
+    }
+
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid stream data: {}", e))?;
+    if let Some(error) = value.get("error") {
+        let message = error.get("message").and_then(|v| v.as_str()).or(error.as_str()).unwrap_or("Unknown error");
+        return Err(format!("LLM error: {}", message));
+    }
+    let content = match format {
+        StreamFormat::Ollama => value.pointer("/message/content"),
+        StreamFormat::OpenAi => value.pointer("/choices/0/delta/content"),
+    };
+    Ok(content.and_then(|v| v.as_str()).filter(|s| !s.is_empty()).map(str::to_string))
+}
+
+/// Stream a reply to `text`, calling `on_chunk` with each piece of text
+///
+/// The exchange is added to the conversation history once the reply is complete.
+pub async fn chat_stream<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    text: &str,
+    cancel: &CancelToken,
+    on_chunk: impl FnMut(&str),
+) -> Result<StreamOutcome, String> {
+    let history = HISTORY.lock().map(|history| history.clone()).unwrap_or_default();
+    let outcome = stream_reply(build_messages(app, &history, text), cancel, on_chunk).await?;
+    if let StreamOutcome::Done(reply) = &outcome {
+        remember_exchange(text, reply);
+    }
+    Ok(outcome)
+}
+
+/// Send `messages` to the current provider and stream the reply
+async fn stream_reply(
+    messages: Vec<serde_json::Value>,
+    cancel: &CancelToken,
+    mut on_chunk: impl FnMut(&str),
+) -> Result<StreamOutcome, String> {
+    let provider = current_provider()?;
+    crate::network::ensure_online(&provider.name, &provider.base_url)?;
+    let base_url = provider.base_url.trim_end_matches('/');
+    let (format, url) = if provider.name == "ollama" {
+        (StreamFormat::Ollama, format!("{}/api/chat", base_url))
+    } else {
+        (StreamFormat::OpenAi, format!("{}/chat/completions", base_url))
+    };
+
+    let client = crate::http::client_builder()?
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "model": provider.model,
+        "messages": messages,
+        "stream": true,
+    }));
+    if !provider.api_key.is_empty() {
+        request = request.bearer_auth(&provider.api_key);
+    }
+
+    // Holds one of the provider's request slots until the stream is done
+    let mut sent = crate::http::send(&provider.name, request).await?;
+    let response = &mut sent.response;
+
+    let mut reply = String::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut finished = false;
+    while !finished {
+        let received = tokio::select! {
+            received = tokio::time::timeout(STREAM_IDLE_TIMEOUT, response.chunk()) => received,
+            _ = cancel.cancelled() => return Ok(StreamOutcome::Cancelled(reply)),
+        };
+        match received {
+            Err(_) => return Err(format!("{} stopped responding", provider.name)),
+            Ok(Err(e)) => return Err(format!("Read error: {}", e)),
+            Ok(Ok(Some(bytes))) => buffer.extend_from_slice(&bytes),
+            // Connection closed: the rest of the buffer is the last line
+            Ok(Ok(None)) => {
+                buffer.push(b'\n');
+                finished = true;
+            }
+        }
+
+        // Lines can be split across chunks; only parse complete ones
+        while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            if let Some(content) = parse_line(format, String::from_utf8_lossy(&line).trim())? {
+                on_chunk(&content);
+                reply.push_str(&content);
+            }
+        }
+    }
+
+    Ok(StreamOutcome::Done(reply))
+}
+
+/// The whole reply of a finished stream
+fn chat_result(outcome: Result<StreamOutcome, String>) -> ChatResult {
+    match outcome {
+        Ok(StreamOutcome::Done(content)) | Ok(StreamOutcome::Cancelled(content)) => ChatResult {
+            success: true,
+            content: Some(content),
+            error: None,
+        },
+        Err(e) => ChatResult {
+            success: false,
+            content: None,
+            error: Some(e),
+        },
+    }
+}
+
+/// Non-streaming chat (the whole reply at once)
+pub async fn chat<R: tauri::Runtime>(app: &tauri::AppHandle<R>, text: &str) -> ChatResult {
+    chat_result(chat_stream(app, text, &CancelToken::new(), |_| {}).await)
+}
+
+/// Non-streaming chat with `history` ({"role", "content"} messages, oldest
+/// first) as the prior turns instead of the conversation history, which is
+/// left untouched (e.g. a saved session)
+pub async fn chat_with_history<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    text: &str,
+    history: &[serde_json::Value],
+) -> ChatResult {
+    chat_result(stream_reply(build_messages(app, history, text), &CancelToken::new(), |_| {}).await)
+}
+
+/// Non-streaming chat without any prior turns (e.g. the self-test)
+pub async fn chat_detached<R: tauri::Runtime>(app: &tauri::AppHandle<R>, text: &str) -> ChatResult {
+    chat_with_history(app, text, &[]).await
+}
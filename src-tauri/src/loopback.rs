@@ -0,0 +1,114 @@
+// ============================================================================
+// Loopback Capture Module - Recording System Audio Output
+// ============================================================================
+//
+// cpal records what the system plays when an input stream is opened on an
+// output device: WASAPI loopback on Windows, and a Core Audio process tap
+// (an aggregate device cpal creates and removes) on macOS 14.6 and later.
+// Older macOS versions and Linux fail to open the stream, which is reported
+// to the caller.
+//
+// A capture source runs on the cpal callback thread and appends 16 kHz mono
+// samples to a shared buffer; the owner drains the buffer at its own pace.
+// Streams are not Send, so they stay on the thread that opened them.
+
+use std::sync::{Arc, Mutex};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample};
+
+use crate::audio::{device_name, find_suitable_config, process_audio_data, resolve_input_device};
+
+/// Running capture stream; capturing stops when it is dropped
+pub struct CaptureSource {
+    _stream: cpal::Stream,
+    /// Device name, for logs and errors
+    pub name: String,
+}
+
+/// Samples captured so far (16kHz mono), shared with the callback
+pub type CaptureBuffer = Arc<Mutex<Vec<f32>>>;
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    buffer: CaptureBuffer,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let sample_rate = config.sample_rate;
+    let channels = config.channels;
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let samples: Vec<f32> = data.iter().map(|&s| s.to_sample::<f32>()).collect();
+            let processed = process_audio_data(&samples, sample_rate, channels);
+            if let Ok(mut buf) = buffer.lock() {
+                buf.extend_from_slice(&processed);
+            }
+        },
+        |err| eprintln!("[LOOPBACK] Stream error: {}", err),
+        None,
+    )
+}
+
+fn open(
+    device: cpal::Device,
+    config: cpal::SupportedStreamConfig,
+    buffer: CaptureBuffer,
+) -> Result<CaptureSource, String> {
+    let name = device_name(&device);
+    let stream_config = config.config();
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => build_stream::<f32>(&device, &stream_config, buffer),
+        cpal::SampleFormat::I16 => build_stream::<i16>(&device, &stream_config, buffer),
+        cpal::SampleFormat::I32 => build_stream::<i32>(&device, &stream_config, buffer),
+        cpal::SampleFormat::U16 => build_stream::<u16>(&device, &stream_config, buffer),
+        format => return Err(format!("{}: unsupported sample format {:?}", name, format)),
+    }
+    .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+
+    stream.play().map_err(|e| format!("Failed to start {}: {}", name, e))?;
+    Ok(CaptureSource { _stream: stream, name })
+}
+
+/// Capture what the default output device plays
+pub fn open_system_output(buffer: CaptureBuffer) -> Result<CaptureSource, String> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "No output device available".to_string())?;
+    // Loopback runs at the device's mix format
+    let config = device
+        .default_output_config()
+        .map_err(|e| format!("Failed to get output config: {}", e))?;
+    open(device, config, buffer)
+}
+
+/// Capture the selected (or default) microphone
+pub fn open_microphone(device_id: Option<&str>, buffer: CaptureBuffer) -> Result<CaptureSource, String> {
+    let device = resolve_input_device(&cpal::default_host(), device_id)?;
+    let config = find_suitable_config(&device)?;
+    open(device, config, buffer)
+}
+
+/// Take the samples of all buffers and mix them into one track
+///
+/// Sources drift slightly apart; the shorter one is padded with silence.
+pub fn drain_mixed(buffers: &[CaptureBuffer]) -> Vec<f32> {
+    let tracks: Vec<Vec<f32>> = buffers
+        .iter()
+        .map(|buffer| buffer.lock().map(|mut buf| std::mem::take(&mut *buf)).unwrap_or_default())
+        .collect();
+    let len = tracks.iter().map(Vec::len).max().unwrap_or(0);
+    (0..len)
+        .map(|i| {
+            tracks
+                .iter()
+                .filter_map(|track| track.get(i))
+                .sum::<f32>()
+                .clamp(-1.0, 1.0)
+        })
+        .collect()
+}
@@ -2,5 +2,13 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // Relaunched as the mock daemon (SPEEKIUM_MOCK_DAEMON=1)
+    if let Some(code) = speekium_lib::run_mock_daemon() {
+        std::process::exit(code);
+    }
+    // `speekium transcribe|tts|chat ...` runs headlessly and exits
+    if let Some(code) = speekium_lib::run_cli() {
+        std::process::exit(code);
+    }
     speekium_lib::run()
 }
@@ -0,0 +1,296 @@
+// ============================================================================
+// Meeting Module - Chunked Transcription of System Audio
+// ============================================================================
+//
+// The `meeting` recording mode turns Speekium into a note taker: what the
+// computer plays (the other participants, see loopback.rs) is recorded
+// together with the microphone (`meeting_capture_mic`) and transcribed every
+// `meeting_chunk_secs` into a session of its own, "Meeting <date>".
+//
+// A capture thread owns the streams and hands each chunk to one async worker,
+// so chunks are transcribed in order while the next one is recorded. Silent
+// chunks are skipped. Every transcript is saved as a message of the session
+// (with its speaker segments, when the model diarizes) and emitted as
+// `meeting-transcript`; `meeting-state` reports start and end.
+//
+// Switching to another recording mode ends the meeting after its last chunk
+// is transcribed. A meeting is never resumed when the app starts.
+
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use tauri::{Manager};
+
+use crate::daemon::{call_daemon_async, APP_HANDLE};
+use crate::loopback::{self, CaptureBuffer};
+use crate::state::AppState;
+use crate::types::{MeetingStatus, RecordingMode, SpeakerSegment};
+use crate::events::{emit_event, AppEvent};
+
+const DEFAULT_CHUNK_SECS: u64 = 30;
+/// Shortest chunk length accepted from the config
+const MIN_CHUNK_SECS: u64 = 5;
+const SAMPLE_RATE: u64 = 16000;
+
+/// RMS level below which a chunk is not sent to ASR
+const SILENCE_RMS_THRESHOLD: f32 = 0.005;
+
+/// Time to wait for the capture streams to open
+const START_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Recorded chunk waiting for ASR
+struct MeetingChunk {
+    audio_path: String,
+    /// Start of the chunk, in ms from the start of the meeting
+    offset_ms: u64,
+}
+
+struct ActiveMeeting {
+    session_id: String,
+    started_at: i64,
+    stop_tx: Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+static MEETING: Mutex<Option<ActiveMeeting>> = Mutex::new(None);
+
+fn chunk_duration() -> Duration {
+    let secs = crate::config::read_config_value("meeting_chunk_secs")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_CHUNK_SECS);
+    Duration::from_secs(secs.max(MIN_CHUNK_SECS))
+}
+
+fn capture_mic() -> bool {
+    crate::config::read_config_value("meeting_capture_mic")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+fn is_silent(samples: &[f32]) -> bool {
+    samples.is_empty()
+        || (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt() < SILENCE_RMS_THRESHOLD
+}
+
+/// Start or stop the meeting to match a new recording mode
+pub fn apply_recording_mode(mode: RecordingMode) -> Result<(), String> {
+    if mode == RecordingMode::Meeting {
+        let app = APP_HANDLE.get().ok_or_else(|| "App is not ready".to_string())?;
+        start(app).map(|_| ())
+    } else {
+        stop();
+        Ok(())
+    }
+}
+
+/// Open the capture streams and start transcribing into a new session
+fn start(app: &tauri::AppHandle) -> Result<MeetingStatus, String> {
+    let mut meeting = MEETING.lock().map_err(|e| e.to_string())?;
+    if let Some(active) = meeting.as_ref() {
+        return Ok(status_of(Some(active)));
+    }
+    let state = app.try_state::<AppState>().ok_or_else(|| "Database is not ready".to_string())?;
+
+    let (stop_tx, stop_rx) = channel();
+    let (started_tx, started_rx) = channel();
+    let (chunk_tx, chunk_rx) = tokio::sync::mpsc::unbounded_channel();
+    let chunk = chunk_duration();
+    let with_mic = capture_mic();
+    let device_id = crate::config::read_config_value(crate::audio::AUDIO_INPUT_DEVICE_KEY)
+        .and_then(|v| v.as_str().map(String::from));
+
+    let thread = thread::spawn(move || {
+        run_capture(stop_rx, chunk_tx, chunk, with_mic, device_id.as_deref(), &started_tx);
+    });
+    match started_rx.recv_timeout(START_TIMEOUT) {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            let _ = thread.join();
+            return Err(e);
+        }
+        Err(_) => {
+            let _ = stop_tx.send(());
+            return Err("Timed out opening the system audio".to_string());
+        }
+    }
+
+    let title = format!("Meeting {}", chrono::Local::now().format("%Y-%m-%d %H:%M"));
+    let session = match state.db.create_session(title) {
+        Ok(session) => session,
+        Err(e) => {
+            let _ = stop_tx.send(());
+            let _ = thread.join();
+            return Err(e);
+        }
+    };
+    println!("[MEETING] Started, transcribing into session {}", session.id);
+
+    let active = ActiveMeeting {
+        session_id: session.id.clone(),
+        started_at: chrono::Utc::now().timestamp_millis(),
+        stop_tx,
+        thread,
+    };
+    let status = status_of(Some(&active));
+    *meeting = Some(active);
+    drop(meeting);
+
+    tauri::async_runtime::spawn(transcribe_chunks(app.clone(), session.id, chunk_rx));
+    emit_event(app, AppEvent::MeetingState(status.clone()));
+    Ok(status)
+}
+
+/// Stop capturing; the last chunk is still transcribed
+fn stop() {
+    let active = MEETING.lock().ok().and_then(|mut meeting| meeting.take());
+    if let Some(active) = active {
+        let _ = active.stop_tx.send(());
+        let _ = active.thread.join();
+        println!("[MEETING] Capture stopped");
+    }
+}
+
+fn status_of(active: Option<&ActiveMeeting>) -> MeetingStatus {
+    MeetingStatus {
+        active: active.is_some(),
+        session_id: active.map(|a| a.session_id.clone()),
+        started_at: active.map(|a| a.started_at),
+    }
+}
+
+/// Capture thread: record until stopped, handing over a chunk every `chunk`
+///
+/// The system audio is required; the microphone is optional and skipped if
+/// it cannot be opened.
+fn run_capture(
+    stop_rx: Receiver<()>,
+    chunk_tx: tokio::sync::mpsc::UnboundedSender<MeetingChunk>,
+    chunk: Duration,
+    with_mic: bool,
+    device_id: Option<&str>,
+    started: &Sender<Result<(), String>>,
+) {
+    let system: CaptureBuffer = Arc::new(Mutex::new(Vec::new()));
+    let mut buffers = vec![system.clone()];
+    let mut sources = match loopback::open_system_output(system) {
+        Ok(source) => vec![source],
+        Err(e) => {
+            eprintln!("[MEETING] {}", e);
+            let _ = started.send(Err(e));
+            return;
+        }
+    };
+    if with_mic {
+        let mic: CaptureBuffer = Arc::new(Mutex::new(Vec::new()));
+        match loopback::open_microphone(device_id, mic.clone()) {
+            Ok(source) => {
+                sources.push(source);
+                buffers.push(mic);
+            }
+            Err(e) => eprintln!("[MEETING] Recording without the microphone: {}", e),
+        }
+    }
+    let names: Vec<&str> = sources.iter().map(|s| s.name.as_str()).collect();
+    println!("[MEETING] Capturing {}", names.join(" + "));
+    let _ = started.send(Ok(()));
+
+    let mut offset_ms = 0;
+    let mut next_chunk = Instant::now() + chunk;
+    loop {
+        let stopped = match stop_rx.recv_timeout(next_chunk.saturating_duration_since(Instant::now())) {
+            Err(RecvTimeoutError::Timeout) => false,
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => true,
+        };
+        next_chunk += chunk;
+
+        let samples = loopback::drain_mixed(&buffers);
+        let chunk_offset = offset_ms;
+        offset_ms += samples.len() as u64 * 1000 / SAMPLE_RATE;
+        if !is_silent(&samples) {
+            match crate::audio::write_temp_wav(&samples, "meeting") {
+                Ok(audio_path) => {
+                    let _ = chunk_tx.send(MeetingChunk { audio_path, offset_ms: chunk_offset });
+                }
+                Err(e) => eprintln!("[MEETING] {}", e),
+            }
+        }
+        if stopped {
+            break;
+        }
+    }
+    // Dropping the sources closes the streams; dropping chunk_tx ends the worker
+    drop(sources);
+}
+
+/// Worker: transcribe chunks in order and save them to the meeting session
+async fn transcribe_chunks(
+    app: tauri::AppHandle,
+    session_id: String,
+    mut chunks: tokio::sync::mpsc::UnboundedReceiver<MeetingChunk>,
+) {
+    while let Some(chunk) = chunks.recv().await {
+        if let Err(e) = transcribe_chunk(&app, &session_id, chunk).await {
+            eprintln!("[MEETING] Chunk failed: {}", e);
+        }
+    }
+    println!("[MEETING] Finished session {}", session_id);
+    emit_event(&app, AppEvent::MeetingState(status_of(None)));
+}
+
+async fn transcribe_chunk(app: &tauri::AppHandle, session_id: &str, chunk: MeetingChunk) -> Result<(), String> {
+    // The daemon deletes the file after reading it
+    let result = call_daemon_async(
+        "transcribe",
+        serde_json::json!({
+            "audio_path": chunk.audio_path,
+            "language": crate::asr_language::request_language(),
+            "hotwords": crate::vocabulary::request_hotwords(),
+        }),
+    )
+    .await?;
+    if !result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return Err(result.get("error").and_then(|v| v.as_str()).unwrap_or("ASR failed").to_string());
+    }
+
+    let text = result.get("text").and_then(|v| v.as_str()).unwrap_or_default();
+    let text = crate::textproc::process_transcript(app, text);
+    if text.trim().is_empty() {
+        return Ok(());
+    }
+    let state = app.try_state::<AppState>().ok_or_else(|| "Database is not ready".to_string())?;
+    let mut message = state.db.add_message(session_id, "user", &text)?;
+
+    // Segment times are relative to the chunk; store them relative to the meeting
+    let segments: Vec<SpeakerSegment> = result
+        .get("segments")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    let segments: Vec<SpeakerSegment> = crate::diarization::process_segments(app, segments)
+        .into_iter()
+        .map(|segment| SpeakerSegment {
+            start_ms: segment.start_ms + chunk.offset_ms,
+            end_ms: segment.end_ms + chunk.offset_ms,
+            ..segment
+        })
+        .collect();
+    if !segments.is_empty() {
+        state.db.set_message_segments(&message.id, &segments)?;
+        message.segments = segments;
+    }
+
+    emit_event(app, AppEvent::MeetingTranscript(Box::new(message)));
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Whether a meeting is being transcribed, and into which session
+#[tauri::command]
+pub fn get_meeting_status() -> Result<MeetingStatus, String> {
+    let meeting = MEETING.lock().map_err(|e| e.to_string())?;
+    Ok(status_of(meeting.as_ref()))
+}
@@ -0,0 +1,158 @@
+// ============================================================================
+// Metrics Module - End-to-End Voice Pipeline Latency
+// ============================================================================
+//
+// Each push-to-talk interaction is timed from the moment recording stops:
+// ASR result, first LLM token, first TTS audio and playback start. Finished
+// interactions are stored in SQLite and summarized as p50/p95 per stage.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{Manager, State};
+
+use crate::database::LatencyRecord;
+use crate::state::AppState;
+use crate::types::{LatencyStageStats, LatencyStats};
+
+/// Number of recent interactions summarized by default
+const DEFAULT_STATS_WINDOW: i32 = 100;
+
+/// Pipeline stages after recording stops
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineStage {
+    AsrDone,
+    FirstToken,
+    FirstAudio,
+    PlaybackStarted,
+}
+
+/// Stage timings of the interaction in progress
+struct InteractionTimings {
+    stopped_at: Instant,
+    created_at: i64,
+    asr: Option<Duration>,
+    first_token: Option<Duration>,
+    first_audio: Option<Duration>,
+    playback: Option<Duration>,
+}
+
+/// Interaction currently being timed
+static CURRENT_INTERACTION: Mutex<Option<InteractionTimings>> = Mutex::new(None);
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// Start timing an interaction (recording just stopped)
+///
+/// Ignored while an interaction is already being timed, so the Rust-side and
+/// daemon-side "processing" signals of one recording only count once.
+pub fn mark_recording_stopped() {
+    if let Ok(mut current) = CURRENT_INTERACTION.lock() {
+        if current.is_none() {
+            *current = Some(InteractionTimings {
+                stopped_at: Instant::now(),
+                created_at: chrono::Utc::now().timestamp_millis(),
+                asr: None,
+                first_token: None,
+                first_audio: None,
+                playback: None,
+            });
+        }
+    }
+}
+
+/// Record the first time the current interaction reached `stage`
+pub fn mark_stage(stage: PipelineStage) {
+    if let Ok(mut current) = CURRENT_INTERACTION.lock() {
+        if let Some(timings) = current.as_mut() {
+            let elapsed = timings.stopped_at.elapsed();
+            let slot = match stage {
+                PipelineStage::AsrDone => &mut timings.asr,
+                PipelineStage::FirstToken => &mut timings.first_token,
+                PipelineStage::FirstAudio => &mut timings.first_audio,
+                PipelineStage::PlaybackStarted => &mut timings.playback,
+            };
+            slot.get_or_insert(elapsed);
+        }
+    }
+}
+
+/// Finish the current interaction and store it
+///
+/// Interactions that never produced an ASR result (too short, cancelled) are dropped.
+pub fn finish_interaction<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let timings = match CURRENT_INTERACTION.lock() {
+        Ok(mut current) => current.take(),
+        Err(_) => None,
+    };
+    let Some(timings) = timings else {
+        return;
+    };
+    if timings.asr.is_none() {
+        return;
+    }
+
+    let as_ms = |d: Option<Duration>| d.map(|d| d.as_millis() as i64);
+    let record = LatencyRecord {
+        created_at: timings.created_at,
+        asr_ms: as_ms(timings.asr),
+        first_token_ms: as_ms(timings.first_token),
+        first_audio_ms: as_ms(timings.first_audio),
+        playback_ms: as_ms(timings.playback),
+    };
+
+    if let Some(state) = app.try_state::<AppState>() {
+        if let Err(e) = state.db.add_latency_record(&record) {
+            eprintln!("Failed to store latency record: {}", e);
+        }
+    }
+}
+
+// ============================================================================
+// Statistics
+// ============================================================================
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[i64], p: f64) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// p50/p95 of the stage durations that are present
+fn stage_stats(durations: impl Iterator<Item = Option<i64>>) -> LatencyStageStats {
+    let mut values: Vec<i64> = durations.flatten().collect();
+    values.sort_unstable();
+    LatencyStageStats {
+        samples: values.len(),
+        p50_ms: percentile(&values, 50.0),
+        p95_ms: percentile(&values, 95.0),
+    }
+}
+
+/// Duration between two stage offsets, if both were reached
+fn between(from: Option<i64>, to: Option<i64>) -> Option<i64> {
+    Some(to? - from?)
+}
+
+/// Latency p50/p95 per pipeline stage over the most recent interactions
+#[tauri::command]
+pub fn get_latency_stats(
+    state: State<'_, AppState>,
+    limit: Option<i32>,
+) -> Result<LatencyStats, String> {
+    let records = state.db.get_latency_records(limit.unwrap_or(DEFAULT_STATS_WINDOW))?;
+
+    Ok(LatencyStats {
+        interactions: records.len(),
+        asr: stage_stats(records.iter().map(|r| r.asr_ms)),
+        llm_first_token: stage_stats(records.iter().map(|r| between(r.asr_ms, r.first_token_ms))),
+        tts_first_audio: stage_stats(records.iter().map(|r| between(r.first_token_ms, r.first_audio_ms))),
+        playback_start: stage_stats(records.iter().map(|r| between(r.first_audio_ms, r.playback_ms))),
+        end_to_end: stage_stats(records.iter().map(|r| r.playback_ms)),
+    })
+}
@@ -0,0 +1,217 @@
+// ============================================================================
+// Network Module - Offline Mode and Connectivity
+// ============================================================================
+//
+// The app is offline when `offline_mode` is on or when the connectivity check
+// fails. While offline, commands that call a remote LLM provider fail early
+// with an `OfflineError` instead of waiting for a connection timeout; local
+// providers (Ollama on this machine or the LAN) keep working. The daemon gets
+// the state with every `reload_config` and switches to local models, and the
+// tray shows an offline badge.
+//
+// The check is a plain TCP connect to the configured proxy, or else to a few
+// public DNS resolvers, so it works behind firewalls that block ICMP. Every
+// change of the offline state is reported as `connectivity-changed`.
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config;
+use crate::daemon::APP_HANDLE;
+use crate::types::ConnectivityPayload;
+use crate::events::{emit_event, AppEvent};
+
+/// Interval between connectivity checks while online
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Interval while unreachable, to notice the connection coming back quickly
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Checked in order; reachable if any accepts a connection (Cloudflare, AliDNS, Google)
+const PROBE_HOSTS: [&str; 3] = ["1.1.1.1:443", "223.5.5.5:443", "8.8.8.8:443"];
+
+/// Result of the last connectivity check (assume online until checked)
+static REACHABLE: AtomicBool = AtomicBool::new(true);
+
+/// Offline state last reported to the frontend
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Why a remote provider cannot be used right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OfflineReason {
+    /// `offline_mode` is on
+    OfflineMode,
+    /// The connectivity check failed
+    NoConnection,
+}
+
+/// A remote provider was called while offline
+///
+/// Commands return it as a string starting with "Offline:".
+#[derive(Debug, Clone, Serialize)]
+pub struct OfflineError {
+    pub provider: String,
+    pub reason: OfflineReason,
+}
+
+impl std::fmt::Display for OfflineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.reason {
+            OfflineReason::OfflineMode => {
+                write!(f, "Offline: {} is unavailable while offline mode is on", self.provider)
+            }
+            OfflineReason::NoConnection => {
+                write!(f, "Offline: {} is unavailable without a network connection", self.provider)
+            }
+        }
+    }
+}
+
+impl From<OfflineError> for String {
+    fn from(error: OfflineError) -> Self {
+        error.to_string()
+    }
+}
+
+/// The `offline_mode` setting
+pub fn offline_mode() -> bool {
+    config::read_config_value("offline_mode")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Whether remote providers are unavailable
+pub fn is_offline() -> bool {
+    offline_mode() || !REACHABLE.load(Ordering::SeqCst)
+}
+
+/// Offline state as last reported (cheap; no config read)
+pub fn offline_state() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+}
+
+/// Whether a URL points at this machine or the local network
+pub fn is_local_url(url: &str) -> bool {
+    let Some(host) = reqwest::Url::parse(url.trim())
+        .ok()
+        .and_then(|url| url.host_str().map(|h| h.trim_matches(['[', ']']).to_ascii_lowercase()))
+    else {
+        return false;
+    };
+    if host == "localhost" || host.ends_with(".localhost") || host.ends_with(".local") {
+        return true;
+    }
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        Ok(IpAddr::V6(ip)) => ip.is_loopback(),
+        Err(_) => false,
+    }
+}
+
+/// Fail with an [`OfflineError`] if `base_url` is remote and the app is offline
+pub fn ensure_online(provider: &str, base_url: &str) -> Result<(), OfflineError> {
+    if is_local_url(base_url) {
+        return Ok(());
+    }
+    let reason = if offline_mode() {
+        OfflineReason::OfflineMode
+    } else if !REACHABLE.load(Ordering::SeqCst) {
+        OfflineReason::NoConnection
+    } else {
+        return Ok(());
+    };
+    Err(OfflineError {
+        provider: provider.to_string(),
+        reason,
+    })
+}
+
+fn payload() -> ConnectivityPayload {
+    let offline_mode = offline_mode();
+    let reachable = REACHABLE.load(Ordering::SeqCst);
+    ConnectivityPayload {
+        offline: offline_mode || !reachable,
+        offline_mode,
+        reachable,
+    }
+}
+
+/// Re-evaluate the offline state; on a change, update the tray and emit `connectivity-changed`
+///
+/// Returns true if the state changed (the caller tells the daemon).
+pub fn refresh() -> bool {
+    let payload = payload();
+    if OFFLINE.swap(payload.offline, Ordering::SeqCst) == payload.offline {
+        return false;
+    }
+    println!(
+        "🌐 {} (offline_mode: {}, reachable: {})",
+        if payload.offline { "Offline" } else { "Online" },
+        payload.offline_mode,
+        payload.reachable
+    );
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = crate::ui::update_tray_menu(app);
+        emit_event(app, AppEvent::ConnectivityChanged(payload));
+    }
+    true
+}
+
+/// Addresses probed by the connectivity check: the proxy if one is set, else public resolvers
+fn probe_addresses() -> Vec<String> {
+    let proxy = config::read_config_value("proxy")
+        .and_then(|v| serde_json::from_value::<config::ProxyConfig>(v).ok())
+        .and_then(|proxy| crate::http::parse_proxy_url(&proxy.url));
+    match proxy {
+        Some(url) => {
+            let host = url.host_str().unwrap_or_default().to_string();
+            vec![format!("{}:{}", host, url.port_or_known_default().unwrap_or(1080))]
+        }
+        None => PROBE_HOSTS.iter().map(|host| host.to_string()).collect(),
+    }
+}
+
+async fn check_connectivity() -> bool {
+    for address in probe_addresses() {
+        let connect = tokio::net::TcpStream::connect(address.as_str());
+        if let Ok(Ok(_)) = tokio::time::timeout(CONNECT_TIMEOUT, connect).await {
+            return true;
+        }
+    }
+    false
+}
+
+/// Start the periodic connectivity check (once per app run)
+pub fn start_connectivity_monitor() {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let reachable = check_connectivity().await;
+            REACHABLE.store(reachable, Ordering::SeqCst);
+            if refresh() {
+                config::notify_daemon_config_changed();
+            }
+            tokio::time::sleep(if reachable { CHECK_INTERVAL } else { RETRY_INTERVAL }).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Current offline state
+#[tauri::command]
+pub fn get_connectivity() -> ConnectivityPayload {
+    payload()
+}
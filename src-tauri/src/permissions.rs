@@ -0,0 +1,182 @@
+// ============================================================================
+// Permissions Module - OS Privacy Permission Checks for Onboarding
+// ============================================================================
+//
+// Most first-run failures are missing privacy permissions. The setup wizard
+// uses these commands to show what is missing, ask for it, and open the
+// matching page of the system settings:
+//
+//   microphone        recording (all platforms)
+//   accessibility     typing / inserting text into other apps (macOS)
+//   input_monitoring  mouse-button and Fn-key push-to-talk (macOS)
+//
+// Screen recording is not listed: nothing in the app captures the screen.
+
+use serde::Serialize;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::daemon::PTT_TRIGGER;
+use crate::platform::PermissionStatus;
+use crate::types::PttTrigger;
+
+/// Permissions the app can use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PermissionKind {
+    Microphone,
+    Accessibility,
+    InputMonitoring,
+}
+
+impl PermissionKind {
+    const ALL: [PermissionKind; 3] = [
+        PermissionKind::Microphone,
+        PermissionKind::Accessibility,
+        PermissionKind::InputMonitoring,
+    ];
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "microphone" => Ok(PermissionKind::Microphone),
+            "accessibility" => Ok(PermissionKind::Accessibility),
+            "input_monitoring" => Ok(PermissionKind::InputMonitoring),
+            _ => Err(format!("Unknown permission: {}", s)),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            PermissionKind::Microphone => "microphone",
+            PermissionKind::Accessibility => "accessibility",
+            PermissionKind::InputMonitoring => "input_monitoring",
+        }
+    }
+
+    /// What the app needs the permission for
+    fn purpose(&self) -> &'static str {
+        match self {
+            PermissionKind::Microphone => "Record your voice",
+            PermissionKind::Accessibility => "Type recognized text into other apps",
+            PermissionKind::InputMonitoring => "Use a mouse button or the Fn key for push-to-talk",
+        }
+    }
+
+    /// System settings page for the permission (None if the platform has none)
+    fn settings_url(&self) -> Option<&'static str> {
+        if cfg!(target_os = "macos") {
+            Some(match self {
+                PermissionKind::Microphone => "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone",
+                PermissionKind::Accessibility => "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility",
+                PermissionKind::InputMonitoring => "x-apple.systempreferences:com.apple.preference.security?Privacy_ListenEvent",
+            })
+        } else if cfg!(target_os = "windows") && *self == PermissionKind::Microphone {
+            Some("ms-settings:privacy-microphone")
+        } else {
+            None
+        }
+    }
+
+    fn status(&self) -> PermissionStatus {
+        #[cfg(target_os = "macos")]
+        {
+            use crate::platform::macos;
+            match self {
+                PermissionKind::Microphone => macos::microphone_permission(),
+                PermissionKind::Accessibility => macos::accessibility_permission(),
+                PermissionKind::InputMonitoring => macos::input_monitoring_permission(),
+            }
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            match self {
+                // No prompt on these platforms, so the device can simply be tried
+                PermissionKind::Microphone => match crate::audio::probe_microphone() {
+                    Ok(()) => PermissionStatus::Granted,
+                    Err(_) if cfg!(target_os = "windows") => PermissionStatus::Denied,
+                    Err(_) => PermissionStatus::Unknown,
+                },
+                PermissionKind::Accessibility | PermissionKind::InputMonitoring => PermissionStatus::NotRequired,
+            }
+        }
+    }
+
+    /// Whether the current settings depend on the permission
+    fn required(&self) -> bool {
+        match self {
+            PermissionKind::Microphone => true,
+            PermissionKind::Accessibility => cfg!(target_os = "macos"),
+            PermissionKind::InputMonitoring => {
+                cfg!(target_os = "macos")
+                    && PTT_TRIGGER.lock().map(|t| *t != PttTrigger::Shortcut).unwrap_or(false)
+            }
+        }
+    }
+
+    fn info(&self) -> PermissionInfo {
+        PermissionInfo {
+            kind: self.as_str(),
+            status: self.status(),
+            required: self.required(),
+            purpose: self.purpose(),
+            can_open_settings: self.settings_url().is_some(),
+        }
+    }
+}
+
+/// Status of one permission, as shown by the setup wizard
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionInfo {
+    pub kind: &'static str,
+    pub status: PermissionStatus,
+    /// Needed with the current settings (e.g. input_monitoring only for mouse / Fn PTT)
+    pub required: bool,
+    pub purpose: &'static str,
+    /// open_permission_settings has a page for it
+    pub can_open_settings: bool,
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Status of every permission the app can use
+#[tauri::command]
+pub fn check_permissions() -> Vec<PermissionInfo> {
+    PermissionKind::ALL.iter().map(PermissionKind::info).collect()
+}
+
+/// Ask the OS for a permission and return its new status
+///
+/// macOS only shows a prompt the first time; once denied, the user has to
+/// change it in System Settings (see [`open_permission_settings`]).
+#[tauri::command]
+pub async fn request_permission(kind: String) -> Result<PermissionInfo, String> {
+    let kind = PermissionKind::from_str(&kind)?;
+    match kind {
+        // Opening the device triggers the prompt; it blocks until the user answers
+        PermissionKind::Microphone => {
+            tauri::async_runtime::spawn_blocking(crate::audio::probe_microphone)
+                .await
+                .map_err(|e| format!("Microphone check failed: {}", e))?
+                .ok();
+        }
+        #[cfg(target_os = "macos")]
+        PermissionKind::Accessibility => crate::platform::macos::request_accessibility_permission(),
+        #[cfg(target_os = "macos")]
+        PermissionKind::InputMonitoring => crate::platform::macos::request_input_monitoring_permission(),
+        #[cfg(not(target_os = "macos"))]
+        PermissionKind::Accessibility | PermissionKind::InputMonitoring => {}
+    }
+    Ok(kind.info())
+}
+
+/// Open the system settings page where a permission is granted
+#[tauri::command]
+pub fn open_permission_settings(app: tauri::AppHandle, kind: String) -> Result<(), String> {
+    let kind = PermissionKind::from_str(&kind)?;
+    let url = kind
+        .settings_url()
+        .ok_or_else(|| format!("No settings page for {} on this platform", kind.as_str()))?;
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| format!("Failed to open settings: {}", e))
+}
@@ -0,0 +1,139 @@
+// ============================================================================
+// Personas Module - Assistant System Prompts and Voices
+// ============================================================================
+//
+// A persona bundles a system prompt with a TTS voice and speed. Personas are
+// stored in SQLite; the `active_persona` config key holds the id of the one in
+// use (null = the built-in prompt and configured voice). Chat and TTS commands
+// send the active persona to the daemon with every request.
+
+use tauri::{Manager, State};
+
+use crate::config;
+use crate::database::Persona;
+use crate::state::AppState;
+
+const ACTIVE_PERSONA_KEY: &str = "active_persona";
+
+/// Accepted speech rate multipliers
+const MIN_SPEED: f64 = 0.5;
+const MAX_SPEED: f64 = 2.0;
+
+/// The persona in use, if any
+///
+/// A dangling `active_persona` id (persona deleted elsewhere) counts as none.
+pub fn active_persona<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<Persona> {
+    let id = config::read_config_value(ACTIVE_PERSONA_KEY)?.as_i64()?;
+    let state = app.try_state::<AppState>()?;
+    state.db.get_persona(id).ok()
+}
+
+/// Add the active persona to daemon command args (`persona` field)
+pub fn with_persona<R: tauri::Runtime>(app: &tauri::AppHandle<R>, mut args: serde_json::Value) -> serde_json::Value {
+    if let (Some(persona), Some(map)) = (active_persona(app), args.as_object_mut()) {
+        map.insert(
+            "persona".to_string(),
+            serde_json::json!({
+                "system_prompt": persona.system_prompt,
+                "voice": persona.voice,
+                "speed": persona.speed,
+            }),
+        );
+    }
+    args
+}
+
+/// Make `id` the active persona (None = defaults) and refresh the tray
+pub fn switch_persona<R: tauri::Runtime>(app: &tauri::AppHandle<R>, id: Option<i64>) -> Result<(), String> {
+    if let Some(id) = id {
+        let state = app.try_state::<AppState>().ok_or("Database is not ready")?;
+        state.db.get_persona(id)?;
+    }
+    config::write_config_value(ACTIVE_PERSONA_KEY, serde_json::json!(id))?;
+    let _ = crate::ui::update_tray_menu(app);
+    Ok(())
+}
+
+/// Trim the fields and check they are usable
+fn validate(name: &str, system_prompt: &str, voice: Option<String>, speed: Option<f64>) -> Result<(String, String, Option<String>, f64), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Persona name must not be empty".to_string());
+    }
+    let system_prompt = system_prompt.trim();
+    if system_prompt.is_empty() {
+        return Err("System prompt must not be empty".to_string());
+    }
+    let voice = voice.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let speed = speed.unwrap_or(1.0);
+    if !(MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        return Err(format!("Speed must be between {} and {}", MIN_SPEED, MAX_SPEED));
+    }
+    Ok((name.to_string(), system_prompt.to_string(), voice, speed))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Create a persona (`voice` None = configured voice, `speed` defaults to 1.0)
+#[tauri::command]
+pub fn create_persona(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+    system_prompt: String,
+    voice: Option<String>,
+    speed: Option<f64>,
+) -> Result<Persona, String> {
+    let (name, system_prompt, voice, speed) = validate(&name, &system_prompt, voice, speed)?;
+    let persona = state.db.add_persona(&name, &system_prompt, voice.as_deref(), speed)?;
+    // The tray lists the personas
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(persona)
+}
+
+/// List personas by name
+#[tauri::command]
+pub fn list_personas(state: State<'_, AppState>) -> Result<Vec<Persona>, String> {
+    state.db.list_personas()
+}
+
+#[tauri::command]
+pub fn update_persona(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: i64,
+    name: String,
+    system_prompt: String,
+    voice: Option<String>,
+    speed: Option<f64>,
+) -> Result<Persona, String> {
+    let (name, system_prompt, voice, speed) = validate(&name, &system_prompt, voice, speed)?;
+    let persona = state.db.update_persona(id, &name, &system_prompt, voice.as_deref(), speed)?;
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(persona)
+}
+
+/// Delete a persona (deleting the active one switches back to the defaults)
+#[tauri::command]
+pub fn delete_persona(app: tauri::AppHandle, state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    let deleted = state.db.delete_persona(id)?;
+    if config::read_config_value(ACTIVE_PERSONA_KEY).and_then(|v| v.as_i64()) == Some(id) {
+        config::write_config_value(ACTIVE_PERSONA_KEY, serde_json::Value::Null)?;
+    }
+    let _ = crate::ui::update_tray_menu(&app);
+    Ok(deleted)
+}
+
+/// The persona in use (None = defaults)
+#[tauri::command]
+pub fn get_active_persona(app: tauri::AppHandle) -> Option<Persona> {
+    active_persona(&app)
+}
+
+/// Switch persona (`id` None = back to the built-in prompt and configured voice)
+#[tauri::command]
+pub fn set_active_persona(app: tauri::AppHandle, id: Option<i64>) -> Result<(), String> {
+    switch_persona(&app, id)
+}
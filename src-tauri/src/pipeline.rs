@@ -0,0 +1,518 @@
+// ============================================================================
+// Pipeline Module - Stage-by-Stage Voice Pipeline
+// ============================================================================
+//
+// A PTT recording goes through three stages, each a separate call:
+//
+//   asr   daemon `ptt_audio` / `ptt_audio_end` (transcript only)
+//   llm   native client (llm.rs, `llm_backend: "native"`) or daemon `chat_stream`
+//   tts   daemon `tts` per sentence (through the TTS cache), then `play_audio`
+//
+// The reply is cut into sentences as it streams in, and each sentence is
+// spoken while the LLM keeps writing. A stage that fails is tried again
+// (`pipeline.retries`), and the LLM and TTS stages can be left out
+// (`pipeline.skip`). APP_STATUS follows the stage in progress; progress is
+// reported with the same PTT events the daemon emits, so the windows do not
+// know who ran the pipeline. Barge-in and interrupt_operation stop a reply
+// like a streaming chat.
+//
+// With `pipeline.orchestrator: "daemon"` the transcript is handed to the
+// daemon's `ptt_chat` instead, which runs LLM + TTS in one go.
+
+use tokio::sync::mpsc;
+
+use crate::audio::AudioData;
+use crate::audio_stream::StreamTail;
+use crate::config::{self, PipelineConfig};
+use crate::daemon::ipc::FrameKind;
+use crate::daemon::request::{
+    register_stream, unregister_request, CancelToken, RequestOptions, STREAM_IDLE_TIMEOUT,
+};
+use crate::daemon::{call_daemon_async_with, daemon_client, APP_STATUS};
+use crate::llm::StreamOutcome;
+use crate::speech_text::SpeechFilter;
+use crate::types::{AppStatus, SpeakerSegment, WordTiming};
+
+const CONFIG_KEY: &str = "pipeline";
+
+/// `pipeline.orchestrator` values
+pub const APP_ORCHESTRATOR: &str = "app";
+pub const DAEMON_ORCHESTRATOR: &str = "daemon";
+
+/// Highest accepted `pipeline.retries` value
+pub const MAX_RETRIES: u32 = 5;
+
+/// ASR result that means "nothing to transcribe", not a failure
+const TOO_SHORT_ERROR: &str = "Recording too short";
+
+/// How long the leftover output of a cancelled `chat_stream` is drained
+const CANCEL_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Sentence ends that cut right away (CJK punctuation, line breaks)
+const FULL_STOPS: [char; 6] = ['。', '！', '？', '；', '…', '\n'];
+
+/// Sentence ends that only cut when followed by whitespace ("3.14" stays whole)
+const ASCII_STOPS: [char; 4] = ['.', '!', '?', ';'];
+
+/// A stage of the voice pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Asr,
+    Llm,
+    Tts,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Asr, Stage::Llm, Stage::Tts];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Stage::Asr => "asr",
+            Stage::Llm => "llm",
+            Stage::Tts => "tts",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Stage::ALL.into_iter().find(|stage| stage.as_str() == s)
+    }
+}
+
+/// Result of the ASR stage (empty text = no speech)
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    pub text: String,
+    pub language: Option<String>,
+    /// 0-1, when the ASR model reports one
+    pub confidence: Option<f64>,
+    /// Other hypotheses, best first
+    pub alternatives: Vec<String>,
+    /// Word timings of the raw text (empty if the model has none)
+    pub words: Vec<WordTiming>,
+    /// Per-speaker parts (empty unless several speakers were detected)
+    pub segments: Vec<SpeakerSegment>,
+}
+
+fn load_config() -> PipelineConfig {
+    config::read_config_value(CONFIG_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+impl PipelineConfig {
+    fn retries(&self, stage: Stage) -> u32 {
+        self.retries.get(stage.as_str()).copied().unwrap_or(0).min(MAX_RETRIES)
+    }
+
+    fn skips(&self, stage: Stage) -> bool {
+        self.skip.iter().any(|name| name == stage.as_str())
+    }
+}
+
+/// Send a PTT event to the windows, as if the daemon had emitted it
+fn emit(app: &tauri::AppHandle, event: &str, data: serde_json::Value) {
+    let mut payload = serde_json::json!({ "ptt_event": event });
+    if let (Some(map), serde_json::Value::Object(data)) = (payload.as_object_mut(), data) {
+        map.extend(data);
+    }
+    crate::ptt::dispatch_event(app, &payload);
+}
+
+// ============================================================================
+// ASR Stage
+// ============================================================================
+
+/// Transcribe a PTT recording
+///
+/// A streamed recording (`stream`) is finished with `ptt_audio_end` first;
+/// retries send the whole recording with `ptt_audio`. The daemon deletes the
+/// files it is given, so every attempt but the last gets a copy.
+pub async fn transcribe(
+    audio: &AudioData,
+    stream: Option<StreamTail>,
+    language: &str,
+) -> Result<Transcript, String> {
+    APP_STATUS.transition(AppStatus::AsrProcessing, "pipeline:asr");
+    let attempts = load_config().retries(Stage::Asr) + 1;
+    let mut stream = stream;
+    let mut last_error = String::new();
+
+    for attempt in 0..attempts {
+        let last = attempt + 1 == attempts;
+        let (command, args) = match stream.take() {
+            Some(tail) => ("ptt_audio_end", serde_json::json!({
+                "stream_id": tail.stream_id,
+                "audio_path": tail.audio_path.unwrap_or_default(),
+                "emit_result": false,
+                "language": language,
+                "hotwords": crate::vocabulary::request_hotwords(),
+            })),
+            None => {
+                let path = if last {
+                    audio.file_path.clone()
+                } else {
+                    copy_for_attempt(&audio.file_path, attempt).unwrap_or_else(|e| {
+                        eprintln!("[PIPELINE] {}", e);
+                        audio.file_path.clone()
+                    })
+                };
+                ("ptt_audio", serde_json::json!({
+                    "audio_path": path,
+                    "sample_rate": audio.sample_rate,
+                    "duration": audio.duration_secs,
+                    "emit_result": false,
+                    "language": language,
+                    "hotwords": crate::vocabulary::request_hotwords(),
+                }))
+            }
+        };
+
+        let result = call_daemon_async_with(command, args, RequestOptions::for_command(command)).await;
+        match result {
+            Ok(result) if result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) => {
+                let _ = std::fs::remove_file(&audio.file_path);
+                return Ok(Transcript {
+                    text: result.get("text").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                    language: result.get("language").and_then(|v| v.as_str()).map(str::to_string),
+                    confidence: result.get("confidence").and_then(|v| v.as_f64()),
+                    alternatives: result
+                        .get("alternatives")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok())
+                        .unwrap_or_default(),
+                    words: result
+                        .get("words")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok())
+                        .unwrap_or_default(),
+                    segments: result
+                        .get("segments")
+                        .and_then(|v| serde_json::from_value(v.clone()).ok())
+                        .unwrap_or_default(),
+                });
+            }
+            Ok(result) => {
+                last_error = result.get("error").and_then(|v| v.as_str()).unwrap_or("ASR failed").to_string();
+                if last_error == TOO_SHORT_ERROR {
+                    let _ = std::fs::remove_file(&audio.file_path);
+                    return Ok(Transcript::default());
+                }
+            }
+            Err(e) => last_error = e,
+        }
+        if !last {
+            eprintln!("[PIPELINE] ASR attempt {} failed, retrying: {}", attempt + 1, last_error);
+        }
+    }
+
+    let _ = std::fs::remove_file(&audio.file_path);
+    Err(last_error)
+}
+
+/// Copy of a recording for one ASR attempt
+fn copy_for_attempt(path: &str, attempt: u32) -> Result<String, String> {
+    let copy = format!("{}.attempt{}.wav", path.trim_end_matches(".wav"), attempt);
+    std::fs::copy(path, &copy).map_err(|e| format!("Failed to copy recording: {}", e))?;
+    Ok(copy)
+}
+
+// ============================================================================
+// LLM and TTS Stages
+// ============================================================================
+
+/// Show a post-processed transcript and reply to it (conversation mode)
+pub async fn respond(app: &tauri::AppHandle, text: String, auto_chat: bool) {
+    let config = load_config();
+    let auto_chat = auto_chat && !config.skips(Stage::Llm);
+    let use_tts = !config.skips(Stage::Tts);
+
+    if config.orchestrator == DAEMON_ORCHESTRATOR {
+        let args = crate::personas::with_persona(app, serde_json::json!({
+            "text": text,
+            "auto_chat": auto_chat,
+            "use_tts": use_tts,
+        }));
+        match daemon_client() {
+            Ok(client) => {
+                let _ = client.send_no_wait("ptt_chat", args);
+            }
+            Err(e) => eprintln!("[PIPELINE] Failed to submit transcript: {}", e),
+        }
+        return;
+    }
+
+    emit(app, "user_message", serde_json::json!({ "text": text }));
+    if !auto_chat {
+        emit(app, "idle", serde_json::json!({}));
+        return;
+    }
+
+    // Cancelled by barge-in and interrupt_operation, like a streaming chat
+    let cancel = CancelToken::new();
+    let key = register_stream(cancel.clone());
+
+    let (sentences, queue) = mpsc::unbounded_channel();
+    let speaking = async {
+        if use_tts {
+            speak(app, queue, &cancel, config.retries(Stage::Tts)).await;
+        }
+    };
+    let (reply, ()) = tokio::join!(
+        async {
+            let reply = chat(app, &text, &cancel, config.retries(Stage::Llm), &sentences).await;
+            // Lets the TTS stage finish once the last sentence is spoken
+            drop(sentences);
+            reply
+        },
+        speaking,
+    );
+    unregister_request(key);
+
+    match reply {
+        Ok(StreamOutcome::Done(content)) if !cancel.is_cancelled() => {
+            emit(app, "assistant_done", serde_json::json!({ "content": content }));
+            emit(app, "idle", serde_json::json!({}));
+        }
+        // Barged in: a new recording already owns the UI state
+        Ok(StreamOutcome::Done(content)) | Ok(StreamOutcome::Cancelled(content)) => {
+            emit(app, "interrupted", serde_json::json!({ "content": content }));
+        }
+        Err(e) => {
+            eprintln!("[PIPELINE] Reply failed: {}", e);
+            emit(app, "error", serde_json::json!({ "error": e }));
+        }
+    }
+}
+
+/// Speak a finished text with the TTS stage (e.g. a prompt template's answer)
+pub async fn speak_text(app: &tauri::AppHandle, text: &str) {
+    let cancel = CancelToken::new();
+    let key = register_stream(cancel.clone());
+
+    let (sentences, queue) = mpsc::unbounded_channel();
+    let mut splitter = SentenceSplitter::default();
+    for sentence in splitter.push(text).into_iter().chain(splitter.finish()) {
+        let _ = sentences.send(sentence);
+    }
+    drop(sentences);
+    speak(app, queue, &cancel, load_config().retries(Stage::Tts)).await;
+
+    unregister_request(key);
+    APP_STATUS.transition(AppStatus::Idle, "pipeline:spoken");
+}
+
+/// LLM stage: stream the reply, emitting its text and queueing its sentences for TTS
+///
+/// Only a reply that failed before any text arrived is tried again.
+async fn chat(
+    app: &tauri::AppHandle,
+    text: &str,
+    cancel: &CancelToken,
+    retries: u32,
+    sentences: &mpsc::UnboundedSender<String>,
+) -> Result<StreamOutcome, String> {
+    APP_STATUS.transition(AppStatus::LlmProcessing, "pipeline:llm");
+    let native = crate::llm::native_enabled();
+
+    let mut attempt = 0;
+    loop {
+        let mut received = false;
+        // Daemon backends already stream whole sentences; native ones stream tokens
+        let mut splitter = SentenceSplitter::default();
+        let mut on_chunk = |chunk: &str| {
+            received = true;
+            emit(app, "assistant_chunk", serde_json::json!({ "content": chunk }));
+            if native {
+                for sentence in splitter.push(chunk) {
+                    let _ = sentences.send(sentence);
+                }
+            } else {
+                let _ = sentences.send(chunk.to_string());
+            }
+        };
+        let result = if native {
+            crate::llm::chat_stream(app, text, cancel, &mut on_chunk).await
+        } else {
+            daemon_chat_stream(app, text, cancel, &mut on_chunk).await
+        };
+        if let Some(rest) = splitter.finish() {
+            let _ = sentences.send(rest);
+        }
+
+        match result {
+            Err(e) if !received && attempt < retries && !cancel.is_cancelled() => {
+                attempt += 1;
+                eprintln!("[PIPELINE] LLM attempt {} failed, retrying: {}", attempt, e);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Stream a reply from the daemon's `chat_stream` (sentence by sentence)
+async fn daemon_chat_stream(
+    app: &tauri::AppHandle,
+    text: &str,
+    cancel: &CancelToken,
+    mut on_chunk: impl FnMut(&str),
+) -> Result<StreamOutcome, String> {
+    let client = daemon_client()?;
+    let args = crate::personas::with_persona(app, serde_json::json!({ "text": text }));
+    let mut request = client.send_request("chat_stream", args)?;
+
+    let mut reply = String::new();
+    loop {
+        let received = tokio::select! {
+            received = request.recv_timeout(STREAM_IDLE_TIMEOUT) => received?,
+            _ = cancel.cancelled() => {
+                request.drain(CANCEL_DRAIN_TIMEOUT);
+                return Ok(StreamOutcome::Cancelled(reply));
+            }
+        };
+        let Some(frame) = received else {
+            return Err("Daemon stopped responding".to_string());
+        };
+        // The final response only arrives on its own if the stream ended abnormally
+        if frame.kind == FrameKind::Response {
+            return Err(frame
+                .payload
+                .get("error")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Stream ended unexpectedly")
+                .to_string());
+        }
+        if frame.kind != FrameKind::Stream {
+            continue;
+        }
+        match frame.payload.get("type").and_then(|v| v.as_str()) {
+            Some("chunk") => {
+                if let Some(content) = frame.payload.get("content").and_then(|v| v.as_str()) {
+                    on_chunk(content);
+                    reply.push_str(content);
+                }
+            }
+            Some("done") => return Ok(StreamOutcome::Done(reply)),
+            Some("error") => {
+                return Err(frame
+                    .payload
+                    .get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Chat failed")
+                    .to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// TTS stage: generate and play each queued sentence in order
+///
+/// Sentences are cleaned up for speech first (see speech_text.rs). A sentence
+/// whose speech still fails after the retries is left out.
+async fn speak(
+    app: &tauri::AppHandle,
+    mut queue: mpsc::UnboundedReceiver<String>,
+    cancel: &CancelToken,
+    retries: u32,
+) {
+    let mut filter = SpeechFilter::from_config();
+    let mut first = true;
+    loop {
+        let sentence = tokio::select! {
+            sentence = queue.recv() => sentence,
+            _ = cancel.cancelled() => None,
+        };
+        let Some(sentence) = sentence else {
+            break;
+        };
+
+        let Some(spoken) = filter.process(&sentence) else {
+            continue;
+        };
+
+        APP_STATUS.transition(AppStatus::TtsProcessing, "pipeline:tts");
+        let Some(audio_path) = synthesize(app, &spoken, retries).await else {
+            continue;
+        };
+        if cancel.is_cancelled() {
+            break;
+        }
+        emit(app, "audio_chunk", serde_json::json!({ "audio_path": audio_path, "text": spoken }));
+
+        // The daemon's interrupt flag is reset once per reply, before its first sentence
+        let args = serde_json::json!({ "audio_path": audio_path, "clear_interrupt": first });
+        first = false;
+        let options = RequestOptions::for_command("play_audio").with_cancel(cancel.clone());
+        if let Err(e) = call_daemon_async_with("play_audio", args, options).await {
+            eprintln!("[PIPELINE] Playback failed: {}", e);
+        }
+    }
+}
+
+/// Speech for one sentence (None if it still failed after the retries)
+async fn synthesize(app: &tauri::AppHandle, sentence: &str, retries: u32) -> Option<String> {
+    for attempt in 0..=retries {
+        match crate::commands::generate_tts(app.clone(), sentence.to_string(), None).await {
+            Ok(result) if result.success && result.audio_path.is_some() => return result.audio_path,
+            Ok(result) => eprintln!(
+                "[PIPELINE] TTS attempt {} failed: {}",
+                attempt + 1,
+                result.error.unwrap_or_default()
+            ),
+            Err(e) => eprintln!("[PIPELINE] TTS attempt {} failed: {}", attempt + 1, e),
+        }
+    }
+    None
+}
+
+// ============================================================================
+// Sentence Splitting
+// ============================================================================
+
+/// Cuts streamed text into sentences for TTS
+#[derive(Default)]
+pub struct SentenceSplitter {
+    buffer: String,
+}
+
+impl SentenceSplitter {
+    /// Add streamed text; returns the sentences it completed
+    pub fn push(&mut self, text: &str) -> Vec<String> {
+        self.buffer.push_str(text);
+        let mut sentences = Vec::new();
+        while let Some(end) = self.sentence_end() {
+            let sentence: String = self.buffer.drain(..end).collect();
+            if !sentence.trim().is_empty() {
+                sentences.push(sentence);
+            }
+        }
+        sentences
+    }
+
+    /// Text left after the last complete sentence
+    pub fn finish(&mut self) -> Option<String> {
+        let rest = std::mem::take(&mut self.buffer);
+        (!rest.trim().is_empty()).then_some(rest)
+    }
+
+    /// Byte index just after the first complete sentence in the buffer
+    fn sentence_end(&self) -> Option<usize> {
+        let mut chars = self.buffer.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let end = i + c.len_utf8();
+            if FULL_STOPS.contains(&c) {
+                return Some(end);
+            }
+            // "1. " starts a list item, it does not end a sentence (lines are cut at '\n' above)
+            let list_marker = c == '.' && {
+                let before = self.buffer[..i].trim();
+                !before.is_empty() && before.chars().all(|c| c.is_ascii_digit())
+            };
+            if ASCII_STOPS.contains(&c)
+                && !list_marker
+                && chars.peek().is_some_and(|(_, next)| next.is_whitespace())
+            {
+                return Some(end);
+            }
+        }
+        None
+    }
+}
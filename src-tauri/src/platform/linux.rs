@@ -0,0 +1,339 @@
+// src-tauri/src/platform/linux.rs
+//
+// Linux 文字输入实现
+//
+// Uses the same clipboard + paste approach as macOS. There is no stable native
+// API that works on both X11 and Wayland, so we shell out to the standard tools:
+// - Wayland: wl-copy / wl-paste + wtype
+// - X11: xclip + xdotool
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Display server the current session is running on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DisplayServer {
+    Wayland,
+    X11,
+}
+
+fn detect_display_server() -> DisplayServer {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        DisplayServer::Wayland
+    } else {
+        DisplayServer::X11
+    }
+}
+
+/// Read current clipboard text, None if empty or not text
+fn read_clipboard(server: DisplayServer) -> Option<String> {
+    let output = match server {
+        DisplayServer::Wayland => Command::new("wl-paste")
+            .arg("--no-newline")
+            .output(),
+        DisplayServer::X11 => Command::new("xclip")
+            .args(["-selection", "clipboard", "-o"])
+            .output(),
+    };
+
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8(out.stdout).ok(),
+        _ => None,
+    }
+}
+
+/// Replace clipboard content with the given text
+fn write_clipboard(server: DisplayServer, text: &str) -> Result<(), String> {
+    let (program, args): (&str, &[&str]) = match server {
+        DisplayServer::Wayland => ("wl-copy", &[]),
+        DisplayServer::X11 => ("xclip", &["-selection", "clipboard"]),
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run {} (is it installed?): {}", program, e))?;
+
+    {
+        let stdin = child.stdin.as_mut()
+            .ok_or_else(|| format!("Failed to open {} stdin", program))?;
+        stdin.write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write clipboard content: {}", e))?;
+    }
+    // Close stdin so the tool takes ownership of the selection
+    drop(child.stdin.take());
+
+    let status = child.wait()
+        .map_err(|e| format!("Failed to wait for {}: {}", program, e))?;
+    if !status.success() {
+        return Err(format!("{} exited with status {}", program, status));
+    }
+
+    Ok(())
+}
+
+/// Simulate Ctrl+V in the focused window
+fn send_paste(server: DisplayServer) -> Result<(), String> {
+    let output = match server {
+        DisplayServer::Wayland => Command::new("wtype")
+            .args(["-M", "ctrl", "v", "-m", "ctrl"])
+            .output(),
+        DisplayServer::X11 => Command::new("xdotool")
+            .args(["key", "--clearmodifiers", "ctrl+v"])
+            .output(),
+    };
+
+    match output {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!(
+            "Failed to simulate paste: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => Err(format!("Failed to simulate paste (is xdotool/wtype installed?): {}", e)),
+    }
+}
+
+pub fn type_text(text: &str) -> Result<(), String> {
+    let server = detect_display_server();
+
+    // 1. Save current clipboard content
+    let old_content = read_clipboard(server);
+
+    // Define clipboard restoration function
+    let restore_clipboard = || -> Result<(), String> {
+        if let Some(ref content) = old_content {
+            write_clipboard(server, content)
+                .map_err(|e| format!("Failed to restore clipboard: {}", e))?;
+        }
+        Ok(())
+    };
+
+    // 2. Set new content to clipboard
+    if let Err(e) = write_clipboard(server, text) {
+        let _ = restore_clipboard();
+        return Err(format!("Failed to set clipboard content: {}", e));
+    }
+
+    // 3. Simulate Ctrl+V keypress
+    if let Err(e) = send_paste(server) {
+        let _ = restore_clipboard();
+        return Err(e);
+    }
+
+    // Wait for paste to complete
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    // 4. Restore original clipboard content
+    restore_clipboard()?;
+
+    Ok(())
+}
+
+/// Press a special key `count` times in the focused window
+pub fn press_key(key: super::Key, count: usize) -> Result<(), String> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    // Both tools take X keysym names
+    let keysym = match key {
+        super::Key::Enter => "Return",
+        super::Key::Tab => "Tab",
+        super::Key::Escape => "Escape",
+        super::Key::Backspace => "BackSpace",
+    };
+
+    let output = match detect_display_server() {
+        DisplayServer::Wayland => {
+            let mut args = Vec::with_capacity(count * 2);
+            for _ in 0..count {
+                args.extend(["-k", keysym]);
+            }
+            Command::new("wtype").args(args).output()
+        }
+        DisplayServer::X11 => Command::new("xdotool")
+            .args(["key", "--clearmodifiers", "--repeat", &count.to_string(), keysym])
+            .output(),
+    };
+
+    match output {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!(
+            "Failed to press {}: {}",
+            keysym,
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => Err(format!("Failed to press {} (is xdotool/wtype installed?): {}", keysym, e)),
+    }
+}
+
+/// Press Ctrl+Z in the focused window
+pub fn press_undo() -> Result<(), String> {
+    let output = match detect_display_server() {
+        DisplayServer::Wayland => Command::new("wtype").args(["-M", "ctrl", "z", "-m", "ctrl"]).output(),
+        DisplayServer::X11 => Command::new("xdotool").args(["key", "--clearmodifiers", "ctrl+z"]).output(),
+    };
+
+    match output {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!(
+            "Failed to press Ctrl+Z: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => Err(format!("Failed to press Ctrl+Z (is xdotool/wtype installed?): {}", e)),
+    }
+}
+
+/// Type text one character at a time, waiting `key_delay` between characters
+pub fn type_keys(text: &str, key_delay: std::time::Duration) -> Result<(), String> {
+    let delay_ms = key_delay.as_millis().to_string();
+    let output = match detect_display_server() {
+        DisplayServer::Wayland => Command::new("wtype").args(["-d", &delay_ms, "--", text]).output(),
+        DisplayServer::X11 => Command::new("xdotool")
+            .args(["type", "--clearmodifiers", "--delay", &delay_ms, "--", text])
+            .output(),
+    };
+
+    match output {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!(
+            "Failed to type text: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => Err(format!("Failed to type text (is xdotool/wtype installed?): {}", e)),
+    }
+}
+
+/// Put text on the clipboard without pasting it
+pub fn copy_text(text: &str) -> Result<(), String> {
+    write_clipboard(detect_display_server(), text)
+}
+
+/// Currently selected text in the focused app, None if nothing is selected
+///
+/// Reads the PRIMARY selection, so unlike macOS / Windows no copy is simulated
+/// and the clipboard is left alone.
+pub fn copy_selection() -> Result<Option<String>, String> {
+    let output = match detect_display_server() {
+        DisplayServer::Wayland => Command::new("wl-paste")
+            .args(["--primary", "--no-newline"])
+            .output(),
+        DisplayServer::X11 => Command::new("xclip")
+            .args(["-selection", "primary", "-o"])
+            .output(),
+    };
+
+    match output {
+        Ok(out) if out.status.success() => Ok(String::from_utf8(out.stdout)
+            .ok()
+            .filter(|text| !text.trim().is_empty())),
+        // Both tools fail when there is no selection
+        Ok(_) => Ok(None),
+        Err(e) => Err(format!("Failed to read the selection (is xclip/wl-clipboard installed?): {}", e)),
+    }
+}
+
+/// Whether GNOME's Do Not Disturb is on, None on other desktops
+pub fn system_dnd_active() -> Option<bool> {
+    let output = Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.notifications", "show-banners"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "false" => Some(true),
+        "true" => Some(false),
+        _ => None,
+    }
+}
+
+/// Names of the apps recording from an input device (PulseAudio / PipeWire)
+pub fn microphone_apps() -> Vec<String> {
+    let Ok(output) = Command::new("pactl").args(["list", "source-outputs"]).output() else {
+        return Vec::new();
+    };
+    let mut apps = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((key, value)) = line.trim().split_once(" = ") else {
+            continue;
+        };
+        if matches!(key, "application.name" | "application.process.binary") {
+            let value = value.trim_matches('"').to_string();
+            if !apps.contains(&value) {
+                apps.push(value);
+            }
+        }
+    }
+    apps
+}
+
+/// XDG autostart entry that starts the app at login
+fn autostart_entry_path(app_id: &str) -> Result<std::path::PathBuf, String> {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => std::path::PathBuf::from(std::env::var_os("HOME").ok_or("HOME is not set")?).join(".config"),
+    };
+    Ok(config_dir.join("autostart").join(format!("{}.desktop", app_id)))
+}
+
+/// Whether an XDG autostart entry is installed
+pub fn autostart_enabled(app_id: &str) -> bool {
+    autostart_entry_path(app_id).is_ok_and(|path| path.exists())
+}
+
+/// Install or remove the XDG autostart entry
+pub fn set_autostart(app_id: &str, exe: &std::path::Path, enabled: bool) -> Result<(), String> {
+    let path = autostart_entry_path(app_id)?;
+    if !enabled {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove {}: {}", path.display(), e))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    // An AppImage runs from a temporary mount; start the image itself
+    let exe = std::env::var_os("APPIMAGE")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| exe.to_path_buf());
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=Speekium\nExec=\"{}\"\nX-GNOME-Autostart-enabled=true\n",
+        exe.to_string_lossy().replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    std::fs::write(&path, entry).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Kernel clock ticks per second in /proc/<pid>/stat (USER_HZ, 100 on all mainstream kernels)
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+
+/// Resident memory and CPU time of a process from /proc
+pub fn process_usage(pid: u32) -> Option<super::ProcessUsage> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let rss_kb: u64 = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+
+    // Fields after the command name, which is in parentheses and may contain spaces;
+    // utime and stime (fields 14 and 15) are the 12th and 13th of these
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+
+    Some(super::ProcessUsage {
+        rss_bytes: rss_kb * 1024,
+        cpu_secs: (utime + stime) as f64 / CLOCK_TICKS_PER_SEC,
+    })
+}
@@ -1,6 +1,6 @@
 // src-tauri/src/platform/macos.rs
 //
-// macOS 文字输入实现
+// macOS 文字输入实现 / 低级输入监听 (CGEventTap)
 
 #[cfg(target_os = "macos")]
 #[allow(dead_code)]
@@ -143,3 +143,776 @@ pub fn type_text(text: &str) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Press a special key `count` times with CGEvent
+#[cfg(target_os = "macos")]
+pub fn press_key(key: super::Key, count: usize) -> Result<(), String> {
+    use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let key_code: u16 = match key {
+        super::Key::Enter => 36,      // kVK_Return
+        super::Key::Tab => 48,        // kVK_Tab
+        super::Key::Escape => 53,     // kVK_Escape
+        super::Key::Backspace => 51,  // kVK_Delete
+    };
+
+    let event_source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|e| format!("Failed to create event source: {:?}", e))?;
+
+    for _ in 0..count {
+        for key_down in [true, false] {
+            let event = CGEvent::new_keyboard_event(event_source.clone(), key_code, key_down)
+                .map_err(|e| format!("Failed to create key event: {:?}", e))?;
+            // Don't inherit modifiers the user may still be holding (e.g. the PTT hotkey)
+            event.set_flags(CGEventFlags::CGEventFlagNull);
+            event.post(CGEventTapLocation::Session);
+        }
+    }
+
+    Ok(())
+}
+
+/// Press Cmd+Z in the focused window
+#[cfg(target_os = "macos")]
+pub fn press_undo() -> Result<(), String> {
+    use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let z_key_code: u16 = 6; // kVK_ANSI_Z
+    let event_source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|e| format!("Failed to create event source: {:?}", e))?;
+
+    for key_down in [true, false] {
+        let event = CGEvent::new_keyboard_event(event_source.clone(), z_key_code, key_down)
+            .map_err(|e| format!("Failed to create key event: {:?}", e))?;
+        event.set_flags(CGEventFlags::CGEventFlagCommand);
+        event.post(CGEventTapLocation::Session);
+    }
+
+    Ok(())
+}
+
+/// Post a key down / up pair carrying `text` as its Unicode string
+#[cfg(target_os = "macos")]
+fn post_unicode(event_source: &core_graphics::event_source::CGEventSource, text: &str) -> Result<(), String> {
+    use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
+
+    for key_down in [true, false] {
+        let event = CGEvent::new_keyboard_event(event_source.clone(), 0, key_down)
+            .map_err(|e| format!("Failed to create key event: {:?}", e))?;
+        event.set_flags(CGEventFlags::CGEventFlagNull);
+        event.set_string(text);
+        event.post(CGEventTapLocation::Session);
+    }
+    Ok(())
+}
+
+/// Type text one character at a time with key events, waiting `key_delay` between characters
+///
+/// The characters are attached to the events as Unicode strings, so any
+/// keyboard layout and non-ASCII text work.
+#[cfg(target_os = "macos")]
+pub fn type_keys(text: &str, key_delay: std::time::Duration) -> Result<(), String> {
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let event_source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|e| format!("Failed to create event source: {:?}", e))?;
+
+    // An input method would take the events as keystrokes to compose
+    with_ascii_input_source(|| {
+        let mut buf = [0u8; 4];
+        for ch in text.chars() {
+            post_unicode(&event_source, ch.encode_utf8(&mut buf))?;
+            std::thread::sleep(key_delay);
+        }
+        Ok(())
+    })
+}
+
+/// Text Input Sources bindings (Carbon framework)
+#[cfg(target_os = "macos")]
+mod tis {
+    use core_foundation::string::CFStringRef;
+
+    pub type TISInputSourceRef = *const std::ffi::c_void;
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        pub fn TISCopyCurrentKeyboardInputSource() -> TISInputSourceRef;
+        pub fn TISCopyCurrentASCIICapableKeyboardLayoutInputSource() -> TISInputSourceRef;
+        pub fn TISGetInputSourceProperty(source: TISInputSourceRef, key: CFStringRef) -> *const std::ffi::c_void;
+        pub fn TISSelectInputSource(source: TISInputSourceRef) -> i32;
+
+        pub static kTISPropertyInputSourceType: CFStringRef;
+        pub static kTISTypeKeyboardLayout: CFStringRef;
+    }
+}
+
+/// Time for the system to apply an input source switch before keys are sent
+#[cfg(target_os = "macos")]
+const INPUT_SOURCE_SWITCH_DELAY: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Whether `source` is an input method (Pinyin, Kotoeri, ...) rather than a plain keyboard layout
+#[cfg(target_os = "macos")]
+unsafe fn is_input_method(source: tis::TISInputSourceRef) -> bool {
+    use core_foundation::base::TCFType;
+    use core_foundation::string::{CFString, CFStringRef};
+
+    let kind = tis::TISGetInputSourceProperty(source, tis::kTISPropertyInputSourceType) as CFStringRef;
+    if kind.is_null() {
+        return false;
+    }
+    CFString::wrap_under_get_rule(kind) != CFString::wrap_under_get_rule(tis::kTISTypeKeyboardLayout)
+}
+
+/// Whether the current keyboard input source is an input method (IME)
+#[cfg(target_os = "macos")]
+pub fn ime_active() -> bool {
+    use core_foundation::base::CFRelease;
+
+    unsafe {
+        let current = tis::TISCopyCurrentKeyboardInputSource();
+        if current.is_null() {
+            return false;
+        }
+        let active = is_input_method(current);
+        CFRelease(current);
+        active
+    }
+}
+
+/// Run `f` with an ASCII-capable keyboard layout selected when an input method is active
+///
+/// Switching away commits the text the input method is composing, and keeps
+/// it from intercepting the simulated keys; the input method is selected
+/// again afterwards.
+#[cfg(target_os = "macos")]
+fn with_ascii_input_source<T>(f: impl FnOnce() -> T) -> T {
+    use core_foundation::base::CFRelease;
+
+    unsafe {
+        let current = tis::TISCopyCurrentKeyboardInputSource();
+        if current.is_null() {
+            return f();
+        }
+        if !is_input_method(current) {
+            CFRelease(current);
+            return f();
+        }
+
+        let ascii = tis::TISCopyCurrentASCIICapableKeyboardLayoutInputSource();
+        let switched = !ascii.is_null() && tis::TISSelectInputSource(ascii) == 0;
+        if !ascii.is_null() {
+            CFRelease(ascii);
+        }
+        if switched {
+            std::thread::sleep(INPUT_SOURCE_SWITCH_DELAY);
+        } else {
+            eprintln!("[TEXT INPUT] Failed to switch away from the input method");
+        }
+
+        let result = f();
+
+        if switched {
+            std::thread::sleep(INPUT_SOURCE_SWITCH_DELAY);
+            tis::TISSelectInputSource(current);
+        }
+        CFRelease(current);
+        result
+    }
+}
+
+/// Longest string a single key event can carry, in UTF-16 code units
+#[cfg(target_os = "macos")]
+const UNICODE_EVENT_MAX_UNITS: usize = 20;
+
+/// Insert text by injecting it as Unicode key events, bypassing the clipboard and any input method
+///
+/// Used instead of pasting while an input method is active: Cmd+V during a
+/// composition can mix the pasted text with the half-composed one.
+#[cfg(target_os = "macos")]
+pub fn insert_text_unicode(text: &str) -> Result<(), String> {
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let event_source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|e| format!("Failed to create event source: {:?}", e))?;
+
+    with_ascii_input_source(|| {
+        // Split on character boundaries, so surrogate pairs stay in one event
+        let mut start = 0;
+        let mut units = 0;
+        for (i, ch) in text.char_indices() {
+            if units + ch.len_utf16() > UNICODE_EVENT_MAX_UNITS {
+                post_unicode(&event_source, &text[start..i])?;
+                start = i;
+                units = 0;
+            }
+            units += ch.len_utf16();
+        }
+        if start < text.len() {
+            post_unicode(&event_source, &text[start..])?;
+        }
+        Ok(())
+    })
+}
+
+/// Accessibility API bindings (ApplicationServices framework)
+#[cfg(target_os = "macos")]
+mod ax {
+    use core_foundation::base::CFTypeRef;
+    use core_foundation::string::CFStringRef;
+
+    pub type AXUIElementRef = CFTypeRef;
+    pub type AXError = i32;
+
+    pub const AX_ERROR_SUCCESS: AXError = 0;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        pub fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+        pub fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+        pub fn AXUIElementIsAttributeSettable(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            settable: *mut u8,
+        ) -> AXError;
+        pub fn AXUIElementSetAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: CFTypeRef,
+        ) -> AXError;
+    }
+}
+
+/// Insert text at the cursor of the focused element via the Accessibility API
+///
+/// Replaces the element's selected text (plain insertion when nothing is
+/// selected) and leaves the clipboard untouched. Returns an error when the
+/// focused element doesn't expose a settable selection, so the caller can
+/// fall back to [`type_text`].
+#[cfg(target_os = "macos")]
+pub fn insert_text_accessibility(text: &str) -> Result<(), String> {
+    use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
+    use core_foundation::string::CFString;
+    use ax::*;
+
+    let focused_attr = CFString::from_static_string("AXFocusedUIElement");
+    let selected_text_attr = CFString::from_static_string("AXSelectedText");
+    let value = CFString::new(text);
+
+    unsafe {
+        // 1. Find the focused element
+        let system = AXUIElementCreateSystemWide();
+        if system.is_null() {
+            return Err("Failed to create system-wide accessibility element".to_string());
+        }
+        let mut focused: CFTypeRef = std::ptr::null();
+        let err = AXUIElementCopyAttributeValue(system, focused_attr.as_concrete_TypeRef(), &mut focused);
+        CFRelease(system);
+        if err != AX_ERROR_SUCCESS || focused.is_null() {
+            return Err(format!("No focused accessibility element (AXError {})", err));
+        }
+
+        // 2. Make sure its selection can be replaced
+        let mut settable: u8 = 0;
+        let err = AXUIElementIsAttributeSettable(focused, selected_text_attr.as_concrete_TypeRef(), &mut settable);
+        if err != AX_ERROR_SUCCESS || settable == 0 {
+            CFRelease(focused);
+            return Err("Focused element does not support accessibility text insertion".to_string());
+        }
+
+        // 3. Replace the selection with the text
+        let err = AXUIElementSetAttributeValue(focused, selected_text_attr.as_concrete_TypeRef(), value.as_CFTypeRef());
+        CFRelease(focused);
+        if err != AX_ERROR_SUCCESS {
+            return Err(format!("Failed to insert text (AXError {})", err));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the focused element is a password field (AXSecureTextField)
+///
+/// False when it can't be told, e.g. without the Accessibility permission.
+#[cfg(target_os = "macos")]
+pub fn focused_field_is_secure() -> bool {
+    use core_foundation::base::{CFRelease, CFType, CFTypeRef, TCFType};
+    use core_foundation::string::CFString;
+    use ax::*;
+
+    const SECURE_TEXT_FIELD: &str = "AXSecureTextField";
+
+    let focused_attr = CFString::from_static_string("AXFocusedUIElement");
+    let role_attrs = [
+        CFString::from_static_string("AXSubrole"),
+        CFString::from_static_string("AXRole"),
+    ];
+
+    unsafe {
+        let system = AXUIElementCreateSystemWide();
+        if system.is_null() {
+            return false;
+        }
+        let mut focused: CFTypeRef = std::ptr::null();
+        let err = AXUIElementCopyAttributeValue(system, focused_attr.as_concrete_TypeRef(), &mut focused);
+        CFRelease(system);
+        if err != AX_ERROR_SUCCESS || focused.is_null() {
+            return false;
+        }
+
+        // NSSecureTextField reports it as subrole, some toolkits as role
+        let secure = role_attrs.iter().any(|attr| {
+            let mut value: CFTypeRef = std::ptr::null();
+            let err = AXUIElementCopyAttributeValue(focused, attr.as_concrete_TypeRef(), &mut value);
+            if err != AX_ERROR_SUCCESS || value.is_null() {
+                return false;
+            }
+            CFType::wrap_under_create_rule(value)
+                .downcast_into::<CFString>()
+                .is_some_and(|role| role.to_string() == SECURE_TEXT_FIELD)
+        });
+        CFRelease(focused);
+        secure
+    }
+}
+
+/// Application that currently has keyboard focus (NSWorkspace)
+#[cfg(target_os = "macos")]
+pub fn frontmost_app() -> Option<super::FrontmostApp> {
+    use cocoa::base::{id, nil};
+    use objc::{msg_send, sel, sel_impl, class};
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+
+    unsafe fn to_string(ns_string: id) -> Option<String> {
+        if ns_string == nil {
+            return None;
+        }
+        let ptr: *const c_char = msg_send![ns_string, UTF8String];
+        if ptr.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+    }
+
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let app: id = msg_send![workspace, frontmostApplication];
+        if app == nil {
+            return None;
+        }
+        let bundle_id: id = msg_send![app, bundleIdentifier];
+        let name: id = msg_send![app, localizedName];
+        Some(super::FrontmostApp {
+            bundle_id: to_string(bundle_id),
+            name: to_string(name),
+        })
+    }
+}
+
+/// Put text on the clipboard without pasting it
+#[cfg(target_os = "macos")]
+pub fn copy_text(text: &str) -> Result<(), String> {
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::NSString as CFString;
+    use objc::{msg_send, sel, sel_impl, class};
+
+    unsafe {
+        let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
+        let pasteboard_type = CFString::alloc(nil).init_str("public.utf8-plain-text");
+        let ns_string = CFString::alloc(nil).init_str(text);
+
+        let _: () = msg_send![pasteboard, clearContents];
+        let types: id = msg_send![class!(NSArray), arrayWithObject: pasteboard_type];
+        let _: () = msg_send![pasteboard, declareTypes: types owner: nil];
+        let success: bool = msg_send![pasteboard, setString: ns_string forType: pasteboard_type];
+
+        if !success {
+            return Err("Failed to set clipboard content".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy the selection of the focused app with Cmd+C and return it, None if nothing is selected
+///
+/// The previous clipboard text is restored afterwards.
+#[cfg(target_os = "macos")]
+pub fn copy_selection() -> Result<Option<String>, String> {
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::NSString as CFString;
+    use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+    use objc::{msg_send, sel, sel_impl, class};
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+
+    // How long to wait for the focused app to put its selection on the clipboard
+    const COPY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+    let pasteboard: id = unsafe { msg_send![class!(NSPasteboard), generalPasteboard] };
+    let pasteboard_type = unsafe { CFString::alloc(nil).init_str("public.utf8-plain-text") };
+
+    let read_string = || -> Option<String> {
+        unsafe {
+            let content: id = msg_send![pasteboard, stringForType: pasteboard_type];
+            if content == nil {
+                return None;
+            }
+            let ptr: *const c_char = msg_send![content, UTF8String];
+            if ptr.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+        }
+    };
+
+    // 1. Save current clipboard content and its change count
+    let old_content = read_string();
+    let change_count: i64 = unsafe { msg_send![pasteboard, changeCount] };
+
+    // 2. Simulate Cmd+C keypress
+    let event_source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|e| format!("Failed to create event source: {:?}", e))?;
+    let cmd_key_code: u16 = 55;
+    let c_key_code: u16 = 8;
+    for (key_code, key_down) in [
+        (cmd_key_code, true),
+        (c_key_code, true),
+        (c_key_code, false),
+        (cmd_key_code, false),
+    ] {
+        let event = CGEvent::new_keyboard_event(event_source.clone(), key_code, key_down)
+            .map_err(|e| format!("Failed to create key event: {:?}", e))?;
+        // Set explicitly so a modifier still held from the shortcut does not leak in
+        event.set_flags(if key_code == cmd_key_code && !key_down {
+            CGEventFlags::CGEventFlagNull
+        } else {
+            CGEventFlags::CGEventFlagCommand
+        });
+        event.post(CGEventTapLocation::Session);
+    }
+
+    // 3. The change count only moves if the app copied something
+    let started = std::time::Instant::now();
+    loop {
+        let current: i64 = unsafe { msg_send![pasteboard, changeCount] };
+        if current != change_count {
+            break;
+        }
+        if started.elapsed() >= COPY_TIMEOUT {
+            return Ok(None);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let selection = read_string().filter(|text| !text.trim().is_empty());
+
+    // 4. Restore original clipboard content
+    if let Some(old) = old_content {
+        copy_text(&old)?;
+    }
+
+    Ok(selection)
+}
+
+/// Listen for mouse buttons and the Fn key with a CGEventTap (for non-keyboard PTT triggers)
+///
+/// Runs on its own thread for the lifetime of the app. Requires the
+/// Accessibility / Input Monitoring permission, otherwise creating the tap fails.
+#[cfg(target_os = "macos")]
+pub fn start_input_listener<F>(on_event: F) -> Result<(), String>
+where
+    F: Fn(super::RawInputEvent) + Send + 'static,
+{
+    use core_foundation::runloop::{kCFRunLoopCommonModes, CFRunLoop};
+    use core_graphics::event::{
+        CGEventFlags, CGEventTap, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement,
+        CGEventType, EventField,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::channel;
+
+    let (result_tx, result_rx) = channel::<Result<(), String>>();
+
+    std::thread::spawn(move || {
+        let fn_down = AtomicBool::new(false);
+
+        let tap = CGEventTap::new(
+            CGEventTapLocation::HID,
+            CGEventTapPlacement::HeadInsertEventTap,
+            CGEventTapOptions::ListenOnly,
+            vec![
+                CGEventType::OtherMouseDown,
+                CGEventType::OtherMouseUp,
+                CGEventType::FlagsChanged,
+            ],
+            |_proxy, event_type, event| {
+                match event_type {
+                    CGEventType::OtherMouseDown | CGEventType::OtherMouseUp => {
+                        let button = event.get_integer_value_field(EventField::MOUSE_EVENT_BUTTON_NUMBER);
+                        on_event(super::RawInputEvent::MouseButton {
+                            button,
+                            pressed: matches!(event_type, CGEventType::OtherMouseDown),
+                        });
+                    }
+                    CGEventType::FlagsChanged => {
+                        // Fn state is a modifier flag; only report changes
+                        let pressed = event.get_flags().contains(CGEventFlags::CGEventFlagSecondaryFn);
+                        if fn_down.swap(pressed, Ordering::SeqCst) != pressed {
+                            on_event(super::RawInputEvent::FnKey { pressed });
+                        }
+                    }
+                    _ => {}
+                }
+                None
+            },
+        );
+
+        let tap = match tap {
+            Ok(tap) => tap,
+            Err(_) => {
+                let _ = result_tx.send(Err(
+                    "Failed to create event tap (Input Monitoring permission required)".to_string(),
+                ));
+                return;
+            }
+        };
+
+        let source = match tap.mach_port.create_runloop_source(0) {
+            Ok(source) => source,
+            Err(_) => {
+                let _ = result_tx.send(Err("Failed to create event tap run loop source".to_string()));
+                return;
+            }
+        };
+
+        let run_loop = CFRunLoop::get_current();
+        unsafe {
+            run_loop.add_source(&source, kCFRunLoopCommonModes);
+        }
+        tap.enable();
+        let _ = result_tx.send(Ok(()));
+
+        CFRunLoop::run_current();
+    });
+
+    result_rx
+        .recv()
+        .map_err(|_| "Input listener thread exited".to_string())?
+}
+
+// ============================================================================
+// Privacy Permissions (TCC)
+// ============================================================================
+
+/// Permission check bindings (ApplicationServices, IOKit, AVFoundation)
+#[cfg(target_os = "macos")]
+mod tcc {
+    use cocoa::base::id;
+    use core_foundation::dictionary::CFDictionaryRef;
+    use core_foundation::string::CFStringRef;
+
+    /// IOHIDRequestType for observing keyboard / mouse input
+    pub const IOHID_REQUEST_TYPE_LISTEN_EVENT: u32 = 1;
+
+    /// IOHIDAccessType values
+    pub const IOHID_ACCESS_GRANTED: u32 = 0;
+    pub const IOHID_ACCESS_DENIED: u32 = 1;
+
+    /// AVAuthorizationStatus values
+    pub const AV_AUTHORIZATION_NOT_DETERMINED: isize = 0;
+    pub const AV_AUTHORIZATION_AUTHORIZED: isize = 3;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        pub static kAXTrustedCheckOptionPrompt: CFStringRef;
+        pub fn AXIsProcessTrusted() -> u8;
+        pub fn AXIsProcessTrustedWithOptions(options: CFDictionaryRef) -> u8;
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        pub fn IOHIDCheckAccess(request_type: u32) -> u32;
+        pub fn IOHIDRequestAccess(request_type: u32) -> u8;
+    }
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        pub static AVMediaTypeAudio: id;
+    }
+}
+
+/// Microphone access (AVCaptureDevice authorization status)
+#[cfg(target_os = "macos")]
+pub fn microphone_permission() -> super::PermissionStatus {
+    use objc::{msg_send, sel, sel_impl, class};
+    use super::PermissionStatus;
+
+    let status: isize = unsafe { msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: tcc::AVMediaTypeAudio] };
+    match status {
+        tcc::AV_AUTHORIZATION_AUTHORIZED => PermissionStatus::Granted,
+        tcc::AV_AUTHORIZATION_NOT_DETERMINED => PermissionStatus::NotDetermined,
+        // Denied or restricted by policy
+        _ => PermissionStatus::Denied,
+    }
+}
+
+/// Accessibility access (needed to post keyboard events and insert text)
+///
+/// macOS does not tell "never asked" from "denied", so both are `Denied`.
+#[cfg(target_os = "macos")]
+pub fn accessibility_permission() -> super::PermissionStatus {
+    if unsafe { tcc::AXIsProcessTrusted() } != 0 {
+        super::PermissionStatus::Granted
+    } else {
+        super::PermissionStatus::Denied
+    }
+}
+
+/// Show the Accessibility prompt (opens System Settings on the user's confirmation)
+#[cfg(target_os = "macos")]
+pub fn request_accessibility_permission() {
+    use core_foundation::base::TCFType;
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::string::CFString;
+
+    let prompt = unsafe { CFString::wrap_under_get_rule(tcc::kAXTrustedCheckOptionPrompt) };
+    let options = CFDictionary::from_CFType_pairs(&[(prompt, CFBoolean::true_value())]);
+    unsafe {
+        tcc::AXIsProcessTrustedWithOptions(options.as_concrete_TypeRef());
+    }
+}
+
+/// Input Monitoring access (needed by the event tap for mouse-button / Fn PTT)
+#[cfg(target_os = "macos")]
+pub fn input_monitoring_permission() -> super::PermissionStatus {
+    match unsafe { tcc::IOHIDCheckAccess(tcc::IOHID_REQUEST_TYPE_LISTEN_EVENT) } {
+        tcc::IOHID_ACCESS_GRANTED => super::PermissionStatus::Granted,
+        tcc::IOHID_ACCESS_DENIED => super::PermissionStatus::Denied,
+        _ => super::PermissionStatus::NotDetermined,
+    }
+}
+
+/// Show the Input Monitoring prompt (only the first time; afterwards it must be changed in System Settings)
+#[cfg(target_os = "macos")]
+pub fn request_input_monitoring_permission() {
+    unsafe {
+        tcc::IOHIDRequestAccess(tcc::IOHID_REQUEST_TYPE_LISTEN_EVENT);
+    }
+}
+
+/// Whether a Focus / Do Not Disturb mode is on
+///
+/// There is no public API; the Focus service keeps its active assertions in a
+/// JSON file in the user's library (macOS 12+). None if it cannot be read.
+#[cfg(target_os = "macos")]
+pub fn system_dnd_active() -> Option<bool> {
+    let path = std::path::PathBuf::from(std::env::var_os("HOME")?)
+        .join("Library/DoNotDisturb/DB/Assertions.json");
+    let content = std::fs::read_to_string(path).ok()?;
+    let assertions: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let active = assertions
+        .get("data")
+        .and_then(|v| v.as_array())
+        .is_some_and(|data| {
+            data.iter().any(|entry| {
+                entry
+                    .get("storeAssertionRecords")
+                    .and_then(|v| v.as_array())
+                    .is_some_and(|records| !records.is_empty())
+            })
+        });
+    Some(active)
+}
+
+/// LaunchAgent that starts the app at login
+#[cfg(target_os = "macos")]
+fn launch_agent_path(app_id: &str) -> Result<std::path::PathBuf, String> {
+    let home = std::env::var_os("HOME").ok_or("HOME is not set")?;
+    Ok(std::path::PathBuf::from(home)
+        .join("Library/LaunchAgents")
+        .join(format!("{}.plist", app_id)))
+}
+
+/// Whether a login LaunchAgent is installed
+#[cfg(target_os = "macos")]
+pub fn autostart_enabled(app_id: &str) -> bool {
+    launch_agent_path(app_id).is_ok_and(|path| path.exists())
+}
+
+/// Install or remove the login LaunchAgent
+#[cfg(target_os = "macos")]
+pub fn set_autostart(app_id: &str, exe: &std::path::Path, enabled: bool) -> Result<(), String> {
+    let path = launch_agent_path(app_id)?;
+    if !enabled {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove {}: {}", path.display(), e))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let escape = |s: &str| s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    let plist = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#,
+        escape(app_id),
+        escape(&exe.to_string_lossy())
+    );
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    std::fs::write(&path, plist).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Resident memory and CPU time of a process (from ps)
+#[cfg(target_os = "macos")]
+pub fn process_usage(pid: u32) -> Option<super::ProcessUsage> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "rss=,time=", "-p", &pid.to_string()])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut fields = text.split_whitespace();
+    let rss_kb: u64 = fields.next()?.parse().ok()?;
+
+    // CPU time as [[dd-]hh:]mm:ss.cc
+    let time = fields.next()?;
+    let (days, time) = match time.split_once('-') {
+        Some((days, rest)) => (days.parse::<f64>().ok()?, rest),
+        None => (0.0, time),
+    };
+    let mut secs = 0.0;
+    for part in time.split(':') {
+        secs = secs * 60.0 + part.parse::<f64>().ok()?;
+    }
+
+    Some(super::ProcessUsage {
+        rss_bytes: rss_kb * 1024,
+        cpu_secs: days * 86_400.0 + secs,
+    })
+}
@@ -6,20 +6,379 @@
 pub mod macos;
 
 #[cfg(target_os = "macos")]
-pub use macos::type_text;
+pub use macos::{
+    autostart_enabled, copy_selection, copy_text, focused_field_is_secure, frontmost_app, ime_active,
+    insert_text_accessibility, insert_text_unicode, press_key, press_undo, process_usage, set_autostart, system_dnd_active, type_keys,
+    type_text,
+};
+
+#[cfg(target_os = "windows")]
+pub mod windows;
+
+#[cfg(target_os = "windows")]
+pub use windows::{
+    autostart_enabled, copy_selection, copy_text, microphone_apps, press_key, press_undo, process_usage,
+    set_autostart, type_keys, type_text,
+};
+
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+#[cfg(target_os = "linux")]
+pub use linux::{
+    autostart_enabled, copy_selection, copy_text, microphone_apps, press_key, press_undo, process_usage,
+    set_autostart, system_dnd_active, type_keys, type_text,
+};
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn copy_text(_text: &str) -> Result<(), String> {
+    Err("Clipboard is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn copy_selection() -> Result<Option<String>, String> {
+    Err("Reading the selection is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn type_text(_text: &str) -> Result<(), String> {
+    Err("Text input is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn type_keys(_text: &str, _key_delay: std::time::Duration) -> Result<(), String> {
+    Err("Key simulation is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn press_key(_key: Key, _count: usize) -> Result<(), String> {
+    Err("Key simulation is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn press_undo() -> Result<(), String> {
+    Err("Key simulation is not supported on this platform".to_string())
+}
+
+/// State of an OS privacy permission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionStatus {
+    Granted,
+    Denied,
+    /// Never asked; requesting it shows the OS prompt
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    NotDetermined,
+    /// The platform has no such permission
+    NotRequired,
+    /// Could not be checked
+    Unknown,
+}
+
+/// Special key that can be pressed in the focused window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+}
+
+impl Key {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "enter" | "return" => Some(Key::Enter),
+            "tab" => Some(Key::Tab),
+            "escape" | "esc" => Some(Key::Escape),
+            "backspace" => Some(Key::Backspace),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub use macos::start_input_listener;
+
+/// Whether start_input_listener can work here (mouse button / Fn key PTT)
+pub const INPUT_LISTENER_SUPPORTED: bool = cfg!(target_os = "macos");
+
+/// Low-level input event used by alternative PTT triggers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum RawInputEvent {
+    /// Non-primary mouse button (2 = middle, 3/4 = side buttons)
+    MouseButton { button: i64, pressed: bool },
+    /// Fn / Globe key
+    FnKey { pressed: bool },
+}
+
+/// Low-level input listening is only implemented with CGEventTap on macOS
+#[cfg(not(target_os = "macos"))]
+pub fn start_input_listener<F>(_on_event: F) -> Result<(), String>
+where
+    F: Fn(RawInputEvent) + Send + 'static,
+{
+    Err("Mouse button / Fn key PTT is not supported on this platform".to_string())
+}
+
+/// Application that has keyboard focus
+#[derive(Debug, Clone, Default)]
+pub struct FrontmostApp {
+    /// Bundle identifier (e.g. "com.tinyspeck.slackmacgap")
+    pub bundle_id: Option<String>,
+    /// Display name (e.g. "Slack")
+    pub name: Option<String>,
+}
+
+impl FrontmostApp {
+    /// Identifiers a text-input profile may be keyed by, most specific first
+    pub fn ids(&self) -> Vec<&str> {
+        [self.bundle_id.as_deref(), self.name.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// Frontmost app detection is only implemented with NSWorkspace on macOS
+#[cfg(not(target_os = "macos"))]
+pub fn frontmost_app() -> Option<FrontmostApp> {
+    None
+}
+
+/// Password field detection is only implemented with the Accessibility API on macOS
+#[cfg(not(target_os = "macos"))]
+pub fn focused_field_is_secure() -> bool {
+    false
+}
+
+/// Do Not Disturb detection is implemented for macOS Focus and GNOME
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn system_dnd_active() -> Option<bool> {
+    None
+}
+
+/// Resident memory and total CPU time of a process
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessUsage {
+    pub rss_bytes: u64,
+    /// User + system CPU time since the process started
+    pub cpu_secs: f64,
+}
+
+/// Process usage is read natively on macOS, Windows and Linux
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn process_usage(_pid: u32) -> Option<ProcessUsage> {
+    None
+}
+
+/// Start at login is implemented for macOS, Windows and Linux
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn autostart_enabled(_app_id: &str) -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn set_autostart(_app_id: &str, _exe: &std::path::Path, _enabled: bool) -> Result<(), String> {
+    Err("Start at login is not supported on this platform".to_string())
+}
+
+/// macOS has no public API telling which app uses the microphone
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn microphone_apps() -> Vec<String> {
+    Vec::new()
+}
+
+use crate::config::TextInputProfile;
+use crate::text_commands::TextStep;
 
 // Tauri command - must be in the same module where it's registered
 #[tauri::command]
 pub async fn type_text_command(text: String) -> Result<String, String> {
+    type_transcript(&text)
+}
+
+/// Remove the text typed last into the focused app (see text_commands::undo_last)
+#[tauri::command]
+pub async fn undo_last_typing(app: tauri::AppHandle) -> Result<String, String> {
+    use tauri::Manager;
+
+    // Keys sent now would go to our own window
+    if app.webview_windows().values().any(|w| w.is_focused().unwrap_or(false)) {
+        return Err("Focus the app the text was typed into first".to_string());
+    }
+    undo_typing()
+}
+
+/// Delay before pasting from a shortcut, so its modifier keys don't combine with the typed text
+const PASTE_SHORTCUT_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Pause between the parts of a text inserted in chunks, so the app can catch up
+const CHUNK_PAUSE: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Type the most recent ASR result into the focused app (any work mode)
+///
+/// Runs on its own thread; does nothing if there is no transcript yet.
+pub fn paste_last_transcription() {
+    let Some(text) = crate::daemon::LAST_TRANSCRIPT.lock().unwrap().clone() else {
+        return;
+    };
+    paste_text_after(text, PASTE_SHORTCUT_DELAY);
+}
+
+/// Undo the last insertion from a shortcut (on its own thread)
+///
+/// Waits for the shortcut's modifiers to be released: Alt+Backspace deletes a word.
+pub fn undo_last_typing_from_shortcut() {
+    std::thread::spawn(|| {
+        std::thread::sleep(PASTE_SHORTCUT_DELAY);
+        if let Err(e) = undo_typing() {
+            eprintln!("[TEXT INPUT] Failed to undo typing: {}", e);
+        }
+    });
+}
+
+fn undo_typing() -> Result<String, String> {
+    let app = frontmost_app();
+    let ids = app.as_ref().map(|app| app.ids()).unwrap_or_default();
+    let profile = crate::config::text_input_profile(&ids);
+    let removed = crate::text_commands::undo_last(app.as_ref(), profile.undoes_with_shortcut())?;
+    Ok(format!("Removed {} characters", removed))
+}
+
+/// Refuse to type into a password field, telling the windows why
+///
+/// Returns true (after emitting `typing-blocked`) when the focused field is secure.
+pub fn block_secure_field(app: &tauri::AppHandle) -> bool {
+    if !focused_field_is_secure() {
+        return false;
+    }
+    let target = frontmost_app().and_then(|app| app.name);
+    println!("[TEXT INPUT] Focused field is a password field, not typing");
+    crate::events::emit_event(app, crate::events::AppEvent::TypingBlocked(crate::types::TypingBlockedPayload {
+        reason: "secure_field".to_string(),
+        app: target,
+        message: "Dictation is not typed into password fields".to_string(),
+    }));
+    true
+}
+
+/// Type a transcript into the focused app after `delay` (on its own thread)
+///
+/// The delay lets shortcut modifiers be released or focus move back to the target app.
+pub fn paste_text_after(text: String, delay: std::time::Duration) {
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        if let Err(e) = type_transcript(&text) {
+            eprintln!("[TEXT INPUT] Failed to paste transcription: {}", e);
+        }
+    });
+}
+
+/// Type a transcript like text-input mode does (app profile, spoken commands)
+fn type_transcript(text: &str) -> Result<String, String> {
+    if crate::daemon::APP_HANDLE.get().map_or_else(focused_field_is_secure, block_secure_field) {
+        return Err("The focused field is a password field".to_string());
+    }
+
+    // Apply the focused app's text-input profile
+    let app = frontmost_app().unwrap_or_default();
+    let profile = crate::config::text_input_profile(&app.ids());
+    if !profile.is_enabled() {
+        let app_name = app.name.as_deref().unwrap_or("this application");
+        return Ok(format!("Text input is disabled for {}", app_name));
+    }
+    if let Some(app_name) = crate::shortcut_apps::frontmost_blocking_app() {
+        return Ok(format!("Dictation is turned off in {}", app_name));
+    }
 
+    // Turn spoken commands ("new line", "press enter", ...) into edits
+    let text = crate::textproc::process_symbols(text);
+    let mut steps = crate::text_commands::parse_transcript(&text);
+    if let Some(TextStep::Text(last)) = steps.last_mut() {
+        *last = profile.apply(last);
+    }
+    let target = Some(app).filter(|app| !app.ids().is_empty());
+    let typed = crate::text_commands::run(steps, target, |text| insert_chunks(text, &profile))?;
+    Ok(format!("Typed {} characters", typed))
+}
+
+/// Parts of at most `max_chars` characters, split after whitespace where possible (0 = no limit)
+fn split_chunks(text: &str, max_chars: usize) -> Vec<&str> {
+    if max_chars == 0 {
+        return vec![text];
+    }
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > max_chars {
+        let limit = rest.char_indices().nth(max_chars).map(|(i, _)| i).unwrap_or(rest.len());
+        let end = match rest[..limit].rfind(char::is_whitespace) {
+            Some(i) if i > 0 => i + rest[i..].chars().next().map(char::len_utf8).unwrap_or(1),
+            _ => limit,
+        };
+        chunks.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Insert text the way the app's profile asks: pasted or typed, whole or in chunks
+fn insert_chunks(text: &str, profile: &TextInputProfile) -> Result<(), String> {
+    for (i, chunk) in split_chunks(text, profile.chunk_chars()).into_iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(CHUNK_PAUSE);
+        }
+        if profile.types_keys() {
+            type_keys(chunk, profile.key_delay())?;
+        } else {
+            insert_text(chunk)?;
+        }
+    }
+    Ok(())
+}
+
+/// Insert text into the focused field with the configured strategy
+fn insert_text(text: &str) -> Result<(), String> {
+    // Insert directly on macOS when opted in, so clipboard managers aren't clobbered
     #[cfg(target_os = "macos")]
     {
-        type_text(&text)?;
-        Ok(format!("Typed {} characters", text.chars().count()))
+        use crate::types::TextInputStrategy;
+
+        // Pasting while an input method composes can garble CJK text
+        let ime_safe = crate::config::read_config_value("text_input_ime_safe")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        if ime_safe && ime_active() {
+            return insert_text_unicode(text);
+        }
+
+        let strategy = crate::config::read_config_value("text_input_strategy")
+            .and_then(|v| v.as_str().and_then(TextInputStrategy::from_str))
+            .unwrap_or(TextInputStrategy::Clipboard);
+        if strategy == TextInputStrategy::Accessibility {
+            match insert_text_accessibility(text) {
+                Ok(()) => return Ok(()),
+                Err(e) => eprintln!("[TEXT INPUT] {}, falling back to clipboard", e),
+            }
+        }
     }
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        Err("Text input is only supported on macOS".to_string())
+    type_text(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_chunks() {
+        assert_eq!(split_chunks("hello world", 0), vec!["hello world"]);
+        assert_eq!(split_chunks("hello world again", 12), vec!["hello world ", "again"]);
+        assert_eq!(split_chunks("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(split_chunks("你好世界", 2), vec!["你好", "世界"]);
+        assert_eq!(split_chunks("", 5), vec![""]);
     }
 }
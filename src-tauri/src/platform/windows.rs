@@ -0,0 +1,419 @@
+// src-tauri/src/platform/windows.rs
+//
+// Windows 文字输入实现
+
+use windows_sys::Win32::Foundation::{GlobalFree, HANDLE};
+use windows_sys::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, GetClipboardData, GetClipboardSequenceNumber, OpenClipboard,
+    SetClipboardData,
+};
+use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+use windows_sys::Win32::System::Ole::CF_UNICODETEXT;
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, VIRTUAL_KEY,
+    VK_BACK, VK_CONTROL, VK_ESCAPE, VK_RETURN, VK_TAB,
+};
+
+// Virtual key codes for 'C', 'V' and 'Z'
+const VK_C: VIRTUAL_KEY = 0x43;
+const VK_V: VIRTUAL_KEY = 0x56;
+const VK_Z: VIRTUAL_KEY = 0x5A;
+
+/// How long to wait for the focused app to put its selection on the clipboard
+const COPY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// RAII guard that keeps the clipboard open for the current thread
+struct ClipboardGuard;
+
+impl ClipboardGuard {
+    fn open() -> Result<Self, String> {
+        // The clipboard may be briefly held by another process, retry a few times
+        for _ in 0..10 {
+            if unsafe { OpenClipboard(std::ptr::null_mut()) } != 0 {
+                return Ok(ClipboardGuard);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        Err("Failed to open clipboard".to_string())
+    }
+}
+
+impl Drop for ClipboardGuard {
+    fn drop(&mut self) {
+        unsafe {
+            CloseClipboard();
+        }
+    }
+}
+
+/// Read current clipboard text as UTF-16, None if empty or not text
+fn read_clipboard() -> Option<Vec<u16>> {
+    let _guard = ClipboardGuard::open().ok()?;
+
+    unsafe {
+        let handle: HANDLE = GetClipboardData(CF_UNICODETEXT as u32);
+        if handle.is_null() {
+            return None;
+        }
+
+        let ptr = GlobalLock(handle) as *const u16;
+        if ptr.is_null() {
+            return None;
+        }
+
+        // Copy the NUL-terminated UTF-16 string
+        let mut len = 0;
+        while *ptr.add(len) != 0 {
+            len += 1;
+        }
+        let content = std::slice::from_raw_parts(ptr, len).to_vec();
+
+        GlobalUnlock(handle);
+        Some(content)
+    }
+}
+
+/// Replace clipboard content with the given UTF-16 text (without trailing NUL)
+fn write_clipboard(text: &[u16]) -> Result<(), String> {
+    let _guard = ClipboardGuard::open()?;
+
+    unsafe {
+        if EmptyClipboard() == 0 {
+            return Err("Failed to empty clipboard".to_string());
+        }
+
+        let byte_len = (text.len() + 1) * std::mem::size_of::<u16>();
+        let hmem = GlobalAlloc(GMEM_MOVEABLE, byte_len);
+        if hmem.is_null() {
+            return Err("Failed to allocate clipboard memory".to_string());
+        }
+
+        let ptr = GlobalLock(hmem) as *mut u16;
+        if ptr.is_null() {
+            GlobalFree(hmem);
+            return Err("Failed to lock clipboard memory".to_string());
+        }
+        std::ptr::copy_nonoverlapping(text.as_ptr(), ptr, text.len());
+        *ptr.add(text.len()) = 0;
+        GlobalUnlock(hmem);
+
+        // On success the system owns the memory, only free it on failure
+        if SetClipboardData(CF_UNICODETEXT as u32, hmem).is_null() {
+            GlobalFree(hmem);
+            return Err("Failed to set clipboard data".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn key_input(vk: VIRTUAL_KEY, key_up: bool) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: vk,
+                wScan: 0,
+                dwFlags: if key_up { KEYEVENTF_KEYUP } else { 0 },
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }
+}
+
+/// Simulate Ctrl+<key> via SendInput
+fn send_ctrl_shortcut(vk: VIRTUAL_KEY) -> bool {
+    let inputs = [
+        key_input(VK_CONTROL, false),
+        key_input(vk, false),
+        key_input(vk, true),
+        key_input(VK_CONTROL, true),
+    ];
+
+    let sent = unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_ptr(),
+            std::mem::size_of::<INPUT>() as i32,
+        )
+    };
+
+    sent as usize == inputs.len()
+}
+
+/// Simulate Ctrl+V keypress via SendInput
+fn send_paste() -> Result<(), String> {
+    if !send_ctrl_shortcut(VK_V) {
+        return Err("Failed to simulate Ctrl+V (input blocked by another application?)".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn type_text(text: &str) -> Result<(), String> {
+    // 1. Save current clipboard content
+    let old_content = read_clipboard();
+
+    // Define clipboard restoration function
+    let restore_clipboard = || -> Result<(), String> {
+        if let Some(ref content) = old_content {
+            write_clipboard(content)
+                .map_err(|e| format!("Failed to restore clipboard: {}", e))?;
+        }
+        Ok(())
+    };
+
+    // 2. Set new content to clipboard
+    let wide: Vec<u16> = text.encode_utf16().collect();
+    if let Err(e) = write_clipboard(&wide) {
+        let _ = restore_clipboard();
+        return Err(format!("Failed to set clipboard content: {}", e));
+    }
+
+    // 3. Simulate Ctrl+V keypress
+    if let Err(e) = send_paste() {
+        let _ = restore_clipboard();
+        return Err(e);
+    }
+
+    // Wait for paste to complete
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    // 4. Restore original clipboard content
+    restore_clipboard()?;
+
+    Ok(())
+}
+
+/// Press a special key `count` times via SendInput
+pub fn press_key(key: super::Key, count: usize) -> Result<(), String> {
+    let vk = match key {
+        super::Key::Enter => VK_RETURN,
+        super::Key::Tab => VK_TAB,
+        super::Key::Escape => VK_ESCAPE,
+        super::Key::Backspace => VK_BACK,
+    };
+
+    let inputs: Vec<INPUT> = (0..count)
+        .flat_map(|_| [key_input(vk, false), key_input(vk, true)])
+        .collect();
+    if inputs.is_empty() {
+        return Ok(());
+    }
+
+    let sent = unsafe {
+        SendInput(
+            inputs.len() as u32,
+            inputs.as_ptr(),
+            std::mem::size_of::<INPUT>() as i32,
+        )
+    };
+
+    if sent as usize != inputs.len() {
+        return Err("Failed to simulate key press (input blocked by another application?)".to_string());
+    }
+
+    Ok(())
+}
+
+/// Press Ctrl+Z in the focused window
+pub fn press_undo() -> Result<(), String> {
+    if !send_ctrl_shortcut(VK_Z) {
+        return Err("Failed to simulate Ctrl+Z (input blocked by another application?)".to_string());
+    }
+
+    Ok(())
+}
+
+/// Key event carrying one UTF-16 code unit instead of a virtual key
+fn unicode_input(unit: u16, key_up: bool) -> INPUT {
+    INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: 0,
+                wScan: unit,
+                dwFlags: KEYEVENTF_UNICODE | if key_up { KEYEVENTF_KEYUP } else { 0 },
+                time: 0,
+                dwExtraInfo: 0,
+            },
+        },
+    }
+}
+
+/// Type text one character at a time via SendInput, waiting `key_delay` between characters
+pub fn type_keys(text: &str, key_delay: std::time::Duration) -> Result<(), String> {
+    let mut buf = [0u16; 2];
+    for ch in text.chars() {
+        // Characters outside the BMP are sent as a surrogate pair
+        let units = ch.encode_utf16(&mut buf);
+        let inputs: Vec<INPUT> = units
+            .iter()
+            .flat_map(|&unit| [unicode_input(unit, false), unicode_input(unit, true)])
+            .collect();
+
+        let sent = unsafe {
+            SendInput(
+                inputs.len() as u32,
+                inputs.as_ptr(),
+                std::mem::size_of::<INPUT>() as i32,
+            )
+        };
+        if sent as usize != inputs.len() {
+            return Err("Failed to type text (input blocked by another application?)".to_string());
+        }
+        std::thread::sleep(key_delay);
+    }
+
+    Ok(())
+}
+
+/// Put text on the clipboard without pasting it
+pub fn copy_text(text: &str) -> Result<(), String> {
+    let wide: Vec<u16> = text.encode_utf16().collect();
+    write_clipboard(&wide)
+}
+
+/// Copy the selection of the focused app with Ctrl+C and return it, None if nothing is selected
+///
+/// The previous clipboard text is restored afterwards.
+pub fn copy_selection() -> Result<Option<String>, String> {
+    let old_content = read_clipboard();
+    let sequence = unsafe { GetClipboardSequenceNumber() };
+
+    if !send_ctrl_shortcut(VK_C) {
+        return Err("Failed to simulate Ctrl+C (input blocked by another application?)".to_string());
+    }
+
+    // The sequence number only changes if the app copied something
+    let started = std::time::Instant::now();
+    while unsafe { GetClipboardSequenceNumber() } == sequence {
+        if started.elapsed() >= COPY_TIMEOUT {
+            return Ok(None);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let selection = read_clipboard()
+        .map(|content| String::from_utf16_lossy(&content))
+        .filter(|text| !text.trim().is_empty());
+
+    if let Some(old) = old_content {
+        let _ = write_clipboard(&old);
+    }
+
+    Ok(selection)
+}
+
+/// Microphone consent entries of desktop apps (one subkey per executable)
+const MICROPHONE_CONSENT_KEY: &str =
+    r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\NonPackaged";
+
+/// Executable names (without ".exe") of the desktop apps using the microphone
+///
+/// Windows records a start and stop time per app; a stop time of 0 means the
+/// app is recording right now.
+pub fn microphone_apps() -> Vec<String> {
+    let Ok(output) = std::process::Command::new("reg")
+        .args(["query", MICROPHONE_CONSENT_KEY, "/s", "/v", "LastUsedTimeStop"])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let mut apps = Vec::new();
+    let mut current_key: Option<String> = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if line.starts_with("HKEY_") {
+            current_key = Some(line.trim().to_string());
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.first() == Some(&"LastUsedTimeStop") && fields.last() == Some(&"0x0") {
+            // Subkey names are paths with '#' as separator: C:#Program Files#Zoom#bin#Zoom.exe
+            let exe = current_key.as_deref().and_then(|key| key.rsplit('#').next());
+            if let Some(exe) = exe {
+                let name = exe.strip_suffix(".exe").unwrap_or(exe).to_string();
+                if !apps.contains(&name) {
+                    apps.push(name);
+                }
+            }
+        }
+    }
+    apps
+}
+
+/// Programs started at login for the current user
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+/// Whether a login Run entry is registered
+pub fn autostart_enabled(app_id: &str) -> bool {
+    std::process::Command::new("reg")
+        .args(["query", RUN_KEY, "/v", app_id])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Register or remove the login Run entry
+pub fn set_autostart(app_id: &str, exe: &std::path::Path, enabled: bool) -> Result<(), String> {
+    if !enabled {
+        if !autostart_enabled(app_id) {
+            return Ok(());
+        }
+        let output = std::process::Command::new("reg")
+            .args(["delete", RUN_KEY, "/v", app_id, "/f"])
+            .output()
+            .map_err(|e| format!("Failed to run reg: {}", e))?;
+        return if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!("Failed to remove Run entry: {}", String::from_utf8_lossy(&output.stderr).trim()))
+        };
+    }
+
+    let command = format!("\"{}\"", exe.display());
+    let output = std::process::Command::new("reg")
+        .args(["add", RUN_KEY, "/v", app_id, "/t", "REG_SZ", "/d", &command, "/f"])
+        .output()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("Failed to add Run entry: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// Working set and CPU time of a process
+pub fn process_usage(pid: u32) -> Option<super::ProcessUsage> {
+    use windows_sys::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows_sys::Win32::System::ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows_sys::Win32::System::Threading::{GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    // FILETIME durations count 100 ns intervals
+    let seconds = |time: &FILETIME| {
+        (((time.dwHighDateTime as u64) << 32) | time.dwLowDateTime as u64) as f64 / 10_000_000.0
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+        counters.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        let memory_ok = K32GetProcessMemoryInfo(handle, &mut counters, counters.cb) != 0;
+
+        let mut creation: FILETIME = std::mem::zeroed();
+        let mut exit: FILETIME = std::mem::zeroed();
+        let mut kernel: FILETIME = std::mem::zeroed();
+        let mut user: FILETIME = std::mem::zeroed();
+        let times_ok = GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) != 0;
+        CloseHandle(handle);
+
+        (memory_ok && times_ok).then(|| super::ProcessUsage {
+            rss_bytes: counters.WorkingSetSize as u64,
+            cpu_secs: seconds(&kernel) + seconds(&user),
+        })
+    }
+}
@@ -0,0 +1,309 @@
+// ============================================================================
+// Profiles Module - Separate Config and History per Profile
+// ============================================================================
+//
+// A profile has its own config.json (settings and shortcuts) and its own
+// database, e.g. to keep work and personal use apart. The "default" profile
+// is the app data directory itself, so data from before profiles existed
+// stays where it is; other profiles live in profiles/<id>/. profiles.json in
+// the app data directory lists them and names the active one. API keys are
+// shared: they live in the keychain, not in config.json.
+//
+// Switching is refused while a recording or file transcription is running.
+// Every global shortcut is unregistered, the config directory and database
+// move to the new profile, the mode globals are reloaded from its config,
+// the daemon is told to read the new config.json, and the shortcuts are
+// registered again. The ASR vocabulary lives in the database, so it follows. The windows reload on `profile-switched`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager};
+
+use crate::daemon::{APP_STATUS, RECORDING_MODE};
+use crate::state::AppState;
+use crate::types::AppStatus;
+use crate::events::{emit_event, AppEvent};
+
+/// The profile stored directly in the app data directory
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+const PROFILES_FILE_NAME: &str = "profiles.json";
+
+/// Directory holding the other profiles, one subdirectory each
+const PROFILES_DIR_NAME: &str = "profiles";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub created_at: i64,
+}
+
+/// Contents of profiles.json (the default profile is implicit)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    active: Option<String>,
+    #[serde(default)]
+    profiles: Vec<Profile>,
+}
+
+/// All profiles (default first) and the active one
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileList {
+    pub active: String,
+    pub profiles: Vec<Profile>,
+}
+
+/// Active profile id, cached per app data directory
+static ACTIVE: Mutex<Option<(PathBuf, String)>> = Mutex::new(None);
+
+fn read_profiles_file(root: &Path) -> ProfilesFile {
+    std::fs::read_to_string(root.join(PROFILES_FILE_NAME))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Replace profiles.json atomically
+fn write_profiles_file(root: &Path, file: &ProfilesFile) -> Result<(), String> {
+    let path = root.join(PROFILES_FILE_NAME);
+    let tmp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+    std::fs::write(&tmp_path, json).map_err(|e| format!("Failed to write profiles: {}", e))?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to replace profiles: {}", e))
+}
+
+fn set_active(root: &Path, id: &str) {
+    *ACTIVE.lock().unwrap() = Some((root.to_path_buf(), id.to_string()));
+}
+
+/// Id of the active profile in the app data directory `root`
+pub fn active_id(root: &Path) -> String {
+    let mut cached = ACTIVE.lock().unwrap();
+    if let Some((dir, id)) = cached.as_ref() {
+        if dir == root {
+            return id.clone();
+        }
+    }
+
+    // A profile removed from the file falls back to the default
+    let file = read_profiles_file(root);
+    let id = file
+        .active
+        .filter(|id| file.profiles.iter().any(|p| &p.id == id))
+        .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string());
+    *cached = Some((root.to_path_buf(), id.clone()));
+    id
+}
+
+fn profile_dir(root: &Path, id: &str) -> PathBuf {
+    if id == DEFAULT_PROFILE_ID {
+        root.to_path_buf()
+    } else {
+        root.join(PROFILES_DIR_NAME).join(id)
+    }
+}
+
+/// Directory of the active profile (config.json, database)
+pub fn active_dir(root: &Path) -> PathBuf {
+    profile_dir(root, &active_id(root))
+}
+
+/// `name` scoped to the active profile, unchanged for the default one (e.g. keychain entries)
+pub fn scoped_name(name: &str) -> String {
+    let id = crate::config::get_app_data_dir()
+        .map(|root| active_id(&root))
+        .unwrap_or_else(|_| DEFAULT_PROFILE_ID.to_string());
+    if id == DEFAULT_PROFILE_ID {
+        name.to_string()
+    } else {
+        format!("{}:{}", name, id)
+    }
+}
+
+fn list(root: &Path) -> ProfileList {
+    let default = Profile {
+        id: DEFAULT_PROFILE_ID.to_string(),
+        name: "Default".to_string(),
+        created_at: 0,
+    };
+    let mut profiles = vec![default];
+    profiles.extend(read_profiles_file(root).profiles);
+    ProfileList { active: active_id(root), profiles }
+}
+
+/// Directory-safe id derived from the name, unique among `existing`
+fn new_id(name: &str, existing: &[Profile]) -> String {
+    let slug = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+    // Names without ASCII letters (e.g. Chinese) get a random id
+    let base = if slug.is_empty() {
+        uuid::Uuid::new_v4().simple().to_string()[..8].to_string()
+    } else {
+        slug
+    };
+
+    let taken = |id: &str| id == DEFAULT_PROFILE_ID || existing.iter().any(|p| p.id == id);
+    if !taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|id| !taken(id))
+        .unwrap_or(base)
+}
+
+fn create(root: &Path, name: &str, copy_settings: bool) -> Result<Profile, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name must not be empty".to_string());
+    }
+    let mut file = read_profiles_file(root);
+    if name.eq_ignore_ascii_case("default") || file.profiles.iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+        return Err(format!("A profile named {} already exists", name));
+    }
+
+    let profile = Profile {
+        id: new_id(name, &file.profiles),
+        name: name.to_string(),
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let dir = profile_dir(root, &profile.id);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create profile directory: {}", e))?;
+
+    // Otherwise the profile starts from the defaults
+    if copy_settings {
+        let current = crate::config::get_config_path()?;
+        if current.exists() {
+            std::fs::copy(&current, dir.join("config.json"))
+                .map_err(|e| format!("Failed to copy settings: {}", e))?;
+        }
+    }
+
+    file.profiles.push(profile.clone());
+    write_profiles_file(root, &file)?;
+    println!("[PROFILES] Created profile {} ({})", profile.name, profile.id);
+    Ok(profile)
+}
+
+/// Make `id` the active profile and reopen its database; undone on failure
+fn activate(app: &tauri::AppHandle, root: &Path, id: &str) -> Result<(), String> {
+    let state = app.try_state::<AppState>().ok_or_else(|| "Database is not ready".to_string())?;
+    let previous = active_id(root);
+
+    set_active(root, id);
+    let opened = crate::config::migrate_config_file()
+        .and_then(|_| crate::database::get_database_path(app))
+        .and_then(|db_path| crate::db_encryption::reopen(&state.db, db_path));
+    if let Err(e) = opened {
+        set_active(root, &previous);
+        return Err(e);
+    }
+    crate::vocabulary::reload(&state.db);
+    crate::textproc::reload_symbols(&state.db);
+    crate::chat_draft::interrupt_stale_drafts(app);
+
+    let mut file = read_profiles_file(root);
+    file.active = Some(id.to_string());
+    write_profiles_file(root, &file)
+}
+
+/// Register the shortcuts of the active profile and sync its modes to the daemon
+fn apply_profile_config(app: &tauri::AppHandle) {
+    crate::daemon::load_modes_from_config();
+    let recording_mode = *RECORDING_MODE.lock().unwrap();
+    if let Err(e) = crate::meeting::apply_recording_mode(recording_mode) {
+        eprintln!("[PROFILES] {}", e);
+    }
+
+    crate::config::notify_daemon_config_changed();
+    if crate::daemon::is_daemon_ready() {
+        let args = serde_json::json!({ "mode": recording_mode.as_str() });
+        if let Err(e) = crate::daemon::daemon_client().and_then(|c| c.send_no_wait("set_recording_mode", args)) {
+            eprintln!("[PROFILES] {}", e);
+        }
+    }
+
+    crate::shortcuts::register_all_shortcuts(app);
+    crate::shortcut_apps::reset();
+}
+
+fn switch(app: &tauri::AppHandle, id: &str) -> Result<ProfileList, String> {
+    let root = crate::config::get_app_data_dir()?;
+    if id != DEFAULT_PROFILE_ID && !read_profiles_file(&root).profiles.iter().any(|p| p.id == id) {
+        return Err(format!("Unknown profile: {}", id));
+    }
+    if active_id(&root) == id {
+        return Ok(list(&root));
+    }
+
+    // Waiting for speech in continuous mode is fine
+    let status = APP_STATUS.current();
+    if !(status.is_idle() || status == AppStatus::Listening) || crate::file_transcription::is_running() {
+        return Err("Finish the current recording or transcription before switching profile".to_string());
+    }
+
+    crate::shortcuts::unregister_all_shortcuts(app);
+    let activated = activate(app, &root, id);
+    // On failure the previous profile's shortcuts come back
+    apply_profile_config(app);
+    activated?;
+
+    crate::file_transcription::resume_jobs(app);
+    let _ = crate::ui::update_tray_menu(app);
+
+    let profiles = list(&root);
+    println!("[PROFILES] Switched to profile {}", id);
+    emit_event(app, AppEvent::ProfileSwitched(profiles.clone()));
+    Ok(profiles)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// All profiles (the default one first) and the active profile id
+#[tauri::command]
+pub fn list_profiles() -> Result<ProfileList, String> {
+    Ok(list(&crate::config::get_app_data_dir()?))
+}
+
+/// Create a profile, starting from the defaults or a copy of the current settings
+#[tauri::command]
+pub fn create_profile(name: String, copy_settings: Option<bool>) -> Result<Profile, String> {
+    create(&crate::config::get_app_data_dir()?, &name, copy_settings.unwrap_or(false))
+}
+
+/// Switch to another profile: its settings, shortcuts and history
+#[tauri::command]
+pub async fn switch_profile(app: tauri::AppHandle, id: String) -> Result<ProfileList, String> {
+    tauri::async_runtime::spawn_blocking(move || switch(&app, &id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str) -> Profile {
+        Profile { id: id.to_string(), name: id.to_string(), created_at: 0 }
+    }
+
+    #[test]
+    fn test_new_id_is_a_unique_slug() {
+        assert_eq!(new_id("Work Stuff!", &[]), "work-stuff");
+        assert_eq!(new_id("Work", &[profile("work")]), "work-2");
+        assert_eq!(new_id("Work", &[profile("work"), profile("work-2")]), "work-3");
+        assert_eq!(new_id("Default", &[]), "default-2");
+        assert_eq!(new_id("工作", &[]).len(), 8);
+    }
+}
@@ -0,0 +1,375 @@
+// ============================================================================
+// Prompt Templates Module - Spoken Prompts Run by Hotkey
+// ============================================================================
+//
+// A prompt template ("Translate into Japanese: {text}") is stored in SQLite
+// with an optional global hotkey and an output:
+//
+//   speak    the answer is spoken (TTS stage of the pipeline)
+//   type     the answer is typed into the focused app
+//   window   the answer is shown in the ask-selection popover
+//
+// Holding a template's hotkey records a short utterance, like PTT; on release
+// it is transcribed, filled into the template (`{text}`, appended if the
+// prompt has no placeholder) and sent to the LLM as a one-off chat. Errors
+// are shown in the popover. Hotkeys are registered as "template:<id>", so
+// they conflict with action and PTT shortcuts like those do with each other.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::{Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+use crate::audio::AudioRecorder;
+use crate::daemon::{APP_STATUS, AUDIO_RECORDER};
+use crate::database::PromptTemplate;
+use crate::earcons::Cue;
+use crate::selection::SelectionAnswer;
+use crate::state::AppState;
+use crate::types::AppStatus;
+
+/// What happens with a template's answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateOutput {
+    Speak,
+    Type,
+    Window,
+}
+
+impl TemplateOutput {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateOutput::Speak => "speak",
+            TemplateOutput::Type => "type",
+            TemplateOutput::Window => "window",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "speak" => Some(TemplateOutput::Speak),
+            "type" => Some(TemplateOutput::Type),
+            "window" => Some(TemplateOutput::Window),
+            _ => None,
+        }
+    }
+}
+
+/// Registered hotkeys: template id -> shortcut string
+static HOTKEYS: Mutex<BTreeMap<i64, String>> = Mutex::new(BTreeMap::new());
+
+/// Template whose hotkey is held (its utterance is being recorded)
+static RECORDING: Mutex<Option<i64>> = Mutex::new(None);
+
+fn owner(id: i64) -> String {
+    format!("template:{}", id)
+}
+
+/// Registered template hotkeys as (owner, shortcut), for conflict checks
+pub fn registered_hotkeys() -> Vec<(String, String)> {
+    HOTKEYS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(id, hotkey)| (owner(*id), hotkey.clone()))
+        .collect()
+}
+
+/// Trim the fields and check they are usable
+///
+/// `id` is the template being edited, so its own hotkey is not a conflict.
+fn validate(
+    id: Option<i64>,
+    name: &str,
+    template: &str,
+    hotkey: Option<String>,
+    output: Option<String>,
+) -> Result<(String, String, Option<String>, TemplateOutput), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Template name must not be empty".to_string());
+    }
+    let template = template.trim();
+    if template.is_empty() {
+        return Err("Template must not be empty".to_string());
+    }
+    let output = output.as_deref().unwrap_or(TemplateOutput::Window.as_str());
+    let output = TemplateOutput::from_str(output)
+        .ok_or_else(|| format!("Invalid output: {} (expected speak, type or window)", output))?;
+    let hotkey = hotkey.map(|h| h.trim().to_string()).filter(|h| !h.is_empty());
+    if let Some(hotkey) = &hotkey {
+        let exclude = id.map(owner);
+        if let Some(other) = crate::shortcuts::find_shortcut_conflict(hotkey, exclude.as_deref())? {
+            return Err(format!("Shortcut {} is already used by {}", hotkey, other));
+        }
+    }
+    Ok((name.to_string(), template.to_string(), hotkey, output))
+}
+
+// ============================================================================
+// Hotkeys
+// ============================================================================
+
+fn register_hotkey(app: &tauri::AppHandle, id: i64, hotkey: &str) -> Result<(), String> {
+    let shortcut = crate::shortcuts::parse_shortcut(hotkey)?;
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| match event.state() {
+            ShortcutState::Pressed => start_utterance(app, id),
+            ShortcutState::Released => finish_utterance(app),
+        })
+        .map_err(|e| format!("Failed to register template shortcut: {}", e))?;
+    HOTKEYS.lock().unwrap().insert(id, hotkey.to_string());
+    Ok(())
+}
+
+fn unregister_hotkey(app: &tauri::AppHandle, id: i64) {
+    if let Some(hotkey) = HOTKEYS.lock().unwrap().remove(&id) {
+        if let Ok(shortcut) = crate::shortcuts::parse_shortcut(&hotkey) {
+            let _ = app.global_shortcut().unregister(shortcut);
+        }
+    }
+}
+
+/// Forget the registered hotkeys (they were unregistered with all other shortcuts)
+pub fn forget_hotkeys() {
+    HOTKEYS.lock().unwrap().clear();
+}
+
+/// Register the hotkeys of all stored templates (app start, profile switch)
+pub fn register_hotkeys(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let templates = state.db.list_prompt_templates().unwrap_or_else(|e| {
+        eprintln!("[TEMPLATES] {}", e);
+        Vec::new()
+    });
+    for template in templates {
+        let Some(hotkey) = template.hotkey else {
+            continue;
+        };
+        let conflict = crate::shortcuts::find_shortcut_conflict(&hotkey, Some(&owner(template.id)));
+        let result = match conflict {
+            Ok(Some(other)) => Err(format!("Shortcut {} is already used by {}", hotkey, other)),
+            Ok(None) => register_hotkey(app, template.id, &hotkey),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            eprintln!("⚠️ Template '{}': {}", template.name, e);
+        }
+    }
+}
+
+// ============================================================================
+// Running Templates
+// ============================================================================
+
+/// Hotkey pressed: record the utterance (unless PTT or another template is recording)
+fn start_utterance(app: &tauri::AppHandle, id: i64) {
+    {
+        let mut recording = RECORDING.lock().unwrap();
+        if recording.is_some() {
+            return;
+        }
+        let mut recorder_guard = AUDIO_RECORDER.lock().unwrap();
+        if recorder_guard.as_ref().is_some_and(|r| r.is_recording()) {
+            return;
+        }
+        if recorder_guard.is_none() {
+            match AudioRecorder::new() {
+                Ok(recorder) => *recorder_guard = Some(recorder),
+                Err(e) => {
+                    eprintln!("[TEMPLATES] {}", e);
+                    crate::earcons::play(Cue::Error);
+                    return;
+                }
+            }
+        }
+        let timeout_app = app.clone();
+        let started = recorder_guard.as_mut().map(|recorder| {
+            // The callback runs on the recording thread, which stop_recording joins
+            recorder.start_recording(move |_| {
+                std::thread::spawn(move || finish_utterance(&timeout_app));
+            })
+        });
+        if let Some(Err(e)) = started {
+            eprintln!("[TEMPLATES] {}", e);
+            crate::earcons::play(Cue::Error);
+            return;
+        }
+        *recording = Some(id);
+    }
+
+    crate::earcons::play(Cue::RecordStart);
+    APP_STATUS.transition(AppStatus::Recording, "template_press");
+    crate::ui::emit_ptt_state_static(app, "recording");
+}
+
+/// Hotkey released (or recording timed out): transcribe and run the template
+fn finish_utterance(app: &tauri::AppHandle) {
+    let Some(id) = RECORDING.lock().unwrap().take() else {
+        return;
+    };
+    let audio = AUDIO_RECORDER
+        .lock()
+        .unwrap()
+        .as_mut()
+        .map(|recorder| recorder.stop_recording());
+
+    crate::earcons::play(Cue::RecordStop);
+    let audio = match audio {
+        Some(Ok(audio)) => audio,
+        Some(Err(e)) => {
+            eprintln!("[TEMPLATES] {}", e);
+            finish(app);
+            return;
+        }
+        None => {
+            finish(app);
+            return;
+        }
+    };
+    crate::ui::emit_ptt_state_static(app, "processing");
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let language = crate::asr_language::request_language();
+        let text = match crate::pipeline::transcribe(&audio, None, &language).await {
+            Ok(transcript) => transcript.text,
+            Err(e) => {
+                eprintln!("[TEMPLATES] ASR failed: {}", e);
+                crate::earcons::play(Cue::Error);
+                String::new()
+            }
+        };
+        crate::ui::emit_ptt_state_static(&app, "idle");
+        if text.trim().is_empty() {
+            finish(&app);
+            return;
+        }
+        let text = crate::textproc::process_transcript(&app, &text);
+        if let Err(e) = run(&app, id, &text).await {
+            eprintln!("[TEMPLATES] {}", e);
+        }
+    });
+}
+
+fn finish(app: &tauri::AppHandle) {
+    APP_STATUS.transition(AppStatus::Idle, "template_done");
+    crate::ui::emit_ptt_state_static(app, "idle");
+}
+
+/// Fill `text` into a template, ask the LLM and deliver the answer
+async fn run(app: &tauri::AppHandle, id: i64, text: &str) -> Result<(), String> {
+    let state = app.try_state::<AppState>().ok_or("Database is not ready")?;
+    let template = state.db.get_prompt_template(id)?;
+    let output = TemplateOutput::from_str(&template.output).unwrap_or(TemplateOutput::Window);
+
+    APP_STATUS.transition(AppStatus::LlmProcessing, "template");
+    let answer = crate::selection::ask_llm(crate::selection::fill_template(&template.template, text)).await;
+    let mut shown = SelectionAnswer {
+        template: template.name,
+        selection: text.to_string(),
+        ..Default::default()
+    };
+
+    match (output, answer) {
+        (TemplateOutput::Speak, Ok(answer)) => {
+            crate::pipeline::speak_text(app, &answer).await;
+            return Ok(());
+        }
+        (TemplateOutput::Type, Ok(answer)) => crate::platform::paste_text_after(answer, Duration::ZERO),
+        (TemplateOutput::Window, Ok(answer)) => {
+            shown.answer = Some(answer);
+            crate::selection::show_answer(app, shown);
+        }
+        (_, Err(e)) => {
+            crate::earcons::play(Cue::Error);
+            shown.error = Some(e.clone());
+            crate::selection::show_answer(app, shown);
+            finish(app);
+            return Err(e);
+        }
+    }
+    finish(app);
+    Ok(())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Create a template (`output` defaults to "window"; `hotkey` None = no shortcut)
+#[tauri::command]
+pub fn create_prompt_template(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+    template: String,
+    hotkey: Option<String>,
+    output: Option<String>,
+) -> Result<PromptTemplate, String> {
+    let (name, template, hotkey, output) = validate(None, &name, &template, hotkey, output)?;
+    let created = state.db.add_prompt_template(&name, &template, hotkey.as_deref(), output.as_str())?;
+    if let Some(hotkey) = &hotkey {
+        if let Err(e) = register_hotkey(&app, created.id, hotkey) {
+            let _ = state.db.delete_prompt_template(created.id);
+            return Err(e);
+        }
+    }
+    Ok(created)
+}
+
+/// List templates by name
+#[tauri::command]
+pub fn list_prompt_templates(state: State<'_, AppState>) -> Result<Vec<PromptTemplate>, String> {
+    state.db.list_prompt_templates()
+}
+
+/// Update a template; a changed hotkey is registered before anything is saved
+#[tauri::command]
+pub fn update_prompt_template(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: i64,
+    name: String,
+    template: String,
+    hotkey: Option<String>,
+    output: Option<String>,
+) -> Result<PromptTemplate, String> {
+    let (name, template, hotkey, output) = validate(Some(id), &name, &template, hotkey, output)?;
+    let old = state.db.get_prompt_template(id)?;
+
+    if old.hotkey != hotkey {
+        unregister_hotkey(&app, id);
+        if let Some(new_hotkey) = &hotkey {
+            if let Err(e) = register_hotkey(&app, id, new_hotkey) {
+                // Restore the previous binding
+                if let Some(old_hotkey) = &old.hotkey {
+                    let _ = register_hotkey(&app, id, old_hotkey);
+                }
+                return Err(e);
+            }
+        }
+    }
+    state.db.update_prompt_template(id, &name, &template, hotkey.as_deref(), output.as_str())
+}
+
+/// Delete a template and release its hotkey
+#[tauri::command]
+pub fn delete_prompt_template(app: tauri::AppHandle, state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    unregister_hotkey(&app, id);
+    state.db.delete_prompt_template(id)
+}
+
+/// Run a template on typed text instead of an utterance
+#[tauri::command]
+pub async fn run_prompt_template(app: tauri::AppHandle, id: i64, text: String) -> Result<(), String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Text must not be empty".to_string());
+    }
+    run(&app, id, text).await
+}
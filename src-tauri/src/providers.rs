@@ -0,0 +1,368 @@
+// ============================================================================
+// Providers Module - LLM Provider Registry
+// ============================================================================
+//
+// Every LLM provider from `llm_providers` implements `Provider`: how to check
+// that its credentials and model work, and how to list its models. The
+// settings page calls the two commands below with the provider name instead
+// of one command per provider.
+//
+// Anthropic and Gemini are chatted with through their OpenAI-compatible
+// endpoints (that is the configured base URL), but tested and listed through
+// their own APIs so errors come back in their native, more helpful form.
+
+use serde::Deserialize;
+
+use crate::config::{self, LlmProviderConfig};
+
+const TEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Connection settings entered in the settings page (missing = provider default)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProviderParams {
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Resolved settings handed to a provider
+struct Connection {
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+/// One LLM provider
+trait Provider: Sync {
+    /// Name used in `llm_provider` / `llm_providers`
+    fn name(&self) -> &'static str;
+    /// Name shown in messages
+    fn display_name(&self) -> &'static str;
+    /// Base URL used when none is configured ("" = must be configured)
+    fn default_base_url(&self) -> &'static str;
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+    /// Smallest request that proves the credentials and model work
+    fn test_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder;
+    /// Check the body of a successful test response (most providers: any 2xx is enough)
+    fn verify_test_response(&self, _body: &serde_json::Value, _conn: &Connection) -> Result<(), String> {
+        Ok(())
+    }
+    fn models_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder;
+    fn parse_models(&self, body: &serde_json::Value) -> Vec<String>;
+}
+
+/// Strings found under `key` in each entry of the `list` array
+fn collect_strings(body: &serde_json::Value, list: &str, key: &str) -> Vec<String> {
+    body.get(list)
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get(key).and_then(|v| v.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Implementations
+// ============================================================================
+
+struct Ollama;
+
+impl Provider for Ollama {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+    fn display_name(&self) -> &'static str {
+        "Ollama"
+    }
+    fn default_base_url(&self) -> &'static str {
+        "http://localhost:11434"
+    }
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+    // The service is up if it lists its models; the model must be among them
+    fn test_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        self.models_request(client, conn)
+    }
+    fn verify_test_response(&self, body: &serde_json::Value, conn: &Connection) -> Result<(), String> {
+        let installed = self
+            .parse_models(body)
+            .iter()
+            .any(|name| name == &conn.model || name.starts_with(&conn.model));
+        if installed {
+            Ok(())
+        } else {
+            Err(format!("Model {} is not installed. Run: ollama pull {}", conn.model, conn.model))
+        }
+    }
+    fn models_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        client.get(format!("{}/api/tags", conn.base_url))
+    }
+    fn parse_models(&self, body: &serde_json::Value) -> Vec<String> {
+        collect_strings(body, "models", "name")
+    }
+}
+
+/// OpenAI and the services that speak its API
+struct OpenAiCompatible {
+    name: &'static str,
+    display_name: &'static str,
+    default_base_url: &'static str,
+    requires_api_key: bool,
+}
+
+impl Provider for OpenAiCompatible {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn display_name(&self) -> &'static str {
+        self.display_name
+    }
+    fn default_base_url(&self) -> &'static str {
+        self.default_base_url
+    }
+    fn requires_api_key(&self) -> bool {
+        self.requires_api_key
+    }
+    fn test_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        // Custom endpoints are sometimes entered with the full path
+        let base_url = conn.base_url.strip_suffix("/chat/completions").unwrap_or(&conn.base_url);
+        let request = client.post(format!("{}/chat/completions", base_url)).json(&serde_json::json!({
+            "model": conn.model,
+            "messages": [{ "role": "user", "content": "Hi" }],
+            "max_tokens": 1,
+        }));
+        with_bearer(request, &conn.api_key)
+    }
+    fn models_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        let base_url = conn.base_url.strip_suffix("/chat/completions").unwrap_or(&conn.base_url);
+        with_bearer(client.get(format!("{}/models", base_url)), &conn.api_key)
+    }
+    fn parse_models(&self, body: &serde_json::Value) -> Vec<String> {
+        collect_strings(body, "data", "id")
+    }
+}
+
+fn with_bearer(request: reqwest::RequestBuilder, api_key: &str) -> reqwest::RequestBuilder {
+    if api_key.is_empty() {
+        request
+    } else {
+        request.bearer_auth(api_key)
+    }
+}
+
+struct Anthropic;
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+impl Provider for Anthropic {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+    fn display_name(&self) -> &'static str {
+        "Anthropic"
+    }
+    fn default_base_url(&self) -> &'static str {
+        "https://api.anthropic.com/v1"
+    }
+    fn test_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        client
+            .post(format!("{}/messages", conn.base_url))
+            .header("x-api-key", &conn.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&serde_json::json!({
+                "model": conn.model,
+                "messages": [{ "role": "user", "content": "Hi" }],
+                "max_tokens": 1,
+            }))
+    }
+    fn models_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        client
+            .get(format!("{}/models", conn.base_url))
+            .header("x-api-key", &conn.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+    }
+    fn parse_models(&self, body: &serde_json::Value) -> Vec<String> {
+        collect_strings(body, "data", "id")
+    }
+}
+
+struct Gemini;
+
+impl Gemini {
+    /// Native API root (the configured base URL is its `/openai` compatibility endpoint)
+    fn api_root(conn: &Connection) -> &str {
+        conn.base_url.strip_suffix("/openai").unwrap_or(&conn.base_url)
+    }
+}
+
+impl Provider for Gemini {
+    fn name(&self) -> &'static str {
+        "gemini"
+    }
+    fn display_name(&self) -> &'static str {
+        "Google Gemini"
+    }
+    fn default_base_url(&self) -> &'static str {
+        "https://generativelanguage.googleapis.com/v1beta/openai"
+    }
+    fn test_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        client
+            .post(format!("{}/models/{}:generateContent", Self::api_root(conn), conn.model))
+            .header("x-goog-api-key", &conn.api_key)
+            .json(&serde_json::json!({
+                "contents": [{ "parts": [{ "text": "Hi" }] }],
+                "generationConfig": { "maxOutputTokens": 1 },
+            }))
+    }
+    fn models_request(&self, client: &reqwest::Client, conn: &Connection) -> reqwest::RequestBuilder {
+        client
+            .get(format!("{}/models", Self::api_root(conn)))
+            .header("x-goog-api-key", &conn.api_key)
+    }
+    fn parse_models(&self, body: &serde_json::Value) -> Vec<String> {
+        collect_strings(body, "models", "name")
+            .into_iter()
+            .map(|name| name.strip_prefix("models/").map(str::to_string).unwrap_or(name))
+            .collect()
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// All providers, by name
+struct ProviderRegistry;
+
+impl ProviderRegistry {
+    const PROVIDERS: [&'static dyn Provider; 7] = [
+        &Ollama,
+        &OpenAiCompatible {
+            name: "openai",
+            display_name: "OpenAI",
+            default_base_url: "https://api.openai.com/v1",
+            requires_api_key: true,
+        },
+        &OpenAiCompatible {
+            name: "openrouter",
+            display_name: "OpenRouter",
+            default_base_url: "https://openrouter.ai/api/v1",
+            requires_api_key: true,
+        },
+        &OpenAiCompatible {
+            name: "zhipu",
+            display_name: "ZhipuAI",
+            default_base_url: "https://open.bigmodel.cn/api/paas/v4",
+            requires_api_key: true,
+        },
+        &OpenAiCompatible {
+            name: "custom",
+            display_name: "Custom API",
+            default_base_url: "",
+            requires_api_key: false,
+        },
+        &Anthropic,
+        &Gemini,
+    ];
+
+    fn get(name: &str) -> Result<&'static dyn Provider, String> {
+        Self::PROVIDERS
+            .into_iter()
+            .find(|p| p.name() == name)
+            .ok_or_else(|| format!("Unknown LLM provider: {}", name))
+    }
+}
+
+/// Fill in missing params from the provider's `llm_providers` entry, then its defaults
+fn resolve(provider: &dyn Provider, params: Option<ProviderParams>) -> Result<Connection, String> {
+    let configured: Option<LlmProviderConfig> = config::read_config_value("llm_providers")
+        .and_then(|v| serde_json::from_value::<Vec<LlmProviderConfig>>(v).ok())
+        .and_then(|providers| providers.into_iter().find(|p| p.name == provider.name()));
+    let params = params.unwrap_or_default();
+    let pick = |given: Option<String>, stored: Option<String>| {
+        given
+            .filter(|v| !v.trim().is_empty())
+            .or(stored.filter(|v| !v.trim().is_empty()))
+            .map(|v| v.trim().to_string())
+    };
+
+    let base_url = pick(params.base_url, configured.as_ref().map(|c| c.base_url.clone()))
+        .unwrap_or_else(|| provider.default_base_url().to_string());
+    if base_url.is_empty() {
+        return Err(format!("Base URL is empty. Please enter your {} URL.", provider.display_name()));
+    }
+    let api_key = pick(params.api_key, configured.as_ref().map(crate::secrets::api_key_for)).unwrap_or_default();
+    if provider.requires_api_key() && api_key.is_empty() {
+        return Err(format!("API Key is empty. Please enter your {} API key.", provider.display_name()));
+    }
+    let model = pick(params.model, configured.map(|c| c.model)).unwrap_or_default();
+
+    Ok(Connection {
+        base_url: base_url.trim_end_matches('/').to_string(),
+        api_key,
+        model,
+    })
+}
+
+/// Send a provider request and return the JSON body of a successful response
+async fn send(provider: &dyn Provider, request: reqwest::RequestBuilder) -> Result<serde_json::Value, String> {
+    crate::http::send(provider.name(), request.timeout(TEST_TIMEOUT))
+        .await?
+        .response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Check that a provider is reachable with the given settings
+///
+/// Returns `{"success": true, "message"}` or `{"success": false, "error"}`.
+#[tauri::command]
+pub async fn test_llm_connection(provider: String, params: Option<ProviderParams>) -> Result<serde_json::Value, String> {
+    let provider = ProviderRegistry::get(&provider)?;
+    let result = async {
+        let conn = resolve(provider, params)?;
+        crate::network::ensure_online(provider.display_name(), &conn.base_url)?;
+        if conn.model.is_empty() {
+            return Err("Model is empty. Please enter a model name.".to_string());
+        }
+        let body = send(provider, provider.test_request(&crate::http::client()?, &conn)).await?;
+        provider.verify_test_response(&body, &conn)
+    }
+    .await;
+
+    Ok(match result {
+        Ok(()) => serde_json::json!({
+            "success": true,
+            "message": format!("{} connection successful", provider.display_name()),
+        }),
+        Err(e) => serde_json::json!({ "success": false, "error": e }),
+    })
+}
+
+/// Models offered by a provider (`params` default to its `llm_providers` entry)
+#[tauri::command]
+pub async fn list_models(provider: String, params: Option<ProviderParams>) -> Result<Vec<String>, String> {
+    let provider = ProviderRegistry::get(&provider)?;
+    let conn = resolve(provider, params)?;
+    crate::network::ensure_online(provider.display_name(), &conn.base_url)?;
+    let body = send(provider, provider.models_request(&crate::http::client()?, &conn)).await?;
+
+    let mut models = provider.parse_models(&body);
+    models.sort();
+    Ok(models)
+}
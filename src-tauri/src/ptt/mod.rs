@@ -5,4 +5,4 @@
 
 mod reader;
 
-pub use reader::start_ptt_reader;
+pub use reader::{dispatch_event, start_ptt_reader};
@@ -4,11 +4,19 @@
 //! and forwarding them to the frontend UI.
 
 use std::sync::atomic::Ordering;
-use std::io::BufRead;
+use std::time::Duration;
 
-use tauri::{Emitter, Manager};
+use tokio::io::AsyncBufReadExt;
+
+use tauri::{Manager};
 use crate::daemon::PTT_STDERR;
 use crate::daemon::PTT_PROCESSING;
+use crate::daemon::LAST_TRANSCRIPT;
+use crate::daemon::APP_STATUS;
+use crate::types::{AppStatus, AudioChunkPayload, WorkMode};
+use crate::events::{emit_event, AppEvent};
+use crate::earcons::Cue;
+use crate::metrics::{self, PipelineStage};
 
 // ============================================================================
 // PTT Event Reader
@@ -28,157 +36,201 @@ use crate::daemon::PTT_PROCESSING;
 /// - `assistant_chunk` - LLM streaming response chunk
 /// - `assistant_done` - LLM response complete
 /// - `audio_chunk` - TTS audio chunk
+/// - `playback_started` - TTS audio playback began
 /// - `error` - Error occurred
 pub fn start_ptt_reader(app_handle: tauri::AppHandle) {
-    std::thread::spawn(move || {
+    tauri::async_runtime::spawn(async move {
         loop {
-            // Get stderr reader
-            let line = {
-                let mut ptt_stderr = PTT_STDERR.lock().unwrap();
-                if let Some(ref mut stderr) = *ptt_stderr {
-                    let mut line = String::new();
-                    match stderr.read_line(&mut line) {
-                        Ok(0) => {
-                            break;
-                        }
-                        Ok(_) => Some(line),
-                        Err(_e) => {
-                            None
-                        }
-                    }
-                } else {
-                    // stderr not ready yet, wait a bit
-                    drop(ptt_stderr);
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    continue;
-                }
+            // Take the stderr of the current daemon (a restarted daemon stores a new one)
+            let stderr = PTT_STDERR.lock().unwrap().take();
+            let Some(mut stderr) = stderr else {
+                // stderr not ready yet, wait a bit
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
             };
 
-            if let Some(line) = line {
-                let line = line.trim();
-                if line.is_empty() {
-                    continue;
+            let mut line = String::new();
+            loop {
+                line.clear();
+                match stderr.read_line(&mut line).await {
+                    // Daemon exited - drop the stale handle and wait for a restarted daemon
+                    Ok(0) => break,
+                    Ok(_) => handle_stderr_line(&app_handle, &line),
+                    // Skip lines that are not valid UTF-8
+                    Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {}
+                    Err(_e) => break,
                 }
+            }
+        }
+    });
+}
 
-                // Try to parse as JSON PTT event
-                if let Ok(event) = serde_json::from_str::<serde_json::Value>(line) {
-                    if let Some(ptt_event) = event.get("ptt_event").and_then(|v| v.as_str()) {
-                        // Get main window and floating window
-                        let main_window = app_handle.get_webview_window("main");
-                        let overlay_window = app_handle.get_webview_window("ptt-overlay");
+/// Handle one line of daemon stderr: forward PTT events, keep the rest for crash reports
+fn handle_stderr_line(app_handle: &tauri::AppHandle, line: &str) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
 
-                        // Send state to floating window and control visibility
-                        if let Some(ref overlay) = overlay_window {
-                            match ptt_event {
-                                "listening" => {
-                                    // Show overlay in listening state (continuous mode waiting for speech)
-                                    let _ = overlay.set_ignore_cursor_events(false);
-                                    let _ = overlay.show();
-                                    let _ = overlay.emit("ptt-state", "listening");
-                                }
-                                "detected" => {
-                                    // Speech detected, transitioning to recording
-                                    let _ = overlay.set_ignore_cursor_events(false);
-                                    let _ = overlay.show();
-                                    let _ = overlay.emit("ptt-state", "detected");
-                                }
-                                "recording" => {
-                                    let _ = overlay.set_ignore_cursor_events(false);
-                                    let _ = overlay.show();
-                                    let _ = overlay.emit("ptt-state", "recording");
-                                }
-                                "processing" => {
-                                    let _ = overlay.emit("ptt-state", "processing");
-                                }
-                                "idle" | "error" => {
-                                    let _ = overlay.hide();
-                                    let _ = overlay.emit("ptt-state", "idle");
-                                }
-                                _ => {}
-                            }
-                        }
+    // Keep daemon log output (not PTT events) for crash reports
+    if !line.contains("\"ptt_event\"") {
+        crate::diagnostics::record_stderr_line(line);
+    }
 
-                        // Send full event to main window
-                        if let Some(window) = main_window {
-                            match ptt_event {
-                                "listening" => {
-                                    let _ = window.emit("ptt-state", "listening");
-                                }
-                                "detected" => {
-                                    let _ = window.emit("ptt-state", "detected");
-                                }
-                                "recording" => {
-                                    let _ = window.emit("ptt-state", "recording");
-                                }
-                                "processing" => {
-                                    let _ = window.emit("ptt-state", "processing");
-                                }
-                                "idle" => {
-                                    let _ = window.emit("ptt-state", "idle");
-                                }
-                                "user_message" => {
-                                    // User speech recognition result - hide overlay, show message
-                                    // Set processing flag to prevent overlay from reappearing
-                                    PTT_PROCESSING.store(true, Ordering::SeqCst);
-                                    let _ = window.emit("ptt-state", "idle");
-                                    if let Some(ref overlay) = overlay_window {
-                                        let _ = overlay.set_ignore_cursor_events(true);
-                                        let _ = overlay.hide();
-                                        let _ = overlay.emit("ptt-state", "idle");
-                                    }
-                                    if let Some(text) = event.get("text").and_then(|v| v.as_str()) {
-                                        let _ = window.emit("ptt-user-message", text);
-                                    }
-                                }
-                                "assistant_chunk" => {
-                                    // LLM streaming response chunk - ensure overlay is hidden
-                                    let _ = window.emit("ptt-state", "idle");
-                                    if let Some(ref overlay) = overlay_window {
-                                        let _ = overlay.set_ignore_cursor_events(true);
-                                        let _ = overlay.hide();
-                                    }
-                                    if let Some(content) = event.get("content").and_then(|v| v.as_str()) {
-                                        let _ = window.emit("ptt-assistant-chunk", content);
-                                    }
-                                }
-                                "assistant_done" => {
-                                    // LLM response complete - ensure overlay is hidden
-                                    // Clear processing flag to allow future recordings
-                                    PTT_PROCESSING.store(false, Ordering::SeqCst);
-                                    let _ = window.emit("ptt-state", "idle");
-                                    if let Some(ref overlay) = overlay_window {
-                                        let _ = overlay.set_ignore_cursor_events(true);
-                                        let _ = overlay.hide();
-                                    }
-                                    if let Some(content) = event.get("content").and_then(|v| v.as_str()) {
-                                        let _ = window.emit("ptt-assistant-done", content);
-                                    }
-                                }
-                                "audio_chunk" => {
-                                    // TTS audio chunk
-                                    let audio_path = event.get("audio_path").and_then(|v| v.as_str());
-                                    let text = event.get("text").and_then(|v| v.as_str());
-                                    if let (Some(path), Some(txt)) = (audio_path, text) {
-                                        let _ = window.emit("ptt-audio-chunk", serde_json::json!({
-                                            "audio_path": path,
-                                            "text": txt
-                                        }));
-                                    }
-                                }
-                                "error" => {
-                                    // Clear processing flag on error
-                                    PTT_PROCESSING.store(false, Ordering::SeqCst);
-                                    let _ = window.emit("ptt-state", "error");
-                                    if let Some(error) = event.get("error").and_then(|v| v.as_str()) {
-                                        let _ = window.emit("ptt-error", error);
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
+    // Try to parse as JSON PTT event
+    if let Ok(event) = serde_json::from_str::<serde_json::Value>(line) {
+        dispatch_event(app_handle, &event);
+    }
+}
+
+/// Forward one PTT event (`{"ptt_event": ..., ...}`) to the windows
+///
+/// Used for events from the daemon and for the ones emitted by the Rust pipeline.
+pub fn dispatch_event(app_handle: &tauri::AppHandle, event: &serde_json::Value) {
+    let Some(ptt_event) = event.get("ptt_event").and_then(|v| v.as_str()) else {
+        return;
+    };
+    crate::api_server::publish_ptt_event(event);
+
+    let overlay_window = app_handle.get_webview_window("ptt-overlay");
+
+    // Control the floating window's visibility (the events below reach every window)
+    if let Some(ref overlay) = overlay_window {
+        match ptt_event {
+            // Continuous mode waiting for speech, speech detected, or recording
+            "listening" | "detected" | "recording" => {
+                let _ = overlay.set_ignore_cursor_events(false);
+                crate::ui::show_overlay(app_handle, overlay);
+            }
+            "idle" | "error" => {
+                let _ = overlay.hide();
             }
+            _ => {}
         }
-    });
+    }
+
+    // Time the pipeline stages for latency metrics
+    match ptt_event {
+        "processing" => metrics::mark_recording_stopped(),
+        "user_message" => metrics::mark_stage(PipelineStage::AsrDone),
+        "assistant_chunk" => metrics::mark_stage(PipelineStage::FirstToken),
+        "audio_chunk" => metrics::mark_stage(PipelineStage::FirstAudio),
+        "playback_started" => metrics::mark_stage(PipelineStage::PlaybackStarted),
+        "assistant_done" | "interrupted" | "idle" | "error" => metrics::finish_interaction(app_handle),
+        _ => {}
+    }
+
+    // Track the pipeline status (used to decide what an interrupt may stop)
+    let status = match ptt_event {
+        "listening" => Some(AppStatus::Listening),
+        "detected" | "recording" => Some(AppStatus::Recording),
+        "processing" => Some(AppStatus::AsrProcessing),
+        "user_message" => Some(AppStatus::LlmProcessing),
+        "audio_chunk" => Some(AppStatus::TtsProcessing),
+        "playback_started" => Some(AppStatus::Playing),
+        "idle" | "error" => Some(AppStatus::Idle),
+        _ => None,
+    };
+    if let Some(status) = status {
+        APP_STATUS.transition(status, &format!("daemon:{}", ptt_event));
+    }
+
+    // Send the event to the windows
+    match ptt_event {
+        "listening" => {
+            emit_event(app_handle, AppEvent::PttState("listening".to_string()));
+        }
+        "detected" => {
+            emit_event(app_handle, AppEvent::PttState("detected".to_string()));
+        }
+        "recording" => {
+            emit_event(app_handle, AppEvent::PttState("recording".to_string()));
+        }
+        "processing" => {
+            emit_event(app_handle, AppEvent::PttState("processing".to_string()));
+        }
+        "idle" => {
+            emit_event(app_handle, AppEvent::PttState("idle".to_string()));
+        }
+        "asr_partial" => {
+            if let Some(text) = event.get("text").and_then(|v| v.as_str()) {
+                emit_event(app_handle, AppEvent::PttAsrPartial(text.to_string()));
+            }
+        }
+        "user_message" => {
+            // User speech recognition result - hide overlay, show message
+            // Set processing flag to prevent overlay from reappearing
+            PTT_PROCESSING.store(true, Ordering::SeqCst);
+            emit_event(app_handle, AppEvent::PttState("idle".to_string()));
+            // Let the final transcript stay readable on the overlay for a moment
+            if overlay_window.is_some() {
+                crate::ui::hide_overlay_after(app_handle, crate::ui::OVERLAY_TRANSCRIPT_LINGER);
+            }
+            // A password dictated into a secure field must not reach the history or clipboard
+            let text_input = *crate::daemon::WORK_MODE.lock().unwrap() == WorkMode::TextInput;
+            if text_input && crate::platform::block_secure_field(app_handle) {
+                return;
+            }
+            if let Some(text) = event.get("text").and_then(|v| v.as_str()) {
+                emit_event(app_handle, AppEvent::PttUserMessage(text.to_string()));
+                *LAST_TRANSCRIPT.lock().unwrap() = Some(text.to_string());
+                crate::transcriptions::record(app_handle, text);
+                let _ = crate::ui::update_tray_menu(app_handle);
+            }
+        }
+        "assistant_chunk" => {
+            // LLM streaming response chunk - ensure overlay is hidden
+            emit_event(app_handle, AppEvent::PttState("idle".to_string()));
+            if let Some(ref overlay) = overlay_window {
+                let _ = overlay.set_ignore_cursor_events(true);
+                let _ = overlay.hide();
+            }
+            if let Some(content) = event.get("content").and_then(|v| v.as_str()) {
+                emit_event(app_handle, AppEvent::PttAssistantChunk(content.to_string()));
+            }
+        }
+        "assistant_done" => {
+            // LLM response complete - ensure overlay is hidden
+            // Clear processing flag to allow future recordings
+            PTT_PROCESSING.store(false, Ordering::SeqCst);
+            crate::earcons::play(Cue::Complete);
+            emit_event(app_handle, AppEvent::PttState("idle".to_string()));
+            if let Some(ref overlay) = overlay_window {
+                let _ = overlay.set_ignore_cursor_events(true);
+                let _ = overlay.hide();
+            }
+            if let Some(content) = event.get("content").and_then(|v| v.as_str()) {
+                emit_event(app_handle, AppEvent::PttAssistantDone(content.to_string()));
+            }
+        }
+        "interrupted" => {
+            // Reply cut short by a barge-in - keep the partial text, but leave
+            // the overlay and PTT state to the recording that interrupted it
+            PTT_PROCESSING.store(false, Ordering::SeqCst);
+            if let Some(content) = event.get("content").and_then(|v| v.as_str()) {
+                emit_event(app_handle, AppEvent::PttAssistantDone(content.to_string()));
+            }
+        }
+        "audio_chunk" => {
+            // TTS audio chunk
+            let audio_path = event.get("audio_path").and_then(|v| v.as_str());
+            let text = event.get("text").and_then(|v| v.as_str());
+            if let (Some(path), Some(txt)) = (audio_path, text) {
+                emit_event(app_handle, AppEvent::PttAudioChunk(AudioChunkPayload {
+                    audio_path: path.to_string(),
+                    text: txt.to_string(),
+                }));
+            }
+        }
+        "error" => {
+            // Clear processing flag on error
+            PTT_PROCESSING.store(false, Ordering::SeqCst);
+            crate::earcons::play(Cue::Error);
+            emit_event(app_handle, AppEvent::PttState("error".to_string()));
+            if let Some(error) = event.get("error").and_then(|v| v.as_str()) {
+                emit_event(app_handle, AppEvent::PttError(error.to_string()));
+            }
+        }
+        _ => {}
+    }
 }
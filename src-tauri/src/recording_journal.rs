@@ -0,0 +1,190 @@
+// ============================================================================
+// Recording Journal Module - Crash Recovery for Untranscribed Recordings
+// ============================================================================
+//
+// A PTT recording only lives in a temporary WAV until ASR has read it, so a
+// crash (or a failed ASR) between the two used to lose the utterance. Before
+// a recording is transcribed it is now copied to <app data>/pending_recordings
+// and registered in the `pending_recordings` table; the entry and the copy
+// are removed once ASR returns a transcript. A failed ASR marks the entry
+// "failed" instead.
+//
+// Entries still in the journal at the next start are offered to the user:
+// `recover_pending_recordings` re-runs ASR on them and saves the transcripts
+// to the dictation history, `discard_pending_recordings` drops them.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tauri::{Manager, State};
+
+use crate::audio::AudioData;
+use crate::daemon::call_daemon_async;
+use crate::database::PendingRecording;
+use crate::state::AppState;
+use crate::types::RecoveredRecording;
+use crate::events::{emit_event, AppEvent};
+
+/// Subdirectory of the app data directory holding journaled recordings
+const JOURNAL_DIR_NAME: &str = "pending_recordings";
+
+/// Entries of recordings being transcribed right now (not offered for recovery)
+static IN_FLIGHT: Mutex<Option<HashSet<i64>>> = Mutex::new(None);
+
+fn journal_dir<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(JOURNAL_DIR_NAME))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+fn set_in_flight(id: i64, in_flight: bool) {
+    if let Ok(mut guard) = IN_FLIGHT.lock() {
+        let ids = guard.get_or_insert_with(HashSet::new);
+        if in_flight {
+            ids.insert(id);
+        } else {
+            ids.remove(&id);
+        }
+    }
+}
+
+fn is_in_flight(id: i64) -> bool {
+    IN_FLIGHT
+        .lock()
+        .map(|guard| guard.as_ref().is_some_and(|ids| ids.contains(&id)))
+        .unwrap_or(false)
+}
+
+/// Journal a recording before it is transcribed; returns the journal entry id
+pub fn register<R: tauri::Runtime>(app: &tauri::AppHandle<R>, audio: &AudioData) -> Option<i64> {
+    let state = app.try_state::<AppState>()?;
+    let result = journal_dir(app).and_then(|dir| {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create journal directory: {}", e))?;
+        let dest = dir.join(format!("{}.wav", uuid::Uuid::new_v4().simple()));
+        std::fs::copy(&audio.file_path, &dest).map_err(|e| format!("Failed to journal recording: {}", e))?;
+
+        let path = dest.to_string_lossy().into_owned();
+        state.db.add_pending_recording(&path, audio.duration_secs as f64).inspect_err(|_| {
+            let _ = std::fs::remove_file(&dest);
+        })
+    });
+
+    match result {
+        Ok(entry) => {
+            set_in_flight(entry.id, true);
+            Some(entry.id)
+        }
+        Err(e) => {
+            eprintln!("[JOURNAL] {}", e);
+            None
+        }
+    }
+}
+
+/// Record the outcome of ASR: a transcript clears the entry, an error keeps it as "failed"
+pub fn finish<R: tauri::Runtime>(app: &tauri::AppHandle<R>, id: i64, transcribed: bool) {
+    set_in_flight(id, false);
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let result = if transcribed {
+        remove_entry(&state, id)
+    } else {
+        state.db.set_pending_recording_status(id, "failed")
+    };
+    if let Err(e) = result {
+        eprintln!("[JOURNAL] Failed to update entry {}: {}", id, e);
+    }
+}
+
+fn remove_entry(state: &AppState, id: i64) -> Result<(), String> {
+    if let Some(entry) = state.db.list_pending_recordings()?.into_iter().find(|r| r.id == id) {
+        let _ = std::fs::remove_file(&entry.path);
+    }
+    state.db.delete_pending_recording(id).map(|_| ())
+}
+
+/// Journal entries left by a previous run or a failed ASR
+fn recoverable(state: &AppState) -> Result<Vec<PendingRecording>, String> {
+    Ok(state
+        .db
+        .list_pending_recordings()?
+        .into_iter()
+        .filter(|r| !is_in_flight(r.id))
+        .collect())
+}
+
+/// Transcribe one journal entry (the daemon deletes the file it is given, so it gets a copy)
+async fn transcribe(entry: &PendingRecording) -> Result<String, String> {
+    let copy = std::env::temp_dir().join(format!("speekium_recover_{}.wav", uuid::Uuid::new_v4().simple()));
+    std::fs::copy(&entry.path, &copy).map_err(|e| format!("Failed to read recording: {}", e))?;
+
+    let result = call_daemon_async(
+        "transcribe",
+        serde_json::json!({
+            "audio_path": copy.to_string_lossy(),
+            "language": crate::asr_language::request_language(),
+            "hotwords": crate::vocabulary::request_hotwords(),
+        }),
+    )
+    .await?;
+    if !result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        let _ = std::fs::remove_file(&copy);
+        return Err(result.get("error").and_then(|v| v.as_str()).unwrap_or("ASR failed").to_string());
+    }
+    Ok(result.get("text").and_then(|v| v.as_str()).unwrap_or_default().to_string())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Recordings that were never transcribed (oldest first)
+#[tauri::command]
+pub fn list_pending_recordings(state: State<'_, AppState>) -> Result<Vec<PendingRecording>, String> {
+    recoverable(&state)
+}
+
+/// Re-run ASR on unprocessed recordings and save the transcripts to the dictation history
+///
+/// Recordings that fail again stay in the journal.
+#[tauri::command]
+pub async fn recover_pending_recordings(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<RecoveredRecording>, String> {
+    let mut recovered = Vec::new();
+    for entry in recoverable(&state)? {
+        println!("[JOURNAL] Recovering recording {} from {}", entry.id, entry.created_at);
+        let outcome = match transcribe(&entry).await {
+            Ok(text) => {
+                let text = crate::textproc::process_transcript(&app, &text);
+                if !text.trim().is_empty() {
+                    let transcription = state.db.add_transcription(&text)?;
+                    emit_event(&app, AppEvent::TranscriptionAdded(transcription));
+                }
+                remove_entry(&state, entry.id)?;
+                RecoveredRecording { id: entry.id, created_at: entry.created_at, text: Some(text), error: None }
+            }
+            Err(e) => {
+                eprintln!("[JOURNAL] Recovery of recording {} failed: {}", entry.id, e);
+                state.db.set_pending_recording_status(entry.id, "failed")?;
+                RecoveredRecording { id: entry.id, created_at: entry.created_at, text: None, error: Some(e) }
+            }
+        };
+        recovered.push(outcome);
+    }
+    Ok(recovered)
+}
+
+/// Drop unprocessed recordings without transcribing them
+#[tauri::command]
+pub fn discard_pending_recordings(state: State<'_, AppState>) -> Result<usize, String> {
+    let entries = recoverable(&state)?;
+    for entry in &entries {
+        remove_entry(&state, entry.id)?;
+    }
+    Ok(entries.len())
+}
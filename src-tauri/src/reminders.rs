@@ -0,0 +1,198 @@
+// ============================================================================
+// Reminders Module - Scheduled and Recurring Spoken Reminders
+// ============================================================================
+//
+// A reminder is a text plus a schedule, stored in SQLite so it survives
+// restarts. The schedule is either
+//
+//   a date/time    "2026-10-20 09:30" (local) or RFC 3339, fires once
+//   a cron line    "30 9 * * Mon-Fri" (5 fields, or 6-7 with seconds/year)
+//
+// The next trigger time is kept in the database. A scheduler task checks for
+// due reminders, shows a notification and speaks the text with the TTS stage
+// of the pipeline. Speech is skipped while the app is busy (recording, a
+// reply in progress) or listening is paused for Do Not Disturb; the
+// notification is always shown.
+//
+// Reminders that came due while the app was closed still fire on the next
+// start if they are less than MISSED_GRACE old; older ones are skipped
+// (recurring reminders move on to their next occurrence).
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use tauri::{Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::daemon::APP_STATUS;
+use crate::database::Reminder;
+use crate::state::AppState;
+use crate::events::{emit_event, AppEvent};
+
+/// How often the scheduler looks for due reminders
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Reminders missed by less than this still fire (e.g. after a restart)
+const MISSED_GRACE_MS: i64 = 10 * 60 * 1000;
+
+/// Accepted local date/time formats for one-time reminders
+const DATETIME_FORMATS: &[&str] = &["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"];
+
+/// A parsed reminder schedule
+enum Schedule {
+    Once(DateTime<Local>),
+    Cron(Box<cron::Schedule>),
+}
+
+impl Schedule {
+    fn parse(input: &str) -> Result<Schedule, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Schedule must not be empty".to_string());
+        }
+
+        if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+            return Ok(Schedule::Once(at.with_timezone(&Local)));
+        }
+        for format in DATETIME_FORMATS {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(input, format) {
+                return Local
+                    .from_local_datetime(&naive)
+                    .earliest()
+                    .map(Schedule::Once)
+                    .ok_or_else(|| format!("'{}' does not exist in the local time zone", input));
+            }
+        }
+
+        // The cron crate expects a seconds field first
+        let expression = match input.split_whitespace().count() {
+            5 => format!("0 {}", input),
+            6 | 7 => input.to_string(),
+            _ => {
+                return Err(format!(
+                    "Invalid schedule '{}': expected a date/time (YYYY-MM-DD HH:MM) or a cron expression",
+                    input
+                ))
+            }
+        };
+        cron::Schedule::from_str(&expression)
+            .map(|schedule| Schedule::Cron(Box::new(schedule)))
+            .map_err(|e| format!("Invalid cron expression '{}': {}", input, e))
+    }
+
+    fn is_recurring(&self) -> bool {
+        matches!(self, Schedule::Cron(_))
+    }
+
+    /// First trigger time after `after` (Unix milliseconds)
+    fn next_after(&self, after: DateTime<Local>) -> Option<i64> {
+        match self {
+            Schedule::Once(at) => (*at > after).then(|| at.timestamp_millis()),
+            Schedule::Cron(schedule) => schedule.after(&after).next().map(|at| at.timestamp_millis()),
+        }
+    }
+}
+
+fn local_time(ms: i64) -> DateTime<Local> {
+    Local.timestamp_millis_opt(ms).single().unwrap_or_else(Local::now)
+}
+
+// ============================================================================
+// Scheduler
+// ============================================================================
+
+/// Start the scheduler task (call once the database is managed)
+pub fn start_scheduler(app: &tauri::AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = run_due(&app).await {
+                eprintln!("[REMINDERS] Check failed: {}", e);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Fire every due reminder and schedule its next occurrence
+async fn run_due(app: &tauri::AppHandle) -> Result<(), String> {
+    let now = Local::now();
+    let due = app.state::<AppState>().db.due_reminders(now.timestamp_millis())?;
+
+    for reminder in due {
+        let next_run = match Schedule::parse(&reminder.schedule) {
+            Ok(schedule) => schedule.next_after(now),
+            Err(e) => {
+                eprintln!("[REMINDERS] Reminder {} disabled: {}", reminder.id, e);
+                None
+            }
+        };
+        app.state::<AppState>().db.set_reminder_next_run(reminder.id, next_run)?;
+
+        let missed_by = now.timestamp_millis() - reminder.next_run.unwrap_or_default();
+        if missed_by > MISSED_GRACE_MS {
+            println!(
+                "[REMINDERS] Skipping reminder {} missed at {}",
+                reminder.id,
+                local_time(reminder.next_run.unwrap_or_default())
+            );
+            continue;
+        }
+        fire(app, &reminder).await;
+    }
+    Ok(())
+}
+
+/// Show and speak a reminder
+async fn fire(app: &tauri::AppHandle, reminder: &Reminder) {
+    println!("[REMINDERS] Reminder {}: {}", reminder.id, reminder.text);
+    emit_event(app, AppEvent::ReminderFired(reminder.clone()));
+
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("Speekium")
+        .body(&reminder.text)
+        .show()
+    {
+        eprintln!("[REMINDERS] Notification failed: {}", e);
+    }
+
+    if crate::focus_pause::is_paused() || !APP_STATUS.current().is_idle() {
+        println!("[REMINDERS] Busy or paused, not speaking reminder {}", reminder.id);
+        return;
+    }
+    crate::pipeline::speak_text(app, &reminder.text).await;
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Create a reminder from a date/time (fires once) or cron expression (recurring)
+#[tauri::command]
+pub fn create_reminder(state: State<'_, AppState>, text: String, schedule: String) -> Result<Reminder, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Reminder text must not be empty".to_string());
+    }
+    let parsed = Schedule::parse(&schedule)?;
+    let next_run = parsed
+        .next_after(Local::now())
+        .ok_or_else(|| format!("'{}' is in the past", schedule.trim()))?;
+
+    state.db.add_reminder(text, schedule.trim(), parsed.is_recurring(), next_run)
+}
+
+/// List reminders, next to fire first
+#[tauri::command]
+pub fn list_reminders(state: State<'_, AppState>) -> Result<Vec<Reminder>, String> {
+    state.db.list_reminders()
+}
+
+/// Delete a reminder
+#[tauri::command]
+pub fn delete_reminder(state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    state.db.delete_reminder(id)
+}
@@ -0,0 +1,111 @@
+// ============================================================================
+// Retention Module - Automatic Deletion of Old Conversations
+// ============================================================================
+//
+// Conversation history is kept forever unless a retention policy is set:
+//
+//   session_history_max_days        delete sessions not updated for this long
+//   session_history_max_messages    keep at most this many messages, deleting
+//                                   the oldest (and sessions left empty)
+//   session_history_favorites_only  delete every session that is not a
+//                                   favorite once idle for a day
+//
+// Favorite sessions are never deleted (0 / false = rule off). The policy is
+// applied at startup and then daily; `run_retention_now` applies it on
+// demand, or with `dry_run` only reports what would be deleted. Archived
+// recordings of deleted messages are removed by the audio archive cleanup.
+//
+// The daily run also purges sessions left in the trash for TRASH_MAX_DAYS,
+// whatever the policy.
+
+use std::time::Duration;
+
+use tauri::{Manager};
+
+use crate::config;
+use crate::database::RetentionReport;
+use crate::state::AppState;
+use crate::events::{emit_event, AppEvent};
+
+const MAX_DAYS_KEY: &str = "session_history_max_days";
+const MAX_MESSAGES_KEY: &str = "session_history_max_messages";
+const FAVORITES_ONLY_KEY: &str = "session_history_favorites_only";
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// With favorites only, the session in use survives until idle this long
+const FAVORITES_ONLY_IDLE_MS: i64 = DAY_MS;
+
+/// Trashed sessions are deleted for good after this many days
+const TRASH_MAX_DAYS: i64 = 30;
+
+const RUN_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Apply the retention policy (or report what it would delete)
+pub fn run<R: tauri::Runtime>(app: &tauri::AppHandle<R>, dry_run: bool) -> Result<RetentionReport, String> {
+    let state = app.try_state::<AppState>().ok_or_else(|| "Database is not ready".to_string())?;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    // 0 / false = rule off
+    let age_cutoff = config::read_config_value(MAX_DAYS_KEY)
+        .and_then(|v| v.as_u64())
+        .filter(|days| *days > 0)
+        .map(|days| now - days as i64 * DAY_MS);
+    let inactive_cutoff = config::read_config_value(FAVORITES_ONLY_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+        .then_some(now - FAVORITES_ONLY_IDLE_MS);
+    let max_messages = config::read_config_value(MAX_MESSAGES_KEY)
+        .and_then(|v| v.as_u64())
+        .filter(|max| *max > 0)
+        .map(|max| max as i64);
+
+    let report = state.db.apply_retention(age_cutoff, inactive_cutoff, max_messages, dry_run)?;
+    if !dry_run && (report.messages > 0 || !report.sessions.is_empty()) {
+        println!(
+            "[RETENTION] Deleted {} session(s) and {} message(s)",
+            report.sessions.len(),
+            report.messages
+        );
+        let _ = crate::ui::update_tray_menu(app);
+        emit_event(app, AppEvent::HistoryRetentionApplied(report.clone()));
+    }
+    Ok(report)
+}
+
+/// Delete sessions that have been in the trash for TRASH_MAX_DAYS
+fn purge_trash<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<usize, String> {
+    let state = app.try_state::<AppState>().ok_or_else(|| "Database is not ready".to_string())?;
+    let cutoff = chrono::Utc::now().timestamp_millis() - TRASH_MAX_DAYS * DAY_MS;
+    let purged = state.db.empty_trash(Some(cutoff))?;
+    if purged > 0 {
+        println!("[RETENTION] Purged {} session(s) from the trash", purged);
+    }
+    Ok(purged)
+}
+
+/// Apply the policy now and then once a day (call once the database is managed)
+pub fn start_scheduler(app: &tauri::AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = run(&app, false) {
+                eprintln!("[RETENTION] Cleanup failed: {}", e);
+            }
+            if let Err(e) = purge_trash(&app) {
+                eprintln!("[RETENTION] Trash purge failed: {}", e);
+            }
+            tokio::time::sleep(RUN_INTERVAL).await;
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Apply the retention policy now; with `dry_run`, only report what would be deleted
+#[tauri::command]
+pub fn run_retention_now(app: tauri::AppHandle, dry_run: Option<bool>) -> Result<RetentionReport, String> {
+    run(&app, dry_run.unwrap_or(false))
+}
@@ -0,0 +1,167 @@
+// ============================================================================
+// Secrets Module - LLM API Keys in the OS Keychain
+// ============================================================================
+//
+// Provider API keys are stored in the macOS Keychain, Windows Credential
+// Manager or the Secret Service (libsecret) rather than config.json: one entry
+// per provider under the "speekium" service. The `api_key` fields of
+// `llm_providers` stay in the file but are saved empty; load_config fills them
+// in for the settings UI and save_config moves them back into the keychain.
+// The daemon receives the keys with every `reload_config`.
+//
+// Keys written in plain text by earlier versions are moved on startup. If the
+// keychain is unavailable (e.g. no Secret Service running), a key stays in
+// config.json so it is never lost.
+
+use crate::config::{self, LlmProviderConfig};
+
+const SERVICE: &str = "speekium";
+
+fn entry(provider: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, provider).map_err(|e| format!("Failed to open keychain entry: {}", e))
+}
+
+/// Stored key for `provider` (None if there is none)
+pub fn get_key(provider: &str) -> Result<Option<String>, String> {
+    match entry(provider)?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read API key for {}: {}", provider, e)),
+    }
+}
+
+/// Store the key for `provider` (an empty key deletes it)
+pub fn store_key(provider: &str, key: &str) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return delete_key(provider).map(|_| ());
+    }
+    entry(provider)?
+        .set_password(key)
+        .map_err(|e| format!("Failed to store API key for {}: {}", provider, e))
+}
+
+/// Remove the key for `provider`; returns false if there was none
+pub fn delete_key(provider: &str) -> Result<bool, String> {
+    match entry(provider)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(format!("Failed to delete API key for {}: {}", provider, e)),
+    }
+}
+
+/// Key to use for a configured provider (keychain first, then a leftover plain-text key)
+pub fn api_key_for(provider: &LlmProviderConfig) -> String {
+    get_key(&provider.name)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| provider.api_key.clone())
+}
+
+/// Keychain keys of every configured provider, by provider name (sent to the daemon)
+pub fn api_keys() -> serde_json::Map<String, serde_json::Value> {
+    let config = config::load_config_with_defaults();
+    let Some(providers) = config.get("llm_providers").and_then(|v| v.as_array()) else {
+        return serde_json::Map::new();
+    };
+    providers
+        .iter()
+        .filter_map(|p| p.get("name").and_then(|v| v.as_str()))
+        .filter_map(|name| get_key(name).ok().flatten().map(|key| (name.to_string(), serde_json::json!(key))))
+        .collect()
+}
+
+/// Fill the `api_key` fields of `llm_providers` from the keychain (for the settings UI)
+pub fn fill_api_keys(config: &mut serde_json::Value) {
+    let Some(providers) = config.get_mut("llm_providers").and_then(|v| v.as_array_mut()) else {
+        return;
+    };
+    for provider in providers.iter_mut() {
+        let Some(name) = provider.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        if let Ok(Some(key)) = get_key(name) {
+            provider["api_key"] = serde_json::json!(key);
+        }
+    }
+}
+
+/// Move the `api_key` fields of `llm_providers` into the keychain and blank them
+///
+/// An empty field deletes the stored key. A key the keychain refuses is left in place.
+pub fn take_api_keys(config: &mut serde_json::Value) {
+    let Some(providers) = config.get_mut("llm_providers").and_then(|v| v.as_array_mut()) else {
+        return;
+    };
+    for provider in providers.iter_mut() {
+        let Some(name) = provider.get("name").and_then(|v| v.as_str()).map(str::to_string) else {
+            continue;
+        };
+        let key = provider.get("api_key").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        match store_key(&name, &key) {
+            Ok(()) => provider["api_key"] = serde_json::json!(""),
+            Err(e) => eprintln!("⚠️ {} (keeping it in config.json)", e),
+        }
+    }
+}
+
+/// Move plain-text keys from config.json into the keychain (run once at startup)
+///
+/// Also queues a config reload so the daemon receives the keys once it is ready.
+pub fn migrate_plaintext_keys() {
+    if let Some(mut providers) = config::read_config_value("llm_providers") {
+        let mut moved = Vec::new();
+        for provider in providers.as_array_mut().into_iter().flatten() {
+            let (Some(name), Some(key)) = (
+                provider.get("name").and_then(|v| v.as_str()).map(str::to_string),
+                provider.get("api_key").and_then(|v| v.as_str()).map(str::to_string),
+            ) else {
+                continue;
+            };
+            if key.trim().is_empty() {
+                continue;
+            }
+            match store_key(&name, &key) {
+                Ok(()) => {
+                    provider["api_key"] = serde_json::json!("");
+                    moved.push(name);
+                }
+                Err(e) => eprintln!("⚠️ {} (keeping it in config.json)", e),
+            }
+        }
+
+        if !moved.is_empty() {
+            match config::write_config_value("llm_providers", providers) {
+                Ok(()) => println!("🔐 Moved API keys to the keychain: {}", moved.join(", ")),
+                Err(e) => eprintln!("⚠️ Failed to remove plain-text API keys: {}", e),
+            }
+        }
+    }
+    config::notify_daemon_config_changed();
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Store an API key for a provider (an empty key deletes it)
+#[tauri::command]
+pub fn set_api_key(provider: String, api_key: String) -> Result<(), String> {
+    store_key(&provider, &api_key)?;
+    config::notify_daemon_config_changed();
+    Ok(())
+}
+
+/// Stored API key for a provider (None if there is none)
+#[tauri::command]
+pub fn get_api_key(provider: String) -> Result<Option<String>, String> {
+    get_key(&provider)
+}
+
+/// Delete the stored API key for a provider; returns false if there was none
+#[tauri::command]
+pub fn delete_api_key(provider: String) -> Result<bool, String> {
+    let deleted = delete_key(&provider)?;
+    config::notify_daemon_config_changed();
+    Ok(deleted)
+}
@@ -0,0 +1,307 @@
+// ============================================================================
+// Selection Module - Ask the LLM About Selected Text
+// ============================================================================
+//
+// The `ask_selection` shortcut takes the text selected in the focused app,
+// fills it into a prompt template and sends it to the LLM as a one-off chat
+// (an empty history, so the conversation is neither used nor changed). The
+// selection is copied with a simulated Cmd/Ctrl+C and the clipboard restored
+// afterwards; on Linux the PRIMARY selection is read instead.
+//
+// `ask_selection.output` decides what happens with the answer: "popover"
+// shows it in a small window (with copy / replace buttons), "replace" types it
+// over the selection. Errors are always shown in the popover.
+//
+// Built-in templates are summarize, translate and fix_grammar;
+// `ask_selection.templates` adds more or overrides them. `{text}` in a prompt
+// is replaced by the selection (appended if the prompt has no placeholder).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{Manager, Runtime, WebviewWindowBuilder};
+
+use crate::config::{self, AskSelectionConfig};
+use crate::daemon::call_daemon_async;
+use crate::types::ChatResult;
+use crate::events::{emit_event, AppEvent};
+
+const CONFIG_KEY: &str = "ask_selection";
+
+const TEXT_PLACEHOLDER: &str = "{text}";
+
+/// Templates available without configuration (name, prompt)
+const BUILTIN_TEMPLATES: [(&str, &str); 3] = [
+    (
+        "summarize",
+        "Summarize the following text concisely, in the language of the text:\n\n{text}",
+    ),
+    (
+        "translate",
+        "Translate the following text into English, or into Chinese if it is already English. \
+         Reply with the translation only:\n\n{text}",
+    ),
+    (
+        "fix_grammar",
+        "Fix the grammar and spelling of the following text without changing its meaning or language. \
+         Reply with the corrected text only:\n\n{text}",
+    ),
+];
+
+pub const POPOVER_LABEL: &str = "ask-popover";
+const POPOVER_WIDTH: f64 = 420.0;
+const POPOVER_HEIGHT: f64 = 280.0;
+
+/// Time to let go of the shortcut keys before the copy is simulated
+const SHORTCUT_RELEASE_DELAY: Duration = Duration::from_millis(150);
+
+/// Time for focus to return to the target app after the popover hides
+const PASTE_DELAY: Duration = Duration::from_millis(250);
+
+/// What to do with the answer (`ask_selection.output`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AskSelectionOutput {
+    Popover,
+    Replace,
+}
+
+impl AskSelectionOutput {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "popover" => Some(AskSelectionOutput::Popover),
+            "replace" => Some(AskSelectionOutput::Replace),
+            _ => None,
+        }
+    }
+}
+
+/// What the popover shows
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SelectionAnswer {
+    pub template: String,
+    pub selection: String,
+    /// None while the LLM is still answering (or after an error)
+    pub answer: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Last answer, read by the popover when it loads
+static LAST_ANSWER: Mutex<Option<SelectionAnswer>> = Mutex::new(None);
+
+static IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Prompt of a configured or built-in template
+pub fn template_prompt(config: &AskSelectionConfig, name: &str) -> Option<String> {
+    config.templates.get(name).cloned().or_else(|| {
+        BUILTIN_TEMPLATES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, prompt)| prompt.to_string())
+    })
+}
+
+/// Put `text` into a prompt at `{text}` (appended if there is no placeholder)
+pub fn fill_template(prompt: &str, text: &str) -> String {
+    if prompt.contains(TEXT_PLACEHOLDER) {
+        prompt.replace(TEXT_PLACEHOLDER, text)
+    } else {
+        format!("{}\n\n{}", prompt, text)
+    }
+}
+
+fn load_config() -> AskSelectionConfig {
+    config::read_config_value(CONFIG_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// One-off chat with the daemon's LLM
+pub async fn ask_llm(prompt: String) -> Result<String, String> {
+    let args = serde_json::json!({ "text": prompt, "history": [] });
+    let result: ChatResult = serde_json::from_value(call_daemon_async("chat", args).await?)
+        .map_err(|e| format!("Failed to parse result: {}", e))?;
+    match (result.success, result.content) {
+        (true, Some(content)) => Ok(content.trim().to_string()),
+        _ => Err(result.error.unwrap_or_else(|| "The LLM returned no answer".to_string())),
+    }
+}
+
+/// Copy the selection, run a template on it and show or type the answer
+///
+/// `template` defaults to `ask_selection.template`.
+pub async fn ask<R: Runtime>(app: tauri::AppHandle<R>, template: Option<String>) -> Result<(), String> {
+    if IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        return Err("Already answering a selection".to_string());
+    }
+    let result = run(&app, template).await;
+    IN_PROGRESS.store(false, Ordering::SeqCst);
+
+    if let Err(e) = &result {
+        set_answer(&app, SelectionAnswer {
+            error: Some(e.clone()),
+            ..Default::default()
+        });
+        show_popover(&app);
+    }
+    result
+}
+
+async fn run<R: Runtime>(app: &tauri::AppHandle<R>, template: Option<String>) -> Result<(), String> {
+    let config = load_config();
+    let name = template.unwrap_or_else(|| config.template.clone());
+    let prompt = template_prompt(&config, &name).ok_or_else(|| format!("Unknown template: {}", name))?;
+    let output = AskSelectionOutput::from_str(&config.output).unwrap_or(AskSelectionOutput::Popover);
+
+    tokio::time::sleep(SHORTCUT_RELEASE_DELAY).await;
+    let selection = tauri::async_runtime::spawn_blocking(crate::platform::copy_selection)
+        .await
+        .map_err(|e| format!("Failed to read the selection: {}", e))??
+        .ok_or_else(|| "No text is selected".to_string())?;
+
+    let mut answer = SelectionAnswer {
+        template: name,
+        selection: selection.clone(),
+        ..Default::default()
+    };
+    if output == AskSelectionOutput::Popover {
+        // Show the selection right away; the answer follows
+        set_answer(app, answer.clone());
+        show_popover(app);
+    }
+
+    match ask_llm(fill_template(&prompt, &selection)).await {
+        Ok(text) => answer.answer = Some(text),
+        Err(e) => answer.error = Some(e),
+    }
+
+    match (output, &answer.answer) {
+        (AskSelectionOutput::Replace, Some(text)) => {
+            crate::platform::paste_text_after(text.clone(), Duration::ZERO);
+        }
+        _ => {
+            set_answer(app, answer);
+            show_popover(app);
+        }
+    }
+    Ok(())
+}
+
+/// Show an answer in the popover (also used by prompt templates)
+pub fn show_answer<R: Runtime>(app: &tauri::AppHandle<R>, answer: SelectionAnswer) {
+    set_answer(app, answer);
+    show_popover(app);
+}
+
+fn set_answer<R: Runtime>(app: &tauri::AppHandle<R>, answer: SelectionAnswer) {
+    *LAST_ANSWER.lock().unwrap() = Some(answer.clone());
+    emit_event(app, AppEvent::SelectionAnswer(answer));
+}
+
+// ============================================================================
+// Popover Window
+// ============================================================================
+
+fn create_popover<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<tauri::WebviewWindow<R>> {
+    WebviewWindowBuilder::new(app, POPOVER_LABEL, tauri::WebviewUrl::App("ask-popover.html".into()))
+        .title("Ask Selection")
+        .inner_size(POPOVER_WIDTH, POPOVER_HEIGHT)
+        .center()
+        .always_on_top(true)
+        .decorations(false)
+        .resizable(false)
+        .skip_taskbar(true)
+        // Keep focus (and the selection) in the app the text came from
+        .focused(false)
+        .visible(false)
+        .build()
+}
+
+fn show_popover<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let popover = match app.get_webview_window(POPOVER_LABEL) {
+        Some(popover) => popover,
+        None => match create_popover(app) {
+            Ok(popover) => popover,
+            Err(e) => {
+                eprintln!("Failed to create ask-selection popover: {}", e);
+                return;
+            }
+        },
+    };
+    if !popover.is_visible().unwrap_or(false) {
+        let _ = popover.center();
+        let _ = popover.show();
+    }
+}
+
+/// Hide the popover and hand focus back to the app the selection came from
+fn hide_popover<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(popover) = app.get_webview_window(POPOVER_LABEL) {
+        let _ = popover.hide();
+    }
+    // Clicking the popover activates the app; hide it so the previous one gets focus
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.hide();
+    }
+}
+
+fn last_answer_text() -> Result<String, String> {
+    LAST_ANSWER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|answer| answer.answer.clone())
+        .ok_or_else(|| "No answer yet".to_string())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Run a template on the selected text (`template` defaults to `ask_selection.template`)
+#[tauri::command]
+pub async fn ask_selection(app: tauri::AppHandle, template: Option<String>) -> Result<(), String> {
+    ask(app, template).await
+}
+
+/// Names of the built-in and configured templates
+#[tauri::command]
+pub fn list_selection_templates() -> Vec<String> {
+    let config = load_config();
+    let mut names: Vec<String> = BUILTIN_TEMPLATES.iter().map(|(name, _)| name.to_string()).collect();
+    for name in config.templates.into_keys() {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Last selection answer (None before the first one)
+#[tauri::command]
+pub fn get_selection_answer() -> Option<SelectionAnswer> {
+    LAST_ANSWER.lock().unwrap().clone()
+}
+
+/// Put the answer on the clipboard
+#[tauri::command]
+pub fn copy_selection_answer() -> Result<(), String> {
+    crate::platform::copy_text(&last_answer_text()?)
+}
+
+/// Close the popover and type the answer over the selection
+#[tauri::command]
+pub fn replace_selection_with_answer(app: tauri::AppHandle) -> Result<(), String> {
+    let text = last_answer_text()?;
+    hide_popover(&app);
+    crate::platform::paste_text_after(text, PASTE_DELAY);
+    Ok(())
+}
+
+/// Close the popover
+#[tauri::command]
+pub fn close_selection_answer(app: tauri::AppHandle) {
+    hide_popover(&app);
+}
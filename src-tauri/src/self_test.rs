@@ -0,0 +1,188 @@
+// ============================================================================
+// Self-Test Module - End-to-End Pipeline Check
+// ============================================================================
+//
+// `run_self_test` walks through every stage a voice conversation uses and
+// reports each one with its timing, so a broken setup can be narrowed down
+// (and pasted into a support request) without reading logs:
+//
+//   daemon      the voice service answers a health check
+//   microphone  the default input device can be opened
+//   asr         2 s of silence are transcribed (an empty transcript passes)
+//   llm         a trivial prompt gets a reply (history is left untouched)
+//   tts         a short sentence is synthesized, bypassing the TTS cache
+//   playback    the clip is played through the daemon
+//
+// A failed stage does not stop the test, but stages that depend on it are
+// reported as skipped (everything but the microphone needs the daemon, and
+// playback needs the TTS clip).
+
+use std::future::Future;
+use std::time::Instant;
+
+use crate::daemon::{call_daemon_async, is_daemon_ready, APP_STATUS};
+use crate::types::{SelfTestReport, SelfTestStage};
+
+/// Length of the silent clip sent to ASR
+const SILENCE_SECS: usize = 2;
+const SAMPLE_RATE: usize = 16000;
+
+const LLM_PROMPT: &str = "Reply with the single word OK.";
+const TTS_TEXT: &str = "Speekium self-test.";
+
+/// Longest detail kept in the report
+const MAX_DETAIL_CHARS: usize = 200;
+
+/// Time a stage; `Ok` carries an optional detail for the report
+async fn run_stage<F>(stage: &str, run: F) -> SelfTestStage
+where
+    F: Future<Output = Result<Option<String>, String>>,
+{
+    let started = Instant::now();
+    let result = run.await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        Ok(_) => println!("[SELF TEST] {} passed in {} ms", stage, duration_ms),
+        Err(e) => eprintln!("[SELF TEST] {} failed in {} ms: {}", stage, duration_ms, e),
+    }
+    let (detail, error) = match result {
+        Ok(detail) => (detail.map(|d| d.chars().take(MAX_DETAIL_CHARS).collect()), None),
+        Err(e) => (None, Some(e)),
+    };
+    SelfTestStage { stage: stage.to_string(), passed: error.is_none(), skipped: false, duration_ms, detail, error }
+}
+
+fn skipped(stage: &str, reason: &str) -> SelfTestStage {
+    SelfTestStage {
+        stage: stage.to_string(),
+        passed: false,
+        skipped: true,
+        duration_ms: 0,
+        detail: None,
+        error: Some(reason.to_string()),
+    }
+}
+
+/// Call a daemon command and turn `success: false` into an error
+async fn daemon_command(command: &str, args: serde_json::Value) -> Result<serde_json::Value, String> {
+    let result = call_daemon_async(command, args).await?;
+    if result.get("success").and_then(|v| v.as_bool()) == Some(false) {
+        let error = result.get("error").and_then(|v| v.as_str()).unwrap_or("Unknown error");
+        return Err(error.to_string());
+    }
+    Ok(result)
+}
+
+async fn check_daemon() -> Result<Option<String>, String> {
+    // call_daemon_async waits for readiness; the test should report it instead
+    if !is_daemon_ready() {
+        return Err("Voice service is not ready".to_string());
+    }
+    daemon_command("health", serde_json::json!({ "quick": true })).await?;
+    Ok(None)
+}
+
+async fn check_microphone() -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(crate::audio::probe_microphone)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(None)
+}
+
+async fn check_asr() -> Result<Option<String>, String> {
+    let silence = vec![0.0f32; SILENCE_SECS * SAMPLE_RATE];
+    // The daemon deletes the file after reading it
+    let audio_path = crate::audio::write_temp_wav(&silence, "self_test")?;
+    let result = daemon_command("transcribe", serde_json::json!({ "audio_path": audio_path })).await?;
+    let text = result.get("text").and_then(|v| v.as_str()).unwrap_or_default();
+    Ok(Some(format!("transcript: {:?}", text)))
+}
+
+async fn check_llm(app: &tauri::AppHandle) -> Result<Option<String>, String> {
+    let reply = if crate::llm::native_enabled() {
+        let result = crate::llm::chat_detached(app, LLM_PROMPT).await;
+        if !result.success {
+            return Err(result.error.unwrap_or_else(|| "Unknown error".to_string()));
+        }
+        result.content.unwrap_or_default()
+    } else {
+        // An explicit (empty) history keeps the test out of the conversation
+        let args = crate::personas::with_persona(app, serde_json::json!({ "text": LLM_PROMPT, "history": [] }));
+        let result = daemon_command("chat", args).await?;
+        result.get("content").and_then(|v| v.as_str()).unwrap_or_default().to_string()
+    };
+    if reply.trim().is_empty() {
+        return Err("The model returned an empty reply".to_string());
+    }
+    Ok(Some(reply.trim().to_string()))
+}
+
+async fn check_tts(app: &tauri::AppHandle, audio_path: &mut Option<String>) -> Result<Option<String>, String> {
+    let args = crate::personas::with_persona(app, serde_json::json!({ "text": TTS_TEXT }));
+    let result = daemon_command("tts", args).await?;
+    let path = result
+        .get("audio_path")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "No audio was generated".to_string())?;
+    *audio_path = Some(path.to_string());
+    Ok(None)
+}
+
+async fn check_playback(audio_path: &str) -> Result<Option<String>, String> {
+    let result = daemon_command(
+        "play_audio",
+        serde_json::json!({ "audio_path": audio_path, "clear_interrupt": true }),
+    )
+    .await?;
+    if result.get("interrupted").and_then(|v| v.as_bool()) == Some(true) {
+        return Err("Playback was interrupted".to_string());
+    }
+    Ok(None)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Exercise the whole voice pipeline and report each stage
+#[tauri::command]
+pub async fn run_self_test(app: tauri::AppHandle) -> Result<SelfTestReport, String> {
+    if !APP_STATUS.current().is_idle() {
+        return Err("Speekium is busy, run the self-test when it is idle".to_string());
+    }
+    println!("[SELF TEST] Starting");
+    let started = Instant::now();
+    let mut stages = Vec::new();
+
+    let daemon = run_stage("daemon", check_daemon()).await;
+    let daemon_ok = daemon.passed;
+    stages.push(daemon);
+    stages.push(run_stage("microphone", check_microphone()).await);
+
+    if daemon_ok {
+        stages.push(run_stage("asr", check_asr()).await);
+        stages.push(run_stage("llm", check_llm(&app)).await);
+
+        let mut audio_path = None;
+        stages.push(run_stage("tts", check_tts(&app, &mut audio_path)).await);
+        match audio_path {
+            Some(path) => {
+                stages.push(run_stage("playback", check_playback(&path)).await);
+                let _ = std::fs::remove_file(&path);
+            }
+            None => stages.push(skipped("playback", "TTS failed")),
+        }
+    } else {
+        for stage in ["asr", "llm", "tts", "playback"] {
+            stages.push(skipped(stage, "Voice service unavailable"));
+        }
+    }
+
+    let report = SelfTestReport {
+        passed: stages.iter().all(|s| s.passed),
+        stages,
+        total_ms: started.elapsed().as_millis() as u64,
+    };
+    println!("[SELF TEST] Finished: {}", if report.passed { "passed" } else { "failed" });
+    Ok(report)
+}
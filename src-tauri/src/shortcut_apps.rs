@@ -0,0 +1,180 @@
+// ============================================================================
+// Shortcut Apps Module - Global Shortcuts Off in Selected Apps
+// ============================================================================
+//
+// Some apps need every key for themselves: games, remote desktops, virtual
+// machines. `shortcut_apps` lists where the global shortcuts are turned off:
+//
+//   blocked   apps where the shortcuts are off
+//   allowed   if not empty, the only apps where the shortcuts are on
+//
+// Entries match a bundle id or app name, case-insensitively (as in
+// `focus_pause.apps`). Speekium's own windows always keep the shortcuts.
+//
+// A monitor looks at the frontmost app every CHECK_INTERVAL. When a blocked
+// app comes to the front, every global shortcut (actions, PTT, prompt
+// template hotkeys) is unregistered, so its keys reach the app; they are
+// registered again once another app is focused. Dictation is not typed into
+// a blocked app either. The frontmost app is only known on macOS.
+//
+// The lists are parsed once and reloaded when config.json is written, and
+// the input listener and text input only read what the last check found, so
+// neither touches the disk or asks for the frontmost app.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::config::{self, ShortcutAppsConfig};
+use crate::daemon::{APP_HANDLE, APP_STATUS};
+use crate::platform::FrontmostApp;
+use crate::types::AppStatus;
+
+const CONFIG_KEY: &str = "shortcut_apps";
+
+/// Short enough that the shortcuts are back right after switching apps
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Parsed `shortcut_apps` (None = not loaded yet)
+static CONFIG: Mutex<Option<ShortcutAppsConfig>> = Mutex::new(None);
+
+/// Frontmost app the shortcuts are off for, as of the last check
+static FRONTMOST_BLOCKING: Mutex<Option<String>> = Mutex::new(None);
+
+/// Whether FRONTMOST_BLOCKING is set, for the input listener callback
+static BLOCKED: AtomicBool = AtomicBool::new(false);
+
+/// App the shortcuts are unregistered for (None = registered)
+static BLOCKED_BY: Mutex<Option<String>> = Mutex::new(None);
+
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn parse_config(config: &serde_json::Value) -> ShortcutAppsConfig {
+    config
+        .get(CONFIG_KEY)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Take the lists from the config just written to config.json
+pub fn reload_config(config: &serde_json::Value) {
+    *CONFIG.lock().unwrap() = Some(parse_config(config));
+}
+
+fn cached_config() -> ShortcutAppsConfig {
+    CONFIG.lock().unwrap().get_or_insert_with(|| parse_config(&config::read_config())).clone()
+}
+
+fn listed(apps: &[String], front: &FrontmostApp) -> bool {
+    front.ids().into_iter().any(|id| apps.iter().any(|app| app.eq_ignore_ascii_case(id)))
+}
+
+/// Name of `front` if the shortcuts are off while it is frontmost
+fn blocking_app(config: &ShortcutAppsConfig, front: &FrontmostApp, own_id: &str) -> Option<String> {
+    if front.bundle_id.as_deref() == Some(own_id) {
+        return None;
+    }
+    let blocked = listed(&config.blocked, front) || (!config.allowed.is_empty() && !listed(&config.allowed, front));
+    if !blocked {
+        return None;
+    }
+    front.name.clone().or_else(|| front.bundle_id.clone())
+}
+
+fn find_blocking_app() -> Option<String> {
+    let front = crate::platform::frontmost_app()?;
+    let own_id = APP_HANDLE.get().map(|app| app.config().identifier.clone()).unwrap_or_default();
+    blocking_app(&cached_config(), &front, &own_id)
+}
+
+/// App the shortcuts are off for, if it was frontmost at the last check
+pub fn frontmost_blocking_app() -> Option<String> {
+    FRONTMOST_BLOCKING.lock().unwrap().clone()
+}
+
+/// Whether a blocked app was frontmost at the last check
+pub fn is_blocked() -> bool {
+    BLOCKED.load(Ordering::SeqCst)
+}
+
+/// Unregister or re-register the shortcuts when the frontmost app changes
+fn check(app: &tauri::AppHandle) {
+    let blocking = find_blocking_app();
+    BLOCKED.store(blocking.is_some(), Ordering::SeqCst);
+    *FRONTMOST_BLOCKING.lock().unwrap() = blocking.clone();
+
+    let mut blocked_by = BLOCKED_BY.lock().unwrap();
+    if *blocked_by == blocking {
+        return;
+    }
+    match (&*blocked_by, &blocking) {
+        (None, Some(name)) => {
+            // Keep the PTT shortcut until its release has been seen
+            if APP_STATUS.current() == AppStatus::Recording {
+                return;
+            }
+            println!("⌨️ Shortcuts off while {} is frontmost", name);
+            crate::shortcuts::unregister_all_shortcuts(app);
+        }
+        (Some(_), None) => {
+            println!("⌨️ Shortcuts back on");
+            crate::shortcuts::register_all_shortcuts(app);
+        }
+        // Another blocked app: the shortcuts stay off
+        _ => {}
+    }
+    *blocked_by = blocking;
+}
+
+/// Start the periodic frontmost app check (once per app run)
+pub fn start_monitor(app: &tauri::AppHandle) {
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let check_app = app.clone();
+            if let Err(e) = tauri::async_runtime::spawn_blocking(move || check(&check_app)).await {
+                eprintln!("[SHORTCUT APPS] Check failed: {}", e);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Forget the state after the shortcuts were registered elsewhere (profile switch)
+///
+/// The lists are read again from the new config.json, and the next check
+/// unregisters the shortcuts again if a blocked app is frontmost.
+pub fn reset() {
+    *CONFIG.lock().unwrap() = None;
+    *FRONTMOST_BLOCKING.lock().unwrap() = None;
+    BLOCKED.store(false, Ordering::SeqCst);
+    *BLOCKED_BY.lock().unwrap() = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn front(bundle_id: &str, name: &str) -> FrontmostApp {
+        FrontmostApp { bundle_id: Some(bundle_id.to_string()), name: Some(name.to_string()) }
+    }
+
+    #[test]
+    fn test_blocking_app() {
+        let own = "com.speekium.app";
+        let game = front("com.valvesoftware.steam", "Steam");
+        let editor = front("com.microsoft.VSCode", "Code");
+
+        let config = ShortcutAppsConfig { blocked: vec!["steam".to_string()], allowed: Vec::new() };
+        assert_eq!(blocking_app(&config, &game, own), Some("Steam".to_string()));
+        assert_eq!(blocking_app(&config, &editor, own), None);
+
+        let config = ShortcutAppsConfig { blocked: Vec::new(), allowed: vec!["com.microsoft.vscode".to_string()] };
+        assert_eq!(blocking_app(&config, &game, own), Some("Steam".to_string()));
+        assert_eq!(blocking_app(&config, &editor, own), None);
+        assert_eq!(blocking_app(&config, &front(own, "Speekium"), own), None);
+    }
+}
@@ -2,13 +2,38 @@
 // Shortcuts Module - Global Shortcut Management
 // ============================================================================
 
-use tauri::{Emitter, Manager, Runtime};
-use crate::daemon::{CURRENT_PTT_SHORTCUT, PTT_KEY_PRESSED, AUDIO_RECORDER, DAEMON, RECORDING_MODE_CHANNEL};
-use crate::types::{RecordingMode, WorkMode, AppStatus};
-use crate::audio::AudioRecorder;
+use tauri::{Manager, Runtime};
+use crate::daemon::{
+    ACTION_SHORTCUTS, CURRENT_PTT_SHORTCUT, PTT_KEY_PRESSED, PTT_TIMED_OUT, PTT_LATCHED, PTT_TAP_PENDING,
+    PTT_IGNORE_RELEASE, PTT_PRESSED_AT, PTT_TRIGGER, INPUT_LISTENER_STARTED, AUDIO_RECORDER, DAEMON,
+    RECORDING_MODE_CHANNEL, APP_STATUS,
+};
+use crate::daemon::request::cancel_active_streams;
+use crate::types::{AppStatus, MicConflictPayload, RecordingMode, RecordingTimeoutPayload, WorkMode, PttTrigger, ShortcutValidationResult};
+use crate::audio::{AudioRecorder, MicError, RecordingTimeout};
+use crate::config;
+use crate::earcons::Cue;
+use crate::platform::{self, RawInputEvent};
 use crate::ui;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use crate::events::{emit_event, AppEvent};
+
+/// Config key enabling double-tap PTT to latch hands-free recording
+pub const PTT_DOUBLE_TAP_LATCH_KEY: &str = "ptt_double_tap_latch";
+
+/// A PTT press shorter than this counts as a tap, and a second tap must follow within it
+const PTT_DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(300);
+
+/// How often a busy microphone is tried again while the PTT key is held
+const MIC_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Give up on a busy microphone after this long
+const MIC_RETRY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A retry loop for a busy microphone is running
+static MIC_RETRYING: AtomicBool = AtomicBool::new(false);
 
 /// Convert hotkey config JSON to Tauri shortcut string
 /// e.g., {"key": "Digit3", "modifiers": ["CmdOrCtrl"]} -> "CommandOrControl+3"
@@ -95,152 +120,462 @@ pub fn register_ptt_shortcut(app_handle: &tauri::AppHandle, shortcut_str: &str)
 
     app_handle.global_shortcut().on_shortcut(ptt_shortcut, move |app, _shortcut, event| {
         match event.state() {
-            ShortcutState::Pressed => {
-                // Filter out key repeat - only handle first press
-                if PTT_KEY_PRESSED.swap(true, Ordering::SeqCst) {
-                    // Already pressed, ignore key repeat
+            ShortcutState::Pressed => handle_ptt_press(app),
+            ShortcutState::Released => handle_ptt_release(app),
+        }
+    }).map_err(|e| format!("Failed to register PTT shortcut: {}", e))?;
+
+    Ok(())
+}
+
+/// Unregister the keyboard PTT shortcut, if any
+fn unregister_ptt_shortcut(app_handle: &tauri::AppHandle) {
+    let mut current = CURRENT_PTT_SHORTCUT.lock().unwrap();
+    if let Some(ref shortcut_str) = *current {
+        if let Ok(shortcut) = shortcut_str.parse::<Shortcut>() {
+            let _ = app_handle.global_shortcut().unregister(shortcut);
+        }
+    }
+    *current = None;
+}
+
+// ============================================================================
+// Alternative PTT Triggers (mouse button / Fn key)
+// ============================================================================
+
+/// Config key selecting the PTT trigger: "shortcut" | "mouse_button" | "fn_key"
+pub const PTT_TRIGGER_KEY: &str = "ptt_trigger";
+
+/// Config key with the mouse button number used by the "mouse_button" trigger
+pub const PTT_MOUSE_BUTTON_KEY: &str = "ptt_mouse_button";
+
+/// Back side button on most mice
+const DEFAULT_PTT_MOUSE_BUTTON: i64 = 3;
+
+/// Read the PTT trigger from config.json (keyboard shortcut if unset or invalid)
+pub fn ptt_trigger_from_config() -> PttTrigger {
+    let mouse_button = config::read_config_value(PTT_MOUSE_BUTTON_KEY)
+        .and_then(|v| v.as_i64())
+        .unwrap_or(DEFAULT_PTT_MOUSE_BUTTON);
+
+    config::read_config_value(PTT_TRIGGER_KEY)
+        .and_then(|v| v.as_str().and_then(|s| PttTrigger::from_str(s, mouse_button)))
+        .unwrap_or(PttTrigger::Shortcut)
+}
+
+/// Pressed state if `event` comes from the given trigger
+fn match_trigger(trigger: PttTrigger, event: RawInputEvent) -> Option<bool> {
+    match (trigger, event) {
+        (PttTrigger::MouseButton(wanted), RawInputEvent::MouseButton { button, pressed }) if wanted == button => {
+            Some(pressed)
+        }
+        (PttTrigger::FnKey, RawInputEvent::FnKey { pressed }) => Some(pressed),
+        _ => None,
+    }
+}
+
+/// Start the low-level input listener (once); events are matched against PTT_TRIGGER
+fn ensure_input_listener(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    if INPUT_LISTENER_STARTED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let app = app_handle.clone();
+    let result = platform::start_input_listener(move |event| {
+        let trigger = *PTT_TRIGGER.lock().unwrap();
+        let Some(pressed) = match_trigger(trigger, event) else {
+            return;
+        };
+
+        // Same rule as the keyboard shortcut: PTT only works in push-to-talk mode
+        if *crate::daemon::RECORDING_MODE.lock().unwrap() != RecordingMode::PushToTalk {
+            return;
+        }
+        // The keyboard shortcut is unregistered in a blocked app, this trigger is ignored
+        if pressed && crate::shortcut_apps::is_blocked() {
+            return;
+        }
+
+        if pressed {
+            handle_ptt_press(&app);
+        } else {
+            handle_ptt_release(&app);
+        }
+    });
+
+    if result.is_err() {
+        INPUT_LISTENER_STARTED.store(false, Ordering::SeqCst);
+    }
+    result
+}
+
+/// Handle a PTT trigger press (keyboard shortcut, mouse button or Fn key)
+fn handle_ptt_press(app: &tauri::AppHandle) {
+    // Filter out key repeat - only handle first press
+    if PTT_KEY_PRESSED.swap(true, Ordering::SeqCst) {
+        // Already pressed, ignore key repeat
+        return;
+    }
+    PTT_TIMED_OUT.store(false, Ordering::SeqCst);
+
+    // Single press while latched: stop hands-free recording
+    if PTT_LATCHED.swap(false, Ordering::SeqCst) {
+        PTT_IGNORE_RELEASE.store(true, Ordering::SeqCst);
+        finish_ptt_recording(app);
+        return;
+    }
+
+    // Second tap of a double-tap: keep the recording started by the first tap running
+    if PTT_TAP_PENDING.swap(false, Ordering::SeqCst) {
+        PTT_LATCHED.store(true, Ordering::SeqCst);
+        PTT_IGNORE_RELEASE.store(true, Ordering::SeqCst);
+        ui::emit_ptt_state_static(app, "latched");
+        return;
+    }
+
+    *PTT_PRESSED_AT.lock().unwrap() = Some(Instant::now());
+
+    barge_in();
+
+    // Start Rust-side audio recording
+    if let Err(e) = start_ptt_audio(app) {
+        report_mic_conflict(app, &e);
+        if e.is_retryable() {
+            retry_ptt_audio(app);
+        }
+        return;
+    }
+    begin_ptt_recording(app);
+}
+
+/// Open the microphone and start recording PTT audio
+fn start_ptt_audio(app: &tauri::AppHandle) -> Result<(), MicError> {
+    let mut recorder_guard = AUDIO_RECORDER.lock().unwrap();
+    if recorder_guard.is_none() {
+        *recorder_guard = Some(AudioRecorder::new().map_err(|e| MicError::Other {
+            device: "default".to_string(),
+            message: e,
+        })?);
+    }
+    match recorder_guard.as_mut() {
+        Some(recorder) => {
+            let timeout_app = app.clone();
+            recorder.start_recording(move |reason| handle_recording_timeout(timeout_app, reason))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Tell the frontend why the microphone could not be opened (`mic-conflict`)
+fn report_mic_conflict(app: &tauri::AppHandle, error: &MicError) {
+    eprintln!("[PTT] {}", error);
+    emit_event(app, AppEvent::MicConflict(MicConflictPayload {
+        device: error.device().map(str::to_string),
+        message: error.to_string(),
+        retrying: error.is_retryable(),
+        resolved: false,
+        error: Some(error.clone()),
+    }));
+    crate::earcons::play(Cue::Error);
+}
+
+/// Keep trying to open a busy microphone while the PTT key is held
+///
+/// Recording starts as soon as the device frees up; releasing the key gives up.
+fn retry_ptt_audio(app: &tauri::AppHandle) {
+    if MIC_RETRYING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let started = Instant::now();
+        let mut last_error = None;
+        while started.elapsed() < MIC_RETRY_TIMEOUT {
+            std::thread::sleep(MIC_RETRY_INTERVAL);
+            if !PTT_KEY_PRESSED.load(Ordering::SeqCst) || PTT_IGNORE_RELEASE.load(Ordering::SeqCst) {
+                break;
+            }
+            match start_ptt_audio(&app) {
+                Ok(()) => {
+                    emit_event(&app, AppEvent::MicConflict(MicConflictPayload {
+                        device: last_error.as_ref().and_then(MicError::device).map(str::to_string),
+                        error: None,
+                        message: "Microphone is available again".to_string(),
+                        retrying: false,
+                        resolved: true,
+                    }));
+                    begin_ptt_recording(&app);
+                    MIC_RETRYING.store(false, Ordering::SeqCst);
                     return;
                 }
-
-                // Start Rust-side audio recording
-                {
-                    let mut recorder_guard = AUDIO_RECORDER.lock().unwrap();
-                    if recorder_guard.is_none() {
-                        match AudioRecorder::new() {
-                            Ok(r) => *recorder_guard = Some(r),
-                            Err(_e) => {
-                                return;
-                            }
-                        }
-                    }
-                    if let Some(ref mut recorder) = *recorder_guard {
-                        if let Err(_e) = recorder.start_recording() {
-                            return;
-                        }
-                    }
+                Err(e) if e.is_retryable() => last_error = Some(e),
+                Err(e) => {
+                    report_mic_conflict(&app, &e);
+                    break;
                 }
+            }
+        }
+        MIC_RETRYING.store(false, Ordering::SeqCst);
+    });
+}
 
-                // Emit recording state to frontend
-                ui::emit_ptt_state_static(app, "recording");
+/// Recording is running: update the state and notify the daemon
+fn begin_ptt_recording(app: &tauri::AppHandle) {
+    crate::earcons::play(Cue::RecordStart);
 
-                // Notify Python daemon (for UI state only, no recording) - async mode
-                if let Ok(mut daemon_guard) = DAEMON.lock() {
-                    if let Some(ref mut daemon) = *daemon_guard {
-                        let _ = daemon.send_command_no_wait("ptt_press", serde_json::json!({}));
-                    }
-                }
+    if crate::audio_stream::is_enabled() {
+        if let Some(recorder) = AUDIO_RECORDER.lock().unwrap().as_ref() {
+            crate::audio_stream::start(recorder.buffer());
+        }
+    }
+
+    // Emit recording state to frontend
+    APP_STATUS.transition(AppStatus::Recording, "ptt_press");
+    ui::emit_ptt_state_static(app, "recording");
+
+    // Notify Python daemon (for UI state only, no recording) - async mode
+    if let Ok(mut daemon_guard) = DAEMON.lock() {
+        if let Some(ref mut daemon) = *daemon_guard {
+            let _ = daemon.send_command_no_wait("ptt_press", serde_json::json!({}));
+        }
+    }
+}
+
+/// Stop the assistant's reply when PTT is pressed while it is speaking
+///
+/// The interrupt is sent without waiting for the daemon so recording starts
+/// right away; the daemon stops playback and drops the rest of the reply.
+fn barge_in() {
+    if !matches!(APP_STATUS.current(), AppStatus::Playing | AppStatus::TtsProcessing) {
+        return;
+    }
+
+    if let Ok(mut daemon_guard) = DAEMON.lock() {
+        if let Some(ref mut daemon) = *daemon_guard {
+            let _ = daemon.send_command_no_wait("interrupt", serde_json::json!({"priority": 1}));
+        }
+    }
+    // Flush audio chunks still queued by a streaming chat in the main window
+    cancel_active_streams();
+    APP_STATUS.transition(AppStatus::Idle, "barge_in");
+}
+
+/// Handle a PTT trigger release
+fn handle_ptt_release(app: &tauri::AppHandle) {
+    // Reset key state
+    PTT_KEY_PRESSED.store(false, Ordering::SeqCst);
+
+    // Recording already stopped and submitted by a timeout
+    if PTT_TIMED_OUT.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    // Press already handled as latch/unlatch
+    if PTT_IGNORE_RELEASE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    // Short tap with double-tap latch enabled: wait for a possible second tap
+    let held_for = PTT_PRESSED_AT.lock().unwrap().map(|t| t.elapsed());
+    if is_double_tap_latch_enabled() && held_for.is_some_and(|d| d < PTT_DOUBLE_TAP_WINDOW) {
+        PTT_TAP_PENDING.store(true, Ordering::SeqCst);
+        let app = app.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(PTT_DOUBLE_TAP_WINDOW);
+            // No second tap came: behave like a normal release
+            if PTT_TAP_PENDING.swap(false, Ordering::SeqCst) {
+                finish_ptt_recording(&app);
             }
-            ShortcutState::Released => {
-                // Reset key state
-                PTT_KEY_PRESSED.store(false, Ordering::SeqCst);
-
-                // Stop Rust-side audio recording and get audio data
-                let audio_data = {
-                    let mut recorder_guard = AUDIO_RECORDER.lock().unwrap();
-                    if let Some(ref mut recorder) = *recorder_guard {
-                        match recorder.stop_recording() {
-                            Ok(data) => Some(data),
-                            Err(_e) => {
-                                None
-                            }
-                        }
-                    } else {
-                        None
-                    }
-                };
-
-                // Emit processing state
-                ui::emit_ptt_state_static(app, "processing");
-
-                // Send audio file path to Python daemon for ASR (async, don't wait)
-                if let Some(audio) = audio_data {
-                    // Determine auto_chat based on work mode (conversation = auto chat, text-input = no chat)
-                    let work_mode = *crate::daemon::WORK_MODE.lock().unwrap();
-                    let auto_chat = work_mode == WorkMode::Conversation;
-
-                    if let Ok(mut daemon_guard) = DAEMON.lock() {
-                        if let Some(ref mut daemon) = *daemon_guard {
-                            let args = serde_json::json!({
-                                "audio_path": audio.file_path,
-                                "sample_rate": audio.sample_rate,
-                                "duration": audio.duration_secs,
-                                "auto_chat": auto_chat,
-                                "use_tts": true
-                            });
-                            // Use send_command_no_wait to avoid blocking UI
-                            let _ = daemon.send_command_no_wait("ptt_audio", args);
-                        }
-                    }
-                } else {
-                    // No audio data, just notify daemon (async, don't wait)
-                    if let Ok(mut daemon_guard) = DAEMON.lock() {
-                        if let Some(ref mut daemon) = *daemon_guard {
-                            // Use send_command_no_wait to avoid blocking UI
-                            let _ = daemon.send_command_no_wait("ptt_release", serde_json::json!({}));
-                        }
-                    }
+        });
+        return;
+    }
+
+    finish_ptt_recording(app);
+}
+
+/// Start a hands-free recording, or stop and submit the one in progress
+///
+/// Used by triggers without a key to hold (e.g. the `speekium://record` deep link).
+pub fn toggle_ptt_recording(app: &tauri::AppHandle) {
+    if PTT_LATCHED.swap(false, Ordering::SeqCst) {
+        finish_ptt_recording(app);
+        return;
+    }
+    // A held PTT key owns the current recording
+    if PTT_KEY_PRESSED.load(Ordering::SeqCst) {
+        return;
+    }
+
+    handle_ptt_press(app);
+    PTT_KEY_PRESSED.store(false, Ordering::SeqCst);
+
+    let recording = AUDIO_RECORDER.lock().unwrap().as_ref().is_some_and(|r| r.is_recording());
+    if recording {
+        PTT_LATCHED.store(true, Ordering::SeqCst);
+        ui::emit_ptt_state_static(app, "latched");
+    }
+}
+
+/// Check the `ptt_double_tap_latch` config toggle
+fn is_double_tap_latch_enabled() -> bool {
+    config::read_config_value(PTT_DOUBLE_TAP_LATCH_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Stop PTT recording and send the audio to the daemon for ASR
+fn finish_ptt_recording(app: &tauri::AppHandle) {
+    // Stop Rust-side audio recording and get audio data
+    let audio_data = {
+        let mut recorder_guard = AUDIO_RECORDER.lock().unwrap();
+        if let Some(ref mut recorder) = *recorder_guard {
+            match recorder.stop_recording() {
+                Ok(data) => Some(data),
+                Err(_e) => {
+                    None
                 }
             }
+        } else {
+            None
         }
-    }).map_err(|e| format!("Failed to register PTT shortcut: {}", e))?;
+    };
+    let stream = crate::audio_stream::finish();
 
-    Ok(())
-}
+    crate::earcons::play(Cue::RecordStop);
 
-/// Write recording mode directly to config file
-/// This bypasses the daemon and allows VAD loop to detect mode changes via config polling
-pub fn write_recording_mode_to_config(mode: &str) -> Result<(), Box<dyn std::error::Error>> {
-    use crate::daemon::APP_HANDLE;
-    use std::path::PathBuf;
+    // Emit processing state
+    APP_STATUS.transition(AppStatus::AsrProcessing, "ptt_release");
+    ui::emit_ptt_state_static(app, "processing");
+    crate::metrics::mark_recording_stopped();
 
-    // Get config directory from app_data_dir (same as Python daemon)
-    let config_dir = if let Some(app_handle) = APP_HANDLE.get() {
-        app_handle.path().app_data_dir()
-            .map_err(|e| format!("Failed to get app data dir: {}", e))?
+    // Send audio file path to Python daemon for ASR (async, don't wait)
+    if let Some(audio) = audio_data {
+        // Determine auto_chat based on work mode (conversation = auto chat, text-input = no chat)
+        let work_mode = *crate::daemon::WORK_MODE.lock().unwrap();
+        let auto_chat = work_mode == WorkMode::Conversation;
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            submit_ptt_audio(&app, audio, stream, auto_chat).await;
+        });
     } else {
-        // Fallback if APP_HANDLE not set (shouldn't happen in normal operation)
-        #[cfg(target_os = "macos")]
-        {
-            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-            PathBuf::from(home).join("Library/Application Support/com.speekium.app")
-        }
-        #[cfg(target_os = "windows")]
-        {
-            let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
-            PathBuf::from(appdata).join("com.speekium.app")
-        }
-        #[cfg(target_os = "linux")]
-        {
-            let xdg = std::env::var("XDG_CONFIG_HOME")
-                .unwrap_or_else(|_| format!("{}/.config", std::env::var("HOME").unwrap_or_else(|_| ".".to_string())));
-            PathBuf::from(xdg).join("com.speekium.app")
-        }
-        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-        {
-            PathBuf::from(".")
+        if let Some(path) = stream.and_then(|tail| tail.audio_path) {
+            let _ = std::fs::remove_file(path);
+        }
+        // No audio data, just notify daemon (async, don't wait)
+        if let Ok(mut daemon_guard) = DAEMON.lock() {
+            if let Some(ref mut daemon) = *daemon_guard {
+                // Use send_command_no_wait to avoid blocking UI
+                let _ = daemon.send_command_no_wait("ptt_release", serde_json::json!({}));
+            }
+        }
+    }
+}
+
+/// Transcribe PTT audio, post-process the transcript and submit it
+///
+/// The pipeline runs ASR here; the processed transcript is then shown and
+/// answered (conversation mode) stage by stage, see pipeline.rs. A streamed
+/// recording (`stream`) only sends the audio after the last chunk.
+async fn submit_ptt_audio(
+    app: &tauri::AppHandle,
+    audio: crate::audio::AudioData,
+    stream: Option<crate::audio_stream::StreamTail>,
+    auto_chat: bool,
+) {
+    let language = crate::asr_language::request_language();
+    // Copy the recording before the daemon deletes it
+    let archived = crate::audio_archive::archive_recording(app, &audio.file_path);
+    // Journal it too, so a crash before ASR finishes doesn't lose the utterance
+    let journaled = crate::recording_journal::register(app, &audio);
+
+    let result = crate::pipeline::transcribe(&audio, stream, &language).await;
+    if let Some(id) = journaled {
+        crate::recording_journal::finish(app, id, result.is_ok());
+    }
+    let transcript = match result {
+        Ok(transcript) => transcript,
+        Err(e) => {
+            eprintln!("[PTT] ASR failed: {}", e);
+            crate::earcons::play(Cue::Error);
+            crate::pipeline::Transcript::default()
         }
     };
+    // Nothing to submit (no speech, too short or failed)
+    if transcript.text.trim().is_empty() {
+        if let Some(path) = archived {
+            crate::audio_archive::discard_recording(&path);
+        }
+        APP_STATUS.transition(AppStatus::Idle, "ptt_no_speech");
+        ui::emit_ptt_state_static(app, "idle");
+        return;
+    }
 
-    let config_path = config_dir.join("config.json");
+    let text = crate::textproc::process_transcript(app, &transcript.text);
+    if let Some(language) = transcript.language {
+        crate::asr_language::set_pending_language(&text, &language);
+    }
+    let alternatives = crate::asr_alternatives::process_alternatives(app, &text, &transcript.alternatives);
+    crate::asr_alternatives::set_pending_alternatives(&text, transcript.confidence, alternatives);
+    crate::word_timings::set_pending_timings(&text, transcript.words);
+    let segments = crate::diarization::process_segments(app, transcript.segments);
+    crate::diarization::set_pending_segments(&text, segments);
+    if let Some(path) = archived {
+        crate::audio_archive::set_pending_audio(&text, path);
+        crate::audio_archive::spawn_retention_cleanup(app);
+    }
 
-    // Ensure config directory exists
-    std::fs::create_dir_all(&config_dir)?;
+    crate::pipeline::respond(app, text, auto_chat).await;
+}
 
-    // Read existing config or create default if not exists
-    let mut config = if config_path.exists() {
-        let config_content = std::fs::read_to_string(&config_path)?;
-        serde_json::from_str(&config_content)?
-    } else {
-        // Create minimal default config
-        serde_json::json!({})
+/// Discard the PTT recording in progress without submitting it (overlay interrupt)
+///
+/// Returns false if no PTT recording was running.
+pub fn cancel_ptt_recording(app: &tauri::AppHandle) -> bool {
+    let audio = {
+        let mut recorder_guard = AUDIO_RECORDER.lock().unwrap();
+        match recorder_guard.as_mut() {
+            Some(recorder) if recorder.is_recording() => recorder.stop_recording().ok(),
+            _ => return false,
+        }
     };
 
-    // Update recording_mode
-    config["recording_mode"] = serde_json::json!(mode);
+    // The key may still be held: its release must not submit anything
+    if PTT_KEY_PRESSED.load(Ordering::SeqCst) {
+        PTT_IGNORE_RELEASE.store(true, Ordering::SeqCst);
+    }
+    PTT_LATCHED.store(false, Ordering::SeqCst);
+    PTT_TAP_PENDING.store(false, Ordering::SeqCst);
+    crate::audio_stream::cancel();
+
+    if let Some(audio) = audio {
+        let _ = std::fs::remove_file(&audio.file_path);
+    }
+    APP_STATUS.transition(AppStatus::Idle, "ptt_cancel");
+    ui::emit_ptt_state_static(app, "idle");
+    true
+}
 
-    // Write back
-    std::fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
+/// Handle a PTT recording that was stopped by the silence timeout or length cap
+fn handle_recording_timeout(app: tauri::AppHandle, reason: RecordingTimeout) {
+    // A latched recording has no key held, so there is no release to ignore
+    if !PTT_LATCHED.swap(false, Ordering::SeqCst) {
+        PTT_TIMED_OUT.store(true, Ordering::SeqCst);
+    }
+    PTT_TAP_PENDING.store(false, Ordering::SeqCst);
+    emit_event(&app, AppEvent::RecordingTimeout(RecordingTimeoutPayload {
+        reason: reason.as_str().to_string(),
+    }));
+
+    // Finish on a separate thread: the callback runs on the recording thread,
+    // which stop_recording joins
+    std::thread::spawn(move || finish_ptt_recording(&app));
+}
 
+/// Write recording mode directly to config file
+/// This bypasses the daemon and allows VAD loop to detect mode changes via config polling
+pub fn write_recording_mode_to_config(mode: &str) -> Result<(), Box<dyn std::error::Error>> {
+    config::write_config_value("recording_mode", serde_json::json!(mode))?;
     Ok(())
 }
 
@@ -262,86 +597,343 @@ pub fn start_recording_mode_dispatcher<R: Runtime>(app: &tauri::AppHandle<R>) {
             // Emit the event to the frontend
             // This is called from a dedicated thread, but emit() is safe here
             // as it handles cross-thread communication internally
-            if let Err(_e) = app_handle.emit("recording-mode-changed", &mode_str) {
-            }
+            emit_event(&app_handle, AppEvent::RecordingModeChanged(mode_str));
         }
     });
 }
 
-pub fn register_shortcuts<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
-    // Register show/hide window shortcut: Command+Shift+Space
-    let toggle_shortcut: Shortcut = "CommandOrControl+Shift+Space".parse().unwrap();
+// ============================================================================
+// Action Shortcut Registry
+// ============================================================================
 
-    let app_handle = app.clone();
-    app.global_shortcut().on_shortcut(toggle_shortcut, move |_app, _shortcut, _event| {
-        if let Some(window) = app_handle.get_webview_window("main") {
-            if window.is_visible().unwrap_or(false) {
-                let _ = window.hide();
-            } else {
-                let _ = window.show();
-                let _ = window.set_focus();
+/// Config key holding the hotkey config of each action shortcut, keyed by action name
+pub const SHORTCUTS_CONFIG_KEY: &str = "shortcuts";
+
+/// Owner name reported when a shortcut conflicts with the PTT hotkey
+pub const PTT_SHORTCUT_OWNER: &str = "push_to_talk";
+
+/// Global shortcut actions other than push-to-talk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    /// Show/hide the main window
+    ToggleWindow,
+    /// Toggle work mode (conversation <-> text-input)
+    SwitchWorkMode,
+    /// Toggle recording mode (push-to-talk <-> continuous)
+    SwitchRecordingMode,
+    /// Type the last transcription into the focused app
+    PasteTranscript,
+    /// Open the picker for re-pasting older dictations
+    TranscriptionPicker,
+    /// Show/hide the history window
+    ToggleHistory,
+    /// Run the default prompt template on the selected text
+    AskSelection,
+    /// Start/stop long-form dictation
+    ToggleDictation,
+    /// Remove the text typed last
+    UndoTyping,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 9] = [
+        ShortcutAction::ToggleWindow,
+        ShortcutAction::SwitchWorkMode,
+        ShortcutAction::SwitchRecordingMode,
+        ShortcutAction::PasteTranscript,
+        ShortcutAction::TranscriptionPicker,
+        ShortcutAction::ToggleHistory,
+        ShortcutAction::AskSelection,
+        ShortcutAction::ToggleDictation,
+        ShortcutAction::UndoTyping,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleWindow => "toggle_window",
+            ShortcutAction::SwitchWorkMode => "toggle_work_mode",
+            ShortcutAction::SwitchRecordingMode => "toggle_recording_mode",
+            ShortcutAction::PasteTranscript => "paste_transcript",
+            ShortcutAction::TranscriptionPicker => "transcription_picker",
+            ShortcutAction::ToggleHistory => "toggle_history",
+            ShortcutAction::AskSelection => "ask_selection",
+            ShortcutAction::ToggleDictation => "toggle_dictation",
+            ShortcutAction::UndoTyping => "undo_last_typing",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        ShortcutAction::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+
+    /// Shortcut used when config has none (or an invalid one)
+    pub fn default_shortcut(&self) -> &'static str {
+        match self {
+            ShortcutAction::ToggleWindow => "CommandOrControl+Shift+Space",
+            ShortcutAction::SwitchWorkMode => "Alt+1",
+            ShortcutAction::SwitchRecordingMode => "Alt+2",
+            ShortcutAction::PasteTranscript => "Alt+4",
+            ShortcutAction::TranscriptionPicker => "Alt+5",
+            ShortcutAction::ToggleHistory => "Alt+6",
+            ShortcutAction::AskSelection => "Alt+7",
+            ShortcutAction::ToggleDictation => "Alt+8",
+            ShortcutAction::UndoTyping => "Alt+9",
+        }
+    }
+
+    /// Perform the action (called on key press)
+    pub fn run<R: Runtime>(&self, app: &tauri::AppHandle<R>) {
+        match self {
+            ShortcutAction::ToggleWindow => {
+                if let Some(window) = app.get_webview_window("main") {
+                    if window.is_visible().unwrap_or(false) {
+                        let _ = window.hide();
+                    } else {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
+                }
+            }
+            ShortcutAction::SwitchWorkMode => {
+                // Acquire lock, toggle mode, then release immediately
+                {
+                    let mut work_mode = crate::daemon::WORK_MODE.lock().unwrap();
+                    *work_mode = match *work_mode {
+                        WorkMode::Conversation => WorkMode::TextInput,
+                        WorkMode::TextInput => WorkMode::Conversation,
+                    };
+                } // Lock released here
+
+                // Don't save config here to avoid deadlock in shortcut callback thread
+                // Frontend polling will detect the change and trigger save
+            }
+            ShortcutAction::SwitchRecordingMode => {
+                // Acquire lock, toggle mode, extract name, then release immediately
+                let mode_name = {
+                    let mut recording_mode = crate::daemon::RECORDING_MODE.lock().unwrap();
+                    *recording_mode = match *recording_mode {
+                        RecordingMode::PushToTalk => RecordingMode::Continuous,
+                        RecordingMode::Continuous | RecordingMode::Meeting => RecordingMode::PushToTalk,
+                    };
+                    recording_mode.as_str()
+                }; // Lock released here
+                // Toggling always leaves meeting mode
+                let _ = crate::meeting::apply_recording_mode(RecordingMode::PushToTalk);
+
+                // Write directly to config file to notify VAD loop (bypasses daemon lock)
+                if let Err(_e) = write_recording_mode_to_config(mode_name) {
+                }
+
+                // Send to channel for cross-thread event dispatch (non-blocking, safe)
+                // The dedicated dispatcher thread will emit the event to the frontend
+                if let Some(tx) = RECORDING_MODE_CHANNEL.lock().unwrap().as_ref() {
+                    let _ = tx.send(mode_name.to_string()); // Non-blocking send
+                }
+            }
+            ShortcutAction::PasteTranscript => platform::paste_last_transcription(),
+            ShortcutAction::UndoTyping => platform::undo_last_typing_from_shortcut(),
+            ShortcutAction::TranscriptionPicker => crate::transcriptions::toggle_picker(app),
+            ShortcutAction::ToggleHistory => crate::ui::toggle_history_window(app),
+            ShortcutAction::AskSelection => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = crate::selection::ask(app, None).await {
+                        eprintln!("Ask selection failed: {}", e);
+                    }
+                });
+            }
+            ShortcutAction::ToggleDictation => {
+                let app = app.clone();
+                std::thread::spawn(move || {
+                    if crate::dictation::is_active() {
+                        crate::dictation::stop();
+                    } else if let Err(e) = crate::dictation::start(&app) {
+                        eprintln!("Dictation failed to start: {}", e);
+                    }
+                });
             }
         }
-    }).map_err(|e| tauri::Error::Anyhow(anyhow::anyhow!("Failed to register toggle shortcut: {}", e)))?;
 
-    // Register Alt+1: Toggle work mode (conversation <-> text-input)
-    let work_mode_shortcut: Shortcut = "Alt+1".parse().unwrap();
-    app.global_shortcut().on_shortcut(work_mode_shortcut, move |_app, _shortcut, event| {
-        // Only trigger on press, not release (to avoid double toggle)
-        if event.state() != ShortcutState::Pressed {
-            return;
+        // Keep the tray mode toggles in sync
+        if matches!(self, ShortcutAction::SwitchWorkMode | ShortcutAction::SwitchRecordingMode) {
+            let _ = crate::ui::update_tray_menu(app);
         }
+    }
+}
+
+/// Shortcut string for an action from config.json, or its default
+fn shortcut_from_config(action: ShortcutAction) -> String {
+    config::read_config_value(SHORTCUTS_CONFIG_KEY)
+        .and_then(|shortcuts| shortcuts.get(action.as_str()).cloned())
+        .and_then(|hotkey_config| hotkey_config_to_shortcut_string(&hotkey_config))
+        .unwrap_or_else(|| action.default_shortcut().to_string())
+}
 
-        // Acquire lock, toggle mode, extract name, then release immediately
-        let _mode_name = {
-            let mut work_mode = crate::daemon::WORK_MODE.lock().unwrap();
-            *work_mode = match *work_mode {
-                WorkMode::Conversation => WorkMode::TextInput,
-                WorkMode::TextInput => WorkMode::Conversation,
-            };
-            match *work_mode {
-                WorkMode::Conversation => "conversation",
-                WorkMode::TextInput => "text-input",
+pub fn parse_shortcut(shortcut_str: &str) -> Result<Shortcut, String> {
+    shortcut_str.parse()
+        .map_err(|e| format!("Failed to parse shortcut '{}': {:?}", shortcut_str, e))
+}
+
+/// Find which registered shortcut (action name, PTT or prompt template) already uses `shortcut_str`
+///
+/// `exclude` is the owner being updated, so it does not conflict with itself.
+pub fn find_shortcut_conflict(shortcut_str: &str, exclude: Option<&str>) -> Result<Option<String>, String> {
+    let shortcut = parse_shortcut(shortcut_str)?;
+    let same = |other: &str| other.parse::<Shortcut>().is_ok_and(|o| o == shortcut);
+
+    if exclude != Some(PTT_SHORTCUT_OWNER) {
+        if let Some(ref ptt) = *CURRENT_PTT_SHORTCUT.lock().unwrap() {
+            if same(ptt) {
+                return Ok(Some(PTT_SHORTCUT_OWNER.to_string()));
             }
-        }; // Lock released here
+        }
+    }
 
-        // Don't save config here to avoid deadlock in shortcut callback thread
-        // Frontend polling will detect the change and trigger save
-        // Note: Config will be saved by frontend when it detects the mode change
-    }).map_err(|e| tauri::Error::Anyhow(anyhow::anyhow!("Failed to register work mode shortcut: {}", e)))?;
+    let registered = ACTION_SHORTCUTS.lock().unwrap();
+    let action = registered
+        .iter()
+        .find(|(action, registered)| exclude != Some(action.as_str()) && same(registered))
+        .map(|(action, _)| action.clone());
+    if action.is_some() {
+        return Ok(action);
+    }
+
+    // Prompt template hotkeys ("template:<id>")
+    Ok(crate::prompt_templates::registered_hotkeys()
+        .into_iter()
+        .find(|(owner, registered)| exclude != Some(owner.as_str()) && same(registered))
+        .map(|(owner, _)| owner))
+}
+
+/// Register the global shortcut for an action
+fn register_action_shortcut<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    action: ShortcutAction,
+    shortcut_str: &str,
+) -> Result<(), String> {
+    let shortcut = parse_shortcut(shortcut_str)?;
 
-    // Register Alt+2: Toggle recording mode (push-to-talk <-> continuous)
-    let recording_mode_shortcut: Shortcut = "Alt+2".parse().unwrap();
-    app.global_shortcut().on_shortcut(recording_mode_shortcut, move |_app, _shortcut, event| {
+    app.global_shortcut().on_shortcut(shortcut, move |app, _shortcut, event| {
         // Only trigger on press, not release (to avoid double toggle)
-        if event.state() != ShortcutState::Pressed {
-            return;
+        if event.state() == ShortcutState::Pressed {
+            action.run(app);
         }
+    }).map_err(|e| format!("Failed to register {} shortcut: {}", action.as_str(), e))?;
 
-        // Acquire lock, toggle mode, extract name, then release immediately
-        let mode_name = {
-            let mut recording_mode = crate::daemon::RECORDING_MODE.lock().unwrap();
-            *recording_mode = match *recording_mode {
-                RecordingMode::PushToTalk => RecordingMode::Continuous,
-                RecordingMode::Continuous => RecordingMode::PushToTalk,
-            };
-            match *recording_mode {
-                RecordingMode::PushToTalk => "push-to-talk",
-                RecordingMode::Continuous => "continuous",
-            }
-        }; // Lock released here
+    ACTION_SHORTCUTS.lock().unwrap()
+        .insert(action.as_str().to_string(), shortcut_str.to_string());
+    Ok(())
+}
 
-        // Write directly to config file to notify VAD loop (bypasses daemon lock)
-        if let Err(_e) = write_recording_mode_to_config(mode_name) {
+/// Remap an action shortcut and save it to config.json
+///
+/// Fails without changing anything if the shortcut is invalid, used by another
+/// action or PTT, or cannot be registered with the OS.
+pub fn update_action_shortcut(
+    app: &tauri::AppHandle,
+    action: ShortcutAction,
+    hotkey_config: &serde_json::Value,
+) -> Result<String, String> {
+    let shortcut_str = hotkey_config_to_shortcut_string(hotkey_config)
+        .ok_or_else(|| "Invalid hotkey config".to_string())?;
+
+    if let Some(owner) = find_shortcut_conflict(&shortcut_str, Some(action.as_str()))? {
+        return Err(format!("Shortcut {} is already used by {}", shortcut_str, owner));
+    }
+
+    let old = ACTION_SHORTCUTS.lock().unwrap().get(action.as_str()).cloned();
+    if let Some(ref old_str) = old {
+        if let Ok(old_shortcut) = old_str.parse::<Shortcut>() {
+            let _ = app.global_shortcut().unregister(old_shortcut);
         }
+    }
 
-        // Send to channel for cross-thread event dispatch (non-blocking, safe)
-        // The dedicated dispatcher thread will emit the event to the frontend
-        if let Some(tx) = RECORDING_MODE_CHANNEL.lock().unwrap().as_ref() {
-            let _ = tx.send(mode_name.to_string()); // Non-blocking send
-        } else {
+    if let Err(e) = register_action_shortcut(app, action, &shortcut_str) {
+        // Restore the previous binding
+        if let Some(ref old_str) = old {
+            let _ = register_action_shortcut(app, action, old_str);
+        }
+        return Err(e);
+    }
+
+    let mut shortcuts = config::read_config_value(SHORTCUTS_CONFIG_KEY)
+        .filter(|v| v.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    shortcuts[action.as_str()] = hotkey_config.clone();
+    config::write_config_value(SHORTCUTS_CONFIG_KEY, shortcuts)?;
+
+    Ok(shortcut_str)
+}
+
+/// Check whether a shortcut can be used, without keeping it registered
+///
+/// `owner` (action name or PTT) is the binding being edited, so its current
+/// shortcut is not reported as a conflict.
+pub fn validate_shortcut<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    shortcut_str: &str,
+    owner: Option<&str>,
+) -> ShortcutValidationResult {
+    let invalid = |reason: &str, conflict_with: Option<String>, error: String| ShortcutValidationResult {
+        valid: false,
+        shortcut: shortcut_str.to_string(),
+        reason: Some(reason.to_string()),
+        conflict_with,
+        error: Some(error),
+    };
+
+    let shortcut = match parse_shortcut(shortcut_str) {
+        Ok(s) => s,
+        Err(e) => return invalid("invalid", None, e),
+    };
+
+    match find_shortcut_conflict(shortcut_str, owner) {
+        Ok(Some(other)) => {
+            let error = format!("Shortcut {} is already used by {}", shortcut_str, other);
+            return invalid("in_use", Some(other), error);
         }
-    }).map_err(|e| tauri::Error::Anyhow(anyhow::anyhow!("Failed to register recording mode shortcut: {}", e)))?;
+        Ok(None) => {}
+        Err(e) => return invalid("invalid", None, e),
+    }
+
+    // Dry-run registration: the OS rejects hotkeys grabbed by other apps or reserved by the system.
+    // Shortcuts already registered by us belong to `owner` and are known to work.
+    if !app.global_shortcut().is_registered(shortcut) {
+        if let Err(e) = app.global_shortcut().register(shortcut) {
+            return invalid("os_rejected", None, format!("Shortcut {} is not available: {}", shortcut_str, e));
+        }
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+
+    ShortcutValidationResult {
+        valid: true,
+        shortcut: shortcut_str.to_string(),
+        reason: None,
+        conflict_with: None,
+        error: None,
+    }
+}
+
+pub fn register_shortcuts<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
+    for action in ShortcutAction::ALL {
+        let shortcut_str = shortcut_from_config(action);
+
+        let result = match find_shortcut_conflict(&shortcut_str, Some(action.as_str())) {
+            Ok(Some(owner)) => Err(format!("Shortcut {} is already used by {}", shortcut_str, owner)),
+            Ok(None) => register_action_shortcut(app, action, &shortcut_str),
+            Err(e) => Err(e),
+        };
+
+        // Fall back to the default if the configured shortcut is unusable
+        if let Err(e) = result {
+            if shortcut_str == action.default_shortcut() {
+                return Err(tauri::Error::Anyhow(anyhow::anyhow!(e)));
+            }
+            eprintln!("⚠️ {}, using default {}", e, action.default_shortcut());
+            register_action_shortcut(app, action, action.default_shortcut())
+                .map_err(|e| tauri::Error::Anyhow(anyhow::anyhow!(e)))?;
+        }
+    }
 
     // PTT shortcut will be registered after daemon starts and config is loaded
     // See register_ptt_from_config() which is called after daemon initialization
@@ -349,54 +941,65 @@ pub fn register_shortcuts<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Resul
     Ok(())
 }
 
+/// Unregister every global shortcut: actions, PTT and prompt template hotkeys (profile switch)
+pub fn unregister_all_shortcuts(app: &tauri::AppHandle) {
+    if let Err(e) = app.global_shortcut().unregister_all() {
+        eprintln!("⚠️ Failed to unregister shortcuts: {}", e);
+    }
+    ACTION_SHORTCUTS.lock().unwrap().clear();
+    *CURRENT_PTT_SHORTCUT.lock().unwrap() = None;
+    crate::prompt_templates::forget_hotkeys();
+}
+
+/// Register every global shortcut from config: actions, prompt template hotkeys and PTT
+pub fn register_all_shortcuts(app: &tauri::AppHandle) {
+    if let Err(e) = register_shortcuts(app) {
+        eprintln!("⚠️ Failed to register shortcuts: {}", e);
+    }
+    crate::prompt_templates::register_hotkeys(app);
+    if crate::daemon::is_daemon_ready() {
+        register_ptt_from_config(app);
+    }
+}
+
 /// Register PTT shortcut from daemon config
 pub fn register_ptt_from_config(app_handle: &tauri::AppHandle) {
     // Check current recording mode - only register PTT shortcut in push-to-talk mode
     // IMPORTANT: Release the lock immediately after checking to avoid deadlock
     let should_register = {
         let recording_mode = crate::daemon::RECORDING_MODE.lock().unwrap();
-        *recording_mode != RecordingMode::Continuous
+        *recording_mode == RecordingMode::PushToTalk
     };
 
     if !should_register {
         return;
     }
 
-    // Check if recording is in progress - if so, skip to avoid deadlock
-    let is_recording = {
-        let status = crate::daemon::APP_STATUS.lock().unwrap();
-        matches!(*status, AppStatus::Recording | AppStatus::Listening)
-    };
-
-    if is_recording {
-        // Use default shortcut without calling daemon
-        let _ = register_ptt_shortcut(app_handle, "Alt+3");
-        return;
+    // A mouse button / Fn key trigger replaces the keyboard shortcut
+    let trigger = ptt_trigger_from_config();
+    if trigger != PttTrigger::Shortcut {
+        match ensure_input_listener(app_handle) {
+            Ok(()) => {
+                *PTT_TRIGGER.lock().unwrap() = trigger;
+                unregister_ptt_shortcut(app_handle);
+                return;
+            }
+            Err(e) => {
+                eprintln!("⚠️ {}, falling back to keyboard PTT shortcut", e);
+            }
+        }
     }
-
-    // Try to get daemon lock with timeout - if can't get it, skip daemon call
-    // Use try_lock to avoid blocking
-    if let Ok(mut daemon_guard) = crate::daemon::DAEMON.try_lock() {
-        if let Some(ref mut daemon) = *daemon_guard {
-            match daemon.send_command("config", serde_json::json!({})) {
-                Ok(config_result) => {
-                    if let Some(config) = config_result.get("config") {
-                        if let Some(hotkey_config) = config.get("push_to_talk_hotkey") {
-                            if let Some(shortcut_str) = hotkey_config_to_shortcut_string(hotkey_config) {
-                                if let Err(_e) = register_ptt_shortcut(app_handle, &shortcut_str) {
-                                    // Fallback to default
-                                    let _ = register_ptt_shortcut(app_handle, "Alt+3");
-                                }
-                                return;
-                            }
-                        }
-                    }
-                }
-                Err(_e) => {
-                }
+    *PTT_TRIGGER.lock().unwrap() = PttTrigger::Shortcut;
+
+    // Read the hotkey straight from config.json (no daemon round-trip)
+    if let Some(hotkey_config) = config::read_config_value("push_to_talk_hotkey") {
+        if let Some(shortcut_str) = hotkey_config_to_shortcut_string(&hotkey_config) {
+            if let Err(_e) = register_ptt_shortcut(app_handle, &shortcut_str) {
+                // Fallback to default
+                let _ = register_ptt_shortcut(app_handle, "Alt+3");
             }
+            return;
         }
-    } else {
     }
 
     // Fallback to default shortcut
@@ -0,0 +1,232 @@
+// ============================================================================
+// Speech Text Module - Reply Post-Processing Before TTS
+// ============================================================================
+//
+// LLM replies are written for reading: code blocks, markdown markers, lists
+// and tables sound terrible when spoken. Between the LLM and TTS stages of
+// the pipeline every sentence goes through a `SpeechFilter`, configured with
+// `tts_postprocess`:
+//
+//   strip_code       drop fenced code blocks
+//   strip_markdown   remove headings, emphasis, inline code and link markup
+//   lists_to_prose   speak list items and table rows as sentences
+//   max_sentences    stop speaking after this many sentences (0 = no limit)
+//
+// Only the spoken text changes; the full reply is still shown. The filter
+// keeps state across sentences (open code block, spoken count), so one filter
+// is used per reply.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::config::{self, TtsPostprocessConfig};
+
+const CONFIG_KEY: &str = "tts_postprocess";
+
+static HEADING: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#{1,6}\s+").unwrap());
+static QUOTE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(>\s?)+").unwrap());
+static RULE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([-*_]\s*){3,}$").unwrap());
+static LIST_ITEM: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([-*+]|\d+[.)])\s+").unwrap());
+static TABLE_SEPARATOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\|?[\s:|-]+\|?$").unwrap());
+static IMAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap());
+static LINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\([^)]*\)").unwrap());
+static INLINE_CODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`([^`]*)`").unwrap());
+static STRONG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\*\*|__|~~)(.+?)(\*\*|__|~~)").unwrap());
+static EMPHASIS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\*([^*\s][^*]*?)\*").unwrap());
+
+/// Turns the sentences of one reply into text for speech
+pub struct SpeechFilter {
+    config: TtsPostprocessConfig,
+    /// Inside a fenced code block
+    in_code: bool,
+    /// Sentences passed on to TTS so far
+    spoken: u32,
+}
+
+impl SpeechFilter {
+    pub fn new(config: TtsPostprocessConfig) -> Self {
+        SpeechFilter {
+            config,
+            in_code: false,
+            spoken: 0,
+        }
+    }
+
+    /// Filter with the configured `tts_postprocess` settings
+    pub fn from_config() -> Self {
+        SpeechFilter::new(
+            config::read_config_value(CONFIG_KEY)
+                .and_then(|v| serde_json::from_value(v).ok())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Text to speak for a sentence of the reply (None = nothing to speak)
+    pub fn process(&mut self, sentence: &str) -> Option<String> {
+        if !self.config.enabled {
+            return Some(sentence.to_string());
+        }
+        if self.config.max_sentences > 0 && self.spoken >= self.config.max_sentences {
+            return None;
+        }
+
+        let mut spoken = Vec::new();
+        for line in sentence.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                self.in_code = !self.in_code;
+                continue;
+            }
+            if self.in_code && self.config.strip_code {
+                continue;
+            }
+            if let Some(line) = self.convert_line(trimmed) {
+                spoken.push(line);
+            }
+        }
+        let text = spoken.join(" ");
+        if text.trim().is_empty() {
+            return None;
+        }
+        self.spoken += 1;
+        Some(text)
+    }
+
+    /// One line of markdown as speech (None = nothing to speak)
+    fn convert_line(&self, line: &str) -> Option<String> {
+        if line.is_empty() || (self.config.strip_markdown && RULE.is_match(line)) {
+            return None;
+        }
+        let mut line = line.to_string();
+
+        if self.config.lists_to_prose {
+            if line.starts_with('|') {
+                if TABLE_SEPARATOR.is_match(&line) {
+                    return None;
+                }
+                let cells: Vec<&str> = line
+                    .trim_matches('|')
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty())
+                    .collect();
+                line = end_sentence(&cells.join(", "));
+            } else if LIST_ITEM.is_match(&line) {
+                line = end_sentence(&LIST_ITEM.replace(&line, ""));
+            }
+        }
+
+        if self.config.strip_markdown {
+            line = HEADING.replace(&line, "").into_owned();
+            line = QUOTE.replace(&line, "").into_owned();
+            line = IMAGE.replace_all(&line, "$1").into_owned();
+            line = LINK.replace_all(&line, "$1").into_owned();
+            line = INLINE_CODE.replace_all(&line, "$1").into_owned();
+            line = STRONG.replace_all(&line, "$2").into_owned();
+            line = EMPHASIS.replace_all(&line, "$1").into_owned();
+        }
+
+        let line = line.trim();
+        (!line.is_empty()).then(|| line.to_string())
+    }
+}
+
+/// Add a full stop so TTS pauses after a list item ("。" after CJK text)
+fn end_sentence(text: &str) -> String {
+    let text = text.trim();
+    match text.chars().last() {
+        None => String::new(),
+        Some(c) if c.is_ascii_punctuation() || "。！？；：，".contains(c) => text.to_string(),
+        Some(c) if is_cjk(c) => format!("{}。", text),
+        Some(_) => format!("{}.", text),
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{3040}'..='\u{30FF}' | '\u{3400}'..='\u{9FFF}' | '\u{AC00}'..='\u{D7AF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter() -> SpeechFilter {
+        SpeechFilter::new(TtsPostprocessConfig::default())
+    }
+
+    fn speak_all(filter: &mut SpeechFilter, sentences: &[&str]) -> Vec<String> {
+        sentences.iter().filter_map(|s| filter.process(s)).collect()
+    }
+
+    #[test]
+    fn test_strips_code_blocks_across_sentences() {
+        let mut filter = filter();
+        let spoken = speak_all(
+            &mut filter,
+            &["Run this:\n", "```python\n", "print('hi')\n", "```\n", "Then you are done."],
+        );
+        assert_eq!(spoken, vec!["Run this:", "Then you are done."]);
+    }
+
+    #[test]
+    fn test_keeps_code_when_disabled() {
+        let mut filter = SpeechFilter::new(TtsPostprocessConfig {
+            strip_code: false,
+            ..TtsPostprocessConfig::default()
+        });
+        let spoken = speak_all(&mut filter, &["```\nls -la\n```"]);
+        assert_eq!(spoken, vec!["ls -la"]);
+    }
+
+    #[test]
+    fn test_strips_markdown() {
+        let mut filter = filter();
+        assert_eq!(filter.process("## Summary").as_deref(), Some("Summary"));
+        assert_eq!(
+            filter.process("Use **bold**, *italic* and `cargo build`.").as_deref(),
+            Some("Use bold, italic and cargo build.")
+        );
+        assert_eq!(
+            filter.process("See [the docs](https://example.com) for more.").as_deref(),
+            Some("See the docs for more.")
+        );
+        assert_eq!(filter.process("---"), None);
+        // snake_case identifiers are left alone
+        assert_eq!(filter.process("Call max_history_turns.").as_deref(), Some("Call max_history_turns."));
+    }
+
+    #[test]
+    fn test_lists_to_prose() {
+        let mut filter = filter();
+        let spoken = speak_all(&mut filter, &["- Apples\n", "2. Pears\n", "* 苹果\n", "- Done!"]);
+        assert_eq!(spoken, vec!["Apples.", "Pears.", "苹果。", "Done!"]);
+    }
+
+    #[test]
+    fn test_tables_to_prose() {
+        let mut filter = filter();
+        let spoken = speak_all(&mut filter, &["| Name | Age |\n|------|-----|\n| Ann | 31 |"]);
+        assert_eq!(spoken, vec!["Name, Age. Ann, 31."]);
+    }
+
+    #[test]
+    fn test_limits_spoken_sentences() {
+        let mut filter = SpeechFilter::new(TtsPostprocessConfig {
+            max_sentences: 2,
+            ..TtsPostprocessConfig::default()
+        });
+        // Skipped code does not count towards the limit
+        let spoken = speak_all(&mut filter, &["One.", "```\ncode\n```", "Two.", "Three."]);
+        assert_eq!(spoken, vec!["One.", "Two."]);
+    }
+
+    #[test]
+    fn test_disabled_passes_text_through() {
+        let mut filter = SpeechFilter::new(TtsPostprocessConfig {
+            enabled: false,
+            ..TtsPostprocessConfig::default()
+        });
+        assert_eq!(filter.process("**as is**").as_deref(), Some("**as is**"));
+    }
+}
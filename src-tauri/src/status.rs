@@ -0,0 +1,108 @@
+// ============================================================================
+// Status Module - Pipeline Status State Machine
+// ============================================================================
+//
+// `APP_STATUS` is the single owner of the pipeline status (idle, recording,
+// ASR, LLM, TTS, playing). Every call site that moves the pipeline forward
+// goes through `transition`, which emits `app-status-changed` to the frontend
+// and keeps the last transitions for the `get_status_history` debug command.
+// While listening is paused (see focus_pause), idle is reported as paused.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+
+use crate::daemon::{APP_HANDLE, APP_STATUS};
+use crate::types::{AppStatus, StatusTransition};
+use crate::events::{emit_event, AppEvent};
+
+/// Number of transitions kept for `get_status_history`
+const STATUS_HISTORY_LEN: usize = 100;
+
+struct StatusState {
+    status: AppStatus,
+    history: VecDeque<StatusTransition>,
+}
+
+/// Owner of the current pipeline status
+pub struct StateMachine {
+    state: Mutex<StatusState>,
+}
+
+impl StateMachine {
+    pub const fn new() -> Self {
+        StateMachine {
+            state: Mutex::new(StatusState {
+                status: AppStatus::Idle,
+                history: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Current status (Idle if the lock is poisoned)
+    pub fn current(&self) -> AppStatus {
+        self.state.lock().map(|s| s.status).unwrap_or(AppStatus::Idle)
+    }
+
+    /// Move to `to`; `source` names the call site for the audit trail
+    ///
+    /// Returns the previous status. Transitions to the current status are ignored.
+    pub fn transition(&self, to: AppStatus, source: &str) -> AppStatus {
+        let to = if to == AppStatus::Idle && crate::focus_pause::is_paused() {
+            AppStatus::Paused
+        } else {
+            to
+        };
+        let transition = {
+            let Ok(mut state) = self.state.lock() else {
+                return AppStatus::Idle;
+            };
+            let from = state.status;
+            if from == to {
+                return from;
+            }
+            state.status = to;
+
+            let transition = StatusTransition {
+                from: from.as_str().to_string(),
+                to: to.as_str().to_string(),
+                source: source.to_string(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            };
+            if state.history.len() == STATUS_HISTORY_LEN {
+                state.history.pop_front();
+            }
+            state.history.push_back(transition.clone());
+            transition
+        };
+
+        // Emitted outside the lock so listeners can query the status
+        if let Some(app) = APP_HANDLE.get() {
+            emit_event(app, AppEvent::AppStatusChanged(transition.clone()));
+        }
+        AppStatus::from_str(&transition.from).unwrap_or(AppStatus::Idle)
+    }
+
+    /// Time of the last transition (Unix milliseconds), None if there was none
+    pub fn last_change_at(&self) -> Option<i64> {
+        self.state.lock().ok()?.history.back().map(|t| t.timestamp)
+    }
+
+    /// Recorded transitions, oldest first
+    pub fn history(&self) -> Vec<StatusTransition> {
+        self.state
+            .lock()
+            .map(|s| s.history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Last status transitions (oldest first), for debugging status drift
+#[tauri::command]
+pub fn get_status_history() -> Vec<StatusTransition> {
+    APP_STATUS.history()
+}
@@ -0,0 +1,228 @@
+// ============================================================================
+// Storage Module - Disk Usage of Generated Audio
+// ============================================================================
+//
+// Recordings and synthesized speech end up in several places: temporary PTT
+// and ASR WAVs, temporary TTS MP3s written by the daemon, the TTS cache and
+// the utterance archive. This module reports how much space each category
+// takes and deletes old files on request. With `storage_auto_clean`, leftover
+// temporary files older than `storage_auto_clean_days` are removed at startup.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tauri::Manager;
+
+use crate::audio_archive;
+use crate::config;
+use crate::state::AppState;
+use crate::tts_cache;
+use crate::types::{StorageCategoryUsage, StorageCleanResult, StorageUsage};
+
+const STORAGE_AUTO_CLEAN_KEY: &str = "storage_auto_clean";
+const STORAGE_AUTO_CLEAN_DAYS_KEY: &str = "storage_auto_clean_days";
+
+/// Startup cleanup defaults (keep in sync with default_config)
+const DEFAULT_AUTO_CLEAN: bool = true;
+const DEFAULT_AUTO_CLEAN_DAYS: u64 = 7;
+
+/// Temporary files younger than this may still be in use and are never deleted
+const TEMP_FILE_GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// A category of generated audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Category {
+    /// PTT recordings and ASR input (temp dir, speekium_ptt_* / speekium_asr_*)
+    Recordings,
+    /// Synthesized speech written by the daemon (temp dir, speekium_tts_*)
+    TtsTemp,
+    /// Cached synthesized speech (app cache dir)
+    TtsCache,
+    /// Recordings linked to history messages (app data dir)
+    AudioArchive,
+}
+
+const ALL_CATEGORIES: [Category; 4] = [
+    Category::Recordings,
+    Category::TtsTemp,
+    Category::TtsCache,
+    Category::AudioArchive,
+];
+
+impl Category {
+    fn as_str(self) -> &'static str {
+        match self {
+            Category::Recordings => "recordings",
+            Category::TtsTemp => "tts_temp",
+            Category::TtsCache => "tts_cache",
+            Category::AudioArchive => "audio_archive",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        ALL_CATEGORIES.into_iter().find(|c| c.as_str() == s)
+    }
+
+    fn is_temporary(self) -> bool {
+        matches!(self, Category::Recordings | Category::TtsTemp)
+    }
+
+    fn dir<R: tauri::Runtime>(self, app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+        match self {
+            Category::Recordings | Category::TtsTemp => Ok(std::env::temp_dir()),
+            Category::TtsCache => tts_cache::cache_dir(app),
+            Category::AudioArchive => audio_archive::archive_dir(app),
+        }
+    }
+
+    /// Whether a file in the category's directory belongs to it
+    fn owns(self, file_name: &str) -> bool {
+        match self {
+            Category::Recordings => {
+                (file_name.starts_with("speekium_ptt_") || file_name.starts_with("speekium_asr_"))
+                    && file_name.ends_with(".wav")
+            }
+            Category::TtsTemp => file_name.starts_with("speekium_tts_"),
+            Category::TtsCache => true,
+            Category::AudioArchive => file_name.ends_with(".wav"),
+        }
+    }
+}
+
+/// Files of a category (path, size, last modified)
+fn category_files<R: tauri::Runtime>(app: &tauri::AppHandle<R>, category: Category) -> Result<Vec<(PathBuf, u64, SystemTime)>, String> {
+    let dir = category.dir(app)?;
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    Ok(entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| category.owns(name)))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            Some((entry.path(), metadata.len(), metadata.modified().ok()?))
+        })
+        .collect())
+}
+
+/// Delete a category's files older than `older_than` (all files if None)
+fn clean_category<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    category: Category,
+    older_than: Option<Duration>,
+) -> Result<StorageCleanResult, String> {
+    let min_age = match (category.is_temporary(), older_than) {
+        (true, Some(age)) => Some(age.max(TEMP_FILE_GRACE_PERIOD)),
+        (true, None) => Some(TEMP_FILE_GRACE_PERIOD),
+        (false, age) => age,
+    };
+
+    let now = SystemTime::now();
+    let mut result = StorageCleanResult::default();
+    for (path, size, modified) in category_files(app, category)? {
+        let age = now.duration_since(modified).unwrap_or_default();
+        if min_age.is_some_and(|min| age < min) {
+            continue;
+        }
+        if std::fs::remove_file(&path).is_err() {
+            continue;
+        }
+        result.removed_files += 1;
+        result.freed_bytes += size;
+
+        if category == Category::AudioArchive {
+            unlink_archived_audio(app, &path)?;
+        }
+    }
+    Ok(result)
+}
+
+/// Forget a deleted archive recording in the messages that referenced it
+fn unlink_archived_audio<R: tauri::Runtime>(app: &tauri::AppHandle<R>, path: &Path) -> Result<(), String> {
+    match app.try_state::<AppState>() {
+        Some(state) => state.db.clear_message_audio(&path.to_string_lossy()),
+        None => Ok(()),
+    }
+}
+
+/// Remove old temporary audio on a background thread (if `storage_auto_clean` is on)
+pub fn spawn_startup_cleanup<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let enabled = config::read_config_value(STORAGE_AUTO_CLEAN_KEY)
+        .and_then(|v| v.as_bool())
+        .unwrap_or(DEFAULT_AUTO_CLEAN);
+    if !enabled {
+        return;
+    }
+    let days = config::read_config_value(STORAGE_AUTO_CLEAN_DAYS_KEY)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_AUTO_CLEAN_DAYS);
+    let older_than = Duration::from_secs(days * 24 * 60 * 60);
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for category in ALL_CATEGORIES.into_iter().filter(|c| c.is_temporary()) {
+            match clean_category(&app, category, Some(older_than)) {
+                Ok(result) if result.removed_files > 0 => println!(
+                    "[STORAGE] Removed {} {} files ({} bytes)",
+                    result.removed_files,
+                    category.as_str(),
+                    result.freed_bytes
+                ),
+                Ok(_) => {}
+                Err(e) => eprintln!("[STORAGE] Cleanup of {} failed: {}", category.as_str(), e),
+            }
+        }
+    });
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Disk space used by generated audio, by category
+#[tauri::command]
+pub fn get_storage_usage(app: tauri::AppHandle) -> Result<StorageUsage, String> {
+    let mut categories = Vec::new();
+    for category in ALL_CATEGORIES {
+        let files = category_files(&app, category)?;
+        categories.push(StorageCategoryUsage {
+            category: category.as_str().to_string(),
+            path: category.dir(&app)?.to_string_lossy().into_owned(),
+            files: files.len(),
+            bytes: files.iter().map(|(_, size, _)| size).sum(),
+        });
+    }
+    let total_bytes = categories.iter().map(|c| c.bytes).sum();
+    Ok(StorageUsage { categories, total_bytes })
+}
+
+/// Delete generated audio
+///
+/// `categories` defaults to all of them; `older_than_days` limits the cleanup
+/// to older files. Temporary files from the last few minutes are always kept.
+#[tauri::command]
+pub fn clean_storage(
+    app: tauri::AppHandle,
+    categories: Option<Vec<String>>,
+    older_than_days: Option<u64>,
+) -> Result<StorageCleanResult, String> {
+    let categories = match categories {
+        Some(names) => names
+            .iter()
+            .map(|name| Category::from_str(name).ok_or_else(|| format!("Unknown storage category: {}", name)))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => ALL_CATEGORIES.to_vec(),
+    };
+    let older_than = older_than_days.map(|days| Duration::from_secs(days * 24 * 60 * 60));
+
+    let mut total = StorageCleanResult::default();
+    for category in categories {
+        let result = clean_category(&app, category, older_than)?;
+        total.removed_files += result.removed_files;
+        total.freed_bytes += result.freed_bytes;
+    }
+    Ok(total)
+}
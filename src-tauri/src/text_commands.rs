@@ -0,0 +1,261 @@
+// ============================================================================
+// Text Commands Module - Spoken Editing Commands for Text-Input Mode
+// ============================================================================
+//
+// After ASR, command phrases in a text-input transcript ("new line", "press
+// enter", "delete that", ...) are turned into edits and keystrokes instead of
+// being typed literally. The built-in phrases can be overridden and extended
+// with the `text_commands` config map (phrase -> action).
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::platform::{press_key, press_undo, FrontmostApp, Key};
+
+/// What a command phrase does
+///
+/// In config: `{"insert": "\n"}`, `{"key": "enter"}`, `"delete_last"`, `"all_caps"`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextCommandAction {
+    /// Insert literal text
+    Insert(String),
+    /// Press a special key (enter, tab, escape, backspace)
+    Key(String),
+    /// Remove the text typed last
+    DeleteLast,
+    /// Upper-case the rest of the utterance
+    AllCaps,
+}
+
+/// One step of a parsed transcript
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextStep {
+    Text(String),
+    Key(Key),
+    /// Delete the text typed by the previous insertion
+    DeleteLast,
+}
+
+/// Punctuation ASR tends to put after a spoken command (dropped with it)
+const COMMAND_TRAILING_PUNCTUATION: &str = ".,!?;:。，！？；：、";
+
+/// The last insertion: what "delete that" and undo_last_typing remove
+struct LastInsertion {
+    /// Characters typed (0 = nothing to remove)
+    chars: usize,
+    /// App the text was typed into, when known
+    app: Option<FrontmostApp>,
+}
+
+static LAST_INSERTION: Mutex<LastInsertion> = Mutex::new(LastInsertion { chars: 0, app: None });
+
+/// Built-in command phrases
+fn builtin_commands() -> Vec<(&'static str, TextCommandAction)> {
+    vec![
+        ("new line", TextCommandAction::Insert("\n".to_string())),
+        ("new paragraph", TextCommandAction::Insert("\n\n".to_string())),
+        ("press enter", TextCommandAction::Key("enter".to_string())),
+        ("press tab", TextCommandAction::Key("tab".to_string())),
+        ("press escape", TextCommandAction::Key("escape".to_string())),
+        ("delete that", TextCommandAction::DeleteLast),
+        ("all caps", TextCommandAction::AllCaps),
+        ("换行", TextCommandAction::Insert("\n".to_string())),
+        ("按回车", TextCommandAction::Key("enter".to_string())),
+        ("删掉这句", TextCommandAction::DeleteLast),
+    ]
+}
+
+/// Built-in phrases merged with the configured ones, longest phrase first
+fn load_commands() -> Vec<(Vec<char>, TextCommandAction)> {
+    let config = crate::config::read_config();
+    if config.get("text_commands_enabled").and_then(|v| v.as_bool()) == Some(false) {
+        return Vec::new();
+    }
+
+    let mut commands: BTreeMap<String, TextCommandAction> = builtin_commands()
+        .into_iter()
+        .map(|(phrase, action)| (phrase.to_string(), action))
+        .collect();
+    let configured: BTreeMap<String, TextCommandAction> = config
+        .get("text_commands")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    for (phrase, action) in configured {
+        let phrase = phrase.trim().to_lowercase();
+        if !phrase.is_empty() {
+            commands.insert(phrase, action);
+        }
+    }
+
+    let mut commands: Vec<(Vec<char>, TextCommandAction)> = commands
+        .into_iter()
+        .map(|(phrase, action)| (phrase.chars().collect(), action))
+        .collect();
+    commands.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.len()));
+    commands
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Parse a transcript with the configured command phrases
+pub fn parse_transcript(text: &str) -> Vec<TextStep> {
+    parse(text, &load_commands())
+}
+
+/// Whether `phrase` occurs at `start` (case-insensitive, on word boundaries for Latin text)
+fn matches_at(chars: &[char], start: usize, phrase: &[char]) -> bool {
+    let end = start + phrase.len();
+    if phrase.is_empty() || end > chars.len() {
+        return false;
+    }
+    let same = chars[start..end]
+        .iter()
+        .zip(phrase)
+        .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()));
+    if !same {
+        return false;
+    }
+
+    // "new line" must not match inside "renew lines"
+    let word_before = start > 0 && chars[start - 1].is_ascii_alphanumeric() && phrase[0].is_ascii_alphanumeric();
+    let word_after = end < chars.len() && chars[end].is_ascii_alphanumeric() && phrase[phrase.len() - 1].is_ascii_alphanumeric();
+    !word_before && !word_after
+}
+
+/// Split a transcript into text and command steps
+fn parse(text: &str, commands: &[(Vec<char>, TextCommandAction)]) -> Vec<TextStep> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut steps = Vec::new();
+    let mut pending = String::new();
+    let mut all_caps = false;
+
+    let flush = |pending: &mut String, steps: &mut Vec<TextStep>, all_caps: bool, trim: bool| {
+        let text = if trim { pending.trim_end() } else { pending.as_str() };
+        if !text.is_empty() {
+            let text = if all_caps { text.to_uppercase() } else { text.to_string() };
+            steps.push(TextStep::Text(text));
+        }
+        pending.clear();
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let command = commands
+            .iter()
+            .find(|(phrase, _)| matches_at(&chars, i, phrase));
+        let Some((phrase, action)) = command else {
+            pending.push(chars[i]);
+            i += 1;
+            continue;
+        };
+
+        // Keep the space before upper-cased words, drop it before anything else
+        let trim = *action != TextCommandAction::AllCaps;
+        flush(&mut pending, &mut steps, all_caps, trim);
+        match action {
+            TextCommandAction::Insert(text) => steps.push(TextStep::Text(text.clone())),
+            TextCommandAction::Key(name) => match Key::from_str(name) {
+                Some(key) => steps.push(TextStep::Key(key)),
+                None => eprintln!("[TEXT COMMANDS] Unknown key: {}", name),
+            },
+            TextCommandAction::DeleteLast => {
+                // Text from this utterance is simply never typed
+                if matches!(steps.last(), Some(TextStep::Text(_))) {
+                    steps.pop();
+                } else {
+                    steps.push(TextStep::DeleteLast);
+                }
+            }
+            TextCommandAction::AllCaps => all_caps = true,
+        }
+
+        i += phrase.len();
+        while i < chars.len() && (chars[i].is_whitespace() || COMMAND_TRAILING_PUNCTUATION.contains(chars[i])) {
+            i += 1;
+        }
+    }
+    flush(&mut pending, &mut steps, all_caps, true);
+
+    steps
+}
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Perform parsed steps in `app`, typing text with `insert`
+///
+/// Consecutive text steps are typed in one go. Returns the number of characters typed.
+pub fn run(
+    steps: Vec<TextStep>,
+    app: Option<FrontmostApp>,
+    insert: impl Fn(&str) -> Result<(), String>,
+) -> Result<usize, String> {
+    let mut last = LAST_INSERTION.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut typed = 0;
+    let mut buffer = String::new();
+
+    let mut flush = |buffer: &mut String, last: &mut LastInsertion| -> Result<(), String> {
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        insert(buffer)?;
+        last.chars = buffer.chars().count();
+        last.app = app.clone();
+        typed += last.chars;
+        buffer.clear();
+        Ok(())
+    };
+
+    for step in steps {
+        match step {
+            TextStep::Text(text) => buffer.push_str(&text),
+            TextStep::Key(key) => {
+                flush(&mut buffer, &mut last)?;
+                press_key(key, 1)?;
+                // Text before a key press (e.g. a sent message) can't be taken back
+                last.chars = 0;
+            }
+            TextStep::DeleteLast => {
+                flush(&mut buffer, &mut last)?;
+                press_key(Key::Backspace, last.chars)?;
+                last.chars = 0;
+            }
+        }
+    }
+    flush(&mut buffer, &mut last)?;
+
+    Ok(typed)
+}
+
+/// Remove the last insertion from the focused `app`, with backspaces or, if
+/// `undo_shortcut`, one Cmd/Ctrl+Z
+///
+/// Refused when the text went to another app than the focused one. Returns
+/// the number of characters removed.
+pub fn undo_last(app: Option<&FrontmostApp>, undo_shortcut: bool) -> Result<usize, String> {
+    let mut last = LAST_INSERTION.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if last.chars == 0 {
+        return Err("Nothing to undo".to_string());
+    }
+    if let (Some(typed_into), Some(focused)) = (&last.app, app) {
+        if typed_into.ids() != focused.ids() {
+            let name = typed_into.name.as_deref().unwrap_or("another application");
+            return Err(format!("The last text was typed into {}, switch back to undo it", name));
+        }
+    }
+
+    if undo_shortcut {
+        press_undo()?;
+    } else {
+        press_key(Key::Backspace, last.chars)?;
+    }
+    let removed = last.chars;
+    last.chars = 0;
+    Ok(removed)
+}
@@ -0,0 +1,295 @@
+// ============================================================================
+// Textproc Module - Transcript Post-Processing
+// ============================================================================
+//
+// User-defined replacements applied to ASR output before it is typed or sent
+// to the LLM: whole-word phrases ("speekium" -> "Speekium", "btw" -> "by the
+// way") and regex rules with `$1`-style captures. Rules are stored in SQLite,
+// applied in the order they were added, and recompiled only when they change.
+//
+// Built-in stages (STAGES) run first, so the user's rules see their output
+// and can correct it. Each stage checks its own config (e.g. itn per work mode).
+//
+// Text-input mode also turns spoken symbol names into the symbols ("thumbs
+// up emoji" -> 👍, "em dash" -> —). DEFAULT_SYMBOLS ships a set; the user's
+// symbol shortcuts add phrases or override built-in ones. Symbols are not
+// applied to conversations, where the LLM reads the words just as well.
+
+use std::sync::Mutex;
+
+use regex::{NoExpand, Regex};
+use tauri::{Manager, State};
+
+use crate::database::{Database, Replacement, SymbolShortcut};
+use crate::state::AppState;
+
+/// Built-in spoken symbols: phrase -> symbol
+const DEFAULT_SYMBOLS: &[(&str, &str)] = &[
+    ("thumbs up emoji", "👍"),
+    ("thumbs down emoji", "👎"),
+    ("smiley emoji", "😊"),
+    ("laughing emoji", "😂"),
+    ("winking emoji", "😉"),
+    ("thinking emoji", "🤔"),
+    ("heart emoji", "❤️"),
+    ("fire emoji", "🔥"),
+    ("party emoji", "🎉"),
+    ("clapping emoji", "👏"),
+    ("rocket emoji", "🚀"),
+    ("check mark emoji", "✅"),
+    ("em dash", "—"),
+    ("en dash", "–"),
+    ("ellipsis", "…"),
+    ("right arrow", "→"),
+    ("left arrow", "←"),
+    ("degree sign", "°"),
+    ("plus minus sign", "±"),
+    ("multiplication sign", "×"),
+    ("bullet point", "•"),
+    ("copyright sign", "©"),
+    ("trademark sign", "™"),
+    ("euro sign", "€"),
+    ("pound sign", "£"),
+    ("点赞表情", "👍"),
+    ("笑脸表情", "😊"),
+    ("爱心表情", "❤️"),
+    ("破折号", "——"),
+    ("省略号", "……"),
+];
+
+/// Longest accepted symbol, in characters
+const MAX_SYMBOL_CHARS: usize = 32;
+
+/// A replacement ready to apply
+struct CompiledRule {
+    regex: Regex,
+    replacement: String,
+    is_regex: bool,
+}
+
+/// Built-in transcript stages, in order (the user's replacements run after them)
+const STAGES: &[fn(&str) -> String] = &[crate::itn::process];
+
+/// Compiled rules (None = load from the database on next use)
+static RULES: Mutex<Option<Vec<CompiledRule>>> = Mutex::new(None);
+
+/// Compiled symbol shortcuts (None = built-in ones only, until reload_symbols)
+static SYMBOLS: Mutex<Option<Vec<CompiledRule>>> = Mutex::new(None);
+
+/// Case-insensitive whole-word pattern for a literal phrase
+///
+/// Word boundaries are only required next to ASCII letters/digits, so phrases
+/// in scripts without spaces (e.g. Chinese) still match inside a sentence.
+fn phrase_pattern(phrase: &str) -> String {
+    let boundary = |c: Option<char>| if c.is_some_and(|c| c.is_ascii_alphanumeric()) { r"\b" } else { "" };
+    format!(
+        "(?i){}{}{}",
+        boundary(phrase.chars().next()),
+        regex::escape(phrase),
+        boundary(phrase.chars().last()),
+    )
+}
+
+fn compile(pattern: &str, replacement: &str, is_regex: bool) -> Result<CompiledRule, String> {
+    let source = if is_regex { pattern.to_string() } else { phrase_pattern(pattern) };
+    let regex = Regex::new(&source)
+        .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+    Ok(CompiledRule {
+        regex,
+        replacement: replacement.to_string(),
+        is_regex,
+    })
+}
+
+/// Load and compile the stored rules, skipping any that no longer compile
+fn load_rules<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Vec<CompiledRule> {
+    let Some(state) = app.try_state::<AppState>() else {
+        return Vec::new();
+    };
+    let replacements = state.db.list_replacements().unwrap_or_else(|e| {
+        eprintln!("[TEXTPROC] {}", e);
+        Vec::new()
+    });
+
+    replacements
+        .iter()
+        .filter_map(|r| {
+            compile(&r.pattern, &r.replacement, r.is_regex)
+                .map_err(|e| eprintln!("[TEXTPROC] {}", e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Drop the compiled rules so the next transcript reloads them
+fn invalidate_rules() {
+    if let Ok(mut rules) = RULES.lock() {
+        *rules = None;
+    }
+}
+
+fn apply_rules(rules: &[CompiledRule], text: &str) -> String {
+    let mut result = text.to_string();
+    for rule in rules {
+        let replaced = if rule.is_regex {
+            rule.regex.replace_all(&result, rule.replacement.as_str())
+        } else {
+            rule.regex.replace_all(&result, NoExpand(&rule.replacement))
+        };
+        result = replaced.into_owned();
+    }
+    result
+}
+
+/// Apply the user's replacements to an ASR transcript
+pub fn process_transcript<R: tauri::Runtime>(app: &tauri::AppHandle<R>, text: &str) -> String {
+    let Ok(mut rules) = RULES.lock() else {
+        return text.to_string();
+    };
+    let rules = rules.get_or_insert_with(|| load_rules(app));
+    let text = STAGES.iter().fold(text.to_string(), |text, stage| stage(&text));
+    apply_rules(rules, &text)
+}
+
+/// Built-in symbols merged with the user's shortcuts, longest phrase first
+fn compile_symbols(shortcuts: &[SymbolShortcut]) -> Vec<CompiledRule> {
+    let mut symbols: Vec<(String, String)> = DEFAULT_SYMBOLS
+        .iter()
+        .filter(|(phrase, _)| !shortcuts.iter().any(|s| s.phrase.eq_ignore_ascii_case(phrase)))
+        .map(|(phrase, symbol)| (phrase.to_string(), symbol.to_string()))
+        .collect();
+    symbols.extend(shortcuts.iter().map(|s| (s.phrase.clone(), s.symbol.clone())));
+    // "left arrow" must not be taken by a shorter "arrow"
+    symbols.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.chars().count()));
+
+    symbols
+        .iter()
+        .filter_map(|(phrase, symbol)| {
+            compile(phrase, symbol, false)
+                .map_err(|e| eprintln!("[TEXTPROC] {}", e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Recompile the symbol shortcuts from `db` (call after they change or the database is reopened)
+pub fn reload_symbols(db: &Database) {
+    let shortcuts = db.list_symbol_shortcuts().unwrap_or_else(|e| {
+        eprintln!("[TEXTPROC] {}", e);
+        Vec::new()
+    });
+    if let Ok(mut symbols) = SYMBOLS.lock() {
+        *symbols = Some(compile_symbols(&shortcuts));
+    }
+}
+
+/// Turn spoken symbol names into symbols (text-input mode only)
+pub fn process_symbols(text: &str) -> String {
+    let Ok(mut symbols) = SYMBOLS.lock() else {
+        return text.to_string();
+    };
+    let symbols = symbols.get_or_insert_with(|| compile_symbols(&[]));
+    apply_rules(symbols, text)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Add a transcript replacement (`is_regex` = false: case-insensitive whole-word phrase)
+#[tauri::command]
+pub fn add_replacement(
+    state: State<'_, AppState>,
+    pattern: String,
+    replacement: String,
+    is_regex: Option<bool>,
+) -> Result<Replacement, String> {
+    let is_regex = is_regex.unwrap_or(false);
+    let pattern = if is_regex { pattern } else { pattern.trim().to_string() };
+    if pattern.is_empty() {
+        return Err("Pattern must not be empty".to_string());
+    }
+    compile(&pattern, &replacement, is_regex)?;
+
+    let added = state.db.add_replacement(&pattern, &replacement, is_regex)?;
+    invalidate_rules();
+    Ok(added)
+}
+
+/// List transcript replacements in the order they are applied
+#[tauri::command]
+pub fn list_replacements(state: State<'_, AppState>) -> Result<Vec<Replacement>, String> {
+    state.db.list_replacements()
+}
+
+/// Delete a transcript replacement
+#[tauri::command]
+pub fn delete_replacement(state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    let deleted = state.db.delete_replacement(id)?;
+    invalidate_rules();
+    Ok(deleted)
+}
+
+/// Built-in spoken symbols (phrase, symbol); the user's shortcuts take precedence
+#[tauri::command]
+pub fn list_default_symbols() -> Vec<(String, String)> {
+    DEFAULT_SYMBOLS
+        .iter()
+        .map(|(phrase, symbol)| (phrase.to_string(), symbol.to_string()))
+        .collect()
+}
+
+/// The user's symbol shortcuts
+#[tauri::command]
+pub fn list_symbol_shortcuts(state: State<'_, AppState>) -> Result<Vec<SymbolShortcut>, String> {
+    state.db.list_symbol_shortcuts()
+}
+
+/// Add a symbol shortcut, or change the symbol of an existing phrase (also overrides a built-in one)
+#[tauri::command]
+pub fn set_symbol_shortcut(state: State<'_, AppState>, phrase: String, symbol: String) -> Result<SymbolShortcut, String> {
+    let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    let symbol = symbol.trim();
+    if phrase.is_empty() || symbol.is_empty() {
+        return Err("Phrase and symbol must not be empty".to_string());
+    }
+    if symbol.chars().count() > MAX_SYMBOL_CHARS {
+        return Err(format!("Symbol is longer than {} characters", MAX_SYMBOL_CHARS));
+    }
+
+    let shortcut = state.db.set_symbol_shortcut(&phrase, symbol)?;
+    reload_symbols(&state.db);
+    Ok(shortcut)
+}
+
+/// Delete a symbol shortcut (a built-in symbol it overrode applies again)
+#[tauri::command]
+pub fn delete_symbol_shortcut(state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    let deleted = state.db.delete_symbol_shortcut(id)?;
+    reload_symbols(&state.db);
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut(phrase: &str, symbol: &str) -> SymbolShortcut {
+        SymbolShortcut { id: 0, phrase: phrase.to_string(), symbol: symbol.to_string(), created_at: 0 }
+    }
+
+    #[test]
+    fn test_symbols() {
+        let defaults = compile_symbols(&[]);
+        assert_eq!(apply_rules(&defaults, "Great job Thumbs up emoji"), "Great job 👍");
+        assert_eq!(apply_rules(&defaults, "wait em dash really"), "wait — really");
+        // Whole words only
+        assert_eq!(apply_rules(&defaults, "the ellipsis-shaped"), "the …-shaped");
+        assert_eq!(apply_rules(&defaults, "ellipsises"), "ellipsises");
+        assert_eq!(apply_rules(&defaults, "好的点赞表情"), "好的👍");
+
+        let custom = compile_symbols(&[shortcut("thumbs up emoji", "👌"), shortcut("arrow", "↑")]);
+        assert_eq!(apply_rules(&custom, "thumbs up emoji"), "👌");
+        assert_eq!(apply_rules(&custom, "left arrow and arrow"), "← and ↑");
+    }
+}
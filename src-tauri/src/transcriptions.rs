@@ -0,0 +1,177 @@
+// ============================================================================
+// Transcriptions Module - Dictation History and Picker
+// ============================================================================
+//
+// In text-input mode every transcript is typed into the focused app and then
+// replaced by the next one. Each dictation is also saved to the
+// `transcriptions` table, so older ones can be pasted again from the
+// transcription picker: a small window opened with the `transcription_picker`
+// shortcut. Retention (`transcription_history_max_days`,
+// `transcription_history_max_items`, 0 = no limit) is applied after each new
+// dictation.
+
+use std::time::Duration;
+
+use tauri::{Manager, Runtime, State, WebviewWindowBuilder, WindowEvent};
+
+use crate::config;
+use crate::database::{PaginatedResult, Transcription};
+use crate::state::AppState;
+use crate::types::WorkMode;
+use crate::events::{emit_event, AppEvent};
+
+const MAX_DAYS_KEY: &str = "transcription_history_max_days";
+const MAX_ITEMS_KEY: &str = "transcription_history_max_items";
+
+/// Retention defaults (keep in sync with default_config)
+const DEFAULT_MAX_DAYS: u64 = 30;
+const DEFAULT_MAX_ITEMS: u64 = 500;
+
+const DEFAULT_PAGE_SIZE: i32 = 20;
+
+pub const PICKER_LABEL: &str = "transcription-picker";
+const PICKER_WIDTH: f64 = 420.0;
+const PICKER_HEIGHT: f64 = 360.0;
+
+/// Time for focus to return to the target app after the picker hides
+const PASTE_DELAY: Duration = Duration::from_millis(250);
+
+/// Save a dictation (text-input mode only) and apply the retention limits
+pub fn record<R: Runtime>(app: &tauri::AppHandle<R>, text: &str) {
+    if *crate::daemon::WORK_MODE.lock().unwrap() != WorkMode::TextInput || text.trim().is_empty() {
+        return;
+    }
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+
+    match state.db.add_transcription(text) {
+        Ok(transcription) => {
+            emit_event(app, AppEvent::TranscriptionAdded(transcription));
+        }
+        Err(e) => {
+            eprintln!("⚠️ Failed to save transcription: {}", e);
+            return;
+        }
+    }
+
+    let limit = |key: &str, default: u64| {
+        config::read_config_value(key)
+            .and_then(|v| v.as_u64())
+            .unwrap_or(default)
+    };
+    let max_days = limit(MAX_DAYS_KEY, DEFAULT_MAX_DAYS);
+    let max_items = limit(MAX_ITEMS_KEY, DEFAULT_MAX_ITEMS);
+    let cutoff = (max_days > 0)
+        .then(|| chrono::Utc::now().timestamp_millis() - (max_days * 24 * 60 * 60 * 1000) as i64);
+    let max_items = (max_items > 0).then_some(max_items as i64);
+    if let Err(e) = state.db.prune_transcriptions(cutoff, max_items) {
+        eprintln!("⚠️ Failed to prune transcription history: {}", e);
+    }
+}
+
+// ============================================================================
+// Picker Window
+// ============================================================================
+
+fn create_picker<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<tauri::WebviewWindow<R>> {
+    let picker = WebviewWindowBuilder::new(
+        app,
+        PICKER_LABEL,
+        tauri::WebviewUrl::App("transcription-picker.html".into()),
+    )
+    .title("Transcriptions")
+    .inner_size(PICKER_WIDTH, PICKER_HEIGHT)
+    .center()
+    .always_on_top(true)
+    .decorations(false)
+    .resizable(false)
+    .skip_taskbar(true)
+    .visible(false)
+    .build()?;
+
+    // Clicking elsewhere dismisses the picker
+    let window = picker.clone();
+    picker.on_window_event(move |event| {
+        if let WindowEvent::Focused(false) = event {
+            let _ = window.hide();
+        }
+    });
+
+    Ok(picker)
+}
+
+/// Show the picker over the focused app, or hide it if it is already showing
+pub fn toggle_picker<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let picker = match app.get_webview_window(PICKER_LABEL) {
+        Some(picker) => picker,
+        None => match create_picker(app) {
+            Ok(picker) => picker,
+            Err(e) => {
+                eprintln!("Failed to create transcription picker: {}", e);
+                return;
+            }
+        },
+    };
+
+    if picker.is_visible().unwrap_or(false) {
+        let _ = picker.hide();
+        return;
+    }
+    let _ = picker.center();
+    let _ = picker.show();
+    let _ = picker.set_focus();
+    // The page reloads its list when shown
+    emit_event(app, AppEvent::TranscriptionPickerShown);
+}
+
+/// Hide the picker and hand focus back to the app it was opened over
+fn hide_picker<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(picker) = app.get_webview_window(PICKER_LABEL) {
+        let _ = picker.hide();
+    }
+    // Hiding one window keeps the app active; hide the app so the previous one gets focus
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.hide();
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List saved dictations (newest first)
+#[tauri::command]
+pub async fn list_transcriptions(
+    state: State<'_, AppState>,
+    page: i32,
+    page_size: Option<i32>,
+) -> Result<PaginatedResult<Transcription>, String> {
+    state.db.list_transcriptions(page.max(1), page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1))
+}
+
+/// Delete a saved dictation
+#[tauri::command]
+pub async fn delete_transcription(state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    state.db.delete_transcription(id)
+}
+
+/// Close the picker and type a saved dictation into the app it was opened over
+#[tauri::command]
+pub async fn paste_transcription(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: i64,
+) -> Result<(), String> {
+    let transcription = state.db.get_transcription(id)?;
+    hide_picker(&app);
+    crate::platform::paste_text_after(transcription.text, PASTE_DELAY);
+    Ok(())
+}
+
+/// Close the picker without pasting
+#[tauri::command]
+pub fn close_transcription_picker(app: tauri::AppHandle) {
+    hide_picker(&app);
+}
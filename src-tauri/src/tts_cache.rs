@@ -0,0 +1,178 @@
+// ============================================================================
+// TTS Cache Module - Reuse Generated Speech
+// ============================================================================
+//
+// Audio returned by the daemon `tts` command is copied into <app cache>/tts,
+// keyed by a hash of the text and the voice settings, so repeated phrases
+// don't have to be synthesized again. The directory is kept under
+// `tts_cache_max_mb` by evicting the least recently used files (a hit
+// refreshes the file's modification time).
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use tauri::Manager;
+
+use crate::config;
+
+const TTS_CACHE_MAX_MB_KEY: &str = "tts_cache_max_mb";
+
+/// Cache size limit when not configured (keep in sync with default_config)
+const DEFAULT_MAX_MB: u64 = 100;
+
+/// Subdirectory of the app cache directory holding cached audio
+const TTS_CACHE_DIR_NAME: &str = "tts";
+
+pub(crate) fn cache_dir<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_cache_dir()
+        .map(|dir| dir.join(TTS_CACHE_DIR_NAME))
+        .map_err(|e| format!("Failed to get app cache dir: {}", e))
+}
+
+/// Configured size limit in bytes (0 = cache disabled)
+fn max_bytes() -> u64 {
+    config::read_config_value(TTS_CACHE_MAX_MB_KEY)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_MAX_MB)
+        .saturating_mul(1024 * 1024)
+}
+
+/// Cache key: FNV-1a hash of the text and everything that changes the voice
+fn cache_key<R: tauri::Runtime>(app: &tauri::AppHandle<R>, text: &str) -> String {
+    let setting = |key: &str, default: &str| {
+        config::read_config_value(key)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| default.to_string())
+    };
+    let mut input = vec![
+        text.to_string(),
+        setting("tts_backend", "edge"),
+        setting("tts_voice", "auto"),
+        setting("tts_rate", "+0%"),
+        setting("tts_pitch", "+0Hz"),
+        setting("tts_volume", "+0%"),
+    ];
+    // The active persona's voice and speed override the configured ones
+    if let Some(persona) = crate::personas::active_persona(app) {
+        input.push(persona.voice.unwrap_or_default());
+        input.push(persona.speed.to_string());
+    }
+    let input = input.join("\0");
+
+    let hash = input
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Cached files (path, size, last use)
+fn cached_files(dir: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            Some((entry.path(), metadata.len(), metadata.modified().ok()?))
+        })
+        .collect()
+}
+
+/// Mark a cached file as just used
+fn touch(path: &Path) {
+    if let Ok(file) = std::fs::File::options().append(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+// ============================================================================
+// Lookup / Store
+// ============================================================================
+
+/// Cached audio for `text` with the current voice settings
+pub fn lookup<R: tauri::Runtime>(app: &tauri::AppHandle<R>, text: &str) -> Option<String> {
+    if max_bytes() == 0 {
+        return None;
+    }
+    let dir = cache_dir(app).ok()?;
+    let key = cache_key(app, text);
+
+    let (path, _, _) = cached_files(&dir)
+        .into_iter()
+        .find(|(path, _, _)| path.file_stem().is_some_and(|stem| stem == key.as_str()))?;
+    touch(&path);
+    Some(path.to_string_lossy().into_owned())
+}
+
+/// Copy freshly generated audio into the cache and return the cached path
+///
+/// Falls back to the original path if caching is disabled or fails.
+pub fn store<R: tauri::Runtime>(app: &tauri::AppHandle<R>, text: &str, audio_path: &str) -> String {
+    let max = max_bytes();
+    if max == 0 {
+        return audio_path.to_string();
+    }
+
+    let result = cache_dir(app).and_then(|dir| {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create TTS cache directory: {}", e))?;
+        let extension = Path::new(audio_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("mp3");
+        let cached = dir.join(format!("{}.{}", cache_key(app, text), extension));
+        std::fs::copy(audio_path, &cached)
+            .map_err(|e| format!("Failed to cache TTS audio: {}", e))?;
+        evict(&dir, max);
+        Ok(cached)
+    });
+
+    match result {
+        Ok(cached) => cached.to_string_lossy().into_owned(),
+        Err(e) => {
+            eprintln!("[TTS CACHE] {}", e);
+            audio_path.to_string()
+        }
+    }
+}
+
+/// Delete the least recently used files until the cache fits in `max` bytes
+fn evict(dir: &Path, max: u64) {
+    let mut files = cached_files(dir);
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    if total <= max {
+        return;
+    }
+
+    files.sort_by_key(|(_, _, used)| *used);
+    for (path, size, _) in files {
+        if total <= max {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(size);
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Delete every cached TTS file; returns the number of removed files
+#[tauri::command]
+pub fn clear_tts_cache(app: tauri::AppHandle) -> Result<usize, String> {
+    let dir = cache_dir(&app)?;
+    let removed = cached_files(&dir)
+        .into_iter()
+        .filter(|(path, _, _)| std::fs::remove_file(path).is_ok())
+        .count();
+    Ok(removed)
+}
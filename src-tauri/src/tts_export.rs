@@ -0,0 +1,288 @@
+// ============================================================================
+// TTS Export Module - Narrating Text to an Audio File
+// ============================================================================
+//
+// `synthesize_to_file` speaks arbitrary text into a file instead of the
+// speakers. The text is cut into sentences (the same splitter the voice
+// pipeline uses), grouped into chunks of up to MAX_CHUNK_CHARS and sent to
+// the daemon `tts` command one chunk at a time, so long texts stay within
+// what the TTS backends accept. The chunk audio is then joined here:
+//
+//   wav  every chunk is decoded (WAV with hound, MP3 with symphonia) and
+//        written as one 16-bit PCM file
+//   mp3  MP3 chunks (Edge TTS) are joined frame by frame, without
+//        re-encoding; backends that produce WAV can only export WAV
+//
+// Progress is emitted as `tts-export-progress` {done, total} per chunk.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+
+use crate::events::{emit_event, AppEvent};
+use crate::pipeline::SentenceSplitter;
+use crate::tts_voices::SpeechParams;
+use crate::types::TtsExportProgressPayload;
+
+/// Longest text sent to TTS at once (whole sentences are never split)
+const MAX_CHUNK_CHARS: usize = 500;
+
+const SUPPORTED_FORMATS: [&str; 2] = ["wav", "mp3"];
+
+/// Decoded chunk audio (interleaved)
+struct Pcm {
+    sample_rate: u32,
+    channels: u16,
+    samples: Vec<i16>,
+}
+
+/// Cut text into sentence-aligned chunks of up to MAX_CHUNK_CHARS
+fn chunk_text(text: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for line in text.lines() {
+        let mut splitter = SentenceSplitter::default();
+        let mut sentences = splitter.push(line);
+        sentences.extend(splitter.finish());
+
+        for sentence in sentences {
+            let sentence = sentence.trim();
+            if !current.is_empty() && current.chars().count() + sentence.chars().count() > MAX_CHUNK_CHARS {
+                chunks.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(sentence);
+        }
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn is_wav(bytes: &[u8]) -> bool {
+    bytes.get(0..4) == Some(b"RIFF") && bytes.get(8..12) == Some(b"WAVE")
+}
+
+/// MP3 frames of a file, without its ID3 tags
+fn mp3_frames(bytes: &[u8]) -> &[u8] {
+    let mut frames = bytes;
+    // ID3v2 header: "ID3", version (2), flags, size as 4 x 7 bits
+    if frames.len() >= 10 && &frames[0..3] == b"ID3" {
+        let size = frames[6..10].iter().fold(0usize, |size, &b| (size << 7) | (b & 0x7F) as usize);
+        frames = &frames[(10 + size).min(frames.len())..];
+    }
+    // ID3v1 tag: the last 128 bytes, starting with "TAG"
+    if frames.len() >= 128 && &frames[frames.len() - 128..frames.len() - 125] == b"TAG" {
+        frames = &frames[..frames.len() - 128];
+    }
+    frames
+}
+
+fn decode_wav(path: &Path) -> Result<Pcm, String> {
+    let mut reader = hound::WavReader::open(path).map_err(|e| format!("Failed to read TTS audio: {}", e))?;
+    let spec = reader.spec();
+    let samples: Result<Vec<i16>, hound::Error> = match (spec.sample_format, spec.bits_per_sample) {
+        (hound::SampleFormat::Int, 16) => reader.samples::<i16>().collect(),
+        (hound::SampleFormat::Int, bits) if bits < 16 => {
+            reader.samples::<i32>().map(|s| s.map(|s| (s << (16 - bits)) as i16)).collect()
+        }
+        (hound::SampleFormat::Int, bits) => {
+            reader.samples::<i32>().map(|s| s.map(|s| (s >> (bits - 16)) as i16)).collect()
+        }
+        (hound::SampleFormat::Float, _) => reader
+            .samples::<f32>()
+            .map(|s| s.map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16))
+            .collect(),
+    };
+    Ok(Pcm {
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+        samples: samples.map_err(|e| format!("Failed to read TTS audio: {}", e))?,
+    })
+}
+
+fn decode_mp3(path: &Path) -> Result<Pcm, String> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::errors::Error;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let file = File::open(path).map_err(|e| format!("Failed to read TTS audio: {}", e))?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension("mp3");
+    let mut format = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Unsupported TTS audio: {}", e))?
+        .format;
+    let track = format.default_track().ok_or("TTS audio has no track")?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Unsupported TTS audio: {}", e))?;
+
+    let mut pcm = Pcm { sample_rate: 0, channels: 0, samples: Vec::new() };
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(format!("Failed to decode TTS audio: {}", e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt frame is skipped
+            Err(Error::DecodeError(_)) => continue,
+            Err(e) => return Err(format!("Failed to decode TTS audio: {}", e)),
+        };
+        let spec = *decoded.spec();
+        pcm.sample_rate = spec.rate;
+        pcm.channels = spec.channels.count() as u16;
+        let mut buffer = SampleBuffer::<i16>::new(decoded.capacity() as u64, spec);
+        buffer.copy_interleaved_ref(decoded);
+        pcm.samples.extend_from_slice(buffer.samples());
+    }
+    Ok(pcm)
+}
+
+/// Join the chunk audio into one 16-bit PCM WAV file
+fn write_wav(chunks: &[PathBuf], output: &Path) -> Result<(), String> {
+    let mut writer: Option<(hound::WavWriter<_>, u32, u16)> = None;
+    for chunk in chunks {
+        let bytes = std::fs::read(chunk).map_err(|e| format!("Failed to read TTS audio: {}", e))?;
+        let pcm = if is_wav(&bytes) { decode_wav(chunk)? } else { decode_mp3(chunk)? };
+        if pcm.samples.is_empty() {
+            continue;
+        }
+
+        if writer.is_none() {
+            let spec = hound::WavSpec {
+                channels: pcm.channels,
+                sample_rate: pcm.sample_rate,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let created = hound::WavWriter::create(output, spec)
+                .map_err(|e| format!("Failed to create {}: {}", output.display(), e))?;
+            writer = Some((created, pcm.sample_rate, pcm.channels));
+        }
+        let Some((writer, sample_rate, channels)) = writer.as_mut() else {
+            continue;
+        };
+        if pcm.sample_rate != *sample_rate || pcm.channels != *channels {
+            return Err("TTS returned audio in different formats".to_string());
+        }
+        for sample in pcm.samples {
+            writer.write_sample(sample).map_err(|e| format!("Failed to write audio: {}", e))?;
+        }
+    }
+
+    let (writer, _, _) = writer.ok_or("TTS returned no audio")?;
+    writer.finalize().map_err(|e| format!("Failed to write audio: {}", e))
+}
+
+/// Join MP3 chunks frame by frame
+fn write_mp3(chunks: &[PathBuf], output: &Path) -> Result<(), String> {
+    let mut joined = Vec::new();
+    for chunk in chunks {
+        let bytes = std::fs::read(chunk).map_err(|e| format!("Failed to read TTS audio: {}", e))?;
+        if is_wav(&bytes) {
+            return Err("The TTS backend produces WAV audio; export as .wav instead".to_string());
+        }
+        joined.extend_from_slice(mp3_frames(&bytes));
+    }
+    std::fs::write(output, joined).map_err(|e| format!("Failed to write {}: {}", output.display(), e))
+}
+
+/// "wav" or "mp3": the requested format, or else the output file's extension
+fn output_format(output: &Path, format: Option<&str>) -> Result<String, String> {
+    let format = format
+        .map(str::to_string)
+        .or_else(|| output.extension().and_then(|e| e.to_str()).map(str::to_string))
+        .unwrap_or_else(|| "wav".to_string())
+        .to_ascii_lowercase();
+    if !SUPPORTED_FORMATS.contains(&format.as_str()) {
+        return Err(format!("Unsupported format {} (supported: {})", format, SUPPORTED_FORMATS.join(", ")));
+    }
+    Ok(format)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Speak text into a WAV or MP3 file without playing it; returns the file path
+#[tauri::command]
+pub async fn synthesize_to_file(
+    app: tauri::AppHandle,
+    text: String,
+    output_path: String,
+    voice: Option<String>,
+    format: Option<String>,
+) -> Result<String, String> {
+    let output = PathBuf::from(&output_path);
+    let format = output_format(&output, format.as_deref())?;
+    let chunks = chunk_text(&text);
+    if chunks.is_empty() {
+        return Err("Nothing to narrate".to_string());
+    }
+    let params = voice.map(|voice| SpeechParams { voice: Some(voice), ..Default::default() });
+
+    let mut audio = Vec::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let result = crate::commands::generate_tts(app.clone(), chunk.clone(), params.clone()).await?;
+        match result.audio_path {
+            Some(path) if result.success => audio.push(PathBuf::from(path)),
+            _ => return Err(result.error.unwrap_or_else(|| "TTS failed".to_string())),
+        }
+        emit_event(&app, AppEvent::TtsExportProgress(TtsExportProgressPayload { done: index + 1, total: chunks.len() }));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || match format.as_str() {
+        "mp3" => write_mp3(&audio, &output),
+        _ => write_wav(&audio, &output),
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    println!("[TTS EXPORT] {} chunk(s) written to {}", chunks.len(), output_path);
+    Ok(output_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_text_keeps_sentences_whole() {
+        let sentence = "This sentence is about fifty characters long, ok. ";
+        let text = sentence.repeat(25);
+        let chunks = chunk_text(&text);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= MAX_CHUNK_CHARS));
+        assert!(chunks.iter().all(|chunk| chunk.ends_with("ok.")));
+
+        assert_eq!(chunk_text("First line\nSecond line."), vec!["First line Second line.".to_string()]);
+        assert!(chunk_text("  \n ").is_empty());
+    }
+
+    #[test]
+    fn test_mp3_frames_strips_id3_tags() {
+        let mut bytes = b"ID3\x04\x00\x00\x00\x00\x00\x05".to_vec();
+        bytes.extend_from_slice(b"tag..");
+        bytes.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x00]);
+        let mut v1 = b"TAG".to_vec();
+        v1.resize(128, 0);
+        bytes.extend_from_slice(&v1);
+
+        assert_eq!(mp3_frames(&bytes), &[0xFF, 0xFB, 0x90, 0x00]);
+    }
+}
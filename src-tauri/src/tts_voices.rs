@@ -0,0 +1,209 @@
+// ============================================================================
+// TTS Voices Module - Voice Selection and Speech Parameters
+// ============================================================================
+//
+// Speech is generated with Edge TTS. The voice (`tts_voice`, "auto" = chosen
+// by the language of the text) and the speech parameters (`tts_rate`,
+// `tts_pitch`, `tts_volume`, as Edge TTS offsets like "+10%" / "-5Hz") are
+// stored in config. generate_tts and chat_tts_stream also take per-request
+// `SpeechParams`, sent to the daemon as a `speech` field; they win over the
+// active persona and the configured values for that request only.
+//
+// The voice list comes from the daemon (Edge TTS service) and is cached for
+// the app run. preview_voice speaks a sample with any voice without changing
+// the settings.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::daemon::call_daemon_async;
+use crate::types::TTSResult;
+
+const VOICE_KEY: &str = "tts_voice";
+
+/// Voice id that picks the voice by the language of the text
+pub const AUTO_VOICE: &str = "auto";
+
+/// Accepted speed multipliers (same range as personas)
+const MIN_SPEED: f64 = 0.5;
+const MAX_SPEED: f64 = 2.0;
+
+/// Accepted pitch offsets in Hz
+const MAX_PITCH_HZ: f64 = 100.0;
+
+/// Accepted volume offsets in percent
+const MAX_VOLUME_PERCENT: f64 = 100.0;
+
+/// Spoken by preview_voice when no sample text is given
+const DEFAULT_PREVIEW_TEXT: &str = "Hello, this is how I sound. 你好，这是我的声音。";
+
+/// Voices fetched from the daemon (None = not fetched yet)
+static VOICES: Mutex<Option<Vec<TtsVoice>>> = Mutex::new(None);
+
+/// One Edge TTS voice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsVoice {
+    /// e.g. "en-US-JennyNeural"
+    pub id: String,
+    pub name: String,
+    /// e.g. "en-US"
+    pub locale: String,
+    pub gender: String,
+}
+
+/// Per-request overrides of the configured voice and speech parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpeechParams {
+    /// Voice id
+    pub voice: Option<String>,
+    /// Speed multiplier (0.5 - 2.0, 1.0 = normal)
+    pub speed: Option<f64>,
+    /// Pitch offset in Hz (-100 - 100)
+    pub pitch: Option<f64>,
+    /// Volume offset in percent (-100 - 100)
+    pub volume: Option<f64>,
+}
+
+impl SpeechParams {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(speed) = self.speed {
+            if !(MIN_SPEED..=MAX_SPEED).contains(&speed) {
+                return Err(format!("Speed must be between {} and {}", MIN_SPEED, MAX_SPEED));
+            }
+        }
+        if let Some(pitch) = self.pitch {
+            if pitch.abs() > MAX_PITCH_HZ {
+                return Err(format!("Pitch must be between -{0} and {0} Hz", MAX_PITCH_HZ));
+            }
+        }
+        if let Some(volume) = self.volume {
+            if volume.abs() > MAX_VOLUME_PERCENT {
+                return Err(format!("Volume must be between -{0}% and {0}%", MAX_VOLUME_PERCENT));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.voice.is_none() && self.speed.is_none() && self.pitch.is_none() && self.volume.is_none()
+    }
+
+    /// Daemon `speech` field: Edge TTS strings ("+25%" for speed 1.25, "+5Hz", "-10%")
+    fn to_daemon_value(&self) -> serde_json::Value {
+        let mut speech = serde_json::Map::new();
+        if let Some(voice) = self.voice.as_ref().filter(|v| v.as_str() != AUTO_VOICE) {
+            speech.insert("voice".to_string(), serde_json::json!(voice));
+        }
+        if let Some(speed) = self.speed {
+            let percent = ((speed - 1.0) * 100.0).round() as i64;
+            speech.insert("rate".to_string(), serde_json::json!(format!("{:+}%", percent)));
+        }
+        if let Some(pitch) = self.pitch {
+            speech.insert("pitch".to_string(), serde_json::json!(format!("{:+}Hz", pitch.round() as i64)));
+        }
+        if let Some(volume) = self.volume {
+            speech.insert("volume".to_string(), serde_json::json!(format!("{:+}%", volume.round() as i64)));
+        }
+        serde_json::Value::Object(speech)
+    }
+}
+
+/// Validated daemon `speech` field for per-request overrides (None = no overrides)
+///
+/// Requests with overrides bypass the TTS cache.
+pub fn speech_value(params: Option<&SpeechParams>) -> Result<Option<serde_json::Value>, String> {
+    let Some(params) = params.filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    params.validate()?;
+    Ok(Some(params.to_daemon_value()))
+}
+
+/// Add the `speech` field to daemon command args
+pub fn insert_speech(args: &mut serde_json::Value, speech: Option<serde_json::Value>) {
+    if let (Some(map), Some(speech)) = (args.as_object_mut(), speech) {
+        map.insert("speech".to_string(), speech);
+    }
+}
+
+/// Whether a config value is an Edge TTS offset with the given unit ("+0%", "-5Hz")
+pub fn is_edge_offset(value: &str, unit: &str) -> bool {
+    value
+        .strip_suffix(unit)
+        .and_then(|number| number.strip_prefix(['+', '-']))
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+}
+
+async fn fetch_voices() -> Result<Vec<TtsVoice>, String> {
+    let result = call_daemon_async("list_tts_voices", serde_json::json!({})).await?;
+    if !result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return Err(result
+            .get("error")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Failed to list voices")
+            .to_string());
+    }
+    serde_json::from_value(result.get("voices").cloned().unwrap_or_default())
+        .map_err(|e| format!("Failed to parse voices: {}", e))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Available TTS voices (cached; `refresh` fetches the list again)
+#[tauri::command]
+pub async fn list_tts_voices(refresh: Option<bool>) -> Result<Vec<TtsVoice>, String> {
+    if !refresh.unwrap_or(false) {
+        if let Some(voices) = VOICES.lock().unwrap().clone() {
+            return Ok(voices);
+        }
+    }
+    let voices = fetch_voices().await?;
+    *VOICES.lock().unwrap() = Some(voices.clone());
+    Ok(voices)
+}
+
+/// Use a voice for all speech ("auto" = by the language of the text), saved to config
+#[tauri::command]
+pub fn set_tts_voice(voice_id: String) -> Result<(), String> {
+    let voice_id = voice_id.trim();
+    if voice_id.is_empty() {
+        return Err("Voice id must not be empty".to_string());
+    }
+    // Only checked against a fetched list; the daemon may not be ready yet
+    if let Some(voices) = VOICES.lock().unwrap().as_ref() {
+        if voice_id != AUTO_VOICE && !voices.iter().any(|v| v.id == voice_id) {
+            return Err(format!("Unknown voice: {}", voice_id));
+        }
+    }
+    config::write_config_value(VOICE_KEY, serde_json::json!(voice_id))?;
+    config::notify_daemon_config_changed();
+    Ok(())
+}
+
+/// Speak a sample with a voice without changing the settings
+///
+/// Returns the generated audio for the settings UI to play (not cached).
+#[tauri::command]
+pub async fn preview_voice(
+    voice_id: String,
+    sample_text: Option<String>,
+    params: Option<SpeechParams>,
+) -> Result<TTSResult, String> {
+    let text = sample_text
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .unwrap_or_else(|| DEFAULT_PREVIEW_TEXT.to_string());
+    let params = SpeechParams {
+        voice: Some(voice_id),
+        ..params.unwrap_or_default()
+    };
+
+    let mut args = serde_json::json!({ "text": text });
+    insert_speech(&mut args, speech_value(Some(&params))?);
+    let result = call_daemon_async("tts", args).await?;
+    serde_json::from_value(result).map_err(|e| format!("Failed to parse result: {}", e))
+}
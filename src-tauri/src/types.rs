@@ -12,6 +12,8 @@ use serde::{Deserialize, Serialize};
 pub enum RecordingMode {
     Continuous,
     PushToTalk,
+    /// System audio and microphone transcribed into a meeting session (see meeting.rs)
+    Meeting,
 }
 
 impl RecordingMode {
@@ -19,6 +21,7 @@ impl RecordingMode {
         match self {
             RecordingMode::Continuous => "continuous",
             RecordingMode::PushToTalk => "push-to-talk",
+            RecordingMode::Meeting => "meeting",
         }
     }
 
@@ -26,6 +29,54 @@ impl RecordingMode {
         match s {
             "continuous" => Some(RecordingMode::Continuous),
             "push-to-talk" => Some(RecordingMode::PushToTalk),
+            "meeting" => Some(RecordingMode::Meeting),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// PTT 触发方式
+// ============================================================================
+
+/// What starts/stops push-to-talk recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PttTrigger {
+    /// Global keyboard shortcut (push_to_talk_hotkey)
+    Shortcut,
+    /// Non-primary mouse button by button number (2 = middle, 3/4 = side buttons)
+    MouseButton(i64),
+    /// Fn / Globe key
+    FnKey,
+}
+
+impl PttTrigger {
+    pub fn from_str(s: &str, mouse_button: i64) -> Option<Self> {
+        match s {
+            "shortcut" => Some(PttTrigger::Shortcut),
+            "mouse_button" => Some(PttTrigger::MouseButton(mouse_button)),
+            "fn_key" => Some(PttTrigger::FnKey),
+            _ => None,
+        }
+    }
+}
+
+/// How text-input mode inserts text into the focused field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextInputStrategy {
+    /// Set the focused element's selected text via the Accessibility API
+    /// (macOS only, falls back to the clipboard). Opt-in: Chromium and
+    /// Electron apps report success without inserting anything
+    Accessibility,
+    /// Put the text on the clipboard and simulate paste (default)
+    Clipboard,
+}
+
+impl TextInputStrategy {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "accessibility" => Some(TextInputStrategy::Accessibility),
+            "clipboard" => Some(TextInputStrategy::Clipboard),
             _ => None,
         }
     }
@@ -71,6 +122,7 @@ pub enum AppStatus {
     LlmProcessing,     // LLM思考中
     TtsProcessing,     // TTS生成中
     Playing,           // TTS播放中
+    Paused,            // 空闲，连续监听已暂停
 }
 
 impl AppStatus {
@@ -83,6 +135,7 @@ impl AppStatus {
             AppStatus::LlmProcessing => "llm",
             AppStatus::TtsProcessing => "tts",
             AppStatus::Playing => "playing",
+            AppStatus::Paused => "paused",
         }
     }
 
@@ -95,10 +148,16 @@ impl AppStatus {
             "llm" => Some(AppStatus::LlmProcessing),
             "tts" => Some(AppStatus::TtsProcessing),
             "playing" => Some(AppStatus::Playing),
+            "paused" => Some(AppStatus::Paused),
             _ => None,
         }
     }
 
+    /// Nothing in progress (paused listening counts as idle)
+    pub fn is_idle(&self) -> bool {
+        matches!(self, AppStatus::Idle | AppStatus::Paused)
+    }
+
     pub fn can_be_interrupted(&self, interrupt_priority: u8) -> bool {
         match interrupt_priority {
             1 => true,  // Mode switch: can interrupt everything
@@ -109,6 +168,15 @@ impl AppStatus {
     }
 }
 
+/// One recorded status change (payload of `app-status-changed`)
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StatusTransition {
+    pub from: String,       // Previous status ("idle", "recording", "asr", ...)
+    pub to: String,         // New status
+    pub source: String,     // Call site that caused the change (e.g. "ptt_press", "daemon:idle")
+    pub timestamp: i64,     // Unix time in milliseconds
+}
+
 // ============================================================================
 // 守护进程模式
 // ============================================================================
@@ -117,6 +185,8 @@ impl AppStatus {
 pub enum DaemonMode {
     Development { script_path: std::path::PathBuf },
     Production { executable_path: std::path::PathBuf },
+    /// The app binary itself, run as the mock daemon (SPEEKIUM_MOCK_DAEMON=1)
+    Mock { executable_path: std::path::PathBuf },
 }
 
 // ============================================================================
@@ -129,6 +199,31 @@ pub struct RecordResult {
     pub text: Option<String>,
     pub language: Option<String>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub confidence: Option<f64>, // 0-1, when the ASR model reports one
+    #[serde(default)]
+    pub alternatives: Vec<String>, // Other ASR hypotheses, best first
+    #[serde(default)]
+    pub words: Vec<WordTiming>,    // Word timings of the raw transcript (empty if the model has none)
+    #[serde(default)]
+    pub segments: Vec<SpeakerSegment>, // Per-speaker parts (empty unless several speakers were detected)
+}
+
+/// Part of a transcript spoken by one speaker (diarization), times in ms from the start
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct SpeakerSegment {
+    pub speaker: u32, // 1-based ("Speaker 1")
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// Timing of one recognized word, in ms from the start of the recording
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct WordTiming {
+    pub word: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -150,6 +245,15 @@ pub struct ConfigResult {
     pub success: bool,
     pub config: Option<serde_json::Value>,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invalid_fields: Option<Vec<ConfigFieldError>>,  // Fields that failed validation
+}
+
+/// A config field that failed validation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConfigFieldError {
+    pub field: String,    // Top-level config key
+    pub message: String,  // Why the value was rejected
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -177,9 +281,108 @@ pub struct ModelStatusResult {
     pub error: Option<String>,
 }
 
+/// p50/p95 of one pipeline stage across recent interactions
+#[derive(Serialize, Debug, Default)]
+pub struct LatencyStageStats {
+    pub samples: usize,
+    pub p50_ms: Option<i64>,
+    pub p95_ms: Option<i64>,
+}
+
+/// Latency percentiles per pipeline stage (each stage measured from the previous one)
+#[derive(Serialize, Debug)]
+pub struct LatencyStats {
+    pub interactions: usize,
+    pub asr: LatencyStageStats,             // Recording stop -> ASR done
+    pub llm_first_token: LatencyStageStats, // ASR done -> first LLM token
+    pub tts_first_audio: LatencyStageStats, // First LLM token -> first TTS audio
+    pub playback_start: LatencyStageStats,  // First TTS audio -> playback start
+    pub end_to_end: LatencyStageStats,      // Recording stop -> playback start
+}
+
+/// Activity of one local calendar day
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct DailyActivity {
+    pub date: String,       // YYYY-MM-DD, local time
+    pub recordings: usize,
+    pub words: usize,
+}
+
+/// Dictation usage over a time range (see usage_stats)
+#[derive(Serialize, Debug)]
+pub struct UsageStats {
+    pub range: String,                  // "day", "week", "month", "year" or "all"
+    pub since: Option<i64>,             // Start of the range (ms), None for "all"
+    pub recordings: usize,              // dictations + conversation_turns
+    pub dictations: usize,              // Text-input mode transcriptions
+    pub conversation_turns: usize,      // User messages in conversations
+    pub words: usize,
+    pub avg_asr_ms: Option<i64>,        // Recording stop -> ASR done
+    pub avg_end_to_end_ms: Option<i64>, // Recording stop -> playback start
+    pub most_used_mode: Option<String>, // "conversation" or "text-input"
+    pub time_saved_secs: i64,           // Typing time minus speaking time for `words`
+    pub daily: Vec<DailyActivity>,      // One bucket per day of the range, oldest first
+}
+
+/// Disk usage of one category of generated audio
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StorageCategoryUsage {
+    pub category: String,   // "recordings", "tts_temp", "tts_cache" or "audio_archive"
+    pub path: String,       // Directory the files live in
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Disk usage of generated audio, by category
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StorageUsage {
+    pub categories: Vec<StorageCategoryUsage>,
+    pub total_bytes: u64,
+}
+
+/// Result of a storage cleanup
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct StorageCleanResult {
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+}
+
+/// One step of the daemon initialization timeline
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct InitTimelineEntry {
+    pub event: String,       // Daemon init event (e.g. "loading_asr", "asr_loaded", "ready")
+    pub elapsed_ms: u64,     // Time since the daemon was launched
+    pub duration_ms: u64,    // Time since the previous event
+}
+
+/// Crash report written by the panic hook or when the daemon dies
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CrashReport {
+    pub kind: String,                 // "panic" | "daemon_exit"
+    pub timestamp: String,            // RFC 3339
+    pub app_version: String,
+    pub os: String,
+    pub message: String,
+    pub location: Option<String>,     // Panic source location
+    pub thread: Option<String>,
+    pub backtrace: Option<String>,
+    pub daemon_stderr_tail: Vec<String>,
+    pub init_timeline: Vec<InitTimelineEntry>,
+}
+
+/// Result of a shortcut dry-run validation
+#[derive(Serialize, Debug)]
+pub struct ShortcutValidationResult {
+    pub valid: bool,
+    pub shortcut: String,
+    pub reason: Option<String>,        // "invalid" | "in_use" | "os_rejected"
+    pub conflict_with: Option<String>, // Action name or "push_to_talk" when reason is "in_use"
+    pub error: Option<String>,
+}
+
 #[derive(Clone, Serialize, Debug)]
 pub struct DaemonStatusPayload {
-    pub status: String,   // "loading" | "ready" | "error"
+    pub status: String,   // "loading" | "ready" | "error" | "restarting"
     pub message: String,  // User-readable status message
 }
 
@@ -195,6 +398,15 @@ pub struct DownloadProgressPayload {
     pub total: Option<u64>,   // Total bytes
 }
 
+/// Update download progress event payload (`update-download-progress`)
+#[derive(Clone, Serialize, Debug)]
+pub struct UpdateProgressPayload {
+    pub version: String,
+    pub downloaded: u64,          // Bytes downloaded
+    pub total: Option<u64>,       // Size, when the server sends it
+    pub percent: Option<u32>,     // 0-100, with a known size
+}
+
 /// Model loading progress event payload
 #[derive(Clone, Serialize, Debug)]
 pub struct ModelLoadingPayload {
@@ -202,3 +414,164 @@ pub struct ModelLoadingPayload {
     pub status: String,      // "loading" | "loaded" | "skipped"
     pub message: String,     // User-readable message
 }
+
+/// Weighted daemon startup progress event payload (`daemon-progress`)
+#[derive(Clone, Serialize, Debug)]
+pub struct DaemonProgressPayload {
+    pub stage: String,            // "vad_load" | "asr_download" | "asr_load" | "llm" | "complete"
+    pub stage_percent: u32,       // Progress within the stage (0-100)
+    pub percent: u32,             // Overall progress over all planned stages (0-100)
+    pub bytes: Option<u64>,       // Downloaded bytes (download stages)
+    pub total_bytes: Option<u64>,
+}
+
+/// Session title updated event payload
+#[derive(Clone, Serialize, Debug)]
+pub struct SessionTitleUpdatedPayload {
+    pub session_id: String,
+    pub title: String,
+}
+
+/// Failed provider request event payload (`provider-error`)
+#[derive(Clone, Serialize, Debug)]
+pub struct ProviderErrorPayload {
+    pub provider: String,     // Provider name (e.g., "openai", "ollama")
+    pub error: String,        // Error of the failed attempt
+    pub status: Option<u16>,  // HTTP status, None if the request never got a response
+    pub retries: u32,         // Retries made before this attempt
+    pub retrying: bool,       // Another attempt follows (429 / 5xx with retries left)
+}
+
+/// Periodic daemon health check event payload (`daemon-health`)
+#[derive(Clone, Serialize, Debug)]
+pub struct DaemonHealthPayload {
+    pub healthy: bool,
+    pub latency_ms: u64,              // Round trip of the health command
+    pub consecutive_failures: u32,    // Failed checks in a row (0 when healthy)
+    pub error: Option<String>,
+    pub restarting: bool,             // Too many failures: the daemon is being restarted
+    pub next_check_secs: u64,         // Delay before the next check (before jitter)
+}
+
+/// Latest daemon resource sample (`get_daemon_resources`)
+#[derive(Clone, Serialize, Debug)]
+pub struct DaemonResources {
+    pub pid: u32,
+    pub rss_bytes: u64,
+    pub cpu_percent: Option<f64>,     // Since the previous sample (100 = one core); None for the first
+    pub sampled_at: i64,
+    pub memory_limit_mb: u64,         // daemon_memory_limit_mb (0 = no limit)
+    pub restart_pending: bool,        // Over the limit: restarts once in-flight requests finish
+}
+
+/// Outcome of re-running ASR on a journaled recording (`recover_pending_recordings`)
+#[derive(Clone, Serialize, Debug)]
+pub struct RecoveredRecording {
+    pub id: i64,
+    pub created_at: i64,
+    pub text: Option<String>,     // Post-processed transcript (empty if no speech)
+    pub error: Option<String>,    // ASR failed again; the recording stays in the journal
+}
+
+/// ASR confidence and alternative transcripts of a message (`get_message_alternatives`)
+#[derive(Clone, Serialize, Debug)]
+pub struct MessageAlternatives {
+    pub message_id: String,
+    pub confidence: Option<f64>,  // 0-1, None if the model reports none (or a typed message)
+    pub alternatives: Vec<String>, // Post-processed, best first
+}
+
+/// Meeting transcription state (`get_meeting_status`, `meeting-state` event)
+#[derive(Clone, Serialize, Debug)]
+pub struct MeetingStatus {
+    pub active: bool,
+    pub session_id: Option<String>, // Session the transcripts are saved to
+    pub started_at: Option<i64>,
+}
+
+/// Transcript appended to the dictation document (`dictation-append`)
+#[derive(Clone, Serialize, Debug)]
+pub struct DictationAppendPayload {
+    pub text: String,             // The new part
+    pub document: String,         // Everything dictated so far
+}
+
+/// Dictation started or finished (`dictation-state`)
+#[derive(Clone, Serialize, Debug)]
+pub struct DictationState {
+    pub active: bool,
+}
+
+/// Result of one self-test stage (`run_self_test`)
+#[derive(Clone, Serialize, Debug)]
+pub struct SelfTestStage {
+    pub stage: String,            // "daemon", "microphone", "asr", "llm", "tts", "playback"
+    pub passed: bool,
+    pub skipped: bool,            // Not run because an earlier stage it needs failed
+    pub duration_ms: u64,
+    pub detail: Option<String>,   // e.g. the transcript or reply
+    pub error: Option<String>,
+}
+
+/// Per-stage report of `run_self_test`
+#[derive(Clone, Serialize, Debug)]
+pub struct SelfTestReport {
+    pub passed: bool,             // Every stage passed
+    pub stages: Vec<SelfTestStage>,
+    pub total_ms: u64,
+}
+
+/// Microphone could not be opened, or became available again (`mic-conflict`)
+#[derive(Clone, Serialize, Debug)]
+pub struct MicConflictPayload {
+    pub device: Option<String>,   // Name of the failing device
+    pub error: Option<crate::audio::MicError>, // Why it failed (None when resolved)
+    pub message: String,
+    pub retrying: bool,           // Retried while the PTT key is held
+    pub resolved: bool,           // The device freed up and recording started
+}
+
+/// Text-input refusal event payload (`typing-blocked`)
+#[derive(Clone, Serialize, Debug)]
+pub struct TypingBlockedPayload {
+    pub reason: String,           // "secure_field"
+    pub app: Option<String>,      // Focused app, when known
+    pub message: String,
+}
+
+/// Continuous listening pause event payload (`listening-paused`)
+#[derive(Clone, Serialize, Debug, Default, PartialEq)]
+pub struct ListeningPausedPayload {
+    pub paused: bool,
+    pub reason: Option<String>,   // "manual", "dnd", "app", "microphone" or "override" (None when not paused)
+    pub app: Option<String>,      // Watched app that caused the pause
+    pub until: Option<i64>,       // When a manual pause ends (Unix ms; None = until resumed)
+}
+
+/// Synthesized audio of a reply sentence (`ptt-audio-chunk`, `tts-audio-chunk`)
+#[derive(Clone, Serialize, Debug)]
+pub struct AudioChunkPayload {
+    pub audio_path: String,
+    pub text: String,             // Sentence the audio speaks
+}
+
+/// Recording stopped at the time limit (`recording-timeout`)
+#[derive(Clone, Serialize, Debug)]
+pub struct RecordingTimeoutPayload {
+    pub reason: String,           // "max_duration" or "silence"
+}
+
+/// Chunks synthesized so far by export_tts (`tts-export-progress`)
+#[derive(Clone, Serialize, Debug)]
+pub struct TtsExportProgressPayload {
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Offline state change event payload (`connectivity-changed`)
+#[derive(Clone, Serialize, Debug)]
+pub struct ConnectivityPayload {
+    pub offline: bool,        // Remote providers are unavailable (offline_mode or no connection)
+    pub offline_mode: bool,   // The `offline_mode` setting is on
+    pub reachable: bool,      // Result of the last connectivity check
+}
@@ -2,13 +2,16 @@
 
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{CheckMenuItemBuilder, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
     tray::{TrayIconBuilder, TrayIconEvent},
     webview::WebviewWindowBuilder,
-    Emitter, Manager, Runtime,
+    Manager, Monitor, PhysicalPosition, Runtime, WindowEvent,
 };
-use std::sync::atomic::Ordering;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::events::{emit_event, AppEvent};
 
 // macOS: Set activation policy to Regular (shows app in Dock)
 #[cfg(target_os = "macos")]
@@ -53,13 +56,243 @@ pub const OVERLAY_WIDTH: f64 = 140.0;
 pub const OVERLAY_HEIGHT: f64 = 50.0;
 pub const BOTTOM_MARGIN: f64 = 60.0;
 
-/// Calculate PTT overlay window position based on current screen size
+/// Largest size the overlay grows to for live transcription
+const OVERLAY_MAX_WIDTH: f64 = 420.0;
+const OVERLAY_MAX_HEIGHT: f64 = 120.0;
+
+/// How long the final transcript stays on the overlay
+pub const OVERLAY_TRANSCRIPT_LINGER: Duration = Duration::from_millis(1500);
+
+/// Config key pinning the overlay to a display by name (unset = follow the cursor)
+pub const OVERLAY_DISPLAY_KEY: &str = "overlay_display";
+
+/// Config key holding dragged overlay positions, keyed by display layout
+pub const OVERLAY_POSITIONS_KEY: &str = "overlay_positions";
+
+/// Wait this long after the last move event before saving a dragged position
+const OVERLAY_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Move events this close to the last programmatic position are not user drags
+/// (some platforms round the position through logical coordinates)
+const OVERLAY_MOVE_TOLERANCE: i32 = 2;
+
+/// Position the overlay was last moved to by the app itself
+static OVERLAY_SET_POSITION: std::sync::Mutex<Option<PhysicalPosition<i32>>> = std::sync::Mutex::new(None);
+
+/// Incremented on every user move; a pending save only runs if it is still the latest
+static OVERLAY_MOVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Display info for the overlay placement setting
+#[derive(Debug, Clone, Serialize)]
+pub struct DisplayInfo {
+    /// Display name (persisted in config)
+    pub name: String,
+    /// Physical resolution
+    pub width: u32,
+    pub height: u32,
+    /// Whether this is the primary display
+    pub is_primary: bool,
+}
+
+/// List connected displays
+pub fn list_displays<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<Vec<DisplayInfo>, String> {
+    let primary_name = app.primary_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+    let monitors = app.available_monitors()
+        .map_err(|e| format!("Failed to enumerate displays: {}", e))?;
+
+    Ok(monitors
+        .iter()
+        .filter_map(|monitor| {
+            let name = monitor.name()?.clone();
+            Some(DisplayInfo {
+                is_primary: primary_name.as_ref() == Some(&name),
+                name,
+                width: monitor.size().width,
+                height: monitor.size().height,
+            })
+        })
+        .collect())
+}
+
+/// Stable key for the current display arrangement
+///
+/// FNV-1a hash of every display's name, origin, size and scale, so a saved
+/// position is only reused with the same set of displays.
+fn monitor_layout_key<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<String, String> {
+    let monitors = app.available_monitors()
+        .map_err(|e| format!("Failed to enumerate displays: {}", e))?;
+
+    let mut descriptors: Vec<String> = monitors
+        .iter()
+        .map(|m| {
+            format!(
+                "{}@{},{}:{}x{}*{}",
+                m.name().map(String::as_str).unwrap_or(""),
+                m.position().x,
+                m.position().y,
+                m.size().width,
+                m.size().height,
+                m.scale_factor()
+            )
+        })
+        .collect();
+    descriptors.sort();
+
+    let hash = descriptors
+        .join(";")
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    Ok(format!("{:016x}", hash))
+}
+
+/// Position the user dragged the overlay to for the current display layout
+fn saved_overlay_position<R: Runtime>(app: &tauri::AppHandle<R>) -> Option<PhysicalPosition<i32>> {
+    let layout = monitor_layout_key(app).ok()?;
+    let saved = crate::config::read_config_value(OVERLAY_POSITIONS_KEY)?;
+    let entry = saved.get(&layout)?;
+    Some(PhysicalPosition::new(
+        entry.get("x")?.as_i64()? as i32,
+        entry.get("y")?.as_i64()? as i32,
+    ))
+}
+
+/// Save (or with `None`, forget) the dragged overlay position for the current display layout
+fn store_overlay_position<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    position: Option<PhysicalPosition<i32>>,
+) -> Result<(), String> {
+    let layout = monitor_layout_key(app)?;
+    let mut saved = crate::config::read_config_value(OVERLAY_POSITIONS_KEY)
+        .filter(|v| v.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    if let Some(map) = saved.as_object_mut() {
+        match position {
+            Some(position) => {
+                map.insert(layout, serde_json::json!({"x": position.x, "y": position.y}));
+            }
+            None => {
+                map.remove(&layout);
+            }
+        }
+    }
+    crate::config::write_config_value(OVERLAY_POSITIONS_KEY, saved)
+}
+
+/// Move the overlay, remembering that this move was not a user drag
+fn move_overlay<R: Runtime>(overlay: &tauri::WebviewWindow<R>, position: PhysicalPosition<i32>) {
+    if let Ok(mut last) = OVERLAY_SET_POSITION.lock() {
+        *last = Some(position);
+    }
+    let _ = overlay.set_position(tauri::Position::Physical(position));
+}
+
+/// Handle an overlay move event: save it (debounced) if the user dragged the window
+fn handle_overlay_moved<R: Runtime>(app: &tauri::AppHandle<R>, position: PhysicalPosition<i32>) {
+    let programmatic = OVERLAY_SET_POSITION.lock().ok().and_then(|last| *last).is_some_and(|last| {
+        (last.x - position.x).abs() <= OVERLAY_MOVE_TOLERANCE
+            && (last.y - position.y).abs() <= OVERLAY_MOVE_TOLERANCE
+    });
+    if programmatic {
+        return;
+    }
+
+    // Save once the drag has settled
+    let generation = OVERLAY_MOVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(OVERLAY_SAVE_DELAY).await;
+        if OVERLAY_MOVE_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if let Err(e) = store_overlay_position(&app, Some(position)) {
+            eprintln!("Failed to save overlay position: {}", e);
+        }
+    });
+}
+
+/// Resize the overlay to fit its content, keeping its bottom center in place
+pub fn resize_overlay<R: Runtime>(app: &tauri::AppHandle<R>, width: f64, height: f64) -> Result<(), String> {
+    let overlay = app.get_webview_window("ptt-overlay")
+        .ok_or_else(|| "PTT overlay not found".to_string())?;
+    let width = width.clamp(OVERLAY_WIDTH, OVERLAY_MAX_WIDTH);
+    let height = height.clamp(OVERLAY_HEIGHT, OVERLAY_MAX_HEIGHT);
+
+    let scale_factor = overlay.scale_factor().map_err(|e| e.to_string())?;
+    let position = overlay.outer_position().map_err(|e| e.to_string())?;
+    let size = overlay.outer_size().map_err(|e| e.to_string())?;
+
+    let center_x = position.x + size.width as i32 / 2;
+    let bottom_y = position.y + size.height as i32;
+    let new_width = (width * scale_factor).round() as i32;
+    let new_height = (height * scale_factor).round() as i32;
+
+    overlay.set_size(tauri::Size::Logical(tauri::LogicalSize { width, height }))
+        .map_err(|e| e.to_string())?;
+    move_overlay(&overlay, PhysicalPosition::new(center_x - new_width / 2, bottom_y - new_height));
+    Ok(())
+}
+
+/// Forget the dragged overlay position for the current display layout and move it back
+pub fn reset_overlay_position<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
+    store_overlay_position(app, None)?;
+
+    if let Some(overlay) = app.get_webview_window("ptt-overlay") {
+        let position = calculate_overlay_position(app).map_err(|e| e.to_string())?;
+        move_overlay(&overlay, position);
+    }
+    Ok(())
+}
+
+/// Pick the display the overlay should appear on
+///
+/// The pinned display from config if it is connected, otherwise the display
+/// under the cursor, then the focused main window's display, then the primary.
+fn overlay_monitor<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<Monitor, Box<dyn std::error::Error>> {
+    let pinned = crate::config::read_config_value(OVERLAY_DISPLAY_KEY)
+        .and_then(|v| v.as_str().map(str::to_string));
+    if let Some(name) = pinned {
+        if let Some(monitor) = app.available_monitors()?.into_iter().find(|m| m.name() == Some(&name)) {
+            return Ok(monitor);
+        }
+    }
+
+    if let Ok(cursor) = app.cursor_position() {
+        if let Ok(Some(monitor)) = app.monitor_from_point(cursor.x, cursor.y) {
+            return Ok(monitor);
+        }
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_focused().unwrap_or(false) {
+            if let Ok(Some(monitor)) = window.current_monitor() {
+                return Ok(monitor);
+            }
+        }
+    }
+
+    app.primary_monitor()?
+        .ok_or_else(|| Box::<dyn std::error::Error>::from("No primary monitor found"))
+}
+
+/// Calculate PTT overlay window position
+///
+/// The position the user dragged it to for this display layout, otherwise
+/// the bottom center of the chosen display.
 pub fn calculate_overlay_position<R: Runtime>(
     app: &tauri::AppHandle<R>,
-) -> Result<(f64, f64), Box<dyn std::error::Error>> {
-    let monitor = app.primary_monitor()?
-        .ok_or_else(|| Box::<dyn std::error::Error>::from("No primary monitor found"))?;
+) -> Result<PhysicalPosition<i32>, Box<dyn std::error::Error>> {
+    if let Some(position) = saved_overlay_position(app) {
+        return Ok(position);
+    }
+
+    let monitor = overlay_monitor(app)?;
     let screen_size = monitor.size();
+    let screen_origin = monitor.position();
     let scale_factor = monitor.scale_factor();
 
     // Validate scale factor
@@ -67,35 +300,66 @@ pub fn calculate_overlay_position<R: Runtime>(
         return Err(format!("Invalid scale factor: {}", scale_factor).into());
     }
 
-    // Calculate scaled screen dimensions
-    let scaled_width = screen_size.width as f64 / scale_factor;
-    let scaled_height = screen_size.height as f64 / scale_factor;
+    // Overlay size in this display's physical pixels
+    let overlay_width = OVERLAY_WIDTH * scale_factor;
+    let overlay_height = OVERLAY_HEIGHT * scale_factor;
+    let screen_width = screen_size.width as f64;
+    let screen_height = screen_size.height as f64;
 
     // Calculate bottom center position with boundary validation
-    let x = (scaled_width / 2.0 - OVERLAY_WIDTH / 2.0).max(0.0);
-    let y = (scaled_height - OVERLAY_HEIGHT - BOTTOM_MARGIN).max(0.0);
+    let x = (screen_width / 2.0 - overlay_width / 2.0).max(0.0);
+    let y = (screen_height - overlay_height - BOTTOM_MARGIN * scale_factor).max(0.0);
 
     // Final boundary check
-    if x + OVERLAY_WIDTH > scaled_width || y + OVERLAY_HEIGHT > scaled_height {
+    if x + overlay_width > screen_width || y + overlay_height > screen_height {
         eprintln!("Warning: PTT overlay position may exceed screen bounds");
     }
 
-    Ok((x, y))
+    Ok(PhysicalPosition::new(
+        screen_origin.x + x.round() as i32,
+        screen_origin.y + y.round() as i32,
+    ))
+}
+
+/// Move the overlay to the current display, then show it
+pub fn show_overlay<R: Runtime>(app: &tauri::AppHandle<R>, overlay: &tauri::WebviewWindow<R>) {
+    // Recalculate position every time (the cursor or screen config may have changed)
+    if let Ok(position) = calculate_overlay_position(app) {
+        move_overlay(overlay, position);
+    }
+    let _ = overlay.show();
+}
+
+/// Hide the overlay after `delay`, unless a new recording has started by then
+pub fn hide_overlay_after(app: &tauri::AppHandle, delay: Duration) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        let recording = matches!(
+            crate::daemon::APP_STATUS.current(),
+            crate::types::AppStatus::Recording | crate::types::AppStatus::Listening
+        );
+        if recording {
+            return;
+        }
+        if let Some(overlay) = app.get_webview_window("ptt-overlay") {
+            let _ = overlay.set_ignore_cursor_events(true);
+            let _ = overlay.hide();
+            emit_event(&app, AppEvent::PttState("idle".to_string()));
+        }
+    });
 }
 
 /// Create the PTT overlay floating window
 pub fn create_ptt_overlay<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), Box<dyn std::error::Error>> {
-    let (x, y) = calculate_overlay_position(app)?;
-
     // Create PTT floating window (transparent window)
-    let _overlay = WebviewWindowBuilder::new(
+    let overlay = WebviewWindowBuilder::new(
         app,
         "ptt-overlay",
         tauri::WebviewUrl::App("ptt-overlay.html".into())
     )
     .title("PTT Status")
     .inner_size(OVERLAY_WIDTH, OVERLAY_HEIGHT)
-    .position(x, y)
     .always_on_top(true)
     .decorations(false)
     .resizable(false)
@@ -106,6 +370,18 @@ pub fn create_ptt_overlay<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), B
     .shadow(false)
     .build()?;
 
+    // Restore the dragged position for this display layout (or the default placement)
+    let position = calculate_overlay_position(app)?;
+    move_overlay(&overlay, position);
+
+    // Remember where the user drags the overlay to
+    let app_handle = app.clone();
+    overlay.on_window_event(move |event| {
+        if let WindowEvent::Moved(position) = event {
+            handle_overlay_moved(&app_handle, *position);
+        }
+    });
+
     Ok(())
 }
 
@@ -119,29 +395,17 @@ pub fn create_ptt_overlay<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), B
 /// and the floating overlay window. It also controls the visibility of the overlay
 /// window based on the state.
 pub fn emit_ptt_state(app_handle: &tauri::AppHandle, state: &str) {
-    // Send to main window
-    if let Some(main_window) = app_handle.get_webview_window("main") {
-        let _ = main_window.emit("ptt-state", state);
-    }
-    // Send to floating window
+    emit_event(app_handle, AppEvent::PttState(state.to_string()));
     if let Some(overlay) = app_handle.get_webview_window("ptt-overlay") {
-        let _ = overlay.emit("ptt-state", state);
         // Control floating window visibility
         match state {
-            "listening" | "detected" | "recording" | "processing" => {
+            "listening" | "detected" | "recording" | "latched" | "processing" => {
                 // Don't show overlay if PTT processing (ASR/LLM/TTS) is in progress
                 if PTT_PROCESSING.load(Ordering::SeqCst) {
                     return;
                 }
-                // Recalculate position before showing (in case screen config changed)
-                match calculate_overlay_position(app_handle) {
-                    Ok((x, y)) => {
-                        let _ = overlay.set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
-                    }
-                    Err(_) => {}
-                }
                 let _ = overlay.set_ignore_cursor_events(false);
-                let _ = overlay.show();
+                show_overlay(app_handle, &overlay);
             }
             "idle" | "error" => {
                 let _ = overlay.set_ignore_cursor_events(true);
@@ -157,17 +421,12 @@ pub fn emit_ptt_state(app_handle: &tauri::AppHandle, state: &str) {
 /// This is a simplified version of `emit_ptt_state` for use in global shortcut
 /// callbacks where the full state checking logic is not needed.
 pub fn emit_ptt_state_static(app_handle: &tauri::AppHandle, state: &str) {
-    // Send to main window
-    if let Some(main_window) = app_handle.get_webview_window("main") {
-        let _ = main_window.emit("ptt-state", state);
-    }
-    // Send to floating window
+    emit_event(app_handle, AppEvent::PttState(state.to_string()));
     if let Some(overlay) = app_handle.get_webview_window("ptt-overlay") {
-        let _ = overlay.emit("ptt-state", state);
         // Control floating window visibility
         match state {
-            "listening" | "detected" | "recording" | "processing" => {
-                let _ = overlay.show();
+            "listening" | "detected" | "recording" | "latched" | "processing" => {
+                show_overlay(app_handle, &overlay);
             }
             "idle" | "error" => {
                 let _ = overlay.hide();
@@ -181,30 +440,6 @@ pub fn emit_ptt_state_static(app_handle: &tauri::AppHandle, state: &str) {
 // Config & Language
 // ============================================================================
 
-/// Get the config directory path
-fn get_config_dir() -> Result<PathBuf, String> {
-    #[cfg(target_os = "macos")]
-    {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        return Ok(PathBuf::from(home).join("Library/Application Support/com.speekium.app"));
-    }
-    #[cfg(target_os = "windows")]
-    {
-        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".to_string());
-        return Ok(PathBuf::from(appdata).join("com.speekium.app"));
-    }
-    #[cfg(target_os = "linux")]
-    {
-        let xdg = std::env::var("XDG_CONFIG_HOME")
-            .unwrap_or_else(|_| format!("{}/.config", std::env::var("HOME").unwrap_or_else(|_| ".".to_string())));
-        return Ok(PathBuf::from(xdg).join("com.speekium.app"));
-    }
-    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-    {
-        Err("Unsupported platform".to_string())
-    }
-}
-
 /// Get system language code
 /// Returns "en" for English locales, "zh" for Chinese locales, default to "en"
 fn get_system_language() -> &'static str {
@@ -236,65 +471,29 @@ fn get_system_language() -> &'static str {
 /// Read language from config file
 /// Returns the language from config, or system language if config doesn't exist
 pub fn get_language_from_config() -> String {
-    let config_dir = match get_config_dir() {
-        Ok(dir) => dir,
-        Err(_) => return get_system_language().to_string(),
+    let Ok(config_path) = crate::config::get_config_path() else {
+        return get_system_language().to_string();
     };
 
-    let config_path = config_dir.join("config.json");
-
     if !config_path.exists() {
         // Config doesn't exist, create it with system language
-        let _ = std::fs::create_dir_all(&config_dir);
-
-        let default_config = serde_json::json!({
-            "language": get_system_language()
-        });
-
-        if let Ok(json) = serde_json::to_string_pretty(&default_config) {
-            let _ = std::fs::write(&config_path, json);
-        }
+        let _ = crate::config::write_config_value("language", serde_json::json!(get_system_language()));
         return get_system_language().to_string();
     }
 
-    // Read existing config
-    match std::fs::read_to_string(&config_path) {
-        Ok(content) => {
-            if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
-                if let Some(lang) = config.get("language").and_then(|v| v.as_str()) {
-                    return lang.to_string();
-                }
-            }
-            // If language field doesn't exist, add it
-            get_system_language().to_string()
-        }
-        Err(_) => get_system_language().to_string(),
+    // Bring an older config file up to the current schema version
+    if let Err(e) = crate::config::migrate_config_file() {
+        eprintln!("⚠️ Config migration failed: {}", e);
     }
+
+    crate::config::read_config_value("language")
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| get_system_language().to_string())
 }
 
 /// Write language to config file
 pub fn write_language_to_config(language: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let config_dir = get_config_dir()?;
-
-    let config_path = config_dir.join("config.json");
-
-    // Ensure config directory exists
-    std::fs::create_dir_all(&config_dir)?;
-
-    // Read existing config or create default
-    let mut config = if config_path.exists() {
-        let config_content = std::fs::read_to_string(&config_path)?;
-        serde_json::from_str(&config_content).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-
-    // Update language
-    config["language"] = serde_json::json!(language);
-
-    // Write back
-    let json = serde_json::to_string_pretty(&config)?;
-    std::fs::write(&config_path, json)?;
+    crate::config::write_config_value("language", serde_json::json!(language))?;
     Ok(())
 }
 
@@ -362,6 +561,18 @@ pub fn get_daemon_message(message_key: &str) -> String {
         ("daemon_exited", "en") => "Voice service exited unexpectedly".to_string(),
         ("daemon_exited", _) => "语音服务意外退出".to_string(),
 
+        ("sidecar_integrity_failed", "en") => "Voice service files are damaged or from another version, please reinstall Speekium".to_string(),
+        ("sidecar_integrity_failed", _) => "语音服务文件已损坏或版本不符，请重新安装 Speekium".to_string(),
+
+        ("protocol_mismatch", "en") => "Voice service version does not match the app, please reinstall Speekium".to_string(),
+        ("protocol_mismatch", _) => "语音服务版本与应用不匹配，请重新安装 Speekium".to_string(),
+
+        ("restarting", "en") => "Voice service stopped unexpectedly, restarting...".to_string(),
+        ("restarting", _) => "语音服务意外停止，正在重启...".to_string(),
+
+        ("restart_failed", "en") => "Voice service could not be restarted. Please restart the app".to_string(),
+        ("restart_failed", _) => "语音服务无法重启，请重新启动应用".to_string(),
+
         ("read_error", "en") => "Failed to read output".to_string(),
         ("read_error", _) => "读取输出失败".to_string(),
 
@@ -376,21 +587,299 @@ pub fn get_daemon_message(message_key: &str) -> String {
     }
 }
 
+/// Localized tray menu texts
+struct TrayMenuTexts {
+    show: &'static str,
+    hide: &'static str,
+    to_text_input: &'static str,
+    to_conversation: &'static str,
+    to_continuous: &'static str,
+    to_push_to_talk: &'static str,
+    copy_transcript: &'static str,
+    paste_transcript: &'static str,
+    pause_listening: &'static str,
+    resume_listening: &'static str,
+    history: &'static str,
+    pinned: &'static str,
+    recent_sessions: &'static str,
+    no_sessions: &'static str,
+    persona: &'static str,
+    default_persona: &'static str,
+    quit: &'static str,
+    tooltip: &'static str,
+    offline: &'static str,
+    offline_tooltip: &'static str,
+}
+
 /// Get localized tray menu texts
-fn get_tray_menu_texts(language: &str) -> (&'static str, &'static str, &'static str, &'static str) {
+fn get_tray_menu_texts(language: &str) -> TrayMenuTexts {
     match language {
-        "en" => (
-            "Show Window",
-            "Hide Window",
-            "Quit",
-            "Speekium"
-        ),
-        _ => (
-            "显示窗口",
-            "隐藏窗口",
-            "退出",
-            "Speekium"
-        ),
+        "en" => TrayMenuTexts {
+            show: "Show Window",
+            hide: "Hide Window",
+            to_text_input: "Switch to Text Input mode",
+            to_conversation: "Switch to Conversation mode",
+            to_continuous: "Switch to Continuous recording",
+            to_push_to_talk: "Switch to Push-to-Talk recording",
+            copy_transcript: "Copy last transcript",
+            paste_transcript: "Paste last transcript",
+            pause_listening: "Pause listening for 30 min",
+            resume_listening: "Resume listening",
+            history: "History...",
+            pinned: "Pinned Answers...",
+            recent_sessions: "Recent Sessions",
+            no_sessions: "No sessions yet",
+            persona: "Persona",
+            default_persona: "Default",
+            quit: "Quit",
+            tooltip: "Speekium",
+            offline: "Offline",
+            offline_tooltip: "Speekium (offline)",
+        },
+        _ => TrayMenuTexts {
+            show: "显示窗口",
+            hide: "隐藏窗口",
+            to_text_input: "切换到文字输入模式",
+            to_conversation: "切换到对话模式",
+            to_continuous: "切换到连续录音",
+            to_push_to_talk: "切换到按键录音",
+            copy_transcript: "复制最近的识别结果",
+            paste_transcript: "输入最近的识别结果",
+            pause_listening: "暂停监听 30 分钟",
+            resume_listening: "恢复监听",
+            history: "历史记录...",
+            pinned: "置顶回答...",
+            recent_sessions: "最近会话",
+            no_sessions: "暂无会话",
+            persona: "角色",
+            default_persona: "默认",
+            quit: "退出",
+            tooltip: "Speekium",
+            offline: "离线",
+            offline_tooltip: "Speekium（离线）",
+        },
+    }
+}
+
+/// Length of the pause started from the tray
+const TRAY_PAUSE_DURATION: Duration = Duration::from_secs(30 * 60);
+
+/// Number of sessions listed in the tray "Recent Sessions" submenu
+const TRAY_RECENT_SESSIONS: i32 = 5;
+
+/// Menu id prefix for tray session items ("session:<id>")
+const TRAY_SESSION_PREFIX: &str = "session:";
+
+/// Menu id prefix for tray persona items ("persona:<id>", "persona:default")
+const TRAY_PERSONA_PREFIX: &str = "persona:";
+
+/// Build the tray menu for the current language, modes, persona and recent sessions
+fn build_tray_menu<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    texts: &TrayMenuTexts,
+) -> tauri::Result<tauri::menu::Menu<R>> {
+    let work_mode = *crate::daemon::WORK_MODE.lock().unwrap();
+    let work_mode_text = match work_mode {
+        crate::types::WorkMode::Conversation => texts.to_text_input,
+        crate::types::WorkMode::TextInput => texts.to_conversation,
+    };
+    let recording_mode = *crate::daemon::RECORDING_MODE.lock().unwrap();
+    let recording_mode_text = match recording_mode {
+        crate::types::RecordingMode::PushToTalk => texts.to_continuous,
+        crate::types::RecordingMode::Continuous | crate::types::RecordingMode::Meeting => texts.to_push_to_talk,
+    };
+    let has_transcript = crate::daemon::LAST_TRANSCRIPT.lock().unwrap().is_some();
+    let pause_item = if crate::focus_pause::is_paused() {
+        MenuItemBuilder::new(texts.resume_listening).id("resume_listening").build(app)?
+    } else {
+        MenuItemBuilder::new(texts.pause_listening).id("pause_listening").build(app)?
+    };
+
+    // Recent sessions (database may not be ready yet during early startup)
+    let sessions = app
+        .try_state::<crate::state::AppState>()
+        .and_then(|state| state.db.list_sessions(1, TRAY_RECENT_SESSIONS).ok())
+        .map(|result| result.items)
+        .unwrap_or_default();
+
+    let mut sessions_menu = SubmenuBuilder::new(app, texts.recent_sessions);
+    if sessions.is_empty() {
+        sessions_menu = sessions_menu.item(
+            &MenuItemBuilder::new(texts.no_sessions).id("no_sessions").enabled(false).build(app)?,
+        );
+    }
+    for session in &sessions {
+        sessions_menu = sessions_menu.item(
+            &MenuItemBuilder::new(&session.title)
+                .id(format!("{}{}", TRAY_SESSION_PREFIX, session.id))
+                .build(app)?,
+        );
+    }
+
+    // Personas, with the active one checked
+    let personas = app
+        .try_state::<crate::state::AppState>()
+        .and_then(|state| state.db.list_personas().ok())
+        .unwrap_or_default();
+    let active_persona = crate::personas::active_persona(app);
+    let active_id = active_persona.as_ref().map(|p| p.id);
+    let persona_title = match &active_persona {
+        Some(persona) => format!("{}: {}", texts.persona, persona.name),
+        None => format!("{}: {}", texts.persona, texts.default_persona),
+    };
+    let mut persona_menu = SubmenuBuilder::new(app, persona_title).item(
+        &CheckMenuItemBuilder::new(texts.default_persona)
+            .id(format!("{}default", TRAY_PERSONA_PREFIX))
+            .checked(active_id.is_none())
+            .build(app)?,
+    );
+    for persona in &personas {
+        persona_menu = persona_menu.item(
+            &CheckMenuItemBuilder::new(&persona.name)
+                .id(format!("{}{}", TRAY_PERSONA_PREFIX, persona.id))
+                .checked(active_id == Some(persona.id))
+                .build(app)?,
+        );
+    }
+
+    let mut menu = MenuBuilder::new(app);
+    if crate::network::offline_state() {
+        menu = menu
+            .item(&MenuItemBuilder::new(format!("● {}", texts.offline)).id("offline").enabled(false).build(app)?)
+            .separator();
+    }
+    menu
+        .item(&MenuItemBuilder::new(texts.show).id("show").build(app)?)
+        .item(&MenuItemBuilder::new(texts.hide).id("hide").build(app)?)
+        .separator()
+        .item(&MenuItemBuilder::new(work_mode_text).id("toggle_work_mode").build(app)?)
+        .item(&MenuItemBuilder::new(recording_mode_text).id("toggle_recording_mode").build(app)?)
+        .item(&pause_item)
+        .item(
+            &MenuItemBuilder::new(texts.copy_transcript)
+                .id("copy_transcript")
+                .enabled(has_transcript)
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::new(texts.paste_transcript)
+                .id("paste_transcript")
+                .enabled(has_transcript)
+                .build(app)?,
+        )
+        .item(&persona_menu.build()?)
+        .separator()
+        .item(&MenuItemBuilder::new(texts.history).id("history").build(app)?)
+        .item(&MenuItemBuilder::new(texts.pinned).id("pinned").build(app)?)
+        .item(&sessions_menu.build()?)
+        .separator()
+        .item(&MenuItemBuilder::new(texts.quit).id("quit").build(app)?)
+        .build()
+}
+
+/// Show and focus the main window
+pub fn show_main_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    #[cfg(target_os = "macos")]
+    {
+        set_activation_policy_regular();
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Label of the standalone history window
+pub const HISTORY_WINDOW_LABEL: &str = "history";
+const HISTORY_WINDOW_WIDTH: f64 = 420.0;
+const HISTORY_WINDOW_HEIGHT: f64 = 640.0;
+
+/// Show and focus the history window, creating it on first use
+///
+/// The window hosts the session browser (`index.html?window=history`) on its
+/// own, so past answers stay readable while dictating into another app.
+/// Closing it only hides it (see the window event handler in app.rs).
+pub fn show_history_window<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
+    let window = match app.get_webview_window(HISTORY_WINDOW_LABEL) {
+        Some(window) => window,
+        None => WebviewWindowBuilder::new(
+            app,
+            HISTORY_WINDOW_LABEL,
+            tauri::WebviewUrl::App("index.html?window=history".into()),
+        )
+        .title("Speekium History")
+        .inner_size(HISTORY_WINDOW_WIDTH, HISTORY_WINDOW_HEIGHT)
+        .min_inner_size(320.0, 400.0)
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Failed to create history window: {}", e))?,
+    };
+
+    window.show().map_err(|e| format!("Failed to show history window: {}", e))?;
+    let _ = window.set_focus();
+    // The page reloads the session list when shown
+    emit_event(app, AppEvent::HistoryWindowShown);
+    Ok(())
+}
+
+/// Hide the history window
+pub fn hide_history_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(window) = app.get_webview_window(HISTORY_WINDOW_LABEL) {
+        let _ = window.hide();
+    }
+}
+
+/// Show the history window, or hide it if it is already showing
+pub fn toggle_history_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let visible = app
+        .get_webview_window(HISTORY_WINDOW_LABEL)
+        .is_some_and(|window| window.is_visible().unwrap_or(false));
+    if visible {
+        hide_history_window(app);
+    } else if let Err(e) = show_history_window(app) {
+        eprintln!("{}", e);
+    }
+}
+
+/// Label of the pinned answers window
+pub const PINNED_WINDOW_LABEL: &str = "pinned";
+const PINNED_WINDOW_WIDTH: f64 = 360.0;
+const PINNED_WINDOW_HEIGHT: f64 = 420.0;
+
+/// Show the pinned answers window, creating it on first use
+///
+/// A small always-on-top window (`index.html?window=pinned`) listing pinned
+/// messages, so a generated snippet stays visible while working in another
+/// app. It is shown without taking focus from that app.
+pub fn show_pinned_window<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
+    let window = match app.get_webview_window(PINNED_WINDOW_LABEL) {
+        Some(window) => window,
+        None => WebviewWindowBuilder::new(
+            app,
+            PINNED_WINDOW_LABEL,
+            tauri::WebviewUrl::App("index.html?window=pinned".into()),
+        )
+        .title("Speekium Pinned")
+        .inner_size(PINNED_WINDOW_WIDTH, PINNED_WINDOW_HEIGHT)
+        .min_inner_size(240.0, 160.0)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true)
+        .focused(false)
+        .visible(false)
+        .build()
+        .map_err(|e| format!("Failed to create pinned window: {}", e))?,
+    };
+
+    window.show().map_err(|e| format!("Failed to show pinned window: {}", e))?;
+    // The page reloads the pinned list when shown
+    emit_event(app, AppEvent::PinnedWindowShown);
+    Ok(())
+}
+
+/// Hide the pinned answers window
+pub fn hide_pinned_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(window) = app.get_webview_window(PINNED_WINDOW_LABEL) {
+        let _ = window.hide();
     }
 }
 
@@ -399,6 +888,12 @@ fn get_tray_menu_texts(language: &str) -> (&'static str, &'static str, &'static
 /// This creates a tray icon in the system menu bar/dock with options to:
 /// - Show the main window
 /// - Hide the main window
+/// - Toggle the work mode and recording mode
+/// - Pause continuous listening for 30 minutes, or resume it
+/// - Copy the last transcript to the clipboard
+/// - Open the history window and the pinned answers window
+/// - Switch the assistant persona
+/// - Open one of the most recent sessions
 /// - Quit the application
 ///
 /// # Arguments
@@ -410,7 +905,7 @@ pub fn create_tray<R: Runtime, F: Fn() + Send + Sync + 'static>(
 ) -> tauri::Result<()> {
     // Read language from config (creates config with system language if not exists)
     let language = get_language_from_config();
-    let (show_text, hide_text, quit_text, tooltip_text) = get_tray_menu_texts(&language);
+    let texts = get_tray_menu_texts(&language);
 
     // Store cleanup function globally
     *TRAY_CLEANUP.lock().unwrap() = Some(Box::new(move || {
@@ -418,12 +913,7 @@ pub fn create_tray<R: Runtime, F: Fn() + Send + Sync + 'static>(
     }));
 
     // Build menu with localized texts
-    let menu = MenuBuilder::new(app)
-        .item(&MenuItemBuilder::new(show_text).id("show").build(app)?)
-        .item(&MenuItemBuilder::new(hide_text).id("hide").build(app)?)
-        .separator()
-        .item(&MenuItemBuilder::new(quit_text).id("quit").build(app)?)
-        .build()?;
+    let menu = build_tray_menu(app, &texts)?;
 
     // Load tray icon (template icon for macOS menu bar)
     let icon_bytes = include_bytes!("../icons/tray-template.png");
@@ -438,17 +928,10 @@ pub fn create_tray<R: Runtime, F: Fn() + Send + Sync + 'static>(
         .menu(&menu)
         .icon(tray_icon)
         .icon_as_template(true)
-        .tooltip(tooltip_text)
+        .tooltip(texts.tooltip)
         .on_menu_event(move |app, event| match event.id().as_ref() {
             "show" => {
-                #[cfg(target_os = "macos")]
-                {
-                    set_activation_policy_regular();
-                }
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+                show_main_window(app);
             }
             "hide" => {
                 if let Some(window) = app.get_webview_window("main") {
@@ -460,6 +943,39 @@ pub fn create_tray<R: Runtime, F: Fn() + Send + Sync + 'static>(
                     set_activation_policy_accessory();
                 }
             }
+            "toggle_work_mode" => {
+                crate::shortcuts::ShortcutAction::SwitchWorkMode.run(app);
+            }
+            "toggle_recording_mode" => {
+                crate::shortcuts::ShortcutAction::SwitchRecordingMode.run(app);
+            }
+            "pause_listening" => {
+                tauri::async_runtime::spawn(crate::focus_pause::pause_for(Some(TRAY_PAUSE_DURATION)));
+            }
+            "resume_listening" => {
+                tauri::async_runtime::spawn(crate::focus_pause::resume());
+            }
+            "copy_transcript" => {
+                let transcript = crate::daemon::LAST_TRANSCRIPT.lock().unwrap().clone();
+                if let Some(text) = transcript {
+                    if let Err(e) = crate::platform::copy_text(&text) {
+                        eprintln!("Failed to copy transcript: {}", e);
+                    }
+                }
+            }
+            "paste_transcript" => {
+                crate::shortcuts::ShortcutAction::PasteTranscript.run(app);
+            }
+            "history" => {
+                if let Err(e) = show_history_window(app) {
+                    eprintln!("{}", e);
+                }
+            }
+            "pinned" => {
+                if let Err(e) = show_pinned_window(app) {
+                    eprintln!("{}", e);
+                }
+            }
             "quit" => {
                 // Use global cleanup function
                 if let Some(cleanup) = TRAY_CLEANUP.lock().unwrap().as_ref() {
@@ -467,7 +983,17 @@ pub fn create_tray<R: Runtime, F: Fn() + Send + Sync + 'static>(
                 }
                 app.exit(0);
             }
-            _ => {}
+            id => {
+                if let Some(session_id) = id.strip_prefix(TRAY_SESSION_PREFIX) {
+                    show_main_window(app);
+                    emit_event(app, AppEvent::OpenSession(session_id.to_string()));
+                } else if let Some(persona_id) = id.strip_prefix(TRAY_PERSONA_PREFIX) {
+                    // "default" (or anything unparsable) switches back to the defaults
+                    if let Err(e) = crate::personas::switch_persona(app, persona_id.parse().ok()) {
+                        eprintln!("Failed to switch persona: {}", e);
+                    }
+                }
+            }
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click { button, .. } = event {
@@ -500,29 +1026,26 @@ pub fn create_tray<R: Runtime, F: Fn() + Send + Sync + 'static>(
     Ok(())
 }
 
-/// Update the tray menu with new language
-/// This can be called when the user changes the language setting
-pub fn update_tray_menu(app: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+/// Rebuild the tray menu
+///
+/// Called when the language changes, when the work or recording mode changes,
+/// when the last transcript or session list changes, when listening is paused
+/// or resumed, and when going offline or back online.
+pub fn update_tray_menu<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), Box<dyn std::error::Error>> {
     // Read current language from config
     let language = get_language_from_config();
-    let (show_text, hide_text, quit_text, tooltip_text) = get_tray_menu_texts(&language);
+    let texts = get_tray_menu_texts(&language);
 
-    eprintln!("Updating tray menu with language: {}", language);
-
-    // Build new menu with localized texts
-    let menu = MenuBuilder::new(app)
-        .item(&MenuItemBuilder::new(show_text).id("show").build(app)?)
-        .item(&MenuItemBuilder::new(hide_text).id("hide").build(app)?)
-        .separator()
-        .item(&MenuItemBuilder::new(quit_text).id("quit").build(app)?)
-        .build()?;
+    // Build new menu with localized texts and current state
+    let menu = build_tray_menu(app, &texts)?;
 
     // Get the tray by its ID and update menu
     if let Some(tray) = app.tray_by_id("main") {
-        eprintln!("Found tray, updating menu");
+        let offline = crate::network::offline_state();
         tray.set_menu(Some(menu))?;
-        tray.set_tooltip(Some(tooltip_text))?;
-        eprintln!("Tray menu updated successfully");
+        tray.set_tooltip(Some(if offline { texts.offline_tooltip } else { texts.tooltip }))?;
+        // Offline badge next to the icon (macOS menu bar / Linux panel)
+        tray.set_title(offline.then_some(texts.offline))?;
     } else {
         eprintln!("WARNING: Tray with ID 'main' not found!");
     }
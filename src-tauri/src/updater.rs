@@ -0,0 +1,250 @@
+// ============================================================================
+// Updater Module - In-App Updates from a Release Channel
+// ============================================================================
+//
+// `updater.endpoint` is the URL of an update manifest, with `{channel}`
+// replaced by `updater.channel` ("stable" or "beta"). New versions go to the
+// beta channel first and to stable once they have proven themselves. The
+// manifest follows the Tauri updater format:
+//
+//   {
+//     "version": "0.3.0",
+//     "notes": "...",
+//     "pub_date": "2026-10-01T12:00:00Z",
+//     "platforms": {
+//       "darwin-aarch64": { "url": "https://.../Speekium.app.tar.gz", "signature": "..." },
+//       "windows-x86_64": { "url": "https://.../Speekium-setup.exe", "signature": "..." },
+//       "linux-x86_64": { "url": "https://.../Speekium.AppImage", "signature": "..." }
+//     }
+//   }
+//
+// Checking, downloading, verifying and installing is done by
+// tauri-plugin-updater. `signature` is the file's signature from `tauri
+// signer sign`; the plugin checks it against UPDATE_PUBLIC_KEY, which is
+// compiled in from SPEEKIUM_UPDATER_PUBKEY when a release is built, so
+// neither the manifest host nor config.json can make the app install a file
+// that wasn't signed with the release key. Builds without a key don't
+// download updates.
+//
+// `check_for_updates` reports a newer version for this platform (semver, so
+// 0.3.0-beta.2 < 0.3.0). `download_update` downloads and verifies it with
+// `update-download-progress` events and keeps it in memory.
+// `install_and_restart` stops the daemon (as on quit), installs the update
+// and restarts into it; on Windows the installer starts the new version. If
+// the install fails after the daemon was stopped, the app restarts as it was.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::config::{self, UpdaterConfig};
+use crate::daemon::APP_STATUS;
+use crate::events::{emit_event, AppEvent};
+use crate::types::{AppStatus, UpdateProgressPayload};
+
+const CONFIG_KEY: &str = "updater";
+
+/// Public key of the release builds (the content of the `tauri signer generate` .pub file)
+const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("SPEEKIUM_UPDATER_PUBKEY");
+
+/// Download events without a known size: one per this many bytes
+const PROGRESS_STEP_BYTES: u64 = 1024 * 1024;
+
+/// Newer version offered by the channel's manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub channel: String,
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+}
+
+/// Verified update waiting for install_and_restart
+struct Downloaded {
+    info: UpdateInfo,
+    update: Update,
+    bytes: Vec<u8>,
+}
+
+static DOWNLOADED: Mutex<Option<Downloaded>> = Mutex::new(None);
+
+static DOWNLOADING: AtomicBool = AtomicBool::new(false);
+
+fn load_config() -> UpdaterConfig {
+    config::read_config_value(CONFIG_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// The updater plugin, with the compiled-in public key
+pub fn plugin<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R, tauri_plugin_updater::Config> {
+    let builder = tauri_plugin_updater::Builder::new();
+    match UPDATE_PUBLIC_KEY {
+        Some(key) => builder.pubkey(key),
+        None => builder,
+    }
+    .build()
+}
+
+/// `latest` if it is a newer version than `current`
+fn newer_version(current: &str, latest: &str) -> Result<bool, String> {
+    let parse = |v: &str| {
+        semver::Version::parse(v.trim().trim_start_matches('v')).map_err(|e| format!("Invalid version {}: {}", v, e))
+    };
+    Ok(parse(latest)? > parse(current)?)
+}
+
+/// Newer version for this platform from the configured channel, None if up to date
+async fn fetch_update(app: &tauri::AppHandle) -> Result<Option<(UpdateInfo, Update)>, String> {
+    if crate::network::is_offline() {
+        return Err("Can't check for updates while offline".to_string());
+    }
+    let config = load_config();
+    let url = config.endpoint.replace("{channel}", &config.channel);
+    let url = reqwest::Url::parse(&url).map_err(|e| format!("Invalid update endpoint {}: {}", url, e))?;
+
+    let mut builder = app
+        .updater_builder()
+        .endpoints(vec![url])
+        .map_err(|e| format!("Invalid update endpoint: {}", e))?
+        .version_comparator(|current, release| {
+            newer_version(&current.to_string(), &release.version.to_string()).unwrap_or(false)
+        });
+    if let Some(proxy) = crate::http::proxy_url() {
+        builder = builder.proxy(proxy);
+    }
+    let update = builder
+        .build()
+        .map_err(|e| format!("Failed to set up the updater: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    Ok(update.map(|update| {
+        let info = UpdateInfo {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            channel: config.channel,
+            notes: update.body.clone(),
+            pub_date: update.raw_json.get("pub_date").and_then(|v| v.as_str()).map(str::to_string),
+        };
+        (info, update)
+    }))
+}
+
+/// Download and verify `update`, emitting progress
+async fn download(app: &tauri::AppHandle, update: &Update) -> Result<Vec<u8>, String> {
+    let mut downloaded: u64 = 0;
+    let mut total = None;
+    let mut reported: Option<u64> = None;
+
+    let progress = |downloaded: u64, total: Option<u64>| UpdateProgressPayload {
+        version: update.version.clone(),
+        downloaded,
+        total,
+        percent: total.filter(|&t| t > 0).map(|t| (downloaded * 100 / t).min(100) as u32),
+    };
+    emit_event(app, AppEvent::UpdateDownloadProgress(progress(0, None)));
+    let bytes = update
+        .download(
+            |chunk, size| {
+                downloaded += chunk as u64;
+                total = size;
+                // One event per percent (or per PROGRESS_STEP_BYTES without a size)
+                let step = match size {
+                    Some(size) if size > 0 => downloaded * 100 / size,
+                    _ => downloaded / PROGRESS_STEP_BYTES,
+                };
+                if reported != Some(step) {
+                    reported = Some(step);
+                    emit_event(app, AppEvent::UpdateDownloadProgress(progress(downloaded, size)));
+                }
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| format!("Failed to download the update: {}", e))?;
+    emit_event(app, AppEvent::UpdateDownloadProgress(progress(bytes.len() as u64, total)));
+    Ok(bytes)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Newer version on the configured channel, None if this one is the latest
+#[tauri::command]
+pub async fn check_for_updates(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, String> {
+    Ok(fetch_update(&app).await?.map(|(info, _)| info))
+}
+
+/// Download the newer version (emits `update-download-progress`); None if up to date
+#[tauri::command]
+pub async fn download_update(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, String> {
+    if UPDATE_PUBLIC_KEY.is_none() {
+        return Err("This build has no update signing key; download updates manually".to_string());
+    }
+    if DOWNLOADING.swap(true, Ordering::SeqCst) {
+        return Err("An update is already being downloaded".to_string());
+    }
+    let result = async {
+        let Some((info, update)) = fetch_update(&app).await? else {
+            return Ok(None);
+        };
+        let bytes = download(&app, &update).await?;
+        println!("[UPDATER] Downloaded {} ({} bytes)", info.version, bytes.len());
+        *DOWNLOADED.lock().unwrap() = Some(Downloaded { info: info.clone(), update, bytes });
+        Ok(Some(info))
+    }
+    .await;
+    DOWNLOADING.store(false, Ordering::SeqCst);
+    result
+}
+
+/// Stop the daemon, install the downloaded update and restart into it
+#[tauri::command]
+pub async fn install_and_restart(app: tauri::AppHandle) -> Result<(), String> {
+    // Waiting for speech in continuous mode is fine
+    let status = APP_STATUS.current();
+    if !(status.is_idle() || status == AppStatus::Listening) || crate::file_transcription::is_running() {
+        return Err("Finish the current recording or transcription before updating".to_string());
+    }
+    let downloaded = DOWNLOADED
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "Download the update first".to_string())?;
+
+    println!("[UPDATER] Installing {}", downloaded.info.version);
+    tauri::async_runtime::spawn_blocking(move || {
+        // Drains in-flight work and stops the daemon, as on quit
+        crate::daemon::cleanup_daemon();
+        // On Windows the installer takes over and this process exits
+        if let Err(e) = downloaded.update.install(&downloaded.bytes) {
+            // The daemon is gone: come back as the old version
+            eprintln!("[UPDATER] Failed to install the update: {}, restarting the current version", e);
+        }
+        app.restart()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newer_version() {
+        assert!(newer_version("0.2.4", "0.3.0").unwrap());
+        assert!(newer_version("0.2.4", "v0.2.10").unwrap());
+        assert!(newer_version("0.3.0-beta.1", "0.3.0-beta.2").unwrap());
+        assert!(newer_version("0.3.0-beta.2", "0.3.0").unwrap());
+        assert!(!newer_version("0.3.0", "0.3.0-beta.2").unwrap());
+        assert!(!newer_version("0.2.4", "0.2.4").unwrap());
+        assert!(newer_version("0.2.4", "latest").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+// ============================================================================
+// Usage Stats Module - Dictation Statistics for the Stats Page
+// ============================================================================
+//
+// `get_usage_stats(range)` summarizes what the database already records:
+// dictations (the transcriptions table, text-input mode), conversation turns
+// (user messages) and pipeline latency (latency_metrics). Nothing extra is
+// tracked for it.
+//
+// Words are counted with every CJK character as one word, since Chinese and
+// Japanese are not space separated. The time saved compares typing the words
+// at TYPING_WPM with speaking them at SPEAKING_WPM. Days are local calendar
+// days; a range covers today and the days before it.
+
+use chrono::{Local, NaiveDate, TimeZone};
+use serde::Deserialize;
+use tauri::State;
+
+use crate::state::AppState;
+use crate::types::{DailyActivity, UsageStats, WorkMode};
+
+/// Typing speed dictation is compared with (words per minute)
+const TYPING_WPM: f64 = 40.0;
+
+/// Speaking speed (words per minute)
+const SPEAKING_WPM: f64 = 150.0;
+
+/// Time range of the statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageRange {
+    Day,
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl UsageRange {
+    fn as_str(&self) -> &'static str {
+        match self {
+            UsageRange::Day => "day",
+            UsageRange::Week => "week",
+            UsageRange::Month => "month",
+            UsageRange::Year => "year",
+            UsageRange::All => "all",
+        }
+    }
+
+    /// Days covered, counting today (None = everything)
+    fn days(&self) -> Option<i64> {
+        match self {
+            UsageRange::Day => Some(1),
+            UsageRange::Week => Some(7),
+            UsageRange::Month => Some(30),
+            UsageRange::Year => Some(365),
+            UsageRange::All => None,
+        }
+    }
+}
+
+/// Han, kana and CJK compatibility characters (written without spaces)
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF)
+}
+
+/// Words in `text`: runs of letters and digits, plus one per CJK character
+fn count_words(text: &str) -> usize {
+    let mut words = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            words += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                words += 1;
+                in_word = true;
+            }
+        } else if !(in_word && (c == '\'' || c == '-')) {
+            // "don't" and "well-known" are one word
+            in_word = false;
+        }
+    }
+    words
+}
+
+/// Local midnight starting `date`, in ms
+fn day_start(date: NaiveDate) -> i64 {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|t| t.timestamp_millis())
+        .unwrap_or_else(|| midnight.and_utc().timestamp_millis())
+}
+
+fn local_date(timestamp_ms: i64) -> Option<NaiveDate> {
+    Local.timestamp_millis_opt(timestamp_ms).earliest().map(|t| t.date_naive())
+}
+
+/// One bucket per day from `first` to `last`, filled from (day, words) entries
+fn daily_buckets(first: NaiveDate, last: NaiveDate, entries: &[(NaiveDate, usize)]) -> Vec<DailyActivity> {
+    let mut buckets: Vec<DailyActivity> = first
+        .iter_days()
+        .take_while(|date| *date <= last)
+        .map(|date| DailyActivity { date: date.format("%Y-%m-%d").to_string(), ..Default::default() })
+        .collect();
+    for (date, words) in entries {
+        let Ok(index) = usize::try_from((*date - first).num_days()) else {
+            continue;
+        };
+        if let Some(bucket) = buckets.get_mut(index) {
+            bucket.recordings += 1;
+            bucket.words += words;
+        }
+    }
+    buckets
+}
+
+/// Mean of the values that are present
+fn average(values: impl Iterator<Item = Option<i64>>) -> Option<i64> {
+    let values: Vec<i64> = values.flatten().collect();
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<i64>() / values.len() as i64)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Recordings, words, latency and daily activity over `range` (default: week)
+#[tauri::command]
+pub fn get_usage_stats(state: State<'_, AppState>, range: Option<UsageRange>) -> Result<UsageStats, String> {
+    let range = range.unwrap_or(UsageRange::Week);
+    let today = Local::now().date_naive();
+    let first_day = range.days().map(|days| today - chrono::Duration::days(days - 1));
+    let since = first_day.map(day_start);
+
+    let dictations = state.db.get_transcription_texts_since(since.unwrap_or(0))?;
+    let turns = state.db.get_user_message_texts_since(since.unwrap_or(0))?;
+    let latency = state.db.get_latency_records_since(since.unwrap_or(0))?;
+
+    let entries: Vec<(NaiveDate, usize)> = dictations
+        .iter()
+        .chain(turns.iter())
+        .filter_map(|(timestamp, text)| Some((local_date(*timestamp)?, count_words(text))))
+        .collect();
+    let words: usize = entries.iter().map(|(_, words)| words).sum();
+
+    // "all" starts at the first activity
+    let first_day = first_day
+        .or_else(|| entries.iter().map(|(date, _)| *date).min())
+        .unwrap_or(today);
+
+    let most_used_mode = match (dictations.len(), turns.len()) {
+        (0, 0) => None,
+        (dictated, spoken) if dictated > spoken => Some(WorkMode::TextInput.as_str().to_string()),
+        _ => Some(WorkMode::Conversation.as_str().to_string()),
+    };
+    let minutes_saved = words as f64 / TYPING_WPM - words as f64 / SPEAKING_WPM;
+
+    Ok(UsageStats {
+        range: range.as_str().to_string(),
+        since,
+        recordings: dictations.len() + turns.len(),
+        dictations: dictations.len(),
+        conversation_turns: turns.len(),
+        words,
+        avg_asr_ms: average(latency.iter().map(|r| r.asr_ms)),
+        avg_end_to_end_ms: average(latency.iter().map(|r| r.playback_ms)),
+        most_used_mode,
+        time_saved_secs: (minutes_saved * 60.0).round() as i64,
+        daily: daily_buckets(first_day, today, &entries),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("Hello, world! Don't stop the well-known demo."), 7);
+        assert_eq!(count_words("今天天气很好"), 6);
+        assert_eq!(count_words("打开 Safari 浏览器"), 6);
+        assert_eq!(count_words("  - "), 0);
+    }
+
+    #[test]
+    fn test_daily_buckets_fill_every_day() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let buckets = daily_buckets(day(1), day(3), &[(day(1), 4), (day(3), 2), (day(3), 5), (day(9), 1)]);
+
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0], DailyActivity { date: "2026-03-01".into(), recordings: 1, words: 4 });
+        assert_eq!(buckets[1], DailyActivity { date: "2026-03-02".into(), recordings: 0, words: 0 });
+        assert_eq!(buckets[2], DailyActivity { date: "2026-03-03".into(), recordings: 2, words: 7 });
+    }
+}
@@ -0,0 +1,129 @@
+// ============================================================================
+// Vocabulary Module - Custom Terms Passed to the ASR as Hotwords
+// ============================================================================
+//
+// Names, product terms and jargon are often misheard. The user keeps a list
+// of them in the database (so each profile has its own), and every
+// transcription request to the daemon carries the list as `hotwords`; models
+// that support hotword biasing prefer these spellings.
+//
+// The list is cached here so the request builders don't need the database:
+// it is loaded at startup, after each change and when the profile switches.
+// `import_vocabulary` adds the terms of a text file (one per line) or a CSV
+// file (first column); lines starting with # are skipped.
+
+use std::sync::Mutex;
+
+use tauri::State;
+
+use crate::database::{Database, VocabularyTerm};
+use crate::state::AppState;
+
+/// Longest accepted term, in characters
+const MAX_TERM_CHARS: usize = 100;
+
+/// Terms of the active profile, as sent with transcription requests
+static HOTWORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Reload the cached hotwords from `db` (call after it changes or is reopened)
+pub fn reload(db: &Database) {
+    match db.list_vocabulary() {
+        Ok(terms) => *HOTWORDS.lock().unwrap() = terms.into_iter().map(|t| t.term).collect(),
+        Err(e) => eprintln!("[VOCABULARY] {}", e),
+    }
+}
+
+/// Hotwords sent with a transcription request
+pub fn request_hotwords() -> Vec<String> {
+    HOTWORDS.lock().map(|hotwords| hotwords.clone()).unwrap_or_default()
+}
+
+/// Trimmed term, or an error if it is empty or too long
+fn normalize_term(term: &str) -> Result<String, String> {
+    let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+    if term.is_empty() {
+        return Err("Term must not be empty".to_string());
+    }
+    if term.chars().count() > MAX_TERM_CHARS {
+        return Err(format!("Term is longer than {} characters", MAX_TERM_CHARS));
+    }
+    Ok(term)
+}
+
+/// Terms of an import file: one per line, the first column of a CSV file
+fn parse_terms(content: &str, csv: bool) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim_start_matches('\u{feff}').trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let field = if csv { line.split(',').next().unwrap_or_default() } else { line };
+        let Ok(term) = normalize_term(field.trim().trim_matches('"')) else {
+            continue;
+        };
+        if !terms.iter().any(|t| t.eq_ignore_ascii_case(&term)) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Vocabulary of the active profile (alphabetical)
+#[tauri::command]
+pub fn list_vocabulary(state: State<'_, AppState>) -> Result<Vec<VocabularyTerm>, String> {
+    state.db.list_vocabulary()
+}
+
+/// Add a term to the vocabulary
+#[tauri::command]
+pub fn add_vocabulary_term(state: State<'_, AppState>, term: String) -> Result<VocabularyTerm, String> {
+    let term = state.db.add_vocabulary_term(&normalize_term(&term)?)?;
+    reload(&state.db);
+    Ok(term)
+}
+
+/// Change the spelling of a term
+#[tauri::command]
+pub fn update_vocabulary_term(state: State<'_, AppState>, id: i64, term: String) -> Result<bool, String> {
+    let updated = state.db.update_vocabulary_term(id, &normalize_term(&term)?)?;
+    reload(&state.db);
+    Ok(updated)
+}
+
+/// Remove a term from the vocabulary
+#[tauri::command]
+pub fn delete_vocabulary_term(state: State<'_, AppState>, id: i64) -> Result<bool, String> {
+    let deleted = state.db.delete_vocabulary_term(id)?;
+    reload(&state.db);
+    Ok(deleted)
+}
+
+/// Add the terms of a .txt or .csv file; returns how many were new
+#[tauri::command]
+pub fn import_vocabulary(state: State<'_, AppState>, path: String) -> Result<usize, String> {
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let csv = path.to_lowercase().ends_with(".csv");
+    let added = state.db.import_vocabulary_terms(&parse_terms(&content, csv))?;
+    reload(&state.db);
+    println!("[VOCABULARY] Imported {} term(s) from {}", added, path);
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_terms() {
+        let text = "\u{feff}Speekium\n\n# comment\n  Kubernetes  \nspeekium\nSense  Voice\n";
+        assert_eq!(parse_terms(text, false), vec!["Speekium", "Kubernetes", "Sense Voice"]);
+
+        let csv = "\"Tauri\",framework\nSQLCipher,database\n";
+        assert_eq!(parse_terms(csv, true), vec!["Tauri", "SQLCipher"]);
+    }
+}
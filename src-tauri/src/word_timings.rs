@@ -0,0 +1,77 @@
+// ============================================================================
+// Word Timings Module - Per-Word Timestamps of Spoken Messages
+// ============================================================================
+//
+// When the ASR model supports timestamps, the daemon returns the timing of
+// each recognized word, in ms from the start of the recording. They are kept
+// until the frontend saves the user message (db_add_message), then stored on
+// the message, so the history view can highlight words in sync with the
+// archived recording (see audio_archive.rs).
+//
+// Timings belong to the raw transcript: words changed by the user's
+// replacements (textproc) keep the text the model recognized.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::State;
+
+use crate::database::{Database, Message};
+use crate::state::AppState;
+use crate::types::WordTiming;
+
+/// Timings are dropped if no message is saved within this time
+const PENDING_TIMINGS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Word timings of the last transcript, waiting for its message to be saved
+struct PendingTimings {
+    transcript: String,
+    words: Vec<WordTiming>,
+    recognized_at: Instant,
+}
+
+static PENDING_TIMINGS: Mutex<Option<PendingTimings>> = Mutex::new(None);
+
+/// Remember the word timings of a transcript until its message is saved
+pub fn set_pending_timings(transcript: &str, words: Vec<WordTiming>) {
+    if let Ok(mut pending) = PENDING_TIMINGS.lock() {
+        *pending = (!words.is_empty()).then(|| PendingTimings {
+            transcript: transcript.to_string(),
+            words,
+            recognized_at: Instant::now(),
+        });
+    }
+}
+
+/// Store the pending word timings on a newly saved user message with the same transcript
+pub fn attach_pending_timings(db: &Database, message: &Message) {
+    if message.role != "user" {
+        return;
+    }
+    let pending = match PENDING_TIMINGS.lock() {
+        Ok(mut pending) => pending.take_if(|p| {
+            p.transcript == message.content && p.recognized_at.elapsed() < PENDING_TIMINGS_TIMEOUT
+        }),
+        Err(_) => None,
+    };
+    let Some(pending) = pending else {
+        return;
+    };
+
+    if let Err(e) = db.set_message_word_timings(&message.id, &pending.words) {
+        eprintln!("[WORD TIMINGS] {}", e);
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Word timings of a spoken message, ordered by start (empty for typed messages)
+#[tauri::command]
+pub fn get_message_word_timings(
+    state: State<'_, AppState>,
+    message_id: String,
+) -> Result<Vec<WordTiming>, String> {
+    state.db.get_message_word_timings(&message_id)
+}
@@ -10,7 +10,8 @@ import { ModeStatusBadge } from './components/ModeStatusBadge';
 import { SystemToast } from './components/SystemToast';
 import type { ToastType } from './components/SystemToast';
 import { CollapsibleInput } from './components/CollapsibleInput';
-import { historyAPI } from './useTauriAPI';
+import { historyAPI, diagnosticsAPI, asrLanguageAPI, recordingJournalAPI } from './useTauriAPI';
+import type { MicConflict, TypingBlocked } from './useTauriAPI';
 import { useWorkMode } from './contexts/WorkModeContext';
 import { useError } from './contexts/ErrorContext';
 import type { WorkModeChangeEvent } from './types/workMode';
@@ -75,6 +76,9 @@ function App() {
     asr: 'pending',
   });
 
+  // Overall startup progress from `daemon-progress` (null until the daemon reports it)
+  const [startupPercent, setStartupPercent] = React.useState<number | null>(null);
+
   // Toast state - 支持多种 Toast 类型
   const [toast, setToast] = React.useState<{
     show: boolean;
@@ -169,6 +173,8 @@ function App() {
     } else {
       localStorage.removeItem('speekium_current_session_id');
     }
+    // Later recordings prefer the language this session was spoken in
+    asrLanguageAPI.setSession(currentSessionId).catch(() => {});
   }, [currentSessionId]);
 
   // Sync recording mode with backend on initial mount
@@ -344,6 +350,55 @@ function App() {
     };
   }, []);
 
+  // Listen for weighted startup progress
+  React.useEffect(() => {
+    const unlistenPromise = listen<{ stage: string; percent: number }>('daemon-progress', (event) => {
+      setStartupPercent(event.payload.percent);
+    });
+
+    return () => {
+      unlistenPromise.then(unlisten => unlisten());
+    };
+  }, []);
+
+  // Offer to send diagnostics if the previous run crashed
+  React.useEffect(() => {
+    const checkLastCrash = async () => {
+      try {
+        const report = await diagnosticsAPI.getLastCrashReport();
+        if (report) {
+          const send = window.confirm(t('errors.crashDetected'));
+          await diagnosticsAPI.clearCrashReport(send);
+        }
+      } catch (error) {
+        console.error('Failed to check last crash report:', error);
+      }
+    };
+
+    checkLastCrash();
+  }, []);
+
+  // Offer to transcribe recordings a crash or failed ASR left behind
+  React.useEffect(() => {
+    const checkPendingRecordings = async () => {
+      try {
+        const pending = await recordingJournalAPI.list();
+        if (pending.length === 0) return;
+        if (!window.confirm(t('errors.pendingRecordings', { count: pending.length }))) {
+          await recordingJournalAPI.discard();
+          return;
+        }
+        const recovered = await recordingJournalAPI.recover();
+        const transcribed = recovered.filter(r => r.text).length;
+        window.alert(t('errors.recordingsRecovered', { count: transcribed, total: recovered.length }));
+      } catch (error) {
+        console.error('Failed to recover pending recordings:', error);
+      }
+    };
+
+    checkPendingRecordings();
+  }, []);
+
   // Restore last session from localStorage
   React.useEffect(() => {
     const restoreSession = async () => {
@@ -373,6 +428,32 @@ function App() {
     restoreSession();
   }, []);
 
+  // Open a session picked from the tray "Recent Sessions" menu or a speekium://open-session link
+  React.useEffect(() => {
+    const unlistenPromise = listen<string>('open-session', async (event) => {
+      const sessionId = event.payload;
+      try {
+        const session = await historyAPI.getSession(sessionId);
+        const messagesResult = await historyAPI.getSessionMessages(sessionId, 1, 1000);
+        clearHistory();
+        setCurrentSessionId(session.id);
+        currentSessionIdRef.current = session.id;
+        setCurrentSessionTitle(session.title);
+        messagesResult.items.forEach(msg => {
+          if (msg.role === 'user' || msg.role === 'assistant') {
+            addMessage(msg.role, msg.content);
+          }
+        });
+      } catch (error) {
+        console.error('Failed to open session:', error);
+      }
+    });
+
+    return () => {
+      unlistenPromise.then(unlisten => unlisten());
+    };
+  }, []);
+
   // Auto scroll to bottom when messages change
   React.useEffect(() => {
     messagesEndRef.current?.scrollIntoView({ behavior: 'smooth' });
@@ -604,6 +685,11 @@ function App() {
               console.log('Recording cancelled due to mode change, stopping continuous listening');
               break;
             }
+            // Paused for Do Not Disturb or a meeting app - wait until it resumes
+            if (result.error?.includes('Listening paused')) {
+              await new Promise(resolve => setTimeout(resolve, 2000));
+              continue;
+            }
             // Check if error is due to streaming in progress - just wait and retry
             if (result.error?.includes('streaming in progress')) {
               console.log('Recording blocked by streaming, waiting...');
@@ -675,6 +761,89 @@ function App() {
       })()
     );
 
+    // 服务商限流/出错时提示正在重试（最终失败由 chat-error 报告）
+    unlisteners.push(
+      (async () => {
+        const unlisten = await listen<{
+          provider: string;
+          error: string;
+          status: number | null;
+          retries: number;
+          retrying: boolean;
+        }>('provider-error', (event) => {
+          const { provider, status, retries, retrying } = event.payload;
+          if (!retrying) return;
+          setToast({
+            show: true,
+            type: 'custom',
+            workMode,
+            message: `${provider}: ${status === 429 ? 'rate limited' : `error ${status}`}, retrying (${retries + 1})...`,
+            duration: 2000,
+          });
+        });
+        return unlisten;
+      })()
+    );
+
+    // Going offline / back online (offline_mode or the connectivity check)
+    unlisteners.push(
+      (async () => {
+        const unlisten = await listen<{
+          offline: boolean;
+          offline_mode: boolean;
+          reachable: boolean;
+        }>('connectivity-changed', (event) => {
+          const { offline, offline_mode } = event.payload;
+          setToast({
+            show: true,
+            type: 'custom',
+            workMode,
+            message: offline
+              ? offline_mode
+                ? 'Offline mode: using local models only'
+                : 'No network connection: using local models only'
+              : 'Back online',
+            duration: 3000,
+          });
+        });
+        return unlisten;
+      })()
+    );
+
+    // Microphone held by another app (PTT recording could not start)
+    unlisteners.push(
+      (async () => {
+        const unlisten = await listen<MicConflict>('mic-conflict', (event) => {
+          const { message, retrying, resolved } = event.payload;
+          setToast({
+            show: true,
+            type: 'custom',
+            workMode,
+            message: retrying && !resolved ? `${message}, waiting for it to free up...` : message,
+            duration: 3000,
+          });
+        });
+        return unlisten;
+      })()
+    );
+
+    // Text input refused (the focused field is a password field)
+    unlisteners.push(
+      (async () => {
+        const unlisten = await listen<TypingBlocked>('typing-blocked', (event) => {
+          const { message, app } = event.payload;
+          setToast({
+            show: true,
+            type: 'custom',
+            workMode,
+            message: app ? `${message} (${app})` : message,
+            duration: 3000,
+          });
+        });
+        return unlisten;
+      })()
+    );
+
     // 监听工作模式变化（通过轮询检测）
     // 注意：快捷键不再使用事件，而是通过配置轮询来检测变化
     unlisteners.push(
@@ -783,10 +952,10 @@ function App() {
     }
 
     try {
-      const result = await chatGenerator(userMessage);
+      const result = await chatGenerator(userMessage, 'auto', true, false, sessionId ?? undefined);
 
-      // Save assistant message to database
-      if (result && result.success && result.content && sessionId) {
+      // Save assistant message to database (a streamed reply is saved while it arrives)
+      if (result && result.success && result.content && sessionId && !result.message_id) {
         try {
           await historyAPI.addSessionMessage(sessionId, 'assistant', result.content);
         } catch (error) {
@@ -848,6 +1017,7 @@ function App() {
         status={daemonStatus}
         downloadProgress={downloadProgress}
         modelLoadingStages={modelLoadingStages}
+        startupPercent={startupPercent}
       />
     );
   }
@@ -56,6 +56,8 @@ import {
   OLLAMA_MODELS,
   CUSTOM_MODELS,
   ZHIPU_MODELS,
+  ANTHROPIC_MODELS,
+  GEMINI_MODELS,
 } from './constants/models';
 
 interface SettingsProps {
@@ -111,6 +113,8 @@ export function Settings({
     ollama: false,
     custom: false,
     zhipu: false,
+    anthropic: false,
+    gemini: false,
   });
   const [ollamaModels, setOllamaModels] = React.useState<string[]>(OLLAMA_MODELS);
   const [isLoadingOllamaModels, setIsLoadingOllamaModels] = React.useState(false);
@@ -264,62 +268,26 @@ export function Settings({
     try {
       let result: { success: boolean; message?: string; error?: string };
 
-      // 根据不同的 provider 类型调用不同的测试命令
-      if (providerName === 'ollama') {
-        result = await invoke<{
-          success: boolean;
-          message?: string;
-          error?: string;
-        }>('test_ollama_connection', {
-          baseUrl: base_url || 'http://localhost:11434',
-          model: model || 'qwen2.5:1.5b'
-        });
-      } else if (providerName === 'openai') {
-        result = await invoke<{
-          success: boolean;
-          message?: string;
-          error?: string;
-        }>('test_openai_connection', {
-          apiKey: api_key,
-          model: model || 'gpt-4o-mini'
-        });
-      } else if (providerName === 'openrouter') {
-        result = await invoke<{
-          success: boolean;
-          message?: string;
-          error?: string;
-        }>('test_openrouter_connection', {
-          apiKey: api_key,
-          model: model || 'google/gemini-2.5-flash'
-        });
-      } else if (providerName === 'custom') {
-        result = await invoke<{
-          success: boolean;
-          message?: string;
-          error?: string;
-        }>('test_custom_connection', {
-          apiKey: api_key,
-          baseUrl: base_url,
-          model: model
-        });
-      } else if (providerName === 'zhipu') {
-        result = await invoke<{
-          success: boolean;
-          message?: string;
-          error?: string;
-        }>('test_zhipu_connection', {
-          apiKey: api_key,
-          baseUrl: base_url || 'https://open.bigmodel.cn/api/paas/v4',
-          model: model || 'glm-4-flash'
-        });
-      } else if (providerName === 'claude') {
+      if (providerName === 'claude') {
         // Claude Code CLI 不需要测试连接，直接返回成功
         result = {
           success: true,
           message: 'Claude Code CLI is available locally'
         };
       } else {
-        throw new Error(`Unknown provider: ${providerName}`);
+        // 空字段由后端按 provider 默认值补全
+        result = await invoke<{
+          success: boolean;
+          message?: string;
+          error?: string;
+        }>('test_llm_connection', {
+          provider: providerName,
+          params: {
+            base_url: base_url || null,
+            api_key: api_key || null,
+            model: model || null,
+          }
+        });
       }
 
       if (result.success) {
@@ -766,13 +734,15 @@ export function Settings({
                           <SelectItem value="openai">OpenAI</SelectItem>
                           <SelectItem value="openrouter">OpenRouter</SelectItem>
                           <SelectItem value="zhipu">智谱AI (BigModel)</SelectItem>
+                          <SelectItem value="anthropic">Anthropic</SelectItem>
+                          <SelectItem value="gemini">Google Gemini</SelectItem>
                           <SelectItem value="custom">Custom (OpenAI-compatible)</SelectItem>
                         </SelectContent>
                       </Select>
                     </div>
 
                     {/* API Key (for providers that require it) */}
-                    {['openai', 'openrouter', 'custom', 'zhipu'].includes(currentProvider) && (
+                    {['openai', 'openrouter', 'custom', 'zhipu', 'anthropic', 'gemini'].includes(currentProvider) && (
                       <div className="space-y-2">
                         <Label htmlFor="provider-api-key" className="text-foreground">
                           {currentProvider === 'zhipu' ? '智谱AI API Key' : `${currentProvider === 'custom' ? 'API Key (Optional)' : `${currentProvider.charAt(0).toUpperCase() + currentProvider.slice(1)} API Key`}`}
@@ -836,8 +806,9 @@ export function Settings({
                             onClick={async () => {
                               setIsLoadingOllamaModels(true);
                               try {
-                                const models = await invoke<string[]>('list_ollama_models', {
-                                  baseUrl: base_url || 'http://localhost:11434'
+                                const models = await invoke<string[]>('list_models', {
+                                  provider: 'ollama',
+                                  params: { base_url: base_url || 'http://localhost:11434' }
                                 });
                                 setOllamaModels(models);
                                 alert(`Found ${models.length} models:\n\n${models.join('\n')}`);
@@ -884,6 +855,8 @@ export function Settings({
                             currentProvider === 'openai' ? 'gpt-4o-mini' :
                             currentProvider === 'openrouter' ? 'google/gemini-2.5-flash' :
                             currentProvider === 'zhipu' ? 'glm-4-flash' :
+                            currentProvider === 'anthropic' ? 'claude-3-5-haiku-latest' :
+                            currentProvider === 'gemini' ? 'gemini-2.0-flash' :
                             currentProvider === 'custom' ? CUSTOM_MODELS[0] :
                             ollamaModels[0]
                           )}
@@ -908,6 +881,12 @@ export function Settings({
                             {currentProvider === 'zhipu' && ZHIPU_MODELS.map(m => (
                               <SelectItem key={m} value={m} className="text-foreground">{m}</SelectItem>
                             ))}
+                            {currentProvider === 'anthropic' && ANTHROPIC_MODELS.map(m => (
+                              <SelectItem key={m} value={m} className="text-foreground">{m}</SelectItem>
+                            ))}
+                            {currentProvider === 'gemini' && GEMINI_MODELS.map(m => (
+                              <SelectItem key={m} value={m} className="text-foreground">{m}</SelectItem>
+                            ))}
                             {currentProvider === 'custom' && CUSTOM_MODELS.map(m => (
                               <SelectItem key={m} value={m} className="text-foreground">{m}</SelectItem>
                             ))}
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
-import { X, Clock, Trash2, MessageSquare, ChevronLeft, PenSquare, Star, Search, Download } from 'lucide-react';
+import { X, Clock, Trash2, MessageSquare, ChevronLeft, PenSquare, Star, Search, Download, ExternalLink, Pin } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
 import { Button } from '@/components/ui/button';
 import { cn } from '@/lib/utils';
 import { historyAPI, Session, HistoryMessage } from '../useTauriAPI';
@@ -10,6 +11,8 @@ interface Props {
   onClose: () => void;
   onLoadSession?: (messages: Array<{ role: 'user' | 'assistant'; content: string }>) => void;
   onNewSession?: () => void;
+  /** 'window': fills the standalone history window instead of sliding over the main window */
+  variant?: 'drawer' | 'window';
 }
 
 // Time grouping helper
@@ -28,7 +31,7 @@ function groupSessionsByDate(sessions: Session[]) {
   };
 }
 
-export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession }: Props) {
+export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession, variant = 'drawer' }: Props) {
   const { t } = useTranslation();
   const [sessions, setSessions] = useState<Session[]>([]);
   const [isLoading, setIsLoading] = useState(false);
@@ -69,6 +72,26 @@ export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession }:
     }
   }, [isOpen, favoriteFilter, loadSessions]);
 
+  // The history window stays mounted while hidden; refresh whenever it is shown again
+  useEffect(() => {
+    if (variant !== 'window') return;
+    const unlistenPromise = listen('history-window-shown', () => {
+      loadSessions();
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [variant, loadSessions]);
+
+  const handleOpenWindow = async () => {
+    try {
+      await historyAPI.openWindow();
+      onClose();
+    } catch (error) {
+      console.error('Failed to open history window:', error);
+    }
+  };
+
   const handleViewSession = async (session: Session) => {
     try {
       const result = await historyAPI.getSessionMessages(session.id, 1, 100);
@@ -81,7 +104,7 @@ export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession }:
 
   const handleDeleteSession = async (sessionId: string) => {
     try {
-      await historyAPI.deleteSession(sessionId);
+      await historyAPI.trashSession(sessionId);
       setSessions((prev) => prev.filter((s) => s.id !== sessionId));
       setDeleteConfirm(null);
       if (selectedSession?.id === sessionId) {
@@ -123,19 +146,27 @@ export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession }:
     }
   };
 
+  const handleTogglePin = async (message: HistoryMessage) => {
+    try {
+      const pinned = !message.pinned;
+      await historyAPI.pinMessage(message.id, pinned);
+      setSessionMessages((prev) => prev.map((m) => (m.id === message.id ? { ...m, pinned } : m)));
+    } catch (error) {
+      console.error('Failed to pin message:', error);
+    }
+  };
+
+  const handleOpenPinned = async () => {
+    try {
+      await historyAPI.openPinnedWindow();
+    } catch (error) {
+      console.error('Failed to open pinned answers:', error);
+    }
+  };
+
   const handleExport = async (sessionId: string) => {
     try {
-      const markdown = await historyAPI.exportConversation(sessionId);
-      // Create and download file
-      const blob = new Blob([markdown], { type: 'text/markdown' });
-      const url = URL.createObjectURL(blob);
-      const a = document.createElement('a');
-      a.href = url;
-      a.download = `conversation-${sessionId.slice(0, 8)}.md`;
-      document.body.appendChild(a);
-      a.click();
-      document.body.removeChild(a);
-      URL.revokeObjectURL(url);
+      await historyAPI.exportSession(sessionId, 'markdown');
     } catch (error) {
       console.error('Failed to export conversation:', error);
     }
@@ -187,18 +218,21 @@ export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession }:
   return (
     <>
       {/* Backdrop */}
-      <div
-        className="fixed inset-0 bg-black/60 backdrop-blur-sm z-40"
-        onClick={onClose}
-      />
+      {variant === 'drawer' && (
+        <div
+          className="fixed inset-0 bg-black/60 backdrop-blur-sm z-40"
+          onClick={onClose}
+        />
+      )}
 
       {/* Drawer */}
       <div
         className={cn(
-          'fixed left-0 top-0 h-full w-96 z-50',
-          'bg-gradient-to-br from-background to-muted border-r border-border/50',
+          'bg-gradient-to-br from-background to-muted',
           'flex flex-col',
-          'animate-in slide-in-from-left duration-300'
+          variant === 'drawer'
+            ? 'fixed left-0 top-0 h-full w-96 z-50 border-r border-border/50 animate-in slide-in-from-left duration-300'
+            : 'h-screen w-full'
         )}
       >
         {/* Header */}
@@ -237,6 +271,30 @@ export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession }:
                   <PenSquare className="w-5 h-5" />
                 </Button>
               )}
+              {/* 置顶回答窗口 */}
+              {!selectedSession && (
+                <Button
+                  variant="ghost"
+                  size="icon"
+                  className="text-muted-foreground hover:text-foreground hover:bg-muted/50"
+                  onClick={handleOpenPinned}
+                  title={t('history.actions.openPinned')}
+                >
+                  <Pin className="w-5 h-5" />
+                </Button>
+              )}
+              {/* 在独立窗口中打开 - 仅抽屉模式 */}
+              {!selectedSession && variant === 'drawer' && (
+                <Button
+                  variant="ghost"
+                  size="icon"
+                  className="text-muted-foreground hover:text-foreground hover:bg-muted/50"
+                  onClick={handleOpenWindow}
+                  title={t('history.openWindow')}
+                >
+                  <ExternalLink className="w-5 h-5" />
+                </Button>
+              )}
               <Button
                 variant="ghost"
                 size="icon"
@@ -316,16 +374,29 @@ export function HistoryDrawer({ isOpen, onClose, onLoadSession, onNewSession }:
                 <div
                   key={msg.id}
                   className={cn(
-                    'p-3 rounded-2xl text-sm',
+                    'group p-3 rounded-2xl text-sm',
                     msg.role === 'user'
                       ? 'bg-blue-600 text-white ml-4 rounded-tr-sm'
                       : 'bg-muted text-foreground mr-4 border border-border/50 rounded-tl-sm'
                   )}
                 >
                   <p className="whitespace-pre-wrap break-words">{msg.content}</p>
-                  <p className="text-xs opacity-60 mt-1">
-                    {new Date(msg.timestamp).toLocaleTimeString()}
-                  </p>
+                  <div className="flex items-center justify-between mt-1">
+                    <p className="text-xs opacity-60">
+                      {new Date(msg.timestamp).toLocaleTimeString()}
+                    </p>
+                    {/* Pin button - always visible if pinned, otherwise show on hover */}
+                    <button
+                      onClick={() => handleTogglePin(msg)}
+                      className={cn(
+                        'p-1 rounded-md transition-all duration-200 hover:bg-black/10',
+                        msg.pinned ? 'opacity-100' : 'opacity-0 group-hover:opacity-60 hover:!opacity-100'
+                      )}
+                      title={msg.pinned ? t('history.actions.unpin') : t('history.actions.pin')}
+                    >
+                      <Pin className={cn('w-3.5 h-3.5', msg.pinned && 'fill-current')} />
+                    </button>
+                  </div>
                 </div>
               ))}
 
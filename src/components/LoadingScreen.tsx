@@ -23,13 +23,15 @@ interface LoadingScreenProps {
   status: 'loading' | 'error';
   downloadProgress?: DownloadProgress;
   modelLoadingStages?: ModelLoadingStages;
+  startupPercent?: number | null; // Weighted progress over all startup stages
 }
 
 export function LoadingScreen({
   message,
   status,
   downloadProgress,
-  modelLoadingStages
+  modelLoadingStages,
+  startupPercent
 }: LoadingScreenProps) {
   const { t } = useTranslation();
 
@@ -177,6 +179,19 @@ export function LoadingScreen({
           <span className="text-sm font-medium">{message || t('app.loading.startingService')}</span>
         </div>
 
+        {/* Overall startup progress */}
+        {displayStatus === 'loading' && startupPercent != null && (
+          <div className="mt-4 w-72 animate-fade-in">
+            <div className="h-1.5 bg-muted rounded-full overflow-hidden">
+              <div
+                className="h-full bg-gradient-to-r from-blue-500 via-purple-500 to-cyan-500 rounded-full transition-all duration-500 ease-out"
+                style={{ width: `${startupPercent}%` }}
+              />
+            </div>
+            <div className="text-right text-xs text-muted-foreground mt-1">{startupPercent}%</div>
+          </div>
+        )}
+
         {/* Progress bar */}
         {displayStatus === 'loading' && downloadProgress?.show && (
           <div className="mt-4 w-72 animate-fade-in">
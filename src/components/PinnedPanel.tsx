@@ -0,0 +1,110 @@
+import { useState, useEffect, useCallback } from 'react';
+import { X, Pin, Copy } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
+import { Button } from '@/components/ui/button';
+import { historyAPI, PinnedMessage } from '../useTauriAPI';
+import { useTranslation } from '@/i18n';
+
+interface Props {
+  onClose: () => void;
+}
+
+/** Content of the always-on-top pinned answers window */
+export function PinnedPanel({ onClose }: Props) {
+  const { t } = useTranslation();
+  const [pinned, setPinned] = useState<PinnedMessage[]>([]);
+
+  const loadPinned = useCallback(async () => {
+    try {
+      setPinned(await historyAPI.listPinned());
+    } catch (error) {
+      console.error('[Pinned] Load failed:', error);
+    }
+  }, []);
+
+  // Refresh when shown again (the window stays mounted while hidden) and when pins change
+  useEffect(() => {
+    loadPinned();
+    const unlistenShown = listen('pinned-window-shown', () => loadPinned());
+    const unlistenChanged = listen('pinned-messages-changed', () => loadPinned());
+    return () => {
+      unlistenShown.then((unlisten) => unlisten());
+      unlistenChanged.then((unlisten) => unlisten());
+    };
+  }, [loadPinned]);
+
+  const handleUnpin = async (messageId: string) => {
+    try {
+      await historyAPI.pinMessage(messageId, false);
+      setPinned((prev) => prev.filter((m) => m.id !== messageId));
+    } catch (error) {
+      console.error('[Pinned] Unpin failed:', error);
+    }
+  };
+
+  const handleCopy = async (content: string) => {
+    try {
+      await navigator.clipboard.writeText(content);
+    } catch (error) {
+      console.error('[Pinned] Copy failed:', error);
+    }
+  };
+
+  return (
+    <div className="h-screen w-full flex flex-col bg-gradient-to-br from-background to-muted">
+      {/* Header */}
+      <header className="shrink-0 h-11 px-3 flex items-center justify-between border-b border-border/50">
+        <div className="flex items-center gap-2">
+          <Pin className="w-4 h-4 text-blue-500" />
+          <h2 className="text-sm font-semibold text-foreground">{t('pinned.title')}</h2>
+        </div>
+        <Button
+          variant="ghost"
+          size="icon"
+          className="h-7 w-7 text-muted-foreground hover:text-foreground"
+          onClick={onClose}
+        >
+          <X className="w-4 h-4" />
+        </Button>
+      </header>
+
+      {/* Content */}
+      <div className="flex-1 overflow-y-auto p-3 space-y-2">
+        {pinned.length === 0 ? (
+          <div className="flex flex-col items-center justify-center h-full text-center px-4">
+            <p className="text-sm text-muted-foreground mb-1">{t('pinned.empty.title')}</p>
+            <p className="text-xs text-muted-foreground">{t('pinned.empty.description')}</p>
+          </div>
+        ) : (
+          pinned.map((msg) => (
+            <div
+              key={msg.id}
+              className="group p-3 rounded-xl text-sm bg-muted/60 border border-border/50"
+            >
+              <p className="whitespace-pre-wrap break-words text-foreground select-text">{msg.content}</p>
+              <div className="flex items-center justify-between mt-2">
+                <p className="text-xs text-muted-foreground truncate pr-2">{msg.session_title}</p>
+                <div className="flex gap-0.5 opacity-0 group-hover:opacity-100 transition-opacity duration-200">
+                  <button
+                    onClick={() => handleCopy(msg.content)}
+                    className="p-1 rounded-md text-muted-foreground hover:text-foreground hover:bg-muted"
+                    title={t('pinned.copy')}
+                  >
+                    <Copy className="w-3.5 h-3.5" />
+                  </button>
+                  <button
+                    onClick={() => handleUnpin(msg.id)}
+                    className="p-1 rounded-md text-muted-foreground hover:text-foreground hover:bg-muted"
+                    title={t('history.actions.unpin')}
+                  >
+                    <Pin className="w-3.5 h-3.5 fill-current" />
+                  </button>
+                </div>
+              </div>
+            </div>
+          ))
+        )}
+      </div>
+    </div>
+  );
+}
@@ -35,3 +35,15 @@ export const ZHIPU_MODELS = [
   'glm-4-flash',
   'glm-4-air',
 ];
+
+export const ANTHROPIC_MODELS = [
+  'claude-3-5-haiku-latest',
+  'claude-3-5-sonnet-latest',
+  'claude-3-opus-latest',
+];
+
+export const GEMINI_MODELS = [
+  'gemini-2.0-flash',
+  'gemini-2.0-flash-lite',
+  'gemini-1.5-pro',
+];
@@ -6,8 +6,7 @@
 
 import React, { createContext, useContext, useState, useCallback, useEffect, useMemo } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
-import { HotkeyConfig } from '../types/hotkey';
+import { HotkeyConfig, ShortcutValidationResult } from '../types/hotkey';
 
 type SaveStatus = 'idle' | 'saving' | 'saved' | 'error';
 
@@ -26,8 +25,15 @@ interface SettingsContextValue {
   saveConfig: () => Promise<void>;
   /** Update hotkey configuration and auto-save */
   updateHotkey: (hotkey: HotkeyConfig) => void;
+  /** Remap a global action shortcut; rejects on conflict or registration failure */
+  updateShortcut: (action: ShortcutAction, hotkey: HotkeyConfig) => Promise<void>;
+  /** Check a shortcut string (e.g. "Alt+3") before saving it; owner is the binding being edited */
+  validateShortcut: (shortcutStr: string, owner?: ShortcutAction | 'push_to_talk') => Promise<ShortcutValidationResult>;
 }
 
+/** Global shortcut actions other than push-to-talk */
+export type ShortcutAction = 'toggle_window' | 'toggle_work_mode' | 'toggle_recording_mode' | 'paste_transcript' | 'transcription_picker' | 'toggle_history' | 'ask_selection' | 'toggle_dictation' | 'undo_last_typing';
+
 const SettingsContext = createContext<SettingsContextValue | undefined>(undefined);
 
 /**
@@ -72,9 +78,8 @@ export function SettingsProvider({ children }: { children: React.ReactNode }) {
   const [config, setConfig] = useState<Record<string, any> | null>(null);
   const [saveStatus, setSaveStatus] = useState<SaveStatus>('idle');
   const [saveError, setSaveError] = useState<string | null>(null);
-  const [daemonReady, setDaemonReady] = useState(false);
 
-  // Load config on mount (only after daemon is ready)
+  // Load config on mount (read by the Rust side, no need to wait for the daemon)
   useEffect(() => {
     const loadConfig = async () => {
       try {
@@ -99,31 +104,11 @@ export function SettingsProvider({ children }: { children: React.ReactNode }) {
           setConfig(configWithDefaults);
         }
       } catch (error) {
-        // Silently ignore errors during daemon initialization
-        const errorMessage = error instanceof Error ? error.message : String(error);
-        if (!errorMessage.includes('语音服务正在启动中')) {
-          console.error('[Settings] Failed to load config:', error);
-        }
+        console.error('[Settings] Failed to load config:', error);
       }
     };
 
-    // Only load config after daemon is ready
-    if (daemonReady) {
-      loadConfig();
-    }
-  }, [daemonReady]);
-
-  // Listen for daemon status events
-  useEffect(() => {
-    const unlistenPromise = listen<{ status: string; message: string }>('daemon-status', (event) => {
-      if (event.payload.status === 'ready') {
-        setDaemonReady(true);
-      }
-    });
-
-    return () => {
-      unlistenPromise.then(unlisten => unlisten());
-    };
+    loadConfig();
   }, []);
 
   // Create debounced save function
@@ -241,6 +226,33 @@ export function SettingsProvider({ children }: { children: React.ReactNode }) {
     });
   }, [debouncedSave]);
 
+  /**
+   * Update a global action shortcut
+   *
+   * The backend validates and saves the shortcut, so only local state is updated here.
+   */
+  const updateShortcut = useCallback(async (action: ShortcutAction, hotkey: HotkeyConfig) => {
+    await invoke<string>('update_shortcut', { action, hotkeyConfig: hotkey });
+
+    setConfig(prev => {
+      if (!prev) return prev;
+
+      return {
+        ...prev,
+        shortcuts: {
+          ...(prev.shortcuts || {}),
+          [action]: hotkey,
+        },
+      };
+    });
+  }, []);
+
+  const validateShortcut = useCallback(
+    (shortcutStr: string, owner?: ShortcutAction | 'push_to_talk') =>
+      invoke<ShortcutValidationResult>('validate_shortcut', { shortcutStr, owner }),
+    []
+  );
+
   const contextValue: SettingsContextValue = {
     config,
     saveStatus,
@@ -249,6 +261,8 @@ export function SettingsProvider({ children }: { children: React.ReactNode }) {
     updateConfigBatch,
     saveConfig,
     updateHotkey,
+    updateShortcut,
+    validateShortcut,
   };
 
   return (
@@ -1,5 +1,6 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
+import { listen } from "@tauri-apps/api/event";
 
 // Dev tool: react-grab - press Cmd/Ctrl+C to grab component context for AI coding
 // Note: "Broken pipe" error may appear in Tauri due to unavailable stdin, but it's harmless
@@ -9,22 +10,41 @@ if (import.meta.env.DEV) {
 
 import "./index.css";
 import App from "./App";
+import { HistoryDrawer } from "./components/HistoryDrawer";
+import { PinnedPanel } from "./components/PinnedPanel";
+import { historyAPI } from "./useTauriAPI";
 import { ThemeProvider } from "./components/ThemeProvider";
 import { WorkModeProvider } from "./contexts/WorkModeContext";
 import { SettingsProvider } from "./contexts/SettingsContext";
 import { ErrorProvider } from "./contexts/ErrorContext";
 import "./i18n"; // Initialize i18n
 
+// The standalone history and pinned answers windows load index.html?window=history / ?window=pinned
+const windowKind = new URLSearchParams(window.location.search).get("window");
+
+const root = windowKind === "history" ? (
+  <ThemeProvider defaultTheme="dark" storageKey="speekium-theme">
+    <HistoryDrawer isOpen variant="window" onClose={() => historyAPI.closeWindow()} />
+  </ThemeProvider>
+) : windowKind === "pinned" ? (
+  <ThemeProvider defaultTheme="dark" storageKey="speekium-theme">
+    <PinnedPanel onClose={() => historyAPI.closePinnedWindow()} />
+  </ThemeProvider>
+) : (
+  <ThemeProvider defaultTheme="dark" storageKey="speekium-theme">
+    <ErrorProvider>
+      <SettingsProvider>
+        <WorkModeProvider>
+          <App />
+        </WorkModeProvider>
+      </SettingsProvider>
+    </ErrorProvider>
+  </ThemeProvider>
+);
+
+// Settings, shortcuts and history all change with the profile: start over
+listen("profile-switched", () => window.location.reload());
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
-  <React.StrictMode>
-    <ThemeProvider defaultTheme="dark" storageKey="speekium-theme">
-      <ErrorProvider>
-        <SettingsProvider>
-          <WorkModeProvider>
-            <App />
-          </WorkModeProvider>
-        </SettingsProvider>
-      </ErrorProvider>
-    </ThemeProvider>
-  </React.StrictMode>,
+  <React.StrictMode>{root}</React.StrictMode>,
 );
@@ -28,3 +28,14 @@ export interface HotkeyValidation {
   valid: boolean;
   error?: string;
 }
+
+/**
+ * Backend dry-run result of `validate_shortcut`
+ */
+export interface ShortcutValidationResult {
+  valid: boolean;
+  shortcut: string;
+  reason: 'invalid' | 'in_use' | 'os_rejected' | null;
+  conflict_with: string | null;
+  error: string | null;
+}
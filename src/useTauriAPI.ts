@@ -22,12 +22,26 @@ interface RecordingResult {
   text?: string;
   language?: string;
   error?: string;
+  // 0-1, when the ASR model reports one
+  confidence?: number | null;
+  // Other hypotheses, best first
+  alternatives?: string[];
+  words?: WordTiming[];
+  segments?: SpeakerSegment[];
+}
+
+// Timing of one recognized word, in ms from the start of the recording
+export interface WordTiming {
+  word: string;
+  start_ms: number;
+  end_ms: number;
 }
 
 interface ChatResult {
   success: boolean;
   content?: string;
   error?: string;
+  message_id?: string;  // Reply already saved to the session while streaming
 }
 
 interface TTSResult {
@@ -51,6 +65,7 @@ interface Session {
   is_favorite?: boolean;
   created_at: number;
   updated_at: number;
+  deleted_at?: number;  // Set while the session is in the trash
 }
 
 interface HistoryMessage {
@@ -59,6 +74,30 @@ interface HistoryMessage {
   role: 'user' | 'assistant' | 'system';
   content: string;
   timestamp: number;
+  audio_path?: string;  // Archived recording (user messages, when audio archive is enabled)
+  language?: string;  // ASR language of spoken user messages
+  segments?: SpeakerSegment[];  // Per-speaker parts of a diarized recording
+  pinned?: boolean;  // Shown in the pinned answers window
+  status?: 'draft' | 'interrupted';  // Streamed reply still arriving or cut off
+}
+
+// Pinned message with its session title (pinned answers window)
+export interface PinnedMessage {
+  id: string;
+  session_id: string;
+  session_title: string;
+  role: 'user' | 'assistant' | 'system';
+  content: string;
+  timestamp: number;
+  pinned_at: number;
+}
+
+// Part of a transcript spoken by one speaker, times in ms from the start of the recording
+export interface SpeakerSegment {
+  speaker: number;  // 1-based ("Speaker 1")
+  start_ms: number;
+  end_ms: number;
+  text: string;
 }
 
 interface PaginatedResult<T> {
@@ -67,6 +106,267 @@ interface PaginatedResult<T> {
   has_more: boolean;
 }
 
+type ExportFormat = 'markdown' | 'json' | 'html';
+
+interface ImportSummary {
+  sessions_imported: number;
+  sessions_skipped: number;
+  messages_imported: number;
+  messages_skipped: number;
+}
+
+// Crash report left by the previous run (panic or daemon exit)
+export interface CrashReport {
+  kind: 'panic' | 'daemon_exit';
+  timestamp: string;
+  app_version: string;
+  os: string;
+  message: string;
+  location: string | null;
+  thread: string | null;
+  backtrace: string | null;
+  daemon_stderr_tail: string[];
+  init_timeline: Array<{ event: string; elapsed_ms: number; duration_ms: number }>;
+}
+
+// p50/p95 of one pipeline stage (ms); null when no samples
+export interface LatencyStageStats {
+  samples: number;
+  p50_ms: number | null;
+  p95_ms: number | null;
+}
+
+// Push-to-talk latency per stage, each measured from the previous stage
+export interface LatencyStats {
+  interactions: number;
+  asr: LatencyStageStats;
+  llm_first_token: LatencyStageStats;
+  tts_first_audio: LatencyStageStats;
+  playback_start: LatencyStageStats;
+  end_to_end: LatencyStageStats;
+}
+
+export type UsageRange = 'day' | 'week' | 'month' | 'year' | 'all';
+
+// Activity of one local calendar day
+export interface DailyActivity {
+  date: string;  // YYYY-MM-DD
+  recordings: number;
+  words: number;
+}
+
+// Dictation usage over a range, for the stats page
+export interface UsageStats {
+  range: UsageRange;
+  since?: number;  // Start of the range (ms), absent for 'all'
+  recordings: number;  // dictations + conversation_turns
+  dictations: number;
+  conversation_turns: number;
+  words: number;
+  avg_asr_ms?: number;
+  avg_end_to_end_ms?: number;
+  most_used_mode?: 'conversation' | 'text-input';
+  time_saved_secs: number;  // Typing time (40 wpm) minus speaking time (150 wpm)
+  daily: DailyActivity[];  // One bucket per day, oldest first
+}
+
+export type StorageCategory = 'recordings' | 'tts_temp' | 'tts_cache' | 'audio_archive';
+
+// Disk space used by generated audio
+export interface StorageUsage {
+  categories: { category: StorageCategory; path: string; files: number; bytes: number }[];
+  total_bytes: number;
+}
+
+export interface StorageCleanResult {
+  removed_files: number;
+  freed_bytes: number;
+}
+
+export interface Persona {
+  id: number;
+  name: string;
+  system_prompt: string;
+  voice: string | null;  // null = configured voice
+  speed: number;         // Speech rate multiplier (1.0 = normal)
+  created_at: number;
+  updated_at: number;
+}
+
+// Prompt run on a spoken utterance by its hotkey ({text} = the utterance)
+export type PromptTemplateOutput = 'speak' | 'type' | 'window';
+
+export interface PromptTemplate {
+  id: number;
+  name: string;
+  template: string;
+  hotkey: string | null;  // e.g. "Alt+8"; null = run from the UI only
+  output: PromptTemplateOutput;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface Reminder {
+  id: number;
+  text: string;
+  // Cron expression or date/time as entered
+  schedule: string;
+  recurring: boolean;
+  // Unix ms; null once a one-time reminder has fired
+  next_run: number | null;
+  created_at: number;
+}
+
+export interface PendingRecording {
+  id: number;
+  path: string;
+  duration_secs: number;
+  // 'pending' (not transcribed yet) or 'failed' (ASR returned an error)
+  status: 'pending' | 'failed';
+  created_at: number;
+}
+
+export interface RecoveredRecording {
+  id: number;
+  created_at: number;
+  text: string | null;
+  // ASR failed again; the recording stays in the journal
+  error: string | null;
+}
+
+export interface MessageAlternatives {
+  message_id: string;
+  // 0-1, null if the model reports none (or a typed message)
+  confidence: number | null;
+  // Post-processed ASR hypotheses, best first
+  alternatives: string[];
+}
+
+// Meeting transcription state (also sent as the 'meeting-state' event)
+export interface MeetingStatus {
+  active: boolean;
+  // Session the transcripts are saved to; each one arrives as a 'meeting-transcript' event
+  session_id: string | null;
+  started_at: number | null;
+}
+
+// Payload of the 'dictation-append' event ('dictation-state' carries { active })
+export interface DictationAppendPayload {
+  text: string;
+  // Everything dictated so far
+  document: string;
+}
+
+// Queued file transcription, also the payload of the 'file-transcription-progress' event
+export interface TranscriptionJob {
+  id: string;
+  path: string;
+  // File name without extension, also the title of the new session
+  file_name: string;
+  status: 'queued' | 'decoding' | 'transcribing' | 'done' | 'failed' | 'cancelled';
+  percent: number;
+  session_id: string | null;
+  error: string | null;
+  created_at: number;
+  updated_at: number;
+}
+
+// Payload of the 'tts-export-progress' event (chunks synthesized so far)
+export interface TtsExportProgress {
+  done: number;
+  total: number;
+}
+
+// Session removed by the history retention policy
+export interface RetentionSession {
+  id: string;
+  title: string;
+  updated_at: number;
+  reason: 'age' | 'not_favorite' | 'message_cap';
+}
+
+// Result of runRetentionNow, also the payload of the 'history-retention-applied' event
+export interface RetentionReport {
+  dry_run: boolean;
+  sessions: RetentionSession[];
+  // Messages removed, including those of the removed sessions
+  messages: number;
+}
+
+// A profile: its own settings, shortcuts and history ('default' is the original data)
+export interface Profile {
+  id: string;
+  name: string;
+  created_at: number;
+}
+
+export interface ProfileList {
+  active: string;
+  profiles: Profile[];  // 'default' first
+}
+
+// Custom vocabulary term, sent to the ASR as a hotword (per profile)
+export interface VocabularyTerm {
+  id: number;
+  term: string;
+  created_at: number;
+}
+
+// Spoken phrase typed as a symbol in text-input mode (match Rust SymbolShortcut)
+export interface SymbolShortcut {
+  id: number;
+  phrase: string;
+  symbol: string;
+  created_at: number;
+}
+
+// Newer version on the configured update channel
+export interface UpdateInfo {
+  version: string;
+  current_version: string;
+  channel: 'stable' | 'beta';
+  notes: string | null;
+  pub_date: string | null;
+}
+
+// `update-download-progress` event payload
+export interface UpdateProgress {
+  version: string;
+  downloaded: number; // bytes
+  total: number | null; // null when the server doesn't send the size
+  percent: number | null;
+}
+
+export interface SelfTestStage {
+  stage: 'daemon' | 'microphone' | 'asr' | 'llm' | 'tts' | 'playback';
+  passed: boolean;
+  // Not run because a stage it needs failed
+  skipped: boolean;
+  duration_ms: number;
+  detail: string | null;
+  error: string | null;
+}
+
+export interface SelfTestReport {
+  passed: boolean;
+  stages: SelfTestStage[];
+  total_ms: number;
+}
+
+// Payload of the `app-status-changed` event (also the entries of getStatusHistory)
+export interface StatusTransition {
+  from: string;
+  to: string;
+  source: string;
+  timestamp: number;
+}
+
+// Payload of the `session-title-updated` event
+export interface SessionTitleUpdated {
+  session_id: string;
+  title: string;
+}
+
 // ============================================================================
 // Main Hook
 // ============================================================================
@@ -237,7 +537,8 @@ export function useTauriAPI() {
     setIsProcessing(false);
   };
 
-  const chatGenerator = async (text: string, _language: string = 'auto', useStreaming: boolean = true, useTTS: boolean = false) => {
+  // With a sessionId, a streamed reply is saved to the session as it arrives
+  const chatGenerator = async (text: string, _language: string = 'auto', useStreaming: boolean = true, useTTS: boolean = false, sessionId?: string) => {
     setIsProcessing(true);
 
     try {
@@ -266,7 +567,7 @@ export function useTauriAPI() {
       }
 
       // Streaming mode
-      return await chatStream(text);
+      return await chatStream(text, sessionId);
 
     } catch (error) {
       console.error('[Chat] Error:', error);
@@ -276,7 +577,7 @@ export function useTauriAPI() {
     }
   };
 
-  const chatStream = async (text: string) => {
+  const chatStream = async (text: string, sessionId?: string) => {
     return new Promise<ChatResult>(async (resolve, reject) => {
       let fullResponse = '';
       let assistantMessageAdded = false;
@@ -285,6 +586,7 @@ export function useTauriAPI() {
       let unlistenChunk: (() => void) | null = null;
       let unlistenDone: (() => void) | null = null;
       let unlistenError: (() => void) | null = null;
+      let unlistenCancelled: (() => void) | null = null;
 
       // Listen for streaming events
       const { listen } = await import('@tauri-apps/api/event');
@@ -327,15 +629,18 @@ export function useTauriAPI() {
           });
         });
 
-        unlistenDone = await listen('chat-done', () => {
+        // Payload: id of the saved reply (null without a session)
+        unlistenDone = await listen<string | null>('chat-done', (event) => {
           // Safely unlisten all
           unlistenChunk?.();
           unlistenDone?.();
           unlistenError?.();
+          unlistenCancelled?.();
 
           resolve({
             success: true,
-            content: fullResponse
+            content: fullResponse,
+            message_id: event.payload ?? undefined
           });
         });
 
@@ -345,23 +650,40 @@ export function useTauriAPI() {
           unlistenChunk?.();
           unlistenDone?.();
           unlistenError?.();
+          unlistenCancelled?.();
 
           reject(new Error(event.payload));
         });
 
+        // Interrupted: keep what was received so far
+        unlistenCancelled = await listen<string>('stream-cancelled', (event) => {
+          if (event.payload !== 'chat_stream') return;
+          unlistenChunk?.();
+          unlistenDone?.();
+          unlistenError?.();
+          unlistenCancelled?.();
+
+          resolve({
+            success: false,
+            content: fullResponse,
+            error: 'Cancelled'
+          });
+        });
+
         // Call Rust command to start streaming response
-        await invoke('chat_llm_stream', { text });
+        await invoke('chat_llm_stream', { text, sessionId });
       } catch (error) {
         // Safely unlisten all (only if they were set up)
         unlistenChunk?.();
         unlistenDone?.();
         unlistenError?.();
+        unlistenCancelled?.();
         reject(error);
       }
     });
   };
 
-  const chatTTSStream = async (text: string) => {
+  const chatTTSStream = async (text: string, params?: SpeechParams) => {
     return new Promise<ChatResult>(async (resolve, reject) => {
       let fullResponse = '';
       let assistantMessageAdded = false;
@@ -371,6 +693,7 @@ export function useTauriAPI() {
       let unlistenAudioChunk: (() => void) | null = null;
       let unlistenDone: (() => void) | null = null;
       let unlistenError: (() => void) | null = null;
+      let unlistenCancelled: (() => void) | null = null;
 
       // Listen for streaming events
       const { listen } = await import('@tauri-apps/api/event');
@@ -425,6 +748,7 @@ export function useTauriAPI() {
           unlistenAudioChunk?.();
           unlistenDone?.();
           unlistenError?.();
+          unlistenCancelled?.();
 
           resolve({
             success: true,
@@ -439,28 +763,47 @@ export function useTauriAPI() {
           unlistenAudioChunk?.();
           unlistenDone?.();
           unlistenError?.();
+          unlistenCancelled?.();
 
           reject(new Error(event.payload));
         });
 
+        // Interrupted: drop queued audio, keep the text received so far
+        unlistenCancelled = await listen<string>('stream-cancelled', (event) => {
+          if (event.payload !== 'chat_tts_stream') return;
+          unlistenTextChunk?.();
+          unlistenAudioChunk?.();
+          unlistenDone?.();
+          unlistenError?.();
+          unlistenCancelled?.();
+
+          setAudioQueue([]);
+          resolve({
+            success: false,
+            content: fullResponse,
+            error: 'Cancelled'
+          });
+        });
+
         // Call Rust command to start TTS streaming response
-        await invoke('chat_tts_stream', { text, autoPlay: true });
+        await invoke('chat_tts_stream', { text, autoPlay: true, params });
       } catch (error) {
         // Safely unlisten all (only if they were set up)
         unlistenTextChunk?.();
         unlistenAudioChunk?.();
         unlistenDone?.();
         unlistenError?.();
+        unlistenCancelled?.();
         reject(error);
       }
     });
   };
 
-  const generateTTS = async (text: string) => {
+  const generateTTS = async (text: string, params?: SpeechParams) => {
     try {
       setIsSpeaking(true);
 
-      const result = await invoke<TTSResult>('generate_tts', { text });
+      const result = await invoke<TTSResult>('generate_tts', { text, params });
 
       if (result.success && result.audio_path) {
         await playAudio(result.audio_path);
@@ -664,19 +1007,22 @@ export function useTauriAPI() {
 }
 
 // Export types for use in components
-export type { Session, HistoryMessage, PaginatedResult };
+export type { Session, HistoryMessage, PaginatedResult, ExportFormat, ImportSummary };
 
 // ============================================================================
 // Standalone History API (can be used outside of hook)
 // ============================================================================
 
 export const historyAPI = {
-  listSessions: async (page: number = 1, pageSize: number = 20, filterFavorite?: boolean) => {
-    console.log('[historyAPI] listSessions called with:', { page, pageSize, filterFavorite });
+  listSessions: async (page: number = 1, pageSize: number = 20, filterFavorite?: boolean, trashed?: boolean) => {
+    console.log('[historyAPI] listSessions called with:', { page, pageSize, filterFavorite, trashed });
     const params: Record<string, any> = { page, pageSize };
     if (filterFavorite !== undefined) {
       params.filterFavorite = filterFavorite;
     }
+    if (trashed !== undefined) {
+      params.trashed = trashed;
+    }
     console.log('[historyAPI] Invoking with params:', params);
     const result = await invoke<PaginatedResult<Session>>('db_list_sessions', params);
     console.log('[historyAPI] Invoke result:', result);
@@ -709,6 +1055,19 @@ export const historyAPI = {
     await invoke('db_delete_session', { sessionId });
   },
 
+  // Trashed sessions are purged automatically after 30 days
+  trashSession: async (sessionId: string) => {
+    return await invoke<boolean>('db_trash_session', { sessionId });
+  },
+
+  restoreSession: async (sessionId: string) => {
+    return await invoke<boolean>('db_restore_session', { sessionId });
+  },
+
+  emptyTrash: async () => {
+    return await invoke<number>('db_empty_trash');
+  },
+
   getSessionMessages: async (sessionId: string, page: number = 1, pageSize: number = 100) => {
     const result = await invoke<PaginatedResult<HistoryMessage>>('db_get_messages', {
       sessionId,
@@ -727,8 +1086,773 @@ export const historyAPI = {
     return result;
   },
 
-  exportConversation: async (sessionId: string) => {
-    const result = await invoke<string>('export_conversation', { sessionId });
+  // Asset URL of the message's archived recording for replay, null if it has none
+  getMessageAudioUrl: async (messageId: string) => {
+    const path = await invoke<string | null>('get_message_audio', { messageId });
+    return path ? convertFileSrc(path) : null;
+  },
+
+  // Sends prior session messages as context; user message and reply are saved to the session
+  chatWithHistory: async (sessionId: string, text: string) => {
+    const result = await invoke<ChatResult>('chat_llm_with_history', { sessionId, text });
+    return result;
+  },
+
+  // Runs in the background; listen for `session-title-updated` to get the new title
+  autoTitleSession: async (sessionId: string) => {
+    await invoke('db_auto_title_session', { sessionId });
+  },
+
+  // Returns the saved file path, or null if the save dialog was cancelled
+  exportSession: async (sessionId: string, format: ExportFormat = 'markdown') => {
+    const result = await invoke<string | null>('db_export_session', { sessionId, format });
+    return result;
+  },
+
+  exportAll: async (format: ExportFormat = 'json') => {
+    const result = await invoke<string | null>('db_export_all', { format });
+    return result;
+  },
+
+  // Opens a file picker when no paths are given; null if cancelled
+  importSessions: async (paths?: string[]) => {
+    const result = await invoke<ImportSummary | null>('db_import_sessions', { paths });
+    return result;
+  },
+
+  // Standalone history window (stays open next to other apps)
+  openWindow: async () => {
+    await invoke('open_history_window');
+  },
+
+  closeWindow: async () => {
+    await invoke('close_history_window');
+  },
+
+  pinMessage: async (messageId: string, pinned: boolean) => {
+    return await invoke<boolean>('db_pin_message', { messageId, pinned });
+  },
+
+  listPinned: async () => {
+    return await invoke<PinnedMessage[]>('db_list_pinned');
+  },
+
+  // Latest streamed reply of the session that is unfinished or was cut off (null if none)
+  getDraft: async (sessionId: string) => {
+    return await invoke<HistoryMessage | null>('db_get_draft', { sessionId });
+  },
+
+  openPinnedWindow: async () => {
+    await invoke('open_pinned_window');
+  },
+
+  closePinnedWindow: async () => {
+    await invoke('close_pinned_window');
+  },
+};
+
+export const diagnosticsAPI = {
+  getLastCrashReport: async () => {
+    const result = await invoke<CrashReport | null>('get_last_crash_report');
+    return result;
+  },
+
+  // send=true queues the report in the error log before removing it
+  clearCrashReport: async (send: boolean) => {
+    await invoke('clear_crash_report', { send });
+  },
+
+  // Last 100 pipeline status changes, oldest first
+  getStatusHistory: async () => {
+    const result = await invoke<StatusTransition[]>('get_status_history');
+    return result;
+  },
+};
+
+export const metricsAPI = {
+  // Summarizes the most recent `limit` interactions (default 100)
+  getLatencyStats: async (limit?: number) => {
+    const result = await invoke<LatencyStats>('get_latency_stats', { limit });
+    return result;
+  },
+
+  getUsageStats: async (range: UsageRange = 'week') => {
+    const result = await invoke<UsageStats>('get_usage_stats', { range });
+    return result;
+  },
+};
+
+export const storageAPI = {
+  getStorageUsage: async () => {
+    const result = await invoke<StorageUsage>('get_storage_usage');
+    return result;
+  },
+
+  // All categories when none are given; olderThanDays keeps newer files
+  cleanStorage: async (categories?: StorageCategory[], olderThanDays?: number) => {
+    const result = await invoke<StorageCleanResult>('clean_storage', { categories, olderThanDays });
+    return result;
+  },
+};
+
+export const personaAPI = {
+  // voice: null = configured voice; speed: 0.5-2.0 (default 1.0)
+  createPersona: async (name: string, systemPrompt: string, voice?: string | null, speed?: number) => {
+    const result = await invoke<Persona>('create_persona', { name, systemPrompt, voice, speed });
+    return result;
+  },
+
+  listPersonas: async () => {
+    const result = await invoke<Persona[]>('list_personas');
+    return result;
+  },
+
+  updatePersona: async (id: number, name: string, systemPrompt: string, voice?: string | null, speed?: number) => {
+    const result = await invoke<Persona>('update_persona', { id, name, systemPrompt, voice, speed });
+    return result;
+  },
+
+  deletePersona: async (id: number) => {
+    const result = await invoke<boolean>('delete_persona', { id });
+    return result;
+  },
+
+  // null = built-in system prompt and configured voice
+  getActivePersona: async () => {
+    const result = await invoke<Persona | null>('get_active_persona');
+    return result;
+  },
+
+  setActivePersona: async (id: number | null) => {
+    await invoke('set_active_persona', { id });
+  },
+};
+
+export const promptTemplateAPI = {
+  // output defaults to 'window' (the ask-selection popover)
+  createTemplate: async (name: string, template: string, hotkey?: string | null, output?: PromptTemplateOutput) => {
+    const result = await invoke<PromptTemplate>('create_prompt_template', { name, template, hotkey, output });
+    return result;
+  },
+
+  listTemplates: async () => {
+    const result = await invoke<PromptTemplate[]>('list_prompt_templates');
+    return result;
+  },
+
+  updateTemplate: async (
+    id: number,
+    name: string,
+    template: string,
+    hotkey?: string | null,
+    output?: PromptTemplateOutput,
+  ) => {
+    const result = await invoke<PromptTemplate>('update_prompt_template', { id, name, template, hotkey, output });
+    return result;
+  },
+
+  deleteTemplate: async (id: number) => {
+    const result = await invoke<boolean>('delete_prompt_template', { id });
+    return result;
+  },
+
+  // Run on typed text instead of an utterance
+  runTemplate: async (id: number, text: string) => {
+    await invoke('run_prompt_template', { id, text });
+  },
+};
+
+export const remindersAPI = {
+  // schedule: 'YYYY-MM-DD HH:MM' (local), RFC 3339, or a cron expression like '30 9 * * Mon-Fri'
+  createReminder: async (text: string, schedule: string) => {
+    const result = await invoke<Reminder>('create_reminder', { text, schedule });
+    return result;
+  },
+
+  listReminders: async () => {
+    const result = await invoke<Reminder[]>('list_reminders');
+    return result;
+  },
+
+  deleteReminder: async (id: number) => {
+    const result = await invoke<boolean>('delete_reminder', { id });
+    return result;
+  },
+};
+
+// API keys live in the OS keychain; load_config/save_config also round-trip
+// them through the `api_key` fields of llm_providers
+export const secretsAPI = {
+  // An empty key deletes the stored one
+  setApiKey: async (provider: string, apiKey: string) => {
+    await invoke('set_api_key', { provider, apiKey });
+  },
+
+  getApiKey: async (provider: string) => {
+    const result = await invoke<string | null>('get_api_key', { provider });
+    return result;
+  },
+
+  deleteApiKey: async (provider: string) => {
+    const result = await invoke<boolean>('delete_api_key', { provider });
+    return result;
+  },
+};
+
+export type PermissionKind = 'microphone' | 'accessibility' | 'input_monitoring';
+
+export interface PermissionInfo {
+  kind: PermissionKind;
+  status: 'granted' | 'denied' | 'not_determined' | 'not_required' | 'unknown';
+  required: boolean;
+  purpose: string;
+  can_open_settings: boolean;
+}
+
+export const permissionsAPI = {
+  check: async () => {
+    const result = await invoke<PermissionInfo[]>('check_permissions');
+    return result;
+  },
+
+  // Shows the OS prompt if the permission was never asked for
+  request: async (kind: PermissionKind) => {
+    const result = await invoke<PermissionInfo>('request_permission', { kind });
+    return result;
+  },
+
+  openSettings: async (kind: PermissionKind) => {
+    await invoke('open_permission_settings', { kind });
+  },
+};
+
+export interface Connectivity {
+  offline: boolean;
+  offline_mode: boolean;
+  reachable: boolean;
+}
+
+export const networkAPI = {
+  // Remote providers fail with an "Offline: ..." error while offline is true
+  getConnectivity: async () => {
+    const result = await invoke<Connectivity>('get_connectivity');
+    return result;
+  },
+};
+
+export interface Transcription {
+  id: number;
+  text: string;
+  created_at: number;
+}
+
+// Text-input dictations (also shown in the transcription picker window)
+export const transcriptionsAPI = {
+  list: async (page: number, pageSize?: number) => {
+    const result = await invoke<PaginatedResult<Transcription>>('list_transcriptions', { page, pageSize });
+    return result;
+  },
+
+  delete: async (id: number) => {
+    const result = await invoke<boolean>('delete_transcription', { id });
+    return result;
+  },
+
+  // Types the dictation into the app the picker was opened over
+  paste: async (id: number) => {
+    await invoke('paste_transcription', { id });
+  },
+};
+
+// Ask Selection types (matches Rust SelectionAnswer)
+export interface SelectionAnswer {
+  template: string;
+  selection: string;
+  answer: string | null; // null while the LLM is answering
+  error: string | null;
+}
+
+export const selectionAPI = {
+  // Copies the selection of the focused app and runs a template on it (default: ask_selection.template)
+  ask: async (template?: string) => {
+    await invoke('ask_selection', { template });
+  },
+
+  listTemplates: async () => {
+    const result = await invoke<string[]>('list_selection_templates');
+    return result;
+  },
+
+  getAnswer: async () => {
+    const result = await invoke<SelectionAnswer | null>('get_selection_answer');
+    return result;
+  },
+};
+
+// ASR language types (matches Rust AsrLanguageInfo)
+export interface AsrLanguageInfo {
+  language: string; // 'auto' or a forced language
+  languages: string[]; // expected languages (asr_languages)
+  session_language: string | null; // used for recordings while language is 'auto'
+  supported: string[];
+}
+
+export const asrLanguageAPI = {
+  get: async () => {
+    const result = await invoke<AsrLanguageInfo>('get_asr_language');
+    return result;
+  },
+
+  set: async (language: string) => {
+    const result = await invoke<AsrLanguageInfo>('set_asr_language', { language });
+    return result;
+  },
+
+  // Later recordings prefer the language the session was spoken in
+  setSession: async (sessionId: string | null) => {
+    const result = await invoke<string | null>('set_asr_session', { sessionId });
+    return result;
+  },
+};
+
+// TTS voice types (match Rust TtsVoice / SpeechParams)
+export interface TtsVoice {
+  id: string; // e.g. 'en-US-JennyNeural'
+  name: string;
+  locale: string;
+  gender: string;
+}
+
+// Per-request overrides of the configured voice and speech parameters
+export interface SpeechParams {
+  voice?: string;
+  speed?: number; // 0.5 - 2.0 multiplier
+  pitch?: number; // -100 - 100 Hz
+  volume?: number; // -100 - 100 percent
+}
+
+export const ttsVoicesAPI = {
+  list: async (refresh?: boolean) => {
+    const result = await invoke<TtsVoice[]>('list_tts_voices', { refresh });
+    return result;
+  },
+
+  // 'auto' picks the voice by the language of the text
+  setVoice: async (voiceId: string) => {
+    await invoke('set_tts_voice', { voiceId });
+  },
+
+  // Generates a sample without changing the settings; play result.audio_path
+  preview: async (voiceId: string, sampleText?: string, params?: SpeechParams) => {
+    const result = await invoke<TTSResult>('preview_voice', { voiceId, sampleText, params });
+    return result;
+  },
+};
+
+// Sound cue names (match Rust earcons::Cue)
+export type CueName = 'record_start' | 'record_stop' | 'error' | 'complete';
+
+export const earconsAPI = {
+  // Skipped silently if the cue is disabled in the earcons settings
+  play: async (name: CueName) => {
+    await invoke('play_cue', { name });
+  },
+};
+
+// Continuous listening pause (match Rust ListeningPausedPayload, event `listening-paused`)
+export interface ListeningPaused {
+  paused: boolean;
+  reason: 'manual' | 'dnd' | 'app' | 'microphone' | 'override' | null;
+  app: string | null;
+  // End of a manual pause (Unix ms); null = until resumed
+  until: number | null;
+}
+
+export const focusPauseAPI = {
+  get: async () => {
+    const result = await invoke<ListeningPaused>('get_listening_paused');
+    return result;
+  },
+
+  // 'pause' / 'resume' force the state until set back to 'auto' (not saved)
+  setOverride: async (mode: 'auto' | 'pause' | 'resume') => {
+    const result = await invoke<ListeningPaused>('set_dnd_override', { mode });
+    return result;
+  },
+
+  // Omit durationSecs to pause until resume() (app status reads 'paused' meanwhile)
+  pause: async (durationSecs?: number) => {
+    const result = await invoke<ListeningPaused>('pause_listening', { durationSecs });
+    return result;
+  },
+
+  resume: async () => {
+    const result = await invoke<ListeningPaused>('resume_listening');
+    return result;
+  },
+};
+
+// Latest daemon memory / CPU sample (match Rust DaemonResources)
+export interface DaemonResources {
+  pid: number;
+  rss_bytes: number;
+  cpu_percent: number | null; // since the previous sample (100 = one core)
+  sampled_at: number;
+  memory_limit_mb: number; // 0 = no limit
+  restart_pending: boolean; // over the limit: restarts once idle
+}
+
+export const daemonResourcesAPI = {
+  // null until the daemon has been sampled (every 10s)
+  get: async () => {
+    const result = await invoke<DaemonResources | null>('get_daemon_resources');
+    return result;
+  },
+};
+
+// Start at login (OS setting; `start_minimized` in the config hides the window at startup)
+export const autostartAPI = {
+  get: async () => {
+    const result = await invoke<boolean>('get_autostart');
+    return result;
+  },
+
+  // Returns the state after the change
+  set: async (enabled: boolean) => {
+    const result = await invoke<boolean>('set_autostart', { enabled });
+    return result;
+  },
+};
+
+// Recordings a crash or failed ASR left untranscribed
+export const recordingJournalAPI = {
+  list: async () => {
+    const result = await invoke<PendingRecording[]>('list_pending_recordings');
+    return result;
+  },
+
+  // Transcripts are also saved to the dictation history
+  recover: async () => {
+    const result = await invoke<RecoveredRecording[]>('recover_pending_recordings');
+    return result;
+  },
+
+  // Returns the number of discarded recordings
+  discard: async () => {
+    const result = await invoke<number>('discard_pending_recordings');
+    return result;
+  },
+};
+
+export const asrAlternativesAPI = {
+  // Alternatives the user can pick from when a spoken message was misrecognized
+  get: async (messageId: string) => {
+    const result = await invoke<MessageAlternatives>('get_message_alternatives', { messageId });
+    return result;
+  },
+};
+
+export const wordTimingsAPI = {
+  // Highlight words in sync with the archived recording of a spoken message
+  get: async (messageId: string) => {
+    const result = await invoke<WordTiming[]>('get_message_word_timings', { messageId });
+    return result;
+  },
+};
+
+export const meetingAPI = {
+  getStatus: async () => {
+    const result = await invoke<MeetingStatus>('get_meeting_status');
+    return result;
+  },
+
+  // Rejects if the system audio cannot be captured (macOS before 14.6, Linux)
+  start: async () => {
+    await invoke('set_recording_mode', { mode: 'meeting' });
+    await invoke('update_recording_mode', { mode: 'meeting' });
+  },
+
+  // Transcribes the last chunk, then ends the meeting
+  stop: async (mode: 'push-to-talk' | 'continuous' = 'push-to-talk') => {
+    await invoke('set_recording_mode', { mode });
+    await invoke('update_recording_mode', { mode });
+  },
+};
+
+export const dictationAPI = {
+  // Rejects if the microphone cannot be opened
+  start: async () => {
+    await invoke('start_dictation');
+  },
+
+  // The last chunk still arrives as a 'dictation-append' event
+  stop: async () => {
+    await invoke('stop_dictation');
+  },
+
+  getDocument: async () => {
+    const result = await invoke<string>('get_dictation_document');
+    return result;
+  },
+};
+
+export const fileTranscriptionAPI = {
+  // wav, mp3 or m4a; returns the job id (files dropped on the main window start jobs too)
+  transcribe: async (path: string) => {
+    const result = await invoke<string>('transcribe_file', { path });
+    return result;
+  },
+
+  // Queues every file, or none if one is not supported
+  transcribeMany: async (paths: string[]) => {
+    const result = await invoke<TranscriptionJob[]>('transcribe_files', { paths });
+    return result;
+  },
+
+  // Newest first
+  listJobs: async () => {
+    const result = await invoke<TranscriptionJob[]>('list_jobs');
+    return result;
+  },
+
+  cancelJob: async (jobId: string) => {
+    await invoke('cancel_job', { jobId });
+  },
+
+  // Failed or cancelled jobs only
+  retryJob: async (jobId: string) => {
+    const result = await invoke<TranscriptionJob>('retry_job', { jobId });
+    return result;
+  },
+};
+
+export const ttsExportAPI = {
+  // Narrates text into a .wav or .mp3 file without playing it; returns the file path
+  synthesizeToFile: async (text: string, outputPath: string, voice?: string, format?: 'wav' | 'mp3') => {
+    const result = await invoke<string>('synthesize_to_file', { text, outputPath, voice, format });
+    return result;
+  },
+};
+
+export const databaseEncryptionAPI = {
+  // Converts the history database in place (key kept in the OS keychain); returns whether it is now encrypted
+  set: async (enabled: boolean) => {
+    const result = await invoke<boolean>('set_database_encryption', { enabled });
+    return result;
+  },
+};
+
+export const retentionAPI = {
+  // Applies the session_history_* policy now; dryRun only reports what would be deleted
+  runNow: async (dryRun = false) => {
+    const result = await invoke<RetentionReport>('run_retention_now', { dryRun });
+    return result;
+  },
+};
+
+export const profilesAPI = {
+  list: async () => {
+    return await invoke<ProfileList>('list_profiles');
+  },
+
+  // copySettings starts the profile from the current settings instead of the defaults
+  create: async (name: string, copySettings?: boolean) => {
+    return await invoke<Profile>('create_profile', { name, copySettings });
+  },
+
+  // Every window reloads on 'profile-switched'
+  switch: async (id: string) => {
+    return await invoke<ProfileList>('switch_profile', { id });
+  },
+};
+
+export const vocabularyAPI = {
+  list: async () => {
+    return await invoke<VocabularyTerm[]>('list_vocabulary');
+  },
+
+  // Rejects if the term already exists (case-insensitive)
+  add: async (term: string) => {
+    return await invoke<VocabularyTerm>('add_vocabulary_term', { term });
+  },
+
+  update: async (id: number, term: string) => {
+    return await invoke<boolean>('update_vocabulary_term', { id, term });
+  },
+
+  delete: async (id: number) => {
+    return await invoke<boolean>('delete_vocabulary_term', { id });
+  },
+
+  // .txt (one term per line) or .csv (first column); returns the number of new terms
+  import: async (path: string) => {
+    return await invoke<number>('import_vocabulary', { path });
+  },
+};
+
+export const symbolsAPI = {
+  // Built-in [phrase, symbol] pairs, e.g. ["em dash", "—"]
+  listDefaults: async () => {
+    return await invoke<[string, string][]>('list_default_symbols');
+  },
+
+  list: async () => {
+    return await invoke<SymbolShortcut[]>('list_symbol_shortcuts');
+  },
+
+  // Replaces the symbol if the phrase exists; a built-in phrase is overridden
+  set: async (phrase: string, symbol: string) => {
+    return await invoke<SymbolShortcut>('set_symbol_shortcut', { phrase, symbol });
+  },
+
+  delete: async (id: number) => {
+    return await invoke<boolean>('delete_symbol_shortcut', { id });
+  },
+};
+
+export const textInputAPI = {
+  // Removes the text typed last; rejects when nothing is left to undo or
+  // another app than the one it was typed into has focus
+  undoLastTyping: async () => {
+    return await invoke<string>('undo_last_typing');
+  },
+};
+
+export const updaterAPI = {
+  // null when this version is the latest on the channel (config `updater.channel`)
+  check: async () => {
+    return await invoke<UpdateInfo | null>('check_for_updates');
+  },
+
+  // Emits `update-download-progress`; null when there is nothing newer
+  download: async () => {
+    return await invoke<UpdateInfo | null>('download_update');
+  },
+
+  // Stops the daemon and restarts into the downloaded version; rejects while recording
+  installAndRestart: async () => {
+    await invoke('install_and_restart');
+  },
+};
+
+export const selfTestAPI = {
+  // Rejects while the app is busy (recording, replying)
+  run: async () => {
+    const result = await invoke<SelfTestReport>('run_self_test');
     return result;
   },
 };
+
+// Why the microphone could not be opened (match Rust audio::MicError)
+export type MicError =
+  | { kind: 'already_recording' }
+  | { kind: 'no_device' }
+  | { kind: 'device_busy'; device: string }
+  | { kind: 'device_unavailable'; device: string }
+  | { kind: 'permission_denied'; device: string }
+  | { kind: 'other'; device: string; message: string };
+
+// `mic-conflict` event payload
+export interface MicConflict {
+  device: string | null;
+  error: MicError | null; // null when resolved
+  message: string;
+  retrying: boolean; // retried while the PTT key is held
+  resolved: boolean; // the device freed up and recording started
+}
+
+// `typing-blocked` event payload: text-input mode refused to type
+export interface TypingBlocked {
+  reason: 'secure_field';
+  app: string | null; // focused app, when known
+  message: string;
+}
+
+// Every backend event, as received on the `app-event` channel (match Rust events::AppEvent).
+// Each one is also sent under its own name with the payload alone.
+export type AppEvent =
+  | { type: 'app-status-changed'; payload: StatusTransition }
+  | { type: 'connectivity-changed'; payload: Connectivity }
+  | { type: 'listening-paused'; payload: ListeningPaused }
+  | { type: 'provider-error'; payload: unknown }
+  | { type: 'daemon-health'; payload: unknown }
+  | { type: 'daemon-progress'; payload: unknown }
+  | { type: 'daemon-status'; payload: unknown }
+  | { type: 'download-progress'; payload: unknown }
+  | { type: 'model-loading'; payload: unknown }
+  | { type: 'update-download-progress'; payload: UpdateProgress }
+  | { type: 'ptt-state'; payload: string }
+  | { type: 'ptt-asr-partial'; payload: string }
+  | { type: 'ptt-user-message'; payload: string }
+  | { type: 'ptt-assistant-chunk'; payload: string }
+  | { type: 'ptt-assistant-done'; payload: string }
+  | { type: 'ptt-audio-chunk'; payload: { audio_path: string; text: string } }
+  | { type: 'ptt-error'; payload: string }
+  | { type: 'mic-conflict'; payload: MicConflict }
+  | { type: 'recording-mode-changed'; payload: string }
+  | { type: 'recording-timeout'; payload: { reason: string } }
+  | { type: 'chat-chunk'; payload: string }
+  | { type: 'chat-done'; payload: string | null }
+  | { type: 'chat-error'; payload: string }
+  | { type: 'tts-text-chunk'; payload: string }
+  | { type: 'tts-audio-chunk'; payload: { audio_path: string; text: string } }
+  | { type: 'tts-done'; payload: null }
+  | { type: 'tts-error'; payload: string }
+  | { type: 'stream-cancelled'; payload: string }
+  | { type: 'tts-export-progress'; payload: TtsExportProgress }
+  | { type: 'session-title-updated'; payload: SessionTitleUpdated }
+  | { type: 'open-session'; payload: string }
+  | { type: 'history-retention-applied'; payload: RetentionReport }
+  | { type: 'history-window-shown'; payload: null }
+  | { type: 'pinned-messages-changed'; payload: null }
+  | { type: 'pinned-window-shown'; payload: null }
+  | { type: 'profile-switched'; payload: ProfileList }
+  | { type: 'transcription-added'; payload: Transcription }
+  | { type: 'transcription-picker-shown'; payload: null }
+  | { type: 'dictation-state'; payload: { active: boolean } }
+  | { type: 'dictation-append'; payload: DictationAppendPayload }
+  | { type: 'meeting-state'; payload: MeetingStatus }
+  | { type: 'meeting-transcript'; payload: HistoryMessage }
+  | { type: 'file-transcription-progress'; payload: TranscriptionJob }
+  | { type: 'typing-blocked'; payload: TypingBlocked }
+  | { type: 'reminder-fired'; payload: Reminder }
+  | { type: 'selection-answer'; payload: SelectionAnswer };
+
+// An AppEvent with its sequence number (increasing, restarts with the app)
+export type SequencedAppEvent = AppEvent & { seq: number };
+
+export const eventsAPI = {
+  // Events sent after sinceSeq (0 = all the backend still keeps), oldest first
+  resync: async (sinceSeq: number) => {
+    return await invoke<SequencedAppEvent[]>('resync_events', { sinceSeq });
+  },
+
+  // Calls handler with every backend event; returns the unlisten function.
+  // With sinceSeq, the events missed since then are delivered first (e.g. after a reload).
+  onAppEvent: async (handler: (event: SequencedAppEvent) => void, sinceSeq?: number) => {
+    const { listen } = await import('@tauri-apps/api/event');
+    let lastSeq = sinceSeq ?? 0;
+    const deliver = (event: SequencedAppEvent) => {
+      if (event.seq <= lastSeq) return; // Already delivered by the resync
+      lastSeq = event.seq;
+      handler(event);
+    };
+
+    // Listen first so nothing is lost between the resync and the listener
+    const queued: SequencedAppEvent[] = [];
+    let resynced = sinceSeq === undefined;
+    const unlisten = await listen<SequencedAppEvent>('app-event', (event) => {
+      if (resynced) deliver(event.payload);
+      else queued.push(event.payload);
+    });
+    if (!resynced) {
+      try {
+        (await eventsAPI.resync(lastSeq)).forEach(deliver);
+      } finally {
+        resynced = true;
+        queued.forEach(deliver);
+      }
+    }
+    return unlisten;
+  },
+};
@@ -267,6 +267,151 @@ class TestLanguageDetection:
         assert language == DEFAULT_LANGUAGE
 
 
+class TestTranscriptionDetails:
+    """测试置信度与候选结果"""
+
+    @patch("funasr.AutoModel")
+    @patch("speekium.create_secure_temp_file")
+    @patch("scipy.io.wavfile.write")
+    @patch("os.path.exists")
+    @patch("os.remove")
+    def test_nbest_and_confidence(
+        self, mock_remove, mock_exists, mock_write_wav, mock_temp_file, mock_automodel
+    ):
+        """测试模型提供的 n-best 与置信度"""
+        mock_model = MagicMock()
+        mock_model.generate.return_value = [
+            {
+                "text": "<|en|>recognize speech",
+                "confidence": 0.82,
+                "nbest": [{"text": "<|en|>wreck a nice beach"}, "recognize speech", "<|en|>"],
+            }
+        ]
+        mock_automodel.return_value = mock_model
+        mock_temp_file.return_value = "/tmp/test.wav"
+        mock_exists.return_value = True
+
+        audio = np.random.randn(16000).astype(np.float32)
+        assistant = VoiceAssistant()
+        result = assistant.transcribe_detailed(audio)
+
+        assert result["text"] == "recognize speech"
+        assert result["confidence"] == 0.82
+        # The best hypothesis and empty ones are not repeated as alternatives
+        assert result["alternatives"] == ["wreck a nice beach"]
+
+    @patch("funasr.AutoModel")
+    @patch("speekium.create_secure_temp_file")
+    @patch("scipy.io.wavfile.write")
+    @patch("os.path.exists")
+    @patch("os.remove")
+    def test_no_details_from_model(
+        self, mock_remove, mock_exists, mock_write_wav, mock_temp_file, mock_automodel
+    ):
+        """测试模型不提供详情时的默认值"""
+        mock_model = MagicMock()
+        mock_model.generate.return_value = [{"text": "<|zh|>你好</s>"}]
+        mock_automodel.return_value = mock_model
+        mock_temp_file.return_value = "/tmp/test.wav"
+        mock_exists.return_value = True
+
+        audio = np.random.randn(16000).astype(np.float32)
+        assistant = VoiceAssistant()
+        result = assistant.transcribe_detailed(audio)
+
+        assert result["confidence"] is None
+        assert result["alternatives"] == []
+        assert result["words"] == []
+        assert result["segments"] == []
+
+    @patch("funasr.AutoModel")
+    @patch("speekium.create_secure_temp_file")
+    @patch("scipy.io.wavfile.write")
+    @patch("os.path.exists")
+    @patch("os.remove")
+    def test_hotwords_passed_to_model(
+        self, mock_remove, mock_exists, mock_write_wav, mock_temp_file, mock_automodel
+    ):
+        """测试自定义词汇作为热词传给模型"""
+        mock_model = MagicMock()
+        mock_model.generate.return_value = [{"text": "<|en|>open Speekium</s>"}]
+        mock_automodel.return_value = mock_model
+        mock_temp_file.return_value = "/tmp/test.wav"
+        mock_exists.return_value = True
+
+        audio = np.random.randn(16000).astype(np.float32)
+        assistant = VoiceAssistant()
+        assistant.transcribe_detailed(audio, hotwords=["Speekium", "SenseVoice"])
+        assert mock_model.generate.call_args.kwargs["hotword"] == "Speekium SenseVoice"
+
+        # Without a vocabulary no hotword is sent
+        assistant.transcribe_detailed(audio)
+        assert "hotword" not in mock_model.generate.call_args.kwargs
+
+    @patch("funasr.AutoModel")
+    @patch("speekium.create_secure_temp_file")
+    @patch("scipy.io.wavfile.write")
+    @patch("os.path.exists")
+    @patch("os.remove")
+    def test_word_timings(
+        self, mock_remove, mock_exists, mock_write_wav, mock_temp_file, mock_automodel
+    ):
+        """测试词级时间戳"""
+        mock_model = MagicMock()
+        mock_model.generate.return_value = [
+            {
+                "text": "<|en|>hello world",
+                "words": ["<|en|>", "hello", "world"],
+                "timestamp": [[0, 0], [120, 480], [520, 910]],
+            }
+        ]
+        mock_automodel.return_value = mock_model
+        mock_temp_file.return_value = "/tmp/test.wav"
+        mock_exists.return_value = True
+
+        audio = np.random.randn(16000).astype(np.float32)
+        assistant = VoiceAssistant()
+        result = assistant.transcribe_detailed(audio)
+
+        # Tag tokens are dropped
+        assert result["words"] == [
+            {"word": "hello", "start_ms": 120, "end_ms": 480},
+            {"word": "world", "start_ms": 520, "end_ms": 910},
+        ]
+
+    @patch("funasr.AutoModel")
+    @patch("speekium.create_secure_temp_file")
+    @patch("scipy.io.wavfile.write")
+    @patch("os.path.exists")
+    @patch("os.remove")
+    def test_speaker_segments(
+        self, mock_remove, mock_exists, mock_write_wav, mock_temp_file, mock_automodel
+    ):
+        """测试说话人分段"""
+        mock_model = MagicMock()
+        mock_model.generate.return_value = [
+            {
+                "text": "<|en|>good morning hi there",
+                "sentence_info": [
+                    {"text": "good morning", "start": 0, "end": 900, "spk": 0},
+                    {"text": "hi there", "start": 1100, "end": 1800, "spk": 1},
+                ],
+            }
+        ]
+        mock_automodel.return_value = mock_model
+        mock_temp_file.return_value = "/tmp/test.wav"
+        mock_exists.return_value = True
+
+        audio = np.random.randn(16000).astype(np.float32)
+        assistant = VoiceAssistant()
+        result = assistant.transcribe_detailed(audio)
+
+        assert result["segments"] == [
+            {"speaker": 1, "start_ms": 0, "end_ms": 900, "text": "good morning"},
+            {"speaker": 2, "start_ms": 1100, "end_ms": 1800, "text": "hi there"},
+        ]
+
+
 class TestAsyncTranscription:
     """测试异步转录功能"""
 
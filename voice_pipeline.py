@@ -610,7 +610,7 @@ class VoicePipeline:
             communicate = edge_tts.Communicate(text, voice, rate=DEFAULT_TTS_RATE)
 
             # Save to temp file
-            fd, temp_file = tempfile.mkstemp(suffix=".mp3")
+            fd, temp_file = tempfile.mkstemp(suffix=".mp3", prefix="speekium_tts_")
             os.close(fd)
 
             await communicate.save(temp_file)
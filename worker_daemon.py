@@ -3,39 +3,146 @@
 Speekium Daemon Worker - 守护进程模式
 一次启动，持续运行，快速响应
 
-通信协议：
-  - 输入：stdin 接收 JSON 命令，每行一个
-  - 输出：stdout 返回 JSON 结果，每行一个
-  - 日志：stderr 输出调试信息
-
-命令格式：
+通信协议（帧协议，stdin/stdout 双向）：
+  - 每一帧：4 字节大端长度 + UTF-8 JSON 信封
+  - 信封：{"id": <请求 ID>, "kind": "request|response|stream|log", "payload": {...}}
+  - response/stream 帧携带对应请求的 id，log 帧不携带 id
+  - 每个请求在独立的 asyncio 任务中处理，响应可能乱序返回（按 id 匹配）
+  - stderr：PTT 事件（每行一个 JSON）和调试信息
+
+请求 payload 格式：
   {"command": "record", "args": {"mode": "push-to-talk", "duration": 3.0}}
   {"command": "chat", "args": {"text": "hello"}}
+  {"command": "chat", "args": {"text": "hello", "history": [{"role": "user", "content": "..."}]}}
   {"command": "tts", "args": {"text": "你好"}}
   {"command": "config", "args": {}}
+  {"command": "reload_config", "args": {"api_keys": {"openai": "sk-..."}, "offline": false, "config_dir": "..."}}
   {"command": "health", "args": {}}
   {"command": "exit", "args": {}}
 """
 
 import asyncio
+import contextvars
+import copy
 import json
 import resource  # NEW: For resource limits
 import signal  # NEW: For signal handling
+import struct
 import sys
+import threading
 import time
 import traceback
 from typing import Optional
 
 import sounddevice as sd
+import structlog
 
 from logger import configure_logging, get_logger
 
-# Configure logging for daemon (JSON format)
+# ===== Framed IPC =====
+# In daemon mode stdout is reserved for frames (see enable_framed_ipc)
+_plain_out = sys.stdout
+_ipc_out = sys.stdout.buffer
+_ipc_in = sys.stdin.buffer
+_ipc_lock = threading.Lock()
+_framed = False
+
+# Id of the request handled by the current task (stream frames are tagged with it)
+_current_request_id: contextvars.ContextVar = contextvars.ContextVar("request_id", default=None)
+
+MAX_FRAME_SIZE = 16 * 1024 * 1024
+
+# Version of the command / event protocol, reported in the daemon_hello event.
+# Must match PROTOCOL_VERSION in src-tauri/src/daemon/integrity.rs; bump both
+# when commands or frames change incompatibly.
+PROTOCOL_VERSION = 1
+
+# Interval between partial transcriptions while PTT is held (seconds)
+ASR_PARTIAL_INTERVAL = 1.0
+
+# Streamed PTT audio (ptt_audio_chunk / ptt_audio_end): audio not yet transcribed
+# is cut at a pause and transcribed early once it is at least this long (seconds)
+STREAM_SEGMENT_MIN_SECS = 3.0
+# A pause: this long (seconds) with an RMS level below STREAM_PAUSE_RMS
+STREAM_PAUSE_SECS = 0.3
+STREAM_PAUSE_RMS = 0.01
+
+# Commands that carry the active persona ({"system_prompt", "voice", "speed"})
+PERSONA_COMMANDS = ("chat", "chat_stream", "chat_tts_stream", "ptt_chat", "tts")
+
+# Commands that carry per-request speech parameters ({"voice", "rate", "pitch", "volume"})
+SPEECH_COMMANDS = ("chat_tts_stream", "tts")
+
+
+def enable_framed_ipc():
+    """Switch stdout to frame-only mode; stray prints from libraries go to stderr"""
+    global _framed
+    _framed = True
+    sys.stdout = sys.stderr
+
+
+def send_frame(kind: str, payload, request_id: Optional[int] = None):
+    """Write one length-prefixed JSON frame to stdout (thread-safe)"""
+    frame = {"kind": kind, "payload": payload}
+    if request_id is not None:
+        frame["id"] = request_id
+    data = json.dumps(frame, ensure_ascii=False).encode("utf-8")
+    with _ipc_lock:
+        _ipc_out.write(struct.pack(">I", len(data)))
+        _ipc_out.write(data)
+        _ipc_out.flush()
+
+
+def read_frame() -> Optional[dict]:
+    """Read one length-prefixed JSON frame from stdin (blocking), None on EOF"""
+    header = _ipc_in.read(4)
+    if len(header) < 4:
+        return None
+    (length,) = struct.unpack(">I", header)
+    if length > MAX_FRAME_SIZE:
+        raise ValueError(f"Frame too large: {length} bytes")
+    data = _ipc_in.read(length)
+    if len(data) < length:
+        return None
+    return json.loads(data.decode("utf-8"))
+
+
+class _LogFrameStream:
+    """File-like sink that turns structlog JSON lines into log frames"""
+
+    def __init__(self):
+        self._buffer = ""
+        self._lock = threading.Lock()
+
+    def write(self, text: str) -> int:
+        with self._lock:
+            self._buffer += text
+            while "\n" in self._buffer:
+                line, self._buffer = self._buffer.split("\n", 1)
+                line = line.strip()
+                if not line:
+                    continue
+                if not _framed:
+                    # Not running as daemon (e.g. imported by tests): plain JSON lines
+                    print(line, file=_plain_out, flush=True)
+                    continue
+                try:
+                    payload = json.loads(line)
+                except ValueError:
+                    payload = {"event": "daemon_log", "message": line}
+                send_frame("log", payload)
+        return len(text)
+
+    def flush(self):
+        pass
+
+
+# Configure logging for daemon (JSON format, delivered as log frames)
 configure_logging(level="INFO", format="json", colored=False)
+structlog.configure(logger_factory=structlog.PrintLoggerFactory(file=_LogFrameStream()))
 logger = get_logger(__name__)
 
 # Ensure immediate output flush
-sys.stdout.reconfigure(line_buffering=True)
 sys.stderr.reconfigure(line_buffering=True)
 
 
@@ -95,6 +202,11 @@ class SpeekiumDaemon:
         self.ptt_recording = False
         self.ptt_audio_frames = []
         self.ptt_stream = None
+        self.ptt_partial_task = None
+
+        # Streamed PTT audio from Rust (see handle_ptt_audio_chunk)
+        self.audio_stream = None
+        self.stream_asr_lock = None  # created on first use, inside the event loop
 
         # Interrupt flag for LLM/TTS operations
         import threading
@@ -106,6 +218,9 @@ class SpeekiumDaemon:
         # Event loop reference (set during initialization)
         self.loop = None
 
+        # In-flight request tasks (kept referenced until done)
+        self._pending_tasks = set()
+
         # Health monitoring task
         self.health_monitor_task = None
         self.health_check_interval = 60  # seconds
@@ -140,6 +255,14 @@ class SpeekiumDaemon:
         # Use stderr to avoid interfering with command responses on stdout
         print(json.dumps(event), file=sys.stderr, flush=True)
 
+    def _emit_asr_partial(self, text: str):
+        """Emit an in-progress transcription (forwarded by Tauri as ptt-asr-partial)"""
+        self._emit_ptt_event("asr_partial", {"text": text})
+
+    def _emit_stream(self, chunk: dict):
+        """Emit a stream chunk frame for the request currently being handled"""
+        send_frame("stream", chunk, _current_request_id.get())
+
     def _cleanup(self):
         """Clean up resources before exit"""
         self._log("🧹 正在清理资源...")
@@ -176,10 +299,11 @@ class SpeekiumDaemon:
             # Store event loop reference for PTT callbacks (called from different thread)
             self.loop = asyncio.get_running_loop()
 
-            from speekium import VoiceAssistant
+            from speekium import VoiceAssistant, emit_init_progress
 
             logger.info("loading_voice_assistant")
             self.assistant = VoiceAssistant()
+            self._emit_init_plan()
 
             # Load VAD and ASR models in parallel for faster startup
             logger.info("preloading_vad_and_asr_models")
@@ -200,7 +324,9 @@ class SpeekiumDaemon:
             logger.info("vad_and_asr_models_loaded")
 
             self._log("🔄 预加载 LLM 后端...")
+            emit_init_progress("llm", 0)
             self.assistant.load_llm()
+            emit_init_progress("llm", 100)
 
             # Note: PTT hotkey is now handled by Tauri global shortcuts (Rust side)
             # The pynput hotkey manager is no longer needed
@@ -214,8 +340,24 @@ class SpeekiumDaemon:
             traceback.print_exc(file=sys.stderr)
             return False
 
-    async def handle_record(self, mode: str = "push-to-talk", duration: float = 3.0) -> dict:
-        """Handle recording command"""
+    def _emit_init_plan(self):
+        """Announce the startup stages and their weights (share of the total startup time)
+
+        Downloads dwarf loading, so they only get weight when the model is missing.
+        """
+        vad_cached, _ = self.assistant._check_vad_model_exists()
+        asr_cached, _ = self.assistant._check_asr_model_exists()
+        stages = [{"stage": "vad_load", "weight": 5 if vad_cached else 15}]
+        if not asr_cached:
+            stages.append({"stage": "asr_download", "weight": 60})
+        stages.append({"stage": "asr_load", "weight": 30})
+        stages.append({"stage": "llm", "weight": 5})
+        logger.info("init_plan", stages=stages)
+
+    async def handle_record(
+        self, mode: str = "push-to-talk", duration: float = 3.0, hotwords: Optional[list] = None
+    ) -> dict:
+        """Handle recording command (hotwords: the user's custom vocabulary)"""
         try:
             self._log(f"🎤 开始录音 (mode={mode}, duration={duration}s)...")
 
@@ -247,12 +389,21 @@ class SpeekiumDaemon:
 
             self._log("🔄 识别中...")
             self._emit_ptt_event("processing")
-            text, language = self.assistant.transcribe(audio)
+            details = self.assistant.transcribe_detailed(audio, hotwords=hotwords)
+            text, language = details["text"], details["language"]
 
             self._log(f"✅ 识别完成: '{text}' ({language})")
             self._emit_ptt_event("idle")
 
-            return {"success": True, "text": text, "language": language}
+            return {
+                "success": True,
+                "text": text,
+                "language": language,
+                "confidence": details["confidence"],
+                "alternatives": details["alternatives"],
+                "words": details["words"],
+                "segments": details["segments"],
+            }
 
         except Exception as e:
             self._log(f"❌ 录音失败: {e}")
@@ -287,6 +438,11 @@ class SpeekiumDaemon:
             )
             self.ptt_stream.start()
 
+            from config_manager import ConfigManager
+
+            if ConfigManager.load(silent=True).get("asr_partial_results", True):
+                self.ptt_partial_task = asyncio.create_task(self._ptt_partial_loop())
+
             self._log("🎤 PTT: Recording started")
             return {"success": True, "message": "Recording started"}
 
@@ -296,6 +452,41 @@ class SpeekiumDaemon:
             traceback.print_exc(file=sys.stderr)
             return {"success": False, "error": str(e)}
 
+    async def _ptt_partial_loop(self):
+        """Periodically transcribe the audio recorded so far and emit asr_partial events"""
+        import numpy as np
+
+        loop = asyncio.get_running_loop()
+        last_text = ""
+
+        while self.ptt_recording:
+            await asyncio.sleep(ASR_PARTIAL_INTERVAL)
+            frames = list(self.ptt_audio_frames)
+            if not self.ptt_recording or not frames:
+                continue
+
+            audio = np.concatenate(frames, axis=0)[:, 0]
+            if len(audio) / 16000 < 0.5:
+                continue
+
+            try:
+                # Run in a thread so commands keep being served while ASR runs
+                text, _ = await loop.run_in_executor(None, self.assistant.transcribe, audio)
+            except Exception as e:
+                self._log(f"⚠️ Partial ASR failed: {e}")
+                return
+
+            if text and text != last_text and self.ptt_recording:
+                last_text = text
+                self._emit_asr_partial(text)
+
+    async def _stop_ptt_partial_loop(self):
+        """Wait for the partial transcription loop (must run after ptt_recording is cleared)"""
+        task, self.ptt_partial_task = self.ptt_partial_task, None
+        if task:
+            # Let an in-flight partial ASR finish so the model is not used concurrently
+            await asyncio.gather(task, return_exceptions=True)
+
     async def handle_ptt_audio(
         self,
         audio_path: str,
@@ -303,8 +494,17 @@ class SpeekiumDaemon:
         duration: float = 0,
         auto_chat: bool = True,
         use_tts: bool = True,
+        emit_result: bool = True,
+        language: Optional[str] = None,
+        hotwords: Optional[list] = None,
     ) -> dict:
-        """Handle PTT audio from Rust - receives WAV file path, performs ASR + chat"""
+        """Handle PTT audio from Rust - receives WAV file path, performs ASR + chat
+
+        With emit_result=False only the transcript is returned; Rust post-processes
+        it and submits it with ptt_chat. language is an ASR hint ("auto" or a
+        language code, e.g. the one the current session was spoken in), hotwords
+        the user's custom vocabulary.
+        """
         import os
         import numpy as np
         from scipy.io import wavfile
@@ -357,34 +557,355 @@ class SpeekiumDaemon:
             # ASR
             t5 = time.time()
             self._log("🔄 识别中...")
-            text, language = self.assistant.transcribe(samples)
+            details = self.assistant.transcribe_detailed(samples, language, hotwords)
+            text, language = details["text"], details["language"]
             t6 = time.time()
             asr_ms = int((t6 - t5) * 1000)
             logger.debug("ptt_timing", step="asr_total", ms=asr_ms)
             self._log(f"✅ 识别完成: '{text}' ({language})")
 
-            if not text or not text.strip():
-                self._emit_ptt_event("idle")
-                return {
-                    "success": True,
-                    "text": "",
+            return await self._finish_ptt_transcript(
+                text, language, auto_chat, use_tts, emit_result, details
+            )
+
+        except Exception as e:
+            self._log(f"❌ PTT Audio processing failed: {e}")
+            self._emit_ptt_event("error", {"error": str(e)})
+            traceback.print_exc(file=sys.stderr)
+            return {"success": False, "error": str(e)}
+
+    async def _finish_ptt_transcript(
+        self,
+        text: str,
+        language,
+        auto_chat: bool,
+        use_tts: bool,
+        emit_result: bool,
+        details: Optional[dict] = None,
+    ) -> dict:
+        """Return the PTT transcript, or show it and chat (emit_result=True)
+
+        details: transcribe_detailed() result; its confidence, alternatives, word timings and speaker segments are returned too
+        """
+        if not text or not text.strip():
+            self._emit_ptt_event("idle")
+            return {
+                "success": True,
+                "text": "",
+                "language": language,
+                "message": "No speech detected",
+            }
+
+        result = {"success": True, "text": text, "language": language}
+        if details:
+            result["confidence"] = details.get("confidence")
+            result["alternatives"] = details.get("alternatives", [])
+            result["words"] = details.get("words", [])
+            result["segments"] = details.get("segments", [])
+
+        if not emit_result:
+            return result
+
+        # Emit user message for frontend display
+        self._emit_ptt_event("user_message", {"text": text})
+
+        # Auto chat with TTS if enabled
+        if auto_chat and text.strip():
+            self._log(f"💬 PTT: Auto chat with TTS...")
+            if not await self._handle_ptt_chat_tts(text, use_tts):
+                # Barged in: a new recording already owns the UI state
+                return result
+
+        self._emit_ptt_event("idle")
+        return result
+
+    # ===== Streamed PTT Audio =====
+    # Rust sends ~1s chunks while the key is held (ptt_audio_chunk) and the rest
+    # on release (ptt_audio_end). Audio is transcribed in segments cut at pauses
+    # as it arrives, so on release only the last segment is left to transcribe.
+
+    @staticmethod
+    def _read_wav_samples(audio_path: str):
+        """Load a WAV file as float32 mono samples and delete it"""
+        import os
+        import numpy as np
+        from scipy.io import wavfile
+
+        _, samples = wavfile.read(audio_path)
+        if samples.dtype == np.int16:
+            samples = samples.astype(np.float32) / 32768.0
+        elif samples.dtype != np.float32:
+            samples = samples.astype(np.float32)
+        if len(samples.shape) > 1:
+            samples = samples.mean(axis=1)
+        try:
+            os.remove(audio_path)
+        except OSError:
+            pass
+        return samples
+
+    @staticmethod
+    def _find_stream_cut(samples, start: int) -> Optional[int]:
+        """Middle of the last pause after STREAM_SEGMENT_MIN_SECS of audio from start, or None"""
+        import numpy as np
+
+        window = int(STREAM_PAUSE_SECS * 16000)
+        earliest = start + int(STREAM_SEGMENT_MIN_SECS * 16000)
+        pos = len(samples) - window
+        while pos >= earliest:
+            chunk = samples[pos : pos + window]
+            if np.sqrt(np.mean(chunk**2)) < STREAM_PAUSE_RMS:
+                return pos + window // 2
+            pos -= window // 2
+        return None
+
+    @staticmethod
+    def _join_segments(texts: list) -> str:
+        """Join segment transcripts; a space only between two non-CJK segments"""
+        result = ""
+        for text in texts:
+            text = text.strip()
+            if result and text and result[-1].isascii() and text[0].isascii():
+                result += " "
+            result += text
+        return result
+
+    async def _transcribe_serialized(
+        self, audio, language: Optional[str], hotwords: Optional[list] = None
+    ):
+        """Transcribe in a thread, one stream segment at a time"""
+        loop = asyncio.get_running_loop()
+        if self.stream_asr_lock is None:
+            self.stream_asr_lock = asyncio.Lock()
+        async with self.stream_asr_lock:
+            return await loop.run_in_executor(
+                None, self.assistant.transcribe, audio, language, hotwords
+            )
+
+    def _stream_language(self, state: dict) -> Optional[str]:
+        """Hint for the next segment: the requested language, else the one detected so far"""
+        if state["language"] not in (None, "auto"):
+            return state["language"]
+        return state["detected"] or state["language"]
+
+    async def _transcribe_stream_segment(self, state: dict):
+        """Transcribe the audio not yet transcribed up to its last pause, if there is one"""
+        cut = self._find_stream_cut(state["samples"], state["committed"])
+        if cut is None:
+            return
+        segment = state["samples"][state["committed"] : cut]
+        try:
+            text, language = await self._transcribe_serialized(
+                segment, self._stream_language(state), state["hotwords"]
+            )
+        except Exception as e:
+            self._log(f"⚠️ Stream segment ASR failed: {e}")
+            return
+        state["committed"] = cut
+        if language:
+            state["detected"] = state["detected"] or language
+        if text and text.strip():
+            state["texts"].append(text)
+            if self.audio_stream is state:
+                self._emit_asr_partial(self._join_segments(state["texts"]))
+
+    async def handle_ptt_audio_chunk(
+        self,
+        stream_id: str,
+        seq: int,
+        audio_path: str,
+        language: Optional[str] = None,
+        hotwords: Optional[list] = None,
+    ) -> dict:
+        """Receive a chunk of a PTT recording that is still running
+
+        Chunks of a stream arrive in order (seq counts from 0); a new stream_id
+        replaces an unfinished stream.
+        """
+        import numpy as np
+
+        try:
+            state = self.audio_stream
+            if state is None or state["id"] != stream_id:
+                state = self.audio_stream = {
+                    "id": stream_id,
+                    "seq": -1,
+                    "samples": np.zeros(0, dtype=np.float32),
+                    "committed": 0,  # samples already transcribed
+                    "texts": [],
                     "language": language,
-                    "message": "No speech detected",
+                    "hotwords": hotwords,
+                    "detected": None,
+                    "task": None,
                 }
+            if seq <= state["seq"]:
+                return {"success": False, "error": f"Chunk {seq} arrived out of order"}
+            state["seq"] = seq
+
+            samples = self._read_wav_samples(audio_path)
+            state["samples"] = np.concatenate([state["samples"], samples])
+
+            if state["task"] is None or state["task"].done():
+                state["task"] = asyncio.create_task(self._transcribe_stream_segment(state))
+            return {"success": True, "duration": len(state["samples"]) / 16000}
+
+        except Exception as e:
+            self._log(f"❌ PTT audio chunk failed: {e}")
+            return {"success": False, "error": str(e)}
+
+    async def handle_ptt_audio_end(
+        self,
+        stream_id: str,
+        audio_path: str = "",
+        auto_chat: bool = True,
+        use_tts: bool = True,
+        emit_result: bool = True,
+        language: Optional[str] = None,
+        hotwords: Optional[list] = None,
+    ) -> dict:
+        """Finish a streamed PTT recording and continue like ptt_audio
+
+        audio_path holds the audio recorded after the last chunk (may be empty).
+        """
+        import os
+        import numpy as np
+
+        state, self.audio_stream = self.audio_stream, None
+        if state is None or state["id"] != stream_id:
+            return {"success": False, "error": f"Unknown audio stream: {stream_id}"}
+
+        try:
+            if audio_path and os.path.exists(audio_path):
+                samples = self._read_wav_samples(audio_path)
+                state["samples"] = np.concatenate([state["samples"], samples])
+            if language and state["language"] is None:
+                state["language"] = language
+            if hotwords and not state["hotwords"]:
+                state["hotwords"] = hotwords
+
+            # Let a segment still being transcribed finish first
+            if state["task"]:
+                await asyncio.gather(state["task"], return_exceptions=True)
+
+            duration = len(state["samples"]) / 16000
+            if duration < 0.3:
+                self._emit_ptt_event("idle")
+                return {"success": False, "error": "Recording too short"}
+
+            t0 = time.time()
+            rest = state["samples"][state["committed"] :]
+            if len(rest) / 16000 >= 0.3:
+                self._log("🔄 识别中...")
+                text, detected = await self._transcribe_serialized(
+                    rest, self._stream_language(state), state["hotwords"]
+                )
+                if text and text.strip():
+                    state["texts"].append(text)
+                state["detected"] = state["detected"] or detected
+            text = self._join_segments(state["texts"])
+            logger.debug(
+                "ptt_timing",
+                step="asr_stream_tail",
+                ms=int((time.time() - t0) * 1000),
+                segments=len(state["texts"]),
+            )
+            self._log(f"✅ 识别完成: '{text}' ({state['detected']}, {duration:.2f}s streamed)")
+
+            return await self._finish_ptt_transcript(
+                text, state["detected"], auto_chat, use_tts, emit_result
+            )
+
+        except Exception as e:
+            self._log(f"❌ PTT audio stream failed: {e}")
+            self._emit_ptt_event("error", {"error": str(e)})
+            traceback.print_exc(file=sys.stderr)
+            return {"success": False, "error": str(e)}
+
+    async def handle_transcribe(
+        self, audio_path: str, language: Optional[str] = None, hotwords: Optional[list] = None
+    ) -> dict:
+        """Transcribe a WAV file without touching the PTT state (used by the local API)"""
+        import os
+        import numpy as np
+        from scipy.io import wavfile
+
+        try:
+            if not os.path.exists(audio_path):
+                return {"success": False, "error": f"Audio file not found: {audio_path}"}
+
+            try:
+                sample_rate, samples = wavfile.read(audio_path)
+            finally:
+                try:
+                    os.remove(audio_path)
+                except Exception as e:
+                    self._log(f"⚠️ Failed to delete temp file: {e}")
+
+            if samples.dtype == np.int16:
+                samples = samples.astype(np.float32) / 32768.0
+            elif samples.dtype == np.int32:
+                samples = samples.astype(np.float32) / 2147483648.0
+            elif samples.dtype != np.float32:
+                samples = samples.astype(np.float32)
+            if len(samples.shape) > 1:
+                samples = samples.mean(axis=1)
+
+            # ASR expects 16 kHz
+            if sample_rate != 16000:
+                from math import gcd
+                from scipy.signal import resample_poly
+
+                divisor = gcd(16000, sample_rate)
+                samples = resample_poly(samples, 16000 // divisor, sample_rate // divisor)
+                samples = samples.astype(np.float32)
+
+            # Run in a thread so commands (and health pings) are served meanwhile
+            loop = asyncio.get_running_loop()
+            details = await loop.run_in_executor(
+                None, self.assistant.transcribe_detailed, samples, language, hotwords
+            )
+            return {
+                "success": True,
+                "text": details["text"] or "",
+                "language": details["language"],
+                "confidence": details["confidence"],
+                "alternatives": details["alternatives"],
+                "words": details["words"],
+                "segments": details["segments"],
+            }
+
+        except Exception as e:
+            self._log(f"❌ Transcription failed: {e}")
+            return {"success": False, "error": str(e)}
+
+    async def handle_ptt_chat(
+        self,
+        text: str,
+        auto_chat: bool = True,
+        use_tts: bool = True,
+        system_prompt: Optional[str] = None,
+        speech: Optional[dict] = None,
+    ) -> dict:
+        """Submit a post-processed PTT transcript - show it and chat if enabled"""
+        try:
+            if not text.strip():
+                self._emit_ptt_event("idle")
+                return {"success": True, "text": ""}
 
             # Emit user message for frontend display
             self._emit_ptt_event("user_message", {"text": text})
 
-            # Auto chat with TTS if enabled
-            if auto_chat and text.strip():
-                self._log(f"💬 PTT: Auto chat with TTS...")
-                await self._handle_ptt_chat_tts(text, use_tts)
+            if auto_chat:
+                self._log("💬 PTT: Auto chat with TTS...")
+                if not await self._handle_ptt_chat_tts(text, use_tts, system_prompt, speech):
+                    # Barged in: a new recording already owns the UI state
+                    return {"success": True, "text": text}
 
             self._emit_ptt_event("idle")
-            return {"success": True, "text": text, "language": language}
+            return {"success": True, "text": text}
 
         except Exception as e:
-            self._log(f"❌ PTT Audio processing failed: {e}")
+            self._log(f"❌ PTT chat failed: {e}")
             self._emit_ptt_event("error", {"error": str(e)})
             traceback.print_exc(file=sys.stderr)
             return {"success": False, "error": str(e)}
@@ -405,6 +926,8 @@ class SpeekiumDaemon:
                 self.ptt_stream.close()
                 self.ptt_stream = None
 
+            await self._stop_ptt_partial_loop()
+
             # Combine audio frames
             if not self.ptt_audio_frames:
                 return {"success": False, "error": "No audio recorded"}
@@ -456,12 +979,25 @@ class SpeekiumDaemon:
             traceback.print_exc(file=sys.stderr)
             return {"success": False, "error": str(e)}
 
-    async def _handle_ptt_chat_tts(self, text: str, use_tts: bool = True) -> None:
-        """Handle LLM streaming chat + TTS for PTT mode (emits via stderr for Rust capture)"""
+    async def _handle_ptt_chat_tts(
+        self,
+        text: str,
+        use_tts: bool = True,
+        system_prompt: Optional[str] = None,
+        speech: Optional[dict] = None,
+    ) -> bool:
+        """Handle LLM streaming chat + TTS for PTT mode (emits via stderr for Rust capture)
+
+        Returns False if the reply was interrupted (e.g. the user barged in with
+        a new recording); the partial reply is then sent as an "interrupted" event.
+        """
         try:
             self._log(f"💬🔊 PTT LLM+TTS: {text[:50]}...")
 
-            backend = self.assistant.load_llm()
+            # Clear interrupt flag at start
+            self.interrupt_event.clear()
+
+            backend = self._llm_backend(system_prompt)
             full_response = ""
 
             # Check if streaming is supported
@@ -473,8 +1009,8 @@ class SpeekiumDaemon:
 
                 # Generate TTS
                 if use_tts:
-                    audio_path = await self.assistant.generate_audio(response)
-                    if audio_path:
+                    audio_path = await self.assistant.generate_audio(response, speech=speech)
+                    if audio_path and not self.interrupt_event.is_set():
                         self._emit_ptt_event(
                             "audio_chunk", {"audio_path": audio_path, "text": response}
                         )
@@ -482,6 +1018,9 @@ class SpeekiumDaemon:
             else:
                 # Stream LLM + TTS generation
                 async for sentence in backend.chat_stream(text):
+                    if self.interrupt_event.is_set():
+                        self._log("🚫 PTT LLM streaming interrupted")
+                        break
                     if sentence and sentence.strip():
                         full_response += sentence
                         self._log(f"📤 PTT streaming: {sentence[:30]}...")
@@ -492,8 +1031,11 @@ class SpeekiumDaemon:
                         # Generate TTS immediately
                         if use_tts:
                             try:
-                                audio_path = await self.assistant.generate_audio(sentence)
-                                if audio_path:
+                                audio_path = await self.assistant.generate_audio(
+                                    sentence, speech=speech
+                                )
+                                # Drop audio generated after an interrupt
+                                if audio_path and not self.interrupt_event.is_set():
                                     self._log(f"🔊 TTS completed: {audio_path}")
                                     self._emit_ptt_event(
                                         "audio_chunk", {"audio_path": audio_path, "text": sentence}
@@ -502,22 +1044,47 @@ class SpeekiumDaemon:
                             except Exception as tts_error:
                                 self._log(f"⚠️ TTS generation failed: {tts_error}")
 
+            if self.interrupt_event.is_set():
+                self._emit_ptt_event("interrupted", {"content": full_response})
+                self._log("🚫 PTT LLM+TTS interrupted")
+                return False
+
             # Send completion marker
             self._emit_ptt_event("assistant_done", {"content": full_response})
             self._log("✅ PTT LLM+TTS completed")
+            return True
 
         except Exception as e:
             self._log(f"❌ PTT LLM+TTS failed: {e}")
             traceback.print_exc(file=sys.stderr)
             self._emit_ptt_event("error", {"error": str(e)})
+            return True
 
-    async def handle_chat(self, text: str) -> dict:
-        """Handle LLM chat command (non-streaming)"""
+    async def handle_chat(
+        self, text: str, history: Optional[list] = None, system_prompt: Optional[str] = None
+    ) -> dict:
+        """Handle LLM chat command (non-streaming)
+
+        Args:
+            text: User message
+            history: Optional prior messages ({"role", "content"}) used as context
+                     instead of the backend's own history
+            system_prompt: The persona's system prompt (None = built-in)
+        """
         try:
             self._log(f"💬 LLM 对话: {text[:50]}...")
 
-            backend = self.assistant.load_llm()
-            response = backend.chat(text)
+            if history is not None:
+                # The session history is used for this call only
+                history = [
+                    {"role": m.get("role", "user"), "content": m.get("content", "")}
+                    for m in history
+                    if m.get("role") in ("user", "assistant")
+                ]
+            backend = self._llm_backend(system_prompt, history)
+            # Run in a thread so commands (and health pings) are served meanwhile
+            loop = asyncio.get_running_loop()
+            response = await loop.run_in_executor(None, backend.chat, text)
 
             self._log(f"✅ LLM 响应: {response[:50]}...")
 
@@ -528,7 +1095,53 @@ class SpeekiumDaemon:
             traceback.print_exc(file=sys.stderr)
             return {"success": False, "error": str(e)}
 
-    async def handle_chat_stream(self, text: str) -> None:
+    def _llm_backend(self, system_prompt: Optional[str] = None, history: Optional[list] = None):
+        """LLM backend for one request, with its system prompt and optionally its own history
+
+        A shallow copy of the shared backend, so concurrent requests never see
+        each other's prompt; without `history` it adds to the shared
+        conversation history. Safe to use from a thread.
+        """
+        request_backend = copy.copy(self.assistant.load_llm())
+        if system_prompt:
+            request_backend.system_prompt = system_prompt
+        if history is not None:
+            request_backend.history = history
+        return request_backend
+
+    async def handle_generate_title(self, messages: list) -> dict:
+        """Summarize a conversation into a short session title
+
+        Args:
+            messages: Conversation messages ({"role", "content"}), usually the first exchange
+        """
+        try:
+            transcript = "\n".join(
+                f"{m.get('role', 'user')}: {m.get('content', '')}" for m in messages
+            )
+            prompt = (
+                "Summarize the following conversation as a title of 5 to 8 words, "
+                "in the same language as the conversation. Reply with the title only, "
+                "without quotes or punctuation at the end.\n\n" + transcript
+            )
+
+            # Do not let the title request leak into the conversation history
+            backend = self._llm_backend(history=[])
+            loop = asyncio.get_running_loop()
+            response = await loop.run_in_executor(None, backend.chat, prompt)
+
+            title = response.strip().splitlines()[0].strip().strip("\"'“”「」") if response.strip() else ""
+            if not title:
+                return {"success": False, "error": "Empty title"}
+
+            self._log(f"🏷️ 会话标题: {title}")
+            return {"success": True, "title": title}
+
+        except Exception as e:
+            self._log(f"❌ 生成标题失败: {e}")
+            return {"success": False, "error": str(e)}
+
+    async def handle_chat_stream(self, text: str, system_prompt: Optional[str] = None) -> None:
         """Handle LLM streaming chat command
 
         流式响应格式：
@@ -539,32 +1152,38 @@ class SpeekiumDaemon:
         try:
             self._log(f"💬 LLM 流式对话: {text[:50]}...")
 
-            backend = self.assistant.load_llm()
+            backend = self._llm_backend(system_prompt)
 
             # Check if streaming is supported
             if not hasattr(backend, "chat_stream"):
                 # Streaming not supported, return complete response
                 response = backend.chat(text)
-                print(json.dumps({"type": "chunk", "content": response}), flush=True)
-                print(json.dumps({"type": "done"}), flush=True)
+                self._emit_stream({"type": "chunk", "content": response})
+                self._emit_stream({"type": "done"})
                 return
 
             # Stream generation
             async for sentence in backend.chat_stream(text):
                 if sentence:
                     self._log(f"📤 流式输出: {sentence[:30]}...")
-                    print(json.dumps({"type": "chunk", "content": sentence}), flush=True)
+                    self._emit_stream({"type": "chunk", "content": sentence})
 
             # Send completion marker
-            print(json.dumps({"type": "done"}), flush=True)
+            self._emit_stream({"type": "done"})
             self._log("✅ 流式对话完成")
 
         except Exception as e:
             self._log(f"❌ 流式对话失败: {e}")
             traceback.print_exc(file=sys.stderr)
-            print(json.dumps({"type": "error", "error": str(e)}), flush=True)
+            self._emit_stream({"type": "error", "error": str(e)})
 
-    async def handle_chat_tts_stream(self, text: str, auto_play: bool = True) -> None:
+    async def handle_chat_tts_stream(
+        self,
+        text: str,
+        auto_play: bool = True,
+        system_prompt: Optional[str] = None,
+        speech: Optional[dict] = None,
+    ) -> None:
         """Handle LLM streaming chat + TTS streaming generation
 
         Streaming response format:
@@ -582,7 +1201,7 @@ class SpeekiumDaemon:
             # Set TTS generation state to pause VAD
             self.assistant.is_generating_tts = True
 
-            backend = self.assistant.load_llm()
+            backend = self._llm_backend(system_prompt)
 
             # Clear interrupt flag at start
             self.interrupt_event.clear()
@@ -595,38 +1214,29 @@ class SpeekiumDaemon:
                 # Check for interrupt before TTS generation
                 if self.interrupt_event.is_set():
                     self._log("🚫 LLM response interrupted (before TTS)")
-                    print(
-                        json.dumps({"type": "interrupted", "reason": "Interrupted before TTS"}),
-                        flush=True,
-                    )
+                    self._emit_stream({"type": "interrupted", "reason": "Interrupted before TTS"})
                     # Clear TTS generation state to resume VAD
                     self.assistant.is_generating_tts = False
                     return
 
-                print(json.dumps({"type": "text_chunk", "content": response}), flush=True)
+                self._emit_stream({"type": "text_chunk", "content": response})
 
                 # Generate TTS
-                audio_path = await self.assistant.generate_audio(response)
+                audio_path = await self.assistant.generate_audio(response, speech=speech)
 
                 # Check for interrupt before playback
                 if self.interrupt_event.is_set():
                     self._log("🚫 TTS generation interrupted (before playback)")
-                    print(
-                        json.dumps(
-                            {"type": "interrupted", "reason": "Interrupted before playback"}
-                        ),
-                        flush=True,
+                    self._emit_stream(
+                        {"type": "interrupted", "reason": "Interrupted before playback"}
                     )
                     # Clear TTS generation state to resume VAD
                     self.assistant.is_generating_tts = False
                     return
 
                 if audio_path and auto_play:
-                    print(
-                        json.dumps(
-                            {"type": "audio_chunk", "audio_path": audio_path, "text": response}
-                        ),
-                        flush=True,
+                    self._emit_stream(
+                        {"type": "audio_chunk", "audio_path": audio_path, "text": response}
                     )
                     # Play audio immediately
                     await self._play_audio(audio_path)
@@ -635,7 +1245,7 @@ class SpeekiumDaemon:
                     self.assistant.is_generating_tts = False
                     return
 
-                print(json.dumps({"type": "done"}), flush=True)
+                self._emit_stream({"type": "done"})
                 return
 
             # Stream LLM + TTS generation
@@ -643,9 +1253,8 @@ class SpeekiumDaemon:
                 # Check for interrupt in streaming loop
                 if self.interrupt_event.is_set():
                     self._log("🚫 LLM streaming interrupted")
-                    print(
-                        json.dumps({"type": "interrupted", "reason": "LLM streaming interrupted"}),
-                        flush=True,
+                    self._emit_stream(
+                        {"type": "interrupted", "reason": "LLM streaming interrupted"}
                     )
                     # Clear TTS generation state to resume VAD
                     self.assistant.is_generating_tts = False
@@ -655,7 +1264,7 @@ class SpeekiumDaemon:
                     self._log(f"📤 Streaming output: {sentence[:30]}...")
 
                     # Send text chunk
-                    print(json.dumps({"type": "text_chunk", "content": sentence}), flush=True)
+                    self._emit_stream({"type": "text_chunk", "content": sentence})
 
                     # Check for interrupt before TTS generation
                     if self.interrupt_event.is_set():
@@ -664,18 +1273,11 @@ class SpeekiumDaemon:
 
                     # Generate TTS immediately
                     try:
-                        audio_path = await self.assistant.generate_audio(sentence)
+                        audio_path = await self.assistant.generate_audio(sentence, speech=speech)
                         if audio_path:
                             self._log(f"🔊 TTS completed: {audio_path}")
-                            print(
-                                json.dumps(
-                                    {
-                                        "type": "audio_chunk",
-                                        "audio_path": audio_path,
-                                        "text": sentence,
-                                    }
-                                ),
-                                flush=True,
+                            self._emit_stream(
+                                {"type": "audio_chunk", "audio_path": audio_path, "text": sentence}
                             )
                             # Play audio immediately if auto_play is enabled
                             if auto_play:
@@ -689,7 +1291,7 @@ class SpeekiumDaemon:
                         # TTS failure should not interrupt streaming chat
 
             # Send completion marker
-            print(json.dumps({"type": "done"}), flush=True)
+            self._emit_stream({"type": "done"})
             self._log("✅ Streaming chat+TTS completed")
 
             # Clear TTS generation state to resume VAD
@@ -698,7 +1300,7 @@ class SpeekiumDaemon:
         except Exception as e:
             self._log(f"❌ Streaming chat+TTS failed: {e}")
             traceback.print_exc(file=sys.stderr)
-            print(json.dumps({"type": "error", "error": str(e)}), flush=True)
+            self._emit_stream({"type": "error", "error": str(e)})
 
             # Clear TTS generation state to resume VAD
             self.assistant.is_generating_tts = False
@@ -713,6 +1315,7 @@ class SpeekiumDaemon:
         try:
             system = platform.system()
             self._log(f"🔊 Playing audio on {system}: {audio_path}")
+            self._emit_ptt_event("playback_started")
 
             if system == "Darwin":  # macOS
                 # Check if audio file exists
@@ -846,12 +1449,14 @@ class SpeekiumDaemon:
             self._log(f"⚠️ Audio playback failed: {e}")
             traceback.print_exc(file=sys.stderr)
 
-    async def handle_tts(self, text: str, language: Optional[str] = None) -> dict:
+    async def handle_tts(
+        self, text: str, language: Optional[str] = None, speech: Optional[dict] = None
+    ) -> dict:
         """Handle TTS generation command"""
         try:
             self._log(f"🔊 TTS 生成: {text[:50]}...")
 
-            audio_path = await self.assistant.generate_audio(text, language)
+            audio_path = await self.assistant.generate_audio(text, language, speech=speech)
 
             if audio_path:
                 self._log(f"✅ TTS 完成: {audio_path}")
@@ -864,6 +1469,55 @@ class SpeekiumDaemon:
             traceback.print_exc(file=sys.stderr)
             return {"success": False, "error": str(e)}
 
+    async def handle_play_audio(self, audio_path: str, clear_interrupt: bool = False) -> dict:
+        """Play one sentence of a reply generated by the Rust pipeline
+
+        clear_interrupt resets the interrupt flag before the first sentence of
+        a reply; an interrupt after that stops this and later sentences.
+        """
+        import os
+
+        if clear_interrupt:
+            self.interrupt_event.clear()
+        if self.interrupt_event.is_set():
+            return {"success": True, "interrupted": True}
+        if not os.path.exists(audio_path):
+            return {"success": False, "error": f"Audio file not found: {audio_path}"}
+
+        await self._play_audio(audio_path)
+        return {"success": True, "interrupted": self.interrupt_event.is_set()}
+
+    async def handle_list_tts_voices(self) -> dict:
+        """List the Edge TTS voices (falls back to the built-in per-language voices offline)"""
+        from speekium import EDGE_TTS_VOICES
+
+        try:
+            import edge_tts
+
+            voices = await edge_tts.list_voices()
+            return {
+                "success": True,
+                "voices": [
+                    {
+                        "id": v["ShortName"],
+                        "name": v.get("FriendlyName") or v["ShortName"],
+                        "locale": v.get("Locale", ""),
+                        "gender": v.get("Gender", ""),
+                    }
+                    for v in voices
+                ],
+            }
+        except Exception as e:
+            self._log(f"⚠️ Failed to list Edge TTS voices, using built-in list: {e}")
+            voices = sorted(set(EDGE_TTS_VOICES.values()))
+            return {
+                "success": True,
+                "voices": [
+                    {"id": v, "name": v, "locale": "-".join(v.split("-")[:2]), "gender": ""}
+                    for v in voices
+                ],
+            }
+
     async def handle_config(self) -> dict:
         """Handle get config command"""
         try:
@@ -892,6 +1546,35 @@ class SpeekiumDaemon:
             ConfigManager.save(merged_config)
             self._log("✅ 配置已保存到文件")
 
+            return await self.handle_reload_config()
+        except Exception as e:
+            self._log(f"❌ 配置保存失败: {e}")
+            return {"success": False, "error": str(e)}
+
+    async def handle_reload_config(
+        self,
+        api_keys: Optional[dict] = None,
+        offline: Optional[bool] = None,
+        config_dir: Optional[str] = None,
+    ) -> dict:
+        """Reload configuration after config.json was changed (the Rust side owns the file)
+
+        api_keys: provider API keys from the OS keychain (they are not in config.json)
+        offline: the app is offline; use local LLM/TTS models instead of remote ones
+        config_dir: directory of the active profile's config.json
+        """
+        try:
+            import os
+
+            from config_manager import ConfigManager
+
+            if config_dir and config_dir != os.path.dirname(ConfigManager.get_path()):
+                ConfigManager.set_config_dir(config_dir)
+            if api_keys is not None:
+                ConfigManager.set_api_keys(api_keys)
+            if offline is not None:
+                ConfigManager.set_offline(offline)
+
             # CRITICAL: Reload all configuration immediately
             # This ensures all changes take effect without needing to track what changed
             if self.assistant:
@@ -899,6 +1582,10 @@ class SpeekiumDaemon:
                 if hasattr(self.assistant, "_load_vad_config"):
                     self.assistant._load_vad_config()
 
+                # Reload ASR language settings
+                if hasattr(self.assistant, "_load_asr_config"):
+                    self.assistant._load_asr_config()
+
                 # Reload TTS settings
                 if hasattr(self.assistant, "_load_tts_config"):
                     self.assistant._load_tts_config()
@@ -913,16 +1600,28 @@ class SpeekiumDaemon:
 
                 # Log the change
                 backend_name = old_backend.__class__.__name__ if old_backend else "None"
-                self._log(f"✅ 所有配置已重新加载 (VAD, TTS, LLM)")
-                self._log(
-                    f"🔄 LLM backend 已重置: {backend_name} → 将在下次对话时使用新配置 ({llm_provider})"
-                )
+                self._log(f"✅ 所有配置已重新加载 (VAD, ASR, TTS, LLM)")
+                self._log(f"🔄 LLM backend 已重置: {backend_name} → 将在下次对话时使用新配置")
 
             return {"success": True}
         except Exception as e:
-            self._log(f"❌ 配置保存失败: {e}")
+            self._log(f"❌ 配置重新加载失败: {e}")
             return {"success": False, "error": str(e)}
 
+    async def handle_set_asr_language(self, language: str) -> dict:
+        """Set the ASR language ("auto" = detect) until the next config reload
+
+        Rust saves the same value to config.json, so it survives restarts.
+        """
+        from config_loader import ASR_LANGUAGES
+
+        if language != "auto" and language not in ASR_LANGUAGES:
+            return {"success": False, "error": f"Unsupported ASR language: {language}"}
+        if self.assistant:
+            self.assistant.asr_language = language
+        self._log(f"🌐 ASR language set to: {language}")
+        return {"success": True, "language": language}
+
     async def handle_update_hotkey(self, hotkey_config: dict) -> dict:
         """Update hotkey configuration
         Note: Actual hotkey registration is handled by Tauri/Rust side.
@@ -939,11 +1638,14 @@ class SpeekiumDaemon:
             self._log(f"❌ 热键更新失败: {e}")
             return {"success": False, "error": str(e)}
 
-    async def handle_health(self) -> dict:
-        """Health check"""
+    async def handle_health(self, quick: bool = False) -> dict:
+        """Health check
+
+        quick: skip the LLM backend check (periodic pings from the app's health monitor)
+        """
         # Check LLM backend health
         llm_health = None
-        if self.assistant and self.assistant.llm_backend:
+        if not quick and self.assistant and self.assistant.llm_backend:
             backend = self.assistant.llm_backend
             if hasattr(backend, "health_check"):
                 llm_health = backend.health_check()
@@ -1062,6 +1764,23 @@ class SpeekiumDaemon:
             traceback.print_exc(file=sys.stderr)
             return {"success": False, "error": str(e)}
 
+    @staticmethod
+    def _request_speech(persona: dict, speech: Optional[dict]) -> dict:
+        """Speech parameters of one request: its own values, then the persona's
+
+        Missing values use the configured ones when the audio is generated.
+        """
+        speech = speech or {}
+        # Speed multiplier -> Edge TTS rate ("+25%" for 1.25)
+        speed = persona.get("speed")
+        persona_rate = f"{round((speed - 1.0) * 100):+d}%" if speed and speed != 1.0 else None
+        return {
+            "voice": speech.get("voice") or persona.get("voice") or None,
+            "rate": speech.get("rate") or persona_rate,
+            "pitch": speech.get("pitch"),
+            "volume": speech.get("volume"),
+        }
+
     async def handle_command(self, command: str, args: dict) -> dict:
         """Route commands to corresponding handler functions
 
@@ -1069,6 +1788,17 @@ class SpeekiumDaemon:
         """
         self.command_count += 1
 
+        # The persona is sent by Rust with every chat/TTS command, so switching
+        # personas takes effect on the next request. It and the speech
+        # parameters are passed to the handler, never stored: requests run
+        # concurrently, and a voice preview must not change a reply being spoken.
+        persona = (args.pop("persona", None) or {}) if command in PERSONA_COMMANDS else {}
+        system_prompt = persona.get("system_prompt") or None
+        # Edge TTS strings ("+25%", "+5Hz")
+        speech = self._request_speech(
+            persona, args.pop("speech", None) if command in SPEECH_COMMANDS else None
+        )
+
         if command == "record":
             return await self.handle_record(**args)
         elif command == "record_start":
@@ -1112,28 +1842,82 @@ class SpeekiumDaemon:
                 duration=args.get("duration", 0),
                 auto_chat=auto_chat,
                 use_tts=True,
+                emit_result=args.get("emit_result", True),
+                language=args.get("language"),
+                hotwords=args.get("hotwords"),
+            )
+        elif command == "ptt_audio_chunk":
+            # Chunk of a PTT recording still in progress (Rust streams ~1s at a time)
+            return await self.handle_ptt_audio_chunk(
+                stream_id=args.get("stream_id", ""),
+                seq=args.get("seq", 0),
+                audio_path=args.get("audio_path", ""),
+                language=args.get("language"),
+                hotwords=args.get("hotwords"),
+            )
+        elif command == "ptt_audio_end":
+            # Key released: transcribe the rest of a streamed recording
+            from config_manager import ConfigManager
+
+            config = ConfigManager.load()
+            auto_chat = config.get("work_mode", "conversation") == "conversation"
+            return await self.handle_ptt_audio_end(
+                stream_id=args.get("stream_id", ""),
+                audio_path=args.get("audio_path", ""),
+                auto_chat=auto_chat,
+                use_tts=True,
+                emit_result=args.get("emit_result", True),
+                language=args.get("language"),
+                hotwords=args.get("hotwords"),
+            )
+        elif command == "ptt_chat":
+            # Post-processed PTT transcript from Rust
+            return await self.handle_ptt_chat(
+                args.get("text", ""),
+                args.get("auto_chat", True),
+                args.get("use_tts", True),
+                system_prompt,
+                speech,
+            )
+        elif command == "transcribe":
+            return await self.handle_transcribe(
+                args.get("audio_path", ""), args.get("language"), args.get("hotwords")
             )
         elif command == "chat":
-            return await self.handle_chat(args.get("text", ""))
+            return await self.handle_chat(args.get("text", ""), args.get("history"), system_prompt)
+        elif command == "generate_title":
+            return await self.handle_generate_title(args.get("messages", []))
         elif command == "chat_stream":
             # Streaming command: output directly to stdout, do not return dict
-            await self.handle_chat_stream(args.get("text", ""))
+            await self.handle_chat_stream(args.get("text", ""), system_prompt)
             return None  # Indicates processed but no return value
         elif command == "chat_tts_stream":
             # Streaming chat + TTS: output directly to stdout, do not return dict
-            await self.handle_chat_tts_stream(args.get("text", ""), args.get("auto_play", True))
+            await self.handle_chat_tts_stream(
+                args.get("text", ""), args.get("auto_play", True), system_prompt, speech
+            )
             return None
         elif command == "tts":
-            return await self.handle_tts(args.get("text", ""), args.get("language"))
+            return await self.handle_tts(args.get("text", ""), args.get("language"), speech)
+        elif command == "play_audio":
+            return await self.handle_play_audio(
+                args.get("audio_path", ""), args.get("clear_interrupt", False)
+            )
+        elif command == "list_tts_voices":
+            return await self.handle_list_tts_voices()
         elif command == "config":
             return await self.handle_config()
         elif command == "save_config":
             # args is directly the config object (Rust side has processed it)
             return await self.handle_save_config(args)
+        elif command == "reload_config":
+            return await self.handle_reload_config(
+                args.get("api_keys"), args.get("offline"), args.get("config_dir")
+            )
         elif command == "update_hotkey":
             return await self.handle_update_hotkey(args)
         elif command == "health":
-            return await self.handle_health()
+            return await self.handle_health(args.get("quick", False))
         elif command == "model_status":
             return await self.handle_model_status()
         elif command == "interrupt":
@@ -1160,6 +1944,8 @@ class SpeekiumDaemon:
                 self._log(f"⚠️ Failed to save recording_mode: {e}")
 
             return {"success": True, "mode": mode}
+        elif command == "set_asr_language":
+            return await self.handle_set_asr_language(args.get("language", "auto"))
         elif command == "exit":
             self._log("👋 收到退出命令")
             self._cleanup()
@@ -1254,6 +2040,23 @@ class SpeekiumDaemon:
 
         logger.info("health_monitor_stopped")
 
+    async def _run_request(self, request_id, command: str, args: dict):
+        """Handle one request and send its response frame"""
+        # Tag stream frames emitted by this task with the request id
+        _current_request_id.set(request_id)
+        try:
+            result = await self.handle_command(command, args)
+
+            # Output result as a response frame
+            # Note: streaming commands (chat_stream) return None because they emit stream frames
+            if result is not None:
+                send_frame("response", result, request_id)
+        except Exception as e:
+            self._log(f"❌ 命令执行错误: {e}")
+            traceback.print_exc(file=sys.stderr)
+            error_result = {"success": False, "error": f"Internal error: {str(e)}"}
+            send_frame("response", error_result, request_id)
+
     async def run_daemon(self):
         """Daemon main loop"""
         # Initialize
@@ -1269,56 +2072,48 @@ class SpeekiumDaemon:
             self.health_monitor_task = asyncio.create_task(self._health_monitor_loop())
             logger.info("health_monitor_task_started")
 
-        # Send ready signal to stdout (Rust expects JSON with "event" field)
-        print(
-            json.dumps({"event": "daemon_success", "message": "就绪，守护进程已准备好接受命令"}),
-            flush=True,
-        )
+        # Send ready signal (Rust expects a log frame with "event" field)
+        send_frame("log", {"event": "daemon_success", "message": "就绪，守护进程已准备好接受命令"})
 
-        # Main loop: listen for stdin commands
+        # Main loop: listen for stdin request frames
         loop = asyncio.get_event_loop()
 
         while self.running:
             try:
-                # Read one line from stdin (blocking operation, must run in executor)
-                line = await loop.run_in_executor(None, sys.stdin.readline)
+                # Read one frame from stdin (blocking operation, must run in executor)
+                try:
+                    request = await loop.run_in_executor(None, read_frame)
+                except ValueError as e:
+                    self._log(f"⚠️ 帧解析错误: {e}")
+                    send_frame("response", {"success": False, "error": f"Invalid frame: {e}"})
+                    continue
 
-                if not line:
+                if request is None:
                     # stdin closed, wait and retry (don't exit - might be reopened)
                     self._log("📪 stdin 关闭，等待重新连接...")
                     await asyncio.sleep(1)
                     continue
 
-                line = line.strip()
-                if not line:
-                    continue
-
-                # Parse JSON command
-                try:
-                    request = json.loads(line)
-                    command = request.get("command")
-                    args = request.get("args", {})
-
-                    self._log(f"📥 收到命令: {command}")
-
-                    # Handle command
-                    result = await self.handle_command(command, args)
+                request_id = request.get("id")
+                payload = request.get("payload") or {}
+                command = payload.get("command")
+                args = payload.get("args", {})
 
-                    # Output result to stdout
-                    # Note: streaming commands (chat_stream) return None because they already output directly
-                    if result is not None:
-                        print(json.dumps(result), flush=True)
+                self._log(f"📥 收到命令: {command}")
 
-                except json.JSONDecodeError as e:
-                    self._log(f"⚠️ JSON 解析错误: {e}")
-                    error_result = {"success": False, "error": f"Invalid JSON: {str(e)}"}
-                    print(json.dumps(error_result), flush=True)
+                if command == "exit":
+                    # Handle inline so the loop stops right after
+                    await self._run_request(request_id, command, args)
+                else:
+                    # Run concurrently so quick commands (health, config) are answered
+                    # while a long stream is in flight
+                    task = asyncio.create_task(self._run_request(request_id, command, args))
+                    self._pending_tasks.add(task)
+                    task.add_done_callback(self._pending_tasks.discard)
 
             except Exception as e:
                 self._log(f"❌ 主循环错误: {e}")
                 traceback.print_exc(file=sys.stderr)
-                error_result = {"success": False, "error": f"Internal error: {str(e)}"}
-                print(json.dumps(error_result), flush=True)
 
         # Clean up resources before exit
         self._cleanup()
@@ -1329,6 +2124,9 @@ def main():
     """Main entry point"""
     # Check if running in daemon mode
     if len(sys.argv) > 1 and sys.argv[1] == "daemon":
+        enable_framed_ipc()
+        # Handshake first, so the app can reject a mismatched daemon before models load
+        send_frame("log", {"event": "daemon_hello", "protocol_version": PROTOCOL_VERSION})
         daemon = SpeekiumDaemon()
         asyncio.run(daemon.run_daemon())
     else: